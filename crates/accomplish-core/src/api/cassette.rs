@@ -0,0 +1,254 @@
+use crate::diagnostics;
+use crate::errors::AppError;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Records every API response to this file.
+pub const RECORD_ENV: &str = "ACCOMPLISH_RECORD";
+/// Answers API requests from this file instead of the network.
+pub const REPLAY_ENV: &str = "ACCOMPLISH_REPLAY";
+
+/// Response fields holding credentials, which are blanked before recording.
+const SECRET_FIELDS: [&str; 4] = ["access_token", "refresh_token", "id_token", "device_code"];
+
+/// One request and the response it got. Headers, and with them the access
+/// token, are never stored, and neither are tokens in response bodies.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Interaction {
+    pub method: String,
+    /// Path relative to the API base, with the query string if any
+    pub endpoint: String,
+    pub status: u16,
+    /// The body when it's JSON, kept as-is so cassettes are easy to read and edit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub json: Option<Value>,
+    /// The body when it isn't JSON
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+}
+
+impl Interaction {
+    pub fn new(method: &str, endpoint: &str, status: u16, body: &str) -> Self {
+        let json = serde_json::from_str(body).ok().map(|mut json| {
+            redact(&mut json);
+            json
+        });
+        Self {
+            method: method.to_string(),
+            endpoint: endpoint.to_string(),
+            status,
+            text: json.is_none().then(|| body.to_string()),
+            json,
+        }
+    }
+
+    pub fn body(&self) -> String {
+        match (&self.json, &self.text) {
+            (Some(json), _) => json.to_string(),
+            (None, Some(text)) => text.clone(),
+            (None, None) => String::new(),
+        }
+    }
+
+    fn path(&self) -> &str {
+        path_of(&self.endpoint)
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CassetteFile {
+    interactions: Vec<Interaction>,
+}
+
+#[derive(Debug)]
+enum Mode {
+    Record,
+    Replay { played: Mutex<Vec<bool>> },
+}
+
+/// Record/replay of API traffic, so interactive flows such as `acc capture`
+/// and `acc init` can be tested and demoed offline with the same responses
+/// every time:
+///
+/// ```text
+/// ACCOMPLISH_RECORD=capture.json acc capture    # talks to the API and saves the responses
+/// ACCOMPLISH_REPLAY=capture.json acc capture    # replays them, no network needed
+/// ```
+#[derive(Debug)]
+pub struct Cassette {
+    path: PathBuf,
+    mode: Mode,
+    interactions: Mutex<Vec<Interaction>>,
+}
+
+impl Cassette {
+    /// Starts an empty recording, written to `path` as responses come in.
+    pub fn record(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            mode: Mode::Record,
+            interactions: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Loads a recording to answer requests from.
+    pub fn replay(path: impl Into<PathBuf>) -> Result<Self, AppError> {
+        let path = path.into();
+        let content = fs::read_to_string(&path)
+            .map_err(|e| AppError::Other(format!("Can't read cassette {}: {e}", path.display())))?;
+        let file: CassetteFile = serde_json::from_str(&content)?;
+        Ok(Self {
+            mode: Mode::Replay {
+                played: Mutex::new(vec![false; file.interactions.len()]),
+            },
+            path,
+            interactions: Mutex::new(file.interactions),
+        })
+    }
+
+    /// The cassette selected by `ACCOMPLISH_RECORD` or `ACCOMPLISH_REPLAY`, if any.
+    pub fn from_env() -> Result<Option<Self>, AppError> {
+        let var = |name: &str| env::var_os(name).filter(|v| !v.is_empty());
+        match (var(RECORD_ENV), var(REPLAY_ENV)) {
+            (Some(_), Some(_)) => Err(AppError::Other(format!(
+                "Set only one of {RECORD_ENV} and {REPLAY_ENV}"
+            ))),
+            (Some(path), None) => Ok(Some(Self::record(path))),
+            (None, Some(path)) => Self::replay(path).map(Some),
+            (None, None) => Ok(None),
+        }
+    }
+
+    pub fn is_replaying(&self) -> bool {
+        matches!(self.mode, Mode::Replay { .. })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Saves a response while recording. Failing to write only warns, so the
+    /// command itself still goes through.
+    pub fn record_interaction(&self, interaction: Interaction) {
+        if self.is_replaying() {
+            return;
+        }
+        let mut interactions = self.interactions.lock().unwrap();
+        interactions.push(interaction);
+        let file = CassetteFile {
+            interactions: interactions.clone(),
+        };
+        let written = serde_json::to_string_pretty(&file)
+            .map_err(AppError::from)
+            .and_then(|json| fs::write(&self.path, json).map_err(AppError::from));
+        if let Err(e) = written {
+            diagnostics::warn(&format!(
+                "Could not write cassette {}: {e}",
+                self.path.display()
+            ));
+        }
+    }
+
+    /// The recorded response to a request while replaying. Requests are
+    /// answered in recorded order, preferring an exact match and otherwise
+    /// ignoring the query string, which often holds today's date.
+    pub fn playback(&self, method: &str, endpoint: &str) -> Option<Interaction> {
+        let Mode::Replay { played } = &self.mode else {
+            return None;
+        };
+        let interactions = self.interactions.lock().unwrap();
+        let mut played = played.lock().unwrap();
+        let unplayed = |matches: &dyn Fn(&Interaction) -> bool| {
+            interactions.iter().enumerate().position(|(i, recorded)| {
+                !played[i] && recorded.method == method && matches(recorded)
+            })
+        };
+        let index = unplayed(&|recorded| recorded.endpoint == endpoint)
+            .or_else(|| unplayed(&|recorded| recorded.path() == path_of(endpoint)))?;
+        played[index] = true;
+        Some(interactions[index].clone())
+    }
+}
+
+fn redact(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, field) in map.iter_mut() {
+                if SECRET_FIELDS.contains(&key.as_str()) && field.is_string() {
+                    *field = Value::String("[redacted]".to_string());
+                } else {
+                    redact(field);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact),
+        _ => {}
+    }
+}
+
+fn path_of(endpoint: &str) -> &str {
+    endpoint.split('?').next().unwrap_or(endpoint)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_recorded_interactions_replay_in_order() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("cassette.json");
+
+        let recording = Cassette::record(&path);
+        recording.record_interaction(Interaction::new(
+            "GET",
+            "api/v1/worklog/entries?from=2025-07-09",
+            200,
+            r#"{"entries":[{"id":"a"}]}"#,
+        ));
+        recording.record_interaction(Interaction::new(
+            "GET",
+            "api/v1/worklog/entries?from=2025-07-09",
+            200,
+            r#"{"entries":[]}"#,
+        ));
+        recording.record_interaction(Interaction::new("HEAD", "", 404, "not found"));
+        recording.record_interaction(Interaction::new(
+            "POST",
+            "auth/token",
+            200,
+            r#"{"access_token":"secret","expires_in":3600}"#,
+        ));
+
+        let replay = Cassette::replay(&path).unwrap();
+        assert!(replay.is_replaying());
+
+        // A different date still finds the entries request
+        let first = replay
+            .playback("GET", "api/v1/worklog/entries?from=2025-07-10")
+            .unwrap();
+        assert_eq!(first.json, Some(json!({ "entries": [{ "id": "a" }] })));
+        let second = replay
+            .playback("GET", "api/v1/worklog/entries?from=2025-07-10")
+            .unwrap();
+        assert_eq!(second.body(), r#"{"entries":[]}"#);
+        assert!(replay
+            .playback("GET", "api/v1/worklog/entries?from=2025-07-10")
+            .is_none());
+
+        let head = replay.playback("HEAD", "").unwrap();
+        assert_eq!((head.status, head.body().as_str()), (404, "not found"));
+
+        let token = replay.playback("POST", "auth/token").unwrap();
+        assert_eq!(
+            token.json,
+            Some(json!({ "access_token": "[redacted]", "expires_in": 3600 }))
+        );
+    }
+}
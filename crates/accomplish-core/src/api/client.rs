@@ -0,0 +1,928 @@
+use crate::api::cassette::{Cassette, Interaction};
+use crate::api::clock::ServerClock;
+use crate::api::errors::ApiError;
+use crate::api::limiter::RequestLimiter;
+use crate::api::models::SseEvent;
+use crate::api::retry::{is_retryable_status, parse_retry_after, RetryPolicy, MAX_RATE_LIMIT_WAIT};
+use crate::api::schema;
+use crate::api::sse::{SseDecoder, SseMessage};
+use crate::api::versioning::{accept_event_stream, accept_json, warn_if_deprecated};
+use crate::diagnostics;
+use crate::user_agent::generate_user_agent;
+use chrono::{DateTime, Utc};
+use futures::stream::{Stream, StreamExt};
+use reqwest::header::{HeaderMap, HeaderName, ACCEPT, DATE, RETRY_AFTER};
+use reqwest::{Client, Method, RequestBuilder};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// How long the connectivity check waits before calling the API unreachable.
+const PREFLIGHT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Tells an event stream which event the client saw last, to resume after it.
+const LAST_EVENT_ID: HeaderName = HeaderName::from_static("last-event-id");
+
+pub struct ApiClient {
+    base_url: String,
+    access_token: Option<String>,
+    client: Client,
+    clock: ServerClock,
+    limiter: RequestLimiter,
+    retry: Mutex<RetryPolicy>,
+    ignore_schema_errors: AtomicBool,
+    cassette: Option<Cassette>,
+}
+
+/// A response as read off the wire, or replayed from a cassette.
+struct Reply {
+    status: u16,
+    body: String,
+    /// From `Retry-After`, kept for rate-limited responses
+    retry_after: Option<Duration>,
+}
+
+impl ApiClient {
+    pub fn new(base_url: &str) -> Self {
+        // Every request negotiates the API version through `Accept`
+        let mut headers = HeaderMap::new();
+        headers.insert(ACCEPT, accept_json());
+
+        let client = Client::builder()
+            .user_agent(generate_user_agent())
+            .default_headers(headers)
+            .build()
+            .expect("Failed to create HTTP client");
+
+        Self {
+            base_url: base_url.to_string(),
+            access_token: None,
+            client,
+            clock: ServerClock::default(),
+            limiter: RequestLimiter::default(),
+            retry: Mutex::new(RetryPolicy::default()),
+            ignore_schema_errors: AtomicBool::new(false),
+            cassette: None,
+        }
+    }
+
+    /// Local view of the server clock, kept in sync by every response.
+    pub fn clock(&self) -> &ServerClock {
+        &self.clock
+    }
+
+    /// Limit on requests in flight at once, shared by everything using this client.
+    pub fn limiter(&self) -> &RequestLimiter {
+        &self.limiter
+    }
+
+    /// How transient failures are retried, from `max_retries` and `--no-retry`.
+    pub fn set_retry_policy(&self, policy: RetryPolicy) {
+        if let Ok(mut retry) = self.retry.lock() {
+            *retry = policy;
+        }
+    }
+
+    fn retry_policy(&self) -> RetryPolicy {
+        self.retry.lock().map(|retry| *retry).unwrap_or_default()
+    }
+
+    /// Whether responses that don't match the expected shape are decoded as
+    /// far as possible instead of failing (`--ignore-schema-errors`).
+    pub fn set_ignore_schema_errors(&self, ignore: bool) {
+        self.ignore_schema_errors.store(ignore, Ordering::Relaxed);
+    }
+
+    /// Records responses to, or replays them from, a cassette (see `Cassette`).
+    pub fn set_cassette(&mut self, cassette: Cassette) {
+        self.cassette = Some(cassette);
+    }
+
+    pub fn is_replaying(&self) -> bool {
+        self.cassette.as_ref().is_some_and(Cassette::is_replaying)
+    }
+
+    /// Sends a request and reads the whole response, going through the
+    /// cassette when one is set. `endpoint` is only used to label the request.
+    ///
+    /// Failures to connect are retried with jittered exponential backoff, as
+    /// the retry policy allows. So are server errors, timeouts and dropped
+    /// connections for idempotent methods only: a POST the server may have
+    /// acted on isn't sent again. Rate-limited GETs are retried too, after the
+    /// `Retry-After` wait if it's short enough. Each attempt waits for its own
+    /// slot in the request limit, so backing off doesn't hold one.
+    async fn send(
+        &self,
+        method: &str,
+        endpoint: &str,
+        request: RequestBuilder,
+    ) -> Result<Reply, ApiError> {
+        if let Some(cassette) = self.cassette.as_ref().filter(|c| c.is_replaying()) {
+            let recorded = cassette.playback(method, endpoint).ok_or_else(|| {
+                ApiError::Unexpected(format!(
+                    "{method} {endpoint}: no recorded response in {}",
+                    cassette.path().display()
+                ))
+            })?;
+            return Ok(Reply {
+                status: recorded.status,
+                body: recorded.body(),
+                retry_after: None,
+            });
+        }
+
+        let policy = self.retry_policy();
+        let mut request = Some(request);
+        let mut retry = 0;
+        let (resp, _permit) = loop {
+            // Requests whose body can't be cloned are only ever sent once
+            let copy = request
+                .as_ref()
+                .filter(|_| retry < policy.max_retries)
+                .and_then(RequestBuilder::try_clone);
+            let last = copy.is_none();
+            let attempt = match copy {
+                Some(copy) => copy,
+                None => request.take().expect("the last attempt ends the loop"),
+            };
+
+            let permit = self.limiter.acquire().await;
+            let wait = match attempt.send().await {
+                // Only GETs are safe to repeat after the server turned them away
+                Ok(resp) if resp.status().as_u16() == 429 && method == "GET" && !last => {
+                    match retry_after(resp.headers()) {
+                        Some(wait) if wait > MAX_RATE_LIMIT_WAIT => break (resp, permit),
+                        Some(wait) => {
+                            diagnostics::warn(&format!(
+                                "Rate limited by the API. Retrying in {}s",
+                                wait.as_secs_f64().ceil()
+                            ));
+                            wait
+                        }
+                        None => policy.delay(retry),
+                    }
+                }
+                Ok(resp)
+                    if last
+                        || !is_idempotent(method)
+                        || !is_retryable_status(resp.status().as_u16()) =>
+                {
+                    break (resp, permit)
+                }
+                Err(e) if last || !is_transient(&e, method) => {
+                    return Err(transport_error(e));
+                }
+                _ => policy.delay(retry),
+            };
+            drop(permit);
+            tokio::time::sleep(wait).await;
+            retry += 1;
+        };
+        self.inspect_response(resp.headers());
+
+        let status = resp.status().as_u16();
+        let retry_after = retry_after(resp.headers());
+        let body = match resp.text().await {
+            Ok(body) => body,
+            Err(e) if (200..300).contains(&status) => {
+                return Err(ApiError::DecodeError(e.to_string()))
+            }
+            Err(_) => String::new(),
+        };
+        if let Some(cassette) = &self.cassette {
+            cassette.record_interaction(Interaction::new(method, endpoint, status, &body));
+        }
+        Ok(Reply {
+            status,
+            body,
+            retry_after,
+        })
+    }
+
+    /// Decodes a successful response, reporting the field that didn't match.
+    fn decode<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        endpoint: &str,
+        reply: Reply,
+    ) -> Result<T, ApiError> {
+        if !(200..300).contains(&reply.status) {
+            return Err(error_from_reply(reply));
+        }
+        schema::decode(
+            &format!("{method} {endpoint}"),
+            &reply.body,
+            self.ignore_schema_errors.load(Ordering::Relaxed),
+        )
+    }
+
+    /// Decodes JSON already taken out of a response, such as the payload of
+    /// an envelope, reporting mismatches the same way as whole responses.
+    pub fn decode_value<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        endpoint: &str,
+        value: Value,
+    ) -> Result<T, ApiError> {
+        schema::decode(
+            &format!("{method} {endpoint}"),
+            &value.to_string(),
+            self.ignore_schema_errors.load(Ordering::Relaxed),
+        )
+    }
+
+    /// Reads the version and clock metadata every response carries.
+    fn inspect_response(&self, headers: &HeaderMap) {
+        warn_if_deprecated(headers);
+        self.clock.observe(headers);
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Quick connectivity check run before long interactive flows, so being
+    /// offline is reported up front rather than at the final request. Any
+    /// HTTP response counts as reachable.
+    pub async fn check_reachable(&self) -> Result<(), ApiError> {
+        if self.is_replaying() {
+            return Ok(());
+        }
+        self.client
+            .head(&self.base_url)
+            .timeout(PREFLIGHT_TIMEOUT)
+            .send()
+            .await
+            .map(|_| ())
+            .map_err(|e| ApiError::Unreachable(e.to_string()))
+    }
+
+    // pub fn new_with_token(base_url: String, access_token: Option<String>) -> Self {
+    //     Self { base_url, access_token }
+    // }
+
+    pub fn set_access_token(&mut self, token: String) {
+        self.access_token = Some(token);
+    }
+
+    // pub fn clear_access_token(&mut self) {
+    //     self.access_token = None;
+    // }
+
+    pub async fn get<T>(&self, endpoint: &str, use_auth: bool) -> Result<T, ApiError>
+    where
+        T: DeserializeOwned,
+    {
+        let full_url = format!("{}/{}", self.base_url, endpoint);
+
+        let mut request = self.client.get(&full_url);
+
+        if use_auth {
+            if let Some(token) = &self.access_token {
+                request = request.bearer_auth(token);
+            } else {
+                return Err(ApiError::Unauthorized(
+                    "Authorization required but no token is set.".into(),
+                ));
+            }
+        }
+
+        let reply = self.send("GET", endpoint, request).await?;
+        self.decode("GET", endpoint, reply)
+    }
+
+    pub async fn post<T>(
+        &self,
+        endpoint: &str,
+        body: serde_json::Value,
+        use_auth: bool,
+    ) -> Result<T, ApiError>
+    where
+        T: DeserializeOwned,
+    {
+        let full_url = format!("{}/{}", self.base_url, endpoint);
+
+        let mut request = self.client.post(&full_url).json(&body);
+
+        if use_auth {
+            if let Some(token) = &self.access_token {
+                request = request.bearer_auth(token);
+            } else {
+                return Err(ApiError::Unauthorized(
+                    "Authorization required but no token is set.".into(),
+                ));
+            }
+        }
+
+        let reply = self.send("POST", endpoint, request).await?;
+        self.decode("POST", endpoint, reply)
+    }
+
+    /// Sends an arbitrary authenticated request and returns the decoded JSON body, or
+    /// `Value::Null` when the response has none. Backs `acc api`.
+    pub async fn request(
+        &self,
+        method: Method,
+        endpoint: &str,
+        query: &[(String, String)],
+        body: Option<Value>,
+    ) -> Result<Value, ApiError> {
+        let full_url = format!("{}/{}", self.base_url, endpoint);
+        let label = method.to_string();
+        let recorded_endpoint = if query.is_empty() {
+            endpoint.to_string()
+        } else {
+            let query = url::form_urlencoded::Serializer::new(String::new())
+                .extend_pairs(query)
+                .finish();
+            format!("{endpoint}?{query}")
+        };
+
+        let mut request = self.client.request(method, &full_url);
+        if !query.is_empty() {
+            request = request.query(query);
+        }
+        if let Some(body) = body {
+            request = request.json(&body);
+        }
+
+        if let Some(token) = &self.access_token {
+            request = request.bearer_auth(token);
+        } else {
+            return Err(ApiError::Unauthorized(
+                "Authorization required but no token is set.".into(),
+            ));
+        }
+
+        let reply = self.send(&label, &recorded_endpoint, request).await?;
+        if !(200..300).contains(&reply.status) {
+            return Err(error_from_reply(reply));
+        }
+        if reply.body.trim().is_empty() {
+            return Ok(Value::Null);
+        }
+        serde_json::from_str(&reply.body).map_err(|e| ApiError::DecodeError(e.to_string()))
+    }
+
+    /// Stream Server-Sent Events from an endpoint. Streams are long-lived, so
+    /// they only count against the request limit while connecting.
+    ///
+    /// A stream that drops before a recap finished (a dropped connection, or
+    /// the server hanging up mid-generation) is picked up again with the ID of
+    /// the last event seen as `Last-Event-ID`, so the server can resume after
+    /// it. Reconnecting follows the retry policy: up to `max_retries` attempts
+    /// in a row, with jittered backoff but never sooner than the server's
+    /// `retry:` delay. Only when those run out does the stream end.
+    pub async fn stream_sse(
+        &self,
+        endpoint: &str,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<SseEvent, ApiError>> + Send + '_>>, ApiError> {
+        if self.is_replaying() {
+            return Err(ApiError::Unexpected(format!(
+                "GET {endpoint}: live streams can't be replayed from a cassette"
+            )));
+        }
+        let response = self.connect_sse(endpoint, None).await?;
+
+        let stream = futures::stream::unfold(
+            Some(SseConnection {
+                endpoint: endpoint.to_string(),
+                response,
+                decoder: SseDecoder::new(),
+                reconnects: 0,
+                resumed_from: None,
+                finished: false,
+            }),
+            move |state| async move {
+                let mut conn = state?;
+                // What the stream ends with if it can't be picked up again
+                let error = match conn.response.chunk().await {
+                    Ok(Some(chunk)) => {
+                        let events = conn.feed(&chunk);
+                        return Some((events, Some(conn)));
+                    }
+                    Ok(None) => {
+                        let events = conn.finish();
+                        if conn.finished {
+                            return Some((events, None));
+                        }
+                        // Hand out what arrived, then pick the stream up again
+                        if !events.is_empty() {
+                            return Some((events, Some(conn)));
+                        }
+                        None
+                    }
+                    Err(e) => Some(ApiError::Unexpected(format!("Stream error: {e}"))),
+                };
+
+                match self.reconnect_sse(&mut conn).await {
+                    Ok(()) => Some((Vec::new(), Some(conn))),
+                    Err(_) => error.map(|e| (vec![Err(e)], None)),
+                }
+            },
+        )
+        .flat_map(futures::stream::iter);
+
+        Ok(Box::pin(stream))
+    }
+
+    /// Opens an event stream, resuming after `last_event_id` when given.
+    async fn connect_sse(
+        &self,
+        endpoint: &str,
+        last_event_id: Option<&str>,
+    ) -> Result<reqwest::Response, ApiError> {
+        let full_url = format!("{}/{}", self.base_url, endpoint);
+
+        let mut request = self
+            .client
+            .get(&full_url)
+            .header(ACCEPT, accept_event_stream());
+        if let Some(id) = last_event_id {
+            request = request.header(LAST_EVENT_ID, id);
+        }
+
+        if let Some(token) = &self.access_token {
+            request = request.bearer_auth(token);
+        } else {
+            return Err(ApiError::Unauthorized(
+                "Authorization required but no token is set.".into(),
+            ));
+        }
+
+        let permit = self.limiter.acquire().await;
+        let response = request.send().await.map_err(transport_error)?;
+        drop(permit);
+        self.inspect_response(response.headers());
+
+        // Check if we got an error response instead of SSE stream
+        if !response.status().is_success() {
+            return match response.status().as_u16() {
+                404 => {
+                    let error_msg = response
+                        .text()
+                        .await
+                        .unwrap_or_else(|_| "Stream not found".to_string());
+                    Err(ApiError::NotFound(error_msg))
+                }
+                _ => {
+                    let error_msg = response
+                        .text()
+                        .await
+                        .unwrap_or_else(|_| "SSE connection failed".to_string());
+                    Err(ApiError::Unexpected(error_msg))
+                }
+            };
+        }
+        Ok(response)
+    }
+
+    /// Replaces a dropped stream's connection, backing off between attempts.
+    /// Gives up once the retry policy runs out, or when the stream is gone.
+    async fn reconnect_sse(&self, conn: &mut SseConnection) -> Result<(), ApiError> {
+        let policy = self.retry_policy();
+        loop {
+            if conn.reconnects >= policy.max_retries {
+                return Err(ApiError::Unexpected(
+                    "Gave up reconnecting to the stream".into(),
+                ));
+            }
+            let wait = policy
+                .delay(conn.reconnects)
+                .max(conn.decoder.retry().unwrap_or_default());
+            conn.reconnects += 1;
+            diagnostics::warn(&format!(
+                "Lost the recap stream. Reconnecting ({}/{})",
+                conn.reconnects, policy.max_retries
+            ));
+            tokio::time::sleep(wait).await;
+
+            match self
+                .connect_sse(&conn.endpoint, conn.decoder.last_event_id())
+                .await
+            {
+                Ok(response) => {
+                    conn.response = response;
+                    conn.resumed_from = conn.decoder.last_event_id().map(str::to_string);
+                    return Ok(());
+                }
+                // The recap finished, or access was lost: nothing to resume
+                Err(e @ (ApiError::NotFound(_) | ApiError::Unauthorized(_))) => return Err(e),
+                Err(_) => {}
+            }
+        }
+    }
+}
+
+/// An open event stream and what's needed to resume it after a drop.
+struct SseConnection {
+    endpoint: String,
+    response: reqwest::Response,
+    decoder: SseDecoder,
+    /// Reconnection attempts since the stream last moved past an event
+    reconnects: u32,
+    /// The `Last-Event-ID` of the latest reconnection
+    resumed_from: Option<String>,
+    /// A final event (completed, failed or an error) was handed out
+    finished: bool,
+}
+
+impl SseConnection {
+    fn feed(&mut self, chunk: &[u8]) -> Vec<Result<SseEvent, ApiError>> {
+        let messages = self.decoder.feed(chunk);
+        self.read(messages)
+    }
+
+    fn finish(&mut self) -> Vec<Result<SseEvent, ApiError>> {
+        let message = self.decoder.finish();
+        self.read(message)
+    }
+
+    /// Reads decoded events, noting whether the recap is done with.
+    fn read(
+        &mut self,
+        messages: impl IntoIterator<Item = SseMessage>,
+    ) -> Vec<Result<SseEvent, ApiError>> {
+        let events = parse_sse_events(messages);
+        // Only new events count as progress, not ones a server replays
+        // before hanging up again
+        if !events.is_empty() && self.decoder.last_event_id() != self.resumed_from.as_deref() {
+            self.reconnects = 0;
+        }
+        self.finished |= events
+            .iter()
+            .any(|event| !matches!(event, Ok(event) if event.status == "processing"));
+        events
+    }
+}
+
+/// Methods the server handles the same however many times they're sent, so
+/// they can be repeated after an answer that doesn't say whether they worked.
+fn is_idempotent(method: &str) -> bool {
+    matches!(method, "GET" | "HEAD" | "PUT" | "DELETE")
+}
+
+/// Failures to get any response that another attempt might not hit. Failing
+/// to connect means the request never reached the server, so any method can
+/// be sent again. After a timeout or a connection dropped mid-request it may
+/// have been handled, so only idempotent methods are.
+fn is_transient(e: &reqwest::Error, method: &str) -> bool {
+    e.is_connect() || (is_idempotent(method) && (e.is_timeout() || e.is_request()))
+}
+
+/// Maps a failure to get any response to an `ApiError`. Only failing to
+/// connect means the request never reached the server; after a timeout it
+/// may have been handled.
+fn transport_error(e: reqwest::Error) -> ApiError {
+    if e.is_connect() {
+        ApiError::Unreachable(e.to_string())
+    } else if e.is_timeout() {
+        ApiError::TimedOut(e.to_string())
+    } else {
+        ApiError::Unexpected(e.to_string())
+    }
+}
+
+/// How long a response asks to wait before trying again, judged by the
+/// server's clock when it sent a `Date`.
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?;
+    let now = headers
+        .get(DATE)
+        .and_then(|date| date.to_str().ok())
+        .and_then(|date| DateTime::parse_from_rfc2822(date.trim()).ok())
+        .map(|date| date.with_timezone(&Utc))
+        .unwrap_or_else(Utc::now);
+    parse_retry_after(value, now)
+}
+
+/// Maps an unsuccessful response to an `ApiError`, keeping the body as the message.
+fn error_from_reply(reply: Reply) -> ApiError {
+    if reply.status == 429 {
+        return ApiError::RateLimited(reply.retry_after);
+    }
+
+    let message = |default: &str| {
+        if reply.body.is_empty() {
+            default.to_string()
+        } else {
+            reply.body.clone()
+        }
+    };
+
+    match reply.status {
+        400 => ApiError::BadRequest(message("Bad Request")),
+        401 => ApiError::Unauthorized(message("Unauthorized")),
+        403 => ApiError::Forbidden(message("Forbidden")),
+        404 => ApiError::NotFound(message("Not Found")),
+        422 => ApiError::InvalidInput(message("Unprocessable Entity")),
+        500 => ApiError::ServerError(message("Internal Server Error")),
+        _ => ApiError::Unexpected(message("Unexpected Error")),
+    }
+}
+
+/// Reads the JSON in recap stream events, skipping empty ones. Anything
+/// other than a recap event is an error object.
+fn parse_sse_events(
+    messages: impl IntoIterator<Item = SseMessage>,
+) -> Vec<Result<SseEvent, ApiError>> {
+    messages
+        .into_iter()
+        .filter(|message| !message.data.trim().is_empty())
+        .map(|message| {
+            serde_json::from_str::<SseEvent>(&message.data).map_err(|e| {
+                match serde_json::from_str::<Value>(&message.data)
+                    .ok()
+                    .as_ref()
+                    .and_then(|error| error.get("error"))
+                    .and_then(Value::as_str)
+                {
+                    Some(error_msg) => ApiError::NotFound(error_msg.to_string()),
+                    None => ApiError::DecodeError(format!("Failed to parse SSE event: {e}")),
+                }
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::Server;
+
+    #[tokio::test]
+    async fn test_check_reachable() {
+        let mut server = Server::new_async().await;
+        let _head = server.mock("HEAD", "/").with_status(404).create();
+        assert!(ApiClient::new(&server.url())
+            .check_reachable()
+            .await
+            .is_ok());
+
+        // Nothing listens on the discard port
+        let offline = ApiClient::new("http://127.0.0.1:9");
+        assert!(matches!(
+            offline.check_reachable().await,
+            Err(ApiError::Unreachable(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_server_errors_are_retried_until_the_policy_gives_up() {
+        let mut server = Server::new_async().await;
+        let unavailable = server
+            .mock("GET", "/api/v1/worklog/entries")
+            .with_status(503)
+            .expect(3)
+            .create_async()
+            .await;
+        let missing = server
+            .mock("GET", "/api/v1/projects/x")
+            .with_status(404)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = ApiClient::new(&server.url());
+        client.set_retry_policy(RetryPolicy {
+            max_retries: 2,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(5),
+        });
+        assert!(client
+            .get::<Value>("api/v1/worklog/entries", false)
+            .await
+            .is_err());
+        assert!(matches!(
+            client.get::<Value>("api/v1/projects/x", false).await,
+            Err(ApiError::NotFound(_))
+        ));
+        unavailable.assert_async().await;
+        missing.assert_async().await;
+
+        // --no-retry
+        client.set_retry_policy(RetryPolicy::none());
+        let once = server
+            .mock("GET", "/api/v1/worklog/entries")
+            .with_status(502)
+            .expect(1)
+            .create_async()
+            .await;
+        assert!(client
+            .get::<Value>("api/v1/worklog/entries", false)
+            .await
+            .is_err());
+        once.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_server_errors_on_a_post_are_not_retried() {
+        let mut server = Server::new_async().await;
+        // The entry may have been created before the server failed
+        let unavailable = server
+            .mock("POST", "/api/v1/worklog/entries")
+            .with_status(503)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = ApiClient::new(&server.url());
+        client.set_retry_policy(RetryPolicy {
+            max_retries: 2,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(5),
+        });
+        let body = serde_json::json!({ "content": "Fixed the build" });
+        assert!(client
+            .post::<Value>("api/v1/worklog/entries", body, false)
+            .await
+            .is_err());
+        unavailable.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_rate_limited_gets_wait_out_retry_after() {
+        let mut server = Server::new_async().await;
+        let limited = server
+            .mock("GET", "/api/v1/projects")
+            .with_status(429)
+            .with_header("Retry-After", "0")
+            .expect(3)
+            .create_async()
+            .await;
+        let posted = server
+            .mock("POST", "/api/v1/worklog/entries")
+            .with_status(429)
+            .with_header("Retry-After", "120")
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = ApiClient::new(&server.url());
+        client.set_retry_policy(RetryPolicy::with_max_retries(2));
+        assert!(matches!(
+            client.get::<Value>("api/v1/projects", false).await,
+            Err(ApiError::RateLimited(Some(wait))) if wait.is_zero()
+        ));
+        // POSTs aren't repeated, but the wait is still reported
+        assert!(matches!(
+            client
+                .post::<Value>("api/v1/worklog/entries", serde_json::json!({}), false)
+                .await,
+            Err(ApiError::RateLimited(Some(wait))) if wait == Duration::from_secs(120)
+        ));
+        limited.assert_async().await;
+        posted.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_cassette_replays_recorded_responses_offline() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("cassette.json");
+        let mut server = Server::new_async().await;
+        let _projects = server
+            .mock("GET", "/api/v1/projects")
+            .with_status(200)
+            .with_body(r#"{"projects":[]}"#)
+            .create();
+        let _missing = server
+            .mock("GET", "/api/v1/worklog/entries/x")
+            .with_status(404)
+            .with_body("gone")
+            .create();
+
+        let mut recording = ApiClient::new(&server.url());
+        recording.set_access_token("token".into());
+        recording.set_cassette(Cassette::record(&path));
+        let _: Value = recording.get("api/v1/projects", true).await.unwrap();
+        let _ = recording
+            .get::<Value>("api/v1/worklog/entries/x", true)
+            .await;
+
+        let mut replaying = ApiClient::new("http://127.0.0.1:9");
+        replaying.set_access_token("token".into());
+        replaying.set_cassette(Cassette::replay(&path).unwrap());
+        assert!(replaying.check_reachable().await.is_ok());
+        let projects: Value = replaying.get("api/v1/projects", true).await.unwrap();
+        assert_eq!(projects, serde_json::json!({ "projects": [] }));
+        assert!(matches!(
+            replaying.get::<Value>("api/v1/worklog/entries/x", true).await,
+            Err(ApiError::NotFound(body)) if body == "gone"
+        ));
+        // Nothing left to replay
+        assert!(replaying
+            .get::<Value>("api/v1/projects", true)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_stream_sse_decodes_events() {
+        let mut server = Server::new_async().await;
+        let _stream = server
+            .mock("GET", "/api/v1/worklog/recaps/r1/stream")
+            .with_status(200)
+            .with_header("content-type", "text/event-stream")
+            .with_body(concat!(
+                ": keep-alive\n\n",
+                "id: 1\nevent: progress\n",
+                "data: {\"recap_id\":\"r1\",\"status\":\"processing\",\"progress\":50}\n\n",
+                "data:\n\n",
+                // The server hangs up without the final blank line
+                "data: {\"recap_id\":\"r1\",\"status\":\"completed\",\"content\":\"Done\"}\n",
+            ))
+            .create();
+
+        let mut api_client = ApiClient::new(&server.url());
+        api_client.set_access_token("token".into());
+        let events: Vec<SseEvent> = api_client
+            .stream_sse("api/v1/worklog/recaps/r1/stream")
+            .await
+            .unwrap()
+            .map(Result::unwrap)
+            .collect()
+            .await;
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].progress, Some(50));
+        assert_eq!(events[1].status, "completed");
+        assert_eq!(events[1].content.as_deref(), Some("Done"));
+    }
+
+    #[tokio::test]
+    async fn test_stream_sse_resumes_after_a_drop() {
+        let mut server = Server::new_async().await;
+        let endpoint = "/api/v1/worklog/recaps/r1/stream";
+        // The first connection drops before the recap finishes
+        let dropped = server
+            .mock("GET", endpoint)
+            .match_header("last-event-id", mockito::Matcher::Missing)
+            .with_header("content-type", "text/event-stream")
+            .with_body(concat!(
+                "retry: 10\nid: 1\n",
+                "data: {\"recap_id\":\"r1\",\"status\":\"processing\",\"progress\":30}\n\n",
+            ))
+            .create_async()
+            .await;
+        let resumed = server
+            .mock("GET", endpoint)
+            .match_header("last-event-id", "1")
+            .with_header("content-type", "text/event-stream")
+            .with_body(concat!(
+                "id: 2\n",
+                "data: {\"recap_id\":\"r1\",\"status\":\"completed\",\"content\":\"Done\"}\n\n",
+            ))
+            .create_async()
+            .await;
+
+        let mut api_client = ApiClient::new(&server.url());
+        api_client.set_access_token("token".into());
+        let events: Vec<SseEvent> = api_client
+            .stream_sse(&endpoint[1..])
+            .await
+            .unwrap()
+            .map(Result::unwrap)
+            .collect()
+            .await;
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].progress, Some(30));
+        assert_eq!(events[1].content.as_deref(), Some("Done"));
+        dropped.assert_async().await;
+        resumed.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_stream_sse_stops_reconnecting() {
+        let mut server = Server::new_async().await;
+        // Replays the same event and hangs up, every time
+        let stream = server
+            .mock("GET", "/api/v1/worklog/recaps/r1/stream")
+            .with_header("content-type", "text/event-stream")
+            .with_body(concat!(
+                "retry: 10\nid: 1\n",
+                "data: {\"recap_id\":\"r1\",\"status\":\"processing\"}\n\n",
+            ))
+            .expect(3)
+            .create_async()
+            .await;
+
+        let mut api_client = ApiClient::new(&server.url());
+        api_client.set_access_token("token".into());
+        api_client.set_retry_policy(RetryPolicy::with_max_retries(2));
+        let events: Vec<_> = api_client
+            .stream_sse("api/v1/worklog/recaps/r1/stream")
+            .await
+            .unwrap()
+            .collect()
+            .await;
+
+        // The connection and two reconnections, after which the stream ends
+        assert_eq!(events.len(), 3);
+        assert!(events.iter().all(Result::is_ok));
+        stream.assert_async().await;
+    }
+}
@@ -0,0 +1,150 @@
+use crate::diagnostics;
+use chrono::{DateTime, Duration, Utc};
+use reqwest::header::{HeaderMap, DATE};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+/// Offsets beyond this are reported to the user. Smaller ones are within what
+/// the one-second `Date` resolution and request latency can explain.
+pub const SKEW_WARNING_THRESHOLD: i64 = 5 * 60;
+
+/// Tracks how far the local clock is from the server's, learned from the `Date`
+/// header of API responses. With adjustment enabled, `now()` returns server time
+/// so entries aren't recorded in the future and expiry isn't misjudged.
+#[derive(Debug, Default)]
+pub struct ServerClock {
+    offset_secs: Mutex<Option<i64>>,
+    adjust: AtomicBool,
+    warned: AtomicBool,
+}
+
+impl ServerClock {
+    pub fn set_adjust(&self, adjust: bool) {
+        self.adjust.store(adjust, Ordering::Relaxed);
+    }
+
+    /// Records the skew reported by a response, warning once per run when it's significant.
+    pub fn observe(&self, headers: &HeaderMap) {
+        let Some(server_time) = headers
+            .get(DATE)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| DateTime::parse_from_rfc2822(v.trim()).ok())
+        else {
+            return;
+        };
+        self.record(server_time.with_timezone(&Utc), Utc::now());
+    }
+
+    fn record(&self, server_time: DateTime<Utc>, local_time: DateTime<Utc>) {
+        let offset = (server_time - local_time).num_seconds();
+        if let Ok(mut stored) = self.offset_secs.lock() {
+            *stored = Some(offset);
+        }
+
+        if offset.abs() >= SKEW_WARNING_THRESHOLD && !self.warned.swap(true, Ordering::Relaxed) {
+            let hint = if self.adjust.load(Ordering::Relaxed) {
+                "Times are being adjusted to the server clock."
+            } else {
+                "Sync your system clock, or set `adjust_clock_skew = true` in ~/.accomplish/config.toml."
+            };
+            diagnostics::warn(&format!(
+                "Your system clock is {} the Accomplish server. {hint}",
+                describe_offset(offset)
+            ));
+        }
+    }
+
+    /// Server minus local time, once a response has been seen.
+    pub fn offset(&self) -> Option<Duration> {
+        self.offset_secs
+            .lock()
+            .ok()
+            .and_then(|offset| *offset)
+            .map(Duration::seconds)
+    }
+
+    /// Current time, corrected to the server clock when adjustment is enabled.
+    pub fn now(&self) -> DateTime<Utc> {
+        let now = Utc::now();
+        match self.offset() {
+            Some(offset) if self.adjust.load(Ordering::Relaxed) => now + offset,
+            _ => now,
+        }
+    }
+}
+
+/// Describes a server-minus-local offset from the local clock's point of view.
+fn describe_offset(offset_secs: i64) -> String {
+    let minutes = offset_secs.abs() / 60;
+    let amount = if minutes >= 120 {
+        format!("{} hours", minutes / 60)
+    } else {
+        format!("{minutes} minutes")
+    };
+
+    // A server ahead of us means the local clock is behind
+    if offset_secs > 0 {
+        format!("{amount} behind")
+    } else {
+        format!("{amount} ahead of")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    fn at(value: &str) -> DateTime<Utc> {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn test_record_offset() {
+        let clock = ServerClock::default();
+        assert_eq!(clock.offset(), None);
+
+        clock.record(at("2025-07-09T12:10:00Z"), at("2025-07-09T12:00:00Z"));
+        assert_eq!(clock.offset(), Some(Duration::minutes(10)));
+    }
+
+    #[test]
+    fn test_now_only_adjusts_when_enabled() {
+        let clock = ServerClock::default();
+        clock.record(Utc::now() + Duration::hours(2), Utc::now());
+
+        assert!((clock.now() - Utc::now()).num_minutes().abs() < 1);
+
+        clock.set_adjust(true);
+        assert!(
+            (clock.now() - Utc::now() - Duration::hours(2))
+                .num_minutes()
+                .abs()
+                < 1
+        );
+    }
+
+    #[test]
+    fn test_observe_reads_date_header() {
+        let clock = ServerClock::default();
+        let mut headers = HeaderMap::new();
+        let server_time = (Utc::now() - Duration::hours(3))
+            .format("%a, %d %b %Y %H:%M:%S GMT")
+            .to_string();
+        headers.insert(DATE, HeaderValue::from_str(&server_time).unwrap());
+
+        clock.observe(&headers);
+        let offset = clock.offset().unwrap();
+        assert!((offset + Duration::hours(3)).num_seconds().abs() <= 2);
+
+        // A response without a Date header leaves the last offset in place
+        clock.observe(&HeaderMap::new());
+        assert_eq!(clock.offset(), Some(offset));
+    }
+
+    #[test]
+    fn test_describe_offset() {
+        assert_eq!(describe_offset(600), "10 minutes behind");
+        assert_eq!(describe_offset(-3 * 3600), "3 hours ahead of");
+    }
+}
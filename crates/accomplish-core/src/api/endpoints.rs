@@ -1,10 +1,12 @@
 use crate::api::client::ApiClient;
 use crate::api::errors::ApiError;
 use crate::api::models::{
-    DeviceCodeResponse, RecapResponse, RecapStatusResponse, TokenInfoResponse, TokenResponse,
+    Commit, CommitsResponse, DeviceCodeResponse, EntriesPage, Project, ProjectsResponse,
+    RecapResponse, RecapStatusResponse, RepositoriesResponse, Repository, TokenInfoResponse,
+    TokenResponse, WorklogEntry,
 };
 use chrono::{NaiveDate, NaiveTime, TimeZone, Utc};
-use serde_json::{json, Value};
+use serde_json::{json, Map, Value};
 
 // Scopes requested by the official CLI
 const CLI_SCOPES: &str = concat!(
@@ -61,6 +63,19 @@ pub async fn exchange_device_code_for_token(
     api_client.post("auth/device/token", body, false).await
 }
 
+/// Trades a refresh token for a new access token (and possibly a new refresh token).
+pub async fn refresh_access_token(
+    api_client: &ApiClient,
+    refresh_token: &str,
+) -> Result<TokenResponse, ApiError> {
+    let body = json!({
+        "grant_type": "refresh_token",
+        "refresh_token": refresh_token,
+    });
+
+    api_client.post("auth/token", body, false).await
+}
+
 /// Checks the validity of an existing token.
 pub async fn check_token_info(
     api_client: &ApiClient,
@@ -83,7 +98,8 @@ pub async fn create_worklog_entry(
     recorded_at: &str,
     tags: &[String],
     project_id: Option<&str>,
-) -> Result<Value, ApiError> {
+    custom_fields: Option<&Map<String, Value>>,
+) -> Result<WorklogEntry, ApiError> {
     let mut body = json!({
         "content": content,
         "recorded_at": recorded_at,
@@ -101,6 +117,12 @@ pub async fn create_worklog_entry(
         }
     }
 
+    if let Some(fields) = custom_fields.filter(|f| !f.is_empty()) {
+        if let Some(obj) = body.as_object_mut() {
+            obj.insert("custom_fields".to_string(), Value::Object(fields.clone()));
+        }
+    }
+
     api_client.post("api/v1/worklog/entries", body, true).await
 }
 
@@ -118,14 +140,78 @@ pub async fn associate_commits_with_entry(
     api_client.post(&endpoint, body, true).await
 }
 
+/// Links a worklog entry to another one, e.g. as a follow-up (`relation` is
+/// `follows_up` or `relates_to`).
+pub async fn create_entry_relation(
+    api_client: &ApiClient,
+    entry_id: &str,
+    relation: &str,
+    related_entry_id: &str,
+) -> Result<Value, ApiError> {
+    let body = json!({
+        "relation": relation,
+        "related_entry_id": related_entry_id,
+    });
+
+    let endpoint = format!("api/v1/worklog/entries/{entry_id}/relations");
+    api_client.post(&endpoint, body, true).await
+}
+
+/// Fetches a single worklog entry by ID.
+pub async fn fetch_worklog_entry(
+    api_client: &ApiClient,
+    entry_id: &str,
+) -> Result<WorklogEntry, ApiError> {
+    let endpoint = format!("api/v1/worklog/entries/{entry_id}");
+    let mut response: Value = api_client.get(&endpoint, true).await?;
+    // Accept both a bare entry and one wrapped in `{ "entry": ... }`
+    let entry = match response.get_mut("entry") {
+        Some(entry) if entry.is_object() => entry.take(),
+        _ => response,
+    };
+    api_client.decode_value("GET", &endpoint, entry)
+}
+
+/// Updates fields of an existing worklog entry (e.g. `content`, `tags`, `recorded_at`).
+pub async fn update_worklog_entry(
+    api_client: &ApiClient,
+    entry_id: &str,
+    changes: Value,
+) -> Result<Value, ApiError> {
+    let endpoint = format!("api/v1/worklog/entries/{entry_id}");
+    api_client
+        .request(reqwest::Method::PATCH, &endpoint, &[], Some(changes))
+        .await
+}
+
+/// Fetches the revisions of a worklog entry, oldest first.
+pub async fn fetch_entry_revisions(
+    api_client: &ApiClient,
+    entry_id: &str,
+) -> Result<Value, ApiError> {
+    let endpoint = format!("api/v1/worklog/entries/{entry_id}/revisions");
+    api_client.get(&endpoint, true).await
+}
+
+/// Deletes a worklog entry.
+pub async fn delete_worklog_entry(api_client: &ApiClient, entry_id: &str) -> Result<(), ApiError> {
+    let endpoint = format!("api/v1/worklog/entries/{entry_id}");
+    api_client
+        .request(reqwest::Method::DELETE, &endpoint, &[], None)
+        .await
+        .map(|_| ())
+}
+
 /// Fetches all projects for the current user.
-pub async fn fetch_projects(api_client: &ApiClient) -> Result<Value, ApiError> {
-    api_client.get("api/v1/projects", true).await
+pub async fn fetch_projects(api_client: &ApiClient) -> Result<Vec<Project>, ApiError> {
+    let response: ProjectsResponse = api_client.get("api/v1/projects", true).await?;
+    Ok(response.projects)
 }
 
 /// Fetches all repositories for the current user.
-pub async fn fetch_repositories(api_client: &ApiClient) -> Result<Value, ApiError> {
-    api_client.get("api/v1/repositories", true).await
+pub async fn fetch_repositories(api_client: &ApiClient) -> Result<Vec<Repository>, ApiError> {
+    let response: RepositoriesResponse = api_client.get("api/v1/repositories", true).await?;
+    Ok(response.repositories)
 }
 
 /// Creates a new project.
@@ -154,6 +240,27 @@ pub async fn create_project(
     api_client.post("api/v1/projects", body, true).await
 }
 
+/// Updates fields of an existing project (`name`, `description`, `identifier`).
+pub async fn update_project(
+    api_client: &ApiClient,
+    project_id: &str,
+    changes: Value,
+) -> Result<Value, ApiError> {
+    let endpoint = format!("api/v1/projects/{project_id}");
+    api_client
+        .request(reqwest::Method::PATCH, &endpoint, &[], Some(changes))
+        .await
+}
+
+/// Archives a project, so it no longer shows up or takes new entries.
+pub async fn archive_project(api_client: &ApiClient, project_id: &str) -> Result<(), ApiError> {
+    let endpoint = format!("api/v1/projects/{project_id}");
+    api_client
+        .request(reqwest::Method::DELETE, &endpoint, &[], None)
+        .await
+        .map(|_| ())
+}
+
 /// Creates a new repository.
 pub async fn create_repo(
     api_client: &ApiClient,
@@ -162,7 +269,7 @@ pub async fn create_repo(
     local_path: Option<&str>,
     remote_url: Option<&str>,
     default_branch: Option<&str>,
-) -> Result<Value, ApiError> {
+) -> Result<Repository, ApiError> {
     let mut body = json!({
         "name": name,
         "project_id": project_id,
@@ -189,6 +296,27 @@ pub async fn create_repo(
     api_client.post("api/v1/repositories", body, true).await
 }
 
+/// Updates fields of an existing repository (e.g. `default_branch`, `remote_url`).
+pub async fn update_repository(
+    api_client: &ApiClient,
+    repo_id: &str,
+    changes: Value,
+) -> Result<Value, ApiError> {
+    let endpoint = format!("api/v1/repositories/{repo_id}");
+    api_client
+        .request(reqwest::Method::PATCH, &endpoint, &[], Some(changes))
+        .await
+}
+
+/// Removes a repository. Entries and commits already captured from it stay.
+pub async fn delete_repository(api_client: &ApiClient, repo_id: &str) -> Result<(), ApiError> {
+    let endpoint = format!("api/v1/repositories/{repo_id}");
+    api_client
+        .request(reqwest::Method::DELETE, &endpoint, &[], None)
+        .await
+        .map(|_| ())
+}
+
 /// Fetches uncaptured commits for a repository.
 pub async fn fetch_uncaptured_commits(
     api_client: &ApiClient,
@@ -206,13 +334,14 @@ pub async fn create_commits(
     api_client: &ApiClient,
     repo_id: &str,
     commits: &[CommitData],
-) -> Result<Value, ApiError> {
+) -> Result<Vec<Commit>, ApiError> {
     let body = json!({
         "commits": commits
     });
 
     let endpoint = format!("api/v1/repositories/{repo_id}/commits");
-    api_client.post(&endpoint, body, true).await
+    let response: CommitsResponse = api_client.post(&endpoint, body, true).await?;
+    Ok(response.commits)
 }
 
 /// Represents commit data for API requests.
@@ -224,19 +353,28 @@ pub struct CommitData {
 }
 
 /// Fetches worklog entries with optional filtering.
+/// Several `project_ids` (a project and its sub-projects) are sent as `project_ids`,
+/// like the recap endpoint; a single one as `project_id`.
+/// `has_commits` restricts results to entries with (`true`) or without (`false`) linked commits.
+#[allow(clippy::too_many_arguments)]
 pub async fn fetch_worklog_entries(
     api_client: &ApiClient,
-    project_id: Option<&str>,
+    project_ids: Option<&[String]>,
     tags: Option<&[String]>,
     from: Option<&str>,
     to: Option<&str>,
+    has_commits: Option<bool>,
     limit: u32,
     starting_after: Option<&str>,
-) -> Result<Value, ApiError> {
+) -> Result<EntriesPage, ApiError> {
     let mut params = vec![format!("limit={}", limit)];
 
-    if let Some(project) = project_id {
-        params.push(format!("project_id={project}"));
+    match project_ids {
+        Some([project]) => params.push(format!("project_id={project}")),
+        Some(projects) if !projects.is_empty() => {
+            params.push(format!("project_ids={}", projects.join(",")))
+        }
+        _ => {}
     }
 
     if let Some(tags_list) = tags {
@@ -255,6 +393,10 @@ pub async fn fetch_worklog_entries(
         params.push(format!("to={formatted_date}"));
     }
 
+    if let Some(has_commits) = has_commits {
+        params.push(format!("has_commits={has_commits}"));
+    }
+
     if let Some(cursor) = starting_after {
         params.push(format!("starting_after={cursor}"));
     }
@@ -269,7 +411,9 @@ pub async fn fetch_worklog_entries(
     api_client.get(&endpoint, true).await
 }
 
-/// Generates a new worklog recap using the API
+/// Generates a new worklog recap using the API. With `team`, the recap covers
+/// every member of the organization, with a section per person in its metadata.
+#[allow(clippy::too_many_arguments)]
 pub async fn generate_worklog_recap(
     api_client: &ApiClient,
     from: Option<&str>,
@@ -277,6 +421,8 @@ pub async fn generate_worklog_recap(
     project_ids: Option<&[String]>,
     tags: Option<&[String]>,
     exclude_tags: Option<&[String]>,
+    audience: Option<&str>,
+    team: bool,
 ) -> Result<RecapResponse, ApiError> {
     let mut params = Vec::new();
 
@@ -308,6 +454,14 @@ pub async fn generate_worklog_recap(
         }
     }
 
+    if let Some(audience) = audience {
+        params.push(format!("audience={audience}"));
+    }
+
+    if team {
+        params.push("scope=team".to_string());
+    }
+
     let query = if params.is_empty() {
         String::new()
     } else {
@@ -397,7 +551,7 @@ mod tests {
         assert_eq!(tok.access_token, "access_token_789");
         assert_eq!(tok.token_type, "bearer");
         assert_eq!(tok.expires_in, 3600);
-        assert_eq!(tok.refresh_token, "refresh_token_101");
+        assert_eq!(tok.refresh_token.as_deref(), Some("refresh_token_101"));
         assert_eq!(tok.scope, CLI_SCOPES);
     }
 
@@ -430,25 +584,107 @@ mod tests {
         // Set a dummy token so that use_auth = true won't fail
         api_client.set_access_token("dummy-token".into());
 
-        let resp =
-            create_worklog_entry(&api_client, "Test entry", "2025-05-16T12:00:00Z", &[], None)
-                .await
-                .expect("Expected Ok");
+        let resp = create_worklog_entry(
+            &api_client,
+            "Test entry",
+            "2025-05-16T12:00:00Z",
+            &[],
+            None,
+            None,
+        )
+        .await
+        .expect("Expected Ok");
 
+        assert_eq!(resp.id, "abcd-1234-uuid");
+        assert_eq!(resp.content, "Test entry");
         assert_eq!(
-            resp.get("id").and_then(Value::as_str),
-            Some("abcd-1234-uuid")
-        );
-        assert_eq!(
-            resp.get("content").and_then(Value::as_str),
-            Some("Test entry")
-        );
-        assert_eq!(
-            resp.get("recorded_at").and_then(Value::as_str),
-            Some("2025-05-16T12:00:00Z")
+            resp.recorded_at,
+            Some("2025-05-16T12:00:00Z".parse().unwrap())
         );
     }
 
+    #[tokio::test]
+    async fn test_create_worklog_entry_with_custom_fields() {
+        let mut server = Server::new_async().await;
+        let payload = json!({
+            "content": "Test entry",
+            "recorded_at": "2025-05-16T12:00:00Z",
+            "custom_fields": { "ticket": "PROJ-1", "effort": 2.5 }
+        });
+
+        let _m = server
+            .mock("POST", "/api/v1/worklog/entries")
+            .match_header("authorization", Matcher::Any)
+            .match_body(Matcher::Json(payload))
+            .with_status(201)
+            .with_body(r#"{"id":"abcd-1234-uuid"}"#)
+            .create();
+
+        let mut api_client = ApiClient::new(&server.url());
+        api_client.set_access_token("dummy-token".into());
+
+        let mut fields = Map::new();
+        fields.insert("ticket".into(), json!("PROJ-1"));
+        fields.insert("effort".into(), json!(2.5));
+
+        let resp = create_worklog_entry(
+            &api_client,
+            "Test entry",
+            "2025-05-16T12:00:00Z",
+            &[],
+            None,
+            Some(&fields),
+        )
+        .await
+        .expect("Expected Ok");
+
+        assert_eq!(resp.id, "abcd-1234-uuid");
+    }
+
+    #[tokio::test]
+    async fn test_fetch_worklog_entry() {
+        let mut server = Server::new_async().await;
+        let _bare = server
+            .mock("GET", "/api/v1/worklog/entries/entry-1")
+            .match_header("authorization", "Bearer dummy-token")
+            .with_status(200)
+            .with_body(r#"{"id":"entry-1","content":"Bare"}"#)
+            .create();
+        let _wrapped = server
+            .mock("GET", "/api/v1/worklog/entries/entry-2")
+            .with_status(200)
+            .with_body(r#"{"entry":{"id":"entry-2","content":"Wrapped"}}"#)
+            .create();
+        let _missing = server
+            .mock("GET", "/api/v1/worklog/entries/missing")
+            .with_status(404)
+            .create();
+        let _malformed = server
+            .mock("GET", "/api/v1/worklog/entries/entry-3")
+            .with_status(200)
+            .with_body(r#"{"entry":{"id":"entry-3","recorded_at":"soon"}}"#)
+            .create();
+
+        let mut api_client = ApiClient::new(&server.url());
+        api_client.set_access_token("dummy-token".into());
+
+        let bare = fetch_worklog_entry(&api_client, "entry-1").await.unwrap();
+        assert_eq!(bare.content, "Bare");
+        let wrapped = fetch_worklog_entry(&api_client, "entry-2").await.unwrap();
+        assert_eq!(wrapped.id, "entry-2");
+        assert_eq!(wrapped.content, "Wrapped");
+        assert!(matches!(
+            fetch_worklog_entry(&api_client, "missing").await,
+            Err(ApiError::NotFound(_))
+        ));
+        match fetch_worklog_entry(&api_client, "entry-3").await {
+            Err(ApiError::DecodeError(message)) => {
+                assert!(message.contains("unexpected response at recorded_at"))
+            }
+            other => panic!("expected a decode error, got {other:?}"),
+        }
+    }
+
     #[tokio::test]
     async fn test_fetch_projects() {
         let response = json!({
@@ -456,6 +692,7 @@ mod tests {
                 {
                     "id": "3fa85f64-5717-4562-b3fc-2c963f66afa6",
                     "name": "website",
+                    "identifier": "web",
                     "slug": "website",
                     "description": "Company website",
                     "company": "Acme Inc",
@@ -469,6 +706,7 @@ mod tests {
                 {
                     "id": "7c9e6679-7425-40de-944b-e07fc1f90ae7",
                     "name": "internal-ops",
+                    "identifier": "ops",
                     "slug": "internal-ops",
                     "description": "Internal operations",
                     "company": "Acme Inc",
@@ -494,22 +732,15 @@ mod tests {
         let mut api_client = ApiClient::new(&server.url());
         api_client.set_access_token("dummy-token".into());
 
-        let result = fetch_projects(&api_client).await.expect("Expected Ok");
+        let projects = fetch_projects(&api_client).await.expect("Expected Ok");
 
-        // Check that we got the projects array
-        let projects = result.get("projects").expect("Expected projects key");
-        assert!(projects.is_array());
-        assert_eq!(projects.as_array().unwrap().len(), 2);
-
-        // Check first project
-        let first_project = &projects.as_array().unwrap()[0];
-        assert_eq!(first_project["id"], "3fa85f64-5717-4562-b3fc-2c963f66afa6");
-        assert_eq!(first_project["name"], "website");
-
-        // Check second project
-        let second_project = &projects.as_array().unwrap()[1];
-        assert_eq!(second_project["id"], "7c9e6679-7425-40de-944b-e07fc1f90ae7");
-        assert_eq!(second_project["name"], "internal-ops");
+        assert_eq!(projects.len(), 2);
+        assert_eq!(projects[0].id, "3fa85f64-5717-4562-b3fc-2c963f66afa6");
+        assert_eq!(projects[0].name, "website");
+        assert_eq!(projects[0].identifier, "web");
+        assert_eq!(projects[0].company.as_deref(), Some("Acme Inc"));
+        assert_eq!(projects[1].id, "7c9e6679-7425-40de-944b-e07fc1f90ae7");
+        assert_eq!(projects[1].name, "internal-ops");
     }
 
     #[tokio::test]
@@ -547,24 +778,14 @@ mod tests {
             "2025-05-16T12:00:00Z",
             &tags,
             None,
+            None,
         )
         .await
         .expect("Expected Ok");
 
-        assert_eq!(
-            resp.get("id").and_then(Value::as_str),
-            Some("efgh-5678-uuid")
-        );
-        assert_eq!(
-            resp.get("content").and_then(Value::as_str),
-            Some("Test entry with tags")
-        );
-        assert_eq!(
-            resp.get("tags")
-                .and_then(|v| v.as_array())
-                .map(|arr| arr.iter().filter_map(|v| v.as_str()).collect::<Vec<_>>()),
-            Some(vec!["rust", "cli"])
-        );
+        assert_eq!(resp.id, "efgh-5678-uuid");
+        assert_eq!(resp.content, "Test entry with tags");
+        assert_eq!(resp.tags, vec!["rust", "cli"]);
     }
 
     #[tokio::test]
@@ -614,24 +835,14 @@ mod tests {
             "2025-05-16T12:00:00Z",
             &processed_tags,
             None,
+            None,
         )
         .await
         .expect("Expected Ok");
 
-        assert_eq!(
-            resp.get("id").and_then(Value::as_str),
-            Some("ijkl-9012-uuid")
-        );
-        assert_eq!(
-            resp.get("content").and_then(Value::as_str),
-            Some("Test entry with comma-separated tags")
-        );
-        assert_eq!(
-            resp.get("tags")
-                .and_then(|v| v.as_array())
-                .map(|arr| arr.iter().filter_map(|v| v.as_str()).collect::<Vec<_>>()),
-            Some(vec!["rust", "cli"])
-        );
+        assert_eq!(resp.id, "ijkl-9012-uuid");
+        assert_eq!(resp.content, "Test entry with comma-separated tags");
+        assert_eq!(resp.tags, vec!["rust", "cli"]);
     }
 
     #[tokio::test]
@@ -792,30 +1003,15 @@ mod tests {
         .await
         .expect("Expected Ok");
 
+        assert_eq!(resp.id, "repo-uuid-123");
+        assert_eq!(resp.name, "My Repository");
+        assert_eq!(resp.project_id.as_deref(), Some("project-uuid-123"));
+        assert_eq!(resp.local_path.as_deref(), Some("/path/to/repo"));
         assert_eq!(
-            resp.get("id").and_then(Value::as_str),
-            Some("repo-uuid-123")
-        );
-        assert_eq!(
-            resp.get("name").and_then(Value::as_str),
-            Some("My Repository")
-        );
-        assert_eq!(
-            resp.get("project_id").and_then(Value::as_str),
-            Some("project-uuid-123")
-        );
-        assert_eq!(
-            resp.get("local_path").and_then(Value::as_str),
-            Some("/path/to/repo")
-        );
-        assert_eq!(
-            resp.get("remote_url").and_then(Value::as_str),
+            resp.remote_url.as_deref(),
             Some("https://github.com/user/repo.git")
         );
-        assert_eq!(
-            resp.get("default_branch").and_then(Value::as_str),
-            Some("main")
-        );
+        assert_eq!(resp.default_branch.as_deref(), Some("main"));
     }
 
     #[tokio::test]
@@ -860,18 +1056,9 @@ mod tests {
         .await
         .expect("Expected Ok");
 
-        assert_eq!(
-            resp.get("id").and_then(Value::as_str),
-            Some("repo-uuid-456")
-        );
-        assert_eq!(
-            resp.get("name").and_then(Value::as_str),
-            Some("Minimal Repo")
-        );
-        assert_eq!(
-            resp.get("project_id").and_then(Value::as_str),
-            Some("project-uuid-456")
-        );
+        assert_eq!(resp.id, "repo-uuid-456");
+        assert_eq!(resp.name, "Minimal Repo");
+        assert_eq!(resp.project_id.as_deref(), Some("project-uuid-456"));
     }
 
     #[tokio::test]
@@ -917,19 +1104,10 @@ mod tests {
         .await
         .expect("Expected Ok");
 
-        assert_eq!(
-            resp.get("id").and_then(Value::as_str),
-            Some("repo-uuid-789")
-        );
-        assert_eq!(
-            resp.get("name").and_then(Value::as_str),
-            Some("Local Repository")
-        );
-        assert_eq!(
-            resp.get("local_path").and_then(Value::as_str),
-            Some("/home/user/my-project")
-        );
-        assert_eq!(resp.get("remote_url"), Some(&serde_json::Value::Null));
+        assert_eq!(resp.id, "repo-uuid-789");
+        assert_eq!(resp.name, "Local Repository");
+        assert_eq!(resp.local_path.as_deref(), Some("/home/user/my-project"));
+        assert_eq!(resp.remote_url, None);
     }
 
     #[tokio::test]
@@ -976,23 +1154,14 @@ mod tests {
         .await
         .expect("Expected Ok");
 
+        assert_eq!(resp.id, "repo-uuid-101");
+        assert_eq!(resp.name, "Remote Repository");
         assert_eq!(
-            resp.get("id").and_then(Value::as_str),
-            Some("repo-uuid-101")
-        );
-        assert_eq!(
-            resp.get("name").and_then(Value::as_str),
-            Some("Remote Repository")
-        );
-        assert_eq!(
-            resp.get("remote_url").and_then(Value::as_str),
+            resp.remote_url.as_deref(),
             Some("git@gitlab.com:group/project.git")
         );
-        assert_eq!(
-            resp.get("default_branch").and_then(Value::as_str),
-            Some("develop")
-        );
-        assert_eq!(resp.get("local_path"), Some(&serde_json::Value::Null));
+        assert_eq!(resp.default_branch.as_deref(), Some("develop"));
+        assert_eq!(resp.local_path, None);
     }
 
     #[tokio::test]
@@ -1139,17 +1308,26 @@ mod tests {
         let mut api_client = ApiClient::new(&server.url());
         api_client.set_access_token("dummy-token".into());
 
-        let resp = fetch_worklog_entries(&api_client, None, None, None, None, 20, None)
+        let page = fetch_worklog_entries(&api_client, None, None, None, None, None, 20, None)
             .await
             .expect("Expected Ok");
 
-        let entries = resp.get("entries").expect("Expected entries array");
-        assert!(entries.is_array());
-        assert_eq!(entries.as_array().unwrap().len(), 2);
+        assert_eq!(page.entries.len(), 2);
+        assert_eq!(page.end_cursor(), Some("entry-uuid-456"));
 
-        let first_entry = &entries.as_array().unwrap()[0];
-        assert_eq!(first_entry["id"], "entry-uuid-123");
-        assert_eq!(first_entry["content"], "Working on feature X");
+        let first_entry = &page.entries[0];
+        assert_eq!(first_entry.id, "entry-uuid-123");
+        assert_eq!(first_entry.content, "Working on feature X");
+        assert_eq!(first_entry.tags, vec!["development", "feature"]);
+        assert_eq!(
+            first_entry.recorded_at.unwrap().to_rfc3339(),
+            "2025-07-09T14:30:00+00:00"
+        );
+
+        // Fields without a typed counterpart survive the round trip
+        let value = Value::from(first_entry.clone());
+        assert_eq!(value["url"], "/api/v1/worklog/entries/entry-uuid-123");
+        assert_eq!(value["recorded_at"], "2025-07-09T14:30:00Z");
     }
 
     #[tokio::test]
@@ -1191,25 +1369,23 @@ mod tests {
         api_client.set_access_token("dummy-token".into());
 
         let tags = vec!["development".to_string(), "feature".to_string()];
-        let resp = fetch_worklog_entries(
+        let page = fetch_worklog_entries(
             &api_client,
-            Some("specific-project"),
+            Some(&["specific-project".to_string()]),
             Some(&tags),
             Some("2025-07-01"),
             Some("2025-07-09"),
+            None,
             10,
             Some("cursor-123"),
         )
         .await
         .expect("Expected Ok");
 
-        let entries = resp.get("entries").expect("Expected entries array");
-        assert!(entries.is_array());
-        assert_eq!(entries.as_array().unwrap().len(), 1);
-
-        let entry = &entries.as_array().unwrap()[0];
-        assert_eq!(entry["id"], "entry-uuid-789");
-        assert_eq!(entry["project_id"], "specific-project");
+        assert_eq!(page.entries.len(), 1);
+        let entry = &page.entries[0];
+        assert_eq!(entry.id, "entry-uuid-789");
+        assert_eq!(entry.project_id.as_deref(), Some("specific-project"));
     }
 
     #[tokio::test]
@@ -1236,16 +1412,199 @@ mod tests {
         let mut api_client = ApiClient::new(&server.url());
         api_client.set_access_token("dummy-token".into());
 
-        let resp = fetch_worklog_entries(&api_client, None, None, None, None, 20, None)
+        let page = fetch_worklog_entries(&api_client, None, None, None, None, None, 20, None)
             .await
             .expect("Expected Ok");
 
-        let entries = resp.get("entries").expect("Expected entries array");
-        assert!(entries.is_array());
-        assert_eq!(entries.as_array().unwrap().len(), 0);
+        assert!(page.entries.is_empty());
+        assert_eq!(page.end_cursor(), None);
+        assert_eq!(page.meta.result_count, Some(0));
+        assert_eq!(page.meta.total_count, Some(0));
+    }
+
+    #[tokio::test]
+    async fn test_malformed_entries_name_the_field() {
+        let mut server = Server::new_async().await;
+        let _m = server
+            .mock("GET", "/api/v1/worklog/entries?limit=20")
+            .with_status(200)
+            .with_body(r#"{"entries":[{"id":"e1","tags":"rust"}],"meta":{}}"#)
+            .create();
+
+        let mut api_client = ApiClient::new(&server.url());
+        api_client.set_access_token("dummy-token".into());
+
+        let err = fetch_worklog_entries(&api_client, None, None, None, None, None, 20, None)
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("unexpected response at entries[0].tags"),
+            "{err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_generate_worklog_recap_with_audience() {
+        let mut server = Server::new_async().await;
+        let _m = server
+            .mock(
+                "POST",
+                "/api/v1/worklog/recaps?from=2025-07-01T00:00:00Z&to=2025-07-09T23:59:59Z&audience=client",
+            )
+            .match_header("authorization", Matcher::Any)
+            .with_status(202)
+            .with_body(
+                json!({
+                    "recap_id": "recap-uuid-123",
+                    "status": "processing",
+                    "poll_url": "/api/v1/worklog/recaps/recap-uuid-123",
+                    "sse_url": null
+                })
+                .to_string(),
+            )
+            .create();
+
+        let mut api_client = ApiClient::new(&server.url());
+        api_client.set_access_token("dummy-token".into());
+
+        let resp = generate_worklog_recap(
+            &api_client,
+            Some("2025-07-01"),
+            Some("2025-07-09"),
+            None,
+            None,
+            None,
+            Some("client"),
+            false,
+        )
+        .await
+        .expect("Expected Ok");
+
+        assert_eq!(resp.recap_id, "recap-uuid-123");
+        assert_eq!(resp.status, "processing");
+    }
+
+    #[tokio::test]
+    async fn test_generate_team_recap_and_read_member_sections() {
+        let mut server = Server::new_async().await;
+        let _recap = server
+            .mock(
+                "POST",
+                "/api/v1/worklog/recaps?from=2025-07-01T00:00:00Z&to=2025-07-09T23:59:59Z&project_ids=p1&scope=team",
+            )
+            .with_status(202)
+            .with_body(r#"{"recap_id": "r1", "status": "processing"}"#)
+            .expect(1)
+            .create();
+        let _status = server
+            .mock("GET", "/api/v1/worklog/recaps/r1")
+            .with_body(
+                json!({
+                    "status": "completed",
+                    "content": "The team shipped billing.",
+                    "metadata": {
+                        "entry_count": 5,
+                        "members": [
+                            { "name": "Ana", "email": "ana@example.com", "entry_count": 3, "content": "Invoices" },
+                            { "name": "Sam", "content": "Reviews" }
+                        ]
+                    }
+                })
+                .to_string(),
+            )
+            .create();
 
-        let meta = resp.get("meta").expect("Expected meta object");
-        assert_eq!(meta["result_count"], 0);
-        assert_eq!(meta["total_count"], 0);
+        let mut api_client = ApiClient::new(&server.url());
+        api_client.set_access_token("dummy-token".into());
+        let projects = ["p1".to_string()];
+        generate_worklog_recap(
+            &api_client,
+            Some("2025-07-01"),
+            Some("2025-07-09"),
+            Some(&projects),
+            None,
+            None,
+            None,
+            true,
+        )
+        .await
+        .unwrap();
+
+        let status = get_recap_status(&api_client, "r1").await.unwrap();
+        let members = status.metadata.unwrap().members;
+        assert_eq!(members.len(), 2);
+        assert_eq!(members[0].name, "Ana");
+        assert_eq!(members[0].entry_count, 3);
+        assert_eq!(members[1].email, None);
+        assert_eq!(members[1].content, "Reviews");
+    }
+
+    #[tokio::test]
+    async fn test_fetch_worklog_entries_has_commits_filter() {
+        let mut server = Server::new_async().await;
+        let response_body = json!({
+            "entries": [],
+            "meta": { "result_count": 0, "total_count": 0, "end_cursor": null, "limit": 20 }
+        });
+
+        let _with = server
+            .mock("GET", "/api/v1/worklog/entries?limit=20&has_commits=true")
+            .match_header("authorization", Matcher::Any)
+            .with_status(200)
+            .with_body(response_body.to_string())
+            .create();
+        let _without = server
+            .mock("GET", "/api/v1/worklog/entries?limit=20&has_commits=false")
+            .match_header("authorization", Matcher::Any)
+            .with_status(200)
+            .with_body(response_body.to_string())
+            .create();
+
+        let mut api_client = ApiClient::new(&server.url());
+        api_client.set_access_token("dummy-token".into());
+
+        fetch_worklog_entries(&api_client, None, None, None, None, Some(true), 20, None)
+            .await
+            .expect("Expected Ok for has_commits=true");
+        fetch_worklog_entries(&api_client, None, None, None, None, Some(false), 20, None)
+            .await
+            .expect("Expected Ok for has_commits=false");
+    }
+
+    #[tokio::test]
+    async fn test_fetch_worklog_entries_multiple_projects() {
+        let mut server = Server::new_async().await;
+        let response_body = json!({
+            "entries": [],
+            "meta": { "result_count": 0, "total_count": 0, "end_cursor": null, "limit": 20 }
+        });
+
+        let _m = server
+            .mock(
+                "GET",
+                "/api/v1/worklog/entries?limit=20&project_ids=web-id,chk-id",
+            )
+            .match_header("authorization", Matcher::Any)
+            .with_status(200)
+            .with_body(response_body.to_string())
+            .create();
+
+        let mut api_client = ApiClient::new(&server.url());
+        api_client.set_access_token("dummy-token".into());
+
+        let project_ids = vec!["web-id".to_string(), "chk-id".to_string()];
+        fetch_worklog_entries(
+            &api_client,
+            Some(&project_ids),
+            None,
+            None,
+            None,
+            None,
+            20,
+            None,
+        )
+        .await
+        .expect("Expected Ok");
     }
 }
@@ -0,0 +1,80 @@
+use std::fmt;
+use std::time::Duration;
+
+#[derive(Debug)]
+pub enum ApiError {
+    Unauthorized(String),
+    /// Signed in, but not allowed to do this, e.g. on the current plan
+    Forbidden(String),
+    BadRequest(String),
+    NotFound(String),
+    ServerError(String),
+    Unexpected(String),
+    DecodeError(String),
+    InvalidInput(String),
+    /// Too many requests, with how long the server asked to wait when it said
+    RateLimited(Option<Duration>),
+    Unreachable(String),
+    /// No answer in time. Unlike `Unreachable`, the request may have been
+    /// handled, so it mustn't be sent again blindly
+    TimedOut(String),
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApiError::Unauthorized(msg) => write!(f, "Unauthorized: {msg}"),
+            ApiError::Forbidden(msg) => write!(f, "Forbidden: {msg}"),
+            ApiError::BadRequest(msg) => write!(f, "Bad Request: {msg}"),
+            ApiError::NotFound(msg) => write!(f, "Not Found: {msg}"),
+            ApiError::ServerError(msg) => write!(f, "Server Error: {msg}"),
+            ApiError::Unexpected(msg) => write!(f, "Unexpected Error: {msg}"),
+            ApiError::DecodeError(msg) => write!(f, "Decoding Error: {msg}"),
+            ApiError::InvalidInput(msg) => write!(f, "Invalid Input: {msg}"),
+            ApiError::Unreachable(msg) => write!(f, "Unreachable: {msg}"),
+            ApiError::TimedOut(msg) => write!(
+                f,
+                "Timed out: {msg}. The server may still have handled the request"
+            ),
+            ApiError::RateLimited(None) => {
+                write!(
+                    f,
+                    "Consider spacing out your requests to avoid hitting the rate limit"
+                )
+            }
+            ApiError::RateLimited(Some(wait)) => {
+                write!(f, "Rate limited. Try again in {}", describe_wait(*wait))
+            }
+        }
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+/// A wait rounded up to whole seconds, minutes past two minutes, or hours
+/// past two hours.
+fn describe_wait(wait: Duration) -> String {
+    let secs = wait.as_secs() + u64::from(wait.subsec_nanos() > 0);
+    match secs {
+        0..120 => format!("{secs}s"),
+        120..7200 => format!("{}m", secs.div_ceil(60)),
+        _ => format!("{}h", secs.div_ceil(3600)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_limited_error_says_how_long_to_wait() {
+        let wait = |secs| ApiError::RateLimited(Some(Duration::from_secs(secs))).to_string();
+        assert_eq!(wait(30), "Rate limited. Try again in 30s");
+        assert_eq!(wait(150), "Rate limited. Try again in 3m");
+        assert_eq!(wait(3 * 3600), "Rate limited. Try again in 3h");
+        assert_eq!(
+            ApiError::RateLimited(Some(Duration::from_millis(1500))).to_string(),
+            "Rate limited. Try again in 2s"
+        );
+    }
+}
@@ -0,0 +1,59 @@
+use std::sync::{Arc, Mutex};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Requests allowed in flight at once unless `max_concurrent_requests` says otherwise.
+pub const DEFAULT_MAX_CONCURRENT_REQUESTS: usize = 4;
+
+/// Caps how many API requests run at the same time, so commands that fan out
+/// (exports, bulk fetches) parallelize without tripping the server's rate limits.
+#[derive(Debug)]
+pub struct RequestLimiter {
+    semaphore: Mutex<Arc<Semaphore>>,
+}
+
+impl Default for RequestLimiter {
+    fn default() -> Self {
+        Self {
+            semaphore: Mutex::new(Arc::new(Semaphore::new(DEFAULT_MAX_CONCURRENT_REQUESTS))),
+        }
+    }
+}
+
+impl RequestLimiter {
+    /// Changes the limit for requests started from now on. Zero is treated as one.
+    pub fn set_max(&self, max: usize) {
+        if let Ok(mut semaphore) = self.semaphore.lock() {
+            *semaphore = Arc::new(Semaphore::new(max.max(1)));
+        }
+    }
+
+    /// Waits for a free slot, held until the permit is dropped.
+    pub async fn acquire(&self) -> Option<OwnedSemaphorePermit> {
+        let semaphore = self.semaphore.lock().ok()?.clone();
+        semaphore.acquire_owned().await.ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use tokio::time::timeout;
+
+    #[tokio::test]
+    async fn test_limits_requests_in_flight() {
+        let limiter = RequestLimiter::default();
+        limiter.set_max(2);
+
+        let first = limiter.acquire().await;
+        let _second = limiter.acquire().await;
+        assert!(timeout(Duration::from_millis(50), limiter.acquire())
+            .await
+            .is_err());
+
+        drop(first);
+        assert!(timeout(Duration::from_millis(50), limiter.acquire())
+            .await
+            .is_ok());
+    }
+}
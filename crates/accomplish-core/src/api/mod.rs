@@ -0,0 +1,11 @@
+pub mod cassette;
+pub mod client;
+pub mod clock;
+pub mod endpoints;
+pub mod errors;
+pub mod limiter;
+pub mod models;
+pub mod retry;
+pub mod schema;
+pub mod sse;
+pub mod versioning;
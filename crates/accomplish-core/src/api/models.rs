@@ -0,0 +1,292 @@
+// src/api/types.rs
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+#[derive(Debug, Deserialize)]
+#[allow(unused)]
+pub struct DeviceCodeResponse {
+    pub device_code: String,
+    pub user_code: String,
+    pub verification_uri: String,
+    pub verification_uri_complete: String,
+    pub interval: u64,
+    /// Seconds until `device_code` expires
+    #[serde(default)]
+    pub expires_in: Option<u64>,
+}
+
+#[derive(Deserialize, Debug)]
+#[allow(unused)]
+pub struct TokenResponse {
+    pub access_token: String,
+    pub token_type: String,
+    pub expires_in: u64,
+    /// Absent when a refresh keeps the current refresh token
+    #[serde(default)]
+    pub refresh_token: Option<String>,
+    pub scope: String,
+}
+
+#[derive(Deserialize, Debug)]
+#[allow(unused)]
+pub struct TokenInfoResponse {
+    pub active: bool,
+    pub scope: String,
+    pub client_id: String,
+    pub username: Option<String>,
+    pub exp: u64,
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct RecapResponse {
+    pub recap_id: String,
+    pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub poll_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sse_url: Option<String>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct RecapStatusResponse {
+    pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filters: Option<RecapFilters>,
+    #[serde(default, deserialize_with = "deserialize_optional_metadata")]
+    pub metadata: Option<RecapMetadata>,
+}
+
+fn deserialize_optional_metadata<'de, D>(deserializer: D) -> Result<Option<RecapMetadata>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    struct Helper {
+        #[serde(default)]
+        entry_count: u32,
+        #[serde(default)]
+        projects: Vec<String>,
+        #[serde(default)]
+        tags: Vec<String>,
+        #[serde(default)]
+        members: Vec<RecapMember>,
+    }
+
+    let helper = Option::<Helper>::deserialize(deserializer)?;
+    Ok(helper.map(|h| RecapMetadata {
+        entry_count: h.entry_count,
+        projects: h.projects,
+        tags: h.tags,
+        members: h.members,
+    }))
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct RecapFilters {
+    #[serde(default)]
+    pub project_ids: Vec<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct RecapMetadata {
+    #[serde(default)]
+    pub entry_count: u32,
+    #[serde(default)]
+    pub projects: Vec<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// One section per person in a team recap, empty otherwise
+    #[serde(default)]
+    pub members: Vec<RecapMember>,
+}
+
+/// One person's part of a team recap.
+#[derive(Debug, serde::Deserialize)]
+pub struct RecapMember {
+    pub name: String,
+    #[serde(default)]
+    pub email: Option<String>,
+    #[serde(default)]
+    pub entry_count: u32,
+    #[serde(default)]
+    pub projects: Vec<String>,
+    #[serde(default)]
+    pub content: String,
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[allow(dead_code)]
+pub struct SseEvent {
+    pub recap_id: String,
+    pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub partial_content: Option<String>,
+    /// Percentage of the recap generated so far
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub progress: Option<u32>,
+    /// What the server is doing, e.g. `collecting_entries` or `summarizing`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stage: Option<String>,
+}
+
+/// A worklog entry. Fields without a typed counterpart (linked commits and
+/// whatever the API adds later) are kept in `extra`, so the entry converts
+/// back to the JSON it was read from.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorklogEntry {
+    pub id: String,
+    #[serde(default)]
+    pub content: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recorded_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<EntryProject>,
+    /// Values of the account's custom fields, by key
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub custom_fields: Map<String, Value>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub relations: Vec<EntryRelation>,
+    /// Number of linked commits, when the API counts them instead of listing them
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit_count: Option<u64>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl WorklogEntry {
+    /// Number of commits linked to the entry, when the API includes them.
+    pub fn commits(&self) -> Option<u64> {
+        self.commit_count.or_else(|| {
+            self.extra
+                .get("commits")
+                .and_then(Value::as_array)
+                .map(|commits| commits.len() as u64)
+        })
+    }
+}
+
+impl From<WorklogEntry> for Value {
+    fn from(entry: WorklogEntry) -> Self {
+        serde_json::to_value(entry).unwrap_or(Value::Null)
+    }
+}
+
+/// The project an entry belongs to, as embedded in the entry.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EntryProject {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub identifier: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+/// A link from an entry to another one, e.g. `follows_up` or `relates_to`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EntryRelation {
+    pub relation: String,
+    pub related_entry_id: String,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// One page of `GET api/v1/worklog/entries`, newest first.
+#[derive(Debug, Deserialize)]
+pub struct EntriesPage {
+    pub entries: Vec<WorklogEntry>,
+    #[serde(default)]
+    pub meta: PageMeta,
+}
+
+impl EntriesPage {
+    /// The cursor for the next page, or None on the last one.
+    pub fn end_cursor(&self) -> Option<&str> {
+        self.meta.end_cursor.as_deref()
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct PageMeta {
+    #[serde(default)]
+    pub result_count: Option<u32>,
+    #[serde(default)]
+    pub total_count: Option<u32>,
+    #[serde(default)]
+    pub end_cursor: Option<String>,
+}
+
+/// A project, as listed by `GET api/v1/projects`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct Project {
+    pub id: String,
+    pub name: String,
+    pub identifier: String,
+    /// Parent project for sub-projects
+    #[serde(default)]
+    pub parent_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub company: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_date: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_date: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ProjectsResponse {
+    pub projects: Vec<Project>,
+}
+
+/// A repository registered with the API.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Repository {
+    pub id: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub project_id: Option<String>,
+    #[serde(default)]
+    pub local_path: Option<String>,
+    #[serde(default)]
+    pub remote_url: Option<String>,
+    #[serde(default)]
+    pub default_branch: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RepositoriesResponse {
+    pub repositories: Vec<Repository>,
+}
+
+/// A commit stored for a repository.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Commit {
+    #[serde(default)]
+    pub id: Option<String>,
+    #[serde(default)]
+    pub sha: Option<String>,
+}
+
+/// The commits stored by `POST api/v1/repositories/{id}/commits`.
+#[derive(Debug, Deserialize)]
+pub struct CommitsResponse {
+    #[serde(default)]
+    pub commits: Vec<Commit>,
+}
@@ -0,0 +1,133 @@
+use chrono::{DateTime, Utc};
+use rand::Rng;
+use std::time::Duration;
+
+/// Retries after a transient failure unless `max_retries` says otherwise.
+pub const DEFAULT_MAX_RETRIES: u32 = 3;
+
+/// Delay before the first retry, doubled for each one after it.
+const BASE_DELAY: Duration = Duration::from_millis(250);
+
+/// Longest wait between two attempts, however many have failed.
+const MAX_DELAY: Duration = Duration::from_secs(8);
+
+/// Longest `Retry-After` a rate-limited GET waits out before trying again.
+/// Anything longer is reported instead, with the wait in the error.
+pub const MAX_RATE_LIMIT_WAIT: Duration = Duration::from_secs(60);
+
+/// How requests that fail for reasons likely to go away on their own (5xx
+/// responses, dropped connections, timeouts) are retried.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: DEFAULT_MAX_RETRIES,
+            base_delay: BASE_DELAY,
+            max_delay: MAX_DELAY,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Never retries, for `--no-retry`.
+    pub fn none() -> Self {
+        Self {
+            max_retries: 0,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_max_retries(max_retries: u32) -> Self {
+        Self {
+            max_retries,
+            ..Self::default()
+        }
+    }
+
+    /// The longest wait before retry number `retry` (counting from 0): the
+    /// base delay doubled for each earlier retry, up to `max_delay`.
+    pub fn backoff(&self, retry: u32) -> Duration {
+        self.base_delay
+            .saturating_mul(2u32.saturating_pow(retry))
+            .min(self.max_delay)
+    }
+
+    /// How long to wait before retry number `retry`: a random time up to
+    /// `backoff`, so clients that failed together don't retry together.
+    pub fn delay(&self, retry: u32) -> Duration {
+        let backoff = self.backoff(retry);
+        backoff.mul_f64(rand::rng().random_range(0.0..=1.0))
+    }
+}
+
+/// Whether a response with this status is worth retrying. Only server-side
+/// failures are; everything else would fail the same way again.
+pub fn is_retryable_status(status: u16) -> bool {
+    matches!(status, 500 | 502 | 503 | 504)
+}
+
+/// Reads a `Retry-After` value: a number of seconds, or an HTTP date that's
+/// compared with `now`, the server's time if known. A date in the past means
+/// no wait at all.
+pub fn parse_retry_after(value: &str, now: DateTime<Utc>) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let at = DateTime::parse_from_rfc2822(value).ok()?;
+    Some((at.with_timezone(&Utc) - now).to_std().unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_doubles_up_to_the_maximum() {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.backoff(0), Duration::from_millis(250));
+        assert_eq!(policy.backoff(1), Duration::from_millis(500));
+        assert_eq!(policy.backoff(3), Duration::from_secs(2));
+        assert_eq!(policy.backoff(10), Duration::from_secs(8));
+        assert_eq!(policy.backoff(u32::MAX), Duration::from_secs(8));
+
+        for retry in 0..5 {
+            assert!(policy.delay(retry) <= policy.backoff(retry));
+        }
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        let now = DateTime::parse_from_rfc2822("Wed, 21 Oct 2026 07:28:00 GMT")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(
+            parse_retry_after("120", now),
+            Some(Duration::from_secs(120))
+        );
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2026 07:28:30 GMT", now),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2026 07:00:00 GMT", now),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(parse_retry_after("soon", now), None);
+        assert_eq!(parse_retry_after("-5", now), None);
+    }
+
+    #[test]
+    fn test_only_server_errors_are_retried() {
+        assert!(is_retryable_status(503));
+        assert!(!is_retryable_status(501));
+        assert!(!is_retryable_status(429));
+        assert!(!is_retryable_status(404));
+    }
+}
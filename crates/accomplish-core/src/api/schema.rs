@@ -0,0 +1,163 @@
+use crate::api::errors::ApiError;
+use crate::diagnostics;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use serde_path_to_error::Segment;
+
+/// Upper bound on repairs for one response, so a badly broken one can't spin.
+const MAX_REPAIRS: usize = 1000;
+
+/// Decodes a response body into `T`, naming the endpoint and the field that
+/// didn't match when it fails, e.g. `GET api/v1/projects: unexpected response
+/// at data[0].name: invalid type: null, expected a string`.
+///
+/// With `lenient` (`--ignore-schema-errors`) mismatches are worked around
+/// instead: a mismatched field is dropped to `null`, and when that isn't
+/// enough the list item containing it is skipped. Each repair is warned about.
+pub fn decode<T: DeserializeOwned>(
+    endpoint: &str,
+    body: &str,
+    lenient: bool,
+) -> Result<T, ApiError> {
+    let mut value: Value = serde_json::from_str(body)
+        .map_err(|e| ApiError::DecodeError(format!("{endpoint}: response is not JSON: {e}")))?;
+
+    let mut nulled: Vec<String> = Vec::new();
+    for _ in 0..MAX_REPAIRS {
+        let err = match serde_path_to_error::deserialize::<_, T>(value.clone()) {
+            Ok(decoded) => return Ok(decoded),
+            Err(err) => err,
+        };
+        let path = err.path().to_string();
+        let mismatch = format!("{endpoint}: unexpected response at {path}: {}", err.inner());
+        if !lenient {
+            return Err(ApiError::DecodeError(format!(
+                "{mismatch}. Run with --ignore-schema-errors to use what can be read"
+            )));
+        }
+
+        let segments: Vec<&Segment> = err.path().iter().collect();
+        match repair(&mut value, &segments, &path, &mut nulled) {
+            Some(repaired) => {
+                diagnostics::warn(&format!("{endpoint}: {repaired} ({})", err.inner()))
+            }
+            None => return Err(ApiError::DecodeError(mismatch)),
+        }
+    }
+
+    Err(ApiError::DecodeError(format!(
+        "{endpoint}: too many unexpected fields in the response"
+    )))
+}
+
+/// Works around one mismatch, describing what was given up, or None when
+/// nothing can be dropped without losing the whole response.
+fn repair(
+    value: &mut Value,
+    segments: &[&Segment],
+    path: &str,
+    nulled: &mut Vec<String>,
+) -> Option<String> {
+    // First try without just this field, which is enough for optional ones
+    if let Some(Segment::Map { .. }) = segments.last() {
+        if !nulled.iter().any(|p| p == path) {
+            if let Some(field) = lookup(value, segments) {
+                if !field.is_null() {
+                    *field = Value::Null;
+                    nulled.push(path.to_string());
+                    return Some(format!("ignored {path}"));
+                }
+            }
+        }
+    }
+
+    // Otherwise skip the closest list item containing the mismatch
+    let item = segments
+        .iter()
+        .rposition(|s| matches!(s, Segment::Seq { .. }))?;
+    let Segment::Seq { index } = segments[item] else {
+        return None;
+    };
+    let list = lookup(value, &segments[..item])?.as_array_mut()?;
+    if *index >= list.len() {
+        return None;
+    }
+    list.remove(*index);
+    // Later items shift down, so repairs recorded under this list no longer apply
+    nulled.clear();
+    Some(format!("skipped item {}", display_path(&segments[..=item])))
+}
+
+fn lookup<'a>(value: &'a mut Value, segments: &[&Segment]) -> Option<&'a mut Value> {
+    segments
+        .iter()
+        .try_fold(value, |current, segment| match segment {
+            Segment::Seq { index } => current.get_mut(*index),
+            Segment::Map { key } => current.get_mut(key.as_str()),
+            Segment::Enum { .. } => Some(current),
+            Segment::Unknown => None,
+        })
+}
+
+fn display_path(segments: &[&Segment]) -> String {
+    let mut out = String::new();
+    for segment in segments {
+        match segment {
+            Segment::Seq { index } => out.push_str(&format!("[{index}]")),
+            Segment::Map { key } => {
+                if !out.is_empty() {
+                    out.push('.');
+                }
+                out.push_str(key);
+            }
+            Segment::Enum { .. } | Segment::Unknown => {}
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize)]
+    struct Project {
+        name: String,
+        #[serde(default)]
+        color: Option<String>,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct Projects {
+        data: Vec<Project>,
+    }
+
+    const BODY: &str = r##"{"data": [
+        {"name": "API", "color": 3},
+        {"name": null},
+        {"name": "Web", "color": "#fff"}
+    ]}"##;
+
+    #[test]
+    fn test_decode_names_mismatched_field() {
+        let err = decode::<Projects>("GET api/v1/projects", BODY, false).unwrap_err();
+        let ApiError::DecodeError(message) = err else {
+            panic!("expected a decode error");
+        };
+        assert!(message.starts_with("GET api/v1/projects: unexpected response at data[0].color:"));
+        assert!(message.contains("invalid type: integer `3`, expected a string"));
+    }
+
+    #[test]
+    fn test_decode_leniently_keeps_what_it_can() {
+        let projects = decode::<Projects>("GET api/v1/projects", BODY, true).unwrap();
+        let names: Vec<&str> = projects.data.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["API", "Web"]);
+        assert_eq!(projects.data[0].color, None);
+        assert_eq!(projects.data[1].color.as_deref(), Some("#fff"));
+
+        // A mismatch outside any list can't be worked around
+        assert!(decode::<Projects>("GET x", r#"{"data": 1}"#, true).is_err());
+    }
+}
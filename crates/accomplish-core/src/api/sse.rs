@@ -0,0 +1,228 @@
+use std::time::Duration;
+
+/// One event from a `text/event-stream`, after its fields were gathered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SseMessage {
+    /// The `event:` field, `message` when the server didn't name one
+    pub event: String,
+    /// Every `data:` line of the event, joined with newlines
+    pub data: String,
+    /// The last event ID seen so far, including from earlier events
+    pub id: Option<String>,
+}
+
+/// Decodes a `text/event-stream` fed to it in arbitrary chunks, as they come
+/// off the network. Lines and UTF-8 characters split across chunks are put
+/// back together, and an event is only handed out once the blank line ending
+/// it has arrived.
+///
+/// Follows the HTML event stream format: lines end in `\n`, `\r\n` or `\r`;
+/// lines starting with `:` are comments (keep-alives); `data:` lines
+/// accumulate, and `event:`, `id:` and `retry:` set the event type, the
+/// last event ID and the reconnection delay.
+#[derive(Debug, Default)]
+pub struct SseDecoder {
+    /// Bytes of a line whose end hasn't arrived yet
+    pending: Vec<u8>,
+    /// A chunk ended in `\r`, so a `\n` starting the next one belongs to it
+    after_cr: bool,
+    event: Option<String>,
+    data: Option<String>,
+    last_event_id: Option<String>,
+    retry: Option<Duration>,
+}
+
+impl SseDecoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reads the next chunk of the stream, returning the events it completed.
+    pub fn feed(&mut self, chunk: &[u8]) -> Vec<SseMessage> {
+        let mut messages = Vec::new();
+        let mut rest = chunk;
+        if self.after_cr {
+            self.after_cr = false;
+            if let Some(after) = rest.strip_prefix(b"\n") {
+                rest = after;
+            }
+        }
+
+        while let Some(end) = rest.iter().position(|&b| b == b'\n' || b == b'\r') {
+            self.pending.extend_from_slice(&rest[..end]);
+            let line = std::mem::take(&mut self.pending);
+            if let Some(message) = self.process_line(&String::from_utf8_lossy(&line)) {
+                messages.push(message);
+            }
+
+            let is_cr = rest[end] == b'\r';
+            rest = &rest[end + 1..];
+            if is_cr {
+                match rest.first() {
+                    Some(b'\n') => rest = &rest[1..],
+                    Some(_) => {}
+                    None => self.after_cr = true,
+                }
+            }
+        }
+        self.pending.extend_from_slice(rest);
+        messages
+    }
+
+    /// Ends the stream. An event missing only its closing blank line is still
+    /// handed out, since some servers close the connection right after the
+    /// last `data:` line. A line cut off mid-way is dropped.
+    pub fn finish(&mut self) -> Option<SseMessage> {
+        self.pending.clear();
+        self.after_cr = false;
+        self.dispatch()
+    }
+
+    /// The ID of the last event with an `id:` field, for `Last-Event-ID`.
+    pub fn last_event_id(&self) -> Option<&str> {
+        self.last_event_id.as_deref()
+    }
+
+    /// How long the server asked clients to wait before reconnecting.
+    pub fn retry(&self) -> Option<Duration> {
+        self.retry
+    }
+
+    fn process_line(&mut self, line: &str) -> Option<SseMessage> {
+        if line.is_empty() {
+            return self.dispatch();
+        }
+        if line.starts_with(':') {
+            return None;
+        }
+
+        let (field, value) = match line.split_once(':') {
+            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+            None => (line, ""),
+        };
+        match field {
+            "data" => {
+                // Each line is followed by a newline, the last one removed on dispatch
+                let data = self.data.get_or_insert_with(String::new);
+                data.push_str(value);
+                data.push('\n');
+            }
+            "event" => self.event = Some(value.to_string()),
+            // IDs with NUL are ignored, as browsers do
+            "id" if !value.contains('\0') => self.last_event_id = Some(value.to_string()),
+            "retry" => {
+                if let Ok(ms) = value.parse::<u64>() {
+                    self.retry = Some(Duration::from_millis(ms));
+                }
+            }
+            _ => {}
+        }
+        None
+    }
+
+    /// Hands out the event gathered so far, if it had any data.
+    fn dispatch(&mut self) -> Option<SseMessage> {
+        let event = self.event.take();
+        let mut data = self.data.take()?;
+        data.pop();
+        Some(SseMessage {
+            event: event
+                .filter(|e| !e.is_empty())
+                .unwrap_or_else(|| "message".to_string()),
+            data,
+            id: self.last_event_id.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(data: &str, id: Option<&str>) -> SseMessage {
+        SseMessage {
+            event: "message".to_string(),
+            data: data.to_string(),
+            id: id.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_events_split_across_chunks() {
+        let stream = "data: {\"status\":\"processing\"}\n\ndata: {\"status\":\"completed\"}\n\n";
+
+        // Every possible split point gives the same two events
+        for split in 0..=stream.len() {
+            let mut decoder = SseDecoder::new();
+            let mut messages = decoder.feed(&stream.as_bytes()[..split]);
+            messages.extend(decoder.feed(&stream.as_bytes()[split..]));
+            assert_eq!(
+                messages,
+                vec![
+                    message("{\"status\":\"processing\"}", None),
+                    message("{\"status\":\"completed\"}", None),
+                ],
+                "split at {split}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_multibyte_characters_split_across_chunks() {
+        let stream = "data: résumé ✨\n\n".as_bytes();
+        let mut decoder = SseDecoder::new();
+        let mut messages = Vec::new();
+        for byte in stream {
+            messages.extend(decoder.feed(std::slice::from_ref(byte)));
+        }
+        assert_eq!(messages, vec![message("résumé ✨", None)]);
+    }
+
+    #[test]
+    fn test_fields_comments_and_line_endings() {
+        let mut decoder = SseDecoder::new();
+        let messages = decoder.feed(
+            b": keep-alive\r\n\
+              retry: 3000\r\n\
+              id: 7\r\n\
+              event: progress\r\n\
+              data: first\r\n\
+              data:second\r\n\r\n\
+              :ping\r\r\
+              data: third\r\r",
+        );
+        assert_eq!(
+            messages,
+            vec![
+                SseMessage {
+                    event: "progress".to_string(),
+                    data: "first\nsecond".to_string(),
+                    id: Some("7".to_string()),
+                },
+                // The ID carries over, the event type doesn't
+                message("third", Some("7")),
+            ]
+        );
+        assert_eq!(decoder.last_event_id(), Some("7"));
+        assert_eq!(decoder.retry(), Some(Duration::from_secs(3)));
+
+        // A \r\n split between chunks is one line ending, not two
+        let mut decoder = SseDecoder::new();
+        assert!(decoder.feed(b"data: x\r").is_empty());
+        assert!(decoder.feed(b"\n").is_empty());
+        assert_eq!(decoder.feed(b"\r\n"), vec![message("x", None)]);
+    }
+
+    #[test]
+    fn test_finish_hands_out_an_unterminated_event() {
+        let mut decoder = SseDecoder::new();
+        assert!(decoder.feed(b"data: done\ndata: cut off mid-li").is_empty());
+        assert_eq!(decoder.finish(), Some(message("done", None)));
+        assert_eq!(decoder.finish(), None);
+
+        // Comments and bare fields alone never make an event
+        let mut decoder = SseDecoder::new();
+        assert!(decoder.feed(b": hi\n\nevent: ping\n\n").is_empty());
+        assert_eq!(decoder.finish(), None);
+    }
+}
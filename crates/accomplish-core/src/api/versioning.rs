@@ -0,0 +1,210 @@
+use crate::diagnostics;
+use chrono::{DateTime, Utc};
+use reqwest::header::{HeaderMap, HeaderValue};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// API version this CLI is built against, sent in every `Accept` header.
+pub const API_VERSION: &str = "1";
+
+static DEPRECATION_WARNED: AtomicBool = AtomicBool::new(false);
+
+/// `Accept` header value requesting JSON of the supported API version.
+pub fn accept_json() -> HeaderValue {
+    accept("application/json")
+}
+
+/// `Accept` header value requesting an event stream of the supported API version.
+pub fn accept_event_stream() -> HeaderValue {
+    accept("text/event-stream")
+}
+
+fn accept(media_type: &str) -> HeaderValue {
+    HeaderValue::from_str(&format!("{media_type}; version={API_VERSION}"))
+        .expect("Accept header value is valid")
+}
+
+/// What the server told us about the retirement of the API version we use,
+/// from the `Deprecation` (RFC 9745), `Sunset` (RFC 8594) and `Link` headers.
+#[derive(Debug, Default, PartialEq)]
+pub struct DeprecationNotice {
+    pub deprecated_at: Option<DateTime<Utc>>,
+    pub sunset_at: Option<DateTime<Utc>>,
+    pub link: Option<String>,
+}
+
+impl DeprecationNotice {
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let header = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(str::trim)
+        };
+
+        let deprecation = header("deprecation");
+        let sunset = header("sunset");
+        if deprecation.is_none() && sunset.is_none() {
+            return None;
+        }
+
+        let deprecated_at = deprecation.and_then(parse_deprecation_date);
+        let sunset_at = sunset.and_then(parse_http_date);
+        let link = headers
+            .get_all("link")
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .flat_map(|v| v.split(','))
+            .find_map(deprecation_link);
+
+        Some(Self {
+            deprecated_at,
+            sunset_at,
+            link,
+        })
+    }
+
+    pub fn message(&self) -> String {
+        let mut message = format!(
+            "This version of acc ({}) uses Accomplish API version {API_VERSION}, which is deprecated",
+            env!("CARGO_PKG_VERSION")
+        );
+        if let Some(deprecated) = self.deprecated_at {
+            message.push_str(&format!(" since {}", deprecated.format("%Y-%m-%d")));
+        }
+        if let Some(sunset) = self.sunset_at {
+            message.push_str(&format!(
+                " and will stop working on {}",
+                sunset.format("%Y-%m-%d")
+            ));
+        }
+        message.push_str(". Please upgrade the CLI.");
+        if let Some(link) = &self.link {
+            message.push_str(&format!(" See {link}"));
+        }
+        message
+    }
+}
+
+/// Reports a deprecation warning, at most once per run.
+pub fn warn_if_deprecated(headers: &HeaderMap) {
+    let Some(notice) = DeprecationNotice::from_headers(headers) else {
+        return;
+    };
+    if !DEPRECATION_WARNED.swap(true, Ordering::Relaxed) {
+        diagnostics::warn(&notice.message());
+    }
+}
+
+/// `Deprecation` is `@<unix seconds>` (RFC 9745), or `true`/an HTTP-date in older drafts.
+fn parse_deprecation_date(value: &str) -> Option<DateTime<Utc>> {
+    match value.strip_prefix('@') {
+        Some(seconds) => DateTime::from_timestamp(seconds.parse().ok()?, 0),
+        None => parse_http_date(value),
+    }
+}
+
+fn parse_http_date(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc2822(value)
+        .ok()
+        .map(|d| d.with_timezone(&Utc))
+}
+
+/// Extracts the URL from a `<url>; rel="deprecation"` (or `rel="sunset"`) link.
+fn deprecation_link(link: &str) -> Option<String> {
+    let (url, params) = link.trim().split_once(';')?;
+    let is_deprecation = params.split(';').any(|param| {
+        let param = param.trim().replace('"', "");
+        param == "rel=deprecation" || param == "rel=sunset"
+    });
+    is_deprecation.then(|| {
+        url.trim()
+            .trim_start_matches('<')
+            .trim_end_matches('>')
+            .to_string()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::client::ApiClient;
+
+    fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for (name, value) in pairs {
+            map.append(*name, HeaderValue::from_static(value));
+        }
+        map
+    }
+
+    #[test]
+    fn test_accept_headers_carry_api_version() {
+        assert!(accept_json()
+            .to_str()
+            .unwrap()
+            .ends_with(&format!("version={API_VERSION}")));
+        assert!(accept_event_stream()
+            .to_str()
+            .unwrap()
+            .ends_with(&format!("version={API_VERSION}")));
+    }
+
+    #[tokio::test]
+    async fn test_api_client_sends_versioned_accept_header() {
+        let mut server = mockito::Server::new_async().await;
+        let m = server
+            .mock("GET", "/api/v1/projects")
+            .match_header("accept", "application/json; version=1")
+            .with_status(200)
+            .with_header("deprecation", "true")
+            .with_body(r#"{"projects":[]}"#)
+            .create();
+
+        let mut api_client = ApiClient::new(&server.url());
+        api_client.set_access_token("dummy-token".into());
+        let _: serde_json::Value = api_client.get("api/v1/projects", true).await.unwrap();
+        m.assert();
+    }
+
+    #[test]
+    fn test_no_notice_without_headers() {
+        assert_eq!(DeprecationNotice::from_headers(&HeaderMap::new()), None);
+    }
+
+    #[test]
+    fn test_notice_from_headers() {
+        let notice = DeprecationNotice::from_headers(&headers(&[
+            ("deprecation", "@1735689600"),
+            ("sunset", "Sat, 01 Nov 2025 00:00:00 GMT"),
+            (
+                "link",
+                "<https://accomplish.dev/changelog>; rel=\"deprecation\"; type=\"text/html\"",
+            ),
+        ]))
+        .unwrap();
+
+        assert_eq!(
+            notice.deprecated_at.unwrap().format("%Y-%m-%d").to_string(),
+            "2025-01-01"
+        );
+        assert_eq!(
+            notice.sunset_at.unwrap().format("%Y-%m-%d").to_string(),
+            "2025-11-01"
+        );
+        assert_eq!(
+            notice.link.as_deref(),
+            Some("https://accomplish.dev/changelog")
+        );
+        assert!(notice
+            .message()
+            .contains("deprecated since 2025-01-01 and will stop working on 2025-11-01"));
+    }
+
+    #[test]
+    fn test_notice_with_legacy_deprecation_value() {
+        let notice = DeprecationNotice::from_headers(&headers(&[("deprecation", "true")])).unwrap();
+        assert_eq!(notice.deprecated_at, None);
+        assert_eq!(notice.sunset_at, None);
+        assert!(notice.message().ends_with("Please upgrade the CLI."));
+    }
+}
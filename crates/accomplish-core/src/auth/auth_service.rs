@@ -0,0 +1,271 @@
+use crate::api::cassette::Cassette;
+use crate::api::client::ApiClient;
+use crate::api::endpoints::{check_token_info, refresh_access_token};
+use crate::api::errors::ApiError;
+use crate::api::models::TokenResponse;
+use crate::errors::{AppError, UnauthenticatedError};
+use crate::storage::{
+    CredentialBackend, CredentialStore, RefreshState, ACCESS_TOKEN_NAME, REFRESH_STATE_NAME,
+};
+use chrono::{DateTime, Duration, Utc};
+use std::path::{Path, PathBuf};
+
+/// Access tokens this close to expiring are refreshed before use.
+const REFRESH_MARGIN_SECS: i64 = 60;
+
+pub struct AuthService {
+    api_client: ApiClient,
+    access_token: Option<String>,
+    state_dir: PathBuf,
+    store: Box<dyn CredentialStore>,
+    token_expires_at: Option<DateTime<Utc>>,
+    refresh: Option<RefreshState>,
+}
+
+impl AuthService {
+    /// Initialize with tokens in plaintext files in `<credentials_dir>/<profile>`.
+    /// Tests use this so they never touch the OS keychain.
+    pub fn new(api_base: String, credentials_dir: PathBuf, profile: &str) -> Self {
+        Self::with_backend(api_base, credentials_dir, profile, CredentialBackend::File)
+    }
+
+    /// Initialize with tokens kept by `backend`; other per-profile state stays
+    /// in `<credentials_dir>/<profile>`.
+    pub fn with_backend(
+        api_base: String,
+        mut credentials_dir: PathBuf,
+        profile: &str,
+        backend: CredentialBackend,
+    ) -> Self {
+        credentials_dir.push(profile);
+        let store = backend.open(profile, credentials_dir.clone());
+        let access_token = store.load(ACCESS_TOKEN_NAME).unwrap_or(None);
+        let refresh = RefreshState::load(store.as_ref());
+
+        let mut api_client = ApiClient::new(&api_base);
+        if let Some(ref t) = access_token {
+            api_client.set_access_token(t.clone());
+        }
+
+        AuthService {
+            api_client,
+            access_token,
+            state_dir: credentials_dir,
+            store,
+            token_expires_at: refresh.as_ref().and_then(|r| r.expires_at),
+            refresh,
+        }
+    }
+
+    pub fn api_client(&self) -> &ApiClient {
+        &self.api_client
+    }
+
+    /// Sends API traffic through a cassette. Replayed requests never reach
+    /// the server, so a stand-in token is used when none is stored.
+    pub fn use_cassette(&mut self, cassette: Cassette) {
+        if cassette.is_replaying() && self.access_token.is_none() {
+            self.access_token = Some("replay".to_string());
+            self.api_client.set_access_token("replay".to_string());
+        }
+        self.api_client.set_cassette(cassette);
+    }
+
+    /// The stored access token and when it expires, as handed to plugins.
+    pub fn access_token(&self) -> Option<(&str, Option<DateTime<Utc>>)> {
+        self.access_token
+            .as_deref()
+            .map(|token| (token, self.token_expires_at))
+    }
+
+    /// Per-profile directory (`<credentials_dir>/<profile>`) for local state files.
+    pub fn state_dir(&self) -> &Path {
+        &self.state_dir
+    }
+
+    /// Validate token, refreshing it when it has expired and a refresh token
+    /// is stored; clear it when neither works.
+    pub async fn ensure_authenticated(&mut self) -> Result<(), AppError> {
+        let expiring = self.token_expires_at.is_some_and(|at| {
+            at - self.api_client.clock().now() < Duration::seconds(REFRESH_MARGIN_SECS)
+        });
+        if (self.access_token.is_none() || expiring) && self.refresh.is_some() {
+            return self.refresh().await;
+        }
+
+        let Some(token) = &self.access_token else {
+            return Err(AppError::Auth(UnauthenticatedError));
+        };
+        match check_token_info(self.api_client(), token).await {
+            Ok(r) if r.active => {
+                self.token_expires_at = i64::try_from(r.exp)
+                    .ok()
+                    .filter(|exp| *exp > 0)
+                    .and_then(|exp| DateTime::from_timestamp(exp, 0));
+                Ok(())
+            }
+            Ok(_) | Err(ApiError::Unauthorized(_)) if self.refresh.is_some() => {
+                self.refresh().await
+            }
+            Ok(_) | Err(ApiError::Unauthorized(_)) => {
+                self.clear_tokens();
+                Err(AppError::Auth(UnauthenticatedError))
+            }
+            Err(e) => Err(AppError::Api(e)),
+        }
+    }
+
+    /// Trades the stored refresh token for a new access token. A rejected
+    /// refresh token means logging in again, so everything is cleared.
+    async fn refresh(&mut self) -> Result<(), AppError> {
+        let Some(state) = &self.refresh else {
+            return Err(AppError::Auth(UnauthenticatedError));
+        };
+        match refresh_access_token(self.api_client(), &state.refresh_token).await {
+            Ok(tokens) => self.save_tokens(&tokens),
+            Err(ApiError::Unauthorized(_)) | Err(ApiError::BadRequest(_)) => {
+                self.clear_tokens();
+                Err(AppError::Auth(UnauthenticatedError))
+            }
+            Err(e) => Err(AppError::Api(e)),
+        }
+    }
+
+    /// Time left on the access token, measured against the server clock when
+    /// clock skew adjustment is enabled. Known after `ensure_authenticated`.
+    pub fn token_expires_in(&self) -> Option<Duration> {
+        self.token_expires_at
+            .map(|expires_at| expires_at - self.api_client.clock().now())
+    }
+
+    /// Remove tokens from memory, disk, and client.
+    pub fn clear_tokens(&mut self) {
+        self.access_token = None;
+        self.token_expires_at = None;
+        self.refresh = None;
+        let _ = self.store.clear(ACCESS_TOKEN_NAME);
+        let _ = self.store.clear(REFRESH_STATE_NAME);
+        self.api_client.set_access_token(String::new());
+    }
+
+    /// Persist new token and set it on the API client.
+    pub fn save_access_token(&mut self, token: &str) -> Result<(), AppError> {
+        self.store.save(ACCESS_TOKEN_NAME, token)?;
+        self.access_token = Some(token.to_string());
+        self.api_client.set_access_token(token.to_string());
+        Ok(())
+    }
+
+    /// Persist a token response: the access token, plus the refresh token (the
+    /// current one when the server doesn't rotate it) and the expiry.
+    pub fn save_tokens(&mut self, tokens: &TokenResponse) -> Result<(), AppError> {
+        self.save_access_token(&tokens.access_token)?;
+
+        let refresh_token = tokens
+            .refresh_token
+            .clone()
+            .filter(|t| !t.is_empty())
+            .or_else(|| self.refresh.take().map(|r| r.refresh_token));
+        let expires_at = i64::try_from(tokens.expires_in)
+            .ok()
+            .filter(|secs| *secs > 0)
+            .map(|secs| self.api_client.clock().now() + Duration::seconds(secs));
+        self.token_expires_at = expires_at;
+
+        match refresh_token {
+            Some(refresh_token) => {
+                let state = RefreshState {
+                    refresh_token,
+                    expires_at,
+                };
+                state.save(self.store.as_ref())?;
+                self.refresh = Some(state);
+            }
+            None => {
+                let _ = self.store.clear(REFRESH_STATE_NAME);
+                self.refresh = None;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::{Matcher, Server};
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn token_response(access: &str, refresh: Option<&str>) -> TokenResponse {
+        TokenResponse {
+            access_token: access.to_string(),
+            token_type: "Bearer".to_string(),
+            expires_in: 3600,
+            refresh_token: refresh.map(str::to_string),
+            scope: "read".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_expired_token_is_refreshed() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut server = Server::new_async().await;
+        let mut auth = AuthService::new(server.url(), temp_dir.path().to_path_buf(), "test");
+        auth.save_tokens(&token_response("old-token", Some("refresh-1")))
+            .unwrap();
+
+        let _inactive = server
+            .mock("POST", "/auth/token_info")
+            .with_status(401)
+            .with_body(r#"{"error":"invalid_token"}"#)
+            .create();
+        let refresh = server
+            .mock("POST", "/auth/token")
+            .match_body(Matcher::Json(
+                json!({ "grant_type": "refresh_token", "refresh_token": "refresh-1" }),
+            ))
+            .with_status(200)
+            .with_body(
+                json!({ "access_token": "new-token", "token_type": "Bearer", "expires_in": 3600, "scope": "read" })
+                    .to_string(),
+            )
+            .create();
+
+        auth.ensure_authenticated().await.unwrap();
+        refresh.assert();
+
+        // A new run picks up the new access token and keeps the refresh token
+        let reloaded = AuthService::new(server.url(), temp_dir.path().to_path_buf(), "test");
+        assert_eq!(reloaded.access_token.as_deref(), Some("new-token"));
+        assert_eq!(
+            reloaded.refresh.map(|r| r.refresh_token).as_deref(),
+            Some("refresh-1")
+        );
+        assert!(reloaded.token_expires_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_rejected_refresh_token_logs_out() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut server = Server::new_async().await;
+        let mut auth = AuthService::new(server.url(), temp_dir.path().to_path_buf(), "test");
+        auth.save_tokens(&token_response("old-token", Some("refresh-1")))
+            .unwrap();
+        // Pretend the access token ran out
+        auth.token_expires_at = Some(Utc::now() - Duration::minutes(5));
+
+        let _refresh = server
+            .mock("POST", "/auth/token")
+            .with_status(400)
+            .with_body(r#"{"error":"invalid_grant"}"#)
+            .create();
+
+        assert!(matches!(
+            auth.ensure_authenticated().await,
+            Err(AppError::Auth(_))
+        ));
+        assert!(auth.access_token.is_none());
+        assert!(!temp_dir.path().join("test").join("refresh.json").exists());
+    }
+}
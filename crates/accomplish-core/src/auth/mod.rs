@@ -0,0 +1,3 @@
+pub mod auth_service;
+
+pub use auth_service::AuthService;
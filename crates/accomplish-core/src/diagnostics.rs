@@ -0,0 +1,18 @@
+use std::sync::OnceLock;
+
+/// Receives warnings that don't fail a request.
+pub type WarningHandler = fn(&str);
+
+static WARNING_HANDLER: OnceLock<WarningHandler> = OnceLock::new();
+
+/// Sets where warnings go, once per process. Without a handler they are
+/// dropped. Returns false if a handler was already set.
+pub fn set_warning_handler(handler: WarningHandler) -> bool {
+    WARNING_HANDLER.set(handler).is_ok()
+}
+
+pub(crate) fn warn(message: &str) {
+    if let Some(handler) = WARNING_HANDLER.get() {
+        handler(message);
+    }
+}
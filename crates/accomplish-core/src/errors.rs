@@ -3,7 +3,7 @@ use config::ConfigError;
 use serde_json::Error as SerdeJsonError;
 use thiserror::Error;
 
-/// Central error type for the CLI.
+/// Central error type for the client library and the CLI.
 #[derive(Error, Debug)]
 pub enum AppError {
     #[error("Configuration error: {0}")]
@@ -31,6 +31,20 @@ pub enum AppError {
     Other(String),
 }
 
+impl AppError {
+    /// Whether the request never got an answer (no network, server unreachable),
+    /// as opposed to the server rejecting it.
+    pub fn is_offline(&self) -> bool {
+        matches!(self, AppError::Api(ApiError::Unreachable(_)))
+    }
+
+    /// Whether the request went out but no answer came in time, so it's
+    /// unknown whether the server acted on it.
+    pub fn is_timeout(&self) -> bool {
+        matches!(self, AppError::Api(ApiError::TimedOut(_)))
+    }
+}
+
 /// Error indicating the user is not authenticated.
 #[derive(Error, Debug)]
 #[error("User is not authenticated. Please log in.")]
@@ -0,0 +1,13 @@
+//! Client library for the Accomplish API, shared by the `acc` CLI and other
+//! Rust tools such as editor integrations and bots.
+//!
+//! Nothing here reads from or writes to the terminal. Warnings that don't
+//! fail a request, like a deprecated endpoint or a skewed clock, go to the
+//! handler set with [`diagnostics::set_warning_handler`].
+
+pub mod api;
+pub mod auth;
+pub mod diagnostics;
+pub mod errors;
+pub mod storage;
+pub mod user_agent;
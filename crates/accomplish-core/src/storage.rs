@@ -0,0 +1,247 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    io::{self, Write},
+    path::PathBuf,
+};
+
+/// What's kept next to the access token to renew it without logging in again.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RefreshState {
+    pub refresh_token: String,
+    /// When the access token expires, if the server said
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl RefreshState {
+    /// Reads the refresh state, returning None when it's missing or unreadable.
+    pub fn load(store: &dyn CredentialStore) -> Option<Self> {
+        let content = store.load(REFRESH_STATE_NAME).ok()??;
+        serde_json::from_str(&content).ok()
+    }
+
+    pub fn save(&self, store: &dyn CredentialStore) -> io::Result<()> {
+        store.save(REFRESH_STATE_NAME, &serde_json::to_string(self)?)
+    }
+}
+
+/// Name of the access token in a `CredentialStore`.
+pub const ACCESS_TOKEN_NAME: &str = "token";
+/// Name of the refresh state in a `CredentialStore`.
+pub const REFRESH_STATE_NAME: &str = "refresh.json";
+
+/// Service name the tokens are filed under in the OS keychain.
+const KEYCHAIN_SERVICE: &str = "accomplish-cli";
+
+/// Where a profile's secrets (access token and refresh state) are kept.
+pub trait CredentialStore: Send + Sync {
+    fn load(&self, name: &str) -> io::Result<Option<String>>;
+    fn save(&self, name: &str, secret: &str) -> io::Result<()>;
+    fn clear(&self, name: &str) -> io::Result<()>;
+}
+
+/// Plaintext files in the profile directory, readable only by the user on Unix.
+pub struct FileStore {
+    dir: PathBuf,
+}
+
+impl FileStore {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+}
+
+impl CredentialStore for FileStore {
+    fn load(&self, name: &str) -> io::Result<Option<String>> {
+        load_token(&self.dir.join(name))
+    }
+
+    fn save(&self, name: &str, secret: &str) -> io::Result<()> {
+        save_token(&self.dir.join(name), secret)
+    }
+
+    fn clear(&self, name: &str) -> io::Result<()> {
+        clear_token(&self.dir.join(name))
+    }
+}
+
+/// The OS keychain: Keychain on macOS, Credential Manager on Windows and the
+/// Secret Service (GNOME Keyring, KWallet) on Linux. Secrets still in plaintext
+/// files from before are moved into the keychain the first time they're read.
+pub struct KeychainStore {
+    profile: String,
+    legacy: FileStore,
+}
+
+impl KeychainStore {
+    pub fn new(profile: &str, dir: PathBuf) -> Self {
+        Self {
+            profile: profile.to_string(),
+            legacy: FileStore::new(dir),
+        }
+    }
+
+    /// Whether a keychain can be reached, e.g. not on a headless Linux box
+    /// without a Secret Service.
+    pub fn is_available(&self) -> bool {
+        self.keychain(ACCESS_TOKEN_NAME, |entry| match entry.get_password() {
+            Ok(_) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(e) => Err(e),
+        })
+        .is_ok()
+    }
+
+    /// Runs a keychain call on its own thread, since some backends block on
+    /// an executor of their own that can't run inside the CLI's runtime.
+    fn keychain<T: Send>(
+        &self,
+        name: &str,
+        call: impl FnOnce(&keyring::Entry) -> keyring::Result<T> + Send,
+    ) -> io::Result<T> {
+        let account = format!("{}/{name}", self.profile);
+        std::thread::scope(|scope| {
+            scope
+                .spawn(|| call(&keyring::Entry::new(KEYCHAIN_SERVICE, &account)?))
+                .join()
+                .unwrap_or_else(|_| {
+                    Err(keyring::Error::Invalid(
+                        "keychain".into(),
+                        "panicked".into(),
+                    ))
+                })
+        })
+        .map_err(|e| io::Error::other(format!("OS keychain: {e}")))
+    }
+}
+
+impl CredentialStore for KeychainStore {
+    fn load(&self, name: &str) -> io::Result<Option<String>> {
+        let stored = self.keychain(name, |entry| match entry.get_password() {
+            Ok(secret) => Ok(Some(secret)),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(e),
+        })?;
+        if stored.is_some() {
+            return Ok(stored);
+        }
+
+        let Some(legacy) = self.legacy.load(name)? else {
+            return Ok(None);
+        };
+        self.save(name, &legacy)?;
+        Ok(Some(legacy))
+    }
+
+    fn save(&self, name: &str, secret: &str) -> io::Result<()> {
+        self.keychain(name, |entry| entry.set_password(secret))?;
+        self.legacy.clear(name)
+    }
+
+    fn clear(&self, name: &str) -> io::Result<()> {
+        self.keychain(name, |entry| match entry.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(e) => Err(e),
+        })?;
+        self.legacy.clear(name)
+    }
+}
+
+/// Which `CredentialStore` keeps the tokens, from `credential_store` in the config.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CredentialBackend {
+    /// The OS keychain when there is one, plaintext files otherwise
+    #[default]
+    Auto,
+    Keychain,
+    File,
+}
+
+impl CredentialBackend {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "auto" => Some(Self::Auto),
+            "keychain" => Some(Self::Keychain),
+            "file" => Some(Self::File),
+            _ => None,
+        }
+    }
+
+    /// Opens the store for a profile whose files live in `dir`.
+    pub fn open(self, profile: &str, dir: PathBuf) -> Box<dyn CredentialStore> {
+        match self {
+            Self::File => Box::new(FileStore::new(dir)),
+            Self::Keychain => Box::new(KeychainStore::new(profile, dir)),
+            Self::Auto => {
+                let keychain = KeychainStore::new(profile, dir.clone());
+                if keychain.is_available() {
+                    Box::new(keychain)
+                } else {
+                    Box::new(FileStore::new(dir))
+                }
+            }
+        }
+    }
+}
+
+/// Reads the token file if it exists, returning Ok(Some(token)) or Ok(None).
+pub fn load_token(path: &PathBuf) -> io::Result<Option<String>> {
+    if path.exists() {
+        let token = fs::read_to_string(path)?.trim().to_string();
+        Ok(Some(token))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Writes `token` to the file, creating parent dirs and setting 0o600 perms on Unix.
+pub fn save_token(path: &PathBuf, token: &str) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut file = fs::File::create(path)?;
+    file.write_all(token.as_bytes())?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mut perms = file.metadata()?.permissions();
+        perms.set_mode(0o600);
+        fs::set_permissions(path, perms)?;
+    }
+    Ok(())
+}
+
+/// Deletes the token file if it exists.
+pub fn clear_token(path: &PathBuf) -> io::Result<()> {
+    if path.exists() {
+        fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_refresh_state_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = FileStore::new(temp_dir.path().join("profile"));
+        let path = temp_dir.path().join("profile").join("refresh.json");
+        assert_eq!(RefreshState::load(&store), None);
+
+        let state = RefreshState {
+            refresh_token: "refresh-123".to_string(),
+            expires_at: DateTime::from_timestamp(1_750_000_000, 0),
+        };
+        state.save(&store).unwrap();
+        assert!(path.exists());
+        assert_eq!(RefreshState::load(&store), Some(state));
+
+        store.clear(REFRESH_STATE_NAME).unwrap();
+        assert_eq!(RefreshState::load(&store), None);
+    }
+}
@@ -1,4 +0,0 @@
-pub mod client;
-pub mod endpoints;
-pub mod errors;
-pub mod models;
@@ -1,4 +1,3 @@
-pub mod auth_service;
 pub mod callback_server;
 
-pub use auth_service::AuthService;
+pub use accomplish_core::auth::AuthService;
@@ -0,0 +1,147 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+pub const CAPTURE_STATE_FILE: &str = "capture_state.json";
+
+/// Local record of commit SHAs known to be captured, keyed by repository ID.
+///
+/// Captured commits never become uncaptured again, so anything recorded here can be
+/// skipped when asking the backend which commits are still uncaptured.
+#[derive(Debug, Default)]
+pub struct CaptureState {
+    path: PathBuf,
+    repos: HashMap<String, BTreeSet<String>>,
+    directories: HashMap<String, String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CaptureStateFile {
+    #[serde(default)]
+    repositories: HashMap<String, BTreeSet<String>>,
+    /// Repository ID of each local clone captured from, so the uncaptured
+    /// count can be worked out without asking the server
+    #[serde(default)]
+    directories: HashMap<String, String>,
+}
+
+impl CaptureState {
+    /// Loads the state from `<state_dir>/capture_state.json`, starting empty if the
+    /// file is missing or unreadable.
+    pub fn load(state_dir: &Path) -> Self {
+        let path = state_dir.join(CAPTURE_STATE_FILE);
+        let file = fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<CaptureStateFile>(&content).ok())
+            .unwrap_or_default();
+
+        Self {
+            path,
+            repos: file.repositories,
+            directories: file.directories,
+        }
+    }
+
+    pub fn is_captured(&self, repo_id: &str, sha: &str) -> bool {
+        self.repos
+            .get(repo_id)
+            .map(|shas| shas.contains(sha))
+            .unwrap_or(false)
+    }
+
+    /// Returns the SHAs that are not yet known to be captured, preserving order.
+    pub fn unknown_shas(&self, repo_id: &str, shas: &[String]) -> Vec<String> {
+        shas.iter()
+            .filter(|sha| !self.is_captured(repo_id, sha))
+            .cloned()
+            .collect()
+    }
+
+    pub fn mark_captured<I>(&mut self, repo_id: &str, shas: I)
+    where
+        I: IntoIterator<Item = String>,
+    {
+        self.repos
+            .entry(repo_id.to_string())
+            .or_default()
+            .extend(shas);
+    }
+
+    /// Remembers which repository a local clone belongs to.
+    pub fn set_repo_for_dir(&mut self, dir: &Path, repo_id: &str) {
+        self.directories
+            .insert(dir.to_string_lossy().to_string(), repo_id.to_string());
+    }
+
+    pub fn repo_for_dir(&self, dir: &Path) -> Option<&str> {
+        self.directories
+            .get(dir.to_string_lossy().as_ref())
+            .map(String::as_str)
+    }
+
+    /// Forgets everything cached for a repository.
+    pub fn clear_repo(&mut self, repo_id: &str) {
+        self.repos.remove(repo_id);
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let file = CaptureStateFile {
+            repositories: self.repos.clone(),
+            directories: self.directories.clone(),
+        };
+        let content = serde_json::to_string_pretty(&file)?;
+        fs::write(&self.path, content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_load_missing_file_is_empty() {
+        let temp_dir = TempDir::new().unwrap();
+        let state = CaptureState::load(temp_dir.path());
+        assert!(!state.is_captured("repo-1", "abc"));
+    }
+
+    #[test]
+    fn test_mark_save_and_reload() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut state = CaptureState::load(temp_dir.path());
+        state.mark_captured("repo-1", vec!["abc".to_string(), "def".to_string()]);
+        state.set_repo_for_dir(Path::new("/src/web"), "repo-1");
+        state.save().unwrap();
+
+        let reloaded = CaptureState::load(temp_dir.path());
+        assert!(reloaded.is_captured("repo-1", "abc"));
+        assert_eq!(reloaded.repo_for_dir(Path::new("/src/web")), Some("repo-1"));
+        assert!(!reloaded.is_captured("repo-2", "abc"));
+
+        let shas = vec!["abc".to_string(), "ghi".to_string(), "def".to_string()];
+        assert_eq!(reloaded.unknown_shas("repo-1", &shas), vec!["ghi"]);
+    }
+
+    #[test]
+    fn test_clear_repo() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut state = CaptureState::load(temp_dir.path());
+        state.mark_captured("repo-1", vec!["abc".to_string()]);
+        state.clear_repo("repo-1");
+        assert!(!state.is_captured("repo-1", "abc"));
+    }
+
+    #[test]
+    fn test_corrupt_file_is_ignored() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join(CAPTURE_STATE_FILE), "not json").unwrap();
+        let state = CaptureState::load(temp_dir.path());
+        assert!(!state.is_captured("repo-1", "abc"));
+    }
+}
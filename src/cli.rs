@@ -1,4 +1,12 @@
-use clap::{Parser, Subcommand};
+use crate::commands::cache::CacheKind;
+use crate::commands::completions::CompletionValues;
+use crate::commands::prompt::DEFAULT_PROMPT_FORMAT;
+use crate::commands::recap::RecapFormat;
+use crate::utils::commit_message::MessageStyle;
+use crate::utils::visibility::Visibility;
+use clap::{Parser, Subcommand, ValueEnum};
+use clap_complete::Shell;
+use std::path::PathBuf;
 
 #[derive(Parser)]
 #[command(
@@ -10,6 +18,36 @@ use clap::{Parser, Subcommand};
 pub struct Cli {
     #[command(subcommand)]
     pub command: Commands,
+
+    /// If another interactive command is running for this profile, wait for it to finish
+    #[arg(long, global = true, conflicts_with = "no_wait")]
+    pub wait: bool,
+
+    /// If another interactive command is running for this profile, fail immediately (default)
+    #[arg(long, global = true)]
+    pub no_wait: bool,
+
+    /// Take the default answer for prompts that have one instead of asking
+    #[arg(long, global = true)]
+    pub defaults: bool,
+
+    /// Disable colored output and syntax highlighting (also honors NO_COLOR)
+    #[arg(long, global = true)]
+    pub no_color: bool,
+
+    /// Use what can be read from API responses that don't match the expected
+    /// shape, skipping mismatched fields and items, instead of failing
+    #[arg(long, global = true)]
+    pub ignore_schema_errors: bool,
+
+    /// Fail on the first server error or dropped connection instead of retrying
+    #[arg(long, global = true)]
+    pub no_retry: bool,
+
+    /// Use this profile from ~/.accomplish/config.toml for this run (overrides
+    /// ACCOMPLISH_ENV and `acc profile use`)
+    #[arg(long, global = true, value_name = "NAME")]
+    pub profile: Option<String>,
 }
 
 #[derive(Subcommand)]
@@ -18,21 +56,80 @@ pub enum Commands {
     Version,
 
     /// Log in to your account
-    Login,
+    Login {
+        /// Continue an interrupted login with the code it already issued
+        #[arg(long)]
+        resume: bool,
+
+        /// Also show the verification link as a QR code (automatic without a browser)
+        #[arg(long)]
+        qr: bool,
+    },
 
     /// Log out from your account
     Logout,
 
     /// Check the current authentication status
-    Status,
+    Status {
+        /// Keep refreshing API latency and auth status until Ctrl-C
+        #[arg(long)]
+        watch: bool,
+
+        /// Seconds between refreshes with --watch
+        #[arg(long, default_value_t = 5, requires = "watch")]
+        interval: u64,
+    },
+
+    /// Make an authenticated request to the Accomplish API and print the JSON response
+    Api {
+        /// HTTP method (GET, POST, PUT, PATCH or DELETE)
+        method: String,
+
+        /// API path relative to the configured base URL, e.g. api/v1/projects
+        endpoint: String,
+
+        /// Request parameter (can be specified multiple times); sent as query
+        /// parameters for GET and DELETE, as a JSON body otherwise
+        #[arg(short = 'f', long = "field", value_name = "KEY=VALUE")]
+        fields: Vec<String>,
+
+        /// Follow `meta.end_cursor` and print the list from every page
+        #[arg(long)]
+        paginate: bool,
+    },
 
     /// Initialize a project in the current directory
-    Init,
+    Init {
+        /// Find git repositories under this directory and track them in bulk
+        #[arg(long = "scan", value_name = "ROOT")]
+        scan: Option<PathBuf>,
+
+        /// Project to associate with the directory (identifier or parent/child path)
+        #[arg(short = 'p', long = "project", conflicts_with = "scan")]
+        project: Option<String>,
+
+        /// Store the configuration in .accomplish.toml in the directory
+        #[arg(long, conflicts_with_all = ["global", "scan"])]
+        local: bool,
+
+        /// Store the configuration in ~/.accomplish/directories.toml
+        #[arg(long, conflicts_with = "scan")]
+        global: bool,
+
+        /// Name of the repository created in Accomplish for a git repository
+        #[arg(long, value_name = "NAME", conflicts_with = "scan")]
+        repo_name: Option<String>,
+
+        /// Don't ask anything: reinitialize if needed, take the defaults for
+        /// whatever isn't given and add .accomplish.toml to .gitignore
+        #[arg(short = 'y', long, requires = "project", conflicts_with = "scan")]
+        yes: bool,
+    },
 
     /// Add a new worklog entry
     Log {
         /// The text of the entry (can be specified multiple times, one per line)
-        #[arg(short = 'm', long = "message", required_unless_present = "edit")]
+        #[arg(short = 'm', long = "message", required_unless_present_any = ["edit", "file", "amend", "porcelain"])]
         messages: Vec<String>,
 
         /// Optional tags to associate with the entry (comma-separated)
@@ -43,9 +140,150 @@ pub enum Commands {
         #[arg(long)]
         edit: bool,
 
-        /// Associate with a project by its 3-letter identifier
+        /// With --edit, start from this template in ~/.accomplish/templates instead of the default
+        #[arg(long, value_name = "NAME", requires = "edit")]
+        template: Option<String>,
+
+        /// Read the entry from a markdown file (- for stdin), with optional front-matter
+        #[arg(
+            short = 'F',
+            long = "file",
+            value_name = "PATH",
+            conflicts_with_all = ["messages", "edit"]
+        )]
+        file: Option<PathBuf>,
+
+        /// Associate with a project by its 3-letter identifier (or parent/child path, e.g. web/chk)
         #[arg(short = 'p', long = "project")]
         project_identifier: Option<String>,
+
+        /// Log without a project, instead of asking when there's no default
+        #[arg(long = "no-project", conflicts_with = "project_identifier")]
+        no_project: bool,
+
+        /// Record the entry at a past time: a date, a date and time, yesterday, or a duration ago (e.g. 2h)
+        #[arg(long = "at", value_name = "WHEN")]
+        at: Option<String>,
+
+        /// Set a custom field defined in the config (can be specified multiple times)
+        #[arg(long = "field", value_name = "KEY=VALUE")]
+        fields: Vec<String>,
+
+        /// Who may see the entry in recaps and exports (default: from config)
+        #[arg(long = "visibility", value_enum)]
+        visibility: Option<Visibility>,
+
+        /// Continue the thread of an earlier entry (ID or short ID)
+        #[arg(long = "follows", value_name = "ID")]
+        follows: Option<String>,
+
+        /// Link the entry to other entries (ID or short ID, comma-separated or repeated)
+        #[arg(long = "relates-to", value_name = "ID", value_delimiter = ',')]
+        relates_to: Vec<String>,
+
+        /// Use the project and tags of the branch route matching the current git branch
+        #[arg(long = "branch-context")]
+        branch_context: bool,
+
+        /// Replace your most recent entry instead of creating one (opens the editor unless -m is given)
+        #[arg(
+            long,
+            conflicts_with_all = ["tags", "edit", "file", "project_identifier", "no_project", "at", "fields", "visibility", "follows", "relates_to", "branch_context"]
+        )]
+        amend: bool,
+
+        /// With --amend, amend the last entry even if it's older than amend_window
+        #[arg(long, requires = "amend")]
+        force: bool,
+
+        /// Add the suggested tags the tag index is sure enough of (see `auto_tags_threshold`)
+        #[arg(long = "auto-tags", conflicts_with = "amend")]
+        auto_tags: bool,
+
+        /// Print only the new entry's ID, for use in scripts
+        #[arg(short = 'q', long, conflicts_with = "amend")]
+        quiet: bool,
+
+        /// Read requests as JSON lines on stdin and answer each on stdout, for editor plugins
+        #[arg(
+            long,
+            conflicts_with_all = ["messages", "tags", "edit", "file", "project_identifier", "no_project", "at", "fields", "visibility", "follows", "relates_to", "branch_context", "amend", "auto_tags", "quiet"]
+        )]
+        porcelain: bool,
+    },
+
+    /// Log what you just did, e.g. `acc did fixed the build` (short for `log -m`)
+    Did {
+        /// The entry's text; words are joined with spaces, so quotes are optional
+        #[arg(required = true, num_args = 1.., trailing_var_arg = true)]
+        words: Vec<String>,
+
+        /// Optional tags to associate with the entry (comma-separated)
+        #[arg(short = 't', long = "tags", value_delimiter = ',')]
+        tags: Option<Vec<String>>,
+
+        /// Associate with a project (default: current project)
+        #[arg(short = 'p', long = "project")]
+        project_identifier: Option<String>,
+
+        /// Log without a project, instead of asking when there's no default
+        #[arg(long = "no-project", conflicts_with = "project_identifier")]
+        no_project: bool,
+
+        /// Print only the new entry's ID, for use in scripts
+        #[arg(short = 'q', long)]
+        quiet: bool,
+    },
+
+    /// Edit an entry in your editor, or replace its content or tags directly
+    Edit {
+        /// Entry ID, or the short ID shown by `acc logs`
+        id: String,
+
+        /// Replace the content instead of opening the editor (can be specified multiple times, one per line)
+        #[arg(short = 'm', long = "message")]
+        messages: Vec<String>,
+
+        /// Replace the tags instead of opening the editor (comma-separated)
+        #[arg(short = 't', long = "tags", value_delimiter = ',')]
+        tags: Option<Vec<String>>,
+    },
+
+    /// Delete entries, after showing them and asking for confirmation
+    Delete {
+        /// Entry IDs, or the short IDs shown by `acc logs`
+        #[arg(required = true)]
+        ids: Vec<String>,
+
+        /// Delete without asking, for scripts
+        #[arg(short = 'f', long)]
+        force: bool,
+    },
+
+    /// Show everything logged today, oldest first, without paging
+    Today {
+        /// Only show entries for a project, including its sub-projects
+        #[arg(short = 'p', long = "project")]
+        project: Option<String>,
+
+        /// Show full entry content instead of truncated preview
+        #[arg(short = 'v', long = "verbose", conflicts_with = "oneline")]
+        verbose: bool,
+
+        /// Show one aligned line per entry
+        #[arg(long = "oneline")]
+        oneline: bool,
+    },
+
+    /// Summarize yesterday, today and blockers for a standup, as Markdown
+    Standup {
+        /// Only include entries for a project, including its sub-projects
+        #[arg(short = 'p', long = "project")]
+        project: Option<String>,
+
+        /// Summarize the previous working day with a recap instead of listing its entries
+        #[arg(long)]
+        recap: bool,
     },
 
     /// Manage projects
@@ -63,17 +301,82 @@ pub enum Commands {
         /// Open editor to write the entry with pre-filled commit messages
         #[arg(long)]
         edit: bool,
+
+        /// Only offer commits from this day (YYYY-MM-DD) and record the entry on it
+        #[arg(long = "date")]
+        date: Option<String>,
+
+        /// Ignore the local capture cache and re-check every commit with the server
+        #[arg(long = "no-cache")]
+        no_cache: bool,
+
+        /// Capture from the repository at this path (working tree, .git directory or bare clone)
+        #[arg(long = "path", value_name = "DIR")]
+        path: Option<PathBuf>,
+
+        /// Only offer commits touching these paths, relative to the repository root (e.g. 'src/billing/**')
+        #[arg(long = "paths", value_name = "PATHSPEC", value_delimiter = ',', num_args = 1..)]
+        paths: Vec<String>,
+
+        /// How much of each commit message goes into the entry (default: capture_message_style, or full)
+        #[arg(long = "message-style", value_enum)]
+        message_style: Option<MessageStyle>,
+
+        /// Keep trailers like Signed-off-by and Co-authored-by in the entry
+        #[arg(long = "keep-trailers")]
+        keep_trailers: bool,
+
+        /// Add the captured commits to an earlier entry instead of creating one
+        #[arg(
+            long,
+            value_name = "ENTRY_ID",
+            conflicts_with_all = ["edit", "message_style", "keep_trailers"]
+        )]
+        fixup: Option<String>,
+
+        /// Capture every uncaptured commit without prompting, e.g. from cron or a git hook
+        #[arg(long, conflicts_with = "edit")]
+        auto: bool,
+
+        /// With --auto, also create a worklog entry from the captured commits
+        #[arg(long = "log", requires = "auto", conflicts_with = "fixup")]
+        log_entry: bool,
+
+        /// With --auto, print nothing unless something goes wrong
+        #[arg(short = 'q', long, requires = "auto")]
+        quiet: bool,
+
+        /// Log meetings from your calendar instead of commits (today, or --date)
+        #[arg(
+            long,
+            conflicts_with_all = ["limit", "edit", "no_cache", "path", "paths", "message_style", "keep_trailers", "fixup", "auto"]
+        )]
+        calendar: bool,
+
+        /// Log branch switches, rebases and stashes from the reflog as one context switching entry
+        #[arg(
+            long,
+            conflicts_with_all = ["limit", "date", "no_cache", "paths", "message_style", "keep_trailers", "calendar", "fixup", "auto"]
+        )]
+        reflog: bool,
+
+        /// With --reflog, how far back to look (e.g. 4h, 1d, today)
+        #[arg(long = "since", default_value = "1d", requires = "reflog")]
+        since: String,
     },
 
     /// List existing worklog entries (defaults to current project if configured)
-    #[command(alias = "ls")]
+    #[command(alias = "ls", args_conflicts_with_subcommands = true)]
     Logs {
-        /// Filter by project identifier
+        #[command(subcommand)]
+        command: Option<LogsCommands>,
+
+        /// Filter by project identifier, including its sub-projects
         #[arg(short = 'p', long = "project")]
         project: Option<String>,
 
         /// Show entries from all projects (overrides current project default)
-        #[arg(short = 'a', long = "all")]
+        #[arg(short = 'a', long = "all", visible_alias = "no-project")]
         all: bool,
 
         /// Filter by comma-separated tags
@@ -88,17 +391,69 @@ pub enum Commands {
         #[arg(long = "to")]
         to: Option<String>,
 
+        /// Only show entries that have associated commits
+        #[arg(long = "has-commits", conflicts_with = "no_commits")]
+        has_commits: bool,
+
+        /// Only show entries without associated commits
+        #[arg(long = "no-commits")]
+        no_commits: bool,
+
         /// Maximum number of entries to return
         #[arg(short = 'n', long = "limit", default_value = "20")]
         limit: u32,
 
         /// Show full entry content instead of truncated preview
-        #[arg(short = 'v', long = "verbose")]
+        #[arg(short = 'v', long = "verbose", conflicts_with = "oneline")]
         verbose: bool,
+
+        /// Show one aligned line per entry (date, project, first line, tags)
+        #[arg(long = "oneline")]
+        oneline: bool,
+
+        /// Print up to --limit entries as a JSON array, fetching pages as needed
+        #[arg(long = "json", conflicts_with_all = ["verbose", "oneline"])]
+        json: bool,
+
+        /// Print only the IDs of up to --limit entries, one per line, for piping into other commands
+        #[arg(long = "id-only", conflicts_with_all = ["verbose", "oneline", "json"])]
+        id_only: bool,
+
+        /// List only pinned entries (ignores project, tag, date and commit filters)
+        #[arg(long = "pinned")]
+        pinned: bool,
+
+        /// Sort by recorded time: newest first (desc) or oldest first (asc)
+        #[arg(long = "order", value_enum, default_value = "desc")]
+        order: SortOrder,
+
+        /// Reverse the sort order
+        #[arg(short = 'r', long = "reverse")]
+        reverse: bool,
+
+        /// Show times like "today 14:32" or "last Tue" (default with relative_dates = true)
+        #[arg(long = "relative-dates", conflicts_with = "absolute_dates")]
+        relative_dates: bool,
+
+        /// Show full timestamps, overriding relative_dates = true
+        #[arg(long = "absolute-dates")]
+        absolute_dates: bool,
+
+        /// Browse entries full screen, with search, tag filters and a detail view
+        #[arg(
+            short = 'i',
+            long,
+            conflicts_with_all = ["verbose", "oneline", "json", "id_only", "pinned", "order", "reverse"]
+        )]
+        interactive: bool,
     },
 
     /// Generate an AI-powered summary of worklog entries
+    #[command(args_conflicts_with_subcommands = true)]
     Recap {
+        #[command(subcommand)]
+        command: Option<RecapCommands>,
+
         /// Start date (inclusive, YYYY-MM-DD format)
         #[arg(long = "from")]
         from: Option<String>,
@@ -119,10 +474,197 @@ pub enum Commands {
         #[arg(short = 'x', long = "exclude-tags", value_delimiter = ' ')]
         exclude_tags: Option<Vec<String>>,
 
-        /// Filter by project identifier (3-letter code)
+        /// Filter by project identifier (3-letter code), including its sub-projects
         #[arg(short = 'p', long = "project")]
         project: Option<String>,
+
+        /// Recap every project without asking, even with no default project
+        #[arg(long = "no-project", conflicts_with = "project")]
+        no_project: bool,
+
+        /// Who the recap is written for (adjusts tone and level of detail)
+        #[arg(long = "audience", value_enum)]
+        audience: Option<RecapAudience>,
+
+        /// Recap every team member's entries, with a section per person
+        /// (organization accounts)
+        #[arg(long)]
+        team: bool,
+
+        /// Submit the recap and return immediately; see it later with `acc recap wait`
+        #[arg(long)]
+        background: bool,
+
+        /// Show a desktop notification when the recap is ready
+        #[arg(long)]
+        notify: bool,
+
+        /// How to print the recap: colored text (default), Markdown, plain text or JSON
+        #[arg(long = "format", value_enum)]
+        format: Option<RecapFormat>,
+
+        /// Also save the recap to this file, with its metadata as front matter
+        /// (or as JSON, for a .json file or --format json)
+        #[arg(short = 'o', long = "output", conflicts_with = "background")]
+        output: Option<PathBuf>,
+    },
+
+    /// Review a week day by day, fill in gaps, and save the review with a recap
+    Review {
+        /// Review the week containing this day (YYYY-MM-DD, default: last week)
+        #[arg(long = "week-of")]
+        week_of: Option<String>,
+
+        /// Skip generating a recap at the end of the review
+        #[arg(long = "no-recap")]
+        no_recap: bool,
+
+        /// Directory to save the review in (default: reviews_dir from config)
+        #[arg(short = 'o', long = "output-dir")]
+        output_dir: Option<PathBuf>,
+    },
+
+    /// Find days with commits in tracked repositories but no worklog entries
+    Audit {
+        /// How far back to look (e.g. "2w", "30d", "this-month")
+        #[arg(long = "since", default_value = "2w")]
+        since: String,
+
+        /// Count commits from every author, not just your git user.email
+        #[arg(long = "all-authors")]
+        all_authors: bool,
+    },
+
+    /// Send entries saved in the outbox while offline
+    Sync,
+
+    /// Suggest entries from recent local activity to log, edit or dismiss
+    Suggest {
+        /// How far back to look (e.g. "1d", "yesterday", "this-week")
+        #[arg(long = "since", default_value = "1d")]
+        since: String,
+
+        /// Count commits from every author, not just your git user.email
+        #[arg(long = "all-authors")]
+        all_authors: bool,
+
+        /// Also suggest entries from your shell history (see `suggest_shell_history`)
+        #[arg(long = "shell-history")]
+        shell_history: bool,
+
+        /// Print the suggestions without asking what to do with them
+        #[arg(long)]
+        list: bool,
+    },
+
+    /// Work with the tags of your entries
+    Tags {
+        #[command(subcommand)]
+        command: TagsCommands,
+    },
+
+    /// Append a Markdown digest of a day's entries to your journal file
+    #[command(args_conflicts_with_subcommands = true)]
+    Digest {
+        #[command(subcommand)]
+        command: Option<DigestCommands>,
+
+        /// Day to summarize (YYYY-MM-DD, defaults to today)
+        #[arg(long = "date")]
+        date: Option<String>,
+
+        /// Summarize the seven days ending on --date instead of a single day
+        #[arg(long)]
+        week: bool,
+
+        /// File to append the digest to (overrides `digest_file` in config)
+        #[arg(short = 'o', long = "output")]
+        output: Option<PathBuf>,
+
+        /// Print the digest to stdout instead of appending to a file
+        #[arg(long, conflicts_with = "output")]
+        stdout: bool,
+
+        /// Email the digest to this address instead, through the local sendmail
+        #[arg(long, value_name = "ADDRESS", conflicts_with_all = ["output", "stdout"])]
+        email: Option<String>,
+    },
+
+    /// Track goals through the entries logged with their tags
+    Goals {
+        #[command(subcommand)]
+        command: GoalsCommands,
+    },
+
+    /// Keep a local list of planned work and log items as they're finished
+    Todo {
+        #[command(subcommand)]
+        command: TodoCommands,
+    },
+
+    /// Manage profiles, e.g. separate work and personal accounts
+    Profile {
+        #[command(subcommand)]
+        command: ProfileCommands,
+    },
+
+    /// Print a short segment for a shell prompt, e.g. the number of uncaptured commits
+    Prompt {
+        /// Text to print; `{uncaptured}` is replaced with the count of uncaptured commits
+        #[arg(long, default_value = DEFAULT_PROMPT_FORMAT)]
+        format: String,
+    },
+
+    /// Print a shell completion script, e.g. `source <(acc completions bash)`
+    Completions {
+        /// Shell to generate completions for
+        #[arg(value_enum, required_unless_present = "list")]
+        shell: Option<Shell>,
+
+        /// Print cached values for an option, one per line (used by the scripts)
+        #[arg(long, value_enum, hide = true, conflicts_with = "shell")]
+        list: Option<CompletionValues>,
+    },
+
+    /// Inspect or clear the local caches
+    Cache {
+        #[command(subcommand)]
+        command: CacheCommands,
+    },
+
+    /// Export worklog entries as documents
+    Export {
+        #[command(subcommand)]
+        command: ExportCommands,
+    },
+
+    /// Install git hooks that capture commits as you make them
+    Hooks {
+        #[command(subcommand)]
+        command: HooksCommands,
     },
+
+    /// Check the configuration
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommands,
+    },
+
+    /// Capture new commits in the background as they're made
+    Daemon {
+        #[command(subcommand)]
+        command: DaemonCommands,
+    },
+
+    /// Manage repositories registered with Accomplish
+    Repo {
+        #[command(subcommand)]
+        command: RepoCommands,
+    },
+
+    /// Any other command runs the `acc-<name>` plugin on PATH
+    #[command(external_subcommand)]
+    Plugin(Vec<String>),
 }
 
 #[derive(Subcommand)]
@@ -131,6 +673,11 @@ pub enum ProjectCommands {
     List,
     /// Show which project identifier will be used by default
     Current,
+    /// Show a project's details, linked repositories and entry count
+    Show {
+        /// Project identifier, or a parent/child path for sub-projects
+        identifier: String,
+    },
     /// Create a new project
     New {
         /// The name of the project
@@ -144,4 +691,475 @@ pub enum ProjectCommands {
         #[arg(short = 'i', long = "identifier")]
         identifier: Option<String>,
     },
+    /// Rename a project, or change its description or identifier
+    Update {
+        /// Project identifier, or a parent/child path for sub-projects
+        project: String,
+
+        /// New name
+        #[arg(short = 'n', long)]
+        name: Option<String>,
+
+        /// New description; an empty one clears it
+        #[arg(short = 'd', long)]
+        description: Option<String>,
+
+        /// New identifier of up to 3 letters
+        #[arg(short = 'i', long)]
+        identifier: Option<String>,
+    },
+    /// Archive a project you no longer work on
+    Archive {
+        /// Project identifier, or a parent/child path for sub-projects
+        project: String,
+
+        /// Archive without asking, for scripts
+        #[arg(short = 'f', long)]
+        force: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum LogsCommands {
+    /// Pin an entry so it's easy to find again (e.g. evidence for a promotion packet)
+    Pin {
+        /// Entry ID, or the short ID shown by `acc logs`
+        id: String,
+    },
+    /// Unpin a pinned entry
+    Unpin {
+        /// Entry ID, or the short ID shown by `acc logs`
+        id: String,
+    },
+    /// Show one entry in full, with the thread it follows up on and related entries
+    Show {
+        /// Entry ID, or the short ID shown by `acc logs`
+        id: String,
+    },
+    /// Show how an entry changed with each edit, as diffs between revisions
+    History {
+        /// Entry ID, or the short ID shown by `acc logs`
+        id: String,
+    },
+    /// List deleted entries that can still be restored
+    Trash,
+    /// Recreate a deleted entry from the trash
+    Restore {
+        /// Entry ID, or the short ID shown by `acc logs trash`
+        id: String,
+    },
+    /// Show the last few entries, oldest first, without paging (works offline)
+    Tail {
+        /// Number of entries to show
+        #[arg(short = 'n', long = "lines", default_value = "5")]
+        count: u32,
+
+        /// Filter by project identifier, including its sub-projects
+        #[arg(short = 'p', long = "project")]
+        project: Option<String>,
+
+        /// Show entries from all projects (overrides current project default)
+        #[arg(short = 'a', long = "all")]
+        all: bool,
+
+        /// Show one aligned line per entry
+        #[arg(long = "oneline")]
+        oneline: bool,
+    },
+    /// Compare raw entry statistics between two periods
+    Diff {
+        /// Earlier period (YYYY-MM-DD, YYYY-MM, YYYY-Www, FROM..TO, or e.g. last-month)
+        #[arg(long = "from")]
+        from: String,
+
+        /// Later period, in the same formats as --from
+        #[arg(long = "to")]
+        to: String,
+
+        /// Filter by project identifier, including its sub-projects
+        #[arg(short = 'p', long = "project")]
+        project: Option<String>,
+
+        /// Compare entries from all projects (overrides current project default)
+        #[arg(short = 'a', long = "all")]
+        all: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum GoalsCommands {
+    /// Add a goal, or update the goal with the same name
+    Add {
+        /// The name of the goal
+        name: String,
+
+        /// Tags whose entries count towards the goal (comma-separated or repeated)
+        #[arg(short = 't', long = "tag", value_delimiter = ',', required = true)]
+        tags: Vec<String>,
+
+        /// Day from which entries count (YYYY-MM-DD, default: today)
+        #[arg(long = "start-date")]
+        start_date: Option<String>,
+
+        /// Day the goal should be reached by (YYYY-MM-DD)
+        #[arg(long = "target-date")]
+        target_date: Option<String>,
+
+        /// Number of entries that marks the goal as done
+        #[arg(long = "target-entries")]
+        target_entries: Option<u32>,
+    },
+    /// Show progress for every goal
+    Status,
+    /// Remove a goal
+    Remove {
+        /// The name of the goal
+        name: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ProfileCommands {
+    /// List profiles, marking the active one
+    List,
+    /// Make a profile the active one for later runs
+    Use {
+        /// Profile name
+        name: String,
+    },
+    /// Add a profile, copying anything not given from the default profile
+    Add {
+        /// Profile name (letters, digits, '-' and '_')
+        name: String,
+
+        /// Accomplish server URL
+        #[arg(long, value_name = "URL")]
+        api_base: Option<String>,
+
+        /// OAuth client ID to log in with
+        #[arg(long)]
+        client_id: Option<String>,
+
+        /// Directory holding per-profile state (default: ~/.accomplish)
+        #[arg(long, value_name = "DIR")]
+        credentials_dir: Option<String>,
+    },
+    /// Remove a profile from the config and log it out
+    Remove {
+        /// Profile name
+        name: String,
+
+        /// Remove without asking
+        #[arg(short = 'f', long)]
+        force: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum CacheCommands {
+    /// Show each cache's size and age
+    Status,
+    /// Delete a cache so it's rebuilt from the server
+    Clear {
+        /// Which cache to clear
+        #[arg(value_enum, default_value = "all")]
+        kind: CacheKind,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum TagsCommands {
+    /// Suggest tags for an entry from the tags of your past entries
+    Suggest {
+        /// The text of the entry (can be specified multiple times, one per line)
+        #[arg(short = 'm', long = "message")]
+        messages: Vec<String>,
+
+        /// Read the entry from a markdown file (- for stdin) instead
+        #[arg(
+            short = 'F',
+            long = "file",
+            value_name = "PATH",
+            conflicts_with = "messages"
+        )]
+        file: Option<PathBuf>,
+
+        /// Tags the entry already has, which aren't suggested (comma-separated)
+        #[arg(short = 't', long = "tags", value_delimiter = ',')]
+        tags: Vec<String>,
+
+        /// Preselect suggestions at least this likely, from 0 to 1 (default: auto_tags_threshold)
+        #[arg(long)]
+        threshold: Option<f64>,
+
+        /// Rebuild the tag index from your entries even if it's recent
+        #[arg(long)]
+        refresh: bool,
+
+        /// Print the suggestions with their confidence without asking which to keep
+        #[arg(long)]
+        list: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum TodoCommands {
+    /// Add an item to the list
+    Add {
+        /// What needs doing; becomes the entry's content when it's done
+        text: String,
+
+        /// Project to log the item against (default: current project)
+        #[arg(short = 'p', long = "project")]
+        project: Option<String>,
+
+        /// Tags for the entry (comma-separated)
+        #[arg(short = 't', long = "tags", value_delimiter = ',')]
+        tags: Vec<String>,
+    },
+    /// List open items and how long they've been open
+    #[command(alias = "ls")]
+    List,
+    /// Log an item as an entry, recording the time since it was added
+    Done {
+        /// The item's number from `acc todo list`
+        id: u32,
+    },
+    /// Remove an item without logging it
+    #[command(alias = "rm")]
+    Remove {
+        /// The item's number from `acc todo list`
+        id: u32,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum RecapCommands {
+    /// Wait for a recap submitted with --background, then print it
+    Wait {
+        /// Recap ID printed by `acc recap --background`
+        id: String,
+
+        /// Show a desktop notification when the recap is ready
+        #[arg(long)]
+        notify: bool,
+
+        /// How to print the recap: colored text, Markdown, plain text or JSON
+        #[arg(long = "format", value_enum, default_value = "text")]
+        format: RecapFormat,
+
+        /// Also save the recap to this file, with its metadata as front matter
+        /// (or as JSON, for a .json file or --format json)
+        #[arg(short = 'o', long = "output")]
+        output: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ExportCommands {
+    /// Build a Markdown packet for performance reviews, by month and project
+    ReviewPacket {
+        /// Start date (inclusive, YYYY-MM-DD format)
+        #[arg(long = "from")]
+        from: String,
+
+        /// End date (inclusive, YYYY-MM-DD format, default: today)
+        #[arg(long = "to")]
+        to: Option<String>,
+
+        /// Filter by project identifier (3-letter code), including its sub-projects
+        #[arg(short = 'p', long = "project")]
+        project: Option<String>,
+
+        /// Who the monthly recaps are written for (default: manager)
+        #[arg(long = "audience", value_enum)]
+        audience: Option<RecapAudience>,
+
+        /// Skip generating a recap for each month
+        #[arg(long = "no-recaps")]
+        no_recaps: bool,
+
+        /// File to write the packet to (default: a file in reviews_dir)
+        #[arg(short = 'o', long = "output")]
+        output: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum HooksCommands {
+    /// Add a post-commit hook that runs `acc capture --auto --quiet` after each commit
+    Install {
+        /// Install into the repository at this path instead of the current one
+        #[arg(long = "path", value_name = "DIR")]
+        path: Option<PathBuf>,
+
+        /// Replace an existing hook (it's kept with a .backup suffix)
+        #[arg(long)]
+        force: bool,
+
+        /// Instead, add a prepare-commit-msg hook that names the project in an Accomplish-Project trailer
+        #[arg(long = "prepare-commit-msg")]
+        prepare_commit_msg: bool,
+
+        /// Project for the trailer (default: the repository's)
+        #[arg(short = 'p', long = "project", requires = "prepare_commit_msg")]
+        project: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ConfigCommands {
+    /// Show which branch route applies to a branch, and the project and tags it gives
+    TestRoute {
+        /// Branch name (default: the branch checked out here)
+        branch: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum DigestCommands {
+    /// Run digests on a schedule with cron, launchd or Task Scheduler
+    #[command(args_conflicts_with_subcommands = true)]
+    Schedule {
+        #[command(subcommand)]
+        command: Option<DigestScheduleCommands>,
+
+        /// Send a digest of the past week once a week
+        #[arg(long, conflicts_with = "daily")]
+        weekly: bool,
+
+        /// Send a digest of the day every day
+        #[arg(long)]
+        daily: bool,
+
+        /// With --weekly, the day it runs on (default: friday)
+        #[arg(long, value_name = "DAY", conflicts_with = "daily")]
+        day: Option<String>,
+
+        /// Local time it runs at, HH:MM (default: 17:00)
+        #[arg(long, value_name = "TIME")]
+        at: Option<String>,
+
+        /// Email each digest to this address
+        #[arg(long, value_name = "ADDRESS")]
+        email: Option<String>,
+
+        /// Append each digest to this file instead (default: `digest_file` in config)
+        #[arg(short = 'o', long = "output", conflicts_with = "email")]
+        output: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum DigestScheduleCommands {
+    /// List the digests scheduled for this profile
+    List,
+    /// Remove a scheduled digest
+    #[command(alias = "rm")]
+    Remove {
+        /// ID shown by `acc digest schedule list`, e.g. digest-weekly
+        id: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum DaemonCommands {
+    /// Start the daemon in the background, logging to daemon.log in the credentials directory
+    Start,
+    /// Run the daemon in the foreground, for service managers like systemd or launchd
+    Run {
+        /// Check the repositories once and exit
+        #[arg(long)]
+        once: bool,
+    },
+    /// Stop the background daemon
+    Stop,
+    /// Show whether the daemon is running and what it last did in each repository
+    Status,
+}
+
+#[derive(Subcommand)]
+pub enum RepoCommands {
+    /// List repositories by project
+    List {
+        /// Only list a project's repositories, including its sub-projects'
+        #[arg(short = 'p', long = "project")]
+        project: Option<String>,
+    },
+    /// Link the clone in the current directory to a registered repository
+    Link {
+        /// Repository ID, ID prefix or name (found by remote URL if omitted)
+        repo: Option<String>,
+    },
+    /// Change a repository's name, remote URL, default branch or project
+    Update {
+        /// Repository ID, ID prefix or name
+        repo: String,
+
+        /// New name
+        #[arg(short = 'n', long)]
+        name: Option<String>,
+
+        /// New remote URL
+        #[arg(long = "remote-url")]
+        remote_url: Option<String>,
+
+        /// New default branch
+        #[arg(long = "default-branch")]
+        default_branch: Option<String>,
+
+        /// Move the repository to another project
+        #[arg(short = 'p', long = "project")]
+        project: Option<String>,
+    },
+    /// Remove a repository
+    Rm {
+        /// Repository ID, ID prefix or name
+        repo: String,
+
+        /// Remove without asking, for scripts
+        #[arg(short = 'f', long)]
+        force: bool,
+    },
+    /// Update each repository's remote URL and default branch from its local clone
+    Sync {
+        /// Show what would change without updating anything
+        #[arg(long = "dry-run")]
+        dry_run: bool,
+    },
+}
+
+/// Order in which entries are listed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum SortOrder {
+    /// Oldest first
+    Asc,
+    /// Newest first
+    Desc,
+}
+
+/// Intended reader of a generated recap.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum RecapAudience {
+    /// Personal notes, full detail
+    #[value(name = "self")]
+    Myself,
+    /// Outcome-focused summary for your manager
+    Manager,
+    /// Shared context for teammates
+    Team,
+    /// Client-facing summary without internal details
+    Client,
+}
+
+impl RecapAudience {
+    /// Value sent to the recap API.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RecapAudience::Myself => "self",
+            RecapAudience::Manager => "manager",
+            RecapAudience::Team => "team",
+            RecapAudience::Client => "client",
+        }
+    }
 }
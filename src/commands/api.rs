@@ -0,0 +1,280 @@
+use crate::auth::AuthService;
+use crate::errors::AppError;
+use reqwest::Method;
+use serde_json::{Map, Value};
+
+/// Upper bound on pages followed by `--paginate`, in case a server keeps
+/// returning the same cursor.
+const MAX_PAGES: usize = 1000;
+
+/// Performs an authenticated request against the configured API and prints the
+/// JSON response. `fields` (`key=value`) become query parameters for GET and
+/// DELETE and a JSON body otherwise. With `paginate`, follows `meta.end_cursor`
+/// and prints the list from every page as one response.
+pub async fn execute(
+    auth_service: &mut AuthService,
+    method: &str,
+    endpoint: &str,
+    fields: &[String],
+    paginate: bool,
+) -> Result<(), AppError> {
+    let method = parse_method(method)?;
+    let endpoint = endpoint.trim_start_matches('/');
+    let fields = parse_fields(fields)?;
+
+    if paginate && method != Method::GET {
+        return Err(AppError::Other(
+            "--paginate can only be used with GET requests".to_string(),
+        ));
+    }
+
+    let sends_query = matches!(method, Method::GET | Method::DELETE);
+    let query: Vec<(String, String)> = if sends_query {
+        fields
+            .iter()
+            .map(|(key, value)| (key.clone(), query_value(value)))
+            .collect()
+    } else {
+        Vec::new()
+    };
+    let body = (!sends_query && !fields.is_empty()).then_some(Value::Object(fields));
+
+    let api_client = auth_service.api_client();
+    let response = if paginate {
+        let mut pages = Vec::new();
+        let mut cursor: Option<String> = None;
+
+        while pages.len() < MAX_PAGES {
+            let mut page_query = query.clone();
+            if let Some(cursor) = &cursor {
+                page_query.push(("starting_after".to_string(), cursor.clone()));
+            }
+
+            let page = api_client
+                .request(Method::GET, endpoint, &page_query, None)
+                .await?;
+            let next = next_cursor(&page).filter(|next| cursor.as_deref() != Some(next));
+            pages.push(page);
+
+            match next {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+
+        merge_pages(pages)?
+    } else {
+        api_client.request(method, endpoint, &query, body).await?
+    };
+
+    if !response.is_null() {
+        println!("{}", serde_json::to_string_pretty(&response)?);
+    }
+    Ok(())
+}
+
+fn parse_method(method: &str) -> Result<Method, AppError> {
+    match method.to_uppercase().as_str() {
+        "GET" => Ok(Method::GET),
+        "POST" => Ok(Method::POST),
+        "PUT" => Ok(Method::PUT),
+        "PATCH" => Ok(Method::PATCH),
+        "DELETE" => Ok(Method::DELETE),
+        other => Err(AppError::Other(format!(
+            "Unsupported method '{other}'. Use GET, POST, PUT, PATCH or DELETE"
+        ))),
+    }
+}
+
+/// Parses `key=value` fields. Values that look like JSON numbers, booleans or null
+/// are sent typed; everything else is sent as a string.
+fn parse_fields(fields: &[String]) -> Result<Map<String, Value>, AppError> {
+    let mut map = Map::new();
+
+    for field in fields {
+        let (key, value) = field.split_once('=').ok_or_else(|| {
+            AppError::Other(format!("Invalid field '{field}'. Expected key=value"))
+        })?;
+        let key = key.trim();
+        if key.is_empty() {
+            return Err(AppError::Other(format!(
+                "Invalid field '{field}'. Expected key=value"
+            )));
+        }
+
+        let value = match serde_json::from_str::<Value>(value) {
+            Ok(v @ (Value::Number(_) | Value::Bool(_) | Value::Null)) => v,
+            _ => Value::String(value.to_string()),
+        };
+        map.insert(key.to_string(), value);
+    }
+
+    Ok(map)
+}
+
+fn query_value(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn next_cursor(page: &Value) -> Option<String> {
+    page.get("meta")
+        .and_then(|m| m.get("end_cursor"))
+        .and_then(Value::as_str)
+        .map(String::from)
+}
+
+/// Concatenates the list (the single top-level array, e.g. `entries`) of every
+/// page into the first page, keeping the last page's `meta`.
+fn merge_pages(pages: Vec<Value>) -> Result<Value, AppError> {
+    let mut pages = pages.into_iter();
+    let Some(Value::Object(mut merged)) = pages.next() else {
+        return Err(AppError::ParseError(
+            "--paginate requires a JSON object response".to_string(),
+        ));
+    };
+
+    let list_keys: Vec<String> = merged
+        .iter()
+        .filter(|(key, value)| key.as_str() != "meta" && value.is_array())
+        .map(|(key, _)| key.clone())
+        .collect();
+    let [list_key] = list_keys.as_slice() else {
+        return Err(AppError::ParseError(
+            "--paginate requires a response with exactly one list".to_string(),
+        ));
+    };
+
+    for page in pages {
+        if let Some(items) = page.get(list_key).and_then(Value::as_array) {
+            if let Some(Value::Array(all)) = merged.get_mut(list_key) {
+                all.extend(items.iter().cloned());
+            }
+        }
+        if let Some(meta) = page.get("meta") {
+            merged.insert("meta".to_string(), meta.clone());
+        }
+    }
+
+    Ok(Value::Object(merged))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::{Matcher, Server};
+    use serde_json::json;
+
+    fn setup_mock_auth_service(server_url: &str) -> AuthService {
+        let mut auth =
+            AuthService::new(server_url.to_string(), std::env::temp_dir(), "test-profile");
+        auth.save_access_token("test-token").unwrap();
+        auth
+    }
+
+    #[test]
+    fn test_parse_fields_infers_types() {
+        let fields = parse_fields(&[
+            "name=Website".to_string(),
+            "limit=5".to_string(),
+            "archived=false".to_string(),
+            "note=a=b".to_string(),
+        ])
+        .unwrap();
+
+        assert_eq!(fields["name"], json!("Website"));
+        assert_eq!(fields["limit"], json!(5));
+        assert_eq!(fields["archived"], json!(false));
+        assert_eq!(fields["note"], json!("a=b"));
+        assert!(parse_fields(&["name".to_string()]).is_err());
+        assert!(parse_fields(&["=x".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_parse_method() {
+        assert_eq!(parse_method("get").unwrap(), Method::GET);
+        assert_eq!(parse_method("PATCH").unwrap(), Method::PATCH);
+        assert!(parse_method("TRACE").is_err());
+    }
+
+    #[test]
+    fn test_merge_pages() {
+        let merged = merge_pages(vec![
+            json!({ "entries": [{ "id": "1" }], "meta": { "end_cursor": "1" } }),
+            json!({ "entries": [{ "id": "2" }], "meta": { "end_cursor": null } }),
+        ])
+        .unwrap();
+
+        assert_eq!(merged["entries"], json!([{ "id": "1" }, { "id": "2" }]));
+        assert_eq!(merged["meta"]["end_cursor"], Value::Null);
+        assert!(merge_pages(vec![json!({ "a": [], "b": [] })]).is_err());
+        assert!(merge_pages(vec![json!([1, 2])]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_execute_post_sends_fields_as_body() {
+        let mut server = Server::new_async().await;
+        let mut auth = setup_mock_auth_service(&server.url());
+
+        let m = server
+            .mock("POST", "/api/v1/projects")
+            .match_header("authorization", "Bearer test-token")
+            .match_body(Matcher::Json(json!({ "name": "Website", "public": true })))
+            .with_status(201)
+            .with_body(r#"{"id":"p1"}"#)
+            .create();
+
+        let fields = vec!["name=Website".to_string(), "public=true".to_string()];
+        execute(&mut auth, "post", "/api/v1/projects", &fields, false)
+            .await
+            .unwrap();
+        m.assert();
+    }
+
+    #[tokio::test]
+    async fn test_execute_paginate_follows_cursor() {
+        let mut server = Server::new_async().await;
+        let mut auth = setup_mock_auth_service(&server.url());
+
+        let first = server
+            .mock("GET", "/api/v1/worklog/entries")
+            .match_query(Matcher::UrlEncoded("limit".into(), "1".into()))
+            .with_status(200)
+            .with_body(
+                json!({ "entries": [{ "id": "1" }], "meta": { "end_cursor": "1" } }).to_string(),
+            )
+            .create();
+        let second = server
+            .mock("GET", "/api/v1/worklog/entries")
+            .match_query(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("limit".into(), "1".into()),
+                Matcher::UrlEncoded("starting_after".into(), "1".into()),
+            ]))
+            .with_status(200)
+            .with_body(
+                json!({ "entries": [{ "id": "2" }], "meta": { "end_cursor": null } }).to_string(),
+            )
+            .create();
+
+        execute(
+            &mut auth,
+            "GET",
+            "api/v1/worklog/entries",
+            &["limit=1".to_string()],
+            true,
+        )
+        .await
+        .unwrap();
+        first.assert();
+        second.assert();
+    }
+
+    #[tokio::test]
+    async fn test_execute_paginate_rejects_post() {
+        let mut auth = setup_mock_auth_service("http://127.0.0.1:9");
+        let result = execute(&mut auth, "POST", "api/v1/projects", &[], true).await;
+        assert!(matches!(result, Err(AppError::Other(_))));
+    }
+}
@@ -0,0 +1,199 @@
+use crate::auth::AuthService;
+use crate::commands::capture::{get_commits_since, get_git_user_email, is_git_repository};
+use crate::commands::logs::fetch_all_entries;
+use crate::config;
+use crate::errors::AppError;
+use crate::utils::duration::parse_since_duration;
+use chrono::{DateTime, Local, NaiveDate, Utc};
+use colored::*;
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::env;
+use std::path::PathBuf;
+
+/// Commit activity aggregated for one calendar day.
+#[derive(Debug, Default)]
+struct DayActivity {
+    commits: usize,
+    repos: BTreeSet<String>,
+}
+
+/// Lists days in the period where tracked repositories have commits but no
+/// worklog entry was recorded, so they can be backfilled with `acc capture --date`.
+pub async fn execute(
+    auth_service: &mut AuthService,
+    since: &str,
+    all_authors: bool,
+) -> Result<(), AppError> {
+    let since_iso = parse_since_duration(since).map_err(|e| AppError::Other(e.to_string()))?;
+    let since_time = since_iso
+        .parse::<DateTime<Utc>>()
+        .map_err(|e| AppError::ParseError(format!("Invalid start time {since_iso}: {e}")))?;
+
+    let repos = repositories_to_audit();
+    if repos.is_empty() {
+        println!("No tracked git repositories found. Run 'acc init' in a repository first.");
+        return Ok(());
+    }
+
+    let mut activity: BTreeMap<NaiveDate, DayActivity> = BTreeMap::new();
+    for repo in &repos {
+        if !is_git_repository(repo) {
+            eprintln!(
+                "⚠️  Warning: Skipping {} (not a git repository)",
+                repo.display()
+            );
+            continue;
+        }
+
+        let author = if all_authors {
+            None
+        } else {
+            get_git_user_email(repo)
+        };
+        let repo_name = repo
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| repo.display().to_string());
+
+        let commits = match get_commits_since(repo, since_time, author.as_deref()) {
+            Ok(commits) => commits,
+            Err(e) => {
+                eprintln!("⚠️  Warning: Could not read {}: {e}", repo.display());
+                continue;
+            }
+        };
+
+        for commit in commits {
+            let day = commit.committed_at.with_timezone(&Local).date_naive();
+            let entry = activity.entry(day).or_default();
+            entry.commits += 1;
+            entry.repos.insert(repo_name.clone());
+        }
+    }
+
+    let from_day = since_time.with_timezone(&Local).date_naive();
+    let today = Local::now().date_naive();
+    let entries = fetch_all_entries(
+        auth_service.api_client(),
+        None,
+        None,
+        Some(&from_day.format("%Y-%m-%d").to_string()),
+        Some(&today.format("%Y-%m-%d").to_string()),
+    )
+    .await?;
+
+    let gaps = find_gaps(&activity, &logged_days(&entries));
+
+    if gaps.is_empty() {
+        println!(
+            "✅ Every day with commits since {from_day} has at least one worklog entry ({} repositories checked).",
+            repos.len()
+        );
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        format!("Days with commits but no worklog entries (since {from_day}):").bright_blue()
+    );
+    println!();
+    for (day, day_activity) in &gaps {
+        let repos = day_activity
+            .repos
+            .iter()
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(", ");
+        println!(
+            "  {}  {:>3} commits  {}",
+            day.format("%Y-%m-%d (%a)").to_string().bright_yellow(),
+            day_activity.commits,
+            repos.bright_black()
+        );
+    }
+    println!();
+    println!(
+        "{}",
+        "Backfill a day from its repository with `acc capture --date <YYYY-MM-DD>`".bright_black()
+    );
+
+    Ok(())
+}
+
+/// Tracked repositories plus the current directory when it is a git repository.
+pub fn repositories_to_audit() -> Vec<PathBuf> {
+    let mut repos = config::tracked_git_directories();
+
+    if let Ok(current_dir) = env::current_dir() {
+        if is_git_repository(&current_dir) && !repos.contains(&current_dir) {
+            repos.push(current_dir);
+        }
+    }
+
+    repos
+}
+
+/// Local calendar days on which at least one entry was recorded.
+fn logged_days(entries: &[Value]) -> HashSet<NaiveDate> {
+    entries
+        .iter()
+        .filter_map(|e| e.get("recorded_at").and_then(Value::as_str))
+        .filter_map(|s| s.parse::<DateTime<Utc>>().ok())
+        .map(|dt| dt.with_timezone(&Local).date_naive())
+        .collect()
+}
+
+fn find_gaps<'a>(
+    activity: &'a BTreeMap<NaiveDate, DayActivity>,
+    logged: &HashSet<NaiveDate>,
+) -> Vec<(NaiveDate, &'a DayActivity)> {
+    activity
+        .iter()
+        .filter(|(day, _)| !logged.contains(day))
+        .map(|(day, a)| (*day, a))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 7, d).unwrap()
+    }
+
+    #[test]
+    fn test_logged_days() {
+        let entries = vec![
+            json!({ "recorded_at": "2025-07-09T12:00:00Z" }),
+            json!({ "recorded_at": "not a date" }),
+            json!({ "content": "missing timestamp" }),
+        ];
+        let days = logged_days(&entries);
+        assert_eq!(days.len(), 1);
+        let expected = "2025-07-09T12:00:00Z"
+            .parse::<DateTime<Utc>>()
+            .unwrap()
+            .with_timezone(&Local)
+            .date_naive();
+        assert!(days.contains(&expected));
+    }
+
+    #[test]
+    fn test_find_gaps() {
+        let mut activity = BTreeMap::new();
+        for d in [1, 2, 3] {
+            let entry: &mut DayActivity = activity.entry(day(d)).or_default();
+            entry.commits = d as usize;
+            entry.repos.insert("repo".to_string());
+        }
+        let logged: HashSet<NaiveDate> = [day(2), day(5)].into_iter().collect();
+
+        let gaps = find_gaps(&activity, &logged);
+        let gap_days: Vec<NaiveDate> = gaps.iter().map(|(d, _)| *d).collect();
+        assert_eq!(gap_days, vec![day(1), day(3)]);
+        assert_eq!(gaps[1].1.commits, 3);
+    }
+}
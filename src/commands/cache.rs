@@ -0,0 +1,134 @@
+use crate::capture_state::CAPTURE_STATE_FILE;
+use crate::completion_cache::COMPLETION_CACHE_FILE;
+use crate::entry_cache::ENTRY_CACHE_FILE;
+use crate::errors::AppError;
+use crate::tag_index::TAG_INDEX_FILE;
+use crate::utils::humanize::ago;
+use chrono::{DateTime, Utc};
+use clap::ValueEnum;
+use colored::*;
+use std::fs;
+use std::path::Path;
+
+/// A local cache that can be inspected and cleared on its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CacheKind {
+    /// Recently fetched entries, used by `acc logs tail` while offline
+    Entries,
+    /// Commits known to be captured, used to skip them in `acc capture`
+    Commits,
+    /// Projects and recent tags offered by shell completion
+    Completions,
+    /// Tags learned from past entries, used by `acc tags suggest`
+    Tags,
+    /// Every cache
+    All,
+}
+
+impl CacheKind {
+    const CACHES: [CacheKind; 4] = [
+        CacheKind::Entries,
+        CacheKind::Commits,
+        CacheKind::Completions,
+        CacheKind::Tags,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            CacheKind::Entries => "entries",
+            CacheKind::Commits => "commits",
+            CacheKind::Completions => "completions",
+            CacheKind::Tags => "tags",
+            CacheKind::All => "all",
+        }
+    }
+
+    fn file(self) -> Option<&'static str> {
+        match self {
+            CacheKind::Entries => Some(ENTRY_CACHE_FILE),
+            CacheKind::Commits => Some(CAPTURE_STATE_FILE),
+            CacheKind::Completions => Some(COMPLETION_CACHE_FILE),
+            CacheKind::Tags => Some(TAG_INDEX_FILE),
+            CacheKind::All => None,
+        }
+    }
+
+    /// The caches this selects, expanding `all`.
+    fn selected(self) -> Vec<CacheKind> {
+        match self {
+            CacheKind::All => Self::CACHES.to_vec(),
+            kind => vec![kind],
+        }
+    }
+}
+
+/// Prints each cache's size and when it was last written.
+pub fn status(state_dir: &Path) -> Result<(), AppError> {
+    let now = Utc::now();
+    for kind in CacheKind::CACHES {
+        let Some(file) = kind.file() else { continue };
+        let details = match fs::metadata(state_dir.join(file)) {
+            Ok(meta) => {
+                let age = meta
+                    .modified()
+                    .map(|t| ago(now - DateTime::<Utc>::from(t)))
+                    .unwrap_or_else(|_| "unknown age".to_string());
+                format!("{:>9}  updated {age}", format_size(meta.len()))
+            }
+            Err(_) => format!("{:>9}", "empty").bright_black().to_string(),
+        };
+        println!("{:<11} {details}", kind.name());
+    }
+    Ok(())
+}
+
+/// Deletes the selected caches. They're rebuilt by the next command that
+/// needs them, so this only costs a slower next run.
+pub fn clear(state_dir: &Path, kind: CacheKind) -> Result<(), AppError> {
+    for kind in kind.selected() {
+        let Some(file) = kind.file() else { continue };
+        match fs::remove_file(state_dir.join(file)) {
+            Ok(()) => println!("✓ Cleared {} cache", kind.name()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                println!("{} cache is already empty", kind.name())
+            }
+            Err(e) => return Err(e.into()),
+        }
+    }
+    Ok(())
+}
+
+fn format_size(bytes: u64) -> String {
+    match bytes {
+        0..1024 => format!("{bytes} B"),
+        1024..1_048_576 => format!("{:.1} KB", bytes as f64 / 1024.0),
+        _ => format!("{:.1} MB", bytes as f64 / 1_048_576.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_clear_only_selected_cache() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join(ENTRY_CACHE_FILE), "{}").unwrap();
+        fs::write(temp_dir.path().join(CAPTURE_STATE_FILE), "{}").unwrap();
+
+        clear(temp_dir.path(), CacheKind::Entries).unwrap();
+        assert!(!temp_dir.path().join(ENTRY_CACHE_FILE).exists());
+        assert!(temp_dir.path().join(CAPTURE_STATE_FILE).exists());
+
+        clear(temp_dir.path(), CacheKind::All).unwrap();
+        assert!(!temp_dir.path().join(CAPTURE_STATE_FILE).exists());
+    }
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(2048), "2.0 KB");
+        assert_eq!(format_size(3 * 1_048_576), "3.0 MB");
+    }
+}
@@ -2,15 +2,31 @@ use crate::api::endpoints::{
     associate_commits_with_entry, create_commits, fetch_projects, fetch_uncaptured_commits,
     CommitData,
 };
+use crate::api::models;
 use crate::auth::AuthService;
-use crate::commands::log;
+use crate::capture_state::CaptureState;
+use crate::commands::{log, logs};
 use crate::config;
 use crate::errors::AppError;
-use chrono::{DateTime, Utc};
-use git2::{Commit, Repository};
+use crate::hooks::{HookEvent, Hooks};
+use crate::utils::branch_routes::{self, BranchRoutes};
+use crate::utils::commit_message::{self, CoAuthorMode, CommitMessageFormat};
+use crate::utils::prompt::{self, PromptOrDefault};
+use chrono::{DateTime, Local, NaiveDate, Utc};
+use git2::{Commit, DiffOptions, Repository, Sort};
 use inquire::{Confirm, MultiSelect};
+use serde_json::{json, Map, Value};
 use std::env;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+static BRANCH_ROUTES: OnceLock<BranchRoutes> = OnceLock::new();
+
+/// Sets the rules routing captures on matching branches to other projects
+/// and tags, from `branch_routes` in the config. Only the first call counts.
+pub fn set_branch_routes(routes: BranchRoutes) {
+    let _ = BRANCH_ROUTES.set(routes);
+}
 
 /// Represents a git commit with its metadata
 #[derive(Debug, Clone)]
@@ -20,6 +36,7 @@ pub struct GitCommit {
     pub committed_at: DateTime<Utc>,
     pub short_sha: String,
     pub summary: String,
+    pub author_email: Option<String>,
 }
 
 impl GitCommit {
@@ -29,6 +46,7 @@ impl GitCommit {
         let short_sha = sha.chars().take(7).collect();
         let message = commit.message().unwrap_or("").to_string();
         let summary = commit.summary().unwrap_or("").to_string();
+        let author_email = commit.author().email().map(|e| e.to_string());
 
         let timestamp = commit.time().seconds();
         let committed_at = DateTime::from_timestamp(timestamp, 0)
@@ -40,51 +58,143 @@ impl GitCommit {
             committed_at,
             short_sha,
             summary,
+            author_email,
         })
     }
 }
 
-/// Executes the capture command
+/// Executes the capture command.
+/// When `date` (YYYY-MM-DD) is given, only commits from that local day are offered
+/// and the resulting entry is recorded at the time of the latest selected commit.
+/// When `path` is given, commits are read from that repository (working tree, `.git`
+/// directory or bare clone) instead of the current directory. When `paths` are
+/// given, only commits touching them are offered.
+/// `message_format` decides how much of each commit message goes into the entry.
+/// With `fixup` (an entry ID or short ID), the captured commits are added to that
+/// entry instead of offering to create a new one.
+/// `mode` decides whether commits are picked and the entry confirmed in prompts.
+/// Returns how many commits were captured.
+#[allow(clippy::too_many_arguments)]
 pub async fn execute(
     auth_service: &mut AuthService,
     limit: u32,
     edit: bool,
-) -> Result<(), AppError> {
-    // Check if current directory is a git repository
-    let current_dir = env::current_dir()
-        .map_err(|e| AppError::ParseError(format!("Failed to get current directory: {e}")))?;
+    date: Option<&str>,
+    no_cache: bool,
+    path: Option<&Path>,
+    paths: &[String],
+    message_format: &CommitMessageFormat,
+    fixup: Option<&str>,
+    mode: CaptureMode,
+    hooks: &Hooks,
+) -> Result<usize, AppError> {
+    let day = date
+        .map(|d| {
+            NaiveDate::parse_from_str(d, "%Y-%m-%d").map_err(|_| {
+                AppError::ParseError(format!("Invalid date format: {d}. Expected YYYY-MM-DD"))
+            })
+        })
+        .transpose()?;
+
+    let current_dir = match path {
+        Some(path) => resolve_repository_dir(path)?,
+        None => {
+            // Check if current directory is a git repository
+            let current_dir = env::current_dir().map_err(|e| {
+                AppError::ParseError(format!("Failed to get current directory: {e}"))
+            })?;
+
+            if !is_git_repository(&current_dir) {
+                return Err(AppError::Other(
+                    "This command must be run in a git repository".to_string(),
+                ));
+            }
+            current_dir
+        }
+    };
 
-    if !is_git_repository(&current_dir) {
-        return Err(AppError::Other(
-            "This command must be run in a git repository".to_string(),
-        ));
+    // Resolve the entry first, so a mistyped ID fails before anything is captured
+    let fixup_entry_id = match fixup {
+        Some(id_or_prefix) => {
+            let entry = logs::find_entry(auth_service.api_client(), id_or_prefix).await?;
+            Some(entry.id)
+        }
+        None => None,
+    };
+
+    // Get recent commits from git
+    let commits = match day {
+        Some(day) => get_commits_on_day(&current_dir, day, limit, paths)?,
+        None => get_recent_commits(&current_dir, limit, paths)?,
+    };
+
+    if commits.is_empty() {
+        let scope = if paths.is_empty() {
+            String::new()
+        } else {
+            format!(" touching {}", paths.join(", "))
+        };
+        if !mode.is_quiet() {
+            match day {
+                Some(day) => println!("No commits{scope} found on {day}."),
+                None => println!("No commits{scope} found in the repository."),
+            }
+        }
+        return Ok(0);
     }
 
-    // Check if directory is initialized (has a project configured)
-    let project_identifier =
-        config::lookup_default_project_for_dir(&current_dir).ok_or_else(|| {
-            AppError::ParseError("Directory not initialized. Run 'acc init' first".to_string())
+    // A branch route decides the entry's project and adds tags. The repository
+    // stays with the directory's project, or else the route's, or else the one
+    // the commits name in a trailer
+    let route = BRANCH_ROUTES
+        .get()
+        .and_then(|routes| routes.route_dir(&current_dir));
+    let repository_project = config::lookup_default_project_for_dir(&current_dir)
+        .or_else(|| route.and_then(|route| route.project.clone()))
+        .or_else(|| commit_message::trailer_project(commits.iter().map(|c| c.message.as_str())))
+        .ok_or_else(|| {
+            AppError::ParseError(format!(
+                "Directory '{}' not initialized. Run 'acc init' there first",
+                current_dir.display()
+            ))
         })?;
 
     // Get the repository from the backend
     let repo_id =
-        get_repository_id_for_project(auth_service, &project_identifier, &current_dir).await?;
-
-    // Get recent commits from git
-    let commits = get_recent_commits(&current_dir, limit)?;
+        get_repository_id_for_project(auth_service, &repository_project, &current_dir).await?;
 
-    if commits.is_empty() {
-        println!("No commits found in the repository.");
-        return Ok(());
+    // Only ask the backend about commits not already known to be captured
+    let mut capture_state = CaptureState::load(auth_service.state_dir());
+    if let Some(key) = repository_key(&current_dir) {
+        capture_state.set_repo_for_dir(&key, &repo_id);
+    }
+    if no_cache {
+        capture_state.clear_repo(&repo_id);
     }
 
-    // Get uncaptured commits from the backend
     let commit_shas: Vec<String> = commits.iter().map(|c| c.sha.clone()).collect();
-    let uncaptured_shas = get_uncaptured_commits(auth_service, &repo_id, &commit_shas).await?;
+    let unknown_shas = capture_state.unknown_shas(&repo_id, &commit_shas);
+
+    let uncaptured_shas = if unknown_shas.is_empty() {
+        Vec::new()
+    } else {
+        get_uncaptured_commits(auth_service, &repo_id, &unknown_shas).await?
+    };
+
+    // Whatever the backend didn't report as uncaptured has already been captured
+    capture_state.mark_captured(
+        &repo_id,
+        unknown_shas
+            .into_iter()
+            .filter(|sha| !uncaptured_shas.contains(sha)),
+    );
+    save_capture_state(&capture_state);
 
     if uncaptured_shas.is_empty() {
-        println!("No new commits to capture.");
-        return Ok(());
+        if !mode.is_quiet() {
+            println!("No new commits to capture.");
+        }
+        return Ok(0);
     }
 
     // Filter commits to only show uncaptured ones
@@ -93,34 +203,61 @@ pub async fn execute(
         .filter(|c| uncaptured_shas.contains(&c.sha))
         .collect();
 
-    // Present interactive selection
-    let options: Vec<String> = uncaptured_commits
-        .iter()
-        .map(|c| format!("{} {}", c.short_sha, c.summary))
-        .collect();
+    let selected_commits: Vec<&GitCommit> = if mode == CaptureMode::Interactive {
+        // Present interactive selection
+        let options: Vec<String> = uncaptured_commits
+            .iter()
+            .map(|c| format!("{} {}", c.short_sha, c.summary))
+            .collect();
+
+        let mut prompt = MultiSelect::new("Select commits to capture:", options.clone())
+            .with_help_message("Use space to select, arrow keys to navigate, enter to confirm");
+        if prompt::assume_defaults() {
+            // Without someone to pick, every uncaptured commit is the sensible answer
+            prompt = prompt.with_all_selected_by_default();
+        }
+        let selected_options = prompt
+            .prompt_or_default()
+            .map_err(|e| AppError::ParseError(format!("Selection failed: {e}")))?;
 
-    let selected_options = MultiSelect::new("Select commits to capture:", options.clone())
-        .with_help_message("Use space to select, arrow keys to navigate, enter to confirm")
-        .prompt()
-        .map_err(|e| AppError::ParseError(format!("Selection failed: {e}")))?;
+        if selected_options.is_empty() {
+            println!("No commits selected.");
+            return Ok(0);
+        }
 
-    if selected_options.is_empty() {
-        println!("No commits selected.");
-        return Ok(());
-    }
+        // Get the selected commits
+        selected_options
+            .iter()
+            .map(|selected_option| {
+                // Find the index of the selected option in the uncaptured_commits
+                let index = options
+                    .iter()
+                    .position(|opt| opt == selected_option)
+                    .unwrap();
+                &uncaptured_commits[index]
+            })
+            .collect()
+    } else {
+        uncaptured_commits.iter().collect()
+    };
 
-    // Get the selected commits
-    let selected_commits: Vec<&GitCommit> = selected_options
-        .iter()
-        .map(|selected_option| {
-            // Find the index of the selected option in the uncaptured_commits
-            let index = options
-                .iter()
-                .position(|opt| opt == selected_option)
-                .unwrap();
-            &uncaptured_commits[index]
-        })
-        .collect();
+    // The route is for work done on the branch: commits that are also on
+    // another branch, like those from before it was cut, aren't routed
+    let route = route.filter(|route| {
+        let shas: Vec<&str> = selected_commits.iter().map(|c| c.sha.as_str()).collect();
+        let on_branch = branch_routes::only_on_current_branch(&current_dir, &shas);
+        if !on_branch && !mode.is_quiet() {
+            println!(
+                "Not applying the branch route '{}': some commits are also on other branches",
+                route.pattern
+            );
+        }
+        on_branch
+    });
+    let project_identifier = route
+        .and_then(|route| route.project.clone())
+        .unwrap_or_else(|| repository_project.clone());
+    let route_tags = route.map(|route| route.tags.as_slice()).unwrap_or_default();
 
     // Create commits in the backend
     let commit_data: Vec<CommitData> = selected_commits
@@ -134,50 +271,170 @@ pub async fn execute(
 
     let created_commits = capture_commits(auth_service, &repo_id, &commit_data).await?;
 
-    println!("✅ Captured {} commits", selected_commits.len());
-
-    // Ask if user wants to create a worklog entry
-    let create_worklog = Confirm::new("Create worklog entry from selected commits?")
-        .with_default(true)
-        .prompt()
-        .map_err(|e| AppError::ParseError(format!("Confirmation failed: {e}")))?;
-
-    if create_worklog {
-        // Extract commit IDs from the API response
-        let commit_ids: Vec<String> = created_commits
-            .get("commits")
-            .and_then(|commits| commits.as_array())
-            .map(|commits| {
-                commits
-                    .iter()
-                    .filter_map(|commit| commit.get("id").and_then(|id| id.as_str()))
-                    .map(|id| id.to_string())
-                    .collect()
-            })
-            .unwrap_or_default();
+    capture_state.mark_captured(&repo_id, selected_commits.iter().map(|c| c.sha.clone()));
+    save_capture_state(&capture_state);
 
-        create_worklog_entry_from_commits(
-            auth_service,
-            &selected_commits,
-            &commit_ids,
-            &project_identifier,
-            edit,
+    if !mode.is_quiet() {
+        println!("✅ Captured {} commits", selected_commits.len());
+    }
+    let commit_ids = created_commit_ids(&created_commits);
+
+    let entry_id = if let Some(entry_id) = fixup_entry_id {
+        associate_commits_with_entry(auth_service.api_client(), &entry_id, &commit_ids)
+            .await
+            .map_err(AppError::Api)?;
+        println!(
+            "🔗 Added {} commits to entry {}",
+            commit_ids.len(),
+            entry_id.get(..8).unwrap_or(&entry_id)
+        );
+        Some(entry_id)
+    } else if match mode {
+        CaptureMode::Interactive => Confirm::new("Create worklog entry from selected commits?")
+            .with_default(true)
+            .prompt_or_default()
+            .map_err(|e| AppError::ParseError(format!("Confirmation failed: {e}")))?,
+        CaptureMode::Auto { log, .. } => log,
+    } {
+        // When backfilling a past day, record the entry when the work happened
+        let recorded_at = day.and_then(|_| {
+            selected_commits
+                .iter()
+                .map(|c| c.committed_at)
+                .max()
+                .map(|t| t.to_rfc3339())
+        });
+
+        Some(
+            create_worklog_entry_from_commits(
+                auth_service,
+                &selected_commits,
+                &commit_ids,
+                &project_identifier,
+                route_tags,
+                edit,
+                recorded_at.as_deref(),
+                message_format,
+                mode.is_quiet(),
+            )
+            .await?,
         )
-        .await?;
+    } else {
+        None
+    };
+
+    let hook_data = json!({
+        "repository_id": repo_id,
+        "project": project_identifier,
+        "commits": commit_data,
+        "entry_id": entry_id,
+    });
+    // post-* hooks only warn on failure
+    let _ = hooks.run(HookEvent::PostCapture, hook_data);
+
+    Ok(selected_commits.len())
+}
+
+/// How `execute` picks commits and decides on the entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaptureMode {
+    /// Commits are picked from a list and the entry is confirmed
+    Interactive,
+    /// Every uncaptured commit is captured without prompting, for cron and git
+    /// hooks. An entry is created from them only when `log` is set, and with
+    /// `quiet` nothing is printed unless something goes wrong.
+    Auto { log: bool, quiet: bool },
+}
+
+impl CaptureMode {
+    fn is_quiet(&self) -> bool {
+        matches!(self, CaptureMode::Auto { quiet: true, .. })
     }
+}
 
-    Ok(())
+/// IDs of the commits `create_commits` stored.
+fn created_commit_ids(commits: &[models::Commit]) -> Vec<String> {
+    commits.iter().filter_map(|c| c.id.clone()).collect()
+}
+
+/// Persists the capture cache; failures only cost a slower next run, so just warn
+fn save_capture_state(state: &CaptureState) {
+    if let Err(e) = state.save() {
+        eprintln!("⚠️  Warning: Could not save capture cache: {e}");
+    }
+}
+
+/// Counting uncaptured commits stops here, so it stays quick in long histories.
+pub const UNCAPTURED_COUNT_LIMIT: usize = 99;
+
+/// How many of the newest commits in the repository containing `dir` come
+/// after the last one known to be captured, from the local capture cache
+/// only. None outside a repository, or one `acc capture` hasn't run in.
+/// Stops counting at `UNCAPTURED_COUNT_LIMIT`.
+pub fn uncaptured_count(dir: &Path, state: &CaptureState) -> Option<usize> {
+    let key = repository_key(dir)?;
+    let repo_id = state.repo_for_dir(&key)?;
+    let mut count = 0;
+    walk_commits(&key, &[], |commit| {
+        if state.is_captured(repo_id, &commit.sha) {
+            return false;
+        }
+        count += 1;
+        count < UNCAPTURED_COUNT_LIMIT
+    })
+    .ok()?;
+    Some(count)
+}
+
+/// `count` for display, with a `+` once counting stopped at the limit.
+pub fn format_uncaptured(count: usize) -> String {
+    if count >= UNCAPTURED_COUNT_LIMIT {
+        format!("{UNCAPTURED_COUNT_LIMIT}+")
+    } else {
+        count.to_string()
+    }
+}
+
+/// The working tree (or bare repository) containing `dir`, normalized so the
+/// same clone always gives the same key.
+pub fn repository_key(dir: &Path) -> Option<PathBuf> {
+    let repo = Repository::discover(dir).ok()?;
+    let dir = repo.workdir().unwrap_or_else(|| repo.path());
+    Some(dir.canonicalize().ok()?.components().collect())
 }
 
 /// Checks if the given directory is a git repository
-fn is_git_repository(dir: &Path) -> bool {
+pub fn is_git_repository(dir: &Path) -> bool {
     Repository::open(dir).is_ok()
 }
 
-/// Gets recent commits from the git repository
-fn get_recent_commits(dir: &Path, limit: u32) -> Result<Vec<GitCommit>, AppError> {
+/// Resolves an explicit repository path to the directory used for project lookup
+/// and repository matching: the working tree for regular repositories (even when
+/// given their `.git` directory), or the repository directory itself for bare clones.
+pub fn resolve_repository_dir(path: &Path) -> Result<PathBuf, AppError> {
+    let path = path
+        .canonicalize()
+        .map_err(|e| AppError::Other(format!("Cannot access '{}': {e}", path.display())))?;
+
+    let repo = Repository::open(&path)
+        .map_err(|_| AppError::Other(format!("'{}' is not a git repository", path.display())))?;
+
+    let dir = repo.workdir().unwrap_or_else(|| repo.path());
+
+    // git2 reports directories with a trailing slash; normalize for path matching
+    Ok(dir.components().collect())
+}
+
+/// Gets recent commits from the git repository, only counting those that
+/// touch `paths` when any are given
+pub fn get_recent_commits(
+    dir: &Path,
+    limit: u32,
+    paths: &[String],
+) -> Result<Vec<GitCommit>, AppError> {
     let repo = Repository::open(dir)
         .map_err(|e| AppError::ParseError(format!("Failed to open git repository: {e}")))?;
+    let mut path_filter = PathFilter::new(paths);
 
     let mut revwalk = repo
         .revwalk()
@@ -189,8 +446,8 @@ fn get_recent_commits(dir: &Path, limit: u32) -> Result<Vec<GitCommit>, AppError
 
     let mut commits = Vec::new();
 
-    for (count, oid) in revwalk.enumerate() {
-        if count >= limit as usize {
+    for oid in revwalk {
+        if commits.len() >= limit as usize {
             break;
         }
 
@@ -200,12 +457,153 @@ fn get_recent_commits(dir: &Path, limit: u32) -> Result<Vec<GitCommit>, AppError
             .find_commit(oid)
             .map_err(|e| AppError::ParseError(format!("Failed to find commit: {e}")))?;
 
-        commits.push(GitCommit::from_git2_commit(&commit)?);
+        if path_filter.matches(&repo, &commit)? {
+            commits.push(GitCommit::from_git2_commit(&commit)?);
+        }
+    }
+
+    Ok(commits)
+}
+
+/// Walks history from HEAD in time order, yielding commits (touching `paths`, when
+/// given) until `keep_going` returns false.
+fn walk_commits<F>(dir: &Path, paths: &[String], mut keep_going: F) -> Result<(), AppError>
+where
+    F: FnMut(GitCommit) -> bool,
+{
+    let repo = Repository::open(dir)
+        .map_err(|e| AppError::ParseError(format!("Failed to open git repository: {e}")))?;
+    let mut path_filter = PathFilter::new(paths);
+
+    let mut revwalk = repo
+        .revwalk()
+        .map_err(|e| AppError::ParseError(format!("Failed to create revision walker: {e}")))?;
+
+    revwalk
+        .set_sorting(Sort::TIME)
+        .map_err(|e| AppError::ParseError(format!("Failed to sort revisions: {e}")))?;
+
+    revwalk
+        .push_head()
+        .map_err(|e| AppError::ParseError(format!("Failed to push HEAD: {e}")))?;
+
+    for oid in revwalk {
+        let oid =
+            oid.map_err(|e| AppError::ParseError(format!("Failed to get commit OID: {e}")))?;
+        let commit = repo
+            .find_commit(oid)
+            .map_err(|e| AppError::ParseError(format!("Failed to find commit: {e}")))?;
+
+        if !path_filter.matches(&repo, &commit)? {
+            continue;
+        }
+        if !keep_going(GitCommit::from_git2_commit(&commit)?) {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+/// Pathspecs (e.g. `src/billing/**`) a commit must touch to be offered. Paths are
+/// relative to the repository root; an empty list matches every commit.
+struct PathFilter {
+    options: Option<DiffOptions>,
+}
+
+impl PathFilter {
+    fn new(paths: &[String]) -> Self {
+        if paths.is_empty() {
+            return Self { options: None };
+        }
+        let mut options = DiffOptions::new();
+        for path in paths {
+            options.pathspec(path);
+        }
+        Self {
+            options: Some(options),
+        }
+    }
+
+    /// Whether the commit changes a matching path compared to its first parent
+    /// (or, for a root commit, adds one).
+    fn matches(&mut self, repo: &Repository, commit: &Commit) -> Result<bool, AppError> {
+        let Some(options) = self.options.as_mut() else {
+            return Ok(true);
+        };
+        let tree = commit
+            .tree()
+            .map_err(|e| AppError::ParseError(format!("Failed to read commit tree: {e}")))?;
+        let parent_tree =
+            match commit.parent(0) {
+                Ok(parent) => Some(parent.tree().map_err(|e| {
+                    AppError::ParseError(format!("Failed to read commit tree: {e}"))
+                })?),
+                Err(_) => None,
+            };
+        let diff = repo
+            .diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), Some(options))
+            .map_err(|e| AppError::ParseError(format!("Failed to diff commit: {e}")))?;
+        Ok(diff.deltas().len() > 0)
     }
+}
+
+/// Gets up to `limit` commits made on the given local calendar day
+fn get_commits_on_day(
+    dir: &Path,
+    day: NaiveDate,
+    limit: u32,
+    paths: &[String],
+) -> Result<Vec<GitCommit>, AppError> {
+    let mut commits = Vec::new();
+
+    walk_commits(dir, paths, |commit| {
+        let commit_day = commit.committed_at.with_timezone(&Local).date_naive();
+        if commit_day < day {
+            return false;
+        }
+        if commit_day == day {
+            commits.push(commit);
+        }
+        commits.len() < limit as usize
+    })?;
 
     Ok(commits)
 }
 
+/// Gets all commits made since the given time, optionally restricted to one author email
+pub fn get_commits_since(
+    dir: &Path,
+    since: DateTime<Utc>,
+    author_email: Option<&str>,
+) -> Result<Vec<GitCommit>, AppError> {
+    let mut commits = Vec::new();
+
+    walk_commits(dir, &[], |commit| {
+        if commit.committed_at < since {
+            return false;
+        }
+        let is_author = match (author_email, commit.author_email.as_deref()) {
+            (Some(wanted), Some(email)) => wanted.eq_ignore_ascii_case(email),
+            (Some(_), None) => false,
+            (None, _) => true,
+        };
+        if is_author {
+            commits.push(commit);
+        }
+        true
+    })?;
+
+    Ok(commits)
+}
+
+/// Reads `user.email` from the repository's git configuration (including global config)
+pub fn get_git_user_email(dir: &Path) -> Option<String> {
+    let repo = Repository::open(dir).ok()?;
+    let config = repo.config().ok()?;
+    config.get_string("user.email").ok()
+}
+
 /// Gets the repository ID for the given project from the backend
 async fn get_repository_id_for_project(
     auth_service: &mut AuthService,
@@ -213,45 +611,26 @@ async fn get_repository_id_for_project(
     current_dir: &Path,
 ) -> Result<String, AppError> {
     // Get all projects to find the one with the given identifier
-    let projects_response = fetch_projects(auth_service.api_client())
+    let projects = fetch_projects(auth_service.api_client())
         .await
         .map_err(AppError::Api)?;
 
-    let projects = projects_response
-        .get("projects")
-        .and_then(|v| v.as_array())
-        .ok_or_else(|| AppError::ParseError("Invalid projects response format".to_string()))?;
-
     // Find the project with the matching identifier
-    let target_project = projects
+    let project_id = projects
         .iter()
-        .find(|p| {
-            p.get("identifier")
-                .and_then(|v| v.as_str())
-                .map(|s| s.to_lowercase())
-                == Some(project_identifier.to_lowercase())
-        })
+        .find(|p| p.identifier.eq_ignore_ascii_case(project_identifier))
+        .map(|p| p.id.as_str())
         .ok_or_else(|| AppError::ParseError(format!("Project '{project_identifier}' not found")))?;
 
-    let project_id = target_project
-        .get("id")
-        .and_then(|v| v.as_str())
-        .ok_or_else(|| AppError::ParseError("Project ID not found".to_string()))?;
-
     // Get repositories for this project
-    let repos_response = crate::api::endpoints::fetch_repositories(auth_service.api_client())
+    let repositories = crate::api::endpoints::fetch_repositories(auth_service.api_client())
         .await
         .map_err(AppError::Api)?;
 
-    let repositories = repos_response
-        .get("repositories")
-        .and_then(|v| v.as_array())
-        .ok_or_else(|| AppError::ParseError("Invalid repositories response format".to_string()))?;
-
     // Filter repositories for this project
     let project_repos: Vec<_> = repositories
         .iter()
-        .filter(|repo| repo.get("project_id").and_then(|v| v.as_str()) == Some(project_id))
+        .filter(|repo| repo.project_id.as_deref() == Some(project_id))
         .collect();
 
     if project_repos.is_empty() {
@@ -267,32 +646,21 @@ async fn get_repository_id_for_project(
     let current_remote = get_git_remote_url(current_dir);
 
     // Try to match by local_path first
-    if let Some(repo) = project_repos.iter().find(|repo| {
-        repo.get("local_path")
-            .and_then(|v| v.as_str())
-            .map(|path| path == current_path)
-            .unwrap_or(false)
-    }) {
-        return repo
-            .get("id")
-            .and_then(|v| v.as_str())
-            .map(|s| s.to_string())
-            .ok_or_else(|| AppError::ParseError("Repository ID not found".to_string()));
+    if let Some(repo) = project_repos
+        .iter()
+        .find(|repo| repo.local_path.as_deref() == Some(current_path.as_str()))
+    {
+        return Ok(repo.id.clone());
     }
 
     // Try to match by remote_url if local_path didn't match
     if let Some(ref remote_url) = current_remote {
         if let Some(repo) = project_repos.iter().find(|repo| {
-            repo.get("remote_url")
-                .and_then(|v| v.as_str())
-                .map(|url| normalize_git_url(url) == normalize_git_url(remote_url))
-                .unwrap_or(false)
+            repo.remote_url
+                .as_deref()
+                .is_some_and(|url| normalize_git_url(url) == normalize_git_url(remote_url))
         }) {
-            return repo
-                .get("id")
-                .and_then(|v| v.as_str())
-                .map(|s| s.to_string())
-                .ok_or_else(|| AppError::ParseError("Repository ID not found".to_string()));
+            return Ok(repo.id.clone());
         }
     }
 
@@ -313,7 +681,7 @@ fn get_git_remote_url(dir: &Path) -> Option<String> {
 }
 
 /// Normalizes git URLs for comparison (handles differences like .git suffix, SSH vs HTTPS)
-fn normalize_git_url(url: &str) -> String {
+pub fn normalize_git_url(url: &str) -> String {
     let mut normalized = url.to_string();
 
     // Remove .git suffix if present
@@ -364,7 +732,7 @@ async fn capture_commits(
     auth_service: &mut AuthService,
     repo_id: &str,
     commit_data: &[CommitData],
-) -> Result<serde_json::Value, AppError> {
+) -> Result<Vec<models::Commit>, AppError> {
     let response = create_commits(auth_service.api_client(), repo_id, commit_data)
         .await
         .map_err(AppError::Api)?;
@@ -372,22 +740,68 @@ async fn capture_commits(
     Ok(response)
 }
 
+/// Tags and custom fields crediting the `Co-authored-by` trailers of the
+/// commits, other than the commits' own authors.
+fn co_author_metadata(
+    commits: &[&GitCommit],
+    mode: CoAuthorMode,
+) -> (Vec<String>, Option<Map<String, Value>>) {
+    if mode == CoAuthorMode::None {
+        return (Vec::new(), None);
+    }
+    let authors: Vec<&str> = commits
+        .iter()
+        .filter_map(|c| c.author_email.as_deref())
+        .collect();
+    let co_authors =
+        commit_message::co_authors(commits.iter().map(|c| c.message.as_str()), &authors);
+    if co_authors.is_empty() {
+        return (Vec::new(), None);
+    }
+
+    let tags = match mode {
+        CoAuthorMode::Tag => co_authors.iter().map(|c| c.tag()).collect(),
+        _ => Vec::new(),
+    };
+    let mut fields = Map::new();
+    fields.insert(
+        "co_authors".to_string(),
+        json!(co_authors
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()),
+    );
+    (tags, Some(fields))
+}
+
 /// Creates a worklog entry from the selected commits
+#[allow(clippy::too_many_arguments)]
 async fn create_worklog_entry_from_commits(
     auth_service: &mut AuthService,
     commits: &[&GitCommit],
     commit_ids: &[String],
     project_identifier: &str,
+    route_tags: &[String],
     edit: bool,
-) -> Result<(), AppError> {
+    recorded_at: Option<&str>,
+    message_format: &CommitMessageFormat,
+    quiet: bool,
+) -> Result<String, AppError> {
     // Create content from commit messages
+    let formatted: Vec<String> = commits
+        .iter()
+        .map(|c| message_format.apply(&c.message))
+        .collect();
+    let (mut tags, fields) = co_author_metadata(commits, message_format.co_authors);
+    for tag in route_tags {
+        if !tags.contains(tag) {
+            tags.push(tag.clone());
+        }
+    }
+
     let messages: Vec<String> = if edit {
         // Pre-fill the editor with commit messages
-        let prefilled_content = commits
-            .iter()
-            .map(|c| c.message.trim())
-            .collect::<Vec<&str>>()
-            .join("\n\n");
+        let prefilled_content = formatted.join("\n\n");
 
         // Create template with commit messages
         let template = format!(
@@ -412,14 +826,32 @@ async fn create_worklog_entry_from_commits(
             }
         }
     } else {
-        commits
-            .iter()
-            .map(|c| c.message.trim().to_string())
-            .collect()
+        formatted
     };
 
     // Create the worklog entry first
-    let entry_id = log::execute(auth_service, &messages, &[], Some(project_identifier)).await?;
+    let entry_id = if quiet {
+        log::create(
+            auth_service,
+            &messages,
+            &tags,
+            Some(project_identifier),
+            recorded_at,
+            fields.as_ref(),
+        )
+        .await?
+        .id
+    } else {
+        log::execute(
+            auth_service,
+            &messages,
+            &tags,
+            Some(project_identifier),
+            recorded_at,
+            fields.as_ref(),
+        )
+        .await?
+    };
 
     // Associate the commits with the worklog entry
     if !commit_ids.is_empty() {
@@ -427,13 +859,15 @@ async fn create_worklog_entry_from_commits(
             .await
             .map_err(AppError::Api)?;
 
-        println!(
-            "🔗 Associated {} commits with worklog entry",
-            commit_ids.len()
-        );
+        if !quiet {
+            println!(
+                "🔗 Associated {} commits with worklog entry",
+                commit_ids.len()
+            );
+        }
     }
 
-    Ok(())
+    Ok(entry_id)
 }
 
 #[cfg(test)]
@@ -441,6 +875,16 @@ mod tests {
     use super::*;
     use tempfile::TempDir;
 
+    #[test]
+    fn test_created_commit_ids() {
+        let response: models::CommitsResponse = serde_json::from_value(json!({
+            "commits": [{ "id": "c1", "sha": "abc" }, { "sha": "def" }, { "id": "c3" }]
+        }))
+        .unwrap();
+        assert_eq!(created_commit_ids(&response.commits), vec!["c1", "c3"]);
+        assert!(created_commit_ids(&[]).is_empty());
+    }
+
     #[test]
     fn test_is_git_repository_true() {
         let temp_dir = TempDir::new().unwrap();
@@ -457,6 +901,245 @@ mod tests {
         assert!(!is_git_repository(temp_dir.path()));
     }
 
+    fn commit_at(repo: &Repository, message: &str, email: &str, timestamp: i64) {
+        let sig = git2::Signature::new("Test User", email, &git2::Time::new(timestamp, 0)).unwrap();
+        let tree_id = repo.index().unwrap().write_tree().unwrap();
+        let tree = repo.find_tree(tree_id).unwrap();
+        let parents: Vec<Commit> = repo
+            .head()
+            .ok()
+            .and_then(|h| h.peel_to_commit().ok())
+            .into_iter()
+            .collect();
+        let parent_refs: Vec<&Commit> = parents.iter().collect();
+        repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &parent_refs)
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_auto_captures_every_uncaptured_commit_without_an_entry() {
+        let temp_dir = TempDir::new().unwrap();
+        let state_dir = TempDir::new().unwrap();
+        let repo_dir = temp_dir.path().canonicalize().unwrap();
+        let repo = Repository::init(&repo_dir).unwrap();
+        let now = Utc::now().timestamp();
+        commit_at(&repo, "First", "me@example.com", now - 60);
+        commit_at(&repo, "Second", "me@example.com", now);
+        std::fs::write(
+            repo_dir.join(".accomplish.toml"),
+            "[project]\ndefault_project = \"web\"\n",
+        )
+        .unwrap();
+
+        let mut server = mockito::Server::new_async().await;
+        let _projects = server
+            .mock("GET", "/api/v1/projects")
+            .with_body(r#"{"projects":[{"id":"p1","identifier":"web","name":"Web"}]}"#)
+            .create_async()
+            .await;
+        let _repositories = server
+            .mock("GET", "/api/v1/repositories")
+            .with_body(
+                json!({ "repositories": [
+                    { "id": "r1", "project_id": "p1", "local_path": repo_dir.to_string_lossy() }
+                ]})
+                .to_string(),
+            )
+            .create_async()
+            .await;
+        let shas: Vec<String> = get_recent_commits(&repo_dir, 25, &[])
+            .unwrap()
+            .into_iter()
+            .map(|c| c.sha)
+            .collect();
+        let _uncaptured = server
+            .mock("GET", "/api/v1/repositories/r1/commits")
+            .match_query(mockito::Matcher::Any)
+            .with_body(json!({ "uncaptured_shas": shas }).to_string())
+            .create_async()
+            .await;
+        let captured = server
+            .mock("POST", "/api/v1/repositories/r1/commits")
+            .with_status(201)
+            .with_body(r#"{"commits":[{"id":"c1"},{"id":"c2"}]}"#)
+            .expect(1)
+            .create_async()
+            .await;
+        let entries = server
+            .mock("POST", "/api/v1/worklog/entries")
+            .expect(0)
+            .create_async()
+            .await;
+
+        let mut auth_service =
+            AuthService::new(server.url(), state_dir.path().to_path_buf(), "test-profile");
+        auth_service.save_access_token("dummy-token").unwrap();
+        let hooks = Hooks::new(state_dir.path().join("hooks"), "test-profile");
+        execute(
+            &mut auth_service,
+            25,
+            false,
+            None,
+            false,
+            Some(&repo_dir),
+            &[],
+            &CommitMessageFormat::default(),
+            None,
+            CaptureMode::Auto {
+                log: false,
+                quiet: true,
+            },
+            &hooks,
+        )
+        .await
+        .unwrap();
+
+        captured.assert_async().await;
+        entries.assert_async().await;
+        let state = CaptureState::load(auth_service.state_dir());
+        assert!(shas.iter().all(|sha| state.is_captured("r1", sha)));
+    }
+
+    #[test]
+    fn test_get_commits_since_filters_by_time_and_author() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = Repository::init(temp_dir.path()).unwrap();
+
+        let now = Utc::now().timestamp();
+        commit_at(&repo, "Old work", "me@example.com", now - 10 * 86_400);
+        commit_at(&repo, "Teammate work", "them@example.com", now - 3_600);
+        commit_at(&repo, "Recent work", "me@example.com", now - 60);
+
+        let since = Utc::now() - chrono::Duration::days(2);
+        let mine = get_commits_since(temp_dir.path(), since, Some("ME@example.com")).unwrap();
+        assert_eq!(mine.len(), 1);
+        assert_eq!(mine[0].summary, "Recent work");
+
+        let everyone = get_commits_since(temp_dir.path(), since, None).unwrap();
+        assert_eq!(everyone.len(), 2);
+    }
+
+    #[test]
+    fn test_get_recent_commits_filtered_by_paths() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = Repository::init(temp_dir.path()).unwrap();
+        let now = Utc::now().timestamp();
+        for (file, message) in [
+            ("src/billing/invoice.rs", "Add invoices"),
+            ("src/web/app.rs", "Add web app"),
+            ("src/billing/tax/vat.rs", "Add VAT"),
+        ] {
+            let full_path = temp_dir.path().join(file);
+            std::fs::create_dir_all(full_path.parent().unwrap()).unwrap();
+            std::fs::write(&full_path, message).unwrap();
+            let mut index = repo.index().unwrap();
+            index.add_path(Path::new(file)).unwrap();
+            index.write().unwrap();
+            commit_at(&repo, message, "me@example.com", now);
+        }
+
+        let summaries = |paths: &[&str], limit| -> Vec<String> {
+            let paths: Vec<String> = paths.iter().map(|p| p.to_string()).collect();
+            get_recent_commits(temp_dir.path(), limit, &paths)
+                .unwrap()
+                .into_iter()
+                .map(|c| c.summary)
+                .collect()
+        };
+
+        assert_eq!(
+            summaries(&["src/billing/**"], 10),
+            ["Add VAT", "Add invoices"]
+        );
+        // The limit counts matching commits only
+        assert_eq!(summaries(&["src/web", "src/billing/tax"], 1), ["Add VAT"]);
+        assert_eq!(summaries(&[], 10).len(), 3);
+        assert!(summaries(&["docs/**"], 10).is_empty());
+    }
+
+    #[test]
+    fn test_uncaptured_count_stops_at_last_captured_commit() {
+        let temp_dir = TempDir::new().unwrap();
+        let state_dir = TempDir::new().unwrap();
+        let repo = Repository::init(temp_dir.path()).unwrap();
+        let now = Utc::now().timestamp();
+        commit_at(&repo, "Captured", "me@example.com", now - 120);
+        let captured = repo.head().unwrap().target().unwrap().to_string();
+        commit_at(&repo, "New", "me@example.com", now - 60);
+        commit_at(&repo, "Newer", "me@example.com", now);
+
+        let mut state = CaptureState::load(state_dir.path());
+        assert_eq!(uncaptured_count(temp_dir.path(), &state), None);
+
+        state.set_repo_for_dir(&repository_key(temp_dir.path()).unwrap(), "repo-1");
+        state.mark_captured("repo-1", vec![captured]);
+        assert_eq!(uncaptured_count(temp_dir.path(), &state), Some(2));
+
+        // Also from a subdirectory of the working tree
+        let sub = temp_dir.path().join("src");
+        std::fs::create_dir(&sub).unwrap();
+        assert_eq!(uncaptured_count(&sub, &state), Some(2));
+        assert_eq!(format_uncaptured(UNCAPTURED_COUNT_LIMIT), "99+");
+    }
+
+    #[test]
+    fn test_get_commits_on_day() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = Repository::init(temp_dir.path()).unwrap();
+
+        let now = Utc::now().timestamp();
+        commit_at(&repo, "Two days ago", "me@example.com", now - 2 * 86_400);
+        commit_at(&repo, "Just now", "me@example.com", now);
+
+        let two_days_ago = DateTime::from_timestamp(now - 2 * 86_400, 0)
+            .unwrap()
+            .with_timezone(&Local)
+            .date_naive();
+        let commits = get_commits_on_day(temp_dir.path(), two_days_ago, 25, &[]).unwrap();
+        assert_eq!(commits.len(), 1);
+        assert_eq!(commits[0].summary, "Two days ago");
+    }
+
+    #[test]
+    fn test_resolve_repository_dir_for_working_tree() {
+        let temp_dir = TempDir::new().unwrap();
+        Repository::init(temp_dir.path()).unwrap();
+        let expected = temp_dir.path().canonicalize().unwrap();
+
+        assert_eq!(resolve_repository_dir(temp_dir.path()).unwrap(), expected);
+        assert_eq!(
+            resolve_repository_dir(&temp_dir.path().join(".git")).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_resolve_repository_dir_for_bare_repository() {
+        let temp_dir = TempDir::new().unwrap();
+        let bare_path = temp_dir.path().join("project.git");
+        let repo = Repository::init_bare(&bare_path).unwrap();
+        commit_at(
+            &repo,
+            "Bare commit",
+            "me@example.com",
+            Utc::now().timestamp(),
+        );
+
+        let resolved = resolve_repository_dir(&bare_path).unwrap();
+        assert_eq!(resolved, bare_path.canonicalize().unwrap());
+
+        let commits = get_recent_commits(&resolved, 10, &[]).unwrap();
+        assert_eq!(commits.len(), 1);
+        assert_eq!(commits[0].summary, "Bare commit");
+    }
+
+    #[test]
+    fn test_resolve_repository_dir_rejects_non_repository() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(resolve_repository_dir(temp_dir.path()).is_err());
+        assert!(resolve_repository_dir(&temp_dir.path().join("missing")).is_err());
+    }
+
     #[test]
     fn test_normalize_git_url() {
         // Test .git suffix removal
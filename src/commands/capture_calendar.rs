@@ -0,0 +1,120 @@
+use crate::auth::AuthService;
+use crate::commands::log;
+use crate::errors::AppError;
+use crate::integrations::calendar::{CalendarConfig, CalendarEvent};
+use crate::utils::prompt;
+use chrono::{Duration, Local, NaiveDate, TimeZone, Utc};
+use inquire::MultiSelect;
+use serde_json::{json, Map, Value};
+
+/// Lists a day's meetings from the configured calendar and logs the selected
+/// ones as entries, recorded at the meeting's start with its duration and
+/// attendee count as custom fields.
+pub async fn execute(
+    auth_service: &mut AuthService,
+    config: Option<&CalendarConfig>,
+    date: Option<&str>,
+    project_identifier: Option<&str>,
+) -> Result<(), AppError> {
+    let config = config.ok_or_else(|| {
+        AppError::Other(
+            "No calendar configured. Add an [<profile>.integrations.calendar] section to ~/.accomplish/config.toml"
+                .to_string(),
+        )
+    })?;
+
+    let day = match date {
+        Some(d) => NaiveDate::parse_from_str(d, "%Y-%m-%d").map_err(|_| {
+            AppError::ParseError(format!("Invalid date format: {d}. Expected YYYY-MM-DD"))
+        })?,
+        None => Local::now().date_naive(),
+    };
+    let start_of_day = Local
+        .from_local_datetime(&day.and_hms_opt(0, 0, 0).unwrap())
+        .earliest()
+        .ok_or_else(|| AppError::Other(format!("Could not determine the start of {day}")))?
+        .with_timezone(&Utc);
+    let end_of_day = start_of_day + Duration::days(1);
+
+    let events = config
+        .fetch_events(auth_service.state_dir(), start_of_day, end_of_day)
+        .await?;
+    if events.is_empty() {
+        println!("No meetings found on {day}.");
+        return Ok(());
+    }
+
+    let options: Vec<String> = events.iter().map(describe_event).collect();
+    let mut prompt = MultiSelect::new("Select meetings to log:", options)
+        .with_help_message("Use space to select, arrow keys to navigate, enter to confirm");
+    if prompt::assume_defaults() {
+        prompt = prompt.with_all_selected_by_default();
+    }
+    let selected = prompt::raw_prompt_or_default(prompt)
+        .map_err(|e| AppError::ParseError(format!("Selection failed: {e}")))?;
+
+    if selected.is_empty() {
+        println!("No meetings selected.");
+        return Ok(());
+    }
+
+    for choice in &selected {
+        let event = &events[choice.index];
+        log::execute(
+            auth_service,
+            std::slice::from_ref(&event.title),
+            &config.tags,
+            project_identifier,
+            Some(&event.start.to_rfc3339()),
+            Some(&meeting_fields(event)),
+        )
+        .await?;
+    }
+
+    println!("✅ Logged {} meetings", selected.len());
+    Ok(())
+}
+
+fn describe_event(event: &CalendarEvent) -> String {
+    let start = event.start.with_timezone(&Local).format("%H:%M");
+    let end = event.end.with_timezone(&Local).format("%H:%M");
+    let attendees = match event.attendees {
+        0 | 1 => String::new(),
+        n => format!(", {n} attendees"),
+    };
+    format!(
+        "{start}–{end} {} ({} min{attendees})",
+        event.title,
+        event.duration_minutes()
+    )
+}
+
+fn meeting_fields(event: &CalendarEvent) -> Map<String, Value> {
+    let mut fields = Map::new();
+    fields.insert(
+        "duration_minutes".to_string(),
+        json!(event.duration_minutes()),
+    );
+    fields.insert("attendees".to_string(), json!(event.attendees));
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_meeting_fields() {
+        let event = CalendarEvent {
+            title: "Retro".to_string(),
+            start: "2025-07-09T15:00:00Z".parse().unwrap(),
+            end: "2025-07-09T16:00:00Z".parse().unwrap(),
+            attendees: 6,
+        };
+        assert_eq!(
+            Value::Object(meeting_fields(&event)),
+            json!({ "duration_minutes": 60, "attendees": 6 })
+        );
+        assert!(describe_event(&event).ends_with(" Retro (60 min, 6 attendees)"));
+    }
+}
@@ -0,0 +1,303 @@
+use crate::auth::AuthService;
+use crate::commands::capture::{is_git_repository, resolve_repository_dir};
+use crate::commands::log;
+use crate::config;
+use crate::errors::AppError;
+use crate::utils::duration::parse_since_duration;
+use crate::utils::prompt::PromptOrDefault;
+use chrono::{DateTime, Utc};
+use git2::Repository;
+use inquire::Confirm;
+use std::env;
+use std::path::{Path, PathBuf};
+
+/// Tag added to context switching entries.
+const CONTEXT_SWITCHING_TAG: &str = "context-switching";
+
+/// A reflog line worth mentioning.
+#[derive(Debug, Clone, PartialEq)]
+enum Activity {
+    Switch { from: String, to: String },
+    Rebase { branch: String },
+    Stash { description: String },
+}
+
+/// Summarizes branch switches, rebases and stashes since `since` (e.g. `1d`)
+/// into a single "context switching" entry, for work that never produced
+/// commits. The summary is shown (or opened in the editor with `edit`) before
+/// anything is logged.
+pub async fn execute(
+    auth_service: &mut AuthService,
+    path: Option<&Path>,
+    since: &str,
+    edit: bool,
+    default_project: Option<&str>,
+) -> Result<(), AppError> {
+    let dir = repository_dir(path)?;
+    let cutoff: DateTime<Utc> = parse_since_duration(since)?
+        .parse()
+        .map_err(|e| AppError::ParseError(format!("Invalid --since value '{since}': {e}")))?;
+
+    let activity = read_activity(&dir, cutoff)?;
+    let Some(summary) = summarize(&activity) else {
+        println!("No branch switches, rebases or stashes since {since}.");
+        return Ok(());
+    };
+
+    let content = if edit {
+        let template = format!(
+            "# Edit the context switching summary below\n\
+             # Lines starting with # will be ignored\n\n{summary}\n"
+        );
+        let content = crate::utils::editor::open_in_editor(Some(&template))?;
+        if content.is_empty() {
+            return Err(AppError::Other(
+                "No content provided. Aborting.".to_string(),
+            ));
+        }
+        content
+    } else {
+        println!("{summary}\n");
+        let confirmed = Confirm::new("Log this as an entry?")
+            .with_default(true)
+            .prompt_or_default()
+            .map_err(|e| AppError::ParseError(format!("Confirmation failed: {e}")))?;
+        if !confirmed {
+            return Ok(());
+        }
+        summary
+    };
+
+    let project = config::lookup_default_project_for_dir(&dir)
+        .or_else(|| default_project.map(str::to_string));
+    log::execute(
+        auth_service,
+        &[content],
+        &[CONTEXT_SWITCHING_TAG.to_string()],
+        project.as_deref(),
+        None,
+        None,
+    )
+    .await?;
+
+    Ok(())
+}
+
+fn repository_dir(path: Option<&Path>) -> Result<PathBuf, AppError> {
+    if let Some(path) = path {
+        return resolve_repository_dir(path);
+    }
+    let current_dir = env::current_dir()
+        .map_err(|e| AppError::ParseError(format!("Failed to get current directory: {e}")))?;
+    if !is_git_repository(&current_dir) {
+        return Err(AppError::Other(
+            "This command must be run in a git repository".to_string(),
+        ));
+    }
+    Ok(current_dir)
+}
+
+/// Reads switches and rebases from the HEAD reflog and stashes from the stash
+/// reflog, oldest first.
+fn read_activity(dir: &Path, cutoff: DateTime<Utc>) -> Result<Vec<Activity>, AppError> {
+    let repo = Repository::open(dir)
+        .map_err(|e| AppError::ParseError(format!("Failed to open git repository: {e}")))?;
+
+    let mut timed = Vec::new();
+    for (reference, is_stash) in [("HEAD", false), ("refs/stash", true)] {
+        // A missing reflog (e.g. nothing was ever stashed) is simply empty
+        let Ok(reflog) = repo.reflog(reference) else {
+            continue;
+        };
+        for entry in reflog.iter() {
+            let when = entry.committer().when();
+            let Some(at) = DateTime::from_timestamp(when.seconds(), 0) else {
+                continue;
+            };
+            if at < cutoff {
+                continue;
+            }
+            let message = entry.message().unwrap_or("");
+            let activity = if is_stash {
+                Some(Activity::Stash {
+                    description: message.to_string(),
+                })
+            } else {
+                parse_head_message(message)
+            };
+            timed.extend(activity.map(|a| (at, a)));
+        }
+    }
+
+    timed.sort_by_key(|(at, _)| *at);
+    Ok(timed.into_iter().map(|(_, activity)| activity).collect())
+}
+
+/// Recognizes `checkout: moving from a to b` and the end of a rebase.
+fn parse_head_message(message: &str) -> Option<Activity> {
+    if let Some(moves) = message.strip_prefix("checkout: moving from ") {
+        let (from, to) = moves.split_once(" to ")?;
+        return (from != to).then(|| Activity::Switch {
+            from: from.to_string(),
+            to: to.trim().to_string(),
+        });
+    }
+
+    // "rebase (finish): returning to refs/heads/x", also for `rebase -i`
+    if message.starts_with("rebase") && message.contains("(finish)") {
+        let target = message.split("): ").nth(1)?;
+        let branch = target
+            .strip_prefix("returning to ")
+            .unwrap_or(target)
+            .split_whitespace()
+            .next()?;
+        return Some(Activity::Rebase {
+            branch: branch.trim_start_matches("refs/heads/").to_string(),
+        });
+    }
+
+    None
+}
+
+/// The entry content, or None when there was nothing to report.
+fn summarize(activity: &[Activity]) -> Option<String> {
+    if activity.is_empty() {
+        return None;
+    }
+
+    let mut lines = vec!["Context switching:".to_string()];
+
+    let switches: Vec<(&str, &str)> = activity
+        .iter()
+        .filter_map(|a| match a {
+            Activity::Switch { from, to } => Some((from.as_str(), to.as_str())),
+            _ => None,
+        })
+        .collect();
+    if let Some((first, _)) = switches.first() {
+        let mut path = vec![*first];
+        for (_, to) in &switches {
+            if path.last() != Some(to) {
+                path.push(to);
+            }
+        }
+        lines.push(format!(
+            "- Switched branches {}: {}",
+            times(switches.len()),
+            path.join(" → ")
+        ));
+    }
+
+    let mut rebased: Vec<(&str, usize)> = Vec::new();
+    for activity in activity {
+        if let Activity::Rebase { branch } = activity {
+            match rebased.iter_mut().find(|(b, _)| b == branch) {
+                Some((_, count)) => *count += 1,
+                None => rebased.push((branch, 1)),
+            }
+        }
+    }
+    for (branch, count) in rebased {
+        lines.push(format!("- Rebased {branch} ({})", times(count)));
+    }
+
+    for activity in activity {
+        if let Activity::Stash { description } = activity {
+            lines.push(format!("- Stashed: {description}"));
+        }
+    }
+
+    Some(lines.join("\n"))
+}
+
+fn times(count: usize) -> String {
+    match count {
+        1 => "once".to_string(),
+        n => format!("{n} times"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_head_message() {
+        assert_eq!(
+            parse_head_message("checkout: moving from main to fix/login"),
+            Some(Activity::Switch {
+                from: "main".into(),
+                to: "fix/login".into()
+            })
+        );
+        assert_eq!(
+            parse_head_message("checkout: moving from main to main"),
+            None
+        );
+        assert_eq!(
+            parse_head_message("rebase -i (finish): returning to refs/heads/feature"),
+            Some(Activity::Rebase {
+                branch: "feature".into()
+            })
+        );
+        assert_eq!(parse_head_message("rebase (start): checkout main"), None);
+        assert_eq!(parse_head_message("commit: Fix typo"), None);
+    }
+
+    #[test]
+    fn test_summarize() {
+        let switch = |from: &str, to: &str| Activity::Switch {
+            from: from.into(),
+            to: to.into(),
+        };
+        let rebase = Activity::Rebase {
+            branch: "feature".into(),
+        };
+        let activity = vec![
+            switch("main", "feature"),
+            rebase.clone(),
+            switch("feature", "main"),
+            Activity::Stash {
+                description: "WIP on main: 1a2b3c4 Add invoices".into(),
+            },
+            switch("main", "hotfix"),
+            rebase,
+        ];
+
+        assert_eq!(
+            summarize(&activity).unwrap(),
+            "Context switching:\n\
+             - Switched branches 3 times: main → feature → main → hotfix\n\
+             - Rebased feature (2 times)\n\
+             - Stashed: WIP on main: 1a2b3c4 Add invoices"
+        );
+        assert_eq!(summarize(&[]), None);
+    }
+
+    #[test]
+    fn test_read_activity_from_reflog() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = Repository::init(temp_dir.path()).unwrap();
+        let sig = git2::Signature::now("Test User", "me@example.com").unwrap();
+        let tree_id = repo.index().unwrap().write_tree().unwrap();
+        let tree = repo.find_tree(tree_id).unwrap();
+        let commit_id = repo
+            .commit(Some("HEAD"), &sig, &sig, "Initial", &tree, &[])
+            .unwrap();
+        let commit = repo.find_commit(commit_id).unwrap();
+        let start = repo.head().unwrap().shorthand().unwrap().to_string();
+        repo.branch("feature", &commit, false).unwrap();
+        repo.set_head("refs/heads/feature").unwrap();
+
+        let activity =
+            read_activity(temp_dir.path(), Utc::now() - chrono::Duration::hours(1)).unwrap();
+        assert_eq!(
+            activity,
+            vec![Activity::Switch {
+                from: start,
+                to: "feature".into()
+            }]
+        );
+    }
+}
@@ -0,0 +1,128 @@
+use crate::cli::Cli;
+use crate::completion_cache::CompletionCache;
+use crate::errors::AppError;
+use clap::{CommandFactory, ValueEnum};
+use clap_complete::Shell;
+use std::io::{self, Write};
+use std::path::Path;
+
+/// Name completions are generated for, which is the installed binary rather
+/// than the command's display name.
+const BIN_NAME: &str = "acc";
+
+/// Values offered for option arguments, listed by the hidden
+/// `acc completions --list` that the shell helpers call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CompletionValues {
+    /// Project identifiers from the last project list fetched
+    Projects,
+    /// Recently used tags, most recent first
+    Tags,
+}
+
+/// Completes `-p/--project` and `-t/--tags` from the completion cache, then
+/// defers to the generated `_acc` for everything else. Tags are completed
+/// after the last comma, so `-t ci,inf<Tab>` works.
+const BASH_HELPER: &str = r#"
+_acc_dynamic() {
+    local cur prev values prefix
+    cur="${COMP_WORDS[COMP_CWORD]}"
+    prev="${COMP_WORDS[COMP_CWORD-1]}"
+    case "$prev" in
+        -p|--project) values="$(acc completions --list projects 2>/dev/null)" ;;
+        -t|--tags|--tag) values="$(acc completions --list tags 2>/dev/null)" ;;
+        *) _acc "$@"; return ;;
+    esac
+    prefix=""
+    if [[ "$cur" == *,* ]]; then
+        prefix="${cur%,*},"
+        cur="${cur##*,}"
+    fi
+    COMPREPLY=($(compgen -P "$prefix" -W "$values" -- "$cur"))
+}
+complete -F _acc_dynamic -o nosort -o bashdefault -o default acc 2>/dev/null ||
+    complete -F _acc_dynamic -o bashdefault -o default acc
+"#;
+
+const ZSH_HELPER: &str = r#"
+_acc_dynamic() {
+    local -a values
+    case "${words[CURRENT-1]}" in
+        -p|--project) values=(${(f)"$(acc completions --list projects 2>/dev/null)"}) ;;
+        -t|--tags|--tag)
+            compset -P '*,'
+            values=(${(f)"$(acc completions --list tags 2>/dev/null)"})
+            ;;
+        *) _acc "$@"; return ;;
+    esac
+    compadd -a values
+}
+compdef _acc_dynamic acc
+"#;
+
+const FISH_HELPER: &str = r#"
+complete -c acc -s p -l project -x -a "(acc completions --list projects 2>/dev/null)"
+complete -c acc -s t -l tags -x -a "(__fish_complete_list , 'acc completions --list tags 2>/dev/null')"
+complete -c acc -l tag -x -a "(__fish_complete_list , 'acc completions --list tags 2>/dev/null')"
+"#;
+
+/// Prints the completion script for `shell`. Bash, zsh and fish also get a
+/// helper that completes projects and tags from the local cache.
+pub fn print_script(shell: Shell) -> Result<(), AppError> {
+    let mut stdout = io::stdout().lock();
+    write_script(shell, &mut stdout)?;
+    Ok(())
+}
+
+fn write_script(shell: Shell, out: &mut impl Write) -> io::Result<()> {
+    clap_complete::generate(shell, &mut Cli::command(), BIN_NAME, out);
+    let helper = match shell {
+        Shell::Bash => BASH_HELPER,
+        Shell::Zsh => ZSH_HELPER,
+        Shell::Fish => FISH_HELPER,
+        _ => return Ok(()),
+    };
+    writeln!(
+        out,
+        "\n# Projects and tags, from the cache `acc` keeps as you use it"
+    )?;
+    out.write_all(helper.trim_start().as_bytes())
+}
+
+/// Prints one cached value per line for a shell helper. Never fails: a
+/// missing cache just means nothing to offer.
+pub fn list(state_dir: &Path, values: CompletionValues) {
+    let cache = CompletionCache::load(state_dir);
+    let values = match values {
+        CompletionValues::Projects => cache.projects(),
+        CompletionValues::Tags => cache.tags(),
+    };
+    for value in values {
+        println!("{value}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn script(shell: Shell) -> String {
+        let mut out = Vec::new();
+        write_script(shell, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_scripts_include_dynamic_helpers() {
+        let bash = script(Shell::Bash);
+        assert!(bash.contains("_acc()"));
+        assert!(bash.contains("complete -F _acc_dynamic"));
+
+        let zsh = script(Shell::Zsh);
+        assert!(zsh.starts_with("#compdef acc"));
+        assert!(zsh.ends_with("compdef _acc_dynamic acc\n"));
+
+        assert!(script(Shell::Fish).contains("acc completions --list tags"));
+        assert!(!script(Shell::PowerShell).contains("completions --list"));
+    }
+}
@@ -0,0 +1,72 @@
+use crate::errors::AppError;
+use crate::utils::branch_routes::{self, BranchRoutes};
+use colored::*;
+use std::env;
+
+/// Prints which `branch_routes` rule applies to `branch`, or to the branch
+/// checked out in the current directory, and where it sends work.
+pub fn test_route(routes: &BranchRoutes, branch: Option<&str>) -> Result<(), AppError> {
+    let branch = match branch {
+        Some(branch) => branch.to_string(),
+        None => {
+            let dir = env::current_dir()?;
+            branch_routes::current_branch(&dir).ok_or_else(|| {
+                AppError::Other(
+                    "No branch is checked out here. Pass the branch name to test".to_string(),
+                )
+            })?
+        }
+    };
+
+    if routes.is_empty() {
+        println!("No branch routes are configured. Add [[<profile>.branch_routes]] to the config.");
+        return Ok(());
+    }
+
+    println!("{}", describe(routes, &branch));
+    Ok(())
+}
+
+fn describe(routes: &BranchRoutes, branch: &str) -> String {
+    let Some((index, route)) = routes.route(branch) else {
+        return format!("No branch route matches '{branch}'. The directory's project is used.");
+    };
+
+    let mut out = format!(
+        "'{branch}' matches rule {index} ({})",
+        route.pattern.bright_black()
+    );
+    if let Some(project) = &route.project {
+        out.push_str(&format!("\n  Project: {}", project.cyan()));
+    }
+    if !route.tags.is_empty() {
+        out.push_str(&format!("\n  Tags: {}", route.tags.join(", ").yellow()));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::branch_routes::RawBranchRoute;
+
+    #[test]
+    fn test_describe() {
+        colored::control::set_override(false);
+        let routes = BranchRoutes::from_raw(vec![RawBranchRoute {
+            pattern: "billing/*".to_string(),
+            project: Some("BIL".to_string()),
+            tags: Some(vec!["billing".to_string()]),
+        }])
+        .unwrap();
+
+        assert_eq!(
+            describe(&routes, "billing/invoices"),
+            "'billing/invoices' matches rule 1 (billing/*)\n  Project: BIL\n  Tags: billing"
+        );
+        assert_eq!(
+            describe(&routes, "main"),
+            "No branch route matches 'main'. The directory's project is used."
+        );
+    }
+}
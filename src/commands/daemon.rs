@@ -0,0 +1,719 @@
+use crate::api::errors::ApiError;
+use crate::auth::AuthService;
+use crate::commands::logs::fetch_all_entries;
+use crate::commands::{capture, sync};
+use crate::config;
+use crate::daemon_state::{DaemonLock, DaemonSettings, DaemonStatus};
+use crate::errors::AppError;
+use crate::hooks::Hooks;
+use crate::outbox::Outbox;
+use crate::utils::commit_message::CommitMessageFormat;
+use crate::utils::humanize::ago;
+use crate::utils::notify::{NotificationKind, NotificationSettings};
+use chrono::{DateTime, Datelike, Local, Utc, Weekday};
+use colored::*;
+use git2::Repository;
+use std::fs::{self, OpenOptions};
+use std::future::Future;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// How many of the newest commits each capture looks at, as for `acc capture`.
+const CAPTURE_LIMIT: u32 = 25;
+
+/// Where `acc daemon start` sends the daemon's output, in the state directory.
+const LOG_FILE: &str = "daemon.log";
+
+/// Watches the repositories in `settings` until interrupted or stopped with
+/// `acc daemon stop`, capturing new commits as `acc capture --auto` would.
+/// With `once`, the repositories are checked a single time instead.
+///
+/// Only local git state is read between captures: the API is contacted when
+/// a repository's HEAD has moved, or when entries are waiting in the outbox.
+/// While the API can't be reached, repositories with new commits stay pending
+/// and are captured on the first check after it comes back, and queued entries
+/// are sent at the same time.
+///
+/// Desktop notifications are shown as `notifications` allows: for captures,
+/// failed captures, and on weekdays with nothing logged by `remind_at`.
+pub async fn run(
+    auth_service: &mut AuthService,
+    settings: &DaemonSettings,
+    notifications: &NotificationSettings,
+    message_format: &CommitMessageFormat,
+    hooks: &Hooks,
+    once: bool,
+) -> Result<(), AppError> {
+    let state_dir = auth_service.state_dir().to_path_buf();
+    let previous = DaemonStatus::load(&state_dir);
+    // Held until this returns, which is what makes the saved pid trustworthy
+    let Some(_lock) = DaemonLock::try_acquire(&state_dir)? else {
+        let pid = previous.map_or_else(|| "unknown pid".to_string(), |p| format!("pid {}", p.pid));
+        return Err(AppError::Other(format!(
+            "acc daemon is already running for this profile ({pid}). Stop it with `acc daemon stop`"
+        )));
+    };
+
+    let shutdown = listen_for_shutdown()?;
+    tokio::pin!(shutdown);
+
+    let mut status = DaemonStatus::start(settings.interval, previous);
+    save_status(&status, &state_dir);
+    log_line(&format!(
+        "Watching {} repositories, checking every {}",
+        watched_repositories(settings).len(),
+        format_interval(status.interval_secs)
+    ));
+
+    let result = loop {
+        if let Err(e) = check(
+            auth_service,
+            settings,
+            notifications,
+            message_format,
+            hooks,
+            &mut status,
+        )
+        .await
+        {
+            break Err(e);
+        }
+        if once {
+            break Ok(());
+        }
+
+        status.next_check_at =
+            Some(Utc::now() + chrono::Duration::seconds(status.interval_secs as i64));
+        save_status(&status, &state_dir);
+        tokio::select! {
+            _ = tokio::time::sleep(settings.interval) => {}
+            _ = &mut shutdown => {
+                log_line("Stopping");
+                break Ok(());
+            }
+        }
+    };
+
+    status.next_check_at = None;
+    status.stopped_at = Some(Utc::now());
+    save_status(&status, &state_dir);
+    result
+}
+
+/// Looks at every watched repository once and captures the ones with new
+/// commits. Fails only when the stored credentials are no longer valid, since
+/// nothing can be captured until the user logs in again.
+pub async fn check(
+    auth_service: &mut AuthService,
+    settings: &DaemonSettings,
+    notifications: &NotificationSettings,
+    message_format: &CommitMessageFormat,
+    hooks: &Hooks,
+    status: &mut DaemonStatus,
+) -> Result<(), AppError> {
+    let repositories = watched_repositories(settings);
+    status
+        .repositories
+        .retain(|path, _| repositories.contains(path));
+
+    let mut due = Vec::new();
+    for path in repositories {
+        let head = head_commit(&path);
+        let known = status.repositories.entry(path.clone()).or_default();
+        match head {
+            Some(head) if known.pending || known.head.as_deref() != Some(&head) => {
+                due.push((path, head));
+            }
+            Some(_) => {}
+            None => {
+                known.error = Some(
+                    "No commits to read (missing, not a git repository, or empty)".to_string(),
+                );
+            }
+        }
+    }
+    status.last_check_at = Some(Utc::now());
+
+    let now = Local::now();
+    let remind = reminder_due(notifications, status, now);
+    let queued = Outbox::new(auth_service.state_dir()).count();
+    if due.is_empty() && queued == 0 && !remind {
+        return Ok(());
+    }
+
+    match auth_service.ensure_authenticated().await {
+        Ok(()) => {
+            if status.offline {
+                log_line("Accomplish is reachable again");
+            }
+            status.offline = false;
+            status.last_error = None;
+        }
+        Err(e @ (AppError::Auth(_) | AppError::Api(ApiError::Unauthorized(_)))) => return Err(e),
+        Err(e) => {
+            if e.is_offline() {
+                if !status.offline {
+                    log_line(&format!(
+                        "Can't reach Accomplish ({e}). New commits are captured once it's back"
+                    ));
+                }
+                status.offline = true;
+            } else {
+                // Likely a passing server error, so the next check tries again
+                log_error(&format!("Could not check the session: {e}"));
+                status.last_error = Some(e.to_string());
+            }
+            for (path, _) in &due {
+                if let Some(known) = status.repositories.get_mut(path) {
+                    known.pending = true;
+                }
+            }
+            return Ok(());
+        }
+    }
+
+    if queued > 0 {
+        if let Err(e) = sync::execute(auth_service).await {
+            log_error(&format!("Could not send queued entries: {e}"));
+        }
+    }
+
+    for (path, head) in due {
+        let result = capture::execute(
+            auth_service,
+            CAPTURE_LIMIT,
+            false,
+            None,
+            false,
+            Some(&path),
+            &[],
+            message_format,
+            None,
+            capture::CaptureMode::Auto {
+                log: settings.log,
+                quiet: true,
+            },
+            hooks,
+        )
+        .await;
+
+        let Some(known) = status.repositories.get_mut(&path) else {
+            continue;
+        };
+        match result {
+            Ok(captured) => {
+                log_line(&format!(
+                    "Captured {captured} commits in {} up to {}",
+                    path.display(),
+                    head.get(..7).unwrap_or(&head)
+                ));
+                if captured > 0 {
+                    notifications.show(
+                        NotificationKind::Captured,
+                        "Accomplish captured your commits",
+                        &format!("{captured} new commits in {}", repository_name(&path)),
+                    );
+                }
+                known.head = Some(head);
+                known.pending = false;
+                known.error = None;
+                known.last_capture_at = Some(Utc::now());
+            }
+            Err(e) if e.is_offline() => {
+                known.pending = true;
+                status.offline = true;
+            }
+            Err(e) => {
+                // Not retried until the next commit, so a broken repository
+                // doesn't fail on every check
+                log_error(&format!("Could not capture {}: {e}", path.display()));
+                notifications.show(
+                    NotificationKind::CaptureFailed,
+                    &format!("Accomplish couldn't capture {}", repository_name(&path)),
+                    &e.to_string(),
+                );
+                known.head = Some(head);
+                known.pending = false;
+                known.error = Some(e.to_string());
+            }
+        }
+    }
+
+    if remind {
+        let today = now.date_naive().format("%Y-%m-%d").to_string();
+        match fetch_all_entries(
+            auth_service.api_client(),
+            None,
+            None,
+            Some(&today),
+            Some(&today),
+        )
+        .await
+        {
+            Ok(entries) => {
+                if entries.is_empty() {
+                    log_line("Nothing logged today, reminding");
+                    notifications.show(
+                        NotificationKind::NothingLogged,
+                        "You haven't logged anything today",
+                        "Log what you did with `acc did \"...\"`",
+                    );
+                }
+                status.reminded_on = Some(now.date_naive());
+            }
+            // Tried again on the next check
+            Err(e) => log_error(&format!("Could not look up today's entries: {e}")),
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether today's nothing-logged reminder should be looked into: it's a
+/// weekday, past `remind_at`, and it hasn't been yet.
+fn reminder_due(
+    notifications: &NotificationSettings,
+    status: &DaemonStatus,
+    now: DateTime<Local>,
+) -> bool {
+    notifications.enabled(NotificationKind::NothingLogged)
+        && !matches!(now.weekday(), Weekday::Sat | Weekday::Sun)
+        && now.time() >= notifications.remind_at
+        && status.reminded_on != Some(now.date_naive())
+}
+
+/// Starts `acc daemon run` in the background for `profile`, detached from the
+/// terminal, with its output appended to `daemon.log` in the state directory.
+pub fn start(state_dir: &Path, profile: &str) -> Result<(), AppError> {
+    if let Some(running) = DaemonStatus::load(state_dir).filter(|s| s.is_running(state_dir)) {
+        return Err(AppError::Other(format!(
+            "acc daemon is already running for this profile (pid {})",
+            running.pid
+        )));
+    }
+
+    fs::create_dir_all(state_dir)?;
+    let log_path = state_dir.join(LOG_FILE);
+    let log = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&log_path)?;
+
+    let mut command = Command::new(std::env::current_exe()?);
+    command
+        .args(["--profile", profile, "daemon", "run"])
+        .stdin(Stdio::null())
+        .stdout(log.try_clone()?)
+        .stderr(log);
+    #[cfg(unix)]
+    {
+        // Its own process group, so closing the terminal or pressing Ctrl-C
+        // there doesn't stop it
+        use std::os::unix::process::CommandExt;
+        command.process_group(0);
+    }
+    let child = command.spawn()?;
+
+    println!(
+        "✅ Started acc daemon (pid {}) for profile '{profile}'",
+        child.id()
+    );
+    println!("   Output goes to {}", log_path.display());
+    println!("   Check on it with `acc daemon status`, stop it with `acc daemon stop`.");
+    Ok(())
+}
+
+/// Asks the running daemon to stop. It finishes the check in progress first.
+pub fn stop(state_dir: &Path) -> Result<(), AppError> {
+    let running = DaemonStatus::load(state_dir)
+        .filter(|s| s.is_running(state_dir))
+        .ok_or_else(|| AppError::Other("acc daemon isn't running for this profile".to_string()))?;
+    terminate(running.pid)?;
+    println!("🛑 Stopping acc daemon (pid {})", running.pid);
+    Ok(())
+}
+
+/// Whether the daemon is running, when it last looked, and how each watched
+/// repository fared.
+pub fn status(state_dir: &Path) -> Result<(), AppError> {
+    let Some(status) = DaemonStatus::load(state_dir) else {
+        println!("acc daemon hasn't run for this profile. Start it with `acc daemon start`.");
+        return Ok(());
+    };
+
+    let running = status.is_running(state_dir);
+    println!();
+    if running {
+        println!(
+            "{} acc daemon is running (pid {}), started {}",
+            "●".green(),
+            status.pid,
+            describe_time(status.started_at)
+        );
+        println!("  Checks every {}", format_interval(status.interval_secs));
+    } else {
+        let stopped = status.stopped_at.map_or_else(
+            || "stopped unexpectedly".to_string(),
+            |at| format!("stopped {}", describe_time(at)),
+        );
+        println!(
+            "{} acc daemon isn't running ({stopped})",
+            "○".bright_black()
+        );
+    }
+    if let Some(at) = status.last_check_at {
+        println!("  Last check: {}", describe_time(at));
+    }
+    if let Some(at) = status.next_check_at.filter(|_| running) {
+        println!(
+            "  Next check: {}",
+            at.with_timezone(&Local).format("%H:%M:%S")
+        );
+    }
+    if status.offline {
+        println!(
+            "  {}",
+            "Accomplish couldn't be reached on the last check".yellow()
+        );
+    }
+    if let Some(error) = &status.last_error {
+        println!("  {}", format!("The last check failed: {error}").yellow());
+    }
+    let queued = Outbox::new(state_dir).count();
+    if queued > 0 {
+        let entries = if queued == 1 { "entry" } else { "entries" };
+        println!("  {queued} {entries} queued, sent on the next check that reaches Accomplish");
+    }
+
+    if status.repositories.is_empty() {
+        println!();
+        println!("No repositories watched. Add some with `acc init` or `[<profile>.daemon] repositories`.");
+        return Ok(());
+    }
+    println!();
+    for (path, repo) in &status.repositories {
+        let state = if let Some(error) = &repo.error {
+            format!("{} {error}", "✗".red())
+        } else if repo.pending {
+            format!("{} new commits waiting for Accomplish", "…".yellow())
+        } else if let Some(at) = repo.last_capture_at {
+            format!("{} captured {}", "✓".green(), describe_time(at))
+        } else {
+            format!("{} no new commits", "✓".green())
+        };
+        println!("  {}  {state}", path.display());
+    }
+    Ok(())
+}
+
+/// The configured repositories, or every git repository set up with `acc init`.
+fn watched_repositories(settings: &DaemonSettings) -> Vec<PathBuf> {
+    if settings.repositories.is_empty() {
+        config::tracked_git_directories()
+    } else {
+        settings.repositories.clone()
+    }
+}
+
+fn repository_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.display().to_string())
+}
+
+fn head_commit(path: &Path) -> Option<String> {
+    let repo = Repository::open(path).ok()?;
+    let head = repo.head().ok()?.peel_to_commit().ok()?;
+    Some(head.id().to_string())
+}
+
+/// Resolves on Ctrl-C or SIGTERM. The handlers are installed straight away,
+/// so a signal arriving before the first await isn't lost.
+#[cfg(unix)]
+fn listen_for_shutdown() -> io::Result<impl Future<Output = ()>> {
+    use tokio::signal::unix::{signal, SignalKind};
+    let mut interrupt = signal(SignalKind::interrupt())?;
+    let mut terminate = signal(SignalKind::terminate())?;
+    Ok(async move {
+        tokio::select! {
+            _ = interrupt.recv() => {}
+            _ = terminate.recv() => {}
+        }
+    })
+}
+
+#[cfg(not(unix))]
+fn listen_for_shutdown() -> io::Result<impl Future<Output = ()>> {
+    Ok(async {
+        let _ = tokio::signal::ctrl_c().await;
+    })
+}
+
+#[cfg(unix)]
+fn terminate(pid: u32) -> Result<(), AppError> {
+    let pid = libc::pid_t::try_from(pid)
+        .map_err(|_| AppError::Other(format!("Invalid daemon pid {pid}")))?;
+    if unsafe { libc::kill(pid, libc::SIGTERM) } != 0 {
+        return Err(io::Error::last_os_error().into());
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn terminate(_pid: u32) -> Result<(), AppError> {
+    Err(AppError::Other(
+        "Stop acc daemon from the terminal or service manager running it".to_string(),
+    ))
+}
+
+fn save_status(status: &DaemonStatus, state_dir: &Path) {
+    if let Err(e) = status.save(state_dir) {
+        log_error(&format!("Could not save the daemon status: {e}"));
+    }
+}
+
+fn log_line(message: &str) {
+    println!("{} {message}", Local::now().format("%Y-%m-%d %H:%M:%S"));
+}
+
+fn log_error(message: &str) {
+    eprintln!(
+        "{} error: {message}",
+        Local::now().format("%Y-%m-%d %H:%M:%S")
+    );
+}
+
+fn describe_time(at: DateTime<Utc>) -> String {
+    ago(Utc::now() - at)
+}
+
+/// `90` as "1m30s", `3600` as "1h".
+fn format_interval(secs: u64) -> String {
+    let (hours, minutes, seconds) = (secs / 3600, secs / 60 % 60, secs % 60);
+    [(hours, "h"), (minutes, "m"), (seconds, "s")]
+        .iter()
+        .filter(|(n, _)| *n > 0)
+        .map(|(n, unit)| format!("{n}{unit}"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use git2::Signature;
+    use serde_json::json;
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    fn commit(repo: &Repository, message: &str) {
+        let sig = Signature::now("Test User", "me@example.com").unwrap();
+        let tree_id = repo.index().unwrap().write_tree().unwrap();
+        let tree = repo.find_tree(tree_id).unwrap();
+        let parent = repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
+        repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &parents)
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_check_only_captures_repositories_whose_head_moved() {
+        let temp_dir = TempDir::new().unwrap();
+        let state_dir = TempDir::new().unwrap();
+        let repo_dir = temp_dir.path().canonicalize().unwrap();
+        let repo = Repository::init(&repo_dir).unwrap();
+        commit(&repo, "First");
+        fs::write(
+            repo_dir.join(".accomplish.toml"),
+            "[project]\ndefault_project = \"web\"\n",
+        )
+        .unwrap();
+
+        let mut server = mockito::Server::new_async().await;
+        let token_checks = server
+            .mock("POST", "/auth/token_info")
+            .with_body(r#"{"active":true,"scope":"","client_id":"cli","exp":0}"#)
+            .expect(2)
+            .create_async()
+            .await;
+        let _projects = server
+            .mock("GET", "/api/v1/projects")
+            .with_body(r#"{"projects":[{"id":"p1","identifier":"web","name":"Web"}]}"#)
+            .create_async()
+            .await;
+        let _repositories = server
+            .mock("GET", "/api/v1/repositories")
+            .with_body(
+                json!({ "repositories": [
+                    { "id": "r1", "project_id": "p1", "local_path": repo_dir.to_string_lossy() }
+                ]})
+                .to_string(),
+            )
+            .create_async()
+            .await;
+        let _uncaptured = server
+            .mock("GET", "/api/v1/repositories/r1/commits")
+            .match_query(mockito::Matcher::Any)
+            .with_body(json!({ "uncaptured_shas": [head_commit(&repo_dir)] }).to_string())
+            .create_async()
+            .await;
+        let captured = server
+            .mock("POST", "/api/v1/repositories/r1/commits")
+            .with_status(201)
+            .with_body(r#"{"commits":[{"id":"c1"}]}"#)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let mut auth_service =
+            AuthService::new(server.url(), state_dir.path().to_path_buf(), "test-profile");
+        auth_service.save_access_token("dummy-token").unwrap();
+        let hooks = Hooks::new(state_dir.path().join("hooks"), "test-profile");
+        let settings = DaemonSettings {
+            interval: Duration::from_secs(60),
+            repositories: vec![repo_dir.clone()],
+            log: false,
+        };
+        let format = CommitMessageFormat::default();
+        let notifications = NotificationSettings {
+            nothing_logged: false,
+            capture_failed: false,
+            ..Default::default()
+        };
+        let mut status = DaemonStatus::start(settings.interval, None);
+
+        check(
+            &mut auth_service,
+            &settings,
+            &notifications,
+            &format,
+            &hooks,
+            &mut status,
+        )
+        .await
+        .unwrap();
+        let known = &status.repositories[&repo_dir];
+        assert_eq!(known.head, head_commit(&repo_dir));
+        assert!(known.error.is_none() && known.last_capture_at.is_some());
+
+        // Nothing moved, so the API isn't asked at all
+        check(
+            &mut auth_service,
+            &settings,
+            &notifications,
+            &format,
+            &hooks,
+            &mut status,
+        )
+        .await
+        .unwrap();
+        captured.assert_async().await;
+
+        // A new commit is picked up; the capture cache already knows the first
+        commit(&repo, "Second");
+        check(
+            &mut auth_service,
+            &settings,
+            &notifications,
+            &format,
+            &hooks,
+            &mut status,
+        )
+        .await
+        .unwrap();
+        token_checks.assert_async().await;
+        assert_eq!(status.repositories[&repo_dir].head, head_commit(&repo_dir));
+    }
+
+    #[tokio::test]
+    async fn test_check_only_stops_for_rejected_credentials() {
+        let temp_dir = TempDir::new().unwrap();
+        let state_dir = TempDir::new().unwrap();
+        let repo_dir = temp_dir.path().canonicalize().unwrap();
+        let repo = Repository::init(&repo_dir).unwrap();
+        commit(&repo, "First");
+
+        let mut server = mockito::Server::new_async().await;
+        let failing = server
+            .mock("POST", "/auth/token_info")
+            .with_status(500)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let mut auth_service =
+            AuthService::new(server.url(), state_dir.path().to_path_buf(), "test-profile");
+        auth_service.save_access_token("dummy-token").unwrap();
+        let hooks = Hooks::new(state_dir.path().join("hooks"), "test-profile");
+        let settings = DaemonSettings {
+            interval: Duration::from_secs(60),
+            repositories: vec![repo_dir.clone()],
+            log: false,
+        };
+        let format = CommitMessageFormat::default();
+        let notifications = NotificationSettings {
+            nothing_logged: false,
+            capture_failed: false,
+            ..Default::default()
+        };
+        let mut status = DaemonStatus::start(settings.interval, None);
+
+        // A server error is recorded and the commits wait for the next check
+        check(
+            &mut auth_service,
+            &settings,
+            &notifications,
+            &format,
+            &hooks,
+            &mut status,
+        )
+        .await
+        .unwrap();
+        failing.assert_async().await;
+        assert!(status.last_error.is_some());
+        assert!(status.repositories[&repo_dir].pending);
+
+        server
+            .mock("POST", "/auth/token_info")
+            .with_status(401)
+            .create_async()
+            .await;
+        let result = check(
+            &mut auth_service,
+            &settings,
+            &notifications,
+            &format,
+            &hooks,
+            &mut status,
+        )
+        .await;
+        assert!(matches!(result, Err(AppError::Auth(_))), "{result:?}");
+    }
+
+    #[test]
+    fn test_reminder_is_due_once_per_weekday_after_remind_at() {
+        let notifications = NotificationSettings::default();
+        let mut status = DaemonStatus::start(Duration::from_secs(60), None);
+        // 2025-07-09 is a Wednesday, 2025-07-12 a Saturday
+        let at = |day: u32, hour: u32| {
+            Local
+                .with_ymd_and_hms(2025, 7, day, hour, 0, 0)
+                .single()
+                .unwrap()
+        };
+
+        assert!(!reminder_due(&notifications, &status, at(9, 16)));
+        assert!(reminder_due(&notifications, &status, at(9, 17)));
+        status.reminded_on = Some(at(9, 17).date_naive());
+        assert!(!reminder_due(&notifications, &status, at(9, 18)));
+        assert!(!reminder_due(&notifications, &status, at(12, 18)));
+    }
+
+    #[test]
+    fn test_format_interval() {
+        assert_eq!(format_interval(300), "5m");
+        assert_eq!(format_interval(5400), "1h30m");
+        assert_eq!(format_interval(45), "45s");
+    }
+}
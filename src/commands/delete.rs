@@ -0,0 +1,140 @@
+use crate::api::endpoints::delete_worklog_entry;
+use crate::auth::AuthService;
+use crate::commands::logs::{find_entry, print_entries, EntryFormat};
+use crate::errors::AppError;
+use crate::pins::Pins;
+use crate::trash::Trash;
+use crate::utils::prompt::PromptOrDefault;
+use chrono::Duration;
+use colored::*;
+use inquire::{Confirm, InquireError};
+
+/// Deletes entries by ID or short ID after showing them and asking for
+/// confirmation, unless `force` is set. Deleted entries are unpinned and kept
+/// in the local trash for `retention`, so they can be restored.
+pub async fn execute(
+    auth_service: &mut AuthService,
+    ids_or_prefixes: &[String],
+    force: bool,
+    retention: Duration,
+) -> Result<(), AppError> {
+    // Resolve everything first, so a typo in one ID deletes nothing
+    let mut entries = Vec::new();
+    for id_or_prefix in ids_or_prefixes {
+        entries.push(find_entry(auth_service.api_client(), id_or_prefix).await?);
+    }
+
+    let mut pins = Pins::load(auth_service.state_dir());
+    if !force {
+        print_entries(&entries, EntryFormat::Oneline, &pins)?;
+        let question = match entries.len() {
+            1 => "Delete this entry?".to_string(),
+            n => format!("Delete these {n} entries?"),
+        };
+        let confirmed = Confirm::new(&question)
+            .with_default(false)
+            .prompt_or_default()
+            .map_err(|e| match e {
+                InquireError::NotTTY => AppError::Other(
+                    "Can't ask for confirmation without a terminal. Use --force to delete anyway"
+                        .to_string(),
+                ),
+                e => AppError::Other(e.to_string()),
+            })?;
+        if !confirmed {
+            println!("Nothing deleted.");
+            return Ok(());
+        }
+    }
+
+    let mut trash = Trash::load(auth_service.state_dir());
+    trash.purge_expired(retention);
+    for entry in entries {
+        let id = entry.id.clone();
+        delete_worklog_entry(auth_service.api_client(), &id).await?;
+        if pins.unpin(&id).is_ok() {
+            pins.save()?;
+        }
+        trash.put(entry.into());
+        trash.save()?;
+        println!("🗑️  Deleted entry {id}");
+    }
+    println!(
+        "{}",
+        "Restore with `acc logs restore <id>` (see `acc logs trash`)".bright_black()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::Server;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_force_delete_unpins_entry() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut server = Server::new_async().await;
+        let mut auth = AuthService::new(server.url(), temp_dir.path().to_path_buf(), "test");
+        auth.save_access_token("test-token").unwrap();
+        let mut pins = Pins::load(auth.state_dir());
+        pins.pin("entry-1");
+        pins.save().unwrap();
+
+        let _fetch = server
+            .mock("GET", "/api/v1/worklog/entries/entry-1")
+            .with_status(200)
+            .with_body(r#"{"id":"entry-1","content":"Oops"}"#)
+            .create();
+        let delete = server
+            .mock("DELETE", "/api/v1/worklog/entries/entry-1")
+            .with_status(204)
+            .create();
+
+        execute(&mut auth, &["entry-1".into()], true, Duration::days(30))
+            .await
+            .unwrap();
+        delete.assert();
+        assert!(!Pins::load(auth.state_dir()).contains("entry-1"));
+        assert!(Trash::load(auth.state_dir()).find("entry-1").is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_unknown_id_deletes_nothing() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut server = Server::new_async().await;
+        let mut auth = AuthService::new(server.url(), temp_dir.path().to_path_buf(), "test");
+        auth.save_access_token("test-token").unwrap();
+
+        let _fetch = server
+            .mock("GET", "/api/v1/worklog/entries/entry-1")
+            .with_status(200)
+            .with_body(r#"{"id":"entry-1"}"#)
+            .create();
+        let _missing = server
+            .mock("GET", "/api/v1/worklog/entries/nope")
+            .with_status(404)
+            .create();
+        let _list = server
+            .mock("GET", "/api/v1/worklog/entries")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_body(r#"{"entries":[{"id":"entry-1"}]}"#)
+            .create();
+        let delete = server
+            .mock("DELETE", mockito::Matcher::Any)
+            .expect(0)
+            .create();
+
+        assert!(execute(
+            &mut auth,
+            &["entry-1".into(), "nope".into()],
+            true,
+            Duration::days(30)
+        )
+        .await
+        .is_err());
+        delete.assert();
+    }
+}
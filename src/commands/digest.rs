@@ -0,0 +1,280 @@
+use crate::auth::AuthService;
+use crate::commands::logs::fetch_all_entries;
+use crate::errors::AppError;
+use crate::utils::custom_fields::format_custom_fields;
+use crate::utils::mail;
+use crate::utils::redaction::RedactionPolicy;
+use chrono::{DateTime, Duration, Local, NaiveDate};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+
+/// Where a digest goes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Delivery<'a> {
+    Stdout,
+    /// Appended to this journal file
+    Journal(&'a Path),
+    /// Emailed to this address
+    Email(&'a str),
+}
+
+/// Builds a Markdown digest of a single day's entries across all projects, or
+/// with `week` of the seven days ending on that day, and delivers it.
+pub async fn execute(
+    auth_service: &mut AuthService,
+    date: Option<&str>,
+    week: bool,
+    delivery: Delivery<'_>,
+    redaction: &RedactionPolicy,
+) -> Result<(), AppError> {
+    let day = match date {
+        Some(d) => NaiveDate::parse_from_str(d, "%Y-%m-%d").map_err(|_| {
+            AppError::ParseError(format!("Invalid date format: {d}. Expected YYYY-MM-DD"))
+        })?,
+        None => Local::now().date_naive(),
+    };
+    let start = if week { day - Duration::days(6) } else { day };
+    let start_str = start.format("%Y-%m-%d").to_string();
+    let day_str = day.format("%Y-%m-%d").to_string();
+
+    let entries = fetch_all_entries(
+        auth_service.api_client(),
+        None,
+        None,
+        Some(&start_str),
+        Some(&day_str),
+    )
+    .await?;
+    let mut entries = redaction.apply(entries);
+
+    // The API returns newest first; a journal reads better chronologically
+    entries.reverse();
+
+    let (digest, period) = if week {
+        (
+            render_week_digest(&start, &day, &entries),
+            format!("the week of {start_str} to {day_str}"),
+        )
+    } else {
+        (render_digest(&day, &entries), day_str)
+    };
+
+    match delivery {
+        Delivery::Journal(path) => {
+            append_to_journal(path, &digest)?;
+            println!(
+                "✅ Appended {} entries for {period} to {}",
+                entries.len(),
+                path.display()
+            );
+        }
+        Delivery::Email(address) => {
+            mail::send(address, &format!("Accomplish digest for {period}"), &digest)?;
+            println!(
+                "✅ Emailed {} entries for {period} to {address}",
+                entries.len()
+            );
+        }
+        Delivery::Stdout => print!("{digest}"),
+    }
+
+    Ok(())
+}
+
+/// Renders a week's entries as one section per day that has any, under a
+/// heading for the whole week.
+pub fn render_week_digest(start: &NaiveDate, end: &NaiveDate, entries: &[Value]) -> String {
+    let mut out = format!(
+        "# Week of {} to {}\n",
+        start.format("%Y-%m-%d"),
+        end.format("%Y-%m-%d")
+    );
+    if entries.is_empty() {
+        out.push_str("\n_No entries logged._\n");
+        return out;
+    }
+
+    let mut days: BTreeMap<NaiveDate, Vec<Value>> = BTreeMap::new();
+    for entry in entries {
+        let day = entry
+            .get("recorded_at")
+            .and_then(Value::as_str)
+            .and_then(|s| s.parse::<DateTime<Local>>().ok())
+            .map_or(*end, |dt| dt.date_naive());
+        days.entry(day).or_default().push(entry.clone());
+    }
+    for (day, entries) in &days {
+        out.push('\n');
+        out.push_str(&render_digest(day, entries));
+    }
+    out
+}
+
+/// Renders entries as a compact Markdown section headed by the date.
+pub fn render_digest(day: &NaiveDate, entries: &[Value]) -> String {
+    let mut out = format!("## {}\n\n", day.format("%Y-%m-%d (%A)"));
+
+    if entries.is_empty() {
+        out.push_str("_No entries logged._\n");
+        return out;
+    }
+
+    for entry in entries {
+        out.push_str(&render_digest_line(entry));
+    }
+
+    out
+}
+
+fn render_digest_line(entry: &Value) -> String {
+    let content = entry
+        .get("content")
+        .and_then(Value::as_str)
+        .unwrap_or("")
+        .trim();
+
+    let time = entry
+        .get("recorded_at")
+        .and_then(Value::as_str)
+        .and_then(|s| s.parse::<DateTime<Local>>().ok())
+        .map(|dt| dt.format("%H:%M").to_string())
+        .unwrap_or_else(|| "--:--".to_string());
+
+    let project = entry
+        .get("project")
+        .and_then(|p| p.get("identifier"))
+        .and_then(Value::as_str)
+        .map(|id| format!(" [{}]", id.to_uppercase()))
+        .unwrap_or_default();
+
+    let tags = entry
+        .get("tags")
+        .and_then(Value::as_array)
+        .map(|arr| {
+            arr.iter()
+                .filter_map(Value::as_str)
+                .map(|t| format!(" `#{t}`"))
+                .collect::<String>()
+        })
+        .unwrap_or_default();
+
+    let mut lines = content.lines();
+    let first_line = lines.next().unwrap_or("");
+    let mut out = format!("- {time}{project} {first_line}{tags}\n");
+    for line in lines {
+        if line.trim().is_empty() {
+            continue;
+        }
+        out.push_str(&format!("  {line}\n"));
+    }
+    if let Some(fields) = format_custom_fields(entry) {
+        out.push_str(&format!("  _{fields}_\n"));
+    }
+
+    out
+}
+
+/// Appends the digest to the journal file, separating it from previous content
+/// with a blank line and creating the file (and its directory) when missing.
+fn append_to_journal(path: &Path, digest: &str) -> Result<(), AppError> {
+    if let Some(dir) = path.parent() {
+        if !dir.as_os_str().is_empty() {
+            fs::create_dir_all(dir)?;
+        }
+    }
+
+    let needs_separator = fs::metadata(path).map(|m| m.len() > 0).unwrap_or(false);
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    if needs_separator {
+        file.write_all(b"\n")?;
+    }
+    file.write_all(digest.as_bytes())?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_render_digest_empty() {
+        let day = NaiveDate::from_ymd_opt(2025, 7, 9).unwrap();
+        let digest = render_digest(&day, &[]);
+        assert_eq!(
+            digest,
+            "## 2025-07-09 (Wednesday)\n\n_No entries logged._\n"
+        );
+    }
+
+    #[test]
+    fn test_render_digest_line_with_project_and_tags() {
+        let entry = json!({
+            "id": "entry-uuid-123",
+            "content": "Fixed checkout bug\n\nRounding error in totals",
+            "recorded_at": "2025-07-09T10:31:00Z",
+            "tags": ["rust", "bug"],
+            "project": { "identifier": "web" }
+        });
+
+        let line = render_digest_line(&entry);
+        assert!(line.starts_with("- "));
+        assert!(line.contains(" [WEB] Fixed checkout bug `#rust` `#bug`\n"));
+        assert!(line.ends_with("  Rounding error in totals\n"));
+    }
+
+    #[test]
+    fn test_render_digest_line_without_metadata() {
+        let entry = json!({ "content": "Standup" });
+        assert_eq!(render_digest_line(&entry), "- --:-- Standup\n");
+    }
+
+    #[test]
+    fn test_render_digest_line_with_custom_fields() {
+        let entry = json!({ "content": "Standup", "custom_fields": { "ticket": "PROJ-1" } });
+        assert_eq!(
+            render_digest_line(&entry),
+            "- --:-- Standup\n  _ticket=PROJ-1_\n"
+        );
+    }
+
+    #[test]
+    fn test_render_week_digest_groups_entries_by_day() {
+        let start = NaiveDate::from_ymd_opt(2025, 7, 3).unwrap();
+        let end = NaiveDate::from_ymd_opt(2025, 7, 9).unwrap();
+        assert_eq!(
+            render_week_digest(&start, &end, &[]),
+            "# Week of 2025-07-03 to 2025-07-09\n\n_No entries logged._\n"
+        );
+
+        let entries = [
+            json!({ "content": "Planning", "recorded_at": "2025-07-04T12:00:00Z" }),
+            json!({ "content": "Review", "recorded_at": "2025-07-08T12:00:00Z" }),
+            json!({ "content": "Retro", "recorded_at": "2025-07-08T13:00:00Z" }),
+        ];
+        let digest = render_week_digest(&start, &end, &entries);
+        assert!(digest
+            .starts_with("# Week of 2025-07-03 to 2025-07-09\n\n## 2025-07-04 (Friday)\n\n- "));
+        assert_eq!(digest.matches("## ").count(), 2);
+        assert!(digest.contains("Review\n- "));
+        assert!(digest.ends_with("Retro\n"));
+    }
+
+    #[test]
+    fn test_append_to_journal_separates_sections() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("journal").join("work.md");
+
+        append_to_journal(&path, "## Day 1\n").unwrap();
+        append_to_journal(&path, "## Day 2\n").unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert_eq!(content, "## Day 1\n\n## Day 2\n");
+    }
+}
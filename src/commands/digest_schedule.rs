@@ -0,0 +1,257 @@
+use crate::errors::AppError;
+use crate::scheduler::{self, Frequency, ScheduledJob};
+use crate::utils::mail;
+use chrono::{NaiveTime, Utc, Weekday};
+use std::env;
+use std::path::{Path, PathBuf};
+
+/// When scheduled digests run unless `--at` says otherwise: the end of the
+/// working day, so the day's (or week's) entries are in.
+const DEFAULT_AT: &str = "17:00";
+
+/// Where the output of scheduled digests goes, in the state directory.
+const LOG_FILE: &str = "digest.log";
+
+/// What `acc digest schedule` was asked to set up.
+pub struct ScheduleOptions<'a> {
+    pub weekly: bool,
+    pub daily: bool,
+    /// Day of the week for `weekly` (default: Friday)
+    pub day: Option<&'a str>,
+    /// Local time, `HH:MM`
+    pub at: Option<&'a str>,
+    pub email: Option<&'a str>,
+    pub output: Option<&'a Path>,
+}
+
+/// Installs a job running `acc digest` for `profile` with the system
+/// scheduler. Scheduling the same kind of digest again replaces it.
+pub fn add(
+    state_dir: &Path,
+    profile: &str,
+    options: &ScheduleOptions,
+    digest_file: Option<&Path>,
+) -> Result<(), AppError> {
+    let frequency = if options.daily {
+        Frequency::Daily
+    } else if options.weekly {
+        let day = match options.day {
+            Some(day) => day.parse::<Weekday>().map_err(|_| {
+                AppError::ParseError(format!(
+                    "Invalid day '{day}'. Expected a weekday like fri or friday"
+                ))
+            })?,
+            None => Weekday::Fri,
+        };
+        Frequency::Weekly(day)
+    } else {
+        return Err(AppError::Other(
+            "Choose how often with --weekly or --daily".to_string(),
+        ));
+    };
+
+    let at = options.at.unwrap_or(DEFAULT_AT);
+    let at = NaiveTime::parse_from_str(at, "%H:%M").map_err(|_| {
+        AppError::ParseError(format!("Invalid time '{at}'. Expected a time like 17:00"))
+    })?;
+
+    let kind = match frequency {
+        Frequency::Daily => "daily",
+        Frequency::Weekly(_) => "weekly",
+    };
+    let mut args = vec![
+        "--profile".to_string(),
+        profile.to_string(),
+        "digest".to_string(),
+    ];
+    if options.weekly {
+        args.push("--week".to_string());
+    }
+    let destination = if let Some(email) = options.email {
+        mail::validate_address(email)?;
+        args.extend(["--email".to_string(), email.to_string()]);
+        format!("emailed to {email}")
+    } else if let Some(output) = options.output {
+        // Schedulers don't start jobs in this directory
+        let output = absolute(output)?;
+        args.extend(["--output".to_string(), output.display().to_string()]);
+        format!("appended to {}", output.display())
+    } else if let Some(digest_file) = digest_file {
+        format!("appended to {}", digest_file.display())
+    } else {
+        return Err(AppError::Other(
+            "Say where digests go with --email or --output, or set digest_file in the config"
+                .to_string(),
+        ));
+    };
+
+    let id = format!("digest-{kind}");
+    let job = ScheduledJob {
+        name: format!("acc-{profile}-{id}"),
+        id,
+        frequency,
+        at,
+        program: env::current_exe()?,
+        args,
+        log: state_dir.join(LOG_FILE),
+        installed_at: Utc::now(),
+    };
+    let replaced = scheduler::install(state_dir, &job)?;
+
+    println!(
+        "✅ {} the {kind} digest: {} at {}, {destination}",
+        if replaced.is_some() {
+            "Rescheduled"
+        } else {
+            "Scheduled"
+        },
+        job.frequency,
+        job.at.format("%H:%M")
+    );
+    if !cfg!(windows) {
+        println!("   Output of each run goes to {}", job.log.display());
+    }
+    Ok(())
+}
+
+/// Lists the digests scheduled for this profile.
+pub fn list(state_dir: &Path) -> Result<(), AppError> {
+    let jobs = scheduler::load(state_dir);
+    if jobs.is_empty() {
+        println!("No digests are scheduled for this profile. Add one with `acc digest schedule --weekly --email <ADDRESS>`.");
+        return Ok(());
+    }
+
+    for job in &jobs {
+        println!("{}", describe(job, scheduler::is_installed(job)));
+    }
+    Ok(())
+}
+
+/// Removes a scheduled digest by the ID `list` shows.
+pub fn remove(state_dir: &Path, id: &str) -> Result<(), AppError> {
+    let job = scheduler::uninstall(state_dir, id)?;
+    println!("✅ Removed the scheduled digest '{}'", job.id);
+    Ok(())
+}
+
+/// One line per job: its ID, when it runs and the command, without the
+/// `--profile` every job starts with.
+fn describe(job: &ScheduledJob, installed: bool) -> String {
+    let command = job
+        .args
+        .iter()
+        .skip_while(|arg| *arg != "digest")
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .join(" ");
+    let mut line = format!(
+        "{:<14} {} at {}  acc {command}",
+        job.id,
+        job.frequency,
+        job.at.format("%H:%M")
+    );
+    if !installed {
+        line.push_str("  (removed from the system scheduler; schedule it again to restore it)");
+    }
+    line
+}
+
+fn absolute(path: &Path) -> Result<PathBuf, AppError> {
+    if path.is_absolute() {
+        Ok(path.to_path_buf())
+    } else {
+        Ok(env::current_dir()?.join(path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_describe() {
+        let job = ScheduledJob {
+            id: "digest-weekly".to_string(),
+            name: "acc-work-digest-weekly".to_string(),
+            frequency: Frequency::Weekly(Weekday::Mon),
+            at: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+            program: PathBuf::from("/usr/local/bin/acc"),
+            args: [
+                "--profile",
+                "work",
+                "digest",
+                "--week",
+                "--email",
+                "me@example.com",
+            ]
+            .map(str::to_string)
+            .to_vec(),
+            log: PathBuf::from("/tmp/digest.log"),
+            installed_at: Utc::now(),
+        };
+        assert_eq!(
+            describe(&job, true),
+            "digest-weekly  every Monday at 09:00  acc digest --week --email me@example.com"
+        );
+        assert!(describe(&job, false)
+            .ends_with("(removed from the system scheduler; schedule it again to restore it)"));
+    }
+
+    #[test]
+    fn test_add_checks_options_before_scheduling() {
+        let state_dir = tempfile::tempdir().unwrap();
+        let options = ScheduleOptions {
+            weekly: false,
+            daily: false,
+            day: None,
+            at: None,
+            email: Some("me@example.com"),
+            output: None,
+        };
+        let no_frequency = add(state_dir.path(), "default", &options, None);
+        assert!(no_frequency
+            .unwrap_err()
+            .to_string()
+            .contains("--weekly or --daily"));
+
+        let weekly = ScheduleOptions {
+            weekly: true,
+            ..options
+        };
+        for (options, message) in [
+            (
+                ScheduleOptions {
+                    day: Some("someday"),
+                    ..weekly
+                },
+                "Invalid day",
+            ),
+            (
+                ScheduleOptions {
+                    at: Some("5pm"),
+                    ..weekly
+                },
+                "Invalid time",
+            ),
+            (
+                ScheduleOptions {
+                    email: Some("me"),
+                    ..weekly
+                },
+                "not an email address",
+            ),
+            (
+                ScheduleOptions {
+                    email: None,
+                    ..weekly
+                },
+                "Say where digests go",
+            ),
+        ] {
+            let err = add(state_dir.path(), "default", &options, None).unwrap_err();
+            assert!(err.to_string().contains(message), "{err}");
+        }
+        assert!(scheduler::load(state_dir.path()).is_empty());
+    }
+}
@@ -0,0 +1,98 @@
+use crate::api::endpoints::update_worklog_entry;
+use crate::auth::AuthService;
+use crate::commands::log::convert_urls_to_markdown;
+use crate::commands::log_amend::edit_changes;
+use crate::commands::logs::find_entry;
+use crate::errors::AppError;
+use serde_json::{json, Map, Value};
+
+/// Edits any entry by ID or short ID. With `messages` or `tags`, those replace
+/// the entry's content or tags; otherwise the entry opens in the editor with
+/// its tags, project and date as front-matter, and only what changed is saved.
+pub async fn execute(
+    auth_service: &mut AuthService,
+    id_or_prefix: &str,
+    messages: &[String],
+    tags: Option<&[String]>,
+) -> Result<String, AppError> {
+    let entry = find_entry(auth_service.api_client(), id_or_prefix).await?;
+    let id = entry.id.clone();
+
+    let changes = if messages.is_empty() && tags.is_none() {
+        edit_changes(auth_service, &Value::from(entry)).await?
+    } else {
+        let mut changes = Map::new();
+        if !messages.is_empty() {
+            changes.insert(
+                "content".to_string(),
+                json!(convert_urls_to_markdown(&messages.join("\n\n"))),
+            );
+        }
+        if let Some(tags) = tags {
+            changes.insert("tags".to_string(), json!(tags));
+        }
+        changes
+    };
+
+    if changes.is_empty() {
+        println!("Nothing changed.");
+        return Ok(id);
+    }
+
+    update_worklog_entry(auth_service.api_client(), &id, Value::Object(changes)).await?;
+    println!("✅ Updated entry {id}");
+    Ok(id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::{Matcher, Server};
+
+    #[tokio::test]
+    async fn test_edit_by_short_id() {
+        let mut server = Server::new_async().await;
+        let mut auth = AuthService::new(server.url(), std::env::temp_dir(), "test-profile");
+        auth.save_access_token("test-token").unwrap();
+
+        let _exact = server
+            .mock("GET", "/api/v1/worklog/entries/3fa85f64")
+            .with_status(404)
+            .create();
+        let _list = server
+            .mock("GET", "/api/v1/worklog/entries")
+            .match_query(Matcher::Any)
+            .with_status(200)
+            .with_body(
+                json!({ "entries": [
+                    { "id": "3fa85f64-5717-4562-b3fc-2c963f66afa6", "content": "Fixd the build" },
+                    { "id": "9b2e0c11-0000-4000-8000-000000000000", "content": "Other" }
+                ] })
+                .to_string(),
+            )
+            .create();
+        let update = server
+            .mock(
+                "PATCH",
+                "/api/v1/worklog/entries/3fa85f64-5717-4562-b3fc-2c963f66afa6",
+            )
+            .match_body(Matcher::Json(json!({
+                "content": "Fixed the build",
+                "tags": ["ci"]
+            })))
+            .with_status(200)
+            .with_body("{}")
+            .create();
+
+        let id = execute(
+            &mut auth,
+            "3fa85f64",
+            &["Fixed the build".into()],
+            Some(&["ci".to_string()]),
+        )
+        .await
+        .unwrap();
+        assert_eq!(id, "3fa85f64-5717-4562-b3fc-2c963f66afa6");
+        update.assert();
+    }
+}
@@ -0,0 +1,437 @@
+use crate::auth::AuthService;
+use crate::commands::logs::fetch_all_entries;
+use crate::commands::project;
+use crate::commands::recap::{self, RecapDestinations, RecapFormat};
+use crate::errors::AppError;
+use crate::pins::Pins;
+use crate::utils::custom_fields::format_custom_fields;
+use crate::utils::redaction::RedactionPolicy;
+use crate::utils::visibility::{Visibility, VisibilityPolicy};
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Entries grouped by calendar month, keyed by the first day of the month.
+type Months = BTreeMap<NaiveDate, Vec<Value>>;
+
+/// Builds a Markdown document for performance reviews covering `from`..`to`:
+/// one section per month with pinned highlights, a recap and entries by project,
+/// followed by an appendix of the commits linked to those entries.
+#[allow(clippy::too_many_arguments)]
+pub async fn review_packet(
+    auth_service: &mut AuthService,
+    from: &str,
+    to: Option<&str>,
+    project_identifier: Option<&str>,
+    audience: Option<&str>,
+    skip_recaps: bool,
+    output: &Path,
+    redaction: &RedactionPolicy,
+    visibility: &VisibilityPolicy,
+) -> Result<(), AppError> {
+    let from = parse_date(from)?;
+    let to = match to {
+        Some(d) => parse_date(d)?,
+        None => Local::now().date_naive(),
+    };
+    if from > to {
+        return Err(AppError::Other(format!(
+            "--from ({from}) must not be after --to ({to})"
+        )));
+    }
+
+    let project_ids = match project_identifier {
+        Some(identifier) => project::resolve_project_filter_ids(auth_service, identifier).await?,
+        None => None,
+    };
+
+    let from_str = from.format("%Y-%m-%d").to_string();
+    let to_str = to.format("%Y-%m-%d").to_string();
+    let entries = fetch_all_entries(
+        auth_service.api_client(),
+        project_ids.as_deref(),
+        None,
+        Some(&from_str),
+        Some(&to_str),
+    )
+    .await?;
+    let minimum = Visibility::minimum_for_audience(audience.unwrap_or("manager"));
+    let mut entries = redaction.apply(visibility.filter(entries, minimum));
+    if entries.is_empty() {
+        return Err(AppError::Other(format!(
+            "No worklog entries found between {from_str} and {to_str}"
+        )));
+    }
+    // The API returns newest first; a review reads better chronologically
+    entries.reverse();
+
+    let months = group_by_month(entries);
+    let pins = Pins::load(auth_service.state_dir());
+
+    let mut recaps = BTreeMap::new();
+    if !skip_recaps {
+        let exclude_tags = redaction.merge_exclude_tags(Some(visibility.exclude_tags(minimum)));
+        for month in months.keys() {
+            let (start, end) = month_bounds(*month, from, to);
+            let start = start.format("%Y-%m-%d").to_string();
+            let end = end.format("%Y-%m-%d").to_string();
+            match recap::execute(
+                auth_service,
+                Some(&start),
+                Some(&end),
+                None,
+                None,
+                exclude_tags.as_deref(),
+                project_identifier,
+                audience,
+                false,
+                RecapFormat::Text,
+                RecapDestinations::default(),
+            )
+            .await
+            {
+                Ok(content) => {
+                    recaps.insert(*month, content);
+                }
+                Err(e) => eprintln!(
+                    "⚠️  Warning: Could not generate recap for {}: {e}",
+                    month.format("%B %Y")
+                ),
+            }
+        }
+    }
+
+    let packet = render_packet(from, to, &months, &recaps, &pins);
+
+    if let Some(dir) = output.parent() {
+        if !dir.as_os_str().is_empty() {
+            fs::create_dir_all(dir)?;
+        }
+    }
+    fs::write(output, packet)?;
+
+    let entry_count: usize = months.values().map(Vec::len).sum();
+    println!(
+        "✅ Review packet with {entry_count} entries across {} months saved to {}",
+        months.len(),
+        output.display()
+    );
+
+    Ok(())
+}
+
+/// Default location for a packet when `--output` isn't given.
+pub fn default_packet_path(reviews_dir: &Path, from: &str, to: Option<&str>) -> PathBuf {
+    let to = to
+        .map(String::from)
+        .unwrap_or_else(|| Local::now().format("%Y-%m-%d").to_string());
+    reviews_dir.join(format!("review-packet-{from}-to-{to}.md"))
+}
+
+fn parse_date(value: &str) -> Result<NaiveDate, AppError> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|_| {
+        AppError::ParseError(format!("Invalid date format: {value}. Expected YYYY-MM-DD"))
+    })
+}
+
+fn recorded_date(entry: &Value) -> Option<NaiveDate> {
+    entry
+        .get("recorded_at")
+        .and_then(Value::as_str)
+        .and_then(|s| s.parse::<DateTime<Local>>().ok())
+        .map(|dt| dt.date_naive())
+}
+
+fn group_by_month(entries: Vec<Value>) -> Months {
+    let mut months = Months::new();
+    for entry in entries {
+        let Some(month) = recorded_date(&entry).and_then(|d| d.with_day(1)) else {
+            continue;
+        };
+        months.entry(month).or_default().push(entry);
+    }
+    months
+}
+
+/// First and last day of `month`, clipped to the packet's range.
+fn month_bounds(month: NaiveDate, from: NaiveDate, to: NaiveDate) -> (NaiveDate, NaiveDate) {
+    let next_month = if month.month() == 12 {
+        NaiveDate::from_ymd_opt(month.year() + 1, 1, 1)
+    } else {
+        NaiveDate::from_ymd_opt(month.year(), month.month() + 1, 1)
+    }
+    .expect("first of the next month is a valid date");
+    let last_day = next_month - Duration::days(1);
+    (month.max(from), last_day.min(to))
+}
+
+fn render_packet(
+    from: NaiveDate,
+    to: NaiveDate,
+    months: &Months,
+    recaps: &BTreeMap<NaiveDate, String>,
+    pins: &Pins,
+) -> String {
+    let all_entries: Vec<&Value> = months.values().flatten().collect();
+    let pinned_count = all_entries.iter().filter(|e| is_pinned(e, pins)).count();
+
+    let mut out = format!(
+        "# Review packet: {} – {}\n\n",
+        from.format("%Y-%m-%d"),
+        to.format("%Y-%m-%d")
+    );
+    out.push_str(&format!(
+        "{} entries over {} months, {pinned_count} highlighted.\n\n",
+        all_entries.len(),
+        months.len()
+    ));
+
+    for (month, entries) in months {
+        out.push_str(&format!("## {}\n\n", month.format("%B %Y")));
+
+        let highlights: Vec<&Value> = entries.iter().filter(|e| is_pinned(e, pins)).collect();
+        if !highlights.is_empty() {
+            out.push_str("### Highlights\n\n");
+            for entry in highlights {
+                out.push_str(&render_entry(entry, true));
+            }
+            out.push('\n');
+        }
+
+        if let Some(recap) = recaps.get(month) {
+            out.push_str("### Recap\n\n");
+            out.push_str(recap.trim());
+            out.push_str("\n\n");
+        }
+
+        out.push_str("### Work by project\n\n");
+        for (project, project_entries) in group_by_project(entries) {
+            out.push_str(&format!("#### {project}\n\n"));
+            // Highlights lead each project too
+            let (pinned, rest): (Vec<&Value>, Vec<&Value>) = project_entries
+                .into_iter()
+                .partition(|e| is_pinned(e, pins));
+            for entry in pinned.into_iter().chain(rest) {
+                out.push_str(&render_entry(entry, is_pinned(entry, pins)));
+            }
+            out.push('\n');
+        }
+    }
+
+    let appendix = render_commit_appendix(&all_entries);
+    if !appendix.is_empty() {
+        out.push_str("## Appendix: Linked commits\n\n");
+        out.push_str(&appendix);
+    }
+
+    out
+}
+
+fn is_pinned(entry: &Value, pins: &Pins) -> bool {
+    entry
+        .get("id")
+        .and_then(Value::as_str)
+        .map(|id| pins.contains(id))
+        .unwrap_or(false)
+}
+
+/// Groups entries by project heading, with unassigned entries last.
+fn group_by_project(entries: &[Value]) -> Vec<(String, Vec<&Value>)> {
+    let mut projects: BTreeMap<String, Vec<&Value>> = BTreeMap::new();
+    let mut unassigned = Vec::new();
+    for entry in entries {
+        match project_heading(entry) {
+            Some(heading) => projects.entry(heading).or_default().push(entry),
+            None => unassigned.push(entry),
+        }
+    }
+
+    let mut grouped: Vec<(String, Vec<&Value>)> = projects.into_iter().collect();
+    if !unassigned.is_empty() {
+        grouped.push(("No project".to_string(), unassigned));
+    }
+    grouped
+}
+
+fn project_heading(entry: &Value) -> Option<String> {
+    let project = entry.get("project")?;
+    let identifier = project
+        .get("identifier")
+        .and_then(Value::as_str)?
+        .to_uppercase();
+    match project.get("name").and_then(Value::as_str) {
+        Some(name) => Some(format!("{name} [{identifier}]")),
+        None => Some(format!("[{identifier}]")),
+    }
+}
+
+fn render_entry(entry: &Value, pinned: bool) -> String {
+    let date = recorded_date(entry)
+        .map(|d| d.format("%Y-%m-%d").to_string())
+        .unwrap_or_else(|| "----------".to_string());
+    let content = entry
+        .get("content")
+        .and_then(Value::as_str)
+        .unwrap_or("")
+        .trim();
+    let tags = entry
+        .get("tags")
+        .and_then(Value::as_array)
+        .map(|arr| {
+            arr.iter()
+                .filter_map(Value::as_str)
+                .map(|t| format!(" `#{t}`"))
+                .collect::<String>()
+        })
+        .unwrap_or_default();
+    let marker = if pinned { "📌 " } else { "" };
+
+    let mut lines = content.lines();
+    let first_line = lines.next().unwrap_or("");
+    let mut out = format!("- {marker}**{date}** {first_line}{tags}\n");
+    for line in lines {
+        if line.trim().is_empty() {
+            continue;
+        }
+        out.push_str(&format!("  {line}\n"));
+    }
+    if let Some(fields) = format_custom_fields(entry) {
+        out.push_str(&format!("  _{fields}_\n"));
+    }
+    let commits = linked_commits(entry);
+    if !commits.is_empty() {
+        out.push_str(&format!("  _{} linked commits_\n", commits.len()));
+    }
+    out
+}
+
+/// `(short sha, summary)` for each commit the API returned with the entry.
+fn linked_commits(entry: &Value) -> Vec<(String, String)> {
+    entry
+        .get("commits")
+        .and_then(Value::as_array)
+        .map(|commits| {
+            commits
+                .iter()
+                .filter_map(|commit| {
+                    let sha = commit.get("sha").and_then(Value::as_str)?;
+                    let summary = commit
+                        .get("message")
+                        .and_then(Value::as_str)
+                        .and_then(|m| m.lines().next())
+                        .unwrap_or("")
+                        .trim()
+                        .to_string();
+                    Some((sha.chars().take(7).collect(), summary))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn render_commit_appendix(entries: &[&Value]) -> String {
+    let mut out = String::new();
+    for entry in entries {
+        let commits = linked_commits(entry);
+        if commits.is_empty() {
+            continue;
+        }
+        let date = recorded_date(entry)
+            .map(|d| d.format("%Y-%m-%d").to_string())
+            .unwrap_or_default();
+        let title = entry
+            .get("content")
+            .and_then(Value::as_str)
+            .and_then(|c| c.trim().lines().next())
+            .unwrap_or("");
+        out.push_str(&format!("### {date} — {title}\n\n"));
+        for (sha, summary) in commits {
+            out.push_str(&format!("- `{sha}` {summary}\n"));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_month_bounds_clip_to_range() {
+        let (start, end) = month_bounds(date("2025-02-01"), date("2025-02-10"), date("2025-06-30"));
+        assert_eq!((start, end), (date("2025-02-10"), date("2025-02-28")));
+
+        let (start, end) = month_bounds(date("2025-12-01"), date("2025-01-01"), date("2026-03-01"));
+        assert_eq!((start, end), (date("2025-12-01"), date("2025-12-31")));
+    }
+
+    #[test]
+    fn test_render_packet_puts_highlights_first() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut pins = Pins::load(temp_dir.path());
+        pins.pin("entry-2");
+
+        let entries = vec![
+            json!({
+                "id": "entry-1",
+                "content": "Reviewed PRs",
+                "recorded_at": "2025-03-03T12:00:00Z",
+                "project": { "identifier": "web", "name": "Website" }
+            }),
+            json!({
+                "id": "entry-2",
+                "content": "Shipped checkout redesign",
+                "recorded_at": "2025-03-20T12:00:00Z",
+                "tags": ["launch"],
+                "project": { "identifier": "web", "name": "Website" },
+                "commits": [{ "sha": "abcdef1234567", "message": "Add new checkout\n\nDetails" }]
+            }),
+            json!({
+                "id": "entry-3",
+                "content": "Team offsite",
+                "recorded_at": "2025-04-02T12:00:00Z"
+            }),
+        ];
+        let months = group_by_month(entries);
+        let mut recaps = BTreeMap::new();
+        recaps.insert(date("2025-03-01"), "A strong month.".to_string());
+
+        let packet = render_packet(
+            date("2025-03-01"),
+            date("2025-04-30"),
+            &months,
+            &recaps,
+            &pins,
+        );
+
+        assert!(packet.starts_with("# Review packet: 2025-03-01 – 2025-04-30\n"));
+        assert!(packet.contains("3 entries over 2 months, 1 highlighted."));
+        let march = packet.find("## March 2025").unwrap();
+        let highlights = packet.find("### Highlights").unwrap();
+        let recap = packet.find("A strong month.").unwrap();
+        let april = packet.find("## April 2025").unwrap();
+        assert!(march < highlights && highlights < recap && recap < april);
+
+        // Within the project, the pinned entry comes before the earlier one
+        let project = packet.find("#### Website [WEB]").unwrap();
+        let pinned = packet[project..]
+            .find("📌 **2025-03-20** Shipped checkout redesign `#launch`")
+            .unwrap();
+        let other = packet[project..].find("Reviewed PRs").unwrap();
+        assert!(pinned < other);
+
+        assert!(packet.contains("#### No project\n\n- **2025-04-02** Team offsite\n"));
+        assert!(packet.contains(
+            "## Appendix: Linked commits\n\n### 2025-03-20 — Shipped checkout redesign\n\n- `abcdef1` Add new checkout\n"
+        ));
+    }
+}
@@ -0,0 +1,228 @@
+use crate::config;
+use crate::errors::AppError;
+use crate::utils::commit_message::PROJECT_TRAILER;
+use git2::Repository;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// First comment line of every hook we write, so a later install knows the
+/// hook is ours to rewrite.
+const MARKER: &str = "# Installed by `acc hooks install`";
+
+/// Which git hook `install` writes.
+#[derive(Debug, Clone, Copy)]
+pub enum GitHook<'a> {
+    /// `post-commit`: captures each new commit with `acc capture --auto
+    /// --quiet` in the background, so committing never waits on the network.
+    /// Errors from the capture go to `acc-capture.log` in the git directory.
+    PostCommit { profile: &'a str },
+    /// `prepare-commit-msg`: adds an `Accomplish-Project` trailer to each
+    /// commit message, which `acc capture` falls back on when the directory
+    /// has no project. The project defaults to the repository's.
+    PrepareCommitMsg { project: Option<&'a str> },
+}
+
+impl GitHook<'_> {
+    fn name(&self) -> &'static str {
+        match self {
+            GitHook::PostCommit { .. } => "post-commit",
+            GitHook::PrepareCommitMsg { .. } => "prepare-commit-msg",
+        }
+    }
+}
+
+/// Writes `hook` into the repository containing `path` (or the current
+/// directory).
+///
+/// A hook of the same name that wasn't written by us is left alone unless
+/// `force` is given, in which case it's kept with a `.backup` suffix.
+pub fn install(path: Option<&Path>, hook: GitHook, force: bool) -> Result<(), AppError> {
+    let dir = match path {
+        Some(path) => path.to_path_buf(),
+        None => std::env::current_dir()
+            .map_err(|e| AppError::Other(format!("Failed to get current directory: {e}")))?,
+    };
+    let repo = Repository::discover(&dir)
+        .map_err(|_| AppError::Other(format!("'{}' is not in a git repository", dir.display())))?;
+    let workdir = repo.workdir().ok_or_else(|| {
+        AppError::Other("Bare repositories have no commits made in them to capture".to_string())
+    })?;
+
+    let name = hook.name();
+    let (script, manual_line) = match hook {
+        GitHook::PostCommit { profile } => (
+            post_commit_script(profile),
+            "acc capture --auto --quiet &".to_string(),
+        ),
+        GitHook::PrepareCommitMsg { project } => {
+            let project = project
+                .map(str::to_string)
+                .or_else(|| config::lookup_default_project_for_dir(workdir))
+                .ok_or_else(|| {
+                    AppError::Other(
+                        "This repository has no project. Pass --project, or run `acc init` first"
+                            .to_string(),
+                    )
+                })?;
+            if !project
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "-_./".contains(c))
+            {
+                return Err(AppError::Other(format!(
+                    "'{project}' isn't a project identifier"
+                )));
+            }
+            (
+                prepare_commit_msg_script(&project),
+                format!("git interpret-trailers --in-place --if-exists doNothing --trailer '{PROJECT_TRAILER}: {project}' \"$1\""),
+            )
+        }
+    };
+
+    let hooks_dir = hooks_dir(&repo, workdir);
+    fs::create_dir_all(&hooks_dir)?;
+    let path = hooks_dir.join(name);
+
+    let mut backup = None;
+    if let Ok(existing) = fs::read_to_string(&path) {
+        if !existing.contains(MARKER) {
+            if !force {
+                return Err(AppError::Other(format!(
+                    "{} already exists. Add `{manual_line}` to it yourself, or pass --force to replace it (it's kept as {name}.backup)",
+                    path.display()
+                )));
+            }
+            let backup_path = path.with_file_name(format!("{name}.backup"));
+            fs::rename(&path, &backup_path)?;
+            backup = Some(backup_path);
+        }
+    }
+
+    fs::write(&path, script)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755))?;
+    }
+
+    println!("✅ Installed {}", path.display());
+    if let Some(backup) = backup {
+        println!("   The previous hook was moved to {}", backup.display());
+    }
+    match hook {
+        GitHook::PostCommit { profile } => {
+            println!("   Each commit is now captured in the background for profile '{profile}'.");
+            if config::lookup_default_project_for_dir(workdir).is_none() {
+                println!(
+                    "   This repository has no project yet. Run `acc init` so commits have somewhere to go."
+                );
+            }
+        }
+        GitHook::PrepareCommitMsg { .. } => {
+            println!("   Commit messages now end with an {PROJECT_TRAILER} trailer.");
+        }
+    }
+    Ok(())
+}
+
+/// `core.hooksPath` when set (relative to the working tree), otherwise the
+/// `hooks` directory shared by all worktrees.
+fn hooks_dir(repo: &Repository, workdir: &Path) -> PathBuf {
+    match repo
+        .config()
+        .and_then(|config| config.get_path("core.hooksPath"))
+    {
+        Ok(path) => workdir.join(path),
+        Err(_) => repo.commondir().join("hooks"),
+    }
+}
+
+/// The profile is fixed at install time, so switching profiles later doesn't
+/// send this repository's commits to another account.
+fn post_commit_script(profile: &str) -> String {
+    format!(
+        "#!/bin/sh\n\
+         {MARKER}. Captures each new commit with Accomplish.\n\
+         # Remove this file to stop, or run `acc hooks install` again to update it.\n\
+         command -v acc >/dev/null 2>&1 || exit 0\n\
+         log=\"$(git rev-parse --git-common-dir)/acc-capture.log\"\n\
+         acc --profile '{profile}' capture --auto --quiet </dev/null >/dev/null 2>>\"$log\" &\n\
+         exit 0\n"
+    )
+}
+
+/// Merges and squashes are left alone, and a message that already names a
+/// project keeps it, e.g. when amending.
+fn prepare_commit_msg_script(project: &str) -> String {
+    format!(
+        "#!/bin/sh\n\
+         {MARKER}. Adds the Accomplish project to each commit message.\n\
+         # Remove this file to stop, or run `acc hooks install --prepare-commit-msg` again to update it.\n\
+         case \"$2\" in merge|squash) exit 0 ;; esac\n\
+         git interpret-trailers --in-place --if-exists doNothing --trailer '{PROJECT_TRAILER}: {project}' \"$1\"\n"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_install_writes_hook_and_keeps_foreign_ones() {
+        let temp_dir = TempDir::new().unwrap();
+        Repository::init(temp_dir.path()).unwrap();
+        let hook = temp_dir.path().join(".git/hooks/post-commit");
+
+        let work = GitHook::PostCommit { profile: "work" };
+        install(Some(temp_dir.path()), work, false).unwrap();
+        let script = fs::read_to_string(&hook).unwrap();
+        assert!(script.contains("acc --profile 'work' capture --auto --quiet"));
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_ne!(fs::metadata(&hook).unwrap().permissions().mode() & 0o111, 0);
+        }
+
+        // Our own hook is rewritten without --force
+        let personal = GitHook::PostCommit {
+            profile: "personal",
+        };
+        install(Some(temp_dir.path()), personal, false).unwrap();
+        assert!(fs::read_to_string(&hook).unwrap().contains("'personal'"));
+
+        fs::write(&hook, "#!/bin/sh\nmake lint\n").unwrap();
+        assert!(install(Some(temp_dir.path()), work, false).is_err());
+        assert_eq!(fs::read_to_string(&hook).unwrap(), "#!/bin/sh\nmake lint\n");
+
+        install(Some(temp_dir.path()), work, true).unwrap();
+        assert!(fs::read_to_string(&hook).unwrap().contains(MARKER));
+        assert_eq!(
+            fs::read_to_string(hook.with_file_name("post-commit.backup")).unwrap(),
+            "#!/bin/sh\nmake lint\n"
+        );
+    }
+
+    #[test]
+    fn test_prepare_commit_msg_hook_needs_a_project() {
+        let temp_dir = TempDir::new().unwrap();
+        Repository::init(temp_dir.path()).unwrap();
+        let hook = temp_dir.path().join(".git/hooks/prepare-commit-msg");
+
+        let unknown = GitHook::PrepareCommitMsg { project: None };
+        assert!(install(Some(temp_dir.path()), unknown, false).is_err());
+        let quoted = GitHook::PrepareCommitMsg {
+            project: Some("web'; rm -rf ~"),
+        };
+        assert!(install(Some(temp_dir.path()), quoted, false).is_err());
+        assert!(!hook.exists());
+
+        let web = GitHook::PrepareCommitMsg {
+            project: Some("web"),
+        };
+        install(Some(temp_dir.path()), web, false).unwrap();
+        assert!(fs::read_to_string(&hook)
+            .unwrap()
+            .contains("--trailer 'Accomplish-Project: web' \"$1\""));
+    }
+}
@@ -0,0 +1,384 @@
+use crate::auth::AuthService;
+use crate::commands::logs::fetch_all_entries;
+use crate::errors::AppError;
+use chrono::{DateTime, Local, NaiveDate};
+use colored::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const GOALS_FILE: &str = "goals.toml";
+const PROGRESS_BAR_WIDTH: usize = 20;
+
+/// A goal tracked through the entries logged with its tags.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Goal {
+    pub name: String,
+    pub tags: Vec<String>,
+    /// Day from which entries count towards the goal
+    pub start_date: NaiveDate,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_date: Option<NaiveDate>,
+    /// Number of entries that marks the goal as done
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_entries: Option<u32>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct GoalsFile {
+    #[serde(default)]
+    goals: Vec<Goal>,
+}
+
+fn goals_path(state_dir: &Path) -> PathBuf {
+    state_dir.join(GOALS_FILE)
+}
+
+fn load_goals(state_dir: &Path) -> Result<Vec<Goal>, AppError> {
+    let path = goals_path(state_dir);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(&path)?;
+    let file: GoalsFile = toml::from_str(&content)
+        .map_err(|e| AppError::ParseError(format!("Failed to parse {}: {e}", path.display())))?;
+    Ok(file.goals)
+}
+
+fn save_goals(state_dir: &Path, goals: Vec<Goal>) -> Result<(), AppError> {
+    fs::create_dir_all(state_dir)?;
+    let content = toml::to_string_pretty(&GoalsFile { goals })
+        .map_err(|e| AppError::ParseError(format!("Failed to serialize goals: {e}")))?;
+    fs::write(goals_path(state_dir), content)?;
+    Ok(())
+}
+
+fn parse_date(value: &str) -> Result<NaiveDate, AppError> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|_| {
+        AppError::ParseError(format!("Invalid date format: {value}. Expected YYYY-MM-DD"))
+    })
+}
+
+/// Adds a goal (or replaces one with the same name) to the profile's goals file.
+pub fn add(
+    state_dir: &Path,
+    name: &str,
+    tags: &[String],
+    start_date: Option<&str>,
+    target_date: Option<&str>,
+    target_entries: Option<u32>,
+) -> Result<(), AppError> {
+    if name.trim().is_empty() {
+        return Err(AppError::ParseError(
+            "Goal name cannot be empty".to_string(),
+        ));
+    }
+    if tags.is_empty() {
+        return Err(AppError::ParseError(
+            "A goal needs at least one tag (--tag)".to_string(),
+        ));
+    }
+    if target_entries == Some(0) {
+        return Err(AppError::ParseError(
+            "--target-entries must be greater than zero".to_string(),
+        ));
+    }
+
+    let goal = Goal {
+        name: name.trim().to_string(),
+        tags: tags.iter().map(|t| t.trim().to_lowercase()).collect(),
+        start_date: match start_date {
+            Some(d) => parse_date(d)?,
+            None => Local::now().date_naive(),
+        },
+        target_date: target_date.map(parse_date).transpose()?,
+        target_entries,
+    };
+
+    let mut goals = load_goals(state_dir)?;
+    let replaced = goals
+        .iter()
+        .any(|g| g.name.eq_ignore_ascii_case(&goal.name));
+    goals.retain(|g| !g.name.eq_ignore_ascii_case(&goal.name));
+    goals.push(goal.clone());
+    save_goals(state_dir, goals)?;
+
+    let verb = if replaced { "Updated" } else { "Added" };
+    println!(
+        "✓ {verb} goal '{}' tracking tags: {}",
+        goal.name,
+        goal.tags.join(", ")
+    );
+    Ok(())
+}
+
+/// Removes a goal by name (case-insensitive).
+pub fn remove(state_dir: &Path, name: &str) -> Result<(), AppError> {
+    let mut goals = load_goals(state_dir)?;
+    let before = goals.len();
+    goals.retain(|g| !g.name.eq_ignore_ascii_case(name.trim()));
+
+    if goals.len() == before {
+        return Err(AppError::Other(format!("No goal named '{name}'")));
+    }
+
+    save_goals(state_dir, goals)?;
+    println!("✓ Removed goal '{name}'");
+    Ok(())
+}
+
+/// Shows progress for every goal based on the entries logged with its tags.
+pub async fn status(auth_service: &mut AuthService) -> Result<(), AppError> {
+    let goals = load_goals(auth_service.state_dir())?;
+
+    if goals.is_empty() {
+        println!("No goals defined. Add one with `acc goals add \"<name>\" --tag <tag>`.");
+        return Ok(());
+    }
+
+    let today = Local::now().date_naive();
+
+    for goal in &goals {
+        let from = goal.start_date.format("%Y-%m-%d").to_string();
+        let entries = fetch_all_entries(
+            auth_service.api_client(),
+            None,
+            Some(&goal.tags),
+            Some(&from),
+            None,
+        )
+        .await?;
+        let progress = GoalProgress::from_entries(goal, &entries);
+
+        print_goal(goal, &progress, today);
+    }
+
+    Ok(())
+}
+
+/// Entry activity counted towards one goal.
+#[derive(Debug, PartialEq)]
+struct GoalProgress {
+    entries: usize,
+    last_entry: Option<NaiveDate>,
+}
+
+impl GoalProgress {
+    /// Counts entries carrying any of the goal's tags. The API filter is applied
+    /// too, but matching here keeps counts right whichever way it combines tags.
+    fn from_entries(goal: &Goal, entries: &[Value]) -> Self {
+        let matching: Vec<&Value> = entries
+            .iter()
+            .filter(|entry| {
+                entry
+                    .get("tags")
+                    .and_then(Value::as_array)
+                    .map(|tags| {
+                        tags.iter()
+                            .filter_map(Value::as_str)
+                            .any(|t| goal.tags.iter().any(|g| g.eq_ignore_ascii_case(t)))
+                    })
+                    .unwrap_or(false)
+            })
+            .collect();
+
+        let last_entry = matching
+            .iter()
+            .filter_map(|e| e.get("recorded_at").and_then(Value::as_str))
+            .filter_map(|s| s.parse::<DateTime<Local>>().ok())
+            .map(|dt| dt.date_naive())
+            .max();
+
+        Self {
+            entries: matching.len(),
+            last_entry,
+        }
+    }
+
+    /// Completion ratio: entries against the entry target, or else elapsed time
+    /// against the target date. None when the goal has neither.
+    fn ratio(&self, goal: &Goal, today: NaiveDate) -> Option<f64> {
+        if let Some(target) = goal.target_entries {
+            return Some((self.entries as f64 / target as f64).min(1.0));
+        }
+
+        let target_date = goal.target_date?;
+        let total = (target_date - goal.start_date).num_days();
+        if total <= 0 {
+            return Some(1.0);
+        }
+        let elapsed = (today - goal.start_date).num_days().clamp(0, total);
+        Some(elapsed as f64 / total as f64)
+    }
+}
+
+fn progress_bar(ratio: f64) -> String {
+    let filled = (ratio * PROGRESS_BAR_WIDTH as f64).round() as usize;
+    format!(
+        "{}{}",
+        "█".repeat(filled),
+        "░".repeat(PROGRESS_BAR_WIDTH - filled)
+    )
+}
+
+fn print_goal(goal: &Goal, progress: &GoalProgress, today: NaiveDate) {
+    println!(
+        "{} {}",
+        goal.name.bold(),
+        format!("(#{})", goal.tags.join(", #")).bright_black()
+    );
+
+    match progress.ratio(goal, today) {
+        Some(ratio) => {
+            let bar = progress_bar(ratio);
+            let bar = if ratio >= 1.0 {
+                bar.bright_green()
+            } else {
+                bar.bright_blue()
+            };
+            let label = match goal.target_entries {
+                Some(target) => format!("{}/{target} entries", progress.entries),
+                None => format!("{} entries, time elapsed", progress.entries),
+            };
+            println!("  {bar} {:>3.0}%  {label}", ratio * 100.0);
+        }
+        None => println!("  {} entries", progress.entries),
+    }
+
+    let recency = match progress.last_entry {
+        Some(day) => {
+            let age = (today - day).num_days();
+            let text = match age {
+                0 => "Last entry today".to_string(),
+                1 => "Last entry yesterday".to_string(),
+                n => format!("Last entry {n} days ago"),
+            };
+            match age {
+                0..=7 => text.bright_green(),
+                8..=14 => text.bright_yellow(),
+                _ => text.bright_red(),
+            }
+        }
+        None => "No entries yet".bright_red(),
+    };
+    print!("  {recency}");
+
+    if let Some(target_date) = goal.target_date {
+        let days_left = (target_date - today).num_days();
+        if days_left >= 0 {
+            print!("  ·  due {target_date} ({days_left} days left)");
+        } else {
+            print!(
+                "  ·  {}",
+                format!("overdue since {target_date}").bright_red()
+            );
+        }
+    }
+    println!();
+    println!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    fn goal(target_date: Option<&str>, target_entries: Option<u32>) -> Goal {
+        Goal {
+            name: "Ship billing".to_string(),
+            tags: vec!["billing".to_string()],
+            start_date: date("2025-07-01"),
+            target_date: target_date.map(date),
+            target_entries,
+        }
+    }
+
+    #[test]
+    fn test_add_replace_and_remove_goals() {
+        let temp_dir = TempDir::new().unwrap();
+        let tags = vec!["Billing".to_string()];
+
+        add(
+            temp_dir.path(),
+            "Ship billing",
+            &tags,
+            Some("2025-07-01"),
+            Some("2025-09-30"),
+            None,
+        )
+        .unwrap();
+        add(temp_dir.path(), "ship billing", &tags, None, None, Some(10)).unwrap();
+
+        let goals = load_goals(temp_dir.path()).unwrap();
+        assert_eq!(goals.len(), 1);
+        assert_eq!(goals[0].tags, vec!["billing"]);
+        assert_eq!(goals[0].target_entries, Some(10));
+        assert_eq!(goals[0].target_date, None);
+
+        remove(temp_dir.path(), "SHIP BILLING").unwrap();
+        assert!(load_goals(temp_dir.path()).unwrap().is_empty());
+        assert!(remove(temp_dir.path(), "missing").is_err());
+    }
+
+    #[test]
+    fn test_add_validation() {
+        let temp_dir = TempDir::new().unwrap();
+        let tags = vec!["billing".to_string()];
+
+        assert!(add(temp_dir.path(), " ", &tags, None, None, None).is_err());
+        assert!(add(temp_dir.path(), "Goal", &[], None, None, None).is_err());
+        assert!(add(temp_dir.path(), "Goal", &tags, None, Some("soon"), None).is_err());
+        assert!(add(temp_dir.path(), "Goal", &tags, None, None, Some(0)).is_err());
+    }
+
+    #[test]
+    fn test_progress_from_entries() {
+        let entries = vec![
+            json!({ "tags": ["billing"], "recorded_at": "2025-07-03T12:00:00Z" }),
+            json!({ "tags": ["BILLING", "rust"], "recorded_at": "2025-07-08T12:00:00Z" }),
+            json!({ "tags": ["meeting"], "recorded_at": "2025-07-09T12:00:00Z" }),
+        ];
+
+        let progress = GoalProgress::from_entries(&goal(None, Some(4)), &entries);
+        assert_eq!(progress.entries, 2);
+        assert_eq!(progress.last_entry, Some(date("2025-07-08")));
+        assert_eq!(
+            progress.ratio(&goal(None, Some(4)), date("2025-07-10")),
+            Some(0.5)
+        );
+    }
+
+    #[test]
+    fn test_ratio_by_target_date() {
+        let progress = GoalProgress {
+            entries: 3,
+            last_entry: None,
+        };
+        let by_date = goal(Some("2025-07-11"), None);
+
+        assert_eq!(progress.ratio(&by_date, date("2025-07-06")), Some(0.5));
+        assert_eq!(progress.ratio(&by_date, date("2025-08-01")), Some(1.0));
+        assert_eq!(progress.ratio(&goal(None, None), date("2025-07-06")), None);
+        assert_eq!(
+            progress.ratio(&goal(None, Some(2)), date("2025-07-06")),
+            Some(1.0)
+        );
+    }
+
+    #[test]
+    fn test_progress_bar() {
+        assert_eq!(progress_bar(0.0), "░".repeat(PROGRESS_BAR_WIDTH));
+        assert_eq!(progress_bar(1.0), "█".repeat(PROGRESS_BAR_WIDTH));
+        assert_eq!(
+            progress_bar(0.5),
+            format!("{}{}", "█".repeat(10), "░".repeat(10))
+        );
+    }
+}
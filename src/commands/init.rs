@@ -1,13 +1,35 @@
 use crate::api::endpoints;
 use crate::auth::AuthService;
-use crate::commands::project::{get_projects, Project};
+use crate::commands::project::{create_project, get_projects, resolve_project, Project};
 use crate::errors::AppError;
+use crate::utils::prompt::PromptOrDefault;
 use dirs_next::home_dir;
+use inquire::validator::Validation;
 use inquire::{Confirm, Select, Text};
 use std::fs;
 use std::path::Path;
 
-pub async fn execute(auth_service: &mut AuthService) -> Result<(), AppError> {
+const LOCAL_CONFIG_FILE: &str = ".accomplish.toml";
+const CREATE_PROJECT_OPTION: &str = "+ Create a new project";
+
+/// Answers given on the command line, so `acc init` can run in scripts
+/// without prompting.
+#[derive(Debug, Default)]
+pub struct InitOptions {
+    /// Project identifier (or parent/child path) to associate
+    pub project: Option<String>,
+    /// `Some(true)` for --local, `Some(false)` for --global
+    pub local: Option<bool>,
+    /// Repository name to create in Accomplish for git repositories
+    pub repo_name: Option<String>,
+    /// Reinitialize without asking and take the default for anything not given
+    pub yes: bool,
+}
+
+pub async fn execute(
+    auth_service: &mut AuthService,
+    options: &InitOptions,
+) -> Result<(), AppError> {
     let current_dir = std::env::current_dir()
         .map_err(|e| AppError::ParseError(format!("Failed to get current directory: {e}")))?;
 
@@ -22,11 +44,12 @@ pub async fn execute(auth_service: &mut AuthService) -> Result<(), AppError> {
         let config_type = if has_local_config { "local" } else { "global" };
         println!("Directory is already initialized with a project ({config_type} config).");
 
-        let proceed = Confirm::new("Do you want to reinitialize this directory?")
-            .with_help_message("This will replace the existing configuration")
-            .with_default(false)
-            .prompt()
-            .map_err(|e| AppError::ParseError(format!("Confirmation failed: {e}")))?;
+        let proceed = options.yes
+            || Confirm::new("Do you want to reinitialize this directory?")
+                .with_help_message("This will replace the existing configuration")
+                .with_default(false)
+                .prompt_or_default()
+                .map_err(|e| AppError::ParseError(format!("Confirmation failed: {e}")))?;
 
         if !proceed {
             println!("Operation cancelled.");
@@ -47,39 +70,32 @@ pub async fn execute(auth_service: &mut AuthService) -> Result<(), AppError> {
     // Fetch available projects
     let projects = get_projects(auth_service).await?;
 
-    if projects.is_empty() {
-        println!("No projects found. Please create a project first using 'acc project new'.");
-        return Ok(());
-    }
-
-    // Create selection options
-    let mut options: Vec<String> = projects
-        .iter()
-        .map(|p| format!("{} - {}", p.identifier.to_uppercase(), p.name))
-        .collect();
-    options.push("Cancel".to_string());
-
-    // Interactive selection
-    let selected = Select::new(
-        "Select a project to associate with this directory:",
-        options,
-    )
-    .with_help_message("Use arrow keys to navigate, Enter to select")
-    .prompt()
-    .map_err(|e| AppError::ParseError(format!("Selection failed: {e}")))?;
+    let selected_project = match &options.project {
+        Some(spec) => resolve_project(&projects, spec).cloned().ok_or_else(|| {
+            AppError::Other(format!(
+                "No project found with identifier '{spec}'. See `acc project list`"
+            ))
+        })?,
+        None => {
+            let Some(project) = choose_project(
+                auth_service,
+                &projects,
+                "Select a project to associate with this directory:",
+            )
+            .await?
+            else {
+                println!("Operation cancelled.");
+                return Ok(());
+            };
+            project
+        }
+    };
+    let selected_project = &selected_project;
 
-    // Handle cancellation
-    if selected == "Cancel" {
-        println!("Operation cancelled.");
-        return Ok(());
+    if options.repo_name.is_some() && !is_git_repo {
+        eprintln!("⚠️  Warning: Not a git repository, so --repo-name is ignored");
     }
 
-    // Find the selected project
-    let selected_project = projects
-        .iter()
-        .find(|p| selected.starts_with(&p.identifier.to_uppercase()))
-        .ok_or_else(|| AppError::ParseError("Selected project not found".to_string()))?;
-
     // Create repository if it's a git repo
     if is_git_repo {
         let git_remote = get_git_remote(&current_dir);
@@ -89,28 +105,11 @@ pub async fn execute(auth_service: &mut AuthService) -> Result<(), AppError> {
         let mut existing_repo = None;
         if let Some(ref remote_url) = git_remote {
             match endpoints::fetch_repositories(auth_service.api_client()).await {
-                Ok(response) => {
-                    if let Some(repositories) =
-                        response.get("repositories").and_then(|v| v.as_array())
-                    {
-                        existing_repo = repositories
-                            .iter()
-                            .find(|repo| {
-                                // Filter by project_id and remote_url
-                                let same_project = repo
-                                    .get("project_id")
-                                    .and_then(|v| v.as_str())
-                                    .map(|id| id == selected_project.id)
-                                    .unwrap_or(false);
-                                let same_remote = repo
-                                    .get("remote_url")
-                                    .and_then(|v| v.as_str())
-                                    .map(|url| url == remote_url)
-                                    .unwrap_or(false);
-                                same_project && same_remote
-                            })
-                            .cloned();
-                    }
+                Ok(repositories) => {
+                    existing_repo = repositories.into_iter().find(|repo| {
+                        repo.project_id.as_deref() == Some(selected_project.id.as_str())
+                            && repo.remote_url.as_deref() == Some(remote_url.as_str())
+                    });
                 }
                 Err(e) => {
                     eprintln!("⚠️  Warning: Could not check for existing repositories: {e}");
@@ -121,20 +120,22 @@ pub async fn execute(auth_service: &mut AuthService) -> Result<(), AppError> {
         if let Some(repo) = existing_repo {
             // Repository already exists
             println!("✓ Repository already exists in project");
-            if let Some(repo_name) = repo.get("name").and_then(|v| v.as_str()) {
-                println!("  Repository name: {repo_name}");
-            }
-            if let Some(repo_id) = repo.get("id").and_then(|v| v.as_str()) {
-                println!("  Repository ID: {repo_id}");
+            if !repo.name.is_empty() {
+                println!("  Repository name: {}", repo.name);
             }
+            println!("  Repository ID: {}", repo.id);
         } else {
             // Create new repository
             let default_repo_name = derive_repo_name(&current_dir, git_remote.as_deref());
-            let repo_name = Text::new("Repository name:")
-                .with_default(&default_repo_name)
-                .with_help_message("This will be the name of the repository in Accomplish")
-                .prompt()
-                .map_err(|e| AppError::ParseError(format!("Input failed: {e}")))?;
+            let repo_name = match &options.repo_name {
+                Some(name) => name.clone(),
+                None if options.yes => default_repo_name,
+                None => Text::new("Repository name:")
+                    .with_default(&default_repo_name)
+                    .with_help_message("This will be the name of the repository in Accomplish")
+                    .prompt_or_default()
+                    .map_err(|e| AppError::ParseError(format!("Input failed: {e}")))?,
+            };
 
             let local_path = current_dir.to_string_lossy().to_string();
 
@@ -148,11 +149,9 @@ pub async fn execute(auth_service: &mut AuthService) -> Result<(), AppError> {
             )
             .await
             {
-                Ok(repo_response) => {
+                Ok(repository) => {
                     println!("✓ Repository '{repo_name}' created successfully");
-                    if let Some(repo_id) = repo_response.get("id").and_then(|v| v.as_str()) {
-                        println!("  Repository ID: {repo_id}");
-                    }
+                    println!("  Repository ID: {}", repository.id);
                 }
                 Err(e) => {
                     eprintln!("⚠️  Warning: Failed to create repository: {e}");
@@ -163,18 +162,23 @@ pub async fn execute(auth_service: &mut AuthService) -> Result<(), AppError> {
     }
 
     // Ask user where to store the configuration
-    let use_local = if is_git_repo {
+    let use_local = if let Some(local) = options.local {
+        local
+    } else if options.yes {
+        // The same defaults the prompts below offer
+        !is_git_repo
+    } else if is_git_repo {
         Confirm::new("Store configuration locally in .accomplish.toml? (No = store globally)")
-            .with_help_message("Local: adds .accomplish.toml to repo (remember to add to .gitignore)\nGlobal: stores in ~/.accomplish/directories.toml")
+            .with_help_message("Local: adds .accomplish.toml to repo (optionally ignored via .gitignore)\nGlobal: stores in ~/.accomplish/directories.toml")
             .with_default(false)
-            .prompt()
+            .prompt_or_default()
             .map_err(|e| AppError::ParseError(format!("Confirmation failed: {e}")))?
     } else {
         // For non-git folders, default to local but still give option
         Confirm::new("Store configuration locally in .accomplish.toml? (No = store globally)")
             .with_help_message("Local: creates .accomplish.toml in this folder\nGlobal: stores in ~/.accomplish/directories.toml")
             .with_default(true)
-            .prompt()
+            .prompt_or_default()
             .map_err(|e| AppError::ParseError(format!("Confirmation failed: {e}")))?
     };
 
@@ -192,7 +196,7 @@ pub async fn execute(auth_service: &mut AuthService) -> Result<(), AppError> {
             selected_project.identifier.to_uppercase()
         );
         if is_git_repo {
-            println!("⚠️  Remember to add .accomplish.toml to your .gitignore file!");
+            offer_gitignore_entry(&current_dir, options.yes)?;
         }
     } else {
         create_global_config(&current_dir, selected_project, is_git_repo)?;
@@ -210,6 +214,99 @@ pub async fn execute(auth_service: &mut AuthService) -> Result<(), AppError> {
     Ok(())
 }
 
+/// Lets the user pick one of `projects` or create a new one inline. Returns
+/// `None` if they cancel.
+pub async fn choose_project(
+    auth_service: &mut AuthService,
+    projects: &[Project],
+    message: &str,
+) -> Result<Option<Project>, AppError> {
+    if projects.is_empty() {
+        let create = Confirm::new("No projects found. Create one now?")
+            .with_default(true)
+            .prompt_or_default()
+            .map_err(|e| AppError::ParseError(format!("Confirmation failed: {e}")))?;
+
+        if !create {
+            return Ok(None);
+        }
+
+        return prompt_and_create_project(auth_service).await.map(Some);
+    }
+
+    // Create selection options
+    let mut options: Vec<String> = projects
+        .iter()
+        .map(|p| format!("{} - {}", p.identifier.to_uppercase(), p.name))
+        .collect();
+    options.push(CREATE_PROJECT_OPTION.to_string());
+    options.push("Cancel".to_string());
+
+    // Interactive selection
+    let selected = Select::new(message, options)
+        .with_help_message("Use arrow keys to navigate, Enter to select")
+        .prompt()
+        .map_err(|e| AppError::ParseError(format!("Selection failed: {e}")))?;
+
+    // Handle cancellation
+    if selected == "Cancel" {
+        return Ok(None);
+    }
+
+    if selected == CREATE_PROJECT_OPTION {
+        prompt_and_create_project(auth_service).await.map(Some)
+    } else {
+        // Find the selected project
+        projects
+            .iter()
+            .find(|p| selected.starts_with(&p.identifier.to_uppercase()))
+            .cloned()
+            .map(Some)
+            .ok_or_else(|| AppError::ParseError("Selected project not found".to_string()))
+    }
+}
+
+/// Prompts for the new project's details and creates it, so init can continue
+/// without sending the user off to `acc project new`.
+async fn prompt_and_create_project(auth_service: &mut AuthService) -> Result<Project, AppError> {
+    let name = Text::new("Project name:")
+        .with_validator(|input: &str| {
+            if input.trim().is_empty() {
+                Ok(Validation::Invalid("Project name cannot be empty".into()))
+            } else {
+                Ok(Validation::Valid)
+            }
+        })
+        .prompt_or_default()
+        .map_err(|e| AppError::ParseError(format!("Input failed: {e}")))?;
+
+    let description = Text::new("Description (optional):")
+        .prompt_or_default()
+        .map_err(|e| AppError::ParseError(format!("Input failed: {e}")))?;
+
+    let identifier = Text::new("Identifier (optional):")
+        .with_help_message("Up to 3 letters, e.g. WEB. Leave empty to generate one")
+        .prompt_or_default()
+        .map_err(|e| AppError::ParseError(format!("Input failed: {e}")))?;
+
+    create_project(
+        auth_service,
+        name.trim(),
+        non_empty(&description),
+        non_empty(&identifier),
+    )
+    .await
+}
+
+fn non_empty(input: &str) -> Option<&str> {
+    let trimmed = input.trim();
+    if trimmed.is_empty() {
+        None
+    } else {
+        Some(trimmed)
+    }
+}
+
 fn create_local_config(dir: &Path, project: &Project, is_git_repo: bool) -> Result<(), AppError> {
     let config_path = dir.join(".accomplish.toml");
 
@@ -250,7 +347,63 @@ type = "folder"
     Ok(())
 }
 
-fn create_global_config(dir: &Path, project: &Project, is_git_repo: bool) -> Result<(), AppError> {
+/// Offers to add `.accomplish.toml` to the repository's `.gitignore`, or
+/// adds it without asking when `yes` is set.
+fn offer_gitignore_entry(dir: &Path, yes: bool) -> Result<(), AppError> {
+    let gitignore_path = dir.join(".gitignore");
+    if gitignore_contains_entry(&gitignore_path) {
+        return Ok(());
+    }
+
+    let add_entry = yes
+        || Confirm::new("Add .accomplish.toml to .gitignore?")
+            .with_default(true)
+            .prompt_or_default()
+            .map_err(|e| AppError::ParseError(format!("Confirmation failed: {e}")))?;
+
+    if add_entry {
+        append_gitignore_entry(&gitignore_path)?;
+        println!("✓ Added .accomplish.toml to .gitignore");
+    } else {
+        println!("⚠️  Remember to add .accomplish.toml to your .gitignore file!");
+    }
+
+    Ok(())
+}
+
+fn gitignore_contains_entry(gitignore_path: &Path) -> bool {
+    fs::read_to_string(gitignore_path)
+        .map(|content| {
+            content
+                .lines()
+                .map(str::trim)
+                .any(|line| line == LOCAL_CONFIG_FILE || line == format!("/{LOCAL_CONFIG_FILE}"))
+        })
+        .unwrap_or(false)
+}
+
+/// Appends the local config file to `.gitignore`, creating it when missing.
+fn append_gitignore_entry(gitignore_path: &Path) -> Result<(), AppError> {
+    let existing = fs::read_to_string(gitignore_path).unwrap_or_default();
+
+    let mut content = existing;
+    if !content.is_empty() && !content.ends_with('\n') {
+        content.push('\n');
+    }
+    content.push_str(LOCAL_CONFIG_FILE);
+    content.push('\n');
+
+    fs::write(gitignore_path, content)
+        .map_err(|e| AppError::ParseError(format!("Failed to update .gitignore: {e}")))?;
+
+    Ok(())
+}
+
+pub fn create_global_config(
+    dir: &Path,
+    project: &Project,
+    is_git_repo: bool,
+) -> Result<(), AppError> {
     let home = home_dir()
         .ok_or_else(|| AppError::ParseError("Could not find home directory".to_string()))?;
 
@@ -301,7 +454,7 @@ fn create_global_config(dir: &Path, project: &Project, is_git_repo: bool) -> Res
     Ok(())
 }
 
-fn is_globally_tracked(dir: &Path) -> Result<bool, AppError> {
+pub fn is_globally_tracked(dir: &Path) -> Result<bool, AppError> {
     let home = home_dir()
         .ok_or_else(|| AppError::ParseError("Could not find home directory".to_string()))?;
 
@@ -338,7 +491,7 @@ fn get_git_remote(dir: &Path) -> Option<String> {
     None
 }
 
-fn get_default_branch(dir: &Path) -> Option<String> {
+pub fn get_default_branch(dir: &Path) -> Option<String> {
     use std::process::Command;
 
     let output = Command::new("git")
@@ -357,7 +510,7 @@ fn get_default_branch(dir: &Path) -> Option<String> {
     }
 }
 
-fn derive_repo_name(dir: &Path, git_remote: Option<&str>) -> String {
+pub fn derive_repo_name(dir: &Path, git_remote: Option<&str>) -> String {
     // First try to derive from git remote URL
     if let Some(remote) = git_remote {
         if let Some(name) = extract_repo_name_from_url(remote) {
@@ -497,6 +650,8 @@ mod tests {
             id: "test-id".to_string(),
             name: "Test Project".to_string(),
             identifier: "tst".to_string(),
+            parent_id: None,
+            ..Default::default()
         };
 
         create_local_config(temp_dir.path(), &project, true).unwrap();
@@ -517,6 +672,8 @@ mod tests {
             id: "test-id".to_string(),
             name: "Test Project".to_string(),
             identifier: "tst".to_string(),
+            parent_id: None,
+            ..Default::default()
         };
 
         create_local_config(temp_dir.path(), &project, false).unwrap();
@@ -576,6 +733,61 @@ mod tests {
         assert_eq!(extract_repo_name_from_url("invalid-url"), None);
     }
 
+    #[test]
+    fn test_append_gitignore_entry_creates_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let gitignore = temp_dir.path().join(".gitignore");
+
+        assert!(!gitignore_contains_entry(&gitignore));
+        append_gitignore_entry(&gitignore).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(&gitignore).unwrap(),
+            ".accomplish.toml\n"
+        );
+        assert!(gitignore_contains_entry(&gitignore));
+    }
+
+    #[test]
+    fn test_append_gitignore_entry_preserves_existing_lines() {
+        let temp_dir = TempDir::new().unwrap();
+        let gitignore = temp_dir.path().join(".gitignore");
+        fs::write(&gitignore, "target/\n*.log").unwrap();
+
+        append_gitignore_entry(&gitignore).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(&gitignore).unwrap(),
+            "target/\n*.log\n.accomplish.toml\n"
+        );
+    }
+
+    #[test]
+    fn test_offer_gitignore_entry_with_yes_adds_without_asking() {
+        let temp_dir = TempDir::new().unwrap();
+        offer_gitignore_entry(temp_dir.path(), true).unwrap();
+
+        assert!(gitignore_contains_entry(
+            &temp_dir.path().join(".gitignore")
+        ));
+    }
+
+    #[test]
+    fn test_gitignore_contains_entry_with_leading_slash() {
+        let temp_dir = TempDir::new().unwrap();
+        let gitignore = temp_dir.path().join(".gitignore");
+        fs::write(&gitignore, "target/\n/.accomplish.toml\n").unwrap();
+
+        assert!(gitignore_contains_entry(&gitignore));
+    }
+
+    #[test]
+    fn test_non_empty() {
+        assert_eq!(non_empty("  web "), Some("web"));
+        assert_eq!(non_empty("   "), None);
+        assert_eq!(non_empty(""), None);
+    }
+
     #[test]
     fn test_cleanup_existing_config() {
         let temp_dir = TempDir::new().unwrap();
@@ -0,0 +1,374 @@
+use crate::api::endpoints;
+use crate::api::models::Repository as ServerRepository;
+use crate::auth::AuthService;
+use crate::commands::capture::normalize_git_url;
+use crate::commands::init::{
+    choose_project, create_global_config, derive_repo_name, get_default_branch, is_globally_tracked,
+};
+use crate::commands::project::{get_projects, Project};
+use crate::commands::repo::local_remote_url;
+use crate::errors::AppError;
+use crate::utils::prompt::PromptOrDefault;
+use git2::Repository;
+use inquire::MultiSelect;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// How deep below the scan root to look for repositories.
+const MAX_SCAN_DEPTH: usize = 4;
+
+/// Directories that never contain repositories worth tracking and are slow to walk.
+const SKIPPED_DIRS: &[&str] = &["node_modules", "target", "vendor", "dist", "build"];
+
+/// A git repository found while scanning.
+#[derive(Debug)]
+struct ScannedRepo {
+    path: PathBuf,
+    remote_url: Option<String>,
+    status: ScanStatus,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ScanStatus {
+    /// Already linked to a project on this machine
+    Configured,
+    /// Known to the server (by path or remote) but not linked on this machine
+    Registered { name: String, project_id: String },
+    /// Not known anywhere yet
+    New,
+}
+
+/// Finds git repositories under `root`, shows which are already tracked, and
+/// registers the selected ones with projects in bulk, storing the
+/// configuration globally so the repositories themselves are left untouched.
+pub async fn execute(auth_service: &mut AuthService, root: &Path) -> Result<(), AppError> {
+    let root = root
+        .canonicalize()
+        .map_err(|e| AppError::ParseError(format!("Cannot scan {}: {e}", root.display())))?;
+
+    println!("Scanning {} for git repositories...", root.display());
+    let paths = find_git_repositories(&root, MAX_SCAN_DEPTH);
+    if paths.is_empty() {
+        println!("No git repositories found.");
+        return Ok(());
+    }
+
+    let server_repos = endpoints::fetch_repositories(auth_service.api_client())
+        .await
+        .map_err(AppError::Api)?;
+
+    let mut repos = Vec::new();
+    for path in paths {
+        let remote_url = Repository::open(&path)
+            .ok()
+            .and_then(|repo| local_remote_url(&repo));
+        let configured = path.join(".accomplish.toml").exists() || is_globally_tracked(&path)?;
+        let status = classify(&path, remote_url.as_deref(), configured, &server_repos);
+        repos.push(ScannedRepo {
+            path,
+            remote_url,
+            status,
+        });
+    }
+
+    println!("\nFound {} git repositories:", repos.len());
+    for repo in &repos {
+        let label = display_path(&root, &repo.path);
+        match &repo.status {
+            ScanStatus::Configured => println!("  ✓ {label} (already tracked)"),
+            ScanStatus::Registered { name, .. } => {
+                println!("  ↺ {label} (registered as '{name}', not linked on this machine)")
+            }
+            ScanStatus::New => println!("  + {label}"),
+        }
+    }
+
+    let candidates: Vec<&ScannedRepo> = repos
+        .iter()
+        .filter(|r| r.status != ScanStatus::Configured)
+        .collect();
+    if candidates.is_empty() {
+        println!("\nAll repositories are already tracked.");
+        return Ok(());
+    }
+
+    let options: Vec<String> = candidates
+        .iter()
+        .map(|r| display_path(&root, &r.path))
+        .collect();
+    let selected = MultiSelect::new("\nSelect repositories to track:", options.clone())
+        .with_all_selected_by_default()
+        .with_help_message("Use space to select, arrow keys to navigate, enter to confirm")
+        .prompt_or_default()
+        .map_err(|e| AppError::ParseError(format!("Selection failed: {e}")))?;
+    let selected: Vec<&ScannedRepo> = selected
+        .iter()
+        .filter_map(|choice| options.iter().position(|o| o == choice))
+        .map(|index| candidates[index])
+        .collect();
+    if selected.is_empty() {
+        println!("No repositories selected.");
+        return Ok(());
+    }
+
+    let mut projects = get_projects(auth_service).await?;
+    let mut linked = 0;
+
+    // Repositories the server already knows keep their project
+    let mut unassigned = Vec::new();
+    for repo in selected {
+        let project = match &repo.status {
+            ScanStatus::Registered { project_id, .. } => {
+                projects.iter().find(|p| &p.id == project_id)
+            }
+            _ => None,
+        };
+        match project {
+            Some(project) => {
+                create_global_config(&repo.path, project, true)?;
+                println!(
+                    "✓ Linked {} to {}",
+                    display_path(&root, &repo.path),
+                    project.identifier.to_uppercase()
+                );
+                linked += 1;
+            }
+            None => unassigned.push(repo),
+        }
+    }
+
+    // New repositories are assigned a project at a time
+    while !unassigned.is_empty() {
+        let options: Vec<String> = unassigned
+            .iter()
+            .map(|r| display_path(&root, &r.path))
+            .collect();
+        let Some(project) = choose_project(
+            auth_service,
+            &projects,
+            &format!("Select a project ({} repositories left):", unassigned.len()),
+        )
+        .await?
+        else {
+            break;
+        };
+
+        let chosen = if unassigned.len() == 1 {
+            options.clone()
+        } else {
+            MultiSelect::new(
+                &format!(
+                    "Repositories to add to {}:",
+                    project.identifier.to_uppercase()
+                ),
+                options.clone(),
+            )
+            .with_all_selected_by_default()
+            .with_help_message("Use space to select, arrow keys to navigate, enter to confirm")
+            .prompt_or_default()
+            .map_err(|e| AppError::ParseError(format!("Selection failed: {e}")))?
+        };
+
+        let mut remaining = Vec::new();
+        for (repo, label) in unassigned.into_iter().zip(&options) {
+            if !chosen.contains(label) {
+                remaining.push(repo);
+                continue;
+            }
+            if register(auth_service, repo, &project).await? {
+                println!("✓ Added {label} to {}", project.identifier.to_uppercase());
+                linked += 1;
+            }
+        }
+        unassigned = remaining;
+
+        if !projects.iter().any(|p| p.id == project.id) {
+            projects.push(project);
+        }
+    }
+
+    if !unassigned.is_empty() {
+        println!("Skipped {} repositories.", unassigned.len());
+    }
+    println!("\n✅ Tracking {linked} new repositories");
+    Ok(())
+}
+
+/// Creates the repository on the server and links the directory to the
+/// project. Returns false if the server rejected it.
+async fn register(
+    auth_service: &mut AuthService,
+    repo: &ScannedRepo,
+    project: &Project,
+) -> Result<bool, AppError> {
+    let name = derive_repo_name(&repo.path, repo.remote_url.as_deref());
+    let local_path = repo.path.to_string_lossy().to_string();
+
+    if let Err(e) = endpoints::create_repo(
+        auth_service.api_client(),
+        &name,
+        &project.id,
+        Some(&local_path),
+        repo.remote_url.as_deref(),
+        get_default_branch(&repo.path).as_deref(),
+    )
+    .await
+    {
+        eprintln!("⚠️  Warning: Failed to create repository {name}: {e}");
+        return Ok(false);
+    }
+
+    create_global_config(&repo.path, project, true)?;
+    Ok(true)
+}
+
+/// Works out whether a local repository is already tracked, matching server
+/// repositories by local path first and then by remote URL.
+fn classify(
+    path: &Path,
+    remote_url: Option<&str>,
+    configured: bool,
+    server_repos: &[ServerRepository],
+) -> ScanStatus {
+    if configured {
+        return ScanStatus::Configured;
+    }
+
+    let path = path.to_string_lossy();
+    let by_path = server_repos
+        .iter()
+        .find(|repo| repo.local_path.as_deref() == Some(path.as_ref()));
+    let by_remote = || {
+        let remote = normalize_git_url(remote_url?);
+        server_repos.iter().find(|repo| {
+            repo.remote_url
+                .as_deref()
+                .is_some_and(|url| normalize_git_url(url) == remote)
+        })
+    };
+
+    match by_path.or_else(by_remote) {
+        Some(repo) => match &repo.project_id {
+            Some(project_id) if !repo.name.is_empty() => ScanStatus::Registered {
+                name: repo.name.clone(),
+                project_id: project_id.clone(),
+            },
+            _ => ScanStatus::New,
+        },
+        None => ScanStatus::New,
+    }
+}
+
+/// Walks `root` looking for directories containing `.git` (a directory, or a
+/// file for worktrees and submodules). Doesn't descend into repositories,
+/// hidden directories or symlinks.
+fn find_git_repositories(root: &Path, max_depth: usize) -> Vec<PathBuf> {
+    let mut found = Vec::new();
+    let mut pending = vec![(root.to_path_buf(), 0)];
+
+    while let Some((dir, depth)) = pending.pop() {
+        if dir.join(".git").exists() {
+            found.push(dir);
+            continue;
+        }
+        if depth >= max_depth {
+            continue;
+        }
+
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let is_dir = entry.file_type().map(|t| t.is_dir()).unwrap_or(false);
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            if is_dir && !name.starts_with('.') && !SKIPPED_DIRS.contains(&name.as_ref()) {
+                pending.push((entry.path(), depth + 1));
+            }
+        }
+    }
+
+    found.sort();
+    found
+}
+
+fn display_path(root: &Path, path: &Path) -> String {
+    match path.strip_prefix(root) {
+        Ok(relative) if !relative.as_os_str().is_empty() => relative.display().to_string(),
+        _ => path.display().to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_find_git_repositories() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        for dir in [
+            "work/api/.git",
+            "work/api/vendor/lib/.git",
+            "work/web/.git",
+            "oss/node_modules/pkg/.git",
+            ".cache/repo/.git",
+            "a/b/c/d/e/deep/.git",
+        ] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        // Worktrees have a `.git` file instead of a directory
+        fs::create_dir_all(root.join("work/api-feature")).unwrap();
+        fs::write(root.join("work/api-feature/.git"), "gitdir: ../api/.git").unwrap();
+
+        let found: Vec<String> = find_git_repositories(root, MAX_SCAN_DEPTH)
+            .iter()
+            .map(|p| display_path(root, p))
+            .collect();
+        assert_eq!(found, vec!["work/api", "work/api-feature", "work/web"]);
+    }
+
+    #[test]
+    fn test_classify() {
+        let server_repos: Vec<ServerRepository> = serde_json::from_value(json!([
+            { "id": "r1", "name": "api", "project_id": "p1", "local_path": "/old/laptop/api",
+              "remote_url": "https://github.com/acme/api.git" },
+            { "id": "r2", "name": "web", "project_id": "p2", "local_path": "/code/web" },
+        ]))
+        .unwrap();
+        let registered = |name: &str, project_id: &str| ScanStatus::Registered {
+            name: name.to_string(),
+            project_id: project_id.to_string(),
+        };
+
+        // A clone on a new machine is matched by its remote
+        assert_eq!(
+            classify(
+                Path::new("/code/api"),
+                Some("git@github.com:acme/api.git"),
+                false,
+                &server_repos
+            ),
+            registered("api", "p1")
+        );
+        assert_eq!(
+            classify(Path::new("/code/web"), None, false, &server_repos),
+            registered("web", "p2")
+        );
+        assert_eq!(
+            classify(Path::new("/code/web"), None, true, &server_repos),
+            ScanStatus::Configured
+        );
+        assert_eq!(
+            classify(
+                Path::new("/code/tools"),
+                Some("git@github.com:acme/tools.git"),
+                false,
+                &server_repos
+            ),
+            ScanStatus::New
+        );
+    }
+}
@@ -1,15 +1,87 @@
 // src/commands/log.rs
-use crate::api::endpoints::create_worklog_entry;
+use crate::api::client::ApiClient;
+use crate::api::endpoints::{create_entry_relation, create_worklog_entry};
 use crate::auth::AuthService;
-use crate::commands::project;
+use crate::commands::{logs, project};
+use crate::completion_cache::CompletionCache;
 use crate::errors::AppError;
-use chrono::Utc;
+use crate::outbox::{Outbox, QueuedEntry};
+use crate::utils::custom_fields::{format_fields, FieldSchema};
+use crate::utils::editor::parse_entry_date;
+use crate::utils::relations::RelationKind;
+use chrono::{DateTime, Duration, Local, Utc};
 use regex::Regex;
-use serde_json::to_string_pretty;
+use serde_json::{Map, Value};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+
+static REQUIRE_PROJECT: AtomicBool = AtomicBool::new(false);
+static FIELD_SCHEMA: OnceLock<FieldSchema> = OnceLock::new();
+
+/// Makes every new entry need a project, from `require_project = true` in the
+/// config: missing ones are asked for in a terminal and are an error otherwise.
+pub fn set_require_project(enabled: bool) {
+    REQUIRE_PROJECT.store(enabled, Ordering::Relaxed);
+}
+
+pub fn require_project() -> bool {
+    REQUIRE_PROJECT.load(Ordering::Relaxed)
+}
+
+/// Sets the custom fields new entries are checked against, from
+/// `[<profile>.fields]` in the config. Only the first call counts.
+pub fn set_field_schema(schema: FieldSchema) {
+    let _ = FIELD_SCHEMA.set(schema);
+}
+
+fn project_required() -> AppError {
+    AppError::Other(
+        "Entries need a project (require_project is set). Pass -p <project> or set a default project"
+            .to_string(),
+    )
+}
+
+/// Refuses an entry without a project when `require_project` is set.
+fn check_project(project_identifier: Option<&str>, required: bool) -> Result<(), AppError> {
+    if required && project_identifier.is_none() {
+        return Err(project_required());
+    }
+    Ok(())
+}
+
+/// Refuses an entry missing a required custom field, whichever command
+/// creates it: capture, review and the rest don't take `--field`.
+fn check_required_fields(custom_fields: Option<&Map<String, Value>>) -> Result<(), AppError> {
+    let Some(schema) = FIELD_SCHEMA.get() else {
+        return Ok(());
+    };
+    let none = Map::new();
+    let missing = schema.missing_required(custom_fields.unwrap_or(&none));
+    if missing.is_empty() {
+        return Ok(());
+    }
+    Err(AppError::Other(format!(
+        "Missing required field(s): {}. Log the entry with `acc log --field key=value` instead",
+        missing.join(", ")
+    )))
+}
+
+/// Parses `acc log --at`, refusing times in the future.
+pub fn parse_at(value: &str) -> Result<DateTime<Local>, AppError> {
+    let now = Local::now();
+    let at = parse_entry_date(value, now)?;
+    if at > now + Duration::minutes(1) {
+        return Err(AppError::ParseError(format!(
+            "--at {value} is in the future; entries can only be backdated"
+        )));
+    }
+    Ok(at)
+}
 
 /// Converts bare URLs in text to markdown links.
 /// URLs that are already in markdown link format are left unchanged.
-fn convert_urls_to_markdown(text: &str) -> String {
+pub fn convert_urls_to_markdown(text: &str) -> String {
     // Simple approach: find URLs that aren't already in markdown links
     let url_regex = Regex::new(r"https?://[^\s\]]+").unwrap();
 
@@ -32,69 +104,203 @@ fn convert_urls_to_markdown(text: &str) -> String {
         .to_string()
 }
 
-/// Adds a new worklog entry with the given messages, optional tags, and optional project identifier.
+/// An entry as created by `create`.
+#[derive(Debug)]
+pub struct CreatedEntry {
+    pub id: String,
+    /// The project as shown to the user, e.g. `Website (WEB)`
+    pub project: Option<String>,
+}
+
+/// Adds a new worklog entry with the given messages, optional tags, and optional project identifier,
+/// and reports it.
+/// `recorded_at` (RFC 3339) defaults to now, in server time when clock skew adjustment
+/// is enabled. `custom_fields` must already be validated against the configured field schema;
+/// entries missing a required field are refused.
 /// Requires an authenticated AuthService.
 pub async fn execute(
     auth_service: &mut AuthService,
     messages: &[String],
     tags: &[String],
     project_identifier: Option<&str>,
+    recorded_at: Option<&str>,
+    custom_fields: Option<&Map<String, Value>>,
 ) -> Result<String, AppError> {
-    let recorded_at = Utc::now().to_rfc3339();
+    let created = create(
+        auth_service,
+        messages,
+        tags,
+        project_identifier,
+        recorded_at,
+        custom_fields,
+    )
+    .await?;
+
+    println!("✅ Created entry with id {}", created.id);
+    if !tags.is_empty() {
+        println!("Tags: {}", tags.join(", "));
+    }
+    if let Some(project) = &created.project {
+        println!("Project: {project}");
+    }
+    if let Some(fields) = custom_fields.filter(|f| !f.is_empty()) {
+        println!("Fields: {}", format_fields(fields));
+    }
+    if let Some(at) = recorded_at.and_then(|d| DateTime::parse_from_rfc3339(d).ok()) {
+        println!(
+            "Recorded at: {}",
+            at.with_timezone(&Local).format("%Y-%m-%d %H:%M")
+        );
+    }
+    Ok(created.id)
+}
+
+/// Creates the entry like `execute` without reporting it, for `acc log --quiet`.
+pub async fn create(
+    auth_service: &mut AuthService,
+    messages: &[String],
+    tags: &[String],
+    project_identifier: Option<&str>,
+    recorded_at: Option<&str>,
+    custom_fields: Option<&Map<String, Value>>,
+) -> Result<CreatedEntry, AppError> {
+    check_required_fields(custom_fields)?;
+    let recorded_at = recorded_at
+        .map(String::from)
+        .unwrap_or_else(|| auth_service.api_client().clock().now().to_rfc3339());
     let content = convert_urls_to_markdown(&messages.join("\n\n"));
 
-    let (project_id, project_info) = if let Some(identifier) = project_identifier {
+    let (mut project_id, mut project_info) = if let Some(identifier) = project_identifier {
         let projects = project::get_projects(auth_service).await?;
 
-        let mut project_id = None;
-        let mut project_info = None;
-
-        for p in &projects {
-            if p.identifier.to_lowercase() == identifier.to_lowercase() {
-                project_id = Some(p.id.clone());
-                project_info = Some((p.name.clone(), p.identifier.to_uppercase()));
-                break;
+        match project::match_project(&projects, identifier) {
+            Some(p) => (
+                Some(p.id.clone()),
+                Some((p.name.clone(), p.identifier.to_uppercase())),
+            ),
+            None => {
+                eprintln!("⚠️ Warning: No project found with identifier '{identifier}'");
+                (None, None)
             }
         }
-
-        if project_id.is_none() {
-            println!("⚠️ Warning: No project found with identifier '{identifier}'");
-        }
-
-        (project_id, project_info)
     } else {
         (None, None)
     };
+    let mut project_identifier = project_identifier.map(str::to_string);
 
-    let resp = create_worklog_entry(
+    if project_id.is_none() && require_project() {
+        let chosen = project::prompt_for_project(
+            auth_service,
+            "This profile requires a project. Log to which project?",
+            "Cancel",
+        )
+        .await?
+        .ok_or_else(project_required)?;
+        let projects = project::get_projects(auth_service).await?;
+        let p = project::resolve_project(&projects, &chosen).ok_or_else(|| {
+            AppError::Other(format!("No project found with identifier '{chosen}'"))
+        })?;
+        project_id = Some(p.id.clone());
+        project_info = Some((p.name.clone(), p.identifier.to_uppercase()));
+        project_identifier = Some(chosen);
+    }
+
+    let created = create_worklog_entry(
         auth_service.api_client(),
         &content,
         &recorded_at,
         tags,
         project_id.as_deref(),
+        custom_fields,
     )
     .await
     .map_err(AppError::Api)?;
 
-    if let Some(id) = resp.get("id").and_then(|v| v.as_str()) {
-        println!("✅ Created entry with id {id}");
-        if !tags.is_empty() {
-            println!("Tags: {}", tags.join(", "));
+    if !tags.is_empty() {
+        let mut cache = CompletionCache::load(auth_service.state_dir());
+        cache.record_tags(tags);
+        if let Err(e) = cache.save() {
+            eprintln!("⚠️  Warning: Could not save completion cache: {e}");
         }
-        if let Some(identifier) = project_identifier {
-            if let Some((name, uppercase_identifier)) = project_info {
-                println!("Project: {name} ({uppercase_identifier})");
-            } else {
-                println!("Project: {}", identifier.to_uppercase());
-            }
-        }
-        Ok(id.to_string())
-    } else {
-        println!("{}", to_string_pretty(&resp)?);
-        Err(AppError::ParseError(
-            "Failed to get entry ID from response".to_string(),
-        ))
     }
+    let project = match (project_identifier, project_info) {
+        (_, Some((name, uppercase_identifier))) => Some(format!("{name} ({uppercase_identifier})")),
+        (Some(identifier), None) => Some(identifier.to_uppercase()),
+        (None, None) => None,
+    };
+    Ok(CreatedEntry {
+        id: created.id,
+        project,
+    })
+}
+
+/// Keeps an entry that couldn't be sent in the outbox for `acc sync`. The
+/// time is fixed now when none was given, so the entry isn't dated to the sync.
+/// With `require_project`, entries without one are refused here: nobody may be
+/// around to pick one when the outbox is sent, e.g. by `acc daemon`.
+pub fn queue(
+    auth_service: &AuthService,
+    messages: &[String],
+    tags: &[String],
+    project_identifier: Option<&str>,
+    recorded_at: Option<&str>,
+    custom_fields: Option<&Map<String, Value>>,
+) -> Result<PathBuf, AppError> {
+    check_project(project_identifier, require_project())?;
+    check_required_fields(custom_fields)?;
+    let entry = QueuedEntry {
+        queued_at: Utc::now(),
+        content: messages.join("\n\n"),
+        tags: tags.to_vec(),
+        project: project_identifier.map(str::to_string),
+        recorded_at: recorded_at
+            .map(String::from)
+            .unwrap_or_else(|| auth_service.api_client().clock().now().to_rfc3339()),
+        custom_fields: custom_fields.filter(|f| !f.is_empty()).cloned(),
+    };
+    let path = Outbox::new(auth_service.state_dir()).push(&entry)?;
+    Ok(path)
+}
+
+/// Resolves the entries a new entry will be linked to (full IDs or unique
+/// prefixes), so a typo is caught before the entry is created.
+pub async fn resolve_relations(
+    api_client: &ApiClient,
+    follows: Option<&str>,
+    relates_to: &[String],
+) -> Result<Vec<(RelationKind, String)>, AppError> {
+    let requested = follows
+        .map(|id| (RelationKind::FollowsUp, id))
+        .into_iter()
+        .chain(
+            relates_to
+                .iter()
+                .map(|id| (RelationKind::RelatesTo, id.as_str())),
+        );
+
+    let mut relations = Vec::new();
+    for (kind, id_or_prefix) in requested {
+        let entry = logs::find_entry(api_client, id_or_prefix).await?;
+        relations.push((kind, entry.id));
+    }
+    Ok(relations)
+}
+
+/// Records the relations from `entry_id` to already resolved entries.
+pub async fn link_entry(
+    api_client: &ApiClient,
+    entry_id: &str,
+    relations: &[(RelationKind, String)],
+) -> Result<(), AppError> {
+    for (kind, related_id) in relations {
+        create_entry_relation(api_client, entry_id, kind.as_str(), related_id).await?;
+        let verb = match kind {
+            RelationKind::FollowsUp => "Follows up on",
+            RelationKind::RelatesTo => "Relates to",
+        };
+        eprintln!("🔗 {verb} entry {}", &related_id[..8.min(related_id.len())]);
+    }
+    Ok(())
 }
 
 #[cfg(test)]
@@ -110,6 +316,14 @@ mod tests {
         auth
     }
 
+    #[test]
+    fn test_check_project() {
+        let err = check_project(None, true).unwrap_err();
+        assert!(err.to_string().contains("-p <project>"), "{err}");
+        assert!(check_project(Some("web"), true).is_ok());
+        assert!(check_project(None, false).is_ok());
+    }
+
     #[tokio::test]
     async fn test_execute_success() {
         let mut server = Server::new_async().await;
@@ -130,7 +344,7 @@ mod tests {
             .with_body(response.to_string())
             .create();
 
-        let result = execute(&mut auth, &["Test message".into()], &[], None).await;
+        let result = execute(&mut auth, &["Test message".into()], &[], None, None, None).await;
         assert!(result.is_ok());
     }
 
@@ -156,7 +370,7 @@ mod tests {
             .with_body(response.to_string())
             .create();
 
-        let result = execute(&mut auth, &messages, &[], None).await;
+        let result = execute(&mut auth, &messages, &[], None, None, None).await;
         assert!(result.is_ok());
     }
 
@@ -184,7 +398,15 @@ mod tests {
             .with_body(response.to_string())
             .create();
 
-        let result = execute(&mut auth, &["Message with tags".into()], &tags, None).await;
+        let result = execute(
+            &mut auth,
+            &["Message with tags".into()],
+            &tags,
+            None,
+            None,
+            None,
+        )
+        .await;
         assert!(result.is_ok());
     }
 
@@ -202,7 +424,7 @@ mod tests {
             .with_body(r#"{"error":"bad_request"}"#)
             .create();
 
-        let result = execute(&mut auth, &["Err message".into()], &[], None).await;
+        let result = execute(&mut auth, &["Err message".into()], &[], None, None, None).await;
         assert!(matches!(result, Err(AppError::Api(_))));
     }
 
@@ -230,12 +452,12 @@ mod tests {
             .create();
 
         // Test with a single message containing newlines
-        let result = execute(&mut auth, &[content.to_string()], &[], None).await;
+        let result = execute(&mut auth, &[content.to_string()], &[], None, None, None).await;
         assert!(result.is_ok());
     }
 
     #[tokio::test]
-    async fn test_execute_with_project() {
+    async fn test_create_with_project() {
         let mut server = Server::new_async().await;
         let mut auth = setup_mock_auth_service(&server.url());
         let project_id = "website";
@@ -279,14 +501,18 @@ mod tests {
             .with_body(entry_response.to_string())
             .create();
 
-        let result = execute(
+        let created = create(
             &mut auth,
             &["Entry with project".into()],
             &[],
             Some(project_identifier),
+            None,
+            None,
         )
-        .await;
-        assert!(result.is_ok());
+        .await
+        .unwrap();
+        assert_eq!(created.id, "id-project");
+        assert_eq!(created.project.as_deref(), Some("Website Project (WEB)"));
     }
 
     #[test]
@@ -397,7 +623,7 @@ mod tests {
             .with_body(response.to_string())
             .create();
 
-        let result = execute(&mut auth, &messages, &[], None).await;
+        let result = execute(&mut auth, &messages, &[], None, None, None).await;
         assert!(result.is_ok());
     }
 }
@@ -0,0 +1,287 @@
+use crate::api::endpoints::{fetch_worklog_entries, update_worklog_entry};
+use crate::auth::AuthService;
+use crate::commands::log::convert_urls_to_markdown;
+use crate::commands::project;
+use crate::errors::AppError;
+use crate::utils::duration::parse_since_duration;
+use crate::utils::editor::{self, EntryMetadata};
+use chrono::{DateTime, Local, Utc};
+use serde_json::{json, Map, Value};
+
+const AMEND_TEMPLATE: &str = "# Amend the entry below\n# Lines starting with # will be ignored\n\n";
+
+/// Replaces the content of the most recent entry, like `git commit --amend`.
+/// With `messages`, they become the new content; otherwise the entry opens in
+/// the editor with its tags, project and date as front-matter. Entries created
+/// longer than `window` (e.g. `24h`) ago are refused unless `force` is set.
+pub async fn execute(
+    auth_service: &mut AuthService,
+    messages: &[String],
+    window: Option<&str>,
+    force: bool,
+) -> Result<String, AppError> {
+    let page = fetch_worklog_entries(
+        auth_service.api_client(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        1,
+        None,
+    )
+    .await?;
+    let entry = page
+        .entries
+        .into_iter()
+        .next()
+        .ok_or_else(|| AppError::Other("No entries to amend".to_string()))?;
+    let id = entry.id.clone();
+    let entry = Value::from(entry);
+
+    if let Some(window) = window.filter(|_| !force) {
+        let cutoff = parse_since_duration(window)?
+            .parse::<DateTime<Utc>>()
+            .map_err(|e| AppError::ParseError(format!("Invalid amend window {window}: {e}")))?;
+        match created_at(&entry) {
+            Some(created) if created >= cutoff => {}
+            Some(_) => {
+                return Err(AppError::Other(format!(
+                    "The last entry ({}) is older than the amend window of {window}. Use --force to amend it anyway",
+                    short_id(&id)
+                )))
+            }
+            None => {
+                return Err(AppError::Other(format!(
+                    "Can't tell when the last entry ({}) was created, so it may be older than the amend window of {window}. Use --force to amend it anyway",
+                    short_id(&id)
+                )))
+            }
+        }
+    }
+
+    let changes = if messages.is_empty() {
+        edit_changes(auth_service, &entry).await?
+    } else {
+        let mut changes = Map::new();
+        changes.insert(
+            "content".to_string(),
+            json!(convert_urls_to_markdown(&messages.join("\n\n"))),
+        );
+        changes
+    };
+
+    if changes.is_empty() {
+        println!("Nothing changed.");
+        return Ok(id);
+    }
+
+    update_worklog_entry(auth_service.api_client(), &id, Value::Object(changes)).await?;
+    println!("✅ Amended entry {id}");
+    Ok(id)
+}
+
+/// Opens the entry in the editor and returns the fields that were changed.
+pub async fn edit_changes(
+    auth_service: &mut AuthService,
+    entry: &Value,
+) -> Result<Map<String, Value>, AppError> {
+    let original = entry_metadata(entry);
+    let content = entry.get("content").and_then(Value::as_str).unwrap_or("");
+
+    let template = format!(
+        "{}{AMEND_TEMPLATE}{content}\n",
+        editor::render_front_matter(&original)
+    );
+    let (metadata, new_content) =
+        editor::parse_front_matter(&editor::open_in_editor(Some(&template))?)?;
+    if new_content.is_empty() {
+        return Err(AppError::Other(
+            "No content provided. Aborting.".to_string(),
+        ));
+    }
+
+    let mut changes = Map::new();
+    let new_content = convert_urls_to_markdown(&new_content);
+    if new_content != content.trim() {
+        changes.insert("content".to_string(), json!(new_content));
+    }
+    if metadata.tags != original.tags {
+        changes.insert("tags".to_string(), json!(metadata.tags));
+    }
+    // The front-matter only has minute precision, so compare what it showed
+    let shown =
+        |date: Option<DateTime<Local>>| date.map(|d| d.format("%Y-%m-%d %H:%M").to_string());
+    if shown(metadata.date) != shown(original.date) {
+        if let Some(date) = metadata.date {
+            changes.insert("recorded_at".to_string(), json!(date.to_rfc3339()));
+        }
+    }
+    let project_changed = metadata.project.as_deref().map(str::to_lowercase)
+        != original.project.as_deref().map(str::to_lowercase);
+    if project_changed {
+        let project_id = match metadata.project.as_deref() {
+            Some(identifier) => {
+                let projects = project::get_projects(auth_service).await?;
+                let project = project::match_project(&projects, identifier).ok_or_else(|| {
+                    AppError::Other(format!("No project found with identifier '{identifier}'"))
+                })?;
+                json!(project.id)
+            }
+            None => Value::Null,
+        };
+        changes.insert("project_id".to_string(), project_id);
+    }
+
+    Ok(changes)
+}
+
+/// An entry's tags, project and date as shown in the editor's front-matter.
+pub fn entry_metadata(entry: &Value) -> EntryMetadata {
+    EntryMetadata {
+        tags: entry
+            .get("tags")
+            .and_then(Value::as_array)
+            .map(|tags| {
+                tags.iter()
+                    .filter_map(Value::as_str)
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default(),
+        project: entry
+            .get("project")
+            .and_then(|p| p.get("identifier"))
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        date: entry
+            .get("recorded_at")
+            .and_then(Value::as_str)
+            .and_then(|s| s.parse::<DateTime<Local>>().ok()),
+    }
+}
+
+/// When the entry was created. Entries without a creation time are aged by
+/// when they were recorded; None when no time can be read.
+fn created_at(entry: &Value) -> Option<DateTime<Utc>> {
+    ["inserted_at", "created_at", "recorded_at"]
+        .iter()
+        .find_map(|field| entry.get(*field).and_then(Value::as_str))
+        .and_then(|s| s.parse::<DateTime<Utc>>().ok())
+}
+
+fn short_id(id: &str) -> &str {
+    id.get(..8).unwrap_or(id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::{Matcher, Server};
+
+    fn setup_mock_auth_service(server_url: &str) -> AuthService {
+        let mut auth =
+            AuthService::new(server_url.to_string(), std::env::temp_dir(), "test-profile");
+        auth.save_access_token("test-token").unwrap();
+        auth
+    }
+
+    #[test]
+    fn test_created_at() {
+        let at = |s: &str| s.parse::<DateTime<Utc>>().ok();
+        let entry = json!({
+            "recorded_at": "2025-07-01T09:00:00Z",
+            "inserted_at": "2025-07-09T09:00:00Z"
+        });
+        assert_eq!(created_at(&entry), at("2025-07-09T09:00:00Z"));
+        assert_eq!(
+            created_at(&json!({ "recorded_at": "2025-07-01T09:00:00Z" })),
+            at("2025-07-01T09:00:00Z")
+        );
+        assert_eq!(created_at(&json!({ "recorded_at": "yesterday" })), None);
+        assert_eq!(created_at(&json!({ "id": "entry-1" })), None);
+    }
+
+    #[tokio::test]
+    async fn test_amend_with_message() {
+        let mut server = Server::new_async().await;
+        let mut auth = setup_mock_auth_service(&server.url());
+        let recent = Utc::now().to_rfc3339();
+
+        let _list = server
+            .mock("GET", "/api/v1/worklog/entries")
+            .match_query(Matcher::UrlEncoded("limit".into(), "1".into()))
+            .with_status(200)
+            .with_body(
+                json!({ "entries": [{ "id": "entry-1", "inserted_at": recent }] }).to_string(),
+            )
+            .create();
+        let update = server
+            .mock("PATCH", "/api/v1/worklog/entries/entry-1")
+            .match_body(Matcher::Json(json!({ "content": "Fixed the typo" })))
+            .with_status(200)
+            .with_body(r#"{"id":"entry-1"}"#)
+            .create();
+
+        let id = execute(&mut auth, &["Fixed the typo".into()], Some("24h"), false)
+            .await
+            .unwrap();
+        assert_eq!(id, "entry-1");
+        update.assert();
+    }
+
+    #[tokio::test]
+    async fn test_amend_refuses_entries_outside_window() {
+        let mut server = Server::new_async().await;
+        let mut auth = setup_mock_auth_service(&server.url());
+
+        let _list = server
+            .mock("GET", "/api/v1/worklog/entries")
+            .match_query(Matcher::Any)
+            .with_status(200)
+            .with_body(r#"{"entries":[{"id":"entry-1","inserted_at":"2020-01-01T00:00:00Z"}]}"#)
+            .create();
+        let update = server
+            .mock("PATCH", "/api/v1/worklog/entries/entry-1")
+            .with_status(200)
+            .with_body(r#"{"id":"entry-1"}"#)
+            .expect(1)
+            .create();
+
+        assert!(execute(&mut auth, &["New".into()], Some("24h"), false)
+            .await
+            .is_err());
+        execute(&mut auth, &["New".into()], Some("24h"), true)
+            .await
+            .unwrap();
+        update.assert();
+    }
+
+    #[tokio::test]
+    async fn test_amend_refuses_entries_of_unknown_age() {
+        let mut server = Server::new_async().await;
+        let mut auth = setup_mock_auth_service(&server.url());
+
+        let _list = server
+            .mock("GET", "/api/v1/worklog/entries")
+            .match_query(Matcher::Any)
+            .with_status(200)
+            .with_body(r#"{"entries":[{"id":"entry-1"}]}"#)
+            .create();
+        let update = server
+            .mock("PATCH", "/api/v1/worklog/entries/entry-1")
+            .with_status(200)
+            .with_body(r#"{"id":"entry-1"}"#)
+            .expect(1)
+            .create();
+
+        let err = execute(&mut auth, &["New".into()], Some("24h"), false)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Can't tell"), "{err}");
+        execute(&mut auth, &["New".into()], Some("24h"), true)
+            .await
+            .unwrap();
+        update.assert();
+    }
+}
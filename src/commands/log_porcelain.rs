@@ -0,0 +1,429 @@
+use crate::auth::AuthService;
+use crate::commands::{log, project};
+use crate::completion_cache::CompletionCache;
+use crate::config;
+use crate::errors::AppError;
+use crate::porcelain::{
+    ErrorCode, Porcelain, Request, RequestError, EXIT_IO, EXIT_OK, EXIT_UNAUTHENTICATED,
+};
+use crate::utils::custom_fields::FieldSchema;
+use crate::utils::prompt;
+use crate::utils::visibility::VisibilityPolicy;
+use serde_json::{json, Map, Value};
+use std::io::{BufRead, Write};
+use std::path::Path;
+
+/// What `acc log --porcelain` takes from the settings.
+pub struct PorcelainOptions<'a> {
+    pub profile: &'a str,
+    pub default_project: Option<&'a str>,
+    pub visibility: &'a VisibilityPolicy,
+    pub field_schema: &'a FieldSchema,
+}
+
+/// Serves porcelain requests from `reader` until it ends, and returns the exit
+/// code. Requests are answered one at a time, in order; see `porcelain` for
+/// the framing. Nothing is ever prompted for.
+///
+/// Methods:
+/// - `log`: creates an entry from `content`, with optional `tags`, `project`,
+///   `at` (as for `acc log --at`), `fields` (an object checked like
+///   `acc log --field`) and `dir`, the directory whose configured project
+///   applies when `project` is missing. Offline, the entry is queued
+///   for `acc sync` and the result has `queued` instead of `id`.
+/// - `projects`: the projects as `{id, identifier, name, parent_id}`.
+/// - `tags`: recently used tags, most recent first, from the local cache.
+pub async fn execute<R: BufRead, W: Write>(
+    auth_service: &mut AuthService,
+    options: &PorcelainOptions<'_>,
+    reader: R,
+    writer: W,
+) -> i32 {
+    let mut porcelain = Porcelain::new(reader, writer);
+    // Questions `log::create` would ask (a project to log to, a guessed
+    // project to confirm) take their default, failing the request instead
+    prompt::set_assume_defaults(true);
+
+    // Offline is fine: entries are queued, and only `projects` fails
+    let offline = match auth_service.ensure_authenticated().await {
+        Ok(()) => false,
+        Err(e) if e.is_offline() => true,
+        Err(e) => {
+            let message = match e {
+                AppError::Auth(_) => "Not logged in. Run `acc login` first".to_string(),
+                e => e.to_string(),
+            };
+            let _ = porcelain.fatal(RequestError::new(ErrorCode::Unauthenticated, message));
+            return EXIT_UNAUTHENTICATED;
+        }
+    };
+
+    let hello = json!({
+        "version": env!("CARGO_PKG_VERSION"),
+        "profile": options.profile,
+        "offline": offline,
+        "methods": ["log", "projects", "tags"],
+    });
+    if porcelain.hello(hello).is_err() {
+        return EXIT_IO;
+    }
+
+    loop {
+        let (id, request) = match porcelain.next_request() {
+            Ok(Some(next)) => next,
+            Ok(None) => return EXIT_OK,
+            Err(_) => return EXIT_IO,
+        };
+        let result = match request {
+            Ok(request) => handle(auth_service, options, request).await,
+            Err(e) => Err(e),
+        };
+        if porcelain.respond(id, result).is_err() {
+            return EXIT_IO;
+        }
+    }
+}
+
+async fn handle(
+    auth_service: &mut AuthService,
+    options: &PorcelainOptions<'_>,
+    request: Request,
+) -> Result<Value, RequestError> {
+    match request.method.as_str() {
+        "log" => create_entry(auth_service, options, &request.params).await,
+        "projects" => {
+            let projects = project::get_projects(auth_service).await?;
+            Ok(json!(projects))
+        }
+        "tags" => {
+            let cache = CompletionCache::load(auth_service.state_dir());
+            Ok(json!(cache.tags()))
+        }
+        method => Err(RequestError::new(
+            ErrorCode::UnknownMethod,
+            format!("Unknown method '{method}'"),
+        )),
+    }
+}
+
+async fn create_entry(
+    auth_service: &mut AuthService,
+    options: &PorcelainOptions<'_>,
+    params: &Map<String, Value>,
+) -> Result<Value, RequestError> {
+    let content = optional_str(params, "content")?
+        .map(str::trim)
+        .filter(|content| !content.is_empty())
+        .ok_or_else(|| RequestError::new(ErrorCode::InvalidParams, "'content' is required"))?;
+    let tags = match params.get("tags") {
+        None | Some(Value::Null) => Vec::new(),
+        Some(Value::Array(tags)) => tags
+            .iter()
+            .map(|tag| tag.as_str().map(|t| t.trim().to_string()))
+            .collect::<Option<Vec<String>>>()
+            .ok_or_else(|| RequestError::new(ErrorCode::InvalidParams, "'tags' must be strings"))?
+            .into_iter()
+            .filter(|tag| !tag.is_empty())
+            .collect(),
+        Some(_) => {
+            return Err(RequestError::new(
+                ErrorCode::InvalidParams,
+                "'tags' must be an array",
+            ))
+        }
+    };
+    let recorded_at = optional_str(params, "at")?
+        .map(|at| {
+            log::parse_at(at)
+                .map(|at| at.to_rfc3339())
+                .map_err(|e| RequestError::new(ErrorCode::InvalidParams, e.to_string()))
+        })
+        .transpose()?;
+    // As `key=value` arguments, so they're checked exactly like `--field`
+    let field_args = match params.get("fields") {
+        None | Some(Value::Null) => Vec::new(),
+        Some(Value::Object(fields)) => fields
+            .iter()
+            .map(|(key, value)| match value {
+                Value::String(value) => Some(format!("{key}={value}")),
+                Value::Number(_) | Value::Bool(_) => Some(format!("{key}={value}")),
+                _ => None,
+            })
+            .collect::<Option<Vec<String>>>()
+            .ok_or_else(|| {
+                RequestError::new(
+                    ErrorCode::InvalidParams,
+                    "'fields' values must be strings, numbers or booleans",
+                )
+            })?,
+        Some(_) => {
+            return Err(RequestError::new(
+                ErrorCode::InvalidParams,
+                "'fields' must be an object",
+            ))
+        }
+    };
+    let custom_fields = options
+        .field_schema
+        .validate(&field_args)
+        .map_err(|e| RequestError::new(ErrorCode::InvalidParams, e))?;
+    let dir = optional_str(params, "dir")?;
+    let project = optional_str(params, "project")?
+        .map(str::to_string)
+        .or_else(|| dir.and_then(|dir| config::lookup_default_project_for_dir(Path::new(dir))))
+        .or(options.default_project.map(str::to_string));
+
+    if project.is_none() && log::require_project() {
+        return Err(RequestError::new(
+            ErrorCode::ProjectRequired,
+            "Entries need a project (require_project is set)",
+        ));
+    }
+    let tags = options
+        .visibility
+        .tag_new_entry(tags, None, project.as_deref());
+    let messages = [content.to_string()];
+
+    let created = match &project {
+        // Only exact identifiers: there's no one to confirm a guess with
+        Some(spec) => match project::get_projects(auth_service).await {
+            Ok(projects) if project::resolve_project(&projects, spec).is_none() => {
+                return Err(RequestError::new(
+                    ErrorCode::UnknownProject,
+                    format!("No project found with identifier '{spec}'"),
+                ));
+            }
+            Ok(_) => {
+                log::create(
+                    auth_service,
+                    &messages,
+                    &tags,
+                    Some(spec),
+                    recorded_at.as_deref(),
+                    Some(&custom_fields),
+                )
+                .await
+            }
+            Err(e) => Err(e),
+        },
+        None => {
+            log::create(
+                auth_service,
+                &messages,
+                &tags,
+                None,
+                recorded_at.as_deref(),
+                Some(&custom_fields),
+            )
+            .await
+        }
+    };
+
+    match created {
+        Ok(created) => Ok(json!({ "id": created.id, "project": created.project })),
+        Err(e) if e.is_offline() => {
+            let path = log::queue(
+                auth_service,
+                &messages,
+                &tags,
+                project.as_deref(),
+                recorded_at.as_deref(),
+                Some(&custom_fields),
+            )?;
+            Ok(json!({ "queued": path }))
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn optional_str<'a>(
+    params: &'a Map<String, Value>,
+    name: &str,
+) -> Result<Option<&'a str>, RequestError> {
+    match params.get(name) {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::String(value)) => Ok(Some(value)),
+        Some(_) => Err(RequestError::new(
+            ErrorCode::InvalidParams,
+            format!("'{name}' must be a string"),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::{Matcher, Server};
+    use tempfile::TempDir;
+
+    fn responses(output: Vec<u8>) -> Vec<Value> {
+        String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_porcelain_logs_entries_and_lists_projects() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut server = Server::new_async().await;
+        let _token = server
+            .mock("POST", "/auth/token_info")
+            .with_body(r#"{"active":true,"scope":"","client_id":"cli","exp":0}"#)
+            .create_async()
+            .await;
+        let _projects = server
+            .mock("GET", "/api/v1/projects")
+            .with_body(r#"{"projects":[{"id":"p1","identifier":"web","name":"Website"}]}"#)
+            .create_async()
+            .await;
+        let created = server
+            .mock("POST", "/api/v1/worklog/entries")
+            .match_body(Matcher::PartialJson(json!({
+                "content": "Fixed the build",
+                "tags": ["ci"],
+                "project_id": "p1",
+            })))
+            .with_status(201)
+            .with_body(r#"{"id": "entry-1"}"#)
+            .expect(1)
+            .create_async()
+            .await;
+        let _no_id = server
+            .mock("POST", "/api/v1/worklog/entries")
+            .match_body(Matcher::PartialJson(json!({ "content": "No id back" })))
+            .with_status(201)
+            .with_body(r#"{"status": "ok"}"#)
+            .create_async()
+            .await;
+
+        let mut auth_service =
+            AuthService::new(server.url(), temp_dir.path().to_path_buf(), "test-profile");
+        auth_service.save_access_token("dummy-token").unwrap();
+        let visibility = VisibilityPolicy::default();
+        let options = PorcelainOptions {
+            profile: "test-profile",
+            default_project: None,
+            visibility: &visibility,
+            field_schema: &FieldSchema::default(),
+        };
+        let input = [
+            r#"{"id":1,"method":"log","params":{"content":"Fixed the build","tags":["ci"],"project":"web"}}"#,
+            r#"{"id":2,"method":"log","params":{"content":"Elsewhere","project":"zzz"}}"#,
+            r#"{"id":3,"method":"log","params":{"tags":"ci"}}"#,
+            r#"{"id":4,"method":"projects"}"#,
+            r#"{"id":5,"method":"status"}"#,
+            r#"{"id":6,"method":"log","params":{"content":"No id back"}}"#,
+        ]
+        .join("\n");
+        let mut output = Vec::new();
+
+        let code = execute(&mut auth_service, &options, input.as_bytes(), &mut output).await;
+        assert_eq!(code, EXIT_OK);
+        created.assert_async().await;
+
+        let lines = responses(output);
+        assert_eq!(lines[0]["type"], "hello");
+        assert_eq!(lines[0]["offline"], false);
+        assert_eq!(lines[1]["result"]["id"], "entry-1");
+        assert_eq!(lines[2]["error"]["code"], "unknown_project");
+        assert_eq!(lines[3]["error"]["code"], "invalid_params");
+        assert_eq!(lines[4]["result"][0]["identifier"], "web");
+        assert_eq!(lines[5]["error"]["code"], "unknown_method");
+        // The unexpected response is reported in the error, not on stdout
+        assert_eq!(lines[6]["error"]["code"], "api");
+        assert!(lines[6]["error"]["message"]
+            .as_str()
+            .unwrap()
+            .contains("missing field `id`"));
+        assert_eq!(lines.len(), 7);
+    }
+
+    #[tokio::test]
+    async fn test_porcelain_checks_custom_fields() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut server = Server::new_async().await;
+        let _token = server
+            .mock("POST", "/auth/token_info")
+            .with_body(r#"{"active":true,"scope":"","client_id":"cli","exp":0}"#)
+            .create_async()
+            .await;
+        let created = server
+            .mock("POST", "/api/v1/worklog/entries")
+            .match_body(Matcher::PartialJson(json!({
+                "custom_fields": { "ticket": "WEB-1", "effort": 2 },
+            })))
+            .with_status(201)
+            .with_body(r#"{"id": "entry-1"}"#)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let mut auth_service =
+            AuthService::new(server.url(), temp_dir.path().to_path_buf(), "test-profile");
+        auth_service.save_access_token("dummy-token").unwrap();
+        let visibility = VisibilityPolicy::default();
+        let field_schema = FieldSchema::from_raw(
+            toml::from_str(
+                r#"
+                [ticket]
+                required = true
+
+                [effort]
+                type = "integer"
+                "#,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        let options = PorcelainOptions {
+            profile: "test-profile",
+            default_project: None,
+            visibility: &visibility,
+            field_schema: &field_schema,
+        };
+        let input = [
+            r#"{"id":1,"method":"log","params":{"content":"No ticket"}}"#,
+            r#"{"id":2,"method":"log","params":{"content":"Bad effort","fields":{"ticket":"WEB-1","effort":"lots"}}}"#,
+            r#"{"id":3,"method":"log","params":{"content":"Fine","fields":{"ticket":"WEB-1","effort":2}}}"#,
+        ]
+        .join("\n");
+        let mut output = Vec::new();
+
+        let code = execute(&mut auth_service, &options, input.as_bytes(), &mut output).await;
+        assert_eq!(code, EXIT_OK);
+        created.assert_async().await;
+
+        let lines = responses(output);
+        assert_eq!(lines[1]["error"]["code"], "invalid_params");
+        assert!(lines[1]["error"]["message"]
+            .as_str()
+            .unwrap()
+            .contains("ticket"));
+        assert_eq!(lines[2]["error"]["code"], "invalid_params");
+        assert_eq!(lines[3]["result"]["id"], "entry-1");
+    }
+
+    #[tokio::test]
+    async fn test_porcelain_exits_when_not_logged_in() {
+        let temp_dir = TempDir::new().unwrap();
+        let server = Server::new_async().await;
+        let mut auth_service =
+            AuthService::new(server.url(), temp_dir.path().to_path_buf(), "test-profile");
+        let visibility = VisibilityPolicy::default();
+        let options = PorcelainOptions {
+            profile: "test-profile",
+            default_project: None,
+            visibility: &visibility,
+            field_schema: &FieldSchema::default(),
+        };
+        let mut output = Vec::new();
+
+        let code = execute(&mut auth_service, &options, "".as_bytes(), &mut output).await;
+        assert_eq!(code, EXIT_UNAUTHENTICATED);
+        let lines = responses(output);
+        assert_eq!(lines[0]["type"], "fatal");
+        assert_eq!(lines[0]["error"]["code"], "unauthenticated");
+    }
+}
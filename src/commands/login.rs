@@ -1,11 +1,82 @@
+use crate::api::client::ApiClient;
 use crate::api::endpoints::{exchange_device_code_for_token, initiate_device_code};
+use crate::api::errors::ApiError;
+use crate::api::models::{DeviceCodeResponse, TokenResponse};
 use crate::auth::callback_server;
 use crate::auth::AuthService;
 use crate::errors::AppError;
+use crate::utils::qr;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
 use tokio::sync::oneshot;
 
-/// Starts the OAuth device flow and saves the token.
-pub async fn execute(auth_service: &mut AuthService, client_id: &str) -> Result<(), AppError> {
+const PENDING_LOGIN_FILE: &str = "pending_login.json";
+
+/// How long a device code lives when the server doesn't say.
+const DEFAULT_DEVICE_CODE_LIFETIME_SECS: u64 = 900;
+
+/// Extra wait the server asks for with `slow_down` (RFC 8628).
+const SLOW_DOWN_SECS: u64 = 5;
+
+/// A device-code login that was started but not finished, kept so
+/// `acc login --resume` can pick it up after an interruption.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct PendingLogin {
+    device_code: String,
+    user_code: String,
+    verification_uri: String,
+    verification_uri_complete: String,
+    interval: u64,
+    expires_at: DateTime<Utc>,
+}
+
+impl PendingLogin {
+    fn from_response(resp: &DeviceCodeResponse, now: DateTime<Utc>) -> Self {
+        let lifetime = resp.expires_in.unwrap_or(DEFAULT_DEVICE_CODE_LIFETIME_SECS);
+        Self {
+            device_code: resp.device_code.clone(),
+            user_code: resp.user_code.clone(),
+            verification_uri: resp.verification_uri.clone(),
+            verification_uri_complete: resp.verification_uri_complete.clone(),
+            interval: resp.interval,
+            expires_at: now + Duration::seconds(lifetime as i64),
+        }
+    }
+}
+
+fn pending_path(state_dir: &Path) -> PathBuf {
+    state_dir.join(PENDING_LOGIN_FILE)
+}
+
+fn load_pending(state_dir: &Path) -> Option<PendingLogin> {
+    let content = fs::read_to_string(pending_path(state_dir)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn save_pending(state_dir: &Path, pending: &PendingLogin) -> Result<(), AppError> {
+    fs::create_dir_all(state_dir)?;
+    fs::write(
+        pending_path(state_dir),
+        serde_json::to_string_pretty(pending)?,
+    )?;
+    Ok(())
+}
+
+fn clear_pending(state_dir: &Path) {
+    let _ = fs::remove_file(pending_path(state_dir));
+}
+
+/// Starts the OAuth device flow and saves the token. With `show_qr`, or when
+/// there's no browser to open, the verification link is also printed as a
+/// QR code to scan with a phone.
+pub async fn execute(
+    auth_service: &mut AuthService,
+    client_id: &str,
+    show_qr: bool,
+) -> Result<(), AppError> {
     // spawn callback server
     let (tx, rx) = oneshot::channel();
     tokio::spawn(async move {
@@ -18,13 +89,18 @@ pub async fn execute(auth_service: &mut AuthService, client_id: &str) -> Result<
     let resp = initiate_device_code(auth_service.api_client(), client_id)
         .await
         .map_err(AppError::Api)?;
-    // open browser immediately
-    let _ = webbrowser::open(&resp.verification_uri_complete);
+    // Kept until the login completes, so an interrupted one can be resumed
+    save_pending(
+        auth_service.state_dir(),
+        &PendingLogin::from_response(&resp, Utc::now()),
+    )?;
+    open_verification_page(&resp.verification_uri_complete, show_qr);
 
     println!(
         "\nVisit {} and enter code {} then press Enter...",
         resp.verification_uri, resp.user_code
     );
+    println!("(Interrupted? Run `acc login --resume` to continue with the same code.)");
     let _ = std::io::stdin().read_line(&mut String::new());
 
     // wait for callback
@@ -34,8 +110,167 @@ pub async fn execute(auth_service: &mut AuthService, client_id: &str) -> Result<
     let tok = exchange_device_code_for_token(auth_service.api_client(), &code)
         .await
         .map_err(AppError::Api)?;
-    auth_service.save_access_token(&tok.access_token)?;
+    auth_service.save_tokens(&tok)?;
+    clear_pending(auth_service.state_dir());
+
+    println!("Authentication successful!");
+    Ok(())
+}
+
+/// Continues an interrupted login: shows the same code again and polls for
+/// the token until it's approved or the code expires.
+pub async fn resume(auth_service: &mut AuthService, show_qr: bool) -> Result<(), AppError> {
+    let pending = load_pending(auth_service.state_dir()).ok_or_else(|| {
+        AppError::Other("No interrupted login to resume. Run `acc login` to start one".to_string())
+    })?;
+    if pending.expires_at <= Utc::now() {
+        clear_pending(auth_service.state_dir());
+        return Err(AppError::Other(
+            "The pending login code has expired. Run `acc login` to get a new one".to_string(),
+        ));
+    }
+
+    println!(
+        "\nVisit {} and enter code {} (valid until {}). Waiting for approval...",
+        pending.verification_uri,
+        pending.user_code,
+        pending
+            .expires_at
+            .with_timezone(&chrono::Local)
+            .format("%H:%M")
+    );
+    open_verification_page(&pending.verification_uri_complete, show_qr);
+
+    let tok = poll_for_token(auth_service.api_client(), &pending).await?;
+    auth_service.save_tokens(&tok)?;
+    clear_pending(auth_service.state_dir());
 
     println!("Authentication successful!");
     Ok(())
 }
+
+/// Opens the verification page in the browser, falling back to a QR code on
+/// headless machines or when the browser can't be launched.
+fn open_verification_page(url: &str, show_qr: bool) {
+    let opened = !qr::is_headless() && webbrowser::open(url).is_ok();
+    if opened && !show_qr {
+        return;
+    }
+    match qr::render(url) {
+        Some(code) => println!("\nScan to open {url}\n\n{code}"),
+        None => println!("\nOpen {url} on another device"),
+    }
+}
+
+/// Polls the token endpoint every `interval` seconds while authorization is
+/// pending, backing off when asked to slow down.
+async fn poll_for_token(
+    api_client: &ApiClient,
+    pending: &PendingLogin,
+) -> Result<TokenResponse, AppError> {
+    let mut interval = pending.interval;
+    loop {
+        match exchange_device_code_for_token(api_client, &pending.device_code).await {
+            Ok(token) => return Ok(token),
+            Err(e) => match oauth_error_code(&e).as_deref() {
+                Some("authorization_pending") => {}
+                Some("slow_down") => interval += SLOW_DOWN_SECS,
+                _ => return Err(AppError::Api(e)),
+            },
+        }
+
+        if Utc::now() + Duration::seconds(interval as i64) >= pending.expires_at {
+            return Err(AppError::Other(
+                "The login code expired before it was approved. Run `acc login` to get a new one"
+                    .to_string(),
+            ));
+        }
+        tokio::time::sleep(std::time::Duration::from_secs(interval)).await;
+    }
+}
+
+/// The OAuth `error` code in a token endpoint error body, e.g. `authorization_pending`.
+fn oauth_error_code(error: &ApiError) -> Option<String> {
+    let body = match error {
+        ApiError::Unauthorized(body) | ApiError::BadRequest(body) => body,
+        _ => return None,
+    };
+    serde_json::from_str::<Value>(body)
+        .ok()?
+        .get("error")?
+        .as_str()
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::Server;
+    use tempfile::TempDir;
+
+    fn pending(expires_in: i64) -> PendingLogin {
+        PendingLogin {
+            device_code: "device-123".into(),
+            user_code: "ABCD-EFGH".into(),
+            verification_uri: "https://accomplish.dev/device".into(),
+            verification_uri_complete: "https://accomplish.dev/device?code=ABCD-EFGH".into(),
+            interval: 0,
+            expires_at: Utc::now() + Duration::seconds(expires_in),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resume_polls_and_clears_pending_login() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut server = Server::new_async().await;
+        let mut auth =
+            AuthService::new(server.url(), temp_dir.path().to_path_buf(), "test-profile");
+        save_pending(auth.state_dir(), &pending(600)).unwrap();
+
+        let token = server
+            .mock("POST", "/auth/device/token")
+            .match_body(mockito::Matcher::Json(
+                serde_json::json!({ "device_code": "device-123" }),
+            ))
+            .with_status(200)
+            .with_body(
+                r#"{"access_token":"new-token","token_type":"Bearer","expires_in":3600,"refresh_token":"r","scope":"read"}"#,
+            )
+            .create();
+
+        resume(&mut auth, false).await.unwrap();
+        token.assert();
+        assert!(load_pending(auth.state_dir()).is_none());
+        assert!(resume(&mut auth, false).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_resume_refuses_expired_code() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut auth = AuthService::new(
+            "http://127.0.0.1:9".into(),
+            temp_dir.path().to_path_buf(),
+            "test-profile",
+        );
+        save_pending(auth.state_dir(), &pending(-1)).unwrap();
+
+        assert!(resume(&mut auth, false).await.is_err());
+        assert!(load_pending(auth.state_dir()).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_poll_stops_on_denied_request() {
+        let mut server = Server::new_async().await;
+        let _denied = server
+            .mock("POST", "/auth/device/token")
+            .with_status(401)
+            .with_body(r#"{"error":"access_denied"}"#)
+            .create();
+
+        let result = poll_for_token(&ApiClient::new(&server.url()), &pending(600)).await;
+        assert!(matches!(
+            result,
+            Err(AppError::Api(ApiError::Unauthorized(_)))
+        ));
+    }
+}
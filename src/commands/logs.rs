@@ -1,43 +1,86 @@
-use crate::api::endpoints::fetch_worklog_entries;
+use crate::api::client::ApiClient;
+use crate::api::endpoints::{fetch_worklog_entries, fetch_worklog_entry};
+use crate::api::errors::ApiError;
+use crate::api::models::WorklogEntry;
 use crate::auth::AuthService;
 use crate::commands::project;
 use crate::errors::AppError;
-use chrono::{DateTime, Utc};
+use crate::pins::Pins;
+use crate::utils::custom_fields::format_fields;
+use crate::utils::highlight::render_content;
+use crate::utils::humanize::relative_time;
+use crate::utils::relations;
+use crate::utils::visibility::{is_visibility_tag, Visibility};
+use chrono::{DateTime, Local};
 use colored::*;
 use crossterm::event::{read, Event, KeyCode, KeyEvent};
-use crossterm::terminal::{disable_raw_mode, enable_raw_mode};
+use crossterm::terminal::{self, disable_raw_mode, enable_raw_mode};
+use inquire::{InquireError, Select};
 use serde_json::Value;
-use std::io::{self, Write};
+use std::io::{self, IsTerminal, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
 
+#[allow(clippy::too_many_arguments)]
 pub async fn execute(
     auth_service: &mut AuthService,
     project_identifier: Option<&str>,
     tags: Option<&[String]>,
     from: Option<&str>,
     to: Option<&str>,
+    has_commits: Option<bool>,
     limit: u32,
-    verbose: bool,
+    format: EntryFormat,
+    oldest_first: bool,
+    pins: &Pins,
 ) -> Result<(), AppError> {
-    // Convert project identifier to project UUID if provided
-    let project_id = if let Some(identifier) = project_identifier {
-        let projects = project::get_projects(auth_service).await?;
-
-        let mut found_id = None;
-        for p in &projects {
-            if p.identifier.to_lowercase() == identifier.to_lowercase() {
-                found_id = Some(p.id.clone());
-                break;
-            }
-        }
+    // Convert project identifier to the UUIDs of the project and its sub-projects
+    let project_ids = match project_identifier {
+        Some(identifier) => project::resolve_project_filter_ids(auth_service, identifier).await?,
+        None => None,
+    };
 
-        if found_id.is_none() {
-            println!("⚠️ Warning: No project found with identifier '{identifier}'");
-        }
+    if format.is_scripted() {
+        let entries = if oldest_first {
+            let mut entries = fetch_all_matching(
+                auth_service.api_client(),
+                project_ids.as_deref(),
+                tags,
+                from,
+                to,
+                has_commits,
+            )
+            .await?;
+            entries.reverse();
+            entries
+        } else {
+            fetch_newest(
+                auth_service.api_client(),
+                project_ids.as_deref(),
+                tags,
+                from,
+                to,
+                has_commits,
+                limit,
+            )
+            .await?
+        };
+        return page_through(&entries, limit, format, pins);
+    }
 
-        found_id
-    } else {
-        None
-    };
+    if oldest_first {
+        // The API pages newest first, so chronological order needs every match up front
+        let mut entries = fetch_all_matching(
+            auth_service.api_client(),
+            project_ids.as_deref(),
+            tags,
+            from,
+            to,
+            has_commits,
+        )
+        .await?;
+        entries.reverse();
+        return page_through(&entries, limit, format, pins);
+    }
 
     let api_client = auth_service.api_client();
     let mut cursor: Option<String> = None;
@@ -45,78 +88,414 @@ pub async fn execute(
     let mut all_entries_loaded = false;
 
     // Load first page
-    let response = fetch_worklog_entries(
+    let page = fetch_worklog_entries(
         api_client,
-        project_id.as_deref(),
+        project_ids.as_deref(),
         tags,
         from,
         to,
+        has_commits,
         limit,
         cursor.as_deref(),
     )
     .await?;
 
-    if let Some(entries) = response.get("entries").and_then(Value::as_array) {
-        if entries.is_empty() {
-            println!("No entries found.");
-            return Ok(());
-        }
+    if page.entries.is_empty() {
+        println!("No entries found.");
+        return Ok(());
+    }
 
-        // Show first page entries
-        for entry in entries {
-            print_entry(entry, verbose)?;
-        }
-        total_entries_shown += entries.len();
+    // Check if we have more pages
+    match page.end_cursor() {
+        Some(end_cursor) => cursor = Some(end_cursor.to_string()),
+        None => all_entries_loaded = true,
+    }
 
-        // Check if we have more pages
-        let meta = response.get("meta");
-        if let Some(end_cursor) = meta.and_then(|m| m.get("end_cursor").and_then(Value::as_str)) {
-            cursor = Some(end_cursor.to_string());
-        } else {
-            all_entries_loaded = true;
+    // Show first page entries
+    let mut pager = Pager::new(format, pins);
+    pager.show(&page.entries)?;
+    total_entries_shown += page.entries.len();
+
+    // If we have more entries, start interactive pagination
+    if !all_entries_loaded {
+        interactive_pagination(
+            auth_service,
+            project_ids.as_deref(),
+            tags,
+            from,
+            to,
+            has_commits,
+            limit,
+            &mut pager,
+            &mut cursor,
+            &mut total_entries_shown,
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Marks pinned entries in listings.
+const PIN_MARKER: &str = "📌";
+
+static RELATIVE_DATES: AtomicBool = AtomicBool::new(false);
+
+/// Shows entry times like "today 14:32" in the preview and `--oneline`
+/// listings, from `relative_dates = true` in the config or `--relative-dates`.
+pub fn set_relative_dates(enabled: bool) {
+    RELATIVE_DATES.store(enabled, Ordering::Relaxed);
+}
+
+/// The time relative dates are shown against, when they're on.
+fn relative_to() -> Option<DateTime<Local>> {
+    RELATIVE_DATES.load(Ordering::Relaxed).then(Local::now)
+}
+
+/// Page size used when every matching entry is needed rather than one screenful.
+const FETCH_ALL_PAGE_SIZE: u32 = 100;
+
+/// Like `fetch_all_matching`, as the JSON the API returned.
+pub async fn fetch_all_entries(
+    api_client: &ApiClient,
+    project_ids: Option<&[String]>,
+    tags: Option<&[String]>,
+    from: Option<&str>,
+    to: Option<&str>,
+) -> Result<Vec<Value>, AppError> {
+    let entries = fetch_all_matching(api_client, project_ids, tags, from, to, None).await?;
+    Ok(entries.into_iter().map(Value::from).collect())
+}
+
+/// Fetches every entry matching the filters by following pagination cursors.
+pub async fn fetch_all_matching(
+    api_client: &ApiClient,
+    project_ids: Option<&[String]>,
+    tags: Option<&[String]>,
+    from: Option<&str>,
+    to: Option<&str>,
+    has_commits: Option<bool>,
+) -> Result<Vec<WorklogEntry>, AppError> {
+    let mut all_entries = Vec::new();
+    let mut cursor: Option<String> = None;
+
+    loop {
+        let page = fetch_worklog_entries(
+            api_client,
+            project_ids,
+            tags,
+            from,
+            to,
+            has_commits,
+            FETCH_ALL_PAGE_SIZE,
+            cursor.as_deref(),
+        )
+        .await?;
+
+        if page.entries.is_empty() {
+            break;
         }
+        let page_len = page.entries.len();
+        let end_cursor = page.end_cursor().map(str::to_string);
+        all_entries.extend(page.entries);
 
-        // If we have more entries, start interactive pagination
-        if !all_entries_loaded {
-            interactive_pagination(
-                auth_service,
-                project_id.as_deref(),
-                tags,
-                from,
-                to,
-                limit,
-                verbose,
-                &mut cursor,
-                &mut total_entries_shown,
-            )
-            .await?;
+        match end_cursor {
+            Some(end_cursor) if page_len as u32 >= FETCH_ALL_PAGE_SIZE => {
+                cursor = Some(end_cursor);
+            }
+            _ => break,
         }
-    } else {
-        println!("No entries found.");
     }
 
-    Ok(())
+    Ok(all_entries)
 }
 
-#[allow(clippy::too_many_arguments)]
-async fn interactive_pagination(
-    auth_service: &mut AuthService,
-    project_id: Option<&str>,
+/// Fetches up to `limit` of the newest matching entries, following
+/// pagination cursors as needed.
+async fn fetch_newest(
+    api_client: &ApiClient,
+    project_ids: Option<&[String]>,
     tags: Option<&[String]>,
     from: Option<&str>,
     to: Option<&str>,
+    has_commits: Option<bool>,
     limit: u32,
-    verbose: bool,
-    cursor: &mut Option<String>,
-    total_entries_shown: &mut usize,
+) -> Result<Vec<WorklogEntry>, AppError> {
+    let mut entries: Vec<WorklogEntry> = Vec::new();
+    let mut cursor: Option<String> = None;
+
+    while (entries.len() as u32) < limit {
+        let page_size = (limit - entries.len() as u32).min(FETCH_ALL_PAGE_SIZE);
+        let page = fetch_worklog_entries(
+            api_client,
+            project_ids,
+            tags,
+            from,
+            to,
+            has_commits,
+            page_size,
+            cursor.as_deref(),
+        )
+        .await?;
+
+        if page.entries.is_empty() {
+            break;
+        }
+        let page_len = page.entries.len();
+        let end_cursor = page.end_cursor().map(str::to_string);
+        entries.extend(page.entries);
+
+        match end_cursor {
+            Some(end_cursor) if page_len as u32 >= page_size => {
+                cursor = Some(end_cursor);
+            }
+            _ => break,
+        }
+    }
+
+    entries.truncate(limit as usize);
+    Ok(entries)
+}
+
+/// Finds an entry by its full ID, or by a unique ID prefix such as the eight
+/// characters `acc logs` shows.
+pub async fn find_entry(
+    api_client: &ApiClient,
+    id_or_prefix: &str,
+) -> Result<WorklogEntry, AppError> {
+    let id_or_prefix = id_or_prefix.trim();
+    match fetch_worklog_entry(api_client, id_or_prefix).await {
+        Ok(entry) => return Ok(entry),
+        Err(ApiError::NotFound(_)) | Err(ApiError::BadRequest(_)) => {}
+        Err(e) => return Err(e.into()),
+    }
+
+    let mut matches: Vec<WorklogEntry> =
+        fetch_all_matching(api_client, None, None, None, None, None)
+            .await?
+            .into_iter()
+            .filter(|entry| entry.id.starts_with(id_or_prefix))
+            .collect();
+
+    match matches.len() {
+        1 => Ok(matches.remove(0)),
+        0 => Err(AppError::Other(format!(
+            "No entry found with ID '{id_or_prefix}'"
+        ))),
+        _ => Err(AppError::Other(format!(
+            "'{id_or_prefix}' matches {} entries. Use a longer ID",
+            matches.len()
+        ))),
+    }
+}
+
+/// Shows already-fetched entries `limit` at a time with the same prompt as API paging.
+pub fn page_through(
+    entries: &[WorklogEntry],
+    limit: u32,
+    format: EntryFormat,
+    pins: &Pins,
 ) -> Result<(), AppError> {
-    let api_client = auth_service.api_client();
+    if format.is_scripted() {
+        return print_entries(&entries[..entries.len().min(limit as usize)], format, pins);
+    }
+    if entries.is_empty() {
+        println!("No entries found.");
+        return Ok(());
+    }
+
+    let mut pager = Pager::new(format, pins);
+    let mut pages = entries.chunks(limit.max(1) as usize).peekable();
+    while let Some(page) = pages.next() {
+        pager.show(page)?;
+        if pages.peek().is_some() && !pager.prompt_for_more()? {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+/// Narrows what the pager shows to loaded entries containing some text and/or
+/// carrying a tag.
+#[derive(Debug, Default)]
+struct PagerFilter {
+    text: Option<String>,
+    tag: Option<String>,
+}
+
+impl PagerFilter {
+    fn is_active(&self) -> bool {
+        self.text.is_some() || self.tag.is_some()
+    }
+
+    fn matches(&self, entry: &WorklogEntry) -> bool {
+        let text_matches = self
+            .text
+            .as_ref()
+            .is_none_or(|text| entry.content.to_lowercase().contains(&text.to_lowercase()));
+        let tag_matches = self
+            .tag
+            .as_ref()
+            .is_none_or(|tag| entry_tags(entry).any(|t| t.eq_ignore_ascii_case(tag)));
+        text_matches && tag_matches
+    }
+
+    fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(text) = &self.text {
+            parts.push(format!("\"{text}\""));
+        }
+        if let Some(tag) = &self.tag {
+            parts.push(format!("#{tag}"));
+        }
+        parts.join(" ")
+    }
+}
+
+/// An entry's tags, without the visibility tag.
+pub fn entry_tags(entry: &WorklogEntry) -> impl Iterator<Item = &str> {
+    entry
+        .tags
+        .iter()
+        .map(String::as_str)
+        .filter(|t| !is_visibility_tag(t))
+}
+
+/// The visibility recorded in an entry's tags, shown as an icon.
+pub fn entry_visibility(entry: &WorklogEntry) -> Option<Visibility> {
+    Visibility::from_tags(entry.tags.iter().map(String::as_str))
+}
+
+/// Tags of the loaded entries, most used first.
+pub fn tags_by_frequency(entries: &[WorklogEntry]) -> Vec<String> {
+    let mut counts: Vec<(String, usize)> = Vec::new();
+    for tag in entries.iter().flat_map(entry_tags) {
+        match counts.iter_mut().find(|(t, _)| t.eq_ignore_ascii_case(tag)) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((tag.to_string(), 1)),
+        }
+    }
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    counts.into_iter().map(|(tag, _)| tag).collect()
+}
+
+/// What the user asked for at the pager prompt.
+enum PagerKey {
+    More,
+    Quit,
+    Search,
+    ToggleTag,
+}
+
+/// Prints pages of entries and keeps the ones loaded so far, so they can be
+/// searched (`/`) or narrowed to a tag (`t`) without fetching anything again.
+struct Pager<'a> {
+    format: EntryFormat,
+    pins: &'a Pins,
+    loaded: Vec<WorklogEntry>,
+    filter: PagerFilter,
+}
+
+impl<'a> Pager<'a> {
+    fn new(format: EntryFormat, pins: &'a Pins) -> Self {
+        Self {
+            format,
+            pins,
+            loaded: Vec::new(),
+            filter: PagerFilter::default(),
+        }
+    }
 
+    /// Prints the entries of a new page that pass the current filter.
+    fn show(&mut self, page: &[WorklogEntry]) -> Result<(), AppError> {
+        self.loaded.extend(page.iter().cloned());
+        let visible: Vec<WorklogEntry> = page
+            .iter()
+            .filter(|entry| self.filter.matches(entry))
+            .cloned()
+            .collect();
+        print_entries(&visible, self.format, self.pins)
+    }
+
+    /// Handles search and tag keys until the user asks for more or quits.
+    /// Returns whether to continue.
+    fn prompt_for_more(&mut self) -> Result<bool, AppError> {
+        loop {
+            match read_pager_key()? {
+                PagerKey::More => return Ok(true),
+                PagerKey::Quit => return Ok(false),
+                PagerKey::Search => {
+                    let text = read_line("/")?;
+                    self.filter.text = (!text.is_empty()).then_some(text);
+                    self.reprint()?;
+                }
+                PagerKey::ToggleTag => {
+                    if self.filter.tag.take().is_none() {
+                        self.filter.tag = self.choose_tag()?;
+                    }
+                    self.reprint()?;
+                }
+            }
+        }
+    }
+
+    fn choose_tag(&self) -> Result<Option<String>, AppError> {
+        let tags = tags_by_frequency(&self.loaded);
+        if tags.is_empty() {
+            println!("{}", "No tags in the loaded entries.".bright_black());
+            return Ok(None);
+        }
+        match Select::new("Filter by tag:", tags).prompt() {
+            Ok(tag) => Ok(Some(tag)),
+            Err(InquireError::OperationCanceled) => Ok(None),
+            Err(e) => Err(AppError::ParseError(format!("Selection failed: {e}"))),
+        }
+    }
+
+    /// Shows the loaded entries again under the new filter.
+    fn reprint(&self) -> Result<(), AppError> {
+        let visible: Vec<WorklogEntry> = self
+            .loaded
+            .iter()
+            .filter(|entry| self.filter.matches(entry))
+            .cloned()
+            .collect();
+
+        println!();
+        if self.filter.is_active() {
+            println!(
+                "{}",
+                format!(
+                    "{} of {} loaded entries match {}",
+                    visible.len(),
+                    self.loaded.len(),
+                    self.filter.describe()
+                )
+                .bright_black()
+            );
+        } else {
+            println!(
+                "{}",
+                format!("All {} loaded entries", self.loaded.len()).bright_black()
+            );
+        }
+        println!();
+        print_entries(&visible, self.format, self.pins)
+    }
+}
+
+/// Reads a single key: SPACE/Enter for more, `/` to search, `t` to toggle a
+/// tag filter, q/Esc to stop.
+fn read_pager_key() -> Result<PagerKey, AppError> {
     loop {
-        // Show pagination prompt
         print!("{}", "Press ".bright_black());
         print!("{}", "SPACE".bright_white());
         print!("{}", " for more, ".bright_black());
+        print!("{}", "/".bright_white());
+        print!("{}", " to search, ".bright_black());
+        print!("{}", "t".bright_white());
+        print!("{}", " to filter by tag, ".bright_black());
         print!("{}", "q".bright_white());
         print!("{}", " to quit: ".bright_black());
         io::stdout().flush().unwrap();
@@ -133,62 +512,19 @@ async fn interactive_pagination(
 
         match key_result {
             Ok(Event::Key(KeyEvent { code, .. })) => {
+                // Clear the prompt line
+                print!("\r{}\r", " ".repeat(80));
+                io::stdout().flush().unwrap();
+
                 match code {
-                    KeyCode::Char(' ') | KeyCode::Enter => {
-                        // Clear the prompt line
-                        print!("\r{}\r", " ".repeat(50));
-                        io::stdout().flush().unwrap();
-
-                        // Load next page
-                        let response = fetch_worklog_entries(
-                            api_client,
-                            project_id,
-                            tags,
-                            from,
-                            to,
-                            limit,
-                            cursor.as_deref(),
-                        )
-                        .await?;
-
-                        if let Some(entries) = response.get("entries").and_then(Value::as_array) {
-                            if entries.is_empty() {
-                                println!("No more entries.");
-                                break;
-                            }
-
-                            for entry in entries {
-                                print_entry(entry, verbose)?;
-                            }
-                            *total_entries_shown += entries.len();
-
-                            // Update cursor for next page
-                            let meta = response.get("meta");
-                            if let Some(end_cursor) =
-                                meta.and_then(|m| m.get("end_cursor").and_then(Value::as_str))
-                            {
-                                *cursor = Some(end_cursor.to_string());
-                            } else {
-                                println!("No more entries.");
-                                break;
-                            }
-                        } else {
-                            println!("No more entries.");
-                            break;
-                        }
-                    }
+                    KeyCode::Char(' ') | KeyCode::Enter => return Ok(PagerKey::More),
                     KeyCode::Char('q') | KeyCode::Char('Q') | KeyCode::Esc => {
-                        // Clear the prompt line
-                        print!("\r{}\r", " ".repeat(50));
-                        io::stdout().flush().unwrap();
-                        break;
-                    }
-                    _ => {
-                        // Clear the prompt line and show it again
-                        print!("\r{}\r", " ".repeat(50));
-                        io::stdout().flush().unwrap();
-                        continue;
+                        return Ok(PagerKey::Quit)
                     }
+                    KeyCode::Char('/') => return Ok(PagerKey::Search),
+                    KeyCode::Char('t') | KeyCode::Char('T') => return Ok(PagerKey::ToggleTag),
+                    // Show the prompt again
+                    _ => continue,
                 }
             }
             Ok(_) => continue,
@@ -197,65 +533,310 @@ async fn interactive_pagination(
             }
         }
     }
+}
+
+/// Reads a line of input after `prompt`; an empty line clears the search.
+fn read_line(prompt: &str) -> Result<String, AppError> {
+    print!("{}", prompt.bright_white());
+    io::stdout().flush().unwrap();
+    let mut line = String::new();
+    io::stdin()
+        .read_line(&mut line)
+        .map_err(|e| AppError::Other(format!("Error reading input: {e}")))?;
+    Ok(line.trim().to_string())
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn interactive_pagination(
+    auth_service: &mut AuthService,
+    project_ids: Option<&[String]>,
+    tags: Option<&[String]>,
+    from: Option<&str>,
+    to: Option<&str>,
+    has_commits: Option<bool>,
+    limit: u32,
+    pager: &mut Pager<'_>,
+    cursor: &mut Option<String>,
+    total_entries_shown: &mut usize,
+) -> Result<(), AppError> {
+    let api_client = auth_service.api_client();
+
+    while pager.prompt_for_more()? {
+        // Load next page
+        let page = fetch_worklog_entries(
+            api_client,
+            project_ids,
+            tags,
+            from,
+            to,
+            has_commits,
+            limit,
+            cursor.as_deref(),
+        )
+        .await?;
+
+        if page.entries.is_empty() {
+            println!("No more entries.");
+            break;
+        }
+        let end_cursor = page.end_cursor().map(str::to_string);
+
+        pager.show(&page.entries)?;
+        *total_entries_shown += page.entries.len();
+
+        // Update cursor for next page
+        if let Some(end_cursor) = end_cursor {
+            *cursor = Some(end_cursor);
+        } else {
+            println!("No more entries.");
+            break;
+        }
+    }
 
     Ok(())
 }
 
-fn print_entry(entry: &Value, verbose: bool) -> Result<(), AppError> {
-    let id = entry.get("id").and_then(Value::as_str).unwrap_or("unknown");
-    let content = entry.get("content").and_then(Value::as_str).unwrap_or("");
-    let recorded_at = entry
-        .get("recorded_at")
-        .and_then(Value::as_str)
-        .unwrap_or("");
-
-    // Parse and format the date
-    let formatted_date = if !recorded_at.is_empty() {
-        match recorded_at.parse::<DateTime<Utc>>() {
-            Ok(dt) => dt.format("%Y-%m-%d %H:%M:%S UTC").to_string(),
-            Err(_) => recorded_at.to_string(),
+/// How `acc logs` renders each entry.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EntryFormat {
+    /// Header plus the truncated first line, tags and fields
+    Preview,
+    /// Header plus full content, tags, fields and commit count
+    Verbose,
+    /// One aligned line per entry, like `git log --oneline`
+    Oneline,
+    /// The entries as returned by the API, as one JSON array
+    Json,
+    /// Just the entry IDs, one per line
+    Ids,
+}
+
+impl EntryFormat {
+    /// Whether the output is meant for scripts, which get everything at once
+    /// with no prompts or messages in between.
+    pub fn is_scripted(self) -> bool {
+        matches!(self, EntryFormat::Json | EntryFormat::Ids)
+    }
+}
+
+pub fn print_entries(
+    entries: &[WorklogEntry],
+    format: EntryFormat,
+    pins: &Pins,
+) -> Result<(), AppError> {
+    if format == EntryFormat::Json {
+        println!("{}", serde_json::to_string_pretty(entries)?);
+        return Ok(());
+    }
+    if format == EntryFormat::Ids {
+        for entry in entries {
+            println!("{}", entry.id);
         }
-    } else {
-        "unknown".to_string()
+        return Ok(());
+    }
+    if format == EntryFormat::Oneline {
+        print_oneline(entries, pins);
+        return Ok(());
+    }
+
+    for entry in entries {
+        print_entry(
+            entry,
+            format == EntryFormat::Verbose,
+            is_pinned(entry, pins),
+        )?;
+    }
+    Ok(())
+}
+
+fn is_pinned(entry: &WorklogEntry, pins: &Pins) -> bool {
+    pins.contains(&entry.id)
+}
+
+/// Width available for `--oneline` rows, or None when output isn't a terminal
+/// (piped output is never truncated, so it stays greppable).
+fn oneline_width() -> Option<usize> {
+    if !io::stdout().is_terminal() {
+        return None;
+    }
+    terminal::size().ok().map(|(cols, _)| cols as usize)
+}
+
+fn print_oneline(entries: &[WorklogEntry], pins: &Pins) {
+    let now = relative_to();
+    let mut rows: Vec<OnelineRow> = entries
+        .iter()
+        .map(|entry| OnelineRow::from_entry(entry, now))
+        .collect();
+    // Relative times vary in length, so they're padded to line up
+    let time_width = rows
+        .iter()
+        .map(|row| row.time.chars().count())
+        .max()
+        .unwrap_or(0);
+    for row in &mut rows {
+        row.time = format!("{:<time_width$}", row.time);
+    }
+    let project_width = rows
+        .iter()
+        .map(|row| row.project.chars().count())
+        .max()
+        .unwrap_or(0);
+    let width = oneline_width();
+
+    for (entry, row) in entries.iter().zip(&rows) {
+        let mut line = row.time.bright_blue().to_string();
+        if project_width > 0 {
+            let project = format!("{:<project_width$}", row.project);
+            line.push_str(&format!(" {}", project.bright_green()));
+        }
+        line.push(' ');
+        line.push_str(&row.fit_content(project_width, width));
+        if !row.tags.is_empty() {
+            line.push_str(&format!(" {}", row.tags.bright_yellow()));
+        }
+        if is_pinned(entry, pins) {
+            line.push_str(&format!(" {PIN_MARKER}"));
+        }
+        if let Some(visibility) = entry_visibility(entry) {
+            line.push_str(&format!(" {}", visibility.icon()));
+        }
+        println!("{line}");
+    }
+}
+
+/// Plain-text columns of a `--oneline` row.
+#[derive(Debug, PartialEq)]
+struct OnelineRow {
+    time: String,
+    project: String,
+    content: String,
+    tags: String,
+}
+
+impl OnelineRow {
+    /// With `now`, the time is shown relative to it.
+    fn from_entry(entry: &WorklogEntry, now: Option<DateTime<Local>>) -> Self {
+        let time = entry
+            .recorded_at
+            .map(|dt| dt.with_timezone(&Local))
+            .map(|dt| match now {
+                Some(now) => relative_time(dt, now),
+                None => dt.format("%Y-%m-%d %H:%M").to_string(),
+            })
+            .unwrap_or_else(|| format!("{:<16}", "unknown"));
+        let project = entry
+            .project
+            .as_ref()
+            .map(|p| format!("[{}]", p.identifier.to_uppercase()))
+            .unwrap_or_default();
+        let content = entry
+            .content
+            .lines()
+            .map(str::trim)
+            .find(|l| !l.is_empty())
+            .unwrap_or("")
+            .to_string();
+        let tags = entry_tags(entry)
+            .map(|t| format!("#{t}"))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        Self {
+            time,
+            project,
+            content,
+            tags,
+        }
+    }
+
+    /// Content shortened so the whole row fits in `width` columns. Tags are kept
+    /// whole, and a few characters of content always remain.
+    fn fit_content(&self, project_width: usize, width: Option<usize>) -> String {
+        const MIN_CONTENT: usize = 10;
+
+        let Some(width) = width else {
+            return self.content.clone();
+        };
+        let column = |w: usize| if w > 0 { w + 1 } else { 0 };
+        let used = self.time.chars().count()
+            + column(project_width)
+            + 1
+            + column(self.tags.chars().count());
+
+        truncate(&self.content, width.saturating_sub(used).max(MIN_CONTENT))
+    }
+}
+
+/// Cuts `text` to at most `max` characters, ending in `…` when shortened.
+fn truncate(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        return text.to_string();
+    }
+    if max == 0 {
+        return String::new();
+    }
+    let mut cut: String = text.chars().take(max - 1).collect();
+    cut.push('…');
+    cut
+}
+
+fn print_entry(entry: &WorklogEntry, verbose: bool, pinned: bool) -> Result<(), AppError> {
+    let id = entry.id.as_str();
+    let content = entry.content.as_str();
+
+    // Full timestamps in verbose mode, which is for reading entries in detail
+    let formatted_date = match (entry.recorded_at, relative_to().filter(|_| !verbose)) {
+        (Some(dt), Some(now)) => relative_time(dt.with_timezone(&Local), now),
+        (Some(dt), None) => dt.format("%Y-%m-%d %H:%M:%S UTC").to_string(),
+        (None, _) => "unknown".to_string(),
     };
 
-    // Get tags
-    let tags = entry
-        .get("tags")
-        .and_then(Value::as_array)
-        .map(|arr| {
-            arr.iter()
-                .filter_map(Value::as_str)
-                .collect::<Vec<_>>()
-                .join(", ")
-        })
-        .unwrap_or_default();
+    // Get tags; the visibility tag is shown as an icon instead
+    let tags = entry_tags(entry).collect::<Vec<_>>().join(", ");
+
+    let custom_fields =
+        (!entry.custom_fields.is_empty()).then(|| format_fields(&entry.custom_fields));
 
     // Get project info
     let project_info = entry
-        .get("project")
-        .and_then(|p| p.get("identifier"))
-        .and_then(Value::as_str)
-        .map(|id| format!(" [{id}]"))
+        .project
+        .as_ref()
+        .map(|p| format!(" [{}]", p.identifier))
         .unwrap_or_default();
 
     // Format the header with colors
-    let header = format!(
+    let mut header = format!(
         "{} ({}){}",
         formatted_date.bright_blue(),
         &id[..8].bright_black(),
         project_info.bright_green()
     );
+    if pinned {
+        header.push_str(&format!(" {PIN_MARKER}"));
+    }
+    if let Some(visibility) = entry_visibility(entry) {
+        header.push_str(&format!(" {}", visibility.icon()));
+    }
 
     // Print the entry
     println!("{header}");
 
     if verbose {
-        // In verbose mode, show full content
-        println!("  {}", content.white());
+        // In verbose mode, show full content with highlighted code blocks
+        println!("  {}", render_content(content));
         if !tags.is_empty() {
             println!("  Tags: {}", tags.bright_yellow());
         }
+        if let Some(fields) = &custom_fields {
+            println!("  Fields: {}", fields.bright_magenta());
+        }
+        if let Some(count) = entry.commits() {
+            println!("  Commits: {}", count.to_string().bright_cyan());
+        }
+        for relation in relations::describe(entry) {
+            println!("  {}", relation.bright_black());
+        }
         println!();
     } else {
         // In non-verbose mode, show truncated first line
@@ -274,8 +855,139 @@ fn print_entry(entry: &Value, verbose: bool) -> Result<(), AppError> {
         if !tags.is_empty() {
             println!("  Tags: {}", tags.bright_yellow());
         }
+        if let Some(fields) = &custom_fields {
+            println!("  Fields: {}", fields.bright_magenta());
+        }
         println!();
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use serde_json::json;
+
+    fn entry(mut value: Value) -> WorklogEntry {
+        value["id"] = json!("e1");
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn row() -> OnelineRow {
+        OnelineRow::from_entry(
+            &entry(json!({
+            "recorded_at": "2025-07-09T10:31:00Z",
+            "content": "\nfixed checkout bug\n\nRounding error in totals",
+            "tags": ["rust", "bug"],
+            "project": { "identifier": "web" }
+            })),
+            None,
+        )
+    }
+
+    #[test]
+    fn test_oneline_row_from_entry() {
+        let row = row();
+        assert_eq!(row.time.len(), 16);
+        assert_eq!(row.project, "[WEB]");
+        assert_eq!(row.content, "fixed checkout bug");
+        assert_eq!(row.tags, "#rust #bug");
+
+        let bare = OnelineRow::from_entry(&entry(json!({ "content": "Standup" })), None);
+        assert_eq!(bare.time, "unknown         ");
+        assert_eq!(bare.project, "");
+        assert_eq!(bare.tags, "");
+
+        // Visibility is shown as an icon, not a tag
+        let private = OnelineRow::from_entry(
+            &entry(json!({ "tags": ["hr", "visibility:private"] })),
+            None,
+        );
+        assert_eq!(private.tags, "#hr");
+
+        let recorded = Local.with_ymd_and_hms(2025, 7, 9, 10, 31, 0).unwrap();
+        let relative = OnelineRow::from_entry(
+            &entry(json!({ "recorded_at": recorded.to_rfc3339(), "content": "Standup" })),
+            Some(recorded + chrono::Duration::hours(5)),
+        );
+        assert_eq!(relative.time, "today 10:31");
+    }
+
+    #[test]
+    fn test_pager_filter() {
+        let entries = vec![
+            entry(json!({ "content": "Fixed the Checkout bug", "tags": ["bug", "web"] })),
+            entry(json!({ "content": "Checkout redesign review", "tags": ["web"] })),
+            entry(json!({ "content": "Standup", "tags": ["meeting", "visibility:team"] })),
+        ];
+        let matching = |filter: &PagerFilter| entries.iter().filter(|e| filter.matches(e)).count();
+
+        assert_eq!(matching(&PagerFilter::default()), 3);
+        let mut filter = PagerFilter {
+            text: Some("checkout".to_string()),
+            tag: None,
+        };
+        assert_eq!(matching(&filter), 2);
+        filter.tag = Some("BUG".to_string());
+        assert_eq!(matching(&filter), 1);
+        assert_eq!(filter.describe(), "\"checkout\" #BUG");
+
+        assert_eq!(tags_by_frequency(&entries), vec!["web", "bug", "meeting"]);
+    }
+
+    #[test]
+    fn test_oneline_fit_content() {
+        let row = row();
+        // Piped output keeps everything
+        assert_eq!(row.fit_content(5, None), "fixed checkout bug");
+        // 16 time + 6 project + 1 + 11 tags = 34 used, leaving 10 for content
+        assert_eq!(row.fit_content(5, Some(44)), "fixed che…");
+        assert_eq!(row.fit_content(5, Some(80)), "fixed checkout bug");
+        // Never shrinks content below the minimum
+        assert_eq!(row.fit_content(5, Some(20)), "fixed che…");
+    }
+
+    #[test]
+    fn test_truncate() {
+        assert_eq!(truncate("héllo", 5), "héllo");
+        assert_eq!(truncate("héllo world", 5), "héll…");
+        assert_eq!(truncate("hello", 0), "");
+    }
+
+    #[tokio::test]
+    async fn test_fetch_newest_follows_cursors_up_to_limit() {
+        let mut server = mockito::Server::new_async().await;
+        let mut api_client = ApiClient::new(&server.url());
+        api_client.set_access_token("test-token".to_string());
+
+        let page = |count: usize| -> Value {
+            json!({
+                "entries": (0..count).map(|i| json!({ "id": format!("e{i}") })).collect::<Vec<_>>(),
+                "meta": { "end_cursor": "c1" }
+            })
+        };
+        let first = server
+            .mock("GET", "/api/v1/worklog/entries")
+            .match_query(mockito::Matcher::Regex("^limit=100$".into()))
+            .with_status(200)
+            .with_body(page(100).to_string())
+            .create();
+        let second = server
+            .mock("GET", "/api/v1/worklog/entries")
+            .match_query(mockito::Matcher::Regex(
+                "^limit=20&starting_after=c1$".into(),
+            ))
+            .with_status(200)
+            .with_body(page(20).to_string())
+            .create();
+
+        let fetched = fetch_newest(&api_client, None, None, None, None, None, 120)
+            .await
+            .unwrap();
+        assert_eq!(fetched.len(), 120);
+        first.assert();
+        second.assert();
+    }
+}
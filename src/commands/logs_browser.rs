@@ -0,0 +1,719 @@
+use crate::api::client::ApiClient;
+use crate::api::endpoints::fetch_worklog_entries;
+use crate::api::models::WorklogEntry;
+use crate::auth::AuthService;
+use crate::commands::logs::{entry_tags, entry_visibility, tags_by_frequency};
+use crate::commands::project;
+use crate::errors::AppError;
+use crate::pins::Pins;
+use crate::utils::custom_fields::format_fields;
+use chrono::Local;
+use crossterm::cursor::{Hide, MoveTo, Show};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::style::{Attribute, Color, Print, ResetColor, SetAttribute, SetForegroundColor};
+use crossterm::terminal::{
+    self, disable_raw_mode, enable_raw_mode, Clear, ClearType, EnterAlternateScreen,
+    LeaveAlternateScreen,
+};
+use crossterm::{execute, queue};
+use std::io::{self, IsTerminal, Write};
+use std::time::Duration;
+
+/// Rows used by the title and status lines around the entry list.
+const CHROME_ROWS: usize = 2;
+
+/// Another page is fetched once the selection is this close to the last
+/// loaded entry, so scrolling rarely waits on the network.
+const PREFETCH_MARGIN: usize = 5;
+
+/// Opens the full-screen log browser for `acc logs --interactive`. Entries are
+/// fetched `page_size` at a time as the selection nears the end of what's
+/// loaded; search and tag filters apply to the loaded entries and keep more
+/// coming in while too few match.
+#[allow(clippy::too_many_arguments)]
+pub async fn execute(
+    auth_service: &mut AuthService,
+    project_identifier: Option<&str>,
+    tags: Option<&[String]>,
+    from: Option<&str>,
+    to: Option<&str>,
+    has_commits: Option<bool>,
+    page_size: u32,
+    pins: &Pins,
+) -> Result<(), AppError> {
+    if !io::stdout().is_terminal() || !io::stdin().is_terminal() {
+        return Err(AppError::Other(
+            "acc logs --interactive needs a terminal. Use --oneline or --json in scripts"
+                .to_string(),
+        ));
+    }
+
+    let project_ids = match project_identifier {
+        Some(identifier) => project::resolve_project_filter_ids(auth_service, identifier).await?,
+        None => None,
+    };
+    let mut pages = PageLoader {
+        api_client: auth_service.api_client(),
+        project_ids: project_ids.as_deref(),
+        tags,
+        from,
+        to,
+        has_commits,
+        page_size,
+        cursor: None,
+        exhausted: false,
+    };
+
+    let mut browser = Browser::new(pins);
+    browser.extend(pages.next().await?);
+    if browser.entries.is_empty() {
+        println!("No entries found.");
+        return Ok(());
+    }
+    browser.has_more = !pages.exhausted;
+
+    let screen = Screen::enter()?;
+    loop {
+        let (width, height) = terminal::size()?;
+        screen.draw(&browser.frame(width as usize, height as usize))?;
+
+        if browser.wants_more() {
+            // Keep loading while nothing is pressed
+            if !event::poll(Duration::ZERO)? {
+                browser.extend(pages.next().await?);
+                browser.has_more = !pages.exhausted;
+                continue;
+            }
+        }
+
+        match event::read()? {
+            Event::Key(key) if key.kind == KeyEventKind::Press => {
+                let list_rows = (height as usize).saturating_sub(CHROME_ROWS).max(1);
+                if browser.handle_key(key, list_rows) == Action::Quit {
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+    drop(screen);
+
+    Ok(())
+}
+
+/// Follows the pagination cursor of the entries endpoint.
+struct PageLoader<'a> {
+    api_client: &'a ApiClient,
+    project_ids: Option<&'a [String]>,
+    tags: Option<&'a [String]>,
+    from: Option<&'a str>,
+    to: Option<&'a str>,
+    has_commits: Option<bool>,
+    page_size: u32,
+    cursor: Option<String>,
+    exhausted: bool,
+}
+
+impl PageLoader<'_> {
+    async fn next(&mut self) -> Result<Vec<WorklogEntry>, AppError> {
+        let page = fetch_worklog_entries(
+            self.api_client,
+            self.project_ids,
+            self.tags,
+            self.from,
+            self.to,
+            self.has_commits,
+            self.page_size,
+            self.cursor.as_deref(),
+        )
+        .await?;
+
+        self.cursor = page.end_cursor().map(String::from);
+        self.exhausted = page.entries.is_empty() || self.cursor.is_none();
+        Ok(page.entries)
+    }
+}
+
+/// The alternate screen in raw mode, restored when dropped, even on errors.
+struct Screen;
+
+impl Screen {
+    fn enter() -> Result<Self, AppError> {
+        enable_raw_mode()
+            .map_err(|e| AppError::Other(format!("Failed to enable raw mode: {e}")))?;
+        execute!(io::stdout(), EnterAlternateScreen, Hide)?;
+        Ok(Screen)
+    }
+
+    fn draw(&self, frame: &[(RowStyle, String)]) -> io::Result<()> {
+        let mut stdout = io::stdout();
+        for (row, (style, text)) in frame.iter().enumerate() {
+            queue!(stdout, MoveTo(0, row as u16), Clear(ClearType::CurrentLine))?;
+            match style {
+                RowStyle::Title | RowStyle::Selected => {
+                    queue!(stdout, SetAttribute(Attribute::Reverse))?
+                }
+                RowStyle::Status => queue!(stdout, SetForegroundColor(Color::DarkGrey))?,
+                RowStyle::Heading => queue!(stdout, SetForegroundColor(Color::Blue))?,
+                RowStyle::Normal => {}
+            }
+            queue!(
+                stdout,
+                Print(text),
+                SetAttribute(Attribute::Reset),
+                ResetColor
+            )?;
+        }
+        queue!(stdout, Clear(ClearType::FromCursorDown))?;
+        stdout.flush()
+    }
+}
+
+impl Drop for Screen {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), Show, LeaveAlternateScreen);
+        let _ = disable_raw_mode();
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RowStyle {
+    Title,
+    Normal,
+    Selected,
+    Heading,
+    Status,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Action {
+    Continue,
+    Quit,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Mode {
+    List,
+    /// Typing a search; the list narrows with every key
+    Search,
+    /// Choosing tags to filter by, with the highlighted row
+    Tags {
+        selected: usize,
+    },
+    /// One entry in full, scrolled down by `scroll` lines
+    Detail {
+        scroll: usize,
+    },
+}
+
+/// Loaded entries, the filters on them and what's on screen. Kept apart from
+/// the terminal so key handling and layout can be tested.
+struct Browser<'a> {
+    pins: &'a Pins,
+    entries: Vec<WorklogEntry>,
+    has_more: bool,
+    search: String,
+    /// Every selected tag must be on an entry for it to show
+    tags: Vec<String>,
+    /// Indexes into `entries` passing the filters
+    visible: Vec<usize>,
+    /// Index into `visible`
+    selected: usize,
+    /// First row of `visible` on screen
+    offset: usize,
+    mode: Mode,
+}
+
+impl<'a> Browser<'a> {
+    fn new(pins: &'a Pins) -> Self {
+        Self {
+            pins,
+            entries: Vec::new(),
+            has_more: false,
+            search: String::new(),
+            tags: Vec::new(),
+            visible: Vec::new(),
+            selected: 0,
+            offset: 0,
+            mode: Mode::List,
+        }
+    }
+
+    fn extend(&mut self, page: Vec<WorklogEntry>) {
+        self.entries.extend(page);
+        self.refilter();
+    }
+
+    /// Whether the selection is close enough to the end to fetch another page.
+    fn wants_more(&self) -> bool {
+        self.has_more
+            && matches!(self.mode, Mode::List | Mode::Search)
+            && self.selected + PREFETCH_MARGIN >= self.visible.len()
+    }
+
+    fn matches(&self, entry: &WorklogEntry) -> bool {
+        let search = self.search.to_lowercase();
+        let text_matches = search.is_empty() || entry.content.to_lowercase().contains(&search);
+        text_matches
+            && self
+                .tags
+                .iter()
+                .all(|tag| entry_tags(entry).any(|t| t.eq_ignore_ascii_case(tag)))
+    }
+
+    /// Recomputes the visible entries, keeping the selected one when it still matches.
+    fn refilter(&mut self) {
+        let selected_entry = self.visible.get(self.selected).copied();
+        self.visible = (0..self.entries.len())
+            .filter(|&i| self.matches(&self.entries[i]))
+            .collect();
+        self.selected = selected_entry
+            .and_then(|entry| self.visible.iter().position(|&i| i == entry))
+            .unwrap_or(0);
+    }
+
+    fn handle_key(&mut self, key: KeyEvent, list_rows: usize) -> Action {
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            return Action::Quit;
+        }
+
+        match self.mode.clone() {
+            Mode::List => match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Action::Quit,
+                KeyCode::Up | KeyCode::Char('k') => self.select(self.selected.saturating_sub(1)),
+                KeyCode::Down | KeyCode::Char('j') => self.select(self.selected + 1),
+                KeyCode::PageUp => self.select(self.selected.saturating_sub(list_rows)),
+                KeyCode::PageDown => self.select(self.selected + list_rows),
+                KeyCode::Home | KeyCode::Char('g') => self.select(0),
+                KeyCode::End | KeyCode::Char('G') => self.select(usize::MAX),
+                KeyCode::Enter | KeyCode::Right | KeyCode::Char('l')
+                    if !self.visible.is_empty() =>
+                {
+                    self.mode = Mode::Detail { scroll: 0 };
+                }
+                KeyCode::Char('/') => self.mode = Mode::Search,
+                KeyCode::Char('t') => self.mode = Mode::Tags { selected: 0 },
+                KeyCode::Char('c') => {
+                    self.search.clear();
+                    self.tags.clear();
+                    self.refilter();
+                }
+                _ => {}
+            },
+            Mode::Search => match key.code {
+                KeyCode::Enter => self.mode = Mode::List,
+                KeyCode::Esc => {
+                    self.search.clear();
+                    self.refilter();
+                    self.mode = Mode::List;
+                }
+                KeyCode::Backspace => {
+                    self.search.pop();
+                    self.refilter();
+                }
+                KeyCode::Up => self.select(self.selected.saturating_sub(1)),
+                KeyCode::Down => self.select(self.selected + 1),
+                KeyCode::Char(c) => {
+                    self.search.push(c);
+                    self.refilter();
+                }
+                _ => {}
+            },
+            Mode::Tags { selected } => {
+                let available = tags_by_frequency(&self.entries);
+                match key.code {
+                    KeyCode::Esc | KeyCode::Enter | KeyCode::Char('t') | KeyCode::Char('q') => {
+                        self.mode = Mode::List
+                    }
+                    KeyCode::Up | KeyCode::Char('k') => {
+                        self.mode = Mode::Tags {
+                            selected: selected.saturating_sub(1),
+                        }
+                    }
+                    KeyCode::Down | KeyCode::Char('j') => {
+                        self.mode = Mode::Tags {
+                            selected: (selected + 1).min(available.len().saturating_sub(1)),
+                        }
+                    }
+                    KeyCode::Char(' ') => {
+                        if let Some(tag) = available.get(selected) {
+                            match self.tags.iter().position(|t| t.eq_ignore_ascii_case(tag)) {
+                                Some(i) => {
+                                    self.tags.remove(i);
+                                }
+                                None => self.tags.push(tag.clone()),
+                            }
+                            self.refilter();
+                        }
+                    }
+                    KeyCode::Char('c') => {
+                        self.tags.clear();
+                        self.refilter();
+                    }
+                    _ => {}
+                }
+            }
+            Mode::Detail { scroll } => match key.code {
+                KeyCode::Char('q') => return Action::Quit,
+                KeyCode::Esc | KeyCode::Left | KeyCode::Char('h') | KeyCode::Backspace => {
+                    self.mode = Mode::List
+                }
+                KeyCode::Up | KeyCode::Char('k') => {
+                    self.mode = Mode::Detail {
+                        scroll: scroll.saturating_sub(1),
+                    }
+                }
+                KeyCode::Down | KeyCode::Char('j') => {
+                    self.mode = Mode::Detail { scroll: scroll + 1 }
+                }
+                KeyCode::PageUp => {
+                    self.mode = Mode::Detail {
+                        scroll: scroll.saturating_sub(list_rows),
+                    }
+                }
+                KeyCode::PageDown | KeyCode::Char(' ') => {
+                    self.mode = Mode::Detail {
+                        scroll: scroll + list_rows,
+                    }
+                }
+                // Next and previous entry without going back to the list
+                KeyCode::Char('n') => {
+                    self.select(self.selected + 1);
+                    self.mode = Mode::Detail { scroll: 0 };
+                }
+                KeyCode::Char('p') => {
+                    self.select(self.selected.saturating_sub(1));
+                    self.mode = Mode::Detail { scroll: 0 };
+                }
+                _ => {}
+            },
+        }
+        Action::Continue
+    }
+
+    fn select(&mut self, index: usize) {
+        self.selected = index.min(self.visible.len().saturating_sub(1));
+    }
+
+    /// The screen as rows of text, at most `width` characters each.
+    fn frame(&mut self, width: usize, height: usize) -> Vec<(RowStyle, String)> {
+        let list_rows = height.saturating_sub(CHROME_ROWS).max(1);
+        let mut rows = vec![(RowStyle::Title, self.title())];
+
+        match &self.mode {
+            Mode::Detail { scroll } => {
+                let lines = match self.visible.get(self.selected) {
+                    Some(&i) => detail_lines(&self.entries[i], self.pins, width),
+                    None => Vec::new(),
+                };
+                let scroll = (*scroll).min(lines.len().saturating_sub(list_rows));
+                self.mode = Mode::Detail { scroll };
+                rows.extend(lines.into_iter().skip(scroll).take(list_rows));
+            }
+            Mode::Tags { selected } => {
+                let available = tags_by_frequency(&self.entries);
+                if available.is_empty() {
+                    rows.push((
+                        RowStyle::Normal,
+                        "No tags in the loaded entries".to_string(),
+                    ));
+                }
+                let first = selected.saturating_sub(list_rows.saturating_sub(1));
+                for (i, tag) in available.iter().enumerate().skip(first).take(list_rows) {
+                    let checked = self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag));
+                    let text = format!("[{}] #{tag}", if checked { "x" } else { " " });
+                    let style = if i == *selected {
+                        RowStyle::Selected
+                    } else {
+                        RowStyle::Normal
+                    };
+                    rows.push((style, text));
+                }
+            }
+            Mode::List | Mode::Search => {
+                // Keep the selection on screen
+                if self.selected < self.offset {
+                    self.offset = self.selected;
+                } else if self.selected >= self.offset + list_rows {
+                    self.offset = self.selected + 1 - list_rows;
+                }
+                if self.visible.is_empty() {
+                    let message = if self.has_more {
+                        "Nothing matches yet, loading more…"
+                    } else {
+                        "Nothing matches"
+                    };
+                    rows.push((RowStyle::Normal, message.to_string()));
+                }
+                for (row, &i) in self
+                    .visible
+                    .iter()
+                    .enumerate()
+                    .skip(self.offset)
+                    .take(list_rows)
+                {
+                    let style = if row == self.selected {
+                        RowStyle::Selected
+                    } else {
+                        RowStyle::Normal
+                    };
+                    rows.push((style, list_row(&self.entries[i], self.pins)));
+                }
+            }
+        }
+
+        while rows.len() < height.saturating_sub(1) {
+            rows.push((RowStyle::Normal, String::new()));
+        }
+        rows.push((RowStyle::Status, self.status()));
+        rows.into_iter()
+            .map(|(style, text)| (style, truncate(&text, width)))
+            .collect()
+    }
+
+    fn title(&self) -> String {
+        let mut title = format!(
+            " acc logs  {} of {} loaded{}",
+            self.visible.len(),
+            self.entries.len(),
+            if self.has_more { "+" } else { "" }
+        );
+        if !self.search.is_empty() {
+            title.push_str(&format!("  \"{}\"", self.search));
+        }
+        for tag in &self.tags {
+            title.push_str(&format!("  #{tag}"));
+        }
+        title
+    }
+
+    fn status(&self) -> String {
+        match self.mode {
+            Mode::List => {
+                "↑↓ move  enter open  / search  t tags  c clear filters  q quit".to_string()
+            }
+            Mode::Search => format!("/{}▏  enter keep  esc clear", self.search),
+            Mode::Tags { .. } => "↑↓ move  space toggle  c clear  enter done".to_string(),
+            Mode::Detail { .. } => "↑↓ scroll  n/p next/previous  esc back  q quit".to_string(),
+        }
+    }
+}
+
+/// `2025-07-09 08:15 [WEB] First line of the entry #tag #other`
+fn list_row(entry: &WorklogEntry, pins: &Pins) -> String {
+    let mut row = local_time(entry).unwrap_or_else(|| "unknown         ".to_string());
+    if let Some(project) = &entry.project {
+        row.push_str(&format!(" [{}]", project.identifier.to_uppercase()));
+    }
+    let first_line = entry
+        .content
+        .lines()
+        .map(str::trim)
+        .find(|l| !l.is_empty())
+        .unwrap_or("");
+    row.push(' ');
+    row.push_str(first_line);
+    for tag in entry_tags(entry) {
+        row.push_str(&format!(" #{tag}"));
+    }
+    if is_pinned(entry, pins) {
+        row.push_str(" (pinned)");
+    }
+    row
+}
+
+/// The entry in full: its metadata, then the content wrapped to `width`.
+fn detail_lines(entry: &WorklogEntry, pins: &Pins, width: usize) -> Vec<(RowStyle, String)> {
+    let mut lines = Vec::new();
+    let mut heading = |label: &str, value: String| {
+        lines.push((RowStyle::Heading, format!("{label:<11}{value}")));
+    };
+
+    heading("ID", entry.id.clone());
+    if let Some(time) = local_time(entry) {
+        heading("Recorded", time);
+    }
+    if let Some(project) = &entry.project {
+        let identifier = project.identifier.to_uppercase();
+        match &project.name {
+            Some(name) => heading("Project", format!("{name} ({identifier})")),
+            None => heading("Project", identifier),
+        }
+    }
+    let tags: Vec<String> = entry_tags(entry).map(|t| format!("#{t}")).collect();
+    if !tags.is_empty() {
+        heading("Tags", tags.join(" "));
+    }
+    if let Some(visibility) = entry_visibility(entry) {
+        heading("Visibility", visibility.as_str().to_string());
+    }
+    if !entry.custom_fields.is_empty() {
+        heading("Fields", format_fields(&entry.custom_fields));
+    }
+    if let Some(count) = entry.commits() {
+        heading("Commits", count.to_string());
+    }
+    if is_pinned(entry, pins) {
+        heading("Pinned", "yes".to_string());
+    }
+
+    lines.push((RowStyle::Normal, String::new()));
+    for line in entry.content.lines() {
+        lines.extend(
+            wrap(line, width.max(1))
+                .into_iter()
+                .map(|l| (RowStyle::Normal, l)),
+        );
+    }
+    lines
+}
+
+fn local_time(entry: &WorklogEntry) -> Option<String> {
+    entry.recorded_at.map(|dt| {
+        dt.with_timezone(&Local)
+            .format("%Y-%m-%d %H:%M")
+            .to_string()
+    })
+}
+
+fn is_pinned(entry: &WorklogEntry, pins: &Pins) -> bool {
+    pins.contains(&entry.id)
+}
+
+/// Breaks a line at spaces to fit `width` characters, splitting words longer
+/// than that.
+fn wrap(line: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    for word in line.split(' ') {
+        let needed = if current.is_empty() {
+            word.chars().count()
+        } else {
+            current.chars().count() + 1 + word.chars().count()
+        };
+        if needed > width && !current.is_empty() {
+            lines.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(word);
+        while current.chars().count() > width {
+            let rest: String = current.chars().skip(width).collect();
+            lines.push(current.chars().take(width).collect());
+            current = rest;
+        }
+    }
+    lines.push(current);
+    lines
+}
+
+fn truncate(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        return text.to_string();
+    }
+    let mut truncated: String = text.chars().take(width.saturating_sub(1)).collect();
+    truncated.push('…');
+    truncated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn entry(id: &str, content: &str, tags: &[&str]) -> WorklogEntry {
+        serde_json::from_value(json!({
+            "id": id,
+            "content": content,
+            "tags": tags,
+            "recorded_at": "2025-07-09T08:15:00Z",
+        }))
+        .unwrap()
+    }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn type_text(browser: &mut Browser, text: &str) {
+        for c in text.chars() {
+            browser.handle_key(key(KeyCode::Char(c)), 10);
+        }
+    }
+
+    #[test]
+    fn test_search_and_tag_toggles_narrow_the_list() {
+        let temp_dir = TempDir::new().unwrap();
+        let pins = Pins::load(temp_dir.path());
+        let mut browser = Browser::new(&pins);
+        browser.extend(vec![
+            entry("a", "Fixed the login bug", &["backend", "bug"]),
+            entry("b", "Reviewed the login PR", &["review"]),
+            entry("c", "Deployed billing", &["backend"]),
+        ]);
+        assert_eq!(browser.visible.len(), 3);
+
+        // Live search narrows as each key is typed
+        browser.handle_key(key(KeyCode::Char('/')), 10);
+        type_text(&mut browser, "log");
+        assert_eq!(browser.visible, [0, 1]);
+        browser.handle_key(key(KeyCode::Enter), 10);
+        assert_eq!(browser.mode, Mode::List);
+
+        // `backend` is the most used tag, so it's first in the picker
+        browser.handle_key(key(KeyCode::Char('t')), 10);
+        browser.handle_key(key(KeyCode::Char(' ')), 10);
+        assert_eq!(browser.tags, ["backend"]);
+        assert_eq!(browser.visible, [0]);
+        browser.handle_key(key(KeyCode::Char(' ')), 10);
+        assert!(browser.tags.is_empty());
+        browser.handle_key(key(KeyCode::Esc), 10);
+
+        browser.handle_key(key(KeyCode::Char('c')), 10);
+        assert_eq!(browser.visible.len(), 3);
+        assert_eq!(
+            browser.handle_key(key(KeyCode::Char('q')), 10),
+            Action::Quit
+        );
+    }
+
+    #[test]
+    fn test_frame_keeps_selection_on_screen_and_opens_detail() {
+        let temp_dir = TempDir::new().unwrap();
+        let pins = Pins::load(temp_dir.path());
+        let mut browser = Browser::new(&pins);
+        browser.extend(
+            (0..10)
+                .map(|i| entry(&format!("id-{i}"), &format!("Entry {i}"), &[]))
+                .collect(),
+        );
+
+        // Four rows: title, two entries, status
+        for _ in 0..5 {
+            browser.handle_key(key(KeyCode::Down), 2);
+        }
+        let frame = browser.frame(40, 4);
+        assert_eq!(frame.len(), 4);
+        assert_eq!(frame[0].1, " acc logs  10 of 10 loaded");
+        assert_eq!(frame[2].0, RowStyle::Selected);
+        assert!(frame[2].1.ends_with("Entry 5"));
+        assert!(frame.iter().all(|(_, text)| text.chars().count() <= 40));
+
+        browser.handle_key(key(KeyCode::Enter), 2);
+        let frame = browser.frame(40, 10);
+        assert_eq!(frame[1].1, "ID         id-5");
+        assert!(frame.iter().any(|(_, text)| text == "Entry 5"));
+        browser.handle_key(key(KeyCode::Char('n')), 8);
+        let frame = browser.frame(40, 10);
+        assert_eq!(frame[1].1, "ID         id-6");
+    }
+
+    #[test]
+    fn test_wrap() {
+        assert_eq!(wrap("one two three", 7), ["one two", "three"]);
+        assert_eq!(wrap("abcdefghij", 4), ["abcd", "efgh", "ij"]);
+        assert_eq!(wrap("", 4), [""]);
+    }
+}
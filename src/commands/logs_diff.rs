@@ -0,0 +1,295 @@
+use crate::auth::AuthService;
+use crate::commands::logs::fetch_all_entries;
+use crate::commands::project;
+use crate::errors::AppError;
+use crate::utils::period::{parse_period, Period};
+use chrono::{DateTime, Local, NaiveDate};
+use colored::*;
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+
+const NO_PROJECT: &str = "(no project)";
+const TOP_TAG_CHANGES: usize = 5;
+
+/// Compares raw entry statistics between two periods: entry counts, active days,
+/// tags gained or lost, and how the project mix shifted. No AI involved.
+pub async fn execute(
+    auth_service: &mut AuthService,
+    project_identifier: Option<&str>,
+    from: &str,
+    to: &str,
+) -> Result<(), AppError> {
+    let today = Local::now().date_naive();
+    let before = parse_period(from, today).map_err(AppError::ParseError)?;
+    let after = parse_period(to, today).map_err(AppError::ParseError)?;
+
+    let project_ids = match project_identifier {
+        Some(identifier) => project::resolve_project_filter_ids(auth_service, identifier).await?,
+        None => None,
+    };
+
+    let before_stats = PeriodStats::from_entries(
+        &fetch_period(auth_service, project_ids.as_deref(), &before).await?,
+    );
+    let after_stats = PeriodStats::from_entries(
+        &fetch_period(auth_service, project_ids.as_deref(), &after).await?,
+    );
+
+    print_diff(&before, &before_stats, &after, &after_stats);
+    Ok(())
+}
+
+async fn fetch_period(
+    auth_service: &mut AuthService,
+    project_ids: Option<&[String]>,
+    period: &Period,
+) -> Result<Vec<Value>, AppError> {
+    fetch_all_entries(
+        auth_service.api_client(),
+        project_ids,
+        None,
+        Some(&period.start.format("%Y-%m-%d").to_string()),
+        Some(&period.end.format("%Y-%m-%d").to_string()),
+    )
+    .await
+}
+
+/// Aggregate numbers for one period.
+#[derive(Debug, Default, PartialEq)]
+struct PeriodStats {
+    entries: usize,
+    active_days: usize,
+    tags: BTreeMap<String, usize>,
+    projects: BTreeMap<String, usize>,
+}
+
+impl PeriodStats {
+    fn from_entries(entries: &[Value]) -> Self {
+        let mut stats = PeriodStats {
+            entries: entries.len(),
+            ..Default::default()
+        };
+        let mut days: BTreeSet<NaiveDate> = BTreeSet::new();
+
+        for entry in entries {
+            if let Some(day) = entry
+                .get("recorded_at")
+                .and_then(Value::as_str)
+                .and_then(|s| s.parse::<DateTime<Local>>().ok())
+            {
+                days.insert(day.date_naive());
+            }
+
+            for tag in entry
+                .get("tags")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(Value::as_str)
+            {
+                *stats.tags.entry(tag.to_lowercase()).or_default() += 1;
+            }
+
+            let project = entry
+                .get("project")
+                .and_then(|p| p.get("identifier"))
+                .and_then(Value::as_str)
+                .map(str::to_uppercase)
+                .unwrap_or_else(|| NO_PROJECT.to_string());
+            *stats.projects.entry(project).or_default() += 1;
+        }
+
+        stats.active_days = days.len();
+        stats
+    }
+
+    /// Share of entries per project, in percent.
+    fn project_share(&self, project: &str) -> f64 {
+        if self.entries == 0 {
+            return 0.0;
+        }
+        *self.projects.get(project).unwrap_or(&0) as f64 * 100.0 / self.entries as f64
+    }
+}
+
+/// Tags only used in the later period, tags no longer used, and the largest
+/// count changes among tags used in both, as (tag, before, after).
+#[allow(clippy::type_complexity)]
+fn tag_changes(
+    before: &PeriodStats,
+    after: &PeriodStats,
+) -> (
+    Vec<(String, usize)>,
+    Vec<(String, usize)>,
+    Vec<(String, usize, usize)>,
+) {
+    let by_count_desc =
+        |a: &(String, usize), b: &(String, usize)| b.1.cmp(&a.1).then(a.0.cmp(&b.0));
+
+    let mut gained: Vec<(String, usize)> = after
+        .tags
+        .iter()
+        .filter(|(tag, _)| !before.tags.contains_key(*tag))
+        .map(|(tag, count)| (tag.clone(), *count))
+        .collect();
+    gained.sort_by(by_count_desc);
+
+    let mut lost: Vec<(String, usize)> = before
+        .tags
+        .iter()
+        .filter(|(tag, _)| !after.tags.contains_key(*tag))
+        .map(|(tag, count)| (tag.clone(), *count))
+        .collect();
+    lost.sort_by(by_count_desc);
+
+    let mut changed: Vec<(String, usize, usize)> = before
+        .tags
+        .iter()
+        .filter_map(|(tag, b)| {
+            let a = *after.tags.get(tag)?;
+            (a != *b).then(|| (tag.clone(), *b, a))
+        })
+        .collect();
+    changed.sort_by(|x, y| {
+        let dx = (x.2 as i64 - x.1 as i64).abs();
+        let dy = (y.2 as i64 - y.1 as i64).abs();
+        dy.cmp(&dx).then(x.0.cmp(&y.0))
+    });
+    changed.truncate(TOP_TAG_CHANGES);
+
+    (gained, lost, changed)
+}
+
+/// Signed change, right-aligned before colouring so escape codes don't skew columns.
+fn format_delta(before: f64, after: f64, decimals: usize) -> String {
+    let delta = after - before;
+    let text = format!("{:>10}", format!("{delta:+.decimals$}"));
+    if delta > 0.0 {
+        text.bright_green().to_string()
+    } else if delta < 0.0 {
+        text.bright_red().to_string()
+    } else {
+        text.bright_black().to_string()
+    }
+}
+
+fn format_tag_counts(tags: &[(String, usize)]) -> String {
+    if tags.is_empty() {
+        return "none".bright_black().to_string();
+    }
+    tags.iter()
+        .map(|(tag, count)| format!("#{tag} ({count})"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn print_diff(before: &Period, b: &PeriodStats, after: &Period, a: &PeriodStats) {
+    println!(
+        "{}",
+        format!(
+            "Comparing {} ({} – {}) → {} ({} – {})",
+            before.label, before.start, before.end, after.label, after.start, after.end
+        )
+        .bright_blue()
+    );
+    println!();
+
+    let per_day =
+        |stats: &PeriodStats, period: &Period| stats.entries as f64 / period.days() as f64;
+
+    println!("{:<18}{:>8}{:>8}{:>10}", "", "Before", "After", "Change");
+    println!(
+        "{:<18}{:>8}{:>8}{}",
+        "Entries",
+        b.entries,
+        a.entries,
+        format_delta(b.entries as f64, a.entries as f64, 0)
+    );
+    println!(
+        "{:<18}{:>8}{:>8}{}",
+        "Active days",
+        b.active_days,
+        a.active_days,
+        format_delta(b.active_days as f64, a.active_days as f64, 0)
+    );
+    println!(
+        "{:<18}{:>8.1}{:>8.1}{}",
+        "Entries per day",
+        per_day(b, before),
+        per_day(a, after),
+        format_delta(per_day(b, before), per_day(a, after), 1)
+    );
+    println!();
+
+    let (gained, lost, changed) = tag_changes(b, a);
+    println!("{} {}", "Tags gained:".bold(), format_tag_counts(&gained));
+    println!("{} {}", "Tags lost:  ".bold(), format_tag_counts(&lost));
+    if !changed.is_empty() {
+        println!("{}", "Tag changes:".bold());
+        for (tag, before_count, after_count) in &changed {
+            println!(
+                "  #{tag:<16}{before_count:>4} → {after_count:<4}{}",
+                format_delta(*before_count as f64, *after_count as f64, 0)
+            );
+        }
+    }
+    println!();
+
+    let projects: BTreeSet<&String> = b.projects.keys().chain(a.projects.keys()).collect();
+    if !projects.is_empty() {
+        println!("{}", "Project mix:".bold());
+        for project in projects {
+            let before_share = b.project_share(project);
+            let after_share = a.project_share(project);
+            println!(
+                "  {project:<14}{before_share:>5.0}% → {after_share:>3.0}%{} pts",
+                format_delta(before_share, after_share, 0)
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn entries() -> Vec<Value> {
+        vec![
+            json!({ "recorded_at": "2025-07-01T10:00:00Z", "tags": ["Rust", "billing"], "project": { "identifier": "web" } }),
+            json!({ "recorded_at": "2025-07-01T15:00:00Z", "tags": ["rust"], "project": { "identifier": "web" } }),
+            json!({ "recorded_at": "2025-07-03T10:00:00Z", "tags": ["meeting"] }),
+        ]
+    }
+
+    #[test]
+    fn test_period_stats_from_entries() {
+        let stats = PeriodStats::from_entries(&entries());
+
+        assert_eq!(stats.entries, 3);
+        assert!(stats.active_days >= 2);
+        assert_eq!(stats.tags.get("rust"), Some(&2));
+        assert_eq!(stats.projects.get("WEB"), Some(&2));
+        assert_eq!(stats.projects.get(NO_PROJECT), Some(&1));
+        assert!((stats.project_share("WEB") - 66.666).abs() < 0.01);
+        assert_eq!(PeriodStats::default().project_share("WEB"), 0.0);
+    }
+
+    #[test]
+    fn test_tag_changes() {
+        let before = PeriodStats::from_entries(&entries());
+        let after = PeriodStats::from_entries(&[
+            json!({ "tags": ["rust", "infra"] }),
+            json!({ "tags": ["infra", "billing"] }),
+            json!({ "tags": ["billing"] }),
+        ]);
+
+        let (gained, lost, changed) = tag_changes(&before, &after);
+        assert_eq!(gained, vec![("infra".to_string(), 2)]);
+        assert_eq!(lost, vec![("meeting".to_string(), 1)]);
+        assert_eq!(
+            changed,
+            vec![("billing".to_string(), 1, 2), ("rust".to_string(), 2, 1)]
+        );
+    }
+}
@@ -0,0 +1,155 @@
+use crate::api::endpoints::fetch_entry_revisions;
+use crate::auth::AuthService;
+use crate::commands::log_amend::entry_metadata;
+use crate::commands::logs::find_entry;
+use crate::errors::AppError;
+use crate::utils::editor::render_front_matter;
+use chrono::{DateTime, Local};
+use colored::*;
+use serde_json::Value;
+use similar::TextDiff;
+
+/// Lines of unchanged text shown around each change.
+const DIFF_CONTEXT: usize = 3;
+
+/// Shows how an entry changed over time: when each revision was made, and a
+/// unified diff of its tags, project, date and content against the one before.
+pub async fn execute(auth_service: &mut AuthService, id_or_prefix: &str) -> Result<(), AppError> {
+    let entry = find_entry(auth_service.api_client(), id_or_prefix).await?;
+    let id = entry.id.clone();
+    let response = fetch_entry_revisions(auth_service.api_client(), &id).await?;
+    // Revisions are snapshots of the entry's JSON, so the current one is compared as JSON too
+    let versions = versions(&response, &Value::from(entry));
+
+    println!("{} {id}", "History of entry".bold());
+    let mut previous: Option<String> = None;
+    for (number, version) in versions.iter().enumerate() {
+        let document = render_version(version);
+        let when = revised_at(version)
+            .map(|d| d.format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_else(|| "unknown time".to_string());
+        let label = format!("v{}", number + 1);
+
+        match &previous {
+            None => {
+                println!(
+                    "\n{} {}",
+                    label.yellow().bold(),
+                    format!("created {when}").bright_black()
+                );
+                for line in document.lines() {
+                    println!("  {line}");
+                }
+            }
+            Some(before) => {
+                println!(
+                    "\n{} {}",
+                    label.yellow().bold(),
+                    format!("edited {when}").bright_black()
+                );
+                let prior = format!("v{number}");
+                print_diff(&unified_diff(before, &document, &prior, &label));
+            }
+        }
+        previous = Some(document);
+    }
+
+    if versions.len() < 2 {
+        println!("\nThis entry hasn't been edited.");
+    }
+    Ok(())
+}
+
+/// Every version of the entry, oldest first: the stored revisions, followed
+/// by the current entry unless the last revision already is it.
+fn versions(response: &Value, entry: &Value) -> Vec<Value> {
+    let mut versions: Vec<Value> = response
+        .get("revisions")
+        .or_else(|| response.get("data"))
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+    let current_shown = versions
+        .last()
+        .is_some_and(|last| render_version(last) == render_version(entry));
+    if !current_shown {
+        versions.push(entry.clone());
+    }
+    versions
+}
+
+/// A version as the editor shows it: front-matter, then the content.
+fn render_version(version: &Value) -> String {
+    let content = version.get("content").and_then(Value::as_str).unwrap_or("");
+    format!(
+        "{}\n{}\n",
+        render_front_matter(&entry_metadata(version)),
+        content.trim_end()
+    )
+}
+
+fn revised_at(version: &Value) -> Option<DateTime<Local>> {
+    ["edited_at", "updated_at", "inserted_at", "created_at"]
+        .iter()
+        .find_map(|field| version.get(*field).and_then(Value::as_str))
+        .and_then(|s| s.parse::<DateTime<Local>>().ok())
+}
+
+fn unified_diff(before: &str, after: &str, before_label: &str, after_label: &str) -> String {
+    TextDiff::from_lines(before, after)
+        .unified_diff()
+        .context_radius(DIFF_CONTEXT)
+        .header(before_label, after_label)
+        .to_string()
+}
+
+fn print_diff(diff: &str) {
+    if diff.is_empty() {
+        println!("  {}", "(no visible changes)".bright_black());
+        return;
+    }
+    for line in diff.lines() {
+        let line = if line.starts_with("+++") || line.starts_with("---") {
+            line.bold()
+        } else if line.starts_with("@@") {
+            line.cyan()
+        } else if line.starts_with('+') {
+            line.green()
+        } else if line.starts_with('-') {
+            line.red()
+        } else {
+            line.normal()
+        };
+        println!("  {line}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_versions_append_current_entry_once() {
+        let first = json!({ "content": "Fixd the build", "tags": ["ci"] });
+        let current = json!({ "content": "Fixed the build", "tags": ["ci", "infra"] });
+
+        let previous_only = json!({ "revisions": [first] });
+        assert_eq!(versions(&previous_only, &current).len(), 2);
+
+        let including_current = json!({ "revisions": [first, current] });
+        assert_eq!(versions(&including_current, &current).len(), 2);
+    }
+
+    #[test]
+    fn test_unified_diff_of_versions() {
+        let before = render_version(&json!({ "content": "Fixd the build", "tags": ["ci"] }));
+        let after =
+            render_version(&json!({ "content": "Fixed the build", "tags": ["ci", "infra"] }));
+
+        let diff = unified_diff(&before, &after, "v1", "v2");
+        assert!(diff.starts_with("--- v1\n+++ v2\n"));
+        assert!(diff.contains("\n-tags: [ci]\n+tags: [ci, infra]\n"));
+        assert!(diff.contains("\n-Fixd the build\n+Fixed the build\n"));
+    }
+}
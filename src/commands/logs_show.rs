@@ -0,0 +1,130 @@
+use crate::api::endpoints::fetch_worklog_entry;
+use crate::api::errors::ApiError;
+use crate::api::models::WorklogEntry;
+use crate::auth::AuthService;
+use crate::commands::logs::{find_entry, print_entries, EntryFormat};
+use crate::errors::AppError;
+use crate::pins::Pins;
+use crate::utils::relations::{related_ids, RelationKind};
+use colored::*;
+
+/// Longest follow-up thread walked back from an entry.
+const MAX_THREAD_LENGTH: usize = 50;
+
+/// Shows one entry in full, then the thread of entries it follows up on
+/// (oldest first) and the entries it relates to.
+pub async fn execute(auth_service: &mut AuthService, id_or_prefix: &str) -> Result<(), AppError> {
+    let pins = Pins::load(auth_service.state_dir());
+    let entry = find_entry(auth_service.api_client(), id_or_prefix).await?;
+    print_entries(std::slice::from_ref(&entry), EntryFormat::Verbose, &pins)?;
+
+    let earlier = thread(auth_service, &entry).await?;
+    if !earlier.is_empty() {
+        println!("{}", "Thread (oldest first):".bold());
+        let mut thread: Vec<WorklogEntry> = earlier.into_iter().rev().collect();
+        thread.push(entry.clone());
+        print_entries(&thread, EntryFormat::Oneline, &pins)?;
+        println!();
+    }
+
+    let related = fetch_all(auth_service, &related_ids(&entry, RelationKind::RelatesTo)).await?;
+    if !related.is_empty() {
+        println!("{}", "Related:".bold());
+        print_entries(&related, EntryFormat::Oneline, &pins)?;
+    }
+
+    Ok(())
+}
+
+/// The entries `entry` follows up on, most recent first, stopping at the
+/// start of the thread, a deleted entry or a cycle.
+async fn thread(
+    auth_service: &mut AuthService,
+    entry: &WorklogEntry,
+) -> Result<Vec<WorklogEntry>, AppError> {
+    let mut seen = vec![entry.id.clone()];
+    let mut earlier = Vec::new();
+    let mut next = related_ids(entry, RelationKind::FollowsUp)
+        .into_iter()
+        .next();
+
+    while let Some(id) = next.take() {
+        if seen.contains(&id) || earlier.len() >= MAX_THREAD_LENGTH {
+            break;
+        }
+        let Some(previous) = fetch_existing(auth_service, &id).await? else {
+            break;
+        };
+        next = related_ids(&previous, RelationKind::FollowsUp)
+            .into_iter()
+            .next();
+        seen.push(id);
+        earlier.push(previous);
+    }
+
+    Ok(earlier)
+}
+
+async fn fetch_all(
+    auth_service: &mut AuthService,
+    ids: &[String],
+) -> Result<Vec<WorklogEntry>, AppError> {
+    let mut entries = Vec::new();
+    for id in ids {
+        entries.extend(fetch_existing(auth_service, id).await?);
+    }
+    Ok(entries)
+}
+
+/// Fetches a linked entry, warning instead of failing when it's been deleted.
+async fn fetch_existing(
+    auth_service: &mut AuthService,
+    id: &str,
+) -> Result<Option<WorklogEntry>, AppError> {
+    match fetch_worklog_entry(auth_service.api_client(), id).await {
+        Ok(entry) => Ok(Some(entry)),
+        Err(ApiError::NotFound(_)) => {
+            eprintln!("⚠️  Warning: Linked entry {id} no longer exists");
+            Ok(None)
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::Server;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_thread_walks_back_to_the_start() {
+        let mut server = Server::new_async().await;
+        let mut auth = AuthService::new(server.url(), std::env::temp_dir(), "test-profile");
+        auth.save_access_token("test-token").unwrap();
+
+        let follows = |id: &str| json!([{ "relation": "follows_up", "related_entry_id": id }]);
+        let _two = server
+            .mock("GET", "/api/v1/worklog/entries/entry-2")
+            .with_status(200)
+            .with_body(json!({ "id": "entry-2", "relations": follows("entry-1") }).to_string())
+            .create();
+        // entry-1 points back at entry-3, which would loop forever
+        let _one = server
+            .mock("GET", "/api/v1/worklog/entries/entry-1")
+            .with_status(200)
+            .with_body(json!({ "id": "entry-1", "relations": follows("entry-3") }).to_string())
+            .create();
+
+        let entry: WorklogEntry =
+            serde_json::from_value(json!({ "id": "entry-3", "relations": follows("entry-2") }))
+                .unwrap();
+        let ids: Vec<String> = thread(&mut auth, &entry)
+            .await
+            .unwrap()
+            .iter()
+            .map(|e| e.id.clone())
+            .collect();
+        assert_eq!(ids, ["entry-2", "entry-1"]);
+    }
+}
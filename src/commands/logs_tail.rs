@@ -0,0 +1,136 @@
+use crate::api::endpoints::fetch_worklog_entries;
+use crate::api::models::WorklogEntry;
+use crate::auth::AuthService;
+use crate::commands::logs::{print_entries, EntryFormat};
+use crate::commands::project;
+use crate::entry_cache::EntryCache;
+use crate::errors::AppError;
+use crate::pins::Pins;
+use chrono::Local;
+use colored::*;
+
+/// Prints the last `count` entries, oldest first so the latest ends up right
+/// above the prompt. Never pages or prompts, and falls back to the entries
+/// cached by the previous run when the server can't be reached.
+pub async fn execute(
+    auth_service: &mut AuthService,
+    project_identifier: Option<&str>,
+    count: u32,
+    format: EntryFormat,
+) -> Result<(), AppError> {
+    let pins = Pins::load(auth_service.state_dir());
+    let mut cache = EntryCache::load(auth_service.state_dir());
+
+    let entries = match fetch_recent(auth_service, project_identifier, count).await {
+        Ok(entries) => {
+            cache.store(project_identifier, entries.clone());
+            if let Err(e) = cache.save() {
+                eprintln!("⚠️  Warning: Could not save entry cache: {e}");
+            }
+            entries
+        }
+        Err(e) if e.is_offline() => {
+            let Some(cached) = cache.get(project_identifier) else {
+                return Err(e);
+            };
+            let fetched_at = cached
+                .fetched_at
+                .map(|t| t.with_timezone(&Local).format("%Y-%m-%d %H:%M").to_string())
+                .unwrap_or_else(|| "an earlier run".to_string());
+            eprintln!(
+                "{}",
+                format!("Offline: showing entries cached at {fetched_at}").bright_black()
+            );
+            cached
+                .entries
+                .iter()
+                .take(count as usize)
+                .cloned()
+                .collect()
+        }
+        Err(e) => return Err(e),
+    };
+
+    if entries.is_empty() {
+        println!("No entries found.");
+        return Ok(());
+    }
+
+    let oldest_first: Vec<WorklogEntry> = entries.into_iter().rev().collect();
+    print_entries(&oldest_first, format, &pins)
+}
+
+async fn fetch_recent(
+    auth_service: &mut AuthService,
+    project_identifier: Option<&str>,
+    count: u32,
+) -> Result<Vec<WorklogEntry>, AppError> {
+    auth_service.ensure_authenticated().await?;
+
+    let project_ids = match project_identifier {
+        Some(identifier) => project::resolve_project_filter_ids(auth_service, identifier).await?,
+        None => None,
+    };
+    let page = fetch_worklog_entries(
+        auth_service.api_client(),
+        project_ids.as_deref(),
+        None,
+        None,
+        None,
+        None,
+        count,
+        None,
+    )
+    .await?;
+
+    Ok(page.entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::Server;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_offline_falls_back_to_cache() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut server = Server::new_async().await;
+        let _token = server
+            .mock("POST", "/auth/token_info")
+            .with_status(200)
+            .with_body(r#"{"active":true,"scope":"","client_id":"cli","exp":0}"#)
+            .create();
+        let _entries = server
+            .mock("GET", "/api/v1/worklog/entries")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_body(r#"{"entries":[{"id":"entry-2-0000"},{"id":"entry-1-0000"}]}"#)
+            .create();
+
+        let mut auth_service =
+            AuthService::new(server.url(), temp_dir.path().to_path_buf(), "test-profile");
+        auth_service.save_access_token("dummy-token").unwrap();
+        execute(&mut auth_service, None, 2, EntryFormat::Oneline)
+            .await
+            .unwrap();
+        let cached = EntryCache::load(auth_service.state_dir());
+        assert_eq!(cached.get(None).unwrap().entries.len(), 2);
+
+        // Nothing listens on port 9, so the request can't be answered
+        let mut offline = AuthService::new(
+            "http://127.0.0.1:9".into(),
+            temp_dir.path().to_path_buf(),
+            "test-profile",
+        );
+        offline.save_access_token("dummy-token").unwrap();
+        execute(&mut offline, None, 1, EntryFormat::Oneline)
+            .await
+            .unwrap();
+
+        // Only what was cached for that project can be shown
+        assert!(execute(&mut offline, Some("web"), 1, EntryFormat::Oneline)
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,150 @@
+use crate::api::endpoints::create_worklog_entry;
+use crate::auth::AuthService;
+use crate::commands::log_amend::entry_metadata;
+use crate::errors::AppError;
+use crate::trash::{Trash, TrashedEntry};
+use chrono::{Duration, Local};
+use colored::*;
+use serde_json::Value;
+use std::path::Path;
+
+/// Lists entries in the local trash, most recently deleted first, with the
+/// date each one can be restored until.
+pub fn list(state_dir: &Path, retention: Duration) -> Result<(), AppError> {
+    let mut trash = Trash::load(state_dir);
+    if trash.purge_expired(retention) > 0 {
+        trash.save()?;
+    }
+    if trash.entries().is_empty() {
+        println!("The trash is empty.");
+        return Ok(());
+    }
+
+    for trashed in trash.entries() {
+        let deleted = trashed.deleted_at.with_timezone(&Local);
+        let until = deleted + retention;
+        println!(
+            "{}  {}",
+            short_id(trashed.id()).yellow(),
+            format!(
+                "deleted {}, restorable until {}",
+                deleted.format("%Y-%m-%d %H:%M"),
+                until.format("%Y-%m-%d")
+            )
+            .bright_black()
+        );
+        println!("          {}", describe(trashed));
+    }
+    Ok(())
+}
+
+/// Recreates a trashed entry with its content, date, tags, project and custom
+/// fields. The API gives it a new ID; it leaves the trash once created.
+pub async fn restore(
+    auth_service: &mut AuthService,
+    id_or_prefix: &str,
+    retention: Duration,
+) -> Result<(), AppError> {
+    let mut trash = Trash::load(auth_service.state_dir());
+    trash.purge_expired(retention);
+    let trashed = trash.find(id_or_prefix).map_err(AppError::Other)?.clone();
+    let entry = &trashed.entry;
+
+    let content = entry.get("content").and_then(Value::as_str).unwrap_or("");
+    let recorded_at = entry
+        .get("recorded_at")
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .unwrap_or_else(|| trashed.deleted_at.to_rfc3339());
+    let project_id = entry
+        .get("project")
+        .and_then(|p| p.get("id"))
+        .or_else(|| entry.get("project_id"))
+        .and_then(Value::as_str);
+    let custom_fields = entry.get("custom_fields").and_then(Value::as_object);
+
+    let created = create_worklog_entry(
+        auth_service.api_client(),
+        content,
+        &recorded_at,
+        &entry_metadata(entry).tags,
+        project_id,
+        custom_fields,
+    )
+    .await?;
+    let new_id = created.id;
+
+    trash.remove(trashed.id());
+    trash.save()?;
+    println!("♻️  Restored entry {} as {new_id}", short_id(trashed.id()));
+    Ok(())
+}
+
+/// First line of the content, then the project and tags.
+fn describe(trashed: &TrashedEntry) -> String {
+    let metadata = entry_metadata(&trashed.entry);
+    let mut line = trashed
+        .entry
+        .get("content")
+        .and_then(Value::as_str)
+        .and_then(|c| c.lines().find(|l| !l.trim().is_empty()))
+        .unwrap_or("")
+        .trim()
+        .to_string();
+    if let Some(project) = metadata.project {
+        line.push_str(&format!(" {}", project.to_uppercase().bright_green()));
+    }
+    if !metadata.tags.is_empty() {
+        let tags: Vec<String> = metadata.tags.iter().map(|t| format!("#{t}")).collect();
+        line.push_str(&format!(" {}", tags.join(" ").bright_yellow()));
+    }
+    line
+}
+
+fn short_id(id: &str) -> &str {
+    id.get(..8).unwrap_or(id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::{Matcher, Server};
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_restore_recreates_entry_and_empties_trash() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut server = Server::new_async().await;
+        let mut auth = AuthService::new(server.url(), temp_dir.path().to_path_buf(), "test");
+        auth.save_access_token("test-token").unwrap();
+
+        let mut trash = Trash::load(auth.state_dir());
+        trash.put(json!({
+            "id": "entry-1",
+            "content": "Shipped the importer",
+            "recorded_at": "2025-07-09T10:00:00Z",
+            "tags": ["release"],
+            "project": { "id": "proj-1", "identifier": "web" }
+        }));
+        trash.save().unwrap();
+
+        let create = server
+            .mock("POST", "/api/v1/worklog/entries")
+            .match_body(Matcher::PartialJson(json!({
+                "content": "Shipped the importer",
+                "recorded_at": "2025-07-09T10:00:00Z",
+                "tags": ["release"],
+                "project_id": "proj-1"
+            })))
+            .with_status(201)
+            .with_body(r#"{"id":"entry-2"}"#)
+            .create();
+
+        restore(&mut auth, "entry", Duration::days(30))
+            .await
+            .unwrap();
+        create.assert();
+        assert!(Trash::load(auth.state_dir()).entries().is_empty());
+    }
+}
@@ -1,9 +1,44 @@
+pub mod api;
+pub mod audit;
+pub mod cache;
 pub mod capture;
+pub mod capture_calendar;
+pub mod capture_reflog;
+pub mod completions;
+pub mod config_route;
+pub mod daemon;
+pub mod delete;
+pub mod digest;
+pub mod digest_schedule;
+pub mod edit;
+pub mod export;
+pub mod git_hooks;
+pub mod goals;
 pub mod init;
+pub mod init_scan;
 pub mod log;
+pub mod log_amend;
+pub mod log_porcelain;
 pub mod login;
 pub mod logout;
 pub mod logs;
+pub mod logs_browser;
+pub mod logs_diff;
+pub mod logs_history;
+pub mod logs_show;
+pub mod logs_tail;
+pub mod logs_trash;
+pub mod pin;
+pub mod profile;
 pub mod project;
+pub mod prompt;
 pub mod recap;
+pub mod repo;
+pub mod review;
+pub mod standup;
 pub mod status;
+pub mod suggest;
+pub mod sync;
+pub mod tags;
+pub mod today;
+pub mod todo;
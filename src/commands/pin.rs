@@ -0,0 +1,128 @@
+use crate::api::endpoints::fetch_worklog_entry;
+use crate::api::errors::ApiError;
+use crate::api::models::WorklogEntry;
+use crate::auth::AuthService;
+use crate::commands::logs::{find_entry, page_through, EntryFormat};
+use crate::errors::AppError;
+use crate::pins::Pins;
+
+/// Pins an entry (by full ID or unique prefix) so `acc logs --pinned` lists it.
+pub async fn pin(auth_service: &mut AuthService, id_or_prefix: &str) -> Result<(), AppError> {
+    let entry = find_entry(auth_service.api_client(), id_or_prefix).await?;
+    let id = entry.id.as_str();
+
+    let mut pins = Pins::load(auth_service.state_dir());
+    if pins.pin(id) {
+        pins.save()?;
+        println!("📌 Pinned entry {}", &id[..8.min(id.len())]);
+    } else {
+        println!("Entry {} is already pinned", &id[..8.min(id.len())]);
+    }
+    Ok(())
+}
+
+/// Unpins an entry. Works offline, and for entries that no longer exist.
+pub fn unpin(auth_service: &AuthService, id_or_prefix: &str) -> Result<(), AppError> {
+    let mut pins = Pins::load(auth_service.state_dir());
+    let id = pins.unpin(id_or_prefix).map_err(AppError::Other)?;
+    pins.save()?;
+    println!("Unpinned entry {}", &id[..8.min(id.len())]);
+    Ok(())
+}
+
+/// Lists pinned entries by recorded time, newest first unless `oldest_first`.
+pub async fn list(
+    auth_service: &mut AuthService,
+    limit: u32,
+    format: EntryFormat,
+    oldest_first: bool,
+) -> Result<(), AppError> {
+    let pins = Pins::load(auth_service.state_dir());
+    if pins.is_empty() && !format.is_scripted() {
+        println!("No pinned entries. Pin one with `acc logs pin <id>`.");
+        return Ok(());
+    }
+
+    let mut entries = Vec::new();
+    for id in pins.ids() {
+        match fetch_worklog_entry(auth_service.api_client(), &id).await {
+            Ok(entry) => entries.push(entry),
+            Err(ApiError::NotFound(_)) => eprintln!(
+                "⚠️  Warning: Pinned entry {id} no longer exists. Remove it with `acc logs unpin {id}`"
+            ),
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    sort_by_recorded_at(&mut entries, oldest_first);
+    page_through(&entries, limit, format, &pins)
+}
+
+/// Entries without a recorded time sort as the oldest.
+fn sort_by_recorded_at(entries: &mut [WorklogEntry], oldest_first: bool) {
+    entries.sort_by_key(|entry| entry.recorded_at);
+    if !oldest_first {
+        entries.reverse();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::Server;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_sort_by_recorded_at() {
+        let mut entries: Vec<WorklogEntry> = serde_json::from_value(json!([
+            { "id": "b", "recorded_at": "2025-07-09T10:00:00Z" },
+            { "id": "a", "recorded_at": "2025-07-01T10:00:00Z" },
+            { "id": "c", "recorded_at": "2025-07-10T10:00:00Z" },
+        ]))
+        .unwrap();
+        let ids = |entries: &[WorklogEntry]| -> Vec<String> {
+            entries.iter().map(|e| e.id.clone()).collect()
+        };
+
+        sort_by_recorded_at(&mut entries, false);
+        assert_eq!(ids(&entries), vec!["c", "b", "a"]);
+        sort_by_recorded_at(&mut entries, true);
+        assert_eq!(ids(&entries), vec!["a", "b", "c"]);
+    }
+
+    #[tokio::test]
+    async fn test_pin_by_prefix() {
+        let mut server = Server::new_async().await;
+        let temp_dir = TempDir::new().unwrap();
+        let mut auth =
+            AuthService::new(server.url(), temp_dir.path().to_path_buf(), "test-profile");
+        auth.save_access_token("test-token").unwrap();
+
+        let _direct = server
+            .mock("GET", "/api/v1/worklog/entries/abcd1234")
+            .with_status(404)
+            .create();
+        let _list = server
+            .mock("GET", "/api/v1/worklog/entries?limit=100")
+            .with_status(200)
+            .with_body(
+                json!({
+                    "entries": [
+                        { "id": "abcd1234-0000-0000-0000-000000000001" },
+                        { "id": "ffff0000-0000-0000-0000-000000000002" }
+                    ],
+                    "meta": { "end_cursor": null }
+                })
+                .to_string(),
+            )
+            .create();
+
+        pin(&mut auth, "abcd1234").await.unwrap();
+        let pins = Pins::load(auth.state_dir());
+        assert!(pins.contains("abcd1234-0000-0000-0000-000000000001"));
+
+        unpin(&auth, "abcd").unwrap();
+        assert!(Pins::load(auth.state_dir()).is_empty());
+    }
+}
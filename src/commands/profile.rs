@@ -0,0 +1,369 @@
+use crate::auth::AuthService;
+use crate::config::{
+    self, ProfileSource, Settings, DEFAULT_API_BASE, DEFAULT_CLIENT_ID, DEFAULT_CREDENTIALS_DIR,
+    DEFAULT_PROFILE,
+};
+use crate::errors::AppError;
+use crate::utils::prompt::PromptOrDefault;
+use colored::*;
+use inquire::{Confirm, InquireError};
+use std::fs;
+use std::path::Path;
+
+/// Settings for a new profile; anything not given is copied from the
+/// `default` profile.
+#[derive(Debug, Default)]
+pub struct NewProfile {
+    pub api_base: Option<String>,
+    pub client_id: Option<String>,
+    pub credentials_dir: Option<String>,
+}
+
+/// Lists the profiles in the config, marking the active one.
+pub fn list() -> Result<(), AppError> {
+    let path = config::config_path()?;
+    let profiles = profiles(&read_config(&path)?)?;
+    let (active, source) = config::resolve_profile();
+
+    if profiles.is_empty() {
+        println!("No profiles in {}.", path.display());
+        return Ok(());
+    }
+
+    let width = profiles
+        .iter()
+        .map(|(name, _)| name.len())
+        .max()
+        .unwrap_or(0);
+    for (name, api_base) in &profiles {
+        let api_base = api_base.as_deref().unwrap_or("");
+        if *name == active {
+            println!(
+                "{} {} {}",
+                "*".green(),
+                format!("{name:<width$}").green().bold(),
+                api_base.bright_black()
+            );
+        } else {
+            println!("  {name:<width$} {}", api_base.bright_black());
+        }
+    }
+
+    let reason = match source {
+        ProfileSource::Flag => Some("selected with --profile"),
+        ProfileSource::Env => Some("selected by ACCOMPLISH_ENV"),
+        ProfileSource::Selected | ProfileSource::Default => None,
+    };
+    if let Some(reason) = reason {
+        println!(
+            "\n{}",
+            format!("Active profile {active} is {reason}").bright_black()
+        );
+    }
+    Ok(())
+}
+
+/// Makes `name` the active profile for later runs.
+pub fn use_profile(name: &str) -> Result<(), AppError> {
+    require_profile(name)?;
+    if name == DEFAULT_PROFILE {
+        config::select_profile(None)?;
+    } else {
+        config::select_profile(Some(name))?;
+    }
+    println!("✓ Now using profile {name}");
+
+    if let Some(env) = std::env::var("ACCOMPLISH_ENV")
+        .ok()
+        .filter(|env| !env.is_empty() && env != name)
+    {
+        eprintln!("⚠️  Warning: ACCOMPLISH_ENV={env} is set and takes precedence in this shell");
+    }
+    Ok(())
+}
+
+/// Adds a profile section to the config.
+pub fn add(name: &str, settings: &NewProfile) -> Result<(), AppError> {
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(AppError::Other(format!(
+            "Invalid profile name '{name}'. Use letters, digits, '-' and '_'"
+        )));
+    }
+
+    let path = config::config_path()?;
+    let content = read_config(&path)?;
+    let existing = profiles(&content)?;
+    if existing.iter().any(|(profile, _)| profile == name) {
+        return Err(AppError::Other(format!("Profile '{name}' already exists")));
+    }
+
+    let default = Settings::load(DEFAULT_PROFILE).ok();
+    let api_base = settings
+        .api_base
+        .clone()
+        .or_else(|| default.as_ref().map(|d| d.api_base.clone()))
+        .unwrap_or_else(|| DEFAULT_API_BASE.to_string());
+    let client_id = settings
+        .client_id
+        .clone()
+        .or_else(|| default.as_ref().map(|d| d.client_id.clone()))
+        .unwrap_or_else(|| DEFAULT_CLIENT_ID.to_string());
+    let credentials_dir = settings
+        .credentials_dir
+        .clone()
+        .unwrap_or_else(|| DEFAULT_CREDENTIALS_DIR.to_string());
+
+    let updated = append_profile(&content, name, &api_base, &client_id, &credentials_dir);
+    fs::write(&path, updated)?;
+    println!("✓ Added profile {name} ({api_base})");
+    println!(
+        "  Log in with `acc --profile {name} login`, then switch with `acc profile use {name}`"
+    );
+    Ok(())
+}
+
+/// Removes a profile's section from the config and its stored tokens, after
+/// asking unless `force` is set. Local state such as pins is left in place.
+pub fn remove(name: &str, force: bool) -> Result<(), AppError> {
+    if name == DEFAULT_PROFILE {
+        return Err(AppError::Other(
+            "The default profile can't be removed".to_string(),
+        ));
+    }
+    require_profile(name)?;
+
+    if !force {
+        let confirmed = Confirm::new(&format!("Remove profile {name} and log it out?"))
+            .with_default(false)
+            .prompt_or_default()
+            .map_err(|e| match e {
+                InquireError::NotTTY => AppError::Other(
+                    "Can't ask for confirmation without a terminal. Use --force to remove anyway"
+                        .to_string(),
+                ),
+                e => AppError::Other(e.to_string()),
+            })?;
+        if !confirmed {
+            println!("Nothing removed.");
+            return Ok(());
+        }
+    }
+
+    // Tokens are found through the profile's own settings, so clear them first
+    let state_dir = match Settings::load(name) {
+        Ok(settings) => {
+            let mut auth_service = AuthService::with_backend(
+                settings.api_base,
+                settings.credentials_dir.clone(),
+                name,
+                settings.credential_store,
+            );
+            auth_service.clear_tokens();
+            Some(settings.credentials_dir.join(name))
+        }
+        Err(e) => {
+            eprintln!("⚠️  Warning: Could not clear the profile's tokens: {e}");
+            None
+        }
+    };
+
+    let path = config::config_path()?;
+    let updated = remove_profile(&read_config(&path)?, name);
+    fs::write(&path, updated)?;
+    println!("✓ Removed profile {name}");
+    if config::selected_profile().as_deref() == Some(name) {
+        config::select_profile(None)?;
+        println!("  Switched back to the {DEFAULT_PROFILE} profile");
+    }
+    if let Some(state_dir) = state_dir.filter(|dir| dir.exists()) {
+        println!(
+            "  Local state such as pins is kept in {}",
+            state_dir.display()
+        );
+    }
+    Ok(())
+}
+
+fn require_profile(name: &str) -> Result<(), AppError> {
+    let path = config::config_path()?;
+    if profiles(&read_config(&path)?)?
+        .iter()
+        .any(|(profile, _)| profile == name)
+    {
+        Ok(())
+    } else {
+        Err(AppError::Other(format!(
+            "No profile named '{name}'. See `acc profile list`"
+        )))
+    }
+}
+
+fn read_config(path: &Path) -> Result<String, AppError> {
+    match fs::read_to_string(path) {
+        Ok(content) => Ok(content),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(String::new()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Profile names in config order, with each profile's `api_base`.
+fn profiles(content: &str) -> Result<Vec<(String, Option<String>)>, AppError> {
+    let table: toml::Table = toml::from_str(content)
+        .map_err(|e| AppError::ParseError(format!("Failed to parse config: {e}")))?;
+    let mut names: Vec<String> = Vec::new();
+    for header in content.lines().filter_map(table_header) {
+        let name = header
+            .split('.')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_string();
+        if !names.contains(&name) && table.get(&name).is_some_and(toml::Value::is_table) {
+            names.push(name);
+        }
+    }
+    Ok(names
+        .into_iter()
+        .map(|name| {
+            let api_base = table[&name]
+                .get("api_base")
+                .and_then(|v| v.as_str())
+                .map(str::to_string);
+            (name, api_base)
+        })
+        .collect())
+}
+
+/// The config with a new profile section appended, leaving the rest of the
+/// file, comments included, as it was.
+fn append_profile(
+    content: &str,
+    name: &str,
+    api_base: &str,
+    client_id: &str,
+    credentials_dir: &str,
+) -> String {
+    let mut updated = content.trim_end().to_string();
+    if !updated.is_empty() {
+        updated.push_str("\n\n");
+    }
+    let quote = |value: &str| toml::Value::String(value.to_string()).to_string();
+    updated.push_str(&format!(
+        "[{name}]\napi_base = {}\nclient_id = {}\ncredentials_dir = {}\n",
+        quote(api_base),
+        quote(client_id),
+        quote(credentials_dir)
+    ));
+    updated
+}
+
+/// The config without the `[name]` section and its subtables such as
+/// `[name.fields]`. Everything else is kept as written.
+fn remove_profile(content: &str, name: &str) -> String {
+    let mut kept: Vec<&str> = Vec::new();
+    let mut skipping = false;
+    for line in content.lines() {
+        if let Some(header) = table_header(line) {
+            skipping = header == name || header.starts_with(&format!("{name}."));
+        }
+        if !skipping {
+            kept.push(line);
+        }
+    }
+    // Drop blank lines left where the section was
+    let mut updated = String::new();
+    for line in kept {
+        if line.trim().is_empty() && (updated.is_empty() || updated.ends_with("\n\n")) {
+            continue;
+        }
+        updated.push_str(line);
+        updated.push('\n');
+    }
+    format!("{}\n", updated.trim_end())
+}
+
+/// The name in a `[table]` or `[[array]]` header line.
+fn table_header(line: &str) -> Option<String> {
+    let line = line.trim();
+    if !line.starts_with('[') {
+        return None;
+    }
+    let inner = line.trim_start_matches('[');
+    let end = inner.find(']')?;
+    Some(inner[..end].trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"# My settings
+[default]
+api_base = "https://accomplish.dev"
+client_id = "abc"
+
+[default.fields]
+ticket = { type = "string" }
+
+[work]
+api_base = "https://work.example.com"
+
+[work.project_parents]
+chk = "web"
+
+[[work.recap_replacements]]
+pattern = "JIRA"
+replacement = "ticket"
+
+[personal]
+api_base = "https://accomplish.dev"
+"#;
+
+    #[test]
+    fn test_profiles_in_config_order() {
+        let names: Vec<String> = profiles(CONFIG)
+            .unwrap()
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        assert_eq!(names, ["default", "work", "personal"]);
+    }
+
+    #[test]
+    fn test_remove_profile_with_subtables() {
+        let updated = remove_profile(CONFIG, "work");
+        assert_eq!(
+            updated,
+            r#"# My settings
+[default]
+api_base = "https://accomplish.dev"
+client_id = "abc"
+
+[default.fields]
+ticket = { type = "string" }
+
+[personal]
+api_base = "https://accomplish.dev"
+"#
+        );
+    }
+
+    #[test]
+    fn test_append_profile_keeps_existing_content() {
+        let updated = append_profile(CONFIG, "side", "https://side.dev", "xyz", "~/.accomplish");
+        assert!(updated.starts_with(CONFIG.trim_end()));
+        assert!(updated.ends_with(
+            "\n\n[side]\napi_base = \"https://side.dev\"\nclient_id = \"xyz\"\ncredentials_dir = \"~/.accomplish\"\n"
+        ));
+        let names: Vec<String> = profiles(&updated)
+            .unwrap()
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        assert_eq!(names.last().map(String::as_str), Some("side"));
+    }
+}
@@ -1,20 +1,37 @@
 use crate::api::endpoints;
+pub use crate::api::models::Project;
+use crate::api::models::Repository;
 use crate::auth::AuthService;
+use crate::completion_cache::CompletionCache;
+use crate::config;
 use crate::errors::AppError;
-use serde::{Deserialize, Serialize};
+use crate::utils::fuzzy::{normalize, similarity};
+use crate::utils::humanize::time_ago;
+use crate::utils::prompt::{assume_defaults, PromptOrDefault};
+use chrono::{DateTime, Local, Utc};
+use colored::*;
+use inquire::{Confirm, InquireError, Select};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::io::{self, IsTerminal};
 use tabled::settings::Style;
 use tabled::{Table, Tabled};
 
-#[derive(Debug, Deserialize, Serialize)]
-pub struct Project {
-    pub id: String,
-    pub name: String,
-    pub identifier: String,
-}
+/// How alike a spec and a project name must be to suggest the project.
+const MIN_FUZZY_SIMILARITY: f64 = 0.6;
 
-#[derive(Debug, Deserialize, Serialize)]
-struct ProjectsResponse {
-    projects: Vec<Project>,
+/// How a project spec matched, see `find_project`.
+#[derive(Debug)]
+pub enum ProjectMatch<'a> {
+    /// Identifier or `parent/child` path
+    Exact(&'a Project),
+    /// The name, or a prefix of the identifier, name or a word in it
+    Likely(&'a Project),
+    /// Only similar, such as a typo; worth confirming
+    Possible(&'a Project),
+    /// Several projects match equally well
+    Ambiguous(Vec<&'a Project>),
+    NoMatch,
 }
 
 /// Lists all projects for the authenticated user.
@@ -27,11 +44,11 @@ pub async fn list(auth_service: &mut AuthService) -> Result<(), AppError> {
         return Ok(());
     }
 
-    let table_data: Vec<ProjectTableRow> = projects
+    let table_data: Vec<ProjectTableRow> = tree_order(&projects)
         .into_iter()
-        .map(|project| ProjectTableRow {
-            name: project.name,
-            identifier: project.identifier.to_uppercase(),
+        .map(|(project, depth)| ProjectTableRow {
+            name: project.name.clone(),
+            identifier: tree_label(&project.identifier.to_uppercase(), depth),
         })
         .collect();
 
@@ -49,27 +66,447 @@ struct ProjectTableRow {
     name: String,
 }
 
-/// Gets projects from the API and parses the response.
+/// How much has been logged to a project (and its sub-projects).
+struct EntryStats {
+    /// None when the API doesn't report a total
+    count: Option<u32>,
+    last_recorded_at: Option<DateTime<Utc>>,
+}
+
+/// Shows one project in detail: its description, company, role and dates,
+/// where it sits among sub-projects, the repositories linked to it, and how
+/// many entries it has.
+pub async fn show(auth_service: &mut AuthService, spec: &str) -> Result<(), AppError> {
+    let projects = get_projects(auth_service).await?;
+    let project = match_project(&projects, spec).ok_or_else(|| {
+        AppError::Other(format!(
+            "No project found with identifier '{spec}'. See `acc project list`"
+        ))
+    })?;
+
+    let repositories: Vec<Repository> = endpoints::fetch_repositories(auth_service.api_client())
+        .await
+        .map_err(AppError::Api)?
+        .into_iter()
+        .filter(|repo| repo.project_id.as_deref() == Some(project.id.as_str()))
+        .collect();
+
+    // Newest first, so one entry is enough to know when work was last logged
+    let project_ids = project_and_descendant_ids(&projects, project);
+    let page = endpoints::fetch_worklog_entries(
+        auth_service.api_client(),
+        Some(&project_ids),
+        None,
+        None,
+        None,
+        None,
+        1,
+        None,
+    )
+    .await
+    .map_err(AppError::Api)?;
+    let stats = EntryStats {
+        count: page
+            .meta
+            .total_count
+            .or_else(|| page.entries.is_empty().then_some(0)),
+        last_recorded_at: page.entries.first().and_then(|entry| entry.recorded_at),
+    };
+
+    print!(
+        "{}",
+        render_details(&projects, project, &repositories, &stats, Local::now())
+    );
+    Ok(())
+}
+
+fn render_details(
+    projects: &[Project],
+    project: &Project,
+    repositories: &[Repository],
+    stats: &EntryStats,
+    now: DateTime<Local>,
+) -> String {
+    let mut out = format!(
+        "{} {}\n",
+        project.identifier.to_uppercase().bold(),
+        project.name
+    );
+    if let Some(description) = project.description.as_deref().filter(|d| !d.is_empty()) {
+        out.push_str(&format!("{}\n", description.bright_black()));
+    }
+    out.push('\n');
+
+    let mut field = |label: &str, value: String| {
+        out.push_str(&format!("{:<14}{value}\n", format!("{label}:")));
+    };
+    if let Some(company) = &project.company {
+        field("Company", company.clone());
+    }
+    if let Some(role) = &project.role {
+        field("Role", role.clone());
+    }
+    match (&project.start_date, &project.end_date) {
+        (Some(start), Some(end)) => field("Dates", format!("{start} – {end}")),
+        (Some(start), None) => field("Dates", format!("{start} – ongoing")),
+        (None, Some(end)) => field("Dates", format!("until {end}")),
+        (None, None) => {}
+    }
+    if let Some(parent) = parent_of(projects, project) {
+        field("Parent", project_path(projects, parent).to_uppercase());
+    }
+    let children: Vec<String> = projects
+        .iter()
+        .filter(|p| p.parent_id.as_deref() == Some(project.id.as_str()))
+        .map(|p| p.identifier.to_uppercase())
+        .collect();
+    if !children.is_empty() {
+        field("Sub-projects", children.join(", "));
+    }
+
+    let scope = if children.is_empty() {
+        ""
+    } else {
+        " (with sub-projects)"
+    };
+    match stats.count {
+        Some(count) => field("Entries", format!("{count}{scope}")),
+        None if stats.last_recorded_at.is_some() => field("Entries", format!("some{scope}")),
+        None => {}
+    }
+    if let Some(last) = stats.last_recorded_at {
+        field("Last entry", time_ago(last.with_timezone(&Local), now));
+    }
+
+    out.push_str(&format!("\n{}\n", "Repositories".bold()));
+    if repositories.is_empty() {
+        out.push_str("  None linked. Run `acc init` in a clone to link one\n");
+    }
+    for repo in repositories {
+        let name = if repo.name.is_empty() {
+            "(unnamed)"
+        } else {
+            &repo.name
+        };
+        let location = repo
+            .local_path
+            .as_deref()
+            .or(repo.remote_url.as_deref())
+            .unwrap_or("");
+        out.push_str(&format!("  {name}  {}\n", location.bright_black()));
+    }
+    out
+}
+
+/// Gets projects from the API, with parents from the `project_parents` config
+/// filled in.
 pub async fn get_projects(auth_service: &mut AuthService) -> Result<Vec<Project>, AppError> {
-    let response = endpoints::fetch_projects(auth_service.api_client())
+    let mut projects = endpoints::fetch_projects(auth_service.api_client())
         .await
         .map_err(AppError::Api)?;
+    apply_parent_overrides(&mut projects, &config::project_parent_overrides());
+
+    let mut cache = CompletionCache::load(auth_service.state_dir());
+    cache.set_projects(projects.iter().map(|p| p.identifier.as_str()));
+    if let Err(e) = cache.save() {
+        eprintln!("⚠️  Warning: Could not save completion cache: {e}");
+    }
 
-    let projects_response: ProjectsResponse = serde_json::from_value(response)
-        .map_err(|e| AppError::ParseError(format!("Failed to parse projects response: {e}")))?;
+    Ok(projects)
+}
+
+/// Resolves a project identifier (or `parent/child` path) to the IDs used to filter
+/// entries: the project itself plus all of its sub-projects. Prints a warning and
+/// returns None when nothing matches.
+pub async fn resolve_project_filter_ids(
+    auth_service: &mut AuthService,
+    identifier: &str,
+) -> Result<Option<Vec<String>>, AppError> {
+    let projects = get_projects(auth_service).await?;
 
-    Ok(projects_response.projects)
+    match match_project(&projects, identifier) {
+        Some(project) => Ok(Some(project_and_descendant_ids(&projects, project))),
+        None => {
+            eprintln!("⚠️ Warning: No project found with identifier '{identifier}'");
+            Ok(None)
+        }
+    }
+}
+
+/// Asks which project to use when none was given and no default is
+/// configured. `none_label` is the first choice, e.g. "No project". Returns
+/// the chosen identifier, or None for that choice, or without asking when
+/// not in an interactive terminal or running with `--defaults`.
+pub async fn prompt_for_project(
+    auth_service: &mut AuthService,
+    message: &str,
+    none_label: &str,
+) -> Result<Option<String>, AppError> {
+    if assume_defaults() || !io::stdin().is_terminal() || !io::stdout().is_terminal() {
+        return Ok(None);
+    }
+
+    let projects = get_projects(auth_service).await?;
+    if projects.is_empty() {
+        return Ok(None);
+    }
+
+    let choices = project_choices(&projects);
+    let mut options = vec![none_label.to_string()];
+    options.extend(choices.iter().map(|(label, _)| label.clone()));
+
+    let selected = Select::new(message, options)
+        .with_help_message("Type to filter, Enter to select. Pass -p or --no-project to skip this")
+        .raw_prompt()
+        .map_err(|e| AppError::ParseError(format!("Selection failed: {e}")))?;
+
+    Ok(selected.index.checked_sub(1).map(|i| choices[i].1.clone()))
+}
+
+/// Select labels in tree order, paired with the `parent/child` path each one
+/// picks, so sub-projects sharing an identifier stay distinct.
+fn project_choices(projects: &[Project]) -> Vec<(String, String)> {
+    tree_order(projects)
+        .into_iter()
+        .map(|(project, depth)| {
+            let label = format!(
+                "{} - {}",
+                tree_label(&project.identifier.to_uppercase(), depth),
+                project.name
+            );
+            (label, project_path(projects, project))
+        })
+        .collect()
+}
+
+/// The `parent/child` identifier path that `resolve_project` accepts.
+fn project_path(projects: &[Project], project: &Project) -> String {
+    let mut path = vec![project.identifier.as_str()];
+    let mut current = project;
+    while let Some(parent) = parent_of(projects, current) {
+        // Guards against cycles in the hierarchy
+        if path.len() > projects.len() {
+            break;
+        }
+        path.push(&parent.identifier);
+        current = parent;
+    }
+    path.reverse();
+    path.join("/")
+}
+
+/// Fills in `parent_id` from configured `child = "parent"` identifier pairs for
+/// projects the backend didn't place in a hierarchy.
+fn apply_parent_overrides(projects: &mut [Project], overrides: &HashMap<String, String>) {
+    if overrides.is_empty() {
+        return;
+    }
+
+    let ids_by_identifier: HashMap<String, String> = projects
+        .iter()
+        .map(|p| (p.identifier.to_lowercase(), p.id.clone()))
+        .collect();
+
+    for project in projects.iter_mut().filter(|p| p.parent_id.is_none()) {
+        let parent_id = overrides
+            .get(&project.identifier.to_lowercase())
+            .and_then(|parent| ids_by_identifier.get(parent))
+            .filter(|parent_id| **parent_id != project.id);
+        project.parent_id = parent_id.cloned();
+    }
+}
+
+fn parent_of<'a>(projects: &'a [Project], project: &Project) -> Option<&'a Project> {
+    let parent_id = project.parent_id.as_deref()?;
+    projects.iter().find(|p| p.id == parent_id)
+}
+
+/// Finds a project by identifier, or by a `parent/child` path where each segment
+/// must be the identifier of the next segment's parent. Case-insensitive.
+pub fn resolve_project<'a>(projects: &'a [Project], spec: &str) -> Option<&'a Project> {
+    let segments: Vec<&str> = spec
+        .split('/')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .collect();
+    let (last, ancestors) = segments.split_last()?;
+
+    projects
+        .iter()
+        .filter(|p| p.identifier.eq_ignore_ascii_case(last))
+        .find(|candidate| {
+            let mut current = *candidate;
+            ancestors
+                .iter()
+                .rev()
+                .all(|ancestor| match parent_of(projects, current) {
+                    Some(parent) if parent.identifier.eq_ignore_ascii_case(ancestor) => {
+                        current = parent;
+                        true
+                    }
+                    _ => false,
+                })
+        })
+}
+
+/// Matches a spec the way `-p` is read everywhere: exactly by identifier or
+/// path, else by name with fuzzy matching. Likely matches are used with a
+/// note, possible ones are confirmed first, and ambiguous ones are listed.
+pub fn match_project<'a>(projects: &'a [Project], spec: &str) -> Option<&'a Project> {
+    let describe = |p: &Project| format!("{} ({})", p.identifier.to_uppercase(), p.name);
+    match find_project(projects, spec) {
+        ProjectMatch::Exact(project) => Some(project),
+        ProjectMatch::Likely(project) => {
+            eprintln!("→ Using project {} for '{spec}'", describe(project));
+            Some(project)
+        }
+        ProjectMatch::Possible(project) => {
+            // Declining, or having no terminal to ask in, means no match
+            Confirm::new(&format!(
+                "No project '{spec}'. Did you mean {}?",
+                describe(project)
+            ))
+            .with_default(false)
+            .prompt_or_default()
+            .unwrap_or(false)
+            .then_some(project)
+        }
+        ProjectMatch::Ambiguous(candidates) => {
+            let names: Vec<String> = candidates.iter().map(|p| describe(p)).collect();
+            eprintln!(
+                "⚠️ Warning: '{spec}' matches several projects: {}. Use the identifier instead",
+                names.join(", ")
+            );
+            None
+        }
+        ProjectMatch::NoMatch => None,
+    }
+}
+
+/// Finds the project a spec most likely means, without asking anything.
+pub fn find_project<'a>(projects: &'a [Project], spec: &str) -> ProjectMatch<'a> {
+    if let Some(project) = resolve_project(projects, spec) {
+        return ProjectMatch::Exact(project);
+    }
+    let query = normalize(spec);
+    // Paths are exact by design; single characters would match too much
+    if query.chars().count() < 2 || spec.contains('/') {
+        return ProjectMatch::NoMatch;
+    }
+
+    let likely: Vec<&Project> = projects
+        .iter()
+        .filter(|p| {
+            let name = normalize(&p.name);
+            name.starts_with(&query)
+                || normalize(&p.identifier).starts_with(&query)
+                || p.name
+                    .split_whitespace()
+                    .any(|word| normalize(word).starts_with(&query))
+        })
+        .collect();
+    match likely.as_slice() {
+        [project] => return ProjectMatch::Likely(project),
+        [] => {}
+        _ => {
+            return match likely.iter().find(|p| normalize(&p.name) == query) {
+                Some(project) => ProjectMatch::Likely(project),
+                None => ProjectMatch::Ambiguous(likely),
+            }
+        }
+    }
+
+    let mut scored: Vec<(f64, &Project)> = projects
+        .iter()
+        .map(|p| {
+            let score = similarity(&query, &normalize(&p.name))
+                .max(similarity(&query, &normalize(&p.identifier)));
+            (score, p)
+        })
+        .filter(|(score, _)| *score >= MIN_FUZZY_SIMILARITY)
+        .collect();
+    scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+    match scored.as_slice() {
+        [] => ProjectMatch::NoMatch,
+        [(best, _), (second, _), ..] if best == second => ProjectMatch::Ambiguous(
+            scored
+                .iter()
+                .take_while(|(score, _)| score == best)
+                .map(|(_, p)| *p)
+                .collect(),
+        ),
+        [(_, project), ..] => ProjectMatch::Possible(project),
+    }
+}
+
+/// Returns the IDs of the project and every project nested below it.
+pub fn project_and_descendant_ids(projects: &[Project], root: &Project) -> Vec<String> {
+    let mut ids = vec![root.id.clone()];
+    let mut index = 0;
+
+    // Breadth-first; the `contains` check guards against cycles in the hierarchy
+    while index < ids.len() {
+        let current = ids[index].clone();
+        for child in projects
+            .iter()
+            .filter(|p| p.parent_id.as_deref() == Some(current.as_str()))
+        {
+            if !ids.contains(&child.id) {
+                ids.push(child.id.clone());
+            }
+        }
+        index += 1;
+    }
+
+    ids
+}
+
+/// Orders projects depth-first with each sub-project after its parent, paired
+/// with its depth in the hierarchy. Projects whose parent is unknown are roots.
+fn tree_order(projects: &[Project]) -> Vec<(&Project, usize)> {
+    fn visit<'a>(
+        projects: &'a [Project],
+        project: &'a Project,
+        depth: usize,
+        out: &mut Vec<(&'a Project, usize)>,
+    ) {
+        if out.iter().any(|(p, _)| p.id == project.id) {
+            return;
+        }
+        out.push((project, depth));
+        for child in projects
+            .iter()
+            .filter(|p| p.parent_id.as_deref() == Some(project.id.as_str()))
+        {
+            visit(projects, child, depth + 1, out);
+        }
+    }
+
+    let mut ordered = Vec::with_capacity(projects.len());
+    for root in projects.iter().filter(|p| parent_of(projects, p).is_none()) {
+        visit(projects, root, 0, &mut ordered);
+    }
+    ordered
+}
+
+fn tree_label(identifier: &str, depth: usize) -> String {
+    if depth == 0 {
+        identifier.to_string()
+    } else {
+        format!("{}└─ {identifier}", "   ".repeat(depth - 1))
+    }
 }
 
 /// Creates a new project with the given name, description, and identifier.
 /// If identifier is None, the backend will auto-generate one.
+/// Returns the created project.
 /// Requires an authenticated AuthService.
 pub async fn create_project(
     auth_service: &mut AuthService,
     name: &str,
     description: Option<&str>,
     identifier: Option<&str>,
-) -> Result<(), AppError> {
+) -> Result<Project, AppError> {
     // Validate project name
     if name.trim().is_empty() {
         return Err(AppError::ParseError(
@@ -77,48 +514,182 @@ pub async fn create_project(
         ));
     }
 
-    // Validate identifier if provided
     if let Some(id) = identifier {
-        if id.trim().is_empty() {
-            return Err(AppError::ParseError(
-                "Identifier cannot be empty".to_string(),
-            ));
-        }
-        if id.trim().len() > 3 {
-            return Err(AppError::ParseError(
-                "Identifier must be 3 characters or less".to_string(),
-            ));
-        }
-        if !id.chars().all(|c| c.is_ascii_alphabetic()) {
+        validate_identifier(id)?;
+    }
+
+    let response =
+        endpoints::create_project(auth_service.api_client(), name, description, identifier)
+            .await
+            .map_err(AppError::Api)?;
+
+    let project: Project = serde_json::from_value(response)
+        .map_err(|e| AppError::ParseError(format!("Failed to parse project response: {e}")))?;
+
+    println!(
+        "✓ Project '{}' created successfully with identifier '{}'",
+        project.name, project.identifier
+    );
+
+    Ok(project)
+}
+
+fn validate_identifier(id: &str) -> Result<(), AppError> {
+    if id.trim().is_empty() {
+        return Err(AppError::ParseError(
+            "Identifier cannot be empty".to_string(),
+        ));
+    }
+    if id.trim().len() > 3 {
+        return Err(AppError::ParseError(
+            "Identifier must be 3 characters or less".to_string(),
+        ));
+    }
+    if !id.chars().all(|c| c.is_ascii_alphabetic()) {
+        return Err(AppError::ParseError(
+            "Identifier must contain only letters".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Renames a project, or changes its description or identifier. Only the
+/// fields given are sent; an empty description clears it.
+pub async fn update_project(
+    auth_service: &mut AuthService,
+    spec: &str,
+    name: Option<&str>,
+    description: Option<&str>,
+    identifier: Option<&str>,
+) -> Result<Project, AppError> {
+    let mut changes = serde_json::Map::new();
+    if let Some(name) = name {
+        if name.trim().is_empty() {
             return Err(AppError::ParseError(
-                "Identifier must contain only letters".to_string(),
+                "Project name cannot be empty".to_string(),
             ));
         }
+        changes.insert("name".to_string(), name.trim().into());
+    }
+    if let Some(description) = description {
+        changes.insert("description".to_string(), description.trim().into());
+    }
+    if let Some(id) = identifier {
+        validate_identifier(id)?;
+        changes.insert("identifier".to_string(), id.trim().into());
     }
+    if changes.is_empty() {
+        return Err(AppError::Other(
+            "Nothing to update. Pass --name, --description or --identifier".to_string(),
+        ));
+    }
+
+    let projects = get_projects(auth_service).await?;
+    let current = match_project(&projects, spec).ok_or_else(|| {
+        AppError::Other(format!(
+            "No project found with identifier '{spec}'. See `acc project list`"
+        ))
+    })?;
 
     let response =
-        endpoints::create_project(auth_service.api_client(), name, description, identifier)
+        endpoints::update_project(auth_service.api_client(), &current.id, changes.into())
             .await
             .map_err(AppError::Api)?;
+    // Some servers answer a PATCH with no body; the project as sent is then
+    // the best we know
+    let project: Project = match response {
+        Value::Null => Project {
+            name: name.map_or_else(|| current.name.clone(), |n| n.trim().to_string()),
+            identifier: identifier
+                .map_or_else(|| current.identifier.clone(), |i| i.trim().to_string()),
+            description: description
+                .map(|d| d.trim().to_string())
+                .or_else(|| current.description.clone()),
+            ..current.clone()
+        },
+        response => serde_json::from_value(response)
+            .map_err(|e| AppError::ParseError(format!("Failed to parse project response: {e}")))?,
+    };
+
+    println!(
+        "✓ Project '{}' ({}) updated",
+        project.name,
+        project.identifier.to_uppercase()
+    );
+    if !project.identifier.eq_ignore_ascii_case(&current.identifier) {
+        println!(
+            "{}",
+            format!(
+                "Settings that name '{}' (default_project, directories set up with `acc init`) need updating to '{}'",
+                current.identifier, project.identifier
+            )
+            .bright_black()
+        );
+    }
+    Ok(project)
+}
 
-    // Extract project details from response
-    let project_name = response
-        .get("name")
-        .and_then(|v| v.as_str())
-        .unwrap_or("Unknown");
-    let project_id = response
-        .get("identifier")
-        .and_then(|v| v.as_str())
-        .unwrap_or("Unknown");
+/// Archives a project after asking for confirmation, unless `force` is set.
+pub async fn archive_project(
+    auth_service: &mut AuthService,
+    spec: &str,
+    force: bool,
+) -> Result<(), AppError> {
+    let projects = get_projects(auth_service).await?;
+    let project = match_project(&projects, spec).ok_or_else(|| {
+        AppError::Other(format!(
+            "No project found with identifier '{spec}'. See `acc project list`"
+        ))
+    })?;
 
-    println!("✓ Project '{project_name}' created successfully with identifier '{project_id}'");
+    if !force {
+        let children = projects
+            .iter()
+            .filter(|p| p.parent_id.as_deref() == Some(project.id.as_str()))
+            .count();
+        let question = match children {
+            0 => format!(
+                "Archive {} ({})?",
+                project.identifier.to_uppercase(),
+                project.name
+            ),
+            n => format!(
+                "Archive {} ({})? It has {n} sub-project(s)",
+                project.identifier.to_uppercase(),
+                project.name
+            ),
+        };
+        let confirmed = Confirm::new(&question)
+            .with_default(false)
+            .prompt_or_default()
+            .map_err(|e| match e {
+                InquireError::NotTTY => AppError::Other(
+                    "Can't ask for confirmation without a terminal. Use --force to archive anyway"
+                        .to_string(),
+                ),
+                e => AppError::Other(e.to_string()),
+            })?;
+        if !confirmed {
+            println!("Nothing archived.");
+            return Ok(());
+        }
+    }
 
+    endpoints::archive_project(auth_service.api_client(), &project.id)
+        .await
+        .map_err(AppError::Api)?;
+    println!(
+        "✓ Project '{}' ({}) archived",
+        project.name,
+        project.identifier.to_uppercase()
+    );
     Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::TimeZone;
     use mockito::Server;
     use serde_json::json;
 
@@ -209,6 +780,191 @@ mod tests {
         assert!(matches!(result, Err(AppError::Api(_))));
     }
 
+    fn project(id: &str, identifier: &str, parent_id: Option<&str>) -> Project {
+        Project {
+            id: id.to_string(),
+            name: identifier.to_string(),
+            identifier: identifier.to_string(),
+            parent_id: parent_id.map(String::from),
+            ..Default::default()
+        }
+    }
+
+    fn hierarchy() -> Vec<Project> {
+        vec![
+            project("web-id", "web", None),
+            project("chk-id", "chk", Some("web-id")),
+            project("pay-id", "pay", Some("chk-id")),
+            project("ops-id", "ops", None),
+            project("ops-chk-id", "chk", Some("ops-id")),
+        ]
+    }
+
+    #[test]
+    fn test_resolve_project_by_path() {
+        let projects = hierarchy();
+
+        assert_eq!(resolve_project(&projects, "WEB").unwrap().id, "web-id");
+        assert_eq!(resolve_project(&projects, "web/chk").unwrap().id, "chk-id");
+        assert_eq!(
+            resolve_project(&projects, "ops/CHK").unwrap().id,
+            "ops-chk-id"
+        );
+        assert_eq!(
+            resolve_project(&projects, "web/chk/pay").unwrap().id,
+            "pay-id"
+        );
+        assert!(resolve_project(&projects, "ops/pay").is_none());
+        assert!(resolve_project(&projects, "").is_none());
+    }
+
+    #[test]
+    fn test_project_and_descendant_ids() {
+        let projects = hierarchy();
+        let web = &projects[0];
+        assert_eq!(
+            project_and_descendant_ids(&projects, web),
+            vec!["web-id", "chk-id", "pay-id"]
+        );
+        assert_eq!(
+            project_and_descendant_ids(&projects, &projects[3]),
+            vec!["ops-id", "ops-chk-id"]
+        );
+    }
+
+    #[test]
+    fn test_apply_parent_overrides() {
+        let mut projects = vec![
+            project("web-id", "web", None),
+            project("chk-id", "chk", None),
+            project("api-id", "api", Some("web-id")),
+        ];
+        let overrides: HashMap<String, String> = [
+            ("chk".to_string(), "web".to_string()),
+            ("api".to_string(), "chk".to_string()),
+            ("web".to_string(), "web".to_string()),
+        ]
+        .into_iter()
+        .collect();
+
+        apply_parent_overrides(&mut projects, &overrides);
+
+        assert_eq!(projects[0].parent_id, None);
+        assert_eq!(projects[1].parent_id.as_deref(), Some("web-id"));
+        // Hierarchy from the API wins over the config
+        assert_eq!(projects[2].parent_id.as_deref(), Some("web-id"));
+    }
+
+    #[test]
+    fn test_tree_order_and_labels() {
+        let projects = hierarchy();
+        let labels: Vec<String> = tree_order(&projects)
+            .into_iter()
+            .map(|(p, depth)| tree_label(&p.identifier.to_uppercase(), depth))
+            .collect();
+        assert_eq!(labels, vec!["WEB", "└─ CHK", "   └─ PAY", "OPS", "└─ CHK"]);
+    }
+
+    #[test]
+    fn test_find_project_fuzzy() {
+        let named = |id: &str, identifier: &str, name: &str| Project {
+            name: name.to_string(),
+            ..project(id, identifier, None)
+        };
+        let projects = vec![
+            named("web-id", "web", "Website"),
+            named("app-id", "app", "Mobile App"),
+            named("api-id", "api", "Public API"),
+            named("bil-id", "bil", "Billing Service"),
+        ];
+        let id = |m: ProjectMatch| match m {
+            ProjectMatch::Exact(p) => format!("exact {}", p.id),
+            ProjectMatch::Likely(p) => format!("likely {}", p.id),
+            ProjectMatch::Possible(p) => format!("possible {}", p.id),
+            ProjectMatch::Ambiguous(ps) => format!("ambiguous {}", ps.len()),
+            ProjectMatch::NoMatch => "none".to_string(),
+        };
+
+        assert_eq!(id(find_project(&projects, "WEB")), "exact web-id");
+        assert_eq!(id(find_project(&projects, "websit")), "likely web-id");
+        assert_eq!(id(find_project(&projects, "mobile")), "likely app-id");
+        assert_eq!(id(find_project(&projects, "service")), "likely bil-id");
+        assert_eq!(id(find_project(&projects, "ap")), "ambiguous 2");
+        assert_eq!(id(find_project(&projects, "webiste")), "possible web-id");
+        assert_eq!(id(find_project(&projects, "zzz")), "none");
+        assert_eq!(id(find_project(&projects, "w")), "none");
+    }
+
+    #[test]
+    fn test_render_details() {
+        colored::control::set_override(false);
+        let projects = vec![
+            Project {
+                name: "Website".to_string(),
+                description: Some("Marketing site and checkout".to_string()),
+                company: Some("Acme".to_string()),
+                role: Some("Tech lead".to_string()),
+                start_date: Some("2025-01-06".to_string()),
+                ..project("web-id", "web", None)
+            },
+            project("chk-id", "chk", Some("web-id")),
+            project("pay-id", "pay", Some("chk-id")),
+        ];
+        let repositories: Vec<Repository> = serde_json::from_value(json!([
+            { "id": "r1", "name": "website", "project_id": "web-id", "local_path": "/src/website" },
+            { "id": "r2", "name": "", "project_id": "web-id", "remote_url": "git@github.com:acme/cms.git" },
+        ]))
+        .unwrap();
+        let stats = EntryStats {
+            count: Some(42),
+            last_recorded_at: Some(Local.with_ymd_and_hms(2025, 7, 8, 10, 0, 0).unwrap().into()),
+        };
+        let now = Local.with_ymd_and_hms(2025, 7, 9, 12, 0, 0).unwrap();
+
+        assert_eq!(
+            render_details(&projects, &projects[0], &repositories, &stats, now),
+            "WEB Website\n\
+             Marketing site and checkout\n\n\
+             Company:      Acme\n\
+             Role:         Tech lead\n\
+             Dates:        2025-01-06 – ongoing\n\
+             Sub-projects: CHK\n\
+             Entries:      42 (with sub-projects)\n\
+             Last entry:   yesterday 10:00\n\n\
+             Repositories\n  \
+             website  /src/website\n  \
+             (unnamed)  git@github.com:acme/cms.git\n"
+        );
+
+        let empty = EntryStats {
+            count: Some(0),
+            last_recorded_at: None,
+        };
+        assert_eq!(
+            render_details(&projects, &projects[2], &[], &empty, now),
+            "PAY pay\n\n\
+             Parent:       WEB/CHK\n\
+             Entries:      0\n\n\
+             Repositories\n  \
+             None linked. Run `acc init` in a clone to link one\n"
+        );
+    }
+
+    #[test]
+    fn test_project_choices_pick_paths() {
+        let projects = hierarchy();
+        let choices = project_choices(&projects);
+        assert_eq!(choices[0], ("WEB - web".to_string(), "web".to_string()));
+        assert_eq!(
+            choices[2],
+            ("   └─ PAY - pay".to_string(), "web/chk/pay".to_string())
+        );
+        assert_eq!(choices[4].1, "ops/chk");
+        for (_, path) in &choices[1..] {
+            assert!(resolve_project(&projects, path).is_some());
+        }
+    }
+
     #[tokio::test]
     async fn test_create_project_success() {
         let mut server = Server::new_async().await;
@@ -239,7 +995,9 @@ mod tests {
             Some("tst"),
         )
         .await;
-        assert!(result.is_ok());
+        let project = result.unwrap();
+        assert_eq!(project.id, "project-uuid-123");
+        assert_eq!(project.identifier, "tst");
     }
 
     #[tokio::test]
@@ -289,4 +1047,75 @@ mod tests {
         let result = create_project(&mut auth, "Test", None, Some("t3t")).await;
         assert!(matches!(result, Err(AppError::ParseError(_))));
     }
+
+    fn mock_projects(server: &mut mockito::ServerGuard) -> mockito::Mock {
+        server
+            .mock("GET", "/api/v1/projects")
+            .with_status(200)
+            .with_body(
+                json!({ "projects": [
+                    { "id": "web-id", "name": "Website", "identifier": "web", "description": "Old" }
+                ]})
+                .to_string(),
+            )
+            .create()
+    }
+
+    #[tokio::test]
+    async fn test_update_project_sends_only_the_changes() {
+        let mut server = Server::new_async().await;
+        let mut auth = setup_mock_auth_service(&server.url());
+        let _projects = mock_projects(&mut server);
+        let patch = server
+            .mock("PATCH", "/api/v1/projects/web-id")
+            .match_header("authorization", "Bearer test-token")
+            .match_body(mockito::Matcher::Json(
+                json!({ "name": "Marketing Site", "identifier": "mkt" }),
+            ))
+            .with_status(200)
+            .with_body(
+                json!({ "id": "web-id", "name": "Marketing Site", "identifier": "mkt" })
+                    .to_string(),
+            )
+            .create();
+
+        let project = update_project(
+            &mut auth,
+            "web",
+            Some(" Marketing Site "),
+            None,
+            Some("mkt"),
+        )
+        .await
+        .unwrap();
+        assert_eq!(project.name, "Marketing Site");
+        assert_eq!(project.identifier, "mkt");
+        patch.assert();
+
+        // Nothing to change, or an invalid change, sends nothing
+        let result = update_project(&mut auth, "web", None, None, None).await;
+        assert!(matches!(result, Err(AppError::Other(_))));
+        let result = update_project(&mut auth, "web", Some(" "), None, None).await;
+        assert!(matches!(result, Err(AppError::ParseError(_))));
+        let result = update_project(&mut auth, "web", None, None, Some("toolong")).await;
+        assert!(matches!(result, Err(AppError::ParseError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_archive_project() {
+        let mut server = Server::new_async().await;
+        let mut auth = setup_mock_auth_service(&server.url());
+        let _projects = mock_projects(&mut server);
+        let delete = server
+            .mock("DELETE", "/api/v1/projects/web-id")
+            .match_header("authorization", "Bearer test-token")
+            .with_status(204)
+            .create();
+
+        archive_project(&mut auth, "web", true).await.unwrap();
+        delete.assert();
+
+        let result = archive_project(&mut auth, "zzz", true).await;
+        assert!(matches!(result, Err(AppError::Other(_))));
+    }
 }
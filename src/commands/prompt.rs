@@ -0,0 +1,45 @@
+use crate::capture_state::CaptureState;
+use crate::commands::capture::{format_uncaptured, uncaptured_count};
+use std::path::Path;
+
+/// Format used when `--format` isn't given.
+pub const DEFAULT_PROMPT_FORMAT: &str = "acc:{uncaptured}";
+
+/// Prints a short segment for a shell prompt, or nothing when every commit in
+/// the current repository is captured or the repository isn't known yet.
+///
+/// Only the local capture cache is read, so this is safe to run on every
+/// prompt.
+pub fn execute(state_dir: &Path, format: &str) {
+    let capture_state = CaptureState::load(state_dir);
+    let Some(count) = std::env::current_dir()
+        .ok()
+        .and_then(|dir| uncaptured_count(&dir, &capture_state))
+    else {
+        return;
+    };
+    if let Some(segment) = render(format, count) {
+        println!("{segment}");
+    }
+}
+
+/// The prompt segment with `{uncaptured}` filled in, or `None` when there's
+/// nothing to show.
+fn render(format: &str, count: usize) -> Option<String> {
+    if count == 0 {
+        return None;
+    }
+    Some(format.replace("{uncaptured}", &format_uncaptured(count)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        assert_eq!(render(DEFAULT_PROMPT_FORMAT, 0), None);
+        assert_eq!(render(DEFAULT_PROMPT_FORMAT, 3).as_deref(), Some("acc:3"));
+        assert_eq!(render("✎{uncaptured} ", 120).as_deref(), Some("✎99+ "));
+    }
+}
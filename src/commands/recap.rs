@@ -1,16 +1,102 @@
+use crate::api::client::ApiClient;
 use crate::api::endpoints::{generate_worklog_recap, get_recap_status};
+use crate::api::models::{RecapFilters, RecapMember, RecapMetadata, RecapResponse};
 use crate::auth::AuthService;
 use crate::commands::project;
 use crate::errors::AppError;
+use crate::hooks::{HookEvent, Hooks};
 use crate::utils::duration::parse_since_duration;
+use crate::utils::notify;
+use crate::utils::progress::RecapProgress;
+use crate::utils::recap_filters::RecapPipeline;
 use crate::utils::spinner::Spinner;
 use chrono::{DateTime, Utc};
+use clap::ValueEnum;
 use colored::*;
 use futures::StreamExt;
+use regex::Regex;
+use serde_json::{json, Map, Value};
+use std::fs;
 use std::io::{self, Write};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::sync::OnceLock;
 use tokio::time::{timeout, Duration};
 use url::Url;
 
+static FILTERS: OnceLock<RecapPipeline> = OnceLock::new();
+
+/// Sets the transforms applied to every recap before it's printed and
+/// returned, from `recap_filters` in the config. Only the first call counts.
+pub fn set_filters(pipeline: RecapPipeline) {
+    let _ = FILTERS.set(pipeline);
+}
+
+/// How a finished recap is printed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum RecapFormat {
+    /// Colored text with a summary of what went into it
+    #[default]
+    Text,
+    /// Markdown, for pasting into Slack, Notion or a document
+    Md,
+    /// Text without any markup
+    Plain,
+    /// One JSON object with the recap, its metadata and its filters
+    Json,
+}
+
+/// Where a finished recap goes besides the terminal: a file, and the
+/// `post-recap` hooks. Both get its metadata along with the content.
+#[derive(Debug, Clone, Default)]
+pub struct RecapDestinations {
+    /// Written as JSON for `--format json` or a `.json` path, otherwise as
+    /// Markdown with the metadata in YAML front matter
+    pub file: Option<PathBuf>,
+    pub hooks: Option<Hooks>,
+}
+
+/// What `print_recap_result` needs besides the recap itself.
+struct RecapOutput {
+    format: RecapFormat,
+    /// Tags are often internal shorthand, so client-facing recaps leave them out
+    show_tags: bool,
+    /// The filters the recap was asked for, included in JSON output
+    requested: Map<String, Value>,
+    /// The caller prints the recap itself, so only progress is shown
+    quiet: bool,
+    destinations: RecapDestinations,
+}
+
+impl RecapOutput {
+    fn new(format: RecapFormat, audience: Option<&str>) -> Self {
+        Self {
+            format,
+            show_tags: audience != Some("client"),
+            requested: Map::new(),
+            quiet: false,
+            destinations: RecapDestinations::default(),
+        }
+    }
+
+    /// Whether progress goes to stderr. Anything but the text format keeps
+    /// stdout for the recap alone, so it can be piped or redirected.
+    fn progress_on_stderr(&self) -> bool {
+        self.quiet || self.format != RecapFormat::Text
+    }
+
+    /// Where progress messages and spinners go.
+    fn progress(&self) -> Box<dyn Write> {
+        if self.progress_on_stderr() {
+            Box::new(io::stderr())
+        } else {
+            Box::new(io::stdout())
+        }
+    }
+}
+
+/// Generates a recap, prints it in `format`, and returns its content.
+#[allow(clippy::too_many_arguments)]
 pub async fn execute(
     auth_service: &mut AuthService,
     from: Option<&str>,
@@ -19,7 +105,229 @@ pub async fn execute(
     tags: Option<&[String]>,
     exclude_tags: Option<&[String]>,
     project_identifier: Option<&str>,
+    audience: Option<&str>,
+    team: bool,
+    format: RecapFormat,
+    destinations: RecapDestinations,
+) -> Result<String, AppError> {
+    let mut output = RecapOutput::new(format, audience);
+    output.destinations = destinations;
+    let recap_response = submit(
+        auth_service,
+        from,
+        to,
+        since,
+        tags,
+        exclude_tags,
+        project_identifier,
+        audience,
+        team,
+        &mut output,
+    )
+    .await?;
+
+    wait_for_recap(auth_service.api_client(), &recap_response, &output).await
+}
+
+/// Generates a recap and returns its content without printing it, for
+/// commands that put it into their own output. Progress goes to stderr.
+pub async fn generate(
+    auth_service: &mut AuthService,
+    from: Option<&str>,
+    to: Option<&str>,
+    exclude_tags: Option<&[String]>,
+    project_identifier: Option<&str>,
+) -> Result<String, AppError> {
+    let mut output = RecapOutput::new(RecapFormat::Plain, None);
+    output.quiet = true;
+    let recap_response = submit(
+        auth_service,
+        from,
+        to,
+        None,
+        None,
+        exclude_tags,
+        project_identifier,
+        None,
+        false,
+        &mut output,
+    )
+    .await?;
+
+    wait_for_recap(auth_service.api_client(), &recap_response, &output).await
+}
+
+/// Submits a recap and returns without waiting for it, unless it was already
+/// generated. With `notify`, a detached `acc recap wait` shows a desktop
+/// notification once it's ready, using the same `profile`.
+#[allow(clippy::too_many_arguments)]
+pub async fn execute_background(
+    auth_service: &mut AuthService,
+    profile: &str,
+    from: Option<&str>,
+    to: Option<&str>,
+    since: Option<&str>,
+    tags: Option<&[String]>,
+    exclude_tags: Option<&[String]>,
+    project_identifier: Option<&str>,
+    audience: Option<&str>,
+    team: bool,
+    notify: bool,
+    format: RecapFormat,
+    destinations: RecapDestinations,
 ) -> Result<(), AppError> {
+    let mut output = RecapOutput::new(format, audience);
+    output.destinations = destinations;
+    let recap_response = submit(
+        auth_service,
+        from,
+        to,
+        since,
+        tags,
+        exclude_tags,
+        project_identifier,
+        audience,
+        team,
+        &mut output,
+    )
+    .await?;
+
+    // A cached recap is ready right away, so there's nothing to wait for
+    if recap_response.status == "completed" {
+        wait_for_recap(auth_service.api_client(), &recap_response, &output).await?;
+        return Ok(());
+    }
+
+    let recap_id = &recap_response.recap_id;
+    println!(
+        "{}",
+        format!("📨 Recap {recap_id} is being generated in the background.").bright_green()
+    );
+    if notify {
+        spawn_notifier(profile, recap_id)?;
+        println!("You'll get a notification when it's ready.");
+    }
+    println!("Run `acc recap wait {recap_id}` to see it.");
+    Ok(())
+}
+
+/// Waits for a recap submitted with `--background`, then prints it in `format`.
+pub async fn wait(
+    auth_service: &mut AuthService,
+    recap_id: &str,
+    notify: bool,
+    format: RecapFormat,
+    destinations: RecapDestinations,
+) -> Result<String, AppError> {
+    let api_client = auth_service.api_client();
+    let status = get_recap_status(api_client, recap_id)
+        .await
+        .map_err(|e| match e {
+            crate::api::errors::ApiError::NotFound(_) => {
+                AppError::Other(format!("No recap found with ID {recap_id}"))
+            }
+            _ => AppError::Other(format!("Failed to check recap status: {e}")),
+        })?;
+
+    // The audience and requested filters aren't known here, so tags are
+    // always shown and only the server's filters are reported
+    let mut output = RecapOutput::new(format, None);
+    output.destinations = destinations;
+    let result = match status.status.as_str() {
+        "completed" => match status.content {
+            Some(content) => Ok(print_recap_result(
+                recap_id,
+                content,
+                &status.metadata,
+                &status.filters,
+                &output,
+            )),
+            None => Err(AppError::Other(
+                "Recap completed but no content was returned".to_string(),
+            )),
+        },
+        "processing" => poll_for_completion(api_client, recap_id, &output).await,
+        "failed" => Err(AppError::Other(
+            "Recap generation failed. Please try again.".to_string(),
+        )),
+        other => Err(AppError::Other(format!("Unexpected recap status: {other}"))),
+    };
+
+    if notify {
+        match &result {
+            Ok(_) => notify::desktop(
+                "Accomplish recap ready",
+                &format!("Run `acc recap wait {recap_id}` to read it."),
+            ),
+            Err(e) => notify::desktop("Accomplish recap failed", &e.to_string()),
+        }
+    }
+    result
+}
+
+/// Explains why the server refused to generate a recap.
+fn recap_error(e: crate::api::errors::ApiError, team: bool) -> AppError {
+    match e {
+        crate::api::errors::ApiError::BadRequest(msg) => {
+            AppError::Other(format!("No worklog entries found for the specified filters.\n\nTry:\n• Expanding your date range\n• Removing project or tag filters\n• Using 'acc logs' to see available entries\n\nAPI response: {msg}"))
+        }
+        // A 401 is the token; the plan restriction comes as a 403
+        crate::api::errors::ApiError::Forbidden(msg) if team => {
+            AppError::Other(format!("Team recaps are only available to organization accounts.\n\nAPI response: {msg}"))
+        }
+        crate::api::errors::ApiError::Unauthorized(msg) => {
+            if msg.contains("not available") {
+                AppError::Other("The recap feature is not available on your current plan. Please upgrade to access AI-powered summaries.".to_string())
+            } else {
+                AppError::Other(format!("Authentication failed: {msg}"))
+            }
+        }
+        crate::api::errors::ApiError::RateLimited(_) => {
+            AppError::Other("You've reached your recap generation limit for this billing cycle. Limits reset monthly.".to_string())
+        }
+        _ => AppError::Other(format!("Failed to generate recap: {e}")),
+    }
+}
+
+/// Starts `acc recap wait <id> --notify` for `profile` detached from this
+/// terminal, so it outlives this process and doesn't write over the prompt.
+fn spawn_notifier(profile: &str, recap_id: &str) -> Result<(), AppError> {
+    notifier_command(std::env::current_exe()?, profile, recap_id).spawn()?;
+    Ok(())
+}
+
+fn notifier_command(exe: PathBuf, profile: &str, recap_id: &str) -> Command {
+    let mut command = Command::new(exe);
+    command
+        // The recap belongs to this profile's account
+        .args(["--profile", profile, "recap", "wait", recap_id, "--notify"])
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        // Its own process group, so closing the terminal doesn't take it down
+        command.process_group(0);
+    }
+    command
+}
+
+/// Resolves filters, asks the server for a recap, and returns its initial
+/// status. The filters as sent are recorded in `output`.
+#[allow(clippy::too_many_arguments)]
+async fn submit(
+    auth_service: &mut AuthService,
+    from: Option<&str>,
+    to: Option<&str>,
+    since: Option<&str>,
+    tags: Option<&[String]>,
+    exclude_tags: Option<&[String]>,
+    project_identifier: Option<&str>,
+    audience: Option<&str>,
+    team: bool,
+    output: &mut RecapOutput,
+) -> Result<RecapResponse, AppError> {
     // Handle date filtering
     let (from_date, to_date) = if let Some(since_duration) = since {
         if from.is_some() || to.is_some() {
@@ -47,25 +355,10 @@ pub async fn execute(
         (from.map(String::from), to.map(String::from))
     };
 
-    // Convert project identifier to UUID if provided
-    let project_ids = if let Some(identifier) = project_identifier {
-        let projects = project::get_projects(auth_service).await?;
-
-        let mut found_id = None;
-        for p in &projects {
-            if p.identifier.to_lowercase() == identifier.to_lowercase() {
-                found_id = Some(p.id.clone());
-                break;
-            }
-        }
-
-        if found_id.is_none() {
-            println!("⚠️ Warning: No project found with identifier '{identifier}");
-        }
-
-        found_id.map(|id| vec![id])
-    } else {
-        None
+    // Convert project identifier to the UUIDs of the project and its sub-projects
+    let project_ids = match project_identifier {
+        Some(identifier) => project::resolve_project_filter_ids(auth_service, identifier).await?,
+        None => None,
     };
 
     // Show what we're generating a recap for
@@ -76,14 +369,25 @@ pub async fn execute(
         tags,
         exclude_tags,
         project_identifier,
+        audience,
     );
 
-    println!(
+    let mut progress = output.progress();
+    let _ = writeln!(
+        progress,
+        "{}",
+        format!(
+            "🤖 Generating {}recap{filter_description}",
+            if team { "team " } else { "" }
+        )
+        .bright_blue()
+    );
+    let _ = write!(
+        progress,
         "{}",
-        format!("🤖 Generating recap{filter_description}").bright_blue()
+        "Analyzing worklog entries...".bright_black()
     );
-    print!("{}", "Analyzing worklog entries...".bright_black());
-    io::stdout().flush().unwrap();
+    let _ = progress.flush();
 
     // Get API client after project resolution to avoid borrowing conflicts
     let api_client = auth_service.api_client();
@@ -98,6 +402,20 @@ pub async fn execute(
         .and_then(|d| d.split('T').next())
         .map(String::from);
 
+    output.requested = Map::from_iter([
+        ("from".to_string(), json!(from_date_api)),
+        ("to".to_string(), json!(to_date_api)),
+        ("since".to_string(), json!(since)),
+        ("project".to_string(), json!(project_identifier)),
+        ("tags".to_string(), json!(tags.unwrap_or_default())),
+        (
+            "exclude_tags".to_string(),
+            json!(exclude_tags.unwrap_or_default()),
+        ),
+        ("audience".to_string(), json!(audience)),
+        ("team".to_string(), json!(team)),
+    ]);
+
     // Generate the recap
     let recap_response = generate_worklog_recap(
         api_client,
@@ -106,29 +424,25 @@ pub async fn execute(
         project_ids.as_deref(),
         tags,
         exclude_tags,
+        audience,
+        team,
     )
     .await
-    .map_err(|e| match e {
-        crate::api::errors::ApiError::BadRequest(msg) => {
-            AppError::Other(format!("No worklog entries found for the specified filters.\n\nTry:\n• Expanding your date range\n• Removing project or tag filters\n• Using 'acc logs' to see available entries\n\nAPI response: {msg}"))
-        }
-        crate::api::errors::ApiError::Unauthorized(msg) => {
-            if msg.contains("not available") {
-                AppError::Other("The recap feature is not available on your current plan. Please upgrade to access AI-powered summaries.".to_string())
-            } else {
-                AppError::Other(format!("Authentication failed: {msg}"))
-            }
-        }
-        crate::api::errors::ApiError::RateLimited => {
-            AppError::Other("You've reached your recap generation limit for this billing cycle. Limits reset monthly.".to_string())
-        }
-        _ => AppError::Other(format!("Failed to generate recap: {e}")),
-    })?;
+    .map_err(|e| recap_error(e, team))?;
 
     // Clear the "Analyzing..." message
-    print!("\r{}\r", " ".repeat(50));
-    io::stdout().flush().unwrap();
+    let _ = write!(progress, "\r{}\r", " ".repeat(50));
+    let _ = progress.flush();
+
+    Ok(recap_response)
+}
 
+/// Waits for a submitted recap to finish, then prints it and returns its content.
+async fn wait_for_recap(
+    api_client: &ApiClient,
+    recap_response: &RecapResponse,
+    output: &RecapOutput,
+) -> Result<String, AppError> {
     match recap_response.status.as_str() {
         "completed" => {
             // Cache hit - get the content immediately
@@ -139,57 +453,60 @@ pub async fn execute(
                     .map_err(|e| AppError::Other(format!("Failed to fetch recap content: {e}")))?;
 
                 if let Some(content) = status_response.content {
-                    print_recap_result(
-                        &content,
+                    Ok(print_recap_result(
+                        recap_id,
+                        content,
                         &status_response.metadata,
                         &status_response.filters,
-                    );
+                        output,
+                    ))
                 } else {
-                    return Err(AppError::Other(
+                    Err(AppError::Other(
                         "Recap completed but no content was returned".to_string(),
-                    ));
+                    ))
                 }
             } else {
-                return Err(AppError::Other(
+                Err(AppError::Other(
                     "Recap completed but no poll URL was provided".to_string(),
-                ));
+                ))
             }
         }
         "processing" => {
-            println!("{}", "✨ Generating your recap...".bright_green());
+            let _ = writeln!(
+                output.progress(),
+                "{}",
+                "✨ Generating your recap...".bright_green()
+            );
 
             let recap_id = &recap_response.recap_id;
 
             // Try SSE first if available, otherwise fall back to polling
             if let Some(sse_url) = &recap_response.sse_url {
-                match try_sse_completion(api_client, sse_url, recap_id).await {
-                    Ok(result) => return result,
+                match try_sse_completion(api_client, sse_url, recap_id, output).await {
+                    Ok(result) => result,
                     Err(_) => {
                         // SSE failed, fall back to polling
-                        return poll_for_completion(api_client, recap_id).await;
+                        poll_for_completion(api_client, recap_id, output).await
                     }
                 }
             } else {
                 // No SSE URL provided, use polling
-                return poll_for_completion(api_client, recap_id).await;
+                poll_for_completion(api_client, recap_id, output).await
             }
         }
-        _ => {
-            return Err(AppError::Other(format!(
-                "Unexpected recap status: {}",
-                recap_response.status
-            )));
-        }
+        _ => Err(AppError::Other(format!(
+            "Unexpected recap status: {}",
+            recap_response.status
+        ))),
     }
-
-    Ok(())
 }
 
 async fn try_sse_completion(
     api_client: &crate::api::client::ApiClient,
     sse_url: &str,
     recap_id: &str,
-) -> Result<Result<(), AppError>, AppError> {
+    output: &RecapOutput,
+) -> Result<Result<String, AppError>, AppError> {
     // Extract the endpoint from the full SSE URL
     // The sse_url comes as a full URL like "http://localhost:4000/api/v1/worklog/recaps/sse?recap_id=123"
     // We need to extract the path portion for the API client
@@ -226,33 +543,14 @@ async fn try_sse_completion(
             }
         };
 
-    use std::time::Instant;
-    let start_time = Instant::now();
-    let mut spinner_index = 0;
-    const SPINNER_CHARS: &[char] = &['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+    let mut progress = RecapProgress::new(output.progress_on_stderr());
 
     loop {
-        // Display spinner
-        let elapsed = start_time.elapsed();
-        let seconds = elapsed.as_secs();
-        let spinner_char = SPINNER_CHARS[spinner_index % SPINNER_CHARS.len()];
-
-        print!(
-            "\r{} {}... ({}s)",
-            spinner_char.to_string().bright_red(),
-            "Generating your recap".bright_red(),
-            seconds
-        );
-        io::stdout().flush().unwrap();
-
-        // Check for SSE events
-        match timeout(Duration::from_millis(100), sse_stream.next()).await {
-            Ok(Some(Ok(event))) => {
+        match sse_stream.next().await {
+            Some(Ok(event)) => {
                 match event.status.as_str() {
                     "completed" => {
-                        // Clear spinner
-                        print!("\r{}\r", " ".repeat(80));
-                        io::stdout().flush().unwrap();
+                        progress.finish();
 
                         // Get the final content from the polling endpoint
                         // Retry a couple times to ensure backend has fully populated metadata
@@ -272,12 +570,13 @@ async fn try_sse_completion(
                                             .unwrap_or(false);
 
                                         if has_metadata || attempt == 2 {
-                                            print_recap_result(
-                                                &content,
+                                            return Ok(Ok(print_recap_result(
+                                                recap_id,
+                                                content,
                                                 &status_response.metadata,
                                                 &status_response.filters,
-                                            );
-                                            return Ok(Ok(()));
+                                                output,
+                                            )));
                                         }
                                         // If no metadata yet and not last attempt, continue retrying
                                     } else {
@@ -304,18 +603,16 @@ async fn try_sse_completion(
                         )));
                     }
                     "failed" => {
-                        print!("\r{}\r", " ".repeat(80));
-                        io::stdout().flush().unwrap();
+                        progress.finish();
                         return Ok(Err(AppError::Other(
                             "Recap generation failed. Please try again.".to_string(),
                         )));
                     }
                     "processing" => {
-                        // Continue listening
+                        progress.update(event.progress, event.stage.as_deref());
                     }
                     _ => {
-                        print!("\r{}\r", " ".repeat(80));
-                        io::stdout().flush().unwrap();
+                        progress.finish();
                         return Ok(Err(AppError::Other(format!(
                             "Unexpected recap status: {}",
                             event.status
@@ -323,23 +620,16 @@ async fn try_sse_completion(
                     }
                 }
             }
-            Ok(Some(Err(e))) => {
+            Some(Err(e)) => {
                 // SSE stream error - fall back to polling
-                print!("\r{}\r", " ".repeat(80));
-                io::stdout().flush().unwrap();
+                progress.finish();
                 return Err(AppError::Other(format!("SSE stream error: {e}")));
             }
-            Ok(None) => {
+            None => {
                 // Stream ended unexpectedly - fall back to polling
-                print!("\r{}\r", " ".repeat(80));
-                io::stdout().flush().unwrap();
+                progress.finish();
                 return Err(AppError::Other("SSE stream ended unexpectedly".to_string()));
             }
-            Err(_) => {
-                // Timeout - continue with next spinner frame
-                spinner_index += 1;
-                tokio::time::sleep(Duration::from_millis(100)).await;
-            }
         }
     }
 }
@@ -347,8 +637,12 @@ async fn try_sse_completion(
 async fn poll_for_completion(
     api_client: &crate::api::client::ApiClient,
     recap_id: &str,
-) -> Result<(), AppError> {
+    output: &RecapOutput,
+) -> Result<String, AppError> {
     let mut spinner = Spinner::new();
+    if output.progress_on_stderr() {
+        spinner = spinner.on_stderr();
+    }
 
     spinner
         .spin_with_callback(|| async {
@@ -356,12 +650,13 @@ async fn poll_for_completion(
                 Ok(status_response) => match status_response.status.as_str() {
                     "completed" => {
                         if let Some(content) = status_response.content {
-                            print_recap_result(
-                                &content,
+                            Some(Ok(print_recap_result(
+                                recap_id,
+                                content,
                                 &status_response.metadata,
                                 &status_response.filters,
-                            );
-                            Some(Ok(()))
+                                output,
+                            )))
                         } else {
                             Some(Err(AppError::Other(
                                 "Recap completed but no content was returned".to_string(),
@@ -385,11 +680,69 @@ async fn poll_for_completion(
         .await
 }
 
+/// Prints a finished recap after running it through the configured filters,
+/// returning the filtered content.
 fn print_recap_result(
-    content: &str,
-    metadata: &Option<crate::api::models::RecapMetadata>,
-    filters: &Option<crate::api::models::RecapFilters>,
-) {
+    recap_id: &str,
+    content: String,
+    metadata: &Option<RecapMetadata>,
+    filters: &Option<RecapFilters>,
+    output: &RecapOutput,
+) -> String {
+    let content = match metadata {
+        Some(meta) if !meta.members.is_empty() => with_member_sections(&content, &meta.members),
+        _ => content,
+    };
+    let content = match FILTERS.get() {
+        Some(pipeline) if !pipeline.is_empty() => pipeline.apply(&content),
+        _ => content,
+    };
+    if output.quiet {
+        return content;
+    }
+    let generated_at = Utc::now();
+    deliver(
+        &render_json(
+            recap_id,
+            &content,
+            metadata.as_ref(),
+            filters.as_ref(),
+            generated_at,
+            output,
+        ),
+        output,
+    );
+    let show_tags = output.show_tags;
+    match output.format {
+        RecapFormat::Text => {}
+        RecapFormat::Md => {
+            println!(
+                "{}",
+                render_markdown(&content, metadata.as_ref(), show_tags)
+            );
+            return content;
+        }
+        RecapFormat::Plain => {
+            println!("{}", render_plain(&content, metadata.as_ref(), show_tags));
+            return content;
+        }
+        RecapFormat::Json => {
+            let json = render_json(
+                recap_id,
+                &content,
+                metadata.as_ref(),
+                filters.as_ref(),
+                generated_at,
+                output,
+            );
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&json).unwrap_or_default()
+            );
+            return content;
+        }
+    }
+
     println!("{}", content.white());
     println!();
 
@@ -400,6 +753,11 @@ fn print_recap_result(
             format!("📊 Processed {} worklog entries", meta.entry_count).purple()
         );
 
+        if !meta.members.is_empty() {
+            let names: Vec<&str> = meta.members.iter().map(|m| m.name.as_str()).collect();
+            println!("{}", format!("👥 Team: {}", names.join(", ")).purple());
+        }
+
         // Show projects found in the data (if any)
         if !meta.projects.is_empty() {
             println!(
@@ -409,7 +767,7 @@ fn print_recap_result(
         }
 
         // Show tags found in the data (if any)
-        if !meta.tags.is_empty() {
+        if show_tags && !meta.tags.is_empty() {
             println!("{}", format!("🏷️  Tags: {}", meta.tags.join(", ")).purple());
         }
 
@@ -435,6 +793,202 @@ fn print_recap_result(
     }
 
     println!("{}", "✅ Recap complete!".bright_green());
+    content
+}
+
+/// A team recap's overall summary followed by a section per person, as
+/// Markdown. This is what's printed, filtered and returned for team recaps.
+fn with_member_sections(content: &str, members: &[RecapMember]) -> String {
+    let mut out = content.trim_end().to_string();
+    for member in members {
+        let entries = match member.entry_count {
+            1 => "1 entry".to_string(),
+            n => format!("{n} entries"),
+        };
+        out.push_str(&format!(
+            "\n\n## {} ({entries})\n\n{}",
+            member.name,
+            member.content.trim()
+        ));
+    }
+    out.push('\n');
+    out
+}
+
+/// The recap as written, then a one-line summary of what went into it.
+fn render_markdown(content: &str, metadata: Option<&RecapMetadata>, show_tags: bool) -> String {
+    match summary_line(metadata, show_tags) {
+        Some(summary) => format!("{}\n\n---\n\n_{summary}_", content.trim_end()),
+        None => content.trim_end().to_string(),
+    }
+}
+
+/// Like `render_markdown`, with the markup taken out.
+fn render_plain(content: &str, metadata: Option<&RecapMetadata>, show_tags: bool) -> String {
+    let content = strip_markdown(content);
+    match summary_line(metadata, show_tags) {
+        Some(summary) => format!("{}\n\n{summary}", content.trim_end()),
+        None => content.trim_end().to_string(),
+    }
+}
+
+fn render_json(
+    recap_id: &str,
+    content: &str,
+    metadata: Option<&RecapMetadata>,
+    filters: Option<&RecapFilters>,
+    generated_at: DateTime<Utc>,
+    output: &RecapOutput,
+) -> Value {
+    let mut requested = output.requested.clone();
+    if let Some(filters) = filters {
+        requested.insert("project_ids".to_string(), json!(filters.project_ids));
+        requested
+            .entry("tags")
+            .or_insert_with(|| json!(filters.tags));
+    }
+    let metadata = metadata.map(|meta| {
+        let mut value = json!({
+            "entry_count": meta.entry_count,
+            "projects": meta.projects,
+        });
+        if output.show_tags {
+            value["tags"] = json!(meta.tags);
+        }
+        if !meta.members.is_empty() {
+            value["members"] = meta
+                .members
+                .iter()
+                .map(|member| {
+                    json!({
+                        "name": member.name,
+                        "email": member.email,
+                        "entry_count": member.entry_count,
+                        "projects": member.projects,
+                        "content": member.content,
+                    })
+                })
+                .collect();
+        }
+        value
+    });
+
+    json!({
+        "recap_id": recap_id,
+        "generated_at": generated_at.to_rfc3339(),
+        "content": content,
+        "metadata": metadata,
+        "filters": requested,
+    })
+}
+
+/// Saves the recap to `destinations.file` and runs the `post-recap` hooks with
+/// `document`, the recap as `render_json` describes it. Neither stops the recap
+/// from being printed, so failures are only reported.
+fn deliver(document: &Value, output: &RecapOutput) {
+    let destinations = &output.destinations;
+    let mut data = document.clone();
+
+    if let Some(path) = &destinations.file {
+        let as_json = output.format == RecapFormat::Json
+            || path
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+        let contents = if as_json {
+            serde_json::to_string_pretty(document).unwrap_or_default() + "\n"
+        } else {
+            with_front_matter(document)
+        };
+        match fs::write(path, contents) {
+            Ok(()) => {
+                let _ = writeln!(
+                    output.progress(),
+                    "{}",
+                    format!("💾 Saved recap to {}", path.display()).bright_green()
+                );
+                data["file"] = json!(path);
+            }
+            Err(e) => eprintln!(
+                "⚠️  Warning: Could not save the recap to {}: {e}",
+                path.display()
+            ),
+        }
+    }
+
+    if let Some(hooks) = &destinations.hooks {
+        // post-* hooks only warn on failure
+        let _ = hooks.run(HookEvent::PostRecap, data);
+    }
+}
+
+/// The recap as a Markdown document, with everything else in `document`
+/// (see `render_json`) as YAML front matter for tools that index recaps.
+/// Values are written as JSON, which YAML reads as they are.
+fn with_front_matter(document: &Value) -> String {
+    let mut fields: Vec<(&str, Value)> = ["recap_id", "generated_at"]
+        .into_iter()
+        .map(|key| (key, document[key].clone()))
+        .collect();
+    if let Some(metadata) = document["metadata"].as_object() {
+        for (key, value) in metadata {
+            let mut value = value.clone();
+            // Each member's section is already in the body
+            if let Some(members) = value.as_array_mut().filter(|_| key == "members") {
+                for member in members.iter_mut().filter_map(Value::as_object_mut) {
+                    member.remove("content");
+                }
+            }
+            fields.push((key, value));
+        }
+    }
+    fields.push(("filters", document["filters"].clone()));
+
+    let mut out = String::from("---\n");
+    for (key, value) in fields {
+        out.push_str(&format!("{key}: {value}\n"));
+    }
+    out.push_str("---\n\n");
+    out.push_str(document["content"].as_str().unwrap_or("").trim_end());
+    out.push('\n');
+    out
+}
+
+/// "12 worklog entries · Projects: web, api · Tags: ci", or None without
+/// metadata.
+fn summary_line(metadata: Option<&RecapMetadata>, show_tags: bool) -> Option<String> {
+    let meta = metadata?;
+    let mut parts = vec![match meta.entry_count {
+        1 => "1 worklog entry".to_string(),
+        n => format!("{n} worklog entries"),
+    }];
+    if !meta.projects.is_empty() {
+        parts.push(format!("Projects: {}", meta.projects.join(", ")));
+    }
+    if show_tags && !meta.tags.is_empty() {
+        parts.push(format!("Tags: {}", meta.tags.join(", ")));
+    }
+    Some(parts.join(" · "))
+}
+
+/// Removes Markdown markup: heading and quote markers, emphasis, inline code
+/// and rules. Links become "text (url)"; list markers are kept.
+fn strip_markdown(content: &str) -> String {
+    let link = Regex::new(r"\[([^\]]+)\]\(([^)]+)\)").expect("valid regex");
+    let heading = Regex::new(r"^\s{0,3}(#{1,6}|>)\s*").expect("valid regex");
+    let rule = Regex::new(r"^\s*([-*_])(\s*[-*_]){2,}\s*$").expect("valid regex");
+
+    content
+        .lines()
+        .map(|line| {
+            if rule.is_match(line) {
+                return String::new();
+            }
+            let line = heading.replace(line, "");
+            let line = link.replace_all(&line, "$1 ($2)");
+            line.replace("**", "").replace("__", "").replace('`', "")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
 }
 
 fn build_filter_description(
@@ -444,6 +998,7 @@ fn build_filter_description(
     tags: Option<&[String]>,
     exclude_tags: Option<&[String]>,
     project: Option<&str>,
+    audience: Option<&str>,
 ) -> String {
     let mut parts = Vec::new();
 
@@ -487,9 +1042,157 @@ fn build_filter_description(
         }
     }
 
+    if let Some(audience) = audience {
+        if audience != "self" {
+            parts.push(format!("written for your {audience}"));
+        }
+    }
+
     if parts.is_empty() {
         " for today".to_string()
     } else {
         format!(" {}", parts.join(", "))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_a_forbidden_team_recap_is_blamed_on_the_plan() {
+        use crate::api::errors::ApiError;
+
+        let forbidden = recap_error(ApiError::Forbidden("org only".to_string()), true);
+        assert!(forbidden.to_string().contains("organization accounts"));
+        let expired = recap_error(ApiError::Unauthorized("token expired".to_string()), true);
+        assert!(!expired.to_string().contains("organization accounts"));
+        assert!(expired.to_string().contains("Authentication failed"));
+    }
+
+    #[test]
+    fn test_notifier_waits_with_the_same_profile() {
+        let command = notifier_command(PathBuf::from("acc"), "work", "recap-1");
+        let args: Vec<_> = command.get_args().collect();
+        assert_eq!(
+            args,
+            ["--profile", "work", "recap", "wait", "recap-1", "--notify"]
+        );
+    }
+
+    fn metadata() -> RecapMetadata {
+        serde_json::from_value(json!({
+            "entry_count": 3,
+            "projects": ["web"],
+            "tags": ["ci"],
+        }))
+        .unwrap()
+    }
+
+    fn at() -> DateTime<Utc> {
+        "2025-07-04T10:00:00Z".parse().unwrap()
+    }
+
+    #[test]
+    fn test_markdown_and_plain_formats() {
+        let content = "## This week\n\n- Shipped **login** via [PR](https://x.dev/1)\n- Fixed `ci`\n\n---\n> Next: docs\n";
+
+        let md = render_markdown(content, Some(&metadata()), true);
+        assert!(md.starts_with("## This week"));
+        assert!(md.ends_with("---\n\n_3 worklog entries · Projects: web · Tags: ci_"));
+
+        let plain = render_plain(content, Some(&metadata()), false);
+        assert_eq!(
+            plain,
+            "This week\n\n- Shipped login via PR (https://x.dev/1)\n- Fixed ci\n\n\nNext: docs\n\n3 worklog entries · Projects: web"
+        );
+    }
+
+    #[test]
+    fn test_json_format_has_metadata_and_filters() {
+        let mut output = RecapOutput::new(RecapFormat::Json, Some("client"));
+        output
+            .requested
+            .insert("from".to_string(), json!("2025-07-01"));
+        let filters: RecapFilters =
+            serde_json::from_value(json!({ "project_ids": ["p1"], "tags": ["ci"] })).unwrap();
+
+        let value = render_json(
+            "r1",
+            "Done",
+            Some(&metadata()),
+            Some(&filters),
+            at(),
+            &output,
+        );
+        assert_eq!(value["recap_id"], "r1");
+        assert_eq!(value["generated_at"], "2025-07-04T10:00:00+00:00");
+        assert_eq!(value["content"], "Done");
+        assert_eq!(
+            value["metadata"],
+            json!({ "entry_count": 3, "projects": ["web"] })
+        );
+        assert_eq!(value["filters"]["from"], "2025-07-01");
+        assert_eq!(value["filters"]["project_ids"], json!(["p1"]));
+        assert_eq!(value["filters"]["tags"], json!(["ci"]));
+    }
+
+    #[test]
+    fn test_team_recaps_get_a_section_per_person() {
+        let metadata: RecapMetadata = serde_json::from_value(json!({
+            "entry_count": 4,
+            "members": [
+                { "name": "Ana", "entry_count": 3, "content": "Shipped invoices.\n" },
+                { "name": "Sam", "entry_count": 1, "content": "Reviewed PRs." },
+            ],
+        }))
+        .unwrap();
+
+        assert_eq!(
+            with_member_sections("The team shipped billing.\n", &metadata.members),
+            "The team shipped billing.\n\n## Ana (3 entries)\n\nShipped invoices.\n\n## Sam (1 entry)\n\nReviewed PRs.\n"
+        );
+
+        let output = RecapOutput::new(RecapFormat::Json, None);
+        let value = render_json("r1", "Done", Some(&metadata), None, at(), &output);
+        assert_eq!(value["metadata"]["members"][0]["name"], "Ana");
+        assert_eq!(value["metadata"]["members"][1]["entry_count"], 1);
+        assert_eq!(value["metadata"]["members"][1]["email"], Value::Null);
+    }
+
+    #[test]
+    fn test_saved_recaps_carry_their_metadata() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut output = RecapOutput::new(RecapFormat::Md, None);
+        output.requested.insert("project".to_string(), json!("WEB"));
+        let document = render_json(
+            "r1",
+            "Shipped login.\n",
+            Some(&metadata()),
+            None,
+            at(),
+            &output,
+        );
+
+        output.destinations.file = Some(dir.path().join("recap.md"));
+        deliver(&document, &output);
+        assert_eq!(
+            fs::read_to_string(dir.path().join("recap.md")).unwrap(),
+            "---\n\
+             recap_id: \"r1\"\n\
+             generated_at: \"2025-07-04T10:00:00+00:00\"\n\
+             entry_count: 3\n\
+             projects: [\"web\"]\n\
+             tags: [\"ci\"]\n\
+             filters: {\"project\":\"WEB\"}\n\
+             ---\n\nShipped login.\n"
+        );
+
+        output.destinations.file = Some(dir.path().join("recap.json"));
+        deliver(&document, &output);
+        let saved: Value =
+            serde_json::from_str(&fs::read_to_string(dir.path().join("recap.json")).unwrap())
+                .unwrap();
+        assert_eq!(saved, document);
+    }
+}
@@ -0,0 +1,622 @@
+use crate::api::endpoints;
+use crate::api::models::Repository as ServerRepository;
+use crate::auth::AuthService;
+use crate::commands::capture::{normalize_git_url, resolve_repository_dir};
+use crate::commands::init::{create_global_config, is_globally_tracked};
+use crate::commands::project::{self, Project};
+use crate::errors::AppError;
+use crate::utils::prompt::PromptOrDefault;
+use git2::Repository;
+use inquire::{Confirm, InquireError, Select};
+use serde_json::{Map, Value};
+use std::env;
+use std::path::Path;
+use tabled::settings::Style;
+use tabled::{Table, Tabled};
+
+/// Branch names tried, in order, when the recorded default branch is gone and
+/// the remote doesn't say which one is the default.
+const COMMON_DEFAULT_BRANCHES: &[&str] = &["main", "master", "trunk", "develop"];
+
+#[derive(Tabled)]
+struct RepositoryTableRow {
+    #[tabled(rename = "ID")]
+    id: String,
+    #[tabled(rename = "Project")]
+    project: String,
+    #[tabled(rename = "Name")]
+    name: String,
+    #[tabled(rename = "Path")]
+    path: String,
+    #[tabled(rename = "Branch")]
+    branch: String,
+}
+
+/// Lists repositories grouped by project, in the order `acc project list`
+/// shows projects, optionally only those of one project and its sub-projects.
+pub async fn list(
+    auth_service: &mut AuthService,
+    project_identifier: Option<&str>,
+) -> Result<(), AppError> {
+    let projects = project::get_projects(auth_service).await?;
+    let mut repositories = endpoints::fetch_repositories(auth_service.api_client())
+        .await
+        .map_err(AppError::Api)?;
+
+    if let Some(spec) = project_identifier {
+        let project = project::match_project(&projects, spec).ok_or_else(|| {
+            AppError::Other(format!(
+                "No project found with identifier '{spec}'. See `acc project list`"
+            ))
+        })?;
+        let ids = project::project_and_descendant_ids(&projects, project);
+        repositories.retain(|repo| repo.project_id.as_ref().is_some_and(|id| ids.contains(id)));
+    }
+
+    if repositories.is_empty() {
+        println!("No repositories found. Run `acc init` in a clone to add one.");
+        return Ok(());
+    }
+
+    let rows = table_rows(&projects, repositories);
+    println!("{}", Table::new(rows).with(Style::modern()));
+    Ok(())
+}
+
+fn table_rows(
+    projects: &[Project],
+    mut repositories: Vec<ServerRepository>,
+) -> Vec<RepositoryTableRow> {
+    // Repositories of projects that are gone or unknown go last
+    let position = |repo: &ServerRepository| {
+        repo.project_id
+            .as_deref()
+            .and_then(|id| projects.iter().position(|p| p.id == id))
+            .unwrap_or(usize::MAX)
+    };
+    repositories.sort_by(|a, b| {
+        position(a)
+            .cmp(&position(b))
+            .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+    });
+
+    repositories
+        .into_iter()
+        .map(|repo| RepositoryTableRow {
+            id: short_id(&repo.id).to_string(),
+            project: repo
+                .project_id
+                .as_deref()
+                .and_then(|id| projects.iter().find(|p| p.id == id))
+                .map_or_else(|| "-".to_string(), |p| p.identifier.to_uppercase()),
+            name: repo.name,
+            path: repo
+                .local_path
+                .or(repo.remote_url)
+                .unwrap_or_else(|| "-".to_string()),
+            branch: repo.default_branch.unwrap_or_else(|| "-".to_string()),
+        })
+        .collect()
+}
+
+/// Enough of a repository ID to tell it apart in a list.
+fn short_id(id: &str) -> &str {
+    id.get(..8).unwrap_or(id)
+}
+
+/// Finds a repository by ID, unique ID prefix (as `acc repo list` shows), or
+/// name.
+fn find_repository<'a>(
+    repositories: &'a [ServerRepository],
+    spec: &str,
+) -> Result<&'a ServerRepository, AppError> {
+    let spec = spec.trim();
+    if let Some(repo) = repositories.iter().find(|repo| repo.id == spec) {
+        return Ok(repo);
+    }
+
+    let matches: Vec<&ServerRepository> = repositories
+        .iter()
+        .filter(|repo| !spec.is_empty() && repo.id.starts_with(spec))
+        .chain(
+            repositories
+                .iter()
+                .filter(|repo| repo.name.eq_ignore_ascii_case(spec)),
+        )
+        .collect();
+    match matches.as_slice() {
+        [] => Err(AppError::Other(format!(
+            "No repository found matching '{spec}'. See `acc repo list`"
+        ))),
+        [repo] => Ok(repo),
+        [first, rest @ ..] if rest.iter().all(|repo| repo.id == first.id) => Ok(first),
+        _ => Err(AppError::Other(format!(
+            "'{spec}' matches {} repositories. Use the ID shown by `acc repo list`",
+            matches.len()
+        ))),
+    }
+}
+
+async fn fetch_repository(
+    auth_service: &mut AuthService,
+    spec: &str,
+) -> Result<ServerRepository, AppError> {
+    let repositories = endpoints::fetch_repositories(auth_service.api_client())
+        .await
+        .map_err(AppError::Api)?;
+    find_repository(&repositories, spec).cloned()
+}
+
+/// Associates the clone in the current directory with a repository already
+/// registered (on another machine, say): records this path, remote and
+/// default branch on the server, and tracks the directory with the
+/// repository's project unless it's set up already. Without `spec`, the
+/// repository is found by remote URL, or picked from a list.
+pub async fn link(auth_service: &mut AuthService, spec: Option<&str>) -> Result<(), AppError> {
+    let dir = resolve_repository_dir(&env::current_dir()?)?;
+    let git_repo = Repository::open(&dir)
+        .map_err(|_| AppError::Other(format!("'{}' is not a git repository", dir.display())))?;
+    let remote_url = local_remote_url(&git_repo);
+
+    let repositories = endpoints::fetch_repositories(auth_service.api_client())
+        .await
+        .map_err(AppError::Api)?;
+    let repo = match spec {
+        Some(spec) => find_repository(&repositories, spec)?.clone(),
+        None => {
+            let by_remote = remote_url.as_deref().and_then(|url| {
+                repositories.iter().find(|repo| {
+                    repo.remote_url.as_deref().is_some_and(|recorded| {
+                        normalize_git_url(recorded) == normalize_git_url(url)
+                    })
+                })
+            });
+            match by_remote {
+                Some(repo) => repo.clone(),
+                None => choose_repository(&repositories)?,
+            }
+        }
+    };
+
+    let local_path = dir.to_string_lossy().to_string();
+    let mut changes = Map::new();
+    changes.insert("local_path".to_string(), Value::String(local_path.clone()));
+    if let Some(url) = &remote_url {
+        changes.insert("remote_url".to_string(), Value::String(url.clone()));
+    }
+    if let Some(branch) = local_default_branch(&git_repo, repo.default_branch.as_deref()) {
+        changes.insert("default_branch".to_string(), Value::String(branch));
+    }
+    endpoints::update_repository(auth_service.api_client(), &repo.id, Value::Object(changes))
+        .await
+        .map_err(AppError::Api)?;
+    println!("✓ Linked {} to {}", display_name(&repo), dir.display());
+
+    let configured = dir.join(".accomplish.toml").exists() || is_globally_tracked(&dir)?;
+    if !configured {
+        let projects = project::get_projects(auth_service).await?;
+        if let Some(project) = repo
+            .project_id
+            .as_deref()
+            .and_then(|id| projects.iter().find(|p| p.id == id))
+        {
+            create_global_config(&dir, project, true)?;
+            println!(
+                "✓ Directory globally tracked with project '{}' ({})",
+                project.name,
+                project.identifier.to_uppercase()
+            );
+        }
+    }
+    Ok(())
+}
+
+fn choose_repository(repositories: &[ServerRepository]) -> Result<ServerRepository, AppError> {
+    if repositories.is_empty() {
+        return Err(AppError::Other(
+            "No repositories to link to. Run `acc init` to add this one".to_string(),
+        ));
+    }
+    let labels: Vec<String> = repositories
+        .iter()
+        .map(|repo| {
+            let location = repo.remote_url.as_deref().or(repo.local_path.as_deref());
+            match location {
+                Some(location) => format!("{} ({location})", display_name(repo)),
+                None => display_name(repo).to_string(),
+            }
+        })
+        .collect();
+    let choice = Select::new("Link this directory to which repository?", labels)
+        .with_help_message("Use arrow keys to navigate, Enter to select")
+        .raw_prompt()
+        .map_err(|e| match e {
+            InquireError::NotTTY => AppError::Other(
+                "No repository has this clone's remote URL. Pass the repository to link"
+                    .to_string(),
+            ),
+            e => AppError::Other(e.to_string()),
+        })?;
+    Ok(repositories[choice.index].clone())
+}
+
+/// Changes a repository's name, remote URL, default branch or project.
+pub async fn update(
+    auth_service: &mut AuthService,
+    spec: &str,
+    name: Option<&str>,
+    remote_url: Option<&str>,
+    default_branch: Option<&str>,
+    project_identifier: Option<&str>,
+) -> Result<(), AppError> {
+    let mut changes = Map::new();
+    if let Some(name) = name {
+        if name.trim().is_empty() {
+            return Err(AppError::ParseError(
+                "Repository name cannot be empty".to_string(),
+            ));
+        }
+        changes.insert("name".to_string(), Value::String(name.trim().to_string()));
+    }
+    if let Some(url) = remote_url {
+        changes.insert(
+            "remote_url".to_string(),
+            Value::String(url.trim().to_string()),
+        );
+    }
+    if let Some(branch) = default_branch {
+        changes.insert(
+            "default_branch".to_string(),
+            Value::String(branch.trim().to_string()),
+        );
+    }
+    if changes.is_empty() && project_identifier.is_none() {
+        return Err(AppError::Other(
+            "Nothing to update. Pass --name, --remote-url, --default-branch or --project"
+                .to_string(),
+        ));
+    }
+
+    let repo = fetch_repository(auth_service, spec).await?;
+    if let Some(spec) = project_identifier {
+        let projects = project::get_projects(auth_service).await?;
+        let project = project::match_project(&projects, spec).ok_or_else(|| {
+            AppError::Other(format!(
+                "No project found with identifier '{spec}'. See `acc project list`"
+            ))
+        })?;
+        changes.insert("project_id".to_string(), Value::String(project.id.clone()));
+    }
+
+    endpoints::update_repository(auth_service.api_client(), &repo.id, Value::Object(changes))
+        .await
+        .map_err(AppError::Api)?;
+    println!(
+        "✓ Repository {} updated",
+        name.unwrap_or(display_name(&repo))
+    );
+    Ok(())
+}
+
+/// Removes a repository after asking for confirmation, unless `force` is set.
+pub async fn remove(
+    auth_service: &mut AuthService,
+    spec: &str,
+    force: bool,
+) -> Result<(), AppError> {
+    let repo = fetch_repository(auth_service, spec).await?;
+
+    if !force {
+        let confirmed = Confirm::new(&format!("Remove repository {}?", display_name(&repo)))
+            .with_help_message("Entries and commits already captured from it are kept")
+            .with_default(false)
+            .prompt_or_default()
+            .map_err(|e| match e {
+                InquireError::NotTTY => AppError::Other(
+                    "Can't ask for confirmation without a terminal. Use --force to remove anyway"
+                        .to_string(),
+                ),
+                e => AppError::Other(e.to_string()),
+            })?;
+        if !confirmed {
+            println!("Nothing removed.");
+            return Ok(());
+        }
+    }
+
+    endpoints::delete_repository(auth_service.api_client(), &repo.id)
+        .await
+        .map_err(AppError::Api)?;
+    println!("🗑️  Removed repository {}", display_name(&repo));
+    Ok(())
+}
+
+fn display_name(repo: &ServerRepository) -> &str {
+    if repo.name.is_empty() {
+        repo.local_path.as_deref().unwrap_or(&repo.id)
+    } else {
+        &repo.name
+    }
+}
+
+/// A field whose value on the server no longer matches the local clone.
+#[derive(Debug, PartialEq, Eq)]
+struct Drift {
+    field: &'static str,
+    recorded: Option<String>,
+    actual: String,
+}
+
+/// Compares every repository registered on the server that has a clone on this
+/// machine with its local git state, and updates `remote_url` and
+/// `default_branch` where they have drifted.
+pub async fn sync(auth_service: &mut AuthService, dry_run: bool) -> Result<(), AppError> {
+    let repositories = endpoints::fetch_repositories(auth_service.api_client())
+        .await
+        .map_err(AppError::Api)?;
+
+    let mut checked = 0;
+    let mut updated = 0;
+    for repo in &repositories {
+        let Some(local_path) = repo.local_path.as_deref() else {
+            continue;
+        };
+        let name = Some(repo.name.as_str())
+            .filter(|name| !name.is_empty())
+            .unwrap_or(local_path);
+
+        let Ok(git_repo) = Repository::open(local_path) else {
+            // Cloned on another machine, or moved since
+            continue;
+        };
+        checked += 1;
+
+        let drift = detect_drift(
+            repo.remote_url.as_deref(),
+            repo.default_branch.as_deref(),
+            local_remote_url(&git_repo).as_deref(),
+            local_default_branch(&git_repo, repo.default_branch.as_deref()).as_deref(),
+        );
+        if drift.is_empty() {
+            continue;
+        }
+
+        println!("{name} ({})", Path::new(local_path).display());
+        let mut changes = Map::new();
+        for d in &drift {
+            println!(
+                "   {}: {} → {}",
+                d.field,
+                d.recorded.as_deref().unwrap_or("(none)"),
+                d.actual
+            );
+            changes.insert(d.field.to_string(), Value::String(d.actual.clone()));
+        }
+
+        if !dry_run {
+            endpoints::update_repository(
+                auth_service.api_client(),
+                &repo.id,
+                Value::Object(changes),
+            )
+            .await
+            .map_err(AppError::Api)?;
+        }
+        updated += 1;
+    }
+
+    if updated == 0 {
+        println!("✅ All {checked} local repositories are up to date");
+    } else if dry_run {
+        println!("\n🔎 Dry run: {updated} of {checked} repositories would be updated");
+    } else {
+        println!("\n✅ Updated {updated} of {checked} repositories");
+    }
+    Ok(())
+}
+
+/// Works out which recorded fields differ from the local state. Remote URLs are
+/// compared normalized, so switching between SSH and HTTPS isn't drift, and a
+/// value that can't be determined locally is left alone.
+fn detect_drift(
+    recorded_remote: Option<&str>,
+    recorded_branch: Option<&str>,
+    actual_remote: Option<&str>,
+    actual_branch: Option<&str>,
+) -> Vec<Drift> {
+    let mut drift = Vec::new();
+
+    if let Some(actual) = actual_remote {
+        let same = recorded_remote
+            .is_some_and(|recorded| normalize_git_url(recorded) == normalize_git_url(actual));
+        if !same {
+            drift.push(Drift {
+                field: "remote_url",
+                recorded: recorded_remote.map(String::from),
+                actual: actual.to_string(),
+            });
+        }
+    }
+
+    if let Some(actual) = actual_branch {
+        if recorded_branch != Some(actual) {
+            drift.push(Drift {
+                field: "default_branch",
+                recorded: recorded_branch.map(String::from),
+                actual: actual.to_string(),
+            });
+        }
+    }
+
+    drift
+}
+
+/// The `origin` remote's URL, or the first remote's if there's no `origin`
+/// (e.g. after it was renamed to `upstream`).
+pub fn local_remote_url(repo: &Repository) -> Option<String> {
+    let remote = match repo.find_remote("origin") {
+        Ok(remote) => remote,
+        Err(_) => {
+            let names = repo.remotes().ok()?;
+            let first = names.iter().flatten().next()?;
+            repo.find_remote(first).ok()?
+        }
+    };
+    remote.url().map(String::from)
+}
+
+/// The branch the remote points `HEAD` at if known; otherwise the recorded
+/// branch while it still exists, falling back to a common default name.
+fn local_default_branch(repo: &Repository, recorded: Option<&str>) -> Option<String> {
+    if let Ok(reference) = repo.find_reference("refs/remotes/origin/HEAD") {
+        if let Some(branch) = reference
+            .symbolic_target()
+            .and_then(|target| target.strip_prefix("refs/remotes/origin/"))
+        {
+            return Some(branch.to_string());
+        }
+    }
+
+    let branch_exists = |name: &str| {
+        repo.find_reference(&format!("refs/heads/{name}")).is_ok()
+            || repo
+                .find_reference(&format!("refs/remotes/origin/{name}"))
+                .is_ok()
+    };
+    if let Some(recorded) = recorded.filter(|b| branch_exists(b)) {
+        return Some(recorded.to_string());
+    }
+    COMMON_DEFAULT_BRANCHES
+        .iter()
+        .find(|name| branch_exists(name))
+        .map(|name| name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn repositories() -> Vec<ServerRepository> {
+        serde_json::from_value(json!([
+            { "id": "7f3a9c21-0000", "name": "api", "project_id": "web-id", "local_path": "/src/api" },
+            { "id": "7f3b1d44-0000", "name": "Website", "project_id": "web-id", "remote_url": "git@github.com:acme/web.git", "default_branch": "main" },
+            { "id": "c0ffee00-0000", "name": "API", "project_id": "ops-id" },
+            { "id": "dead0000-0000", "name": "scratch", "project_id": "gone-id" },
+        ]))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_find_repository() {
+        let repositories = repositories();
+        let id = |spec: &str| find_repository(&repositories, spec).map(|repo| repo.id.as_str());
+
+        assert_eq!(id("c0ffee00-0000").unwrap(), "c0ffee00-0000");
+        assert_eq!(id("7f3b").unwrap(), "7f3b1d44-0000");
+        assert_eq!(id("website").unwrap(), "7f3b1d44-0000");
+        // Two prefixes, or two names differing only in case, are ambiguous
+        assert!(id("7f3").is_err());
+        assert!(id("api").is_err());
+        assert!(id("nope").is_err());
+    }
+
+    #[test]
+    fn test_table_rows_follow_project_order() {
+        let project = |id: &str, identifier: &str| Project {
+            id: id.to_string(),
+            name: identifier.to_string(),
+            identifier: identifier.to_string(),
+            ..Default::default()
+        };
+        let projects = vec![project("ops-id", "ops"), project("web-id", "web")];
+
+        let rows = table_rows(&projects, repositories());
+        let summary: Vec<String> = rows
+            .iter()
+            .map(|row| {
+                format!(
+                    "{} {} {} {} {}",
+                    row.id, row.project, row.name, row.path, row.branch
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                "c0ffee00 OPS API - -",
+                "7f3a9c21 WEB api /src/api -",
+                "7f3b1d44 WEB Website git@github.com:acme/web.git main",
+                "dead0000 - scratch - -",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_detect_drift() {
+        // Same repository over SSH vs HTTPS, same branch
+        assert!(detect_drift(
+            Some("https://github.com/acme/api.git"),
+            Some("main"),
+            Some("git@github.com:acme/api.git"),
+            Some("main"),
+        )
+        .is_empty());
+
+        let drift = detect_drift(
+            Some("https://github.com/acme/api.git"),
+            Some("master"),
+            Some("git@github.com:acme/platform-api.git"),
+            Some("main"),
+        );
+        assert_eq!(
+            drift,
+            vec![
+                Drift {
+                    field: "remote_url",
+                    recorded: Some("https://github.com/acme/api.git".to_string()),
+                    actual: "git@github.com:acme/platform-api.git".to_string(),
+                },
+                Drift {
+                    field: "default_branch",
+                    recorded: Some("master".to_string()),
+                    actual: "main".to_string(),
+                },
+            ]
+        );
+
+        // Unknown local values never overwrite what's recorded
+        assert!(detect_drift(Some("https://x/y"), Some("main"), None, None).is_empty());
+    }
+
+    #[test]
+    fn test_local_state_after_rename() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = Repository::init(temp_dir.path()).unwrap();
+        let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+        let tree_id = repo.index().unwrap().write_tree().unwrap();
+        let tree = repo.find_tree(tree_id).unwrap();
+        let commit = repo
+            .commit(None, &sig, &sig, "Initial", &tree, &[])
+            .unwrap();
+        let commit = repo.find_commit(commit).unwrap();
+        repo.branch("main", &commit, false).unwrap();
+        repo.remote("upstream", "git@github.com:acme/api.git")
+            .unwrap();
+
+        // `master` was never created, so the recorded branch is gone
+        assert_eq!(
+            local_default_branch(&repo, Some("master")),
+            Some("main".to_string())
+        );
+        assert_eq!(
+            local_default_branch(&repo, Some("main")),
+            Some("main".to_string())
+        );
+        assert_eq!(
+            local_remote_url(&repo),
+            Some("git@github.com:acme/api.git".to_string())
+        );
+    }
+}
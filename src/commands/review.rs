@@ -0,0 +1,391 @@
+use crate::auth::AuthService;
+use crate::commands::digest::render_digest;
+use crate::commands::log;
+use crate::commands::logs::fetch_all_entries;
+use crate::commands::recap::{self, RecapDestinations, RecapFormat};
+use crate::errors::AppError;
+use crate::utils::prompt::PromptOrDefault;
+use crate::utils::redaction::RedactionPolicy;
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, TimeZone, Weekday};
+use colored::*;
+use inquire::{Confirm, Select, Text};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+const ADD_ENTRY: &str = "Add an entry for this day";
+const ADD_ANOTHER_ENTRY: &str = "Add another entry for this day";
+const CONTINUE: &str = "Continue";
+const SKIP: &str = "Skip";
+const FINISH: &str = "Finish review";
+
+/// Walks through a week's entries day by day, prompting to fill in gaps, then
+/// optionally generates a recap and saves the review as Markdown in `reviews_dir`.
+/// Reviews last week unless `week_of` (any YYYY-MM-DD in the week) is given.
+pub async fn execute(
+    auth_service: &mut AuthService,
+    week_of: Option<&str>,
+    reviews_dir: &Path,
+    skip_recap: bool,
+    redaction: &RedactionPolicy,
+) -> Result<(), AppError> {
+    let reference = match week_of {
+        Some(d) => NaiveDate::parse_from_str(d, "%Y-%m-%d").map_err(|_| {
+            AppError::ParseError(format!("Invalid date format: {d}. Expected YYYY-MM-DD"))
+        })?,
+        None => Local::now().date_naive() - Duration::days(7),
+    };
+    let (monday, sunday) = week_bounds(reference);
+
+    println!(
+        "{}",
+        format!(
+            "📅 Weekly review: {} – {}",
+            monday.format("%a %b %-d"),
+            sunday.format("%a %b %-d, %Y")
+        )
+        .bright_blue()
+    );
+    println!();
+
+    let entries = fetch_week(auth_service, monday, sunday).await?;
+    let by_day = group_by_day(&entries);
+
+    let mut added = 0;
+    'days: for day in monday.iter_days().take(7) {
+        let day_entries = by_day.get(&day).map(Vec::as_slice).unwrap_or(&[]);
+        let is_weekend = matches!(day.weekday(), Weekday::Sat | Weekday::Sun);
+
+        // Quiet weekends aren't gaps worth asking about
+        if day_entries.is_empty() && is_weekend {
+            continue;
+        }
+
+        println!("{}", day.format("%A, %B %-d").to_string().bold());
+        if day_entries.is_empty() {
+            println!("  {}", "No entries logged".bright_yellow());
+        }
+        for entry in day_entries {
+            println!("  {}", entry_summary(entry));
+        }
+        println!();
+
+        let mut options = if day_entries.is_empty() {
+            vec![ADD_ENTRY, SKIP, FINISH]
+        } else {
+            vec![CONTINUE, ADD_ANOTHER_ENTRY, FINISH]
+        };
+
+        loop {
+            let choice = Select::new("What next?", options.clone())
+                .prompt()
+                .map_err(|e| AppError::ParseError(format!("Selection failed: {e}")))?;
+
+            match choice {
+                ADD_ENTRY | ADD_ANOTHER_ENTRY => {
+                    if add_entry_for_day(auth_service, day).await? {
+                        added += 1;
+                    }
+                    options = vec![CONTINUE, ADD_ANOTHER_ENTRY, FINISH];
+                }
+                FINISH => break 'days,
+                _ => break,
+            }
+        }
+        println!();
+    }
+
+    if added > 0 {
+        println!("✅ Added {added} entries during this review");
+    }
+
+    let generate_recap = !skip_recap
+        && Confirm::new("Generate a recap for this week?")
+            .with_default(true)
+            .prompt_or_default()
+            .map_err(|e| AppError::ParseError(format!("Confirmation failed: {e}")))?;
+
+    let recap_content = if generate_recap {
+        let from = monday.format("%Y-%m-%d").to_string();
+        let to = sunday.format("%Y-%m-%d").to_string();
+        let exclude_tags = redaction.merge_exclude_tags(None);
+        match recap::execute(
+            auth_service,
+            Some(&from),
+            Some(&to),
+            None,
+            None,
+            exclude_tags.as_deref(),
+            None,
+            None,
+            false,
+            RecapFormat::Text,
+            RecapDestinations::default(),
+        )
+        .await
+        {
+            Ok(content) => Some(content),
+            Err(e) => {
+                eprintln!("⚠️  Warning: Could not generate recap: {e}");
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    let path = reviews_dir.join(review_file_name(monday));
+    let save = Confirm::new(&format!("Save this review to {}?", path.display()))
+        .with_default(true)
+        .prompt_or_default()
+        .map_err(|e| AppError::ParseError(format!("Confirmation failed: {e}")))?;
+
+    if save {
+        // Re-fetch so entries added during the review are included
+        let entries = if added > 0 {
+            fetch_week(auth_service, monday, sunday).await?
+        } else {
+            entries
+        };
+        let entries = redaction.apply(entries);
+        let review = render_review(
+            monday,
+            sunday,
+            &group_by_day(&entries),
+            recap_content.as_deref(),
+        );
+
+        fs::create_dir_all(reviews_dir)?;
+        fs::write(&path, review)?;
+        println!("✅ Review saved to {}", path.display());
+    }
+
+    Ok(())
+}
+
+async fn fetch_week(
+    auth_service: &mut AuthService,
+    monday: NaiveDate,
+    sunday: NaiveDate,
+) -> Result<Vec<Value>, AppError> {
+    fetch_all_entries(
+        auth_service.api_client(),
+        None,
+        None,
+        Some(&monday.format("%Y-%m-%d").to_string()),
+        Some(&sunday.format("%Y-%m-%d").to_string()),
+    )
+    .await
+}
+
+/// Prompts for an entry and records it at midday on the given day. Returns false
+/// when the user leaves the content empty.
+async fn add_entry_for_day(
+    auth_service: &mut AuthService,
+    day: NaiveDate,
+) -> Result<bool, AppError> {
+    let content = Text::new("What did you work on?")
+        .prompt_or_default()
+        .map_err(|e| AppError::ParseError(format!("Input failed: {e}")))?;
+    if content.trim().is_empty() {
+        println!("No content provided, skipping.");
+        return Ok(false);
+    }
+
+    let tags = Text::new("Tags (comma-separated, optional):")
+        .prompt_or_default()
+        .map_err(|e| AppError::ParseError(format!("Input failed: {e}")))?;
+    let tags: Vec<String> = tags
+        .split(',')
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+        .collect();
+
+    let recorded_at = midday(day).to_rfc3339();
+    log::execute(
+        auth_service,
+        &[content.trim().to_string()],
+        &tags,
+        None,
+        Some(&recorded_at),
+        None,
+    )
+    .await?;
+
+    Ok(true)
+}
+
+/// Monday and Sunday of the week containing `day`.
+fn week_bounds(day: NaiveDate) -> (NaiveDate, NaiveDate) {
+    let monday = day - Duration::days(day.weekday().num_days_from_monday() as i64);
+    (monday, monday + Duration::days(6))
+}
+
+fn midday(day: NaiveDate) -> DateTime<Local> {
+    let noon = day.and_hms_opt(12, 0, 0).expect("noon is a valid time");
+    Local
+        .from_local_datetime(&noon)
+        .earliest()
+        .unwrap_or_else(|| Local.from_utc_datetime(&noon))
+}
+
+/// Groups entries by the local day they were recorded on, oldest first within a day.
+fn group_by_day(entries: &[Value]) -> BTreeMap<NaiveDate, Vec<Value>> {
+    let mut by_day: BTreeMap<NaiveDate, Vec<Value>> = BTreeMap::new();
+
+    for entry in entries {
+        let Some(recorded_at) = entry
+            .get("recorded_at")
+            .and_then(Value::as_str)
+            .and_then(|s| s.parse::<DateTime<Local>>().ok())
+        else {
+            continue;
+        };
+        by_day
+            .entry(recorded_at.date_naive())
+            .or_default()
+            .push(entry.clone());
+    }
+
+    // The API returns newest first; reviews read chronologically
+    for day_entries in by_day.values_mut() {
+        day_entries.reverse();
+    }
+
+    by_day
+}
+
+fn entry_summary(entry: &Value) -> String {
+    let content = entry
+        .get("content")
+        .and_then(Value::as_str)
+        .and_then(|c| c.lines().next())
+        .unwrap_or("")
+        .trim();
+    let tags = entry
+        .get("tags")
+        .and_then(Value::as_array)
+        .map(|arr| {
+            arr.iter()
+                .filter_map(Value::as_str)
+                .map(|t| format!(" #{t}"))
+                .collect::<String>()
+        })
+        .unwrap_or_default();
+
+    format!("• {content}{}", tags.bright_black())
+}
+
+fn review_file_name(monday: NaiveDate) -> String {
+    let week = monday.iso_week();
+    format!("review-{}-W{:02}.md", week.year(), week.week())
+}
+
+fn render_review(
+    monday: NaiveDate,
+    sunday: NaiveDate,
+    by_day: &BTreeMap<NaiveDate, Vec<Value>>,
+    recap: Option<&str>,
+) -> String {
+    let mut out = format!(
+        "# Weekly review: {} – {}\n\n",
+        monday.format("%Y-%m-%d"),
+        sunday.format("%Y-%m-%d")
+    );
+
+    if let Some(recap) = recap {
+        out.push_str("## Recap\n\n");
+        out.push_str(recap.trim());
+        out.push_str("\n\n");
+    }
+
+    for day in monday.iter_days().take(7) {
+        let day_entries = by_day.get(&day).map(Vec::as_slice).unwrap_or(&[]);
+        if day_entries.is_empty() && matches!(day.weekday(), Weekday::Sat | Weekday::Sun) {
+            continue;
+        }
+        out.push_str(&render_digest(&day, day_entries).replacen("## ", "### ", 1));
+        out.push('\n');
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_week_bounds() {
+        // 2025-07-09 is a Wednesday
+        assert_eq!(
+            week_bounds(date("2025-07-09")),
+            (date("2025-07-07"), date("2025-07-13"))
+        );
+        assert_eq!(
+            week_bounds(date("2025-07-07")),
+            (date("2025-07-07"), date("2025-07-13"))
+        );
+        assert_eq!(
+            week_bounds(date("2025-07-13")),
+            (date("2025-07-07"), date("2025-07-13"))
+        );
+    }
+
+    #[test]
+    fn test_review_file_name() {
+        assert_eq!(review_file_name(date("2025-07-07")), "review-2025-W28.md");
+        assert_eq!(review_file_name(date("2024-12-30")), "review-2025-W01.md");
+    }
+
+    #[test]
+    fn test_group_by_day_orders_chronologically() {
+        let late = midday(date("2025-07-08")) + Duration::hours(3);
+        let early = midday(date("2025-07-08")) - Duration::hours(3);
+        let entries = vec![
+            json!({ "id": "late", "recorded_at": late.to_rfc3339() }),
+            json!({ "id": "early", "recorded_at": early.to_rfc3339() }),
+            json!({ "id": "other", "recorded_at": midday(date("2025-07-10")).to_rfc3339() }),
+            json!({ "id": "undated" }),
+        ];
+
+        let by_day = group_by_day(&entries);
+        assert_eq!(by_day.len(), 2);
+        let ids: Vec<&str> = by_day[&date("2025-07-08")]
+            .iter()
+            .map(|e| e["id"].as_str().unwrap())
+            .collect();
+        assert_eq!(ids, vec!["early", "late"]);
+    }
+
+    #[test]
+    fn test_render_review() {
+        let mut by_day = BTreeMap::new();
+        by_day.insert(
+            date("2025-07-08"),
+            vec![json!({ "content": "Shipped invoices", "tags": ["billing"] })],
+        );
+
+        let review = render_review(
+            date("2025-07-07"),
+            date("2025-07-13"),
+            &by_day,
+            Some("A productive week.\n"),
+        );
+
+        assert!(review.starts_with(
+            "# Weekly review: 2025-07-07 – 2025-07-13\n\n## Recap\n\nA productive week.\n\n"
+        ));
+        assert!(review.contains("### 2025-07-07 (Monday)\n\n_No entries logged._\n"));
+        assert!(
+            review.contains("### 2025-07-08 (Tuesday)\n\n- --:-- Shipped invoices `#billing`\n")
+        );
+        assert!(!review.contains("Saturday"));
+    }
+}
@@ -0,0 +1,255 @@
+use crate::auth::AuthService;
+use crate::commands::logs::fetch_all_entries;
+use crate::commands::{project, recap};
+use crate::errors::AppError;
+use crate::utils::redaction::RedactionPolicy;
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, Weekday};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// Entries with one of these tags are reported as blockers rather than under
+/// the day they were logged.
+const BLOCKER_TAGS: &[&str] = &["blocker", "blocked"];
+
+/// Heading for entries that don't belong to a project.
+const NO_PROJECT: &str = "Other";
+
+/// Prints a standup summary in Markdown: what was logged on the previous
+/// working day and today, grouped by project, and anything tagged as a
+/// blocker on either day. With `use_recap`, the previous day is summarized by
+/// the recap API instead of listed entry by entry.
+pub async fn execute(
+    auth_service: &mut AuthService,
+    project_identifier: Option<&str>,
+    use_recap: bool,
+    redaction: &RedactionPolicy,
+) -> Result<(), AppError> {
+    let today = Local::now().date_naive();
+    let yesterday = previous_workday(today);
+
+    let project_ids = match project_identifier {
+        Some(identifier) => project::resolve_project_filter_ids(auth_service, identifier).await?,
+        None => None,
+    };
+    let entries = fetch_all_entries(
+        auth_service.api_client(),
+        project_ids.as_deref(),
+        None,
+        Some(&yesterday.format("%Y-%m-%d").to_string()),
+        Some(&today.format("%Y-%m-%d").to_string()),
+    )
+    .await?;
+    let entries = redaction.apply(entries);
+
+    let recap = if use_recap {
+        let day = yesterday.format("%Y-%m-%d").to_string();
+        let exclude_tags = redaction.merge_exclude_tags(None);
+        match recap::generate(
+            auth_service,
+            Some(&day),
+            Some(&day),
+            exclude_tags.as_deref(),
+            project_identifier,
+        )
+        .await
+        {
+            Ok(content) => Some(content),
+            Err(e) => {
+                eprintln!("⚠️  Warning: Could not generate a recap for {day}: {e}");
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    print!(
+        "{}",
+        render_standup(yesterday, today, &entries, recap.as_deref())
+    );
+    Ok(())
+}
+
+/// The last weekday before `day`, so Monday's standup covers Friday.
+fn previous_workday(day: NaiveDate) -> NaiveDate {
+    let mut previous = day - Duration::days(1);
+    while matches!(previous.weekday(), Weekday::Sat | Weekday::Sun) {
+        previous -= Duration::days(1);
+    }
+    previous
+}
+
+fn is_blocker(entry: &Value) -> bool {
+    entry
+        .get("tags")
+        .and_then(Value::as_array)
+        .is_some_and(|tags| {
+            tags.iter()
+                .filter_map(Value::as_str)
+                .any(|tag| BLOCKER_TAGS.iter().any(|b| b.eq_ignore_ascii_case(tag)))
+        })
+}
+
+fn recorded_on(entry: &Value) -> Option<NaiveDate> {
+    entry
+        .get("recorded_at")
+        .and_then(Value::as_str)
+        .and_then(|s| s.parse::<DateTime<Local>>().ok())
+        .map(|recorded_at| recorded_at.date_naive())
+}
+
+fn render_standup(
+    yesterday: NaiveDate,
+    today: NaiveDate,
+    entries: &[Value],
+    recap: Option<&str>,
+) -> String {
+    // The API returns newest first; standups read chronologically
+    let entries: Vec<&Value> = entries.iter().rev().collect();
+    let blockers: Vec<&Value> = entries.iter().copied().filter(|e| is_blocker(e)).collect();
+    let on = |day: NaiveDate| -> Vec<&Value> {
+        entries
+            .iter()
+            .copied()
+            .filter(|e| !is_blocker(e) && recorded_on(e) == Some(day))
+            .collect()
+    };
+
+    let mut out = format!("## Yesterday ({})\n\n", yesterday.format("%A, %B %-d"));
+    match recap {
+        Some(recap) => {
+            out.push_str(recap.trim());
+            out.push('\n');
+        }
+        None => out.push_str(&render_by_project(&on(yesterday), "Nothing logged")),
+    }
+
+    out.push_str("\n## Today\n\n");
+    out.push_str(&render_by_project(&on(today), "Nothing logged yet"));
+
+    out.push_str("\n## Blockers\n\n");
+    if blockers.is_empty() {
+        out.push_str("- None\n");
+    }
+    for entry in blockers {
+        out.push_str(&format!("- {}\n", first_line(entry)));
+    }
+    out
+}
+
+/// Entries under a bold heading per project, with project-less ones last.
+fn render_by_project(entries: &[&Value], empty: &str) -> String {
+    if entries.is_empty() {
+        return format!("- {empty}\n");
+    }
+
+    let mut by_project: BTreeMap<Option<String>, Vec<&Value>> = BTreeMap::new();
+    for entry in entries {
+        let project = entry
+            .get("project")
+            .and_then(|p| p.get("identifier"))
+            .and_then(Value::as_str)
+            .map(str::to_uppercase);
+        by_project.entry(project).or_default().push(entry);
+    }
+
+    // Only one group, and no project: a heading would just be noise
+    if by_project.len() == 1 && by_project.contains_key(&None) {
+        return entries
+            .iter()
+            .map(|e| format!("- {}\n", first_line(e)))
+            .collect();
+    }
+
+    let none = by_project.remove(&None);
+    let groups = by_project
+        .into_iter()
+        .map(|(project, entries)| (project.unwrap_or_default(), entries))
+        .chain(none.map(|entries| (NO_PROJECT.to_string(), entries)));
+
+    let mut out = String::new();
+    for (i, (project, entries)) in groups.enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+        out.push_str(&format!("**{project}**\n"));
+        for entry in entries {
+            out.push_str(&format!("- {}\n", first_line(entry)));
+        }
+    }
+    out
+}
+
+fn first_line(entry: &Value) -> &str {
+    entry
+        .get("content")
+        .and_then(Value::as_str)
+        .and_then(|c| c.lines().next())
+        .unwrap_or("")
+        .trim()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use serde_json::json;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    fn at(day: &str, hour: u32) -> String {
+        let time = date(day).and_hms_opt(hour, 0, 0).unwrap();
+        Local
+            .from_local_datetime(&time)
+            .earliest()
+            .unwrap()
+            .to_rfc3339()
+    }
+
+    #[test]
+    fn test_previous_workday_skips_weekends() {
+        // 2025-07-07 is a Monday
+        assert_eq!(previous_workday(date("2025-07-07")), date("2025-07-04"));
+        assert_eq!(previous_workday(date("2025-07-08")), date("2025-07-07"));
+        assert_eq!(previous_workday(date("2025-07-06")), date("2025-07-04"));
+    }
+
+    #[test]
+    fn test_render_standup_groups_by_project_and_lists_blockers() {
+        let web = json!({ "identifier": "web" });
+        // Newest first, as the API returns them
+        let entries = vec![
+            json!({ "content": "Waiting on API keys", "tags": ["blocked"], "recorded_at": at("2025-07-08", 11) }),
+            json!({ "content": "Pairing on checkout", "project": web, "recorded_at": at("2025-07-08", 10) }),
+            json!({ "content": "1:1 with Sam", "recorded_at": at("2025-07-07", 16) }),
+            json!({ "content": "Fixed login redirect\nDetails", "project": web, "recorded_at": at("2025-07-07", 9) }),
+            json!({ "content": "Reviewed PRs", "project": { "identifier": "api" }, "recorded_at": at("2025-07-07", 8) }),
+        ];
+
+        let standup = render_standup(date("2025-07-07"), date("2025-07-08"), &entries, None);
+        assert_eq!(
+            standup,
+            "## Yesterday (Monday, July 7)\n\n\
+             **API**\n- Reviewed PRs\n\n\
+             **WEB**\n- Fixed login redirect\n\n\
+             **Other**\n- 1:1 with Sam\n\n\
+             ## Today\n\n**WEB**\n- Pairing on checkout\n\n\
+             ## Blockers\n\n- Waiting on API keys\n"
+        );
+
+        let standup = render_standup(
+            date("2025-07-07"),
+            date("2025-07-08"),
+            &[],
+            Some("Fixed the login flow.\n"),
+        );
+        assert_eq!(
+            standup,
+            "## Yesterday (Monday, July 7)\n\nFixed the login flow.\n\n\
+             ## Today\n\n- Nothing logged yet\n\n\
+             ## Blockers\n\n- None\n"
+        );
+    }
+}
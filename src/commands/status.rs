@@ -1,16 +1,233 @@
 use crate::auth::AuthService;
+use crate::capture_state::CaptureState;
+use crate::commands::capture::{format_uncaptured, uncaptured_count};
 use crate::errors::AppError;
+use crate::outbox::Outbox;
+use crate::utils::humanize::time_until;
+use chrono::{DateTime, Duration, Local};
+use colored::*;
+use crossterm::cursor::MoveTo;
+use crossterm::execute;
+use crossterm::terminal::{Clear, ClearType};
+use std::io::{self, IsTerminal};
+use std::path::Path;
+use std::time::Instant;
 
 pub async fn execute(auth_service: &mut AuthService) -> Result<(), AppError> {
     match auth_service.ensure_authenticated().await {
         Ok(()) => {
             println!();
             println!("You’re logged in.");
+            if let Some(remaining) = auth_service.token_expires_in() {
+                println!("Your session expires {}.", time_until(remaining));
+            }
         }
         Err(_) => {
             println!();
             println!("You are not authenticated. Run `accomplish login` first.");
         }
     }
+
+    for line in render_pending(&Pending::load(auth_service.state_dir())) {
+        println!("{line}");
+    }
     Ok(())
 }
+
+/// Work waiting on the user, shown by both views.
+struct Pending {
+    /// Entries in the outbox
+    queued: usize,
+    /// Commits in the current repository not captured yet
+    uncaptured: Option<usize>,
+}
+
+impl Pending {
+    fn load(state_dir: &Path) -> Self {
+        let capture_state = CaptureState::load(state_dir);
+        Self {
+            queued: Outbox::new(state_dir).count(),
+            uncaptured: std::env::current_dir()
+                .ok()
+                .and_then(|dir| uncaptured_count(&dir, &capture_state)),
+        }
+    }
+}
+
+fn render_pending(pending: &Pending) -> Vec<String> {
+    let mut lines = Vec::new();
+    let queued = pending.queued;
+    if queued > 0 {
+        let entries = if queued == 1 { "entry" } else { "entries" };
+        lines.push(format!(
+            "{queued} {entries} saved while offline. Send them with `acc sync`."
+        ));
+    }
+    if let Some(count) = pending.uncaptured.filter(|count| *count > 0) {
+        let (commits, have) = if count == 1 {
+            ("commit", "hasn't")
+        } else {
+            ("commits", "haven't")
+        };
+        lines.push(format!(
+            "{} {commits} in this repository {have} been captured yet. Run `acc capture`.",
+            format_uncaptured(count)
+        ));
+    }
+    lines
+}
+
+/// One refresh of the watch view.
+struct Snapshot {
+    checked_at: DateTime<Local>,
+    /// Round trip of the connectivity check, or why it failed
+    latency: Result<std::time::Duration, String>,
+    auth: AuthState,
+    pending: Pending,
+}
+
+enum AuthState {
+    LoggedIn {
+        expires_in: Option<Duration>,
+    },
+    LoggedOut,
+    /// Couldn't be checked, e.g. while offline
+    Unknown,
+}
+
+/// Redraws API latency, auth status and pending work every `interval_secs`
+/// until Ctrl-C.
+/// When stdout isn't a terminal each refresh is appended instead.
+pub async fn watch(auth_service: &mut AuthService, interval_secs: u64) -> Result<(), AppError> {
+    let interval = std::time::Duration::from_secs(interval_secs.max(1));
+    let redraw = io::stdout().is_terminal();
+
+    loop {
+        let snapshot = take_snapshot(auth_service).await;
+        if redraw {
+            execute!(io::stdout(), MoveTo(0, 0), Clear(ClearType::All))?;
+        } else {
+            println!();
+        }
+        for line in render(&snapshot, auth_service.api_client().base_url()) {
+            println!("{line}");
+        }
+
+        tokio::select! {
+            _ = tokio::time::sleep(interval) => {}
+            _ = tokio::signal::ctrl_c() => return Ok(()),
+        }
+    }
+}
+
+async fn take_snapshot(auth_service: &mut AuthService) -> Snapshot {
+    let started = Instant::now();
+    let latency = match auth_service.api_client().check_reachable().await {
+        Ok(()) => Ok(started.elapsed()),
+        Err(e) => Err(e.to_string()),
+    };
+
+    let auth = if latency.is_err() {
+        AuthState::Unknown
+    } else {
+        match auth_service.ensure_authenticated().await {
+            Ok(()) => AuthState::LoggedIn {
+                expires_in: auth_service.token_expires_in(),
+            },
+            Err(AppError::Auth(_)) => AuthState::LoggedOut,
+            Err(_) => AuthState::Unknown,
+        }
+    };
+
+    Snapshot {
+        checked_at: Local::now(),
+        latency,
+        auth,
+        pending: Pending::load(auth_service.state_dir()),
+    }
+}
+
+fn render(snapshot: &Snapshot, base_url: &str) -> Vec<String> {
+    let api = match &snapshot.latency {
+        Ok(latency) => format!("{} {} ms", "●".green(), latency.as_millis()),
+        Err(reason) => format!("{} offline ({reason})", "●".red()),
+    };
+    let auth = match &snapshot.auth {
+        AuthState::LoggedIn {
+            expires_in: Some(remaining),
+        } => format!(
+            "{} logged in, session expires {}",
+            "●".green(),
+            time_until(*remaining)
+        ),
+        AuthState::LoggedIn { expires_in: None } => format!("{} logged in", "●".green()),
+        AuthState::LoggedOut => format!("{} not logged in, run `acc login`", "●".red()),
+        AuthState::Unknown => format!("{} unknown", "●".yellow()),
+    };
+
+    let mut lines = vec![
+        format!("{}  {}", "Accomplish".bold(), base_url.bright_black()),
+        String::new(),
+        format!("API   {api}"),
+        format!("Auth  {auth}"),
+        String::new(),
+    ];
+    let pending = render_pending(&snapshot.pending);
+    if !pending.is_empty() {
+        lines.extend(pending);
+        lines.push(String::new());
+    }
+    lines.push(
+        format!(
+            "Updated {}. Press Ctrl-C to quit.",
+            snapshot.checked_at.format("%H:%M:%S")
+        )
+        .bright_black()
+        .to_string(),
+    );
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_watch_view() {
+        colored::control::set_override(false);
+        let snapshot = Snapshot {
+            checked_at: Local::now(),
+            latency: Ok(std::time::Duration::from_millis(42)),
+            auth: AuthState::LoggedIn {
+                expires_in: Some(Duration::hours(5)),
+            },
+            pending: Pending {
+                queued: 0,
+                uncaptured: None,
+            },
+        };
+        let lines = render(&snapshot, "https://accomplish.dev");
+        assert_eq!(lines[2], "API   ● 42 ms");
+        assert_eq!(lines[3], "Auth  ● logged in, session expires in 5 hours");
+        assert!(lines[5].starts_with("Updated"), "{lines:?}");
+
+        // What's waiting shows up like in the one-off view
+        let offline = Snapshot {
+            latency: Err("connection refused".into()),
+            auth: AuthState::Unknown,
+            pending: Pending {
+                queued: 2,
+                uncaptured: Some(3),
+            },
+            ..snapshot
+        };
+        let lines = render(&offline, "https://accomplish.dev");
+        assert_eq!(lines[2], "API   ● offline (connection refused)");
+        assert_eq!(lines[3], "Auth  ● unknown");
+        assert_eq!(
+            lines[5],
+            "2 entries saved while offline. Send them with `acc sync`."
+        );
+        assert!(lines[6].contains("in this repository haven't been captured"));
+    }
+}
@@ -0,0 +1,218 @@
+use crate::auth::AuthService;
+use crate::capture_state::CaptureState;
+use crate::commands::audit::repositories_to_audit;
+use crate::commands::capture::{get_git_user_email, is_git_repository, repository_key};
+use crate::commands::log;
+use crate::config;
+use crate::errors::AppError;
+use crate::suggestions::{self, Suggestion, SuggestionState};
+use crate::utils::duration::parse_since_duration;
+use crate::utils::editor::open_in_editor;
+use chrono::{DateTime, Local, Utc};
+use colored::*;
+use inquire::Select;
+use std::fs;
+use std::io::{self, IsTerminal};
+
+const LOG: &str = "Log it";
+const EDIT: &str = "Edit, then log";
+const DISMISS: &str = "Dismiss";
+const SKIP: &str = "Skip for now";
+const STOP: &str = "Stop";
+
+/// Options for `acc suggest`.
+#[derive(Debug)]
+pub struct SuggestOptions<'a> {
+    /// How far back to look, e.g. "1d" or "yesterday"
+    pub since: &'a str,
+    /// Count commits from every author, not just the repository's user.email
+    pub all_authors: bool,
+    /// Also suggest entries from the shell history
+    pub shell_history: bool,
+    /// Print the suggestions without asking what to do with them
+    pub list_only: bool,
+    /// Project for suggestions from a repository without one configured
+    pub default_project: Option<&'a str>,
+}
+
+/// Proposes draft entries from recent commits in tracked repositories (and,
+/// when enabled, the shell history), then logs, opens in the editor or
+/// dismisses each one as chosen. Logged and dismissed activity isn't
+/// suggested again.
+pub async fn execute(
+    auth_service: &mut AuthService,
+    options: &SuggestOptions<'_>,
+) -> Result<(), AppError> {
+    let since_iso =
+        parse_since_duration(options.since).map_err(|e| AppError::Other(e.to_string()))?;
+    let since = since_iso
+        .parse::<DateTime<Utc>>()
+        .map_err(|e| AppError::ParseError(format!("Invalid start time {since_iso}: {e}")))?;
+
+    let mut state = SuggestionState::load(auth_service.state_dir());
+    let capture_state = CaptureState::load(auth_service.state_dir());
+    let suggestions = collect(options, since, &state, &capture_state);
+
+    let since_label = since.with_timezone(&Local).format("%Y-%m-%d %H:%M");
+    if suggestions.is_empty() {
+        println!("No new activity to suggest entries for since {since_label}.");
+        return Ok(());
+    }
+
+    if options.list_only || !io::stdin().is_terminal() {
+        println!(
+            "{}",
+            format!("Suggested entries from activity since {since_label}:").bright_blue()
+        );
+        for suggestion in &suggestions {
+            println!();
+            print_suggestion(suggestion);
+        }
+        println!();
+        println!(
+            "{}",
+            "Run `acc suggest` in a terminal to log or dismiss them".bright_black()
+        );
+        return Ok(());
+    }
+
+    let mut logged = 0;
+    for suggestion in &suggestions {
+        println!();
+        print_suggestion(suggestion);
+
+        let choice = Select::new("What next?", vec![LOG, EDIT, DISMISS, SKIP, STOP])
+            .prompt()
+            .map_err(|e| AppError::ParseError(format!("Selection failed: {e}")))?;
+
+        let content = match choice {
+            LOG => suggestion.draft(),
+            EDIT => {
+                let edited = open_in_editor(Some(&suggestion.draft()))?;
+                if edited.trim().is_empty() {
+                    println!("Empty entry, nothing logged.");
+                    continue;
+                }
+                edited.trim().to_string()
+            }
+            DISMISS => {
+                state.set_aside(suggestion);
+                save(&mut state);
+                continue;
+            }
+            STOP => break,
+            _ => continue,
+        };
+
+        log::execute(
+            auth_service,
+            &[content],
+            &[],
+            suggestion.project.as_deref(),
+            Some(&suggestion.ended_at.to_rfc3339()),
+            None,
+        )
+        .await?;
+        logged += 1;
+        state.set_aside(suggestion);
+        save(&mut state);
+    }
+
+    if logged > 0 {
+        println!();
+        println!("✅ Logged {logged} suggested entries");
+    }
+    Ok(())
+}
+
+/// Suggestions from every source, most recent activity first.
+fn collect(
+    options: &SuggestOptions<'_>,
+    since: DateTime<Utc>,
+    state: &SuggestionState,
+    capture_state: &CaptureState,
+) -> Vec<Suggestion> {
+    let mut all = Vec::new();
+
+    for repo in repositories_to_audit() {
+        if !is_git_repository(&repo) {
+            continue;
+        }
+        let author = if options.all_authors {
+            None
+        } else {
+            get_git_user_email(&repo)
+        };
+        let repo_id = repository_key(&repo)
+            .and_then(|key| capture_state.repo_for_dir(&key).map(str::to_string));
+        let skip = |sha: &str| {
+            state.is_set_aside(sha)
+                || repo_id
+                    .as_deref()
+                    .is_some_and(|id| capture_state.is_captured(id, sha))
+        };
+
+        match suggestions::git_suggestions(&repo, since, author.as_deref(), skip) {
+            Ok(found) => {
+                let project = config::lookup_default_project_for_dir(&repo)
+                    .or(options.default_project.map(str::to_string));
+                all.extend(found.into_iter().map(|mut suggestion| {
+                    suggestion.project = project.clone();
+                    suggestion
+                }));
+            }
+            Err(e) => eprintln!("⚠️  Warning: Could not read {}: {e}", repo.display()),
+        }
+    }
+
+    if options.shell_history {
+        match suggestions::shell_history_path() {
+            Some(path) => match fs::read(&path) {
+                Ok(bytes) => {
+                    let commands =
+                        suggestions::parse_shell_history(&String::from_utf8_lossy(&bytes));
+                    all.extend(
+                        suggestions::shell_suggestions(&commands, since, |id| {
+                            state.is_set_aside(id)
+                        })
+                        .into_iter()
+                        .map(|mut suggestion| {
+                            suggestion.project = options.default_project.map(str::to_string);
+                            suggestion
+                        }),
+                    );
+                }
+                Err(e) => eprintln!("⚠️  Warning: Could not read {}: {e}", path.display()),
+            },
+            None => eprintln!("⚠️  Warning: No shell history file found. Set HISTFILE to use one"),
+        }
+    }
+
+    all.sort_by_key(|suggestion| std::cmp::Reverse(suggestion.ended_at));
+    all
+}
+
+fn print_suggestion(suggestion: &Suggestion) {
+    println!("{}", suggestion.title.bold());
+    let start = suggestion.started_at.with_timezone(&Local);
+    let end = suggestion.ended_at.with_timezone(&Local);
+    let when = if start.date_naive() == end.date_naive() {
+        format!("{}–{}", start.format("%a %H:%M"), end.format("%H:%M"))
+    } else {
+        format!("{}–{}", start.format("%a %H:%M"), end.format("%a %H:%M"))
+    };
+    let mut about = vec![suggestion.source.clone(), when];
+    if let Some(project) = &suggestion.project {
+        about.push(project.to_uppercase());
+    }
+    println!("  {}", about.join(" · ").bright_black());
+    for detail in &suggestion.details {
+        println!("  - {detail}");
+    }
+}
+
+fn save(state: &mut SuggestionState) {
+    if let Err(e) = state.save() {
+        eprintln!("⚠️  Warning: Could not save dismissed suggestions: {e}");
+    }
+}
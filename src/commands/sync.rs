@@ -0,0 +1,176 @@
+use crate::auth::AuthService;
+use crate::commands::log;
+use crate::errors::AppError;
+use crate::outbox::{Outbox, PendingEntry};
+use chrono::{DateTime, Local};
+use colored::*;
+
+/// Sends the entries queued in the outbox while offline, oldest first, with
+/// the time each was written. Entries the server rejects stay queued and are
+/// reported; losing the connection again stops the sync.
+pub async fn execute(auth_service: &mut AuthService) -> Result<(), AppError> {
+    let outbox = Outbox::new(auth_service.state_dir());
+    let (pending, unreadable) = outbox.pending()?;
+    for path in &unreadable {
+        eprintln!(
+            "⚠️  Warning: Skipping {}, which isn't a queued entry",
+            path.display()
+        );
+    }
+    if pending.is_empty() {
+        println!("Nothing to sync.");
+        return Ok(());
+    }
+
+    let total = pending.len();
+    let mut synced = 0;
+    let mut failed = 0;
+    for queued in &pending {
+        let entry = &queued.entry;
+        let result = log::create(
+            auth_service,
+            std::slice::from_ref(&entry.content),
+            &entry.tags,
+            entry.project.as_deref(),
+            Some(&entry.recorded_at),
+            entry.custom_fields.as_ref(),
+        )
+        .await;
+
+        match result {
+            Ok(created) => {
+                synced += 1;
+                if let Err(e) = outbox.remove(queued) {
+                    eprintln!(
+                        "⚠️  Warning: Sent as {}, but could not remove {}: {e}",
+                        created.id,
+                        queued.path.display()
+                    );
+                }
+                println!("✓ {} {}", describe(queued), created.id.bright_black());
+            }
+            Err(e) if e.is_offline() => {
+                return Err(AppError::Other(format!(
+                    "Still can't reach Accomplish ({e}). Synced {synced} of {total}; the rest stay in {}",
+                    outbox.dir().display()
+                )));
+            }
+            // Sending it again could create it twice
+            Err(e) if e.is_timeout() => {
+                return Err(AppError::Other(format!(
+                    "{e}. {} may have been created: check `acc logs`, and delete {} if it's there before syncing again. Synced {synced} of {total}",
+                    describe(queued),
+                    queued.path.display()
+                )));
+            }
+            Err(e) => {
+                failed += 1;
+                eprintln!("⚠️  Warning: Could not sync {}: {e}", describe(queued));
+            }
+        }
+    }
+
+    if failed > 0 {
+        println!(
+            "Synced {synced} of {total} entries. {failed} stay in {} to retry with `acc sync`.",
+            outbox.dir().display()
+        );
+    } else {
+        println!("✅ Synced {synced} entries");
+    }
+    Ok(())
+}
+
+/// `Tue 08:15 "Fixed flaky login test"`, with the content cut to one short line.
+fn describe(queued: &PendingEntry) -> String {
+    let entry = &queued.entry;
+    let when = DateTime::parse_from_rfc3339(&entry.recorded_at)
+        .map(|at| at.with_timezone(&Local).format("%a %H:%M").to_string())
+        .unwrap_or_else(|_| entry.recorded_at.clone());
+    let first_line = entry.content.lines().next().unwrap_or("").trim();
+    let preview: String = first_line.chars().take(50).collect();
+    let ellipsis = if first_line.chars().count() > 50 || entry.content.lines().count() > 1 {
+        "…"
+    } else {
+        ""
+    };
+    format!("{when} \"{preview}{ellipsis}\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::outbox::QueuedEntry;
+    use mockito::Server;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn auth_service(api_base: String, temp_dir: &TempDir) -> AuthService {
+        let mut auth_service =
+            AuthService::new(api_base, temp_dir.path().to_path_buf(), "test-profile");
+        auth_service.save_access_token("dummy-token").unwrap();
+        auth_service
+    }
+
+    fn queue(outbox: &Outbox, content: &str) {
+        outbox
+            .push(&QueuedEntry {
+                queued_at: chrono::Utc::now(),
+                content: content.to_string(),
+                tags: Vec::new(),
+                project: None,
+                recorded_at: "2025-07-09T08:15:00+00:00".to_string(),
+                custom_fields: None,
+            })
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_sync_sends_queued_entries_and_keeps_rejected_ones() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut server = Server::new_async().await;
+        let mut auth_service = auth_service(server.url(), &temp_dir);
+        let outbox = Outbox::new(auth_service.state_dir());
+        queue(&outbox, "Written on the train");
+        queue(&outbox, "Rejected");
+
+        let _created = server
+            .mock("POST", "/api/v1/worklog/entries")
+            .match_body(mockito::Matcher::PartialJson(json!({
+                "content": "Written on the train",
+                "recorded_at": "2025-07-09T08:15:00+00:00",
+            })))
+            .with_status(201)
+            .with_body(r#"{"id": "entry-1"}"#)
+            .create_async()
+            .await;
+        let _rejected = server
+            .mock("POST", "/api/v1/worklog/entries")
+            .match_body(mockito::Matcher::PartialJson(
+                json!({ "content": "Rejected" }),
+            ))
+            .with_status(422)
+            .with_body(r#"{"error": "invalid"}"#)
+            .create_async()
+            .await;
+
+        execute(&mut auth_service).await.unwrap();
+
+        let (pending, _) = outbox.pending().unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].entry.content, "Rejected");
+    }
+
+    #[tokio::test]
+    async fn test_sync_stops_when_offline() {
+        let temp_dir = TempDir::new().unwrap();
+        // Nothing listens on port 9, so the request can't be answered
+        let mut auth_service = auth_service("http://127.0.0.1:9".into(), &temp_dir);
+        let outbox = Outbox::new(auth_service.state_dir());
+        queue(&outbox, "Still offline");
+
+        let err = execute(&mut auth_service).await.unwrap_err();
+        assert!(err.to_string().contains("Still can't reach Accomplish"));
+        assert_eq!(outbox.count(), 1);
+    }
+}
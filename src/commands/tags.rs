@@ -0,0 +1,271 @@
+use crate::auth::AuthService;
+use crate::commands::logs::fetch_all_entries;
+use crate::errors::AppError;
+use crate::tag_index::{TagIndex, TagSuggestion};
+use crate::utils::editor::read_entry_file;
+use crate::utils::prompt::PromptOrDefault;
+use chrono::{Duration, Local, Utc};
+use colored::*;
+use inquire::MultiSelect;
+use std::io::{self, IsTerminal};
+use std::path::Path;
+
+/// How old the index may get before it's rebuilt from the server.
+const INDEX_MAX_AGE_HOURS: i64 = 24;
+
+/// How far back the index learns from.
+const HISTORY_DAYS: i64 = 180;
+
+/// Most suggestions offered for one entry.
+const MAX_SUGGESTIONS: usize = 10;
+
+/// Suggestions less likely than this aren't worth offering.
+const MIN_CONFIDENCE: f64 = 0.2;
+
+/// Options for `acc tags suggest`.
+#[derive(Debug)]
+pub struct SuggestOptions<'a> {
+    /// The entry's text, one line per message
+    pub messages: &'a [String],
+    /// Read the entry from this file instead (`-` for stdin)
+    pub file: Option<&'a Path>,
+    /// Tags the entry already has, which aren't suggested
+    pub tags: &'a [String],
+    /// Suggestions at least this likely are preselected
+    pub threshold: f64,
+    /// Rebuild the index even if it's recent
+    pub refresh: bool,
+    /// Print the suggestions without asking which to keep
+    pub list_only: bool,
+}
+
+/// Suggests tags for an entry from how tags were used on past entries, and
+/// lets the ones to keep be picked, preselecting those at least as likely as
+/// the threshold. The picked tags are printed comma-separated, ready for
+/// `acc log -t`.
+pub async fn suggest(
+    auth_service: &mut AuthService,
+    offline: bool,
+    options: &SuggestOptions<'_>,
+) -> Result<(), AppError> {
+    check_threshold(options.threshold)?;
+
+    let mut existing = options.tags.to_vec();
+    let content = if !options.messages.is_empty() {
+        options.messages.join("\n\n")
+    } else {
+        let path = match options.file {
+            Some(path) => path,
+            None if !io::stdin().is_terminal() => Path::new("-"),
+            None => {
+                return Err(AppError::Other(
+                    "Give the entry with -m, --file or on stdin".to_string(),
+                ))
+            }
+        };
+        let (metadata, content) = read_entry_file(path)?;
+        existing.extend(metadata.tags);
+        content
+    };
+    if content.trim().is_empty() {
+        return Err(AppError::Other("The entry has no content".to_string()));
+    }
+
+    let index = load_index(auth_service, offline, options.refresh).await?;
+    if index.is_empty() {
+        println!("None of your entries from the last {HISTORY_DAYS} days have tags to learn from.");
+        return Ok(());
+    }
+    let suggestions = likely_tags(&index, &content, &existing);
+    if suggestions.is_empty() {
+        println!("No tag suggestions for this entry.");
+        return Ok(());
+    }
+
+    let interactive = io::stdin().is_terminal() && io::stdout().is_terminal();
+    if options.list_only || !interactive {
+        for suggestion in &suggestions {
+            let line = format!(
+                "{:>3.0}%  {}",
+                suggestion.confidence * 100.0,
+                suggestion.tag
+            );
+            if suggestion.confidence >= options.threshold {
+                println!("{}", line.green());
+            } else {
+                println!("{line}");
+            }
+        }
+        return Ok(());
+    }
+
+    let choices: Vec<String> = suggestions
+        .iter()
+        .map(|s| format!("{} ({:.0}%)", s.tag, s.confidence * 100.0))
+        .collect();
+    let preselected: Vec<usize> = suggestions
+        .iter()
+        .enumerate()
+        .filter(|(_, s)| s.confidence >= options.threshold)
+        .map(|(index, _)| index)
+        .collect();
+    let selected = MultiSelect::new("Tags for this entry:", choices.clone())
+        .with_default(&preselected)
+        .with_help_message("Use space to select, arrow keys to navigate, enter to confirm")
+        .prompt_or_default()
+        .map_err(|e| AppError::ParseError(format!("Selection failed: {e}")))?;
+
+    if selected.is_empty() {
+        println!("No tags selected.");
+        return Ok(());
+    }
+    let tags: Vec<&str> = selected
+        .iter()
+        .map(|choice| {
+            let index = choices.iter().position(|c| c == choice).unwrap();
+            suggestions[index].tag.as_str()
+        })
+        .collect();
+    println!("{}", tags.join(","));
+    Ok(())
+}
+
+/// The suggested tags for a new entry at least as likely as `threshold`, for
+/// `acc log --auto-tags`. Offline, the index is used as it is.
+pub async fn auto_tags(
+    auth_service: &mut AuthService,
+    offline: bool,
+    content: &str,
+    existing: &[String],
+    threshold: f64,
+) -> Result<Vec<String>, AppError> {
+    let index = load_index(auth_service, offline, false).await?;
+    Ok(likely_tags(&index, content, existing)
+        .into_iter()
+        .filter(|s| s.confidence >= threshold)
+        .map(|s| s.tag)
+        .collect())
+}
+
+/// The suggestions worth offering, most likely first.
+fn likely_tags(index: &TagIndex, content: &str, existing: &[String]) -> Vec<TagSuggestion> {
+    let mut suggestions = index.suggest(content, existing);
+    suggestions.retain(|s| s.confidence >= MIN_CONFIDENCE);
+    suggestions.truncate(MAX_SUGGESTIONS);
+    suggestions
+}
+
+/// The tag index, rebuilt from the last months of entries when it's missing,
+/// older than a day or `refresh` is set. A stale index is still used when the
+/// server can't be reached.
+async fn load_index(
+    auth_service: &mut AuthService,
+    offline: bool,
+    refresh: bool,
+) -> Result<TagIndex, AppError> {
+    let mut index = TagIndex::load(auth_service.state_dir());
+    let fresh = index
+        .built_at()
+        .is_some_and(|built_at| Utc::now() - built_at < Duration::hours(INDEX_MAX_AGE_HOURS));
+    if fresh && !refresh {
+        return Ok(index);
+    }
+    if offline {
+        return usable_offline(index);
+    }
+
+    let from = (Local::now() - Duration::days(HISTORY_DAYS))
+        .format("%Y-%m-%d")
+        .to_string();
+    let entries =
+        match fetch_all_entries(auth_service.api_client(), None, None, Some(&from), None).await {
+            Ok(entries) => entries,
+            Err(e) if e.is_offline() => {
+                eprintln!("⚠️  Warning: Could not refresh the tag index: {e}");
+                return usable_offline(index);
+            }
+            Err(e) => return Err(e),
+        };
+
+    index.rebuild(&entries);
+    if let Err(e) = index.save() {
+        eprintln!("⚠️  Warning: Could not save the tag index: {e}");
+    }
+    Ok(index)
+}
+
+fn usable_offline(index: TagIndex) -> Result<TagIndex, AppError> {
+    if index.built_at().is_none() {
+        return Err(AppError::Other(
+            "There's no tag index yet; it's built from your entries the first time you're online"
+                .to_string(),
+        ));
+    }
+    Ok(index)
+}
+
+pub fn check_threshold(threshold: f64) -> Result<(), AppError> {
+    if (0.0..=1.0).contains(&threshold) {
+        Ok(())
+    } else {
+        Err(AppError::ParseError(format!(
+            "Invalid threshold {threshold}. Expected a number from 0 to 1"
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_likely_tags_drops_unlikely_ones() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut index = TagIndex::load(temp_dir.path());
+        index.rebuild(&[
+            json!({ "content": "Fixed the release pipeline", "tags": ["ci"] }),
+            json!({ "content": "Release notes for the pipeline", "tags": ["ci", "docs"] }),
+            json!({ "content": "Wrote onboarding notes", "tags": ["docs"] }),
+            json!({ "content": "Cut the release", "tags": ["ops"] }),
+            json!({ "content": "Release planning" }),
+            json!({ "content": "Lunch and learn" }),
+            json!({ "content": "Planning" }),
+            json!({ "content": "Planning notes" }),
+            json!({ "content": "Planning notes" }),
+            json!({ "content": "Planning notes" }),
+        ]);
+
+        let tags: Vec<String> = likely_tags(&index, "Sped up the release pipeline", &[])
+            .into_iter()
+            .map(|s| s.tag)
+            .collect();
+        assert_eq!(tags, vec!["ci", "docs"]);
+
+        let tags: Vec<String> = likely_tags(&index, "Planning session", &[])
+            .into_iter()
+            .map(|s| s.tag)
+            .collect();
+        assert!(tags.is_empty(), "{tags:?}");
+    }
+
+    #[test]
+    fn test_check_threshold() {
+        assert!(check_threshold(0.0).is_ok());
+        assert!(check_threshold(0.75).is_ok());
+        assert!(check_threshold(1.5).is_err());
+        assert!(check_threshold(-0.1).is_err());
+    }
+
+    #[test]
+    fn test_usable_offline_needs_a_built_index() {
+        let temp_dir = TempDir::new().unwrap();
+        let err = usable_offline(TagIndex::load(temp_dir.path())).unwrap_err();
+        assert!(err.to_string().contains("no tag index yet"), "{err}");
+
+        let mut index = TagIndex::load(temp_dir.path());
+        index.rebuild(&[]);
+        assert!(usable_offline(index).is_ok());
+    }
+}
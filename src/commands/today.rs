@@ -0,0 +1,80 @@
+use crate::auth::AuthService;
+use crate::commands::logs::{fetch_all_matching, print_entries, EntryFormat};
+use crate::commands::project;
+use crate::errors::AppError;
+use crate::pins::Pins;
+use chrono::Local;
+use colored::*;
+
+/// Prints everything logged today, oldest first, without paging or prompting.
+pub async fn execute(
+    auth_service: &mut AuthService,
+    project_identifier: Option<&str>,
+    format: EntryFormat,
+) -> Result<(), AppError> {
+    let project_ids = match project_identifier {
+        Some(identifier) => project::resolve_project_filter_ids(auth_service, identifier).await?,
+        None => None,
+    };
+
+    let today = Local::now().date_naive().format("%Y-%m-%d").to_string();
+    let mut entries = fetch_all_matching(
+        auth_service.api_client(),
+        project_ids.as_deref(),
+        None,
+        Some(&today),
+        Some(&today),
+        None,
+    )
+    .await?;
+
+    if entries.is_empty() {
+        println!("Nothing logged today yet. Log something with `acc did \"<what you did>\"`.");
+        return Ok(());
+    }
+
+    entries.reverse();
+    let pins = Pins::load(auth_service.state_dir());
+    print_entries(&entries, format, &pins)?;
+    println!("{}", summary(entries.len()).bright_black());
+    Ok(())
+}
+
+fn summary(count: usize) -> String {
+    match count {
+        1 => "1 entry today".to_string(),
+        n => format!("{n} entries today"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::{Matcher, Server};
+
+    #[tokio::test]
+    async fn test_fetches_only_todays_entries() {
+        let mut server = Server::new_async().await;
+        let mut auth = AuthService::new(server.url(), std::env::temp_dir(), "test-profile");
+        auth.save_access_token("test-token").unwrap();
+
+        let today = Local::now().date_naive().format("%Y-%m-%d").to_string();
+        let list = server
+            .mock("GET", "/api/v1/worklog/entries")
+            .match_query(Matcher::Regex(format!("from={today}.*&to={today}")))
+            .with_status(200)
+            .with_body(r#"{"entries": [], "meta": {}}"#)
+            .create();
+
+        execute(&mut auth, None, EntryFormat::Oneline)
+            .await
+            .unwrap();
+        list.assert();
+    }
+
+    #[test]
+    fn test_summary() {
+        assert_eq!(summary(1), "1 entry today");
+        assert_eq!(summary(3), "3 entries today");
+    }
+}
@@ -0,0 +1,246 @@
+use crate::auth::AuthService;
+use crate::commands::log;
+use crate::errors::AppError;
+use crate::utils::visibility::VisibilityPolicy;
+use chrono::{DateTime, Duration, Utc};
+use colored::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const TODOS_FILE: &str = "todos.json";
+
+/// Something planned but not yet logged. Finishing it turns it into an entry.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Todo {
+    /// Short number used to refer to the item, e.g. `acc todo done 2`
+    pub id: u32,
+    pub text: String,
+    pub added_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct TodosFile {
+    #[serde(default)]
+    todos: Vec<Todo>,
+}
+
+fn todos_path(state_dir: &Path) -> PathBuf {
+    state_dir.join(TODOS_FILE)
+}
+
+fn load_todos(state_dir: &Path) -> Result<Vec<Todo>, AppError> {
+    let path = todos_path(state_dir);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(&path)?;
+    let file: TodosFile = serde_json::from_str(&content)
+        .map_err(|e| AppError::ParseError(format!("Failed to parse {}: {e}", path.display())))?;
+    Ok(file.todos)
+}
+
+fn save_todos(state_dir: &Path, todos: Vec<Todo>) -> Result<(), AppError> {
+    fs::create_dir_all(state_dir)?;
+    let content = serde_json::to_string_pretty(&TodosFile { todos })?;
+    fs::write(todos_path(state_dir), content)?;
+    Ok(())
+}
+
+fn take_todo(todos: &mut Vec<Todo>, id: u32) -> Result<Todo, AppError> {
+    let index = todos
+        .iter()
+        .position(|t| t.id == id)
+        .ok_or_else(|| AppError::Other(format!("No todo #{id}. See `acc todo list`")))?;
+    Ok(todos.remove(index))
+}
+
+/// Adds an item to the profile's todo list. Numbers are reused once the list
+/// is empty, so they stay short.
+pub fn add(
+    state_dir: &Path,
+    text: &str,
+    project: Option<&str>,
+    tags: &[String],
+) -> Result<(), AppError> {
+    if text.trim().is_empty() {
+        return Err(AppError::ParseError(
+            "Todo text cannot be empty".to_string(),
+        ));
+    }
+
+    let mut todos = load_todos(state_dir)?;
+    let todo = Todo {
+        id: todos.iter().map(|t| t.id).max().unwrap_or(0) + 1,
+        text: text.trim().to_string(),
+        added_at: Utc::now(),
+        project: project.map(str::to_string),
+        tags: tags.iter().map(|t| t.trim().to_string()).collect(),
+    };
+    println!("✓ Added todo #{}: {}", todo.id, todo.text);
+    todos.push(todo);
+    save_todos(state_dir, todos)
+}
+
+/// Lists open items, oldest first, with how long they've been open.
+pub fn list(state_dir: &Path) -> Result<(), AppError> {
+    let todos = load_todos(state_dir)?;
+    if todos.is_empty() {
+        println!("Nothing to do. Add an item with `acc todo add \"<text>\"`.");
+        return Ok(());
+    }
+
+    let now = Utc::now();
+    for todo in &todos {
+        let project = todo
+            .project
+            .as_deref()
+            .map(|p| format!(" [{}]", p.to_uppercase()))
+            .unwrap_or_default();
+        let tags = if todo.tags.is_empty() {
+            String::new()
+        } else {
+            format!(" #{}", todo.tags.join(" #"))
+        };
+        println!(
+            "{} {}{}{} {}",
+            format!("#{:<3}", todo.id).bright_black(),
+            todo.text,
+            project.bright_green(),
+            tags.bright_yellow(),
+            format!("({} open)", format_elapsed(now - todo.added_at)).bright_black()
+        );
+    }
+    Ok(())
+}
+
+/// Marks an item done by logging it as an entry, recording the time since it
+/// was added in the `duration_minutes` field.
+pub async fn done(
+    auth_service: &mut AuthService,
+    id: u32,
+    visibility: &VisibilityPolicy,
+) -> Result<(), AppError> {
+    let mut todos = load_todos(auth_service.state_dir())?;
+    let todo = take_todo(&mut todos, id)?;
+
+    let elapsed = Utc::now() - todo.added_at;
+    let mut fields = Map::new();
+    fields.insert(
+        "duration_minutes".to_string(),
+        json!(elapsed.num_minutes().max(0)),
+    );
+    let tags = visibility.tag_new_entry(todo.tags.clone(), None, todo.project.as_deref());
+
+    log::execute(
+        auth_service,
+        std::slice::from_ref(&todo.text),
+        &tags,
+        todo.project.as_deref(),
+        None,
+        Some(&fields),
+    )
+    .await?;
+
+    // Only drop the item once it's safely logged
+    save_todos(auth_service.state_dir(), todos)?;
+    println!("✓ Done #{id} after {}", format_elapsed(elapsed));
+    Ok(())
+}
+
+/// Removes an item without logging it.
+pub fn remove(state_dir: &Path, id: u32) -> Result<(), AppError> {
+    let mut todos = load_todos(state_dir)?;
+    let todo = take_todo(&mut todos, id)?;
+    save_todos(state_dir, todos)?;
+    println!("✓ Removed todo #{id}: {}", todo.text);
+    Ok(())
+}
+
+/// Compact elapsed time such as `45m`, `3h 20m` or `2d 4h`.
+fn format_elapsed(elapsed: Duration) -> String {
+    let minutes = elapsed.num_minutes().max(0);
+    let (days, hours, minutes) = (minutes / 1440, minutes / 60 % 24, minutes % 60);
+    match (days, hours) {
+        (0, 0) => format!("{minutes}m"),
+        (0, _) => format!("{hours}h {minutes}m"),
+        _ => format!("{days}d {hours}h"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::{Matcher, Server};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_add_and_remove_reuse_numbers() {
+        let temp_dir = TempDir::new().unwrap();
+        add(temp_dir.path(), "write migration", Some("api"), &[]).unwrap();
+        add(temp_dir.path(), "review PR", None, &["review".into()]).unwrap();
+        assert!(add(temp_dir.path(), "  ", None, &[]).is_err());
+
+        let todos = load_todos(temp_dir.path()).unwrap();
+        assert_eq!(todos.iter().map(|t| t.id).collect::<Vec<_>>(), [1, 2]);
+        assert_eq!(todos[0].project.as_deref(), Some("api"));
+
+        remove(temp_dir.path(), 1).unwrap();
+        assert!(remove(temp_dir.path(), 1).is_err());
+        add(temp_dir.path(), "deploy", None, &[]).unwrap();
+        assert_eq!(load_todos(temp_dir.path()).unwrap()[1].id, 3);
+
+        remove(temp_dir.path(), 2).unwrap();
+        remove(temp_dir.path(), 3).unwrap();
+        add(temp_dir.path(), "fresh start", None, &[]).unwrap();
+        assert_eq!(load_todos(temp_dir.path()).unwrap()[0].id, 1);
+    }
+
+    #[test]
+    fn test_format_elapsed() {
+        assert_eq!(format_elapsed(Duration::minutes(45)), "45m");
+        assert_eq!(format_elapsed(Duration::minutes(200)), "3h 20m");
+        assert_eq!(format_elapsed(Duration::minutes(3120)), "2d 4h");
+    }
+
+    #[tokio::test]
+    async fn test_done_logs_entry_with_elapsed_time() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut server = Server::new_async().await;
+        let mut auth =
+            AuthService::new(server.url(), temp_dir.path().to_path_buf(), "test-profile");
+        auth.save_access_token("test-token").unwrap();
+
+        let todos = vec![Todo {
+            id: 1,
+            text: "write migration".to_string(),
+            added_at: Utc::now() - Duration::minutes(90),
+            project: None,
+            tags: vec!["db".to_string()],
+        }];
+        save_todos(auth.state_dir(), todos).unwrap();
+
+        let create = server
+            .mock("POST", "/api/v1/worklog/entries")
+            .match_body(Matcher::PartialJson(json!({
+                "content": "write migration",
+                "tags": ["db"],
+                "custom_fields": { "duration_minutes": 90 }
+            })))
+            .with_status(201)
+            .with_body(r#"{"id":"entry-1"}"#)
+            .create();
+
+        done(&mut auth, 1, &VisibilityPolicy::default())
+            .await
+            .unwrap();
+        create.assert();
+        assert!(load_todos(auth.state_dir()).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,96 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+pub const COMPLETION_CACHE_FILE: &str = "completions.json";
+
+/// How many recently used tags are kept for completion.
+const MAX_TAGS: usize = 200;
+
+/// Project identifiers and recently used tags, saved as they're seen so shell
+/// completion can offer them without a network round trip on every Tab.
+#[derive(Debug, Default)]
+pub struct CompletionCache {
+    path: PathBuf,
+    file: CompletionCacheFile,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CompletionCacheFile {
+    #[serde(default)]
+    projects: Vec<String>,
+    /// Most recently used first
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+impl CompletionCache {
+    /// Loads the cache from `<state_dir>/completions.json`, starting empty if
+    /// the file is missing or unreadable.
+    pub fn load(state_dir: &Path) -> Self {
+        let path = state_dir.join(COMPLETION_CACHE_FILE);
+        let file = fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+
+        Self { path, file }
+    }
+
+    pub fn projects(&self) -> &[String] {
+        &self.file.projects
+    }
+
+    pub fn tags(&self) -> &[String] {
+        &self.file.tags
+    }
+
+    /// Replaces the known projects with the latest list from the API.
+    pub fn set_projects<'a>(&mut self, identifiers: impl IntoIterator<Item = &'a str>) {
+        let mut projects: Vec<String> = identifiers.into_iter().map(str::to_lowercase).collect();
+        projects.sort();
+        projects.dedup();
+        self.file.projects = projects;
+    }
+
+    /// Moves the given tags to the front of the recently used list.
+    pub fn record_tags(&mut self, tags: &[String]) {
+        let tags: Vec<String> = tags
+            .iter()
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty())
+            .collect();
+        self.file.tags.retain(|t| !tags.contains(t));
+        self.file.tags.splice(0..0, tags);
+        self.file.tags.truncate(MAX_TAGS);
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let content = serde_json::to_string_pretty(&self.file)?;
+        fs::write(&self.path, content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_recent_tags_first_and_reload() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut cache = CompletionCache::load(temp_dir.path());
+        cache.set_projects(["WEB", "api", "web"]);
+        cache.record_tags(&["ci".to_string(), "infra".to_string()]);
+        cache.record_tags(&["bug".to_string(), "ci".to_string()]);
+        cache.save().unwrap();
+
+        let reloaded = CompletionCache::load(temp_dir.path());
+        assert_eq!(reloaded.projects(), ["api", "web"]);
+        assert_eq!(reloaded.tags(), ["bug", "ci", "infra"]);
+    }
+}
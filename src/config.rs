@@ -1,7 +1,44 @@
+use crate::api::limiter::DEFAULT_MAX_CONCURRENT_REQUESTS;
+use crate::api::retry::DEFAULT_MAX_RETRIES;
+use crate::daemon_state::{DaemonSettings, RawDaemonSettings};
+use crate::integrations::Integrations;
+use crate::storage::CredentialBackend;
+use crate::tag_index::DEFAULT_AUTO_TAGS_THRESHOLD;
+use crate::utils::branch_routes::{BranchRoutes, RawBranchRoute};
+use crate::utils::commit_message::{CoAuthorMode, CommitMessageFormat, MessageStyle};
+use crate::utils::custom_fields::{FieldSchema, RawFieldDefinition};
+use crate::utils::duration::parse_duration;
+use crate::utils::notify::{NotificationSettings, RawNotificationSettings};
+use crate::utils::recap_defaults::{ProjectRecapDefaults, RawRecapDefaults, RecapDefaults};
+use crate::utils::recap_filters::{RawReplacement, RecapPipeline};
+use crate::utils::redaction::{RedactionMode, RedactionPolicy, DEFAULT_REDACTED_TAGS};
+use crate::utils::visibility::{Visibility, VisibilityPolicy};
 use config::{Config, ConfigError, Environment, File};
 use dirs_next::home_dir;
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Profile used when none is selected.
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// Settings written for the default profile, and for new profiles without a
+/// `default` profile to copy from.
+pub const DEFAULT_API_BASE: &str = "https://accomplish.dev";
+pub const DEFAULT_CLIENT_ID: &str = "90w0AXnlNgnh2XBJdexYjw";
+pub const DEFAULT_CREDENTIALS_DIR: &str = "~/.accomplish";
+
+/// File in `~/.accomplish` holding the profile chosen with `acc profile use`.
+const ACTIVE_PROFILE_FILE: &str = "active_profile";
+
+static PROFILE_OVERRIDE: OnceLock<String> = OnceLock::new();
+
+/// How long after creating an entry `acc log --amend` may replace it.
+const DEFAULT_AMEND_WINDOW: &str = "24h";
+
+/// How long `acc delete` keeps entries restorable with `acc logs restore`.
+const DEFAULT_TRASH_RETENTION: &str = "30d";
 
 pub struct Settings {
     pub api_base: String,
@@ -9,17 +46,46 @@ pub struct Settings {
     pub credentials_dir: PathBuf,
     pub profile: String,
     pub default_project: Option<String>,
+    pub digest_file: Option<PathBuf>,
+    pub recap_audience: Option<String>,
+    pub reviews_dir: PathBuf,
+    pub redaction: RedactionPolicy,
+    pub field_schema: FieldSchema,
+    pub adjust_clock_skew: bool,
+    pub hooks_dir: PathBuf,
+    pub integrations: Integrations,
+    pub assume_defaults: bool,
+    pub visibility: VisibilityPolicy,
+    pub commit_message: CommitMessageFormat,
+    pub amend_window: Option<String>,
+    pub max_concurrent_requests: usize,
+    pub require_project: bool,
+    pub recap_filters: RecapPipeline,
+    pub credential_store: CredentialBackend,
+    pub trash_retention: chrono::Duration,
+    pub suggest_shell_history: bool,
+    pub daemon: DaemonSettings,
+    pub notifications: NotificationSettings,
+    pub branch_routes: BranchRoutes,
+    pub max_retries: u32,
+    pub relative_dates: bool,
+    pub templates_dir: PathBuf,
+    pub project_recaps: ProjectRecapDefaults,
+    pub auto_tags_threshold: f64,
 }
 
 impl Settings {
+    /// Loads the settings of the active profile, see `resolve_profile`.
     pub fn new() -> Result<Self, ConfigError> {
-        // 1) Which profile? default or prod
-        let profile = std::env::var("ACCOMPLISH_ENV").unwrap_or_else(|_| "default".into());
+        Self::load(&active_profile())
+    }
+
+    pub fn load(profile: &str) -> Result<Self, ConfigError> {
+        // 1) Which profile? Chosen by the caller
+        let profile = profile.to_string();
 
         // 2) Path to ~/.accomplish/config.toml
-        let mut path =
-            home_dir().ok_or_else(|| ConfigError::Message("Could not find home dir".into()))?;
-        path.push(".accomplish/config.toml");
+        let path = config_path()?;
 
         // 3) Create default config if it doesn't exist
         Self::ensure_default_config(&path)?;
@@ -29,6 +95,12 @@ impl Settings {
             .add_source(File::with_name(path.to_str().unwrap()).required(false))
             .add_source(Environment::with_prefix("ACCOMPLISH").separator("__"))
             .build()?;
+        if cfg.get_table(&profile).is_err() {
+            return Err(ConfigError::Message(format!(
+                "Profile '{profile}' isn't in {}. See `acc profile list`",
+                path.display()
+            )));
+        }
 
         // 5) Extract each setting under the chosen profile
         let api_base = cfg.get_string(&format!("{profile}.api_base"))?;
@@ -36,15 +108,7 @@ impl Settings {
         let cred_dir_raw = cfg.get_string(&format!("{profile}.credentials_dir"))?;
 
         // 6) Expand leading '~' if present
-        let credentials_dir = if let Some(path_without_tilde) = cred_dir_raw.strip_prefix("~/") {
-            let mut home = home_dir().ok_or_else(|| {
-                ConfigError::Message("Cannot expand '~' in credentials_dir".into())
-            })?;
-            home.push(path_without_tilde);
-            home
-        } else {
-            PathBuf::from(cred_dir_raw)
-        };
+        let credentials_dir = expand_tilde(&cred_dir_raw)?;
 
         // 7) Optional global default project
         let default_project = match cfg.get_string(&format!("{profile}.default_project")) {
@@ -52,12 +116,303 @@ impl Settings {
             _ => None,
         };
 
+        // 8) Optional journal file that `acc digest` appends to
+        let digest_file = match cfg.get_string(&format!("{profile}.digest_file")) {
+            Ok(s) if !s.is_empty() => Some(expand_tilde(&s)?),
+            _ => None,
+        };
+
+        // 9) Optional default audience for `acc recap`
+        let recap_audience = match cfg.get_string(&format!("{profile}.recap_audience")) {
+            Ok(s) if !s.is_empty() => Some(s),
+            _ => None,
+        };
+
+        // 10) Where `acc review` saves weekly reviews
+        let reviews_dir = match cfg.get_string(&format!("{profile}.reviews_dir")) {
+            Ok(s) if !s.is_empty() => expand_tilde(&s)?,
+            _ => credentials_dir.join("reviews"),
+        };
+
+        // 11) Tags whose entries must not leave the terminal (recaps, digests, exports)
+        let redacted_tags = cfg
+            .get::<Vec<String>>(&format!("{profile}.redacted_tags"))
+            .unwrap_or_else(|_| {
+                DEFAULT_REDACTED_TAGS
+                    .iter()
+                    .map(|t| t.to_string())
+                    .collect()
+            });
+        let redaction_mode = match cfg.get_string(&format!("{profile}.redaction_mode")) {
+            Ok(s) => RedactionMode::parse(&s).ok_or_else(|| {
+                ConfigError::Message(format!(
+                    "Invalid redaction_mode '{s}'. Expected 'exclude' or 'summarize'"
+                ))
+            })?,
+            Err(_) => RedactionMode::Exclude,
+        };
+        let redaction = RedactionPolicy::new(redacted_tags, redaction_mode);
+
+        // 12) Org-defined custom entry fields, `[<profile>.fields.<name>]`
+        let field_schema =
+            match cfg.get::<HashMap<String, RawFieldDefinition>>(&format!("{profile}.fields")) {
+                Ok(raw) => FieldSchema::from_raw(raw).map_err(ConfigError::Message)?,
+                Err(ConfigError::NotFound(_)) => FieldSchema::default(),
+                Err(e) => {
+                    return Err(ConfigError::Message(format!(
+                        "Invalid custom field definitions: {e}"
+                    )))
+                }
+            };
+
+        // 13) Use the server clock for entry times and token expiry when the local one is off
+        let adjust_clock_skew = cfg
+            .get_bool(&format!("{profile}.adjust_clock_skew"))
+            .unwrap_or(false);
+
+        // 14) User scripts run on CLI events
+        let hooks_dir = match cfg.get_string(&format!("{profile}.hooks_dir")) {
+            Ok(s) if !s.is_empty() => expand_tilde(&s)?,
+            _ => credentials_dir.join("hooks"),
+        };
+
+        // 15) Issue trackers that tagged entries are pushed to
+        let integrations = match cfg.get::<Integrations>(&format!("{profile}.integrations")) {
+            Ok(integrations) => integrations,
+            Err(ConfigError::NotFound(_)) => Integrations::default(),
+            Err(e) => {
+                return Err(ConfigError::Message(format!(
+                    "Invalid integrations settings: {e}"
+                )))
+            }
+        };
+
+        // 16) Take default answers instead of prompting
+        let assume_defaults = cfg
+            .get_bool(&format!("{profile}.assume_defaults"))
+            .unwrap_or(false);
+
+        // 17) Visibility given to new entries, overall and per project
+        let parse_visibility = |value: &str| {
+            Visibility::parse(value).ok_or_else(|| {
+                ConfigError::Message(format!(
+                    "Invalid visibility '{value}'. Expected 'private', 'team' or 'public'"
+                ))
+            })
+        };
+        let default_visibility = match cfg.get_string(&format!("{profile}.default_visibility")) {
+            Ok(s) if !s.is_empty() => Some(parse_visibility(&s)?),
+            _ => None,
+        };
+        let project_visibility = cfg
+            .get::<HashMap<String, String>>(&format!("{profile}.project_visibility"))
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(project, value)| Ok((project, parse_visibility(&value)?)))
+            .collect::<Result<HashMap<_, _>, ConfigError>>()?;
+        let visibility = VisibilityPolicy::new(default_visibility, project_visibility);
+
+        // 18) How much of each commit message `acc capture` puts in the entry
+        let style = match cfg.get_string(&format!("{profile}.capture_message_style")) {
+            Ok(s) if !s.is_empty() => MessageStyle::parse(&s).ok_or_else(|| {
+                ConfigError::Message(format!(
+                    "Invalid capture_message_style '{s}'. Expected 'summary', 'full' or 'collapsed'"
+                ))
+            })?,
+            _ => MessageStyle::default(),
+        };
+        let strip_trailers = cfg
+            .get_bool(&format!("{profile}.capture_strip_trailers"))
+            .unwrap_or(true);
+        let co_authors = match cfg.get_string(&format!("{profile}.capture_co_authors")) {
+            Ok(s) if !s.is_empty() => CoAuthorMode::parse(&s).ok_or_else(|| {
+                ConfigError::Message(format!(
+                    "Invalid capture_co_authors '{s}'. Expected 'none', 'field' or 'tag'"
+                ))
+            })?,
+            _ => CoAuthorMode::default(),
+        };
+        let commit_message = CommitMessageFormat {
+            style,
+            strip_trailers,
+            co_authors,
+        };
+
+        // 19) How recent the last entry must be for `acc log --amend` ("off" for no limit)
+        let amend_window = match cfg.get_string(&format!("{profile}.amend_window")) {
+            Ok(s) if s.is_empty() || s.eq_ignore_ascii_case("off") => None,
+            Ok(s) => Some(s),
+            Err(_) => Some(DEFAULT_AMEND_WINDOW.to_string()),
+        };
+
+        // 20) How many API requests may run at once
+        let max_concurrent_requests =
+            match cfg.get_int(&format!("{profile}.max_concurrent_requests")) {
+                Ok(n) if n >= 1 => n as usize,
+                Ok(n) => {
+                    return Err(ConfigError::Message(format!(
+                        "Invalid max_concurrent_requests {n}. Expected at least 1"
+                    )))
+                }
+                Err(_) => DEFAULT_MAX_CONCURRENT_REQUESTS,
+            };
+
+        // 21) Refuse to create entries without a project
+        let require_project = cfg
+            .get_bool(&format!("{profile}.require_project"))
+            .unwrap_or(false);
+
+        // 22) Transforms applied to recaps before they're shown or saved, in order
+        let filter_names = cfg
+            .get::<Vec<String>>(&format!("{profile}.recap_filters"))
+            .unwrap_or_default();
+        let translate_command = cfg
+            .get_string(&format!("{profile}.recap_translate_command"))
+            .ok();
+        let replacements =
+            match cfg.get::<Vec<RawReplacement>>(&format!("{profile}.recap_replacements")) {
+                Ok(replacements) => replacements,
+                Err(ConfigError::NotFound(_)) => Vec::new(),
+                Err(e) => {
+                    return Err(ConfigError::Message(format!(
+                        "Invalid recap_replacements: {e}"
+                    )))
+                }
+            };
+        let recap_filters =
+            RecapPipeline::from_config(&filter_names, translate_command.as_deref(), &replacements)
+                .map_err(ConfigError::Message)?;
+
+        // 23) Where the access and refresh tokens are kept
+        let credential_store = match cfg.get_string(&format!("{profile}.credential_store")) {
+            Ok(s) if !s.is_empty() => CredentialBackend::parse(&s).ok_or_else(|| {
+                ConfigError::Message(format!(
+                    "Invalid credential_store '{s}'. Expected 'auto', 'keychain' or 'file'"
+                ))
+            })?,
+            _ => CredentialBackend::default(),
+        };
+
+        // 24) How long deleted entries stay in the local trash
+        let trash_retention = cfg
+            .get_string(&format!("{profile}.trash_retention"))
+            .unwrap_or_else(|_| DEFAULT_TRASH_RETENTION.to_string());
+        let trash_retention = parse_duration(&trash_retention).map_err(|_| {
+            ConfigError::Message(format!(
+                "Invalid trash_retention '{trash_retention}'. Expected a duration like 30d or 2w"
+            ))
+        })?;
+
+        // 25) Let `acc suggest` read the shell history
+        let suggest_shell_history = cfg
+            .get_bool(&format!("{profile}.suggest_shell_history"))
+            .unwrap_or(false);
+
+        // 26) What `acc daemon` watches and how often, `[<profile>.daemon]`
+        let daemon = match cfg.get::<RawDaemonSettings>(&format!("{profile}.daemon")) {
+            Ok(raw) => DaemonSettings::from_raw(raw).map_err(ConfigError::Message)?,
+            Err(ConfigError::NotFound(_)) => DaemonSettings::default(),
+            Err(e) => {
+                return Err(ConfigError::Message(format!(
+                    "Invalid daemon settings: {e}"
+                )))
+            }
+        };
+
+        // 27) Which desktop notifications are shown, `[<profile>.notifications]`
+        let notifications =
+            match cfg.get::<RawNotificationSettings>(&format!("{profile}.notifications")) {
+                Ok(raw) => NotificationSettings::from_raw(raw).map_err(ConfigError::Message)?,
+                Err(ConfigError::NotFound(_)) => NotificationSettings::default(),
+                Err(e) => {
+                    return Err(ConfigError::Message(format!(
+                        "Invalid notifications settings: {e}"
+                    )))
+                }
+            };
+
+        // 28) Projects and tags for work on matching branches, `[[<profile>.branch_routes]]`
+        let branch_routes =
+            match cfg.get::<Vec<RawBranchRoute>>(&format!("{profile}.branch_routes")) {
+                Ok(raw) => BranchRoutes::from_raw(raw).map_err(ConfigError::Message)?,
+                Err(ConfigError::NotFound(_)) => BranchRoutes::default(),
+                Err(e) => return Err(ConfigError::Message(format!("Invalid branch_routes: {e}"))),
+            };
+
+        // 29) How many times a request failing with a server error or a dropped connection is retried
+        let max_retries = match cfg.get_int(&format!("{profile}.max_retries")) {
+            Ok(n) if n >= 0 => n as u32,
+            Ok(n) => {
+                return Err(ConfigError::Message(format!(
+                    "Invalid max_retries {n}. Expected 0 or more"
+                )))
+            }
+            Err(_) => DEFAULT_MAX_RETRIES,
+        };
+
+        // 30) Show entry times as "today 14:32" instead of full timestamps
+        let relative_dates = cfg
+            .get_bool(&format!("{profile}.relative_dates"))
+            .unwrap_or(false);
+
+        // 31) Markdown templates for `acc log --edit`
+        let templates_dir = match cfg.get_string(&format!("{profile}.templates_dir")) {
+            Ok(s) if !s.is_empty() => expand_tilde(&s)?,
+            _ => credentials_dir.join("templates"),
+        };
+
+        // 32) Recap format, audience and excluded tags per project
+        let project_recaps = match cfg
+            .get::<HashMap<String, RawRecapDefaults>>(&format!("{profile}.project_recaps"))
+        {
+            Ok(raw) => ProjectRecapDefaults::from_raw(raw).map_err(ConfigError::Message)?,
+            Err(ConfigError::NotFound(_)) => ProjectRecapDefaults::default(),
+            Err(e) => return Err(ConfigError::Message(format!("Invalid project_recaps: {e}"))),
+        };
+
+        // 33) How sure a tag suggestion must be for `acc log --auto-tags` to add it
+        let auto_tags_threshold = match cfg.get_float(&format!("{profile}.auto_tags_threshold")) {
+            Ok(t) if (0.0..=1.0).contains(&t) => t,
+            Ok(t) => {
+                return Err(ConfigError::Message(format!(
+                    "Invalid auto_tags_threshold {t}. Expected a number from 0 to 1"
+                )))
+            }
+            Err(_) => DEFAULT_AUTO_TAGS_THRESHOLD,
+        };
+
         Ok(Settings {
             api_base,
             client_id,
             credentials_dir,
             profile,
             default_project,
+            digest_file,
+            recap_audience,
+            reviews_dir,
+            redaction,
+            field_schema,
+            adjust_clock_skew,
+            hooks_dir,
+            integrations,
+            assume_defaults,
+            visibility,
+            commit_message,
+            amend_window,
+            max_concurrent_requests,
+            require_project,
+            recap_filters,
+            credential_store,
+            trash_retention,
+            suggest_shell_history,
+            daemon,
+            notifications,
+            branch_routes,
+            max_retries,
+            relative_dates,
+            templates_dir,
+            project_recaps,
+            auto_tags_threshold,
         })
     }
 
@@ -75,11 +430,13 @@ impl Settings {
         }
 
         // Create default configuration content
-        let default_config = r#"[default]
-api_base = "https://accomplish.dev"
-client_id = "90w0AXnlNgnh2XBJdexYjw"
-credentials_dir = "~/.accomplish"
-"#;
+        let default_config = format!(
+            r#"[{DEFAULT_PROFILE}]
+api_base = "{DEFAULT_API_BASE}"
+client_id = "{DEFAULT_CLIENT_ID}"
+credentials_dir = "{DEFAULT_CREDENTIALS_DIR}"
+"#
+        );
 
         // Write the default configuration
         fs::write(config_path, default_config).map_err(|e| {
@@ -90,6 +447,94 @@ credentials_dir = "~/.accomplish"
     }
 }
 
+/// Where the active profile was chosen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProfileSource {
+    /// `--profile`
+    Flag,
+    /// The `ACCOMPLISH_ENV` environment variable
+    Env,
+    /// `acc profile use`
+    Selected,
+    /// Nothing chose one
+    Default,
+}
+
+/// Uses `name` for the rest of this run, from `--profile`.
+pub fn set_profile_override(name: &str) {
+    let _ = PROFILE_OVERRIDE.set(name.to_string());
+}
+
+/// The profile to use: `--profile`, then `ACCOMPLISH_ENV`, then the one chosen
+/// with `acc profile use`, then `default`.
+pub fn resolve_profile() -> (String, ProfileSource) {
+    if let Some(profile) = PROFILE_OVERRIDE.get() {
+        return (profile.clone(), ProfileSource::Flag);
+    }
+    if let Some(profile) = std::env::var("ACCOMPLISH_ENV")
+        .ok()
+        .filter(|p| !p.is_empty())
+    {
+        return (profile, ProfileSource::Env);
+    }
+    match selected_profile() {
+        Some(profile) => (profile, ProfileSource::Selected),
+        None => (DEFAULT_PROFILE.to_string(), ProfileSource::Default),
+    }
+}
+
+pub fn active_profile() -> String {
+    resolve_profile().0
+}
+
+/// `~/.accomplish`, where the config and per-profile state live.
+pub fn accomplish_dir() -> Result<PathBuf, ConfigError> {
+    home_dir()
+        .map(|home| home.join(".accomplish"))
+        .ok_or_else(|| ConfigError::Message("Could not find home dir".into()))
+}
+
+pub fn config_path() -> Result<PathBuf, ConfigError> {
+    accomplish_dir().map(|dir| dir.join("config.toml"))
+}
+
+/// The profile chosen with `acc profile use`, if any.
+pub fn selected_profile() -> Option<String> {
+    let path = accomplish_dir().ok()?.join(ACTIVE_PROFILE_FILE);
+    fs::read_to_string(path)
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+/// Remembers `profile` for later runs, or forgets the choice with None.
+pub fn select_profile(profile: Option<&str>) -> std::io::Result<()> {
+    let dir = accomplish_dir().map_err(|e| std::io::Error::other(e.to_string()))?;
+    let path = dir.join(ACTIVE_PROFILE_FILE);
+    match profile {
+        Some(profile) => {
+            fs::create_dir_all(&dir)?;
+            fs::write(path, format!("{profile}\n"))
+        }
+        None => match fs::remove_file(path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        },
+    }
+}
+
+/// Expands a leading `~/` in a configured path to the user's home directory.
+pub fn expand_tilde(raw: &str) -> Result<PathBuf, ConfigError> {
+    if let Some(path_without_tilde) = raw.strip_prefix("~/") {
+        let mut home = home_dir()
+            .ok_or_else(|| ConfigError::Message(format!("Cannot expand '~' in {raw}")))?;
+        home.push(path_without_tilde);
+        Ok(home)
+    } else {
+        Ok(PathBuf::from(raw))
+    }
+}
+
 pub fn lookup_default_project_for_dir(start: &Path) -> Option<String> {
     // First, check for local .accomplish.toml files up the directory tree
     let mut current = Some(start);
@@ -112,6 +557,43 @@ pub fn lookup_default_project_for_dir(start: &Path) -> Option<String> {
     lookup_global_project_for_dir(start)
 }
 
+/// The `[recap]` defaults in the `.accomplish.toml` that sets the directory's
+/// project, found as in `lookup_default_project_for_dir`, along with that
+/// project. They only apply to recaps of that project.
+pub fn lookup_recap_defaults_for_dir(
+    start: &Path,
+) -> Result<Option<(String, RecapDefaults)>, ConfigError> {
+    for dir in start.ancestors() {
+        let config_path = dir.join(".accomplish.toml");
+        if !config_path.exists() {
+            continue;
+        }
+        let Ok(config) = Config::builder()
+            .add_source(File::from(config_path.as_path()))
+            .build()
+        else {
+            continue;
+        };
+        let Ok(project) = config.get_string("project.default_project") else {
+            continue;
+        };
+        return match config.get::<RawRecapDefaults>("recap") {
+            Ok(raw) => {
+                let defaults = RecapDefaults::from_raw(raw).map_err(|e| {
+                    ConfigError::Message(format!("{e} in {}", config_path.display()))
+                })?;
+                Ok(Some((project, defaults)))
+            }
+            Err(ConfigError::NotFound(_)) => Ok(None),
+            Err(e) => Err(ConfigError::Message(format!(
+                "Invalid [recap] in {}: {e}",
+                config_path.display()
+            ))),
+        };
+    }
+    Ok(None)
+}
+
 fn lookup_global_project_for_dir(dir: &Path) -> Option<String> {
     let home = home_dir()?;
     let global_config_path = home.join(".accomplish/directories.toml");
@@ -130,6 +612,50 @@ fn lookup_global_project_for_dir(dir: &Path) -> Option<String> {
         .map(|entry| entry.project_identifier.clone())
 }
 
+/// Reads the `[<profile>.project_parents]` table mapping a project identifier to its
+/// parent's identifier, used to group projects when the backend has no hierarchy.
+pub fn project_parent_overrides() -> HashMap<String, String> {
+    let profile = active_profile();
+    let Some(home) = home_dir() else {
+        return HashMap::new();
+    };
+    let path = home.join(".accomplish/config.toml");
+
+    Config::builder()
+        .add_source(File::from(path).required(false))
+        .build()
+        .and_then(|cfg| cfg.get::<HashMap<String, String>>(&format!("{profile}.project_parents")))
+        .map(|parents| {
+            parents
+                .into_iter()
+                .map(|(child, parent)| (child.to_lowercase(), parent.to_lowercase()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Lists git repositories registered in `~/.accomplish/directories.toml`.
+pub fn tracked_git_directories() -> Vec<PathBuf> {
+    let Some(home) = home_dir() else {
+        return Vec::new();
+    };
+    let Ok(content) = std::fs::read_to_string(home.join(".accomplish/directories.toml")) else {
+        return Vec::new();
+    };
+    let Ok(config) = toml::from_str::<GlobalConfig>(&content) else {
+        return Vec::new();
+    };
+
+    let mut dirs: Vec<PathBuf> = config
+        .directories
+        .into_iter()
+        .filter(|(_, entry)| entry.directory_type == "git")
+        .map(|(dir, _)| PathBuf::from(dir))
+        .collect();
+    dirs.sort();
+    dirs
+}
+
 #[derive(Debug, serde::Serialize, serde::Deserialize, Default)]
 struct GlobalConfig {
     directories: std::collections::HashMap<String, DirectoryEntry>,
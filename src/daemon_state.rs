@@ -0,0 +1,238 @@
+use crate::config::expand_tilde;
+use crate::utils::duration::parse_duration;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{self, File, OpenOptions, TryLockError};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+const DAEMON_STATUS_FILE: &str = "daemon.json";
+
+/// Locked by the running daemon for as long as it runs.
+const DAEMON_LOCK_FILE: &str = "daemon.lock";
+
+/// How often `acc daemon` looks for new commits unless configured.
+const DEFAULT_INTERVAL: &str = "5m";
+
+/// `[<profile>.daemon]` as written in the config file.
+#[derive(Debug, Default, Deserialize)]
+pub struct RawDaemonSettings {
+    interval: Option<String>,
+    #[serde(default)]
+    repositories: Vec<String>,
+    #[serde(default)]
+    log: bool,
+}
+
+/// What `acc daemon` watches and what it does with new commits.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DaemonSettings {
+    /// Time between two looks at the repositories
+    pub interval: Duration,
+    /// Repositories to watch; empty means the ones in `directories.toml`
+    pub repositories: Vec<PathBuf>,
+    /// Also create an entry from each batch of captured commits
+    pub log: bool,
+}
+
+impl Default for DaemonSettings {
+    fn default() -> Self {
+        Self::from_raw(RawDaemonSettings::default()).expect("default settings are valid")
+    }
+}
+
+impl DaemonSettings {
+    pub fn from_raw(raw: RawDaemonSettings) -> Result<Self, String> {
+        let interval = raw.interval.as_deref().unwrap_or(DEFAULT_INTERVAL);
+        let interval = parse_duration(interval)
+            .ok()
+            .and_then(|d| d.to_std().ok())
+            .filter(|d| !d.is_zero())
+            .ok_or_else(|| {
+                format!("Invalid daemon interval '{interval}'. Expected a duration like 5m or 1h")
+            })?;
+        let repositories = raw
+            .repositories
+            .iter()
+            .map(|path| expand_tilde(path).map_err(|e| e.to_string()))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            interval,
+            repositories,
+            log: raw.log,
+        })
+    }
+}
+
+/// What the daemon last saw in one repository.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RepositoryStatus {
+    /// Commit HEAD pointed at when the repository was last handled
+    pub head: Option<String>,
+    /// New commits are waiting for the API to be reachable again
+    #[serde(default)]
+    pub pending: bool,
+    pub last_capture_at: Option<DateTime<Utc>>,
+    /// Why the last capture failed, until one succeeds
+    pub error: Option<String>,
+}
+
+/// Kept in `<state_dir>/daemon.json` by the running daemon, for
+/// `acc daemon status` and `acc daemon stop`. Left behind when the daemon
+/// stops, so the last run can still be reported.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DaemonStatus {
+    pub pid: u32,
+    pub started_at: DateTime<Utc>,
+    pub interval_secs: u64,
+    pub last_check_at: Option<DateTime<Utc>>,
+    pub next_check_at: Option<DateTime<Utc>>,
+    /// The API couldn't be reached on the last check that needed it
+    #[serde(default)]
+    pub offline: bool,
+    /// Why the last check couldn't reach the point of capturing, when the API
+    /// answered with an error the daemon keeps running through
+    #[serde(default)]
+    pub last_error: Option<String>,
+    pub stopped_at: Option<DateTime<Utc>>,
+    /// Keyed by repository path
+    #[serde(default)]
+    pub repositories: BTreeMap<PathBuf, RepositoryStatus>,
+    /// Local day the nothing-logged reminder was last looked into
+    pub reminded_on: Option<NaiveDate>,
+}
+
+impl DaemonStatus {
+    /// A fresh status for this process, keeping what `previous` knew about each
+    /// repository so commits made while the daemon was stopped aren't missed,
+    /// and whether today's reminder was already shown.
+    pub fn start(interval: Duration, previous: Option<DaemonStatus>) -> Self {
+        let reminded_on = previous.as_ref().and_then(|p| p.reminded_on);
+        Self {
+            pid: std::process::id(),
+            started_at: Utc::now(),
+            interval_secs: interval.as_secs(),
+            last_check_at: None,
+            next_check_at: None,
+            offline: false,
+            last_error: None,
+            stopped_at: None,
+            repositories: previous.map(|p| p.repositories).unwrap_or_default(),
+            reminded_on,
+        }
+    }
+
+    /// None when the daemon has never run for this profile, or the file is
+    /// unreadable.
+    pub fn load(state_dir: &Path) -> Option<Self> {
+        fs::read_to_string(state_dir.join(DAEMON_STATUS_FILE))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+    }
+
+    pub fn save(&self, state_dir: &Path) -> io::Result<()> {
+        fs::create_dir_all(state_dir)?;
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(state_dir.join(DAEMON_STATUS_FILE), content)
+    }
+
+    /// Whether the process that wrote this status is still running it. Its
+    /// pid is only trusted while a daemon holds the lock: after a crash, the
+    /// pid may have been given to an unrelated process.
+    pub fn is_running(&self, state_dir: &Path) -> bool {
+        self.stopped_at.is_none() && DaemonLock::is_held(state_dir)
+    }
+}
+
+/// An exclusive lock on `daemon.lock` in the state directory, held by the
+/// daemon for its whole life so only one runs per profile. The system
+/// releases it when the process exits, however it exits.
+#[derive(Debug)]
+pub struct DaemonLock {
+    _file: File,
+}
+
+impl DaemonLock {
+    /// Takes the lock, or returns None when another daemon holds it.
+    pub fn try_acquire(state_dir: &Path) -> io::Result<Option<Self>> {
+        fs::create_dir_all(state_dir)?;
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(state_dir.join(DAEMON_LOCK_FILE))?;
+        match file.try_lock() {
+            Ok(()) => Ok(Some(Self { _file: file })),
+            Err(TryLockError::WouldBlock) => Ok(None),
+            Err(TryLockError::Error(e)) => Err(e),
+        }
+    }
+
+    /// Whether a daemon holds the lock right now.
+    pub fn is_held(state_dir: &Path) -> bool {
+        let Ok(file) = File::open(state_dir.join(DAEMON_LOCK_FILE)) else {
+            return false;
+        };
+        matches!(file.try_lock_shared(), Err(TryLockError::WouldBlock))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_settings_defaults_and_validation() {
+        let settings = DaemonSettings::default();
+        assert_eq!(settings.interval, Duration::from_secs(300));
+        assert!(settings.repositories.is_empty());
+        assert!(!settings.log);
+
+        let raw = RawDaemonSettings {
+            interval: Some("1h30m".to_string()),
+            repositories: vec!["/src/web".to_string()],
+            log: true,
+        };
+        let settings = DaemonSettings::from_raw(raw).unwrap();
+        assert_eq!(settings.interval, Duration::from_secs(5400));
+        assert_eq!(settings.repositories, vec![PathBuf::from("/src/web")]);
+
+        let raw = RawDaemonSettings {
+            interval: Some("often".to_string()),
+            ..Default::default()
+        };
+        assert!(DaemonSettings::from_raw(raw).is_err());
+    }
+
+    #[test]
+    fn test_status_round_trips_and_keeps_repositories_across_restarts() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(DaemonStatus::load(temp_dir.path()).is_none());
+
+        let mut status = DaemonStatus::start(Duration::from_secs(60), None);
+        status.repositories.insert(
+            PathBuf::from("/src/web"),
+            RepositoryStatus {
+                head: Some("abc".to_string()),
+                ..Default::default()
+            },
+        );
+        status.save(temp_dir.path()).unwrap();
+        let loaded = DaemonStatus::load(temp_dir.path()).unwrap();
+        assert_eq!(loaded, status);
+        // Only a daemon holding the lock counts as running
+        assert!(!loaded.is_running(temp_dir.path()));
+        let lock = DaemonLock::try_acquire(temp_dir.path()).unwrap().unwrap();
+        assert!(loaded.is_running(temp_dir.path()));
+        assert!(DaemonLock::try_acquire(temp_dir.path()).unwrap().is_none());
+        drop(lock);
+        assert!(!loaded.is_running(temp_dir.path()));
+
+        let restarted = DaemonStatus::start(Duration::from_secs(60), Some(loaded));
+        assert_eq!(restarted.repositories, status.repositories);
+    }
+}
@@ -0,0 +1,107 @@
+use crate::api::models::WorklogEntry;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+pub const ENTRY_CACHE_FILE: &str = "recent_entries.json";
+
+/// Cache key for listings that aren't limited to a project.
+const ALL_PROJECTS: &str = "*";
+
+/// Local copy of the most recently fetched entries, keyed by project, so quick
+/// views like `acc logs tail` still work offline.
+#[derive(Debug, Default)]
+pub struct EntryCache {
+    path: PathBuf,
+    projects: HashMap<String, CachedEntries>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CachedEntries {
+    pub fetched_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub entries: Vec<WorklogEntry>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct EntryCacheFile {
+    #[serde(default)]
+    projects: HashMap<String, CachedEntries>,
+}
+
+impl EntryCache {
+    /// Loads the cache from `<state_dir>/recent_entries.json`, starting empty if
+    /// the file is missing or unreadable.
+    pub fn load(state_dir: &Path) -> Self {
+        let path = state_dir.join(ENTRY_CACHE_FILE);
+        let projects = fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<EntryCacheFile>(&content).ok())
+            .map(|file| file.projects)
+            .unwrap_or_default();
+
+        Self { path, projects }
+    }
+
+    pub fn get(&self, project_identifier: Option<&str>) -> Option<&CachedEntries> {
+        self.projects.get(&key(project_identifier))
+    }
+
+    /// Replaces the cached entries for a project (newest first, as fetched).
+    pub fn store(&mut self, project_identifier: Option<&str>, entries: Vec<WorklogEntry>) {
+        self.projects.insert(
+            key(project_identifier),
+            CachedEntries {
+                fetched_at: Some(Utc::now()),
+                entries,
+            },
+        );
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let file = EntryCacheFile {
+            projects: self.projects.clone(),
+        };
+        let content = serde_json::to_string_pretty(&file)?;
+        fs::write(&self.path, content)
+    }
+}
+
+fn key(project_identifier: Option<&str>) -> String {
+    project_identifier
+        .map(|p| p.to_lowercase())
+        .unwrap_or_else(|| ALL_PROJECTS.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_store_and_reload() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut cache = EntryCache::load(temp_dir.path());
+        assert!(cache.get(Some("web")).is_none());
+
+        let entry = |id: &str| -> WorklogEntry {
+            serde_json::from_value(json!({ "id": id, "content": "Standup" })).unwrap()
+        };
+        cache.store(Some("WEB"), vec![entry("1")]);
+        cache.store(None, vec![entry("2"), entry("1")]);
+        cache.save().unwrap();
+
+        let reloaded = EntryCache::load(temp_dir.path());
+        let web = reloaded.get(Some("web")).unwrap();
+        assert_eq!(web.entries, vec![entry("1")]);
+        assert!(web.fetched_at.is_some());
+        assert_eq!(reloaded.get(None).unwrap().entries.len(), 2);
+    }
+}
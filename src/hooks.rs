@@ -0,0 +1,218 @@
+use crate::errors::AppError;
+use serde_json::{json, Value};
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// CLI events that user scripts can hook into.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HookEvent {
+    /// After `acc log` creates an entry
+    PostLog,
+    /// After `acc capture` records commits
+    PostCapture,
+    /// Before `acc recap` asks the server for a recap
+    PreRecap,
+    /// After `acc recap` or `acc recap wait` prints a finished recap
+    PostRecap,
+}
+
+impl HookEvent {
+    pub fn name(&self) -> &'static str {
+        match self {
+            HookEvent::PostLog => "post-log",
+            HookEvent::PostCapture => "post-capture",
+            HookEvent::PreRecap => "pre-recap",
+            HookEvent::PostRecap => "post-recap",
+        }
+    }
+
+    /// A failing `pre-*` hook aborts the command; `post-*` hooks run after the
+    /// fact, so their failures are only reported.
+    fn is_blocking(&self) -> bool {
+        self.name().starts_with("pre-")
+    }
+}
+
+/// User scripts in the hooks directory (`~/.accomplish/hooks` by default), run
+/// on CLI events with a JSON description of the event on stdin.
+///
+/// For an event like `post-log`, the executable `<hooks_dir>/post-log` runs first,
+/// followed by every executable in `<hooks_dir>/post-log.d/` in name order.
+#[derive(Debug, Clone)]
+pub struct Hooks {
+    dir: PathBuf,
+    profile: String,
+}
+
+impl Hooks {
+    pub fn new(dir: PathBuf, profile: &str) -> Self {
+        Self {
+            dir,
+            profile: profile.to_string(),
+        }
+    }
+
+    /// Runs the scripts for `event`. Returns an error only when a blocking hook fails.
+    pub fn run(&self, event: HookEvent, data: Value) -> Result<(), AppError> {
+        let scripts = self.scripts(event);
+        if scripts.is_empty() {
+            return Ok(());
+        }
+
+        let payload = json!({
+            "event": event.name(),
+            "profile": self.profile,
+            "data": data,
+        })
+        .to_string();
+
+        for script in scripts {
+            let Err(reason) = self.run_script(&script, event, &payload) else {
+                continue;
+            };
+            let message = format!("Hook {} {reason}", script.display());
+            if event.is_blocking() {
+                return Err(AppError::Other(format!("{message}. Aborting.")));
+            }
+            eprintln!("⚠️  Warning: {message}");
+        }
+
+        Ok(())
+    }
+
+    fn scripts(&self, event: HookEvent) -> Vec<PathBuf> {
+        let mut scripts = Vec::new();
+
+        let main = self.dir.join(event.name());
+        if is_executable(&main) {
+            scripts.push(main);
+        }
+
+        let hook_dir = self.dir.join(format!("{}.d", event.name()));
+        if let Ok(read_dir) = fs::read_dir(&hook_dir) {
+            let mut extra: Vec<PathBuf> = read_dir
+                .filter_map(|e| e.ok().map(|e| e.path()))
+                .filter(|p| is_executable(p))
+                .collect();
+            extra.sort();
+            scripts.extend(extra);
+        }
+
+        scripts
+    }
+
+    fn run_script(&self, script: &Path, event: HookEvent, payload: &str) -> Result<(), String> {
+        let mut child = Command::new(script)
+            .env("ACC_HOOK_EVENT", event.name())
+            .env("ACC_PROFILE", &self.profile)
+            .stdin(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("could not be started: {e}"))?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            // Scripts that don't read their input close the pipe early, which is fine
+            match stdin.write_all(payload.as_bytes()) {
+                Ok(()) => {}
+                Err(e) if e.kind() == io::ErrorKind::BrokenPipe => {}
+                Err(e) => return Err(format!("could not be given its input: {e}")),
+            }
+        }
+
+        let status = child
+            .wait()
+            .map_err(|e| format!("could not be waited on: {e}"))?;
+        if status.success() {
+            Ok(())
+        } else {
+            Err(format!("failed ({status})"))
+        }
+    }
+}
+
+/// Hidden files (like editor swap files) and non-executables are skipped.
+pub fn is_executable(path: &Path) -> bool {
+    let hidden = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .map(|n| n.starts_with('.'))
+        .unwrap_or(true);
+    if hidden {
+        return false;
+    }
+
+    let Ok(metadata) = fs::metadata(path) else {
+        return false;
+    };
+    if !metadata.is_file() {
+        return false;
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        metadata.permissions().mode() & 0o111 != 0
+    }
+    #[cfg(not(unix))]
+    {
+        true
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+    use tempfile::TempDir;
+
+    fn write_script(path: &Path, body: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, format!("#!/bin/sh\n{body}\n")).unwrap();
+        fs::set_permissions(path, fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    #[test]
+    fn test_hooks_receive_payload_in_order() {
+        let temp_dir = TempDir::new().unwrap();
+        let hooks_dir = temp_dir.path().join("hooks");
+        let out = temp_dir.path().join("out.txt");
+        let out = out.display();
+
+        write_script(
+            &hooks_dir.join("post-log"),
+            &format!("cat >> {out}; echo \" $ACC_HOOK_EVENT\" >> {out}"),
+        );
+        write_script(
+            &hooks_dir.join("post-log.d").join("10-second"),
+            &format!("echo second >> {out}"),
+        );
+        // Not executable, so never run
+        fs::write(hooks_dir.join("post-log.d").join("notes.txt"), "ignored").unwrap();
+
+        let hooks = Hooks::new(hooks_dir, "work");
+        hooks
+            .run(HookEvent::PostLog, json!({ "entry": { "id": "abc" } }))
+            .unwrap();
+
+        let written = fs::read_to_string(temp_dir.path().join("out.txt")).unwrap();
+        let (payload, rest) = written.split_once(' ').unwrap();
+        let payload: Value = serde_json::from_str(payload).unwrap();
+        assert_eq!(payload["event"], "post-log");
+        assert_eq!(payload["profile"], "work");
+        assert_eq!(payload["data"]["entry"]["id"], "abc");
+        assert_eq!(rest, "post-log\nsecond\n");
+    }
+
+    #[test]
+    fn test_only_pre_hooks_abort_on_failure() {
+        let temp_dir = TempDir::new().unwrap();
+        write_script(&temp_dir.path().join("pre-recap"), "exit 1");
+        write_script(&temp_dir.path().join("post-capture"), "exit 1");
+
+        let hooks = Hooks::new(temp_dir.path().to_path_buf(), "default");
+        assert!(hooks.run(HookEvent::PreRecap, json!({})).is_err());
+        assert!(hooks.run(HookEvent::PostCapture, json!({})).is_ok());
+        assert!(hooks.run(HookEvent::PostLog, json!({})).is_ok());
+    }
+}
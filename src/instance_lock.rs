@@ -0,0 +1,181 @@
+use crate::errors::AppError;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions, TryLockError};
+use std::io::{Seek, Write};
+use std::path::Path;
+
+const LOCK_FILE: &str = "interactive.lock";
+
+const POLL_INTERVAL_MS: u64 = 500;
+
+/// What to do when another interactive command holds the lock.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LockPolicy {
+    /// Wait until the other command finishes
+    Wait,
+    /// Fail straight away
+    Fail,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct LockInfo {
+    pid: u32,
+    command: String,
+    started_at: DateTime<Utc>,
+}
+
+/// Per-profile guard that keeps interactive commands (prompts, editors) from
+/// running at the same time and interleaving on the terminal.
+///
+/// It's an exclusive lock on `interactive.lock` rather than the file's
+/// existence, so the system releases it when the holder exits, even if it
+/// crashes, and there's no stale lock to detect or clear. The file only
+/// says who holds the lock, for the message shown to the others. Released on
+/// drop.
+#[derive(Debug)]
+pub struct InstanceLock {
+    _file: File,
+}
+
+impl InstanceLock {
+    /// Takes the lock in `<state_dir>/interactive.lock`.
+    pub async fn acquire(
+        state_dir: &Path,
+        command: &str,
+        policy: LockPolicy,
+    ) -> Result<Self, AppError> {
+        fs::create_dir_all(state_dir)?;
+        let path = state_dir.join(LOCK_FILE);
+        let mut announced = false;
+
+        loop {
+            let mut file = OpenOptions::new()
+                .create(true)
+                .truncate(false)
+                .write(true)
+                .open(&path)?;
+            match file.try_lock() {
+                Ok(()) => {
+                    write_info(&mut file, command)?;
+                    return Ok(Self { _file: file });
+                }
+                Err(TryLockError::WouldBlock) => {}
+                Err(TryLockError::Error(e)) => return Err(e.into()),
+            }
+
+            // Unreadable while the holder is still writing it, or where locks
+            // keep others from reading
+            let holder = read_lock(&path).map_or_else(
+                || "Another interactive command".to_string(),
+                |info| {
+                    format!(
+                        "Another interactive command (acc {}, pid {})",
+                        info.command, info.pid
+                    )
+                },
+            );
+            match policy {
+                LockPolicy::Fail => {
+                    return Err(AppError::Other(format!(
+                        "{holder} is running for this profile. Re-run with --wait to wait for it"
+                    )))
+                }
+                LockPolicy::Wait => {
+                    if !announced {
+                        eprintln!("⏳ {holder} is running; waiting for it to finish...");
+                        announced = true;
+                    }
+                    tokio::time::sleep(std::time::Duration::from_millis(POLL_INTERVAL_MS)).await;
+                }
+            }
+        }
+    }
+}
+
+/// Replaces what the lock file says with this process, once it holds the lock.
+fn write_info(file: &mut File, command: &str) -> std::io::Result<()> {
+    let info = LockInfo {
+        pid: std::process::id(),
+        command: command.to_string(),
+        started_at: Utc::now(),
+    };
+    file.set_len(0)?;
+    file.rewind()?;
+    file.write_all(serde_json::to_string(&info)?.as_bytes())
+}
+
+fn read_lock(path: &Path) -> Option<LockInfo> {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_lock_is_exclusive_and_released_on_drop() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let lock = InstanceLock::acquire(temp_dir.path(), "capture", LockPolicy::Fail)
+            .await
+            .unwrap();
+        let err = InstanceLock::acquire(temp_dir.path(), "review", LockPolicy::Fail)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("acc capture"));
+
+        drop(lock);
+        InstanceLock::acquire(temp_dir.path(), "review", LockPolicy::Fail)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_only_the_lock_decides_who_holds_it() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(LOCK_FILE);
+
+        // Left behind by a process that's gone: nobody holds the lock
+        fs::write(&path, "garbage").unwrap();
+        let lock = InstanceLock::acquire(temp_dir.path(), "review", LockPolicy::Fail)
+            .await
+            .unwrap();
+        assert_eq!(read_lock(&path).unwrap().command, "review");
+
+        // Held, but not written yet: still held
+        fs::write(&path, "").unwrap();
+        let err = InstanceLock::acquire(temp_dir.path(), "capture", LockPolicy::Fail)
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("Another interactive command is running"),
+            "{err}"
+        );
+        drop(lock);
+    }
+
+    #[tokio::test]
+    async fn test_wait_policy_waits_for_release() {
+        let temp_dir = TempDir::new().unwrap();
+        let lock = InstanceLock::acquire(temp_dir.path(), "capture", LockPolicy::Fail)
+            .await
+            .unwrap();
+
+        let dir = temp_dir.path().to_path_buf();
+        let waiter = tokio::spawn(async move {
+            InstanceLock::acquire(&dir, "review", LockPolicy::Wait)
+                .await
+                .map(|_| ())
+        });
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        assert!(!waiter.is_finished());
+
+        drop(lock);
+        waiter.await.unwrap().unwrap();
+    }
+}
@@ -0,0 +1,171 @@
+use super::{check_response, CalendarConfig, CalendarEvent, CalendarProvider};
+use crate::errors::AppError;
+use chrono::{DateTime, SecondsFormat, Utc};
+use reqwest::Client;
+use serde_json::Value;
+
+const GOOGLE_CALENDAR_API: &str = "https://www.googleapis.com/calendar/v3";
+
+pub async fn fetch_events(
+    client: &Client,
+    config: &CalendarConfig,
+    token: &str,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Result<Vec<CalendarEvent>, AppError> {
+    let url = format!(
+        "{}/calendars/primary/events",
+        config.api_base(GOOGLE_CALENDAR_API)
+    );
+    let time_min = from.to_rfc3339_opts(SecondsFormat::Secs, true);
+    let time_max = to.to_rfc3339_opts(SecondsFormat::Secs, true);
+
+    let mut events = Vec::new();
+    let mut page_token: Option<String> = None;
+    loop {
+        let mut query = vec![
+            ("timeMin", time_min.as_str()),
+            ("timeMax", time_max.as_str()),
+            ("singleEvents", "true"),
+            ("orderBy", "startTime"),
+        ];
+        if let Some(page) = &page_token {
+            query.push(("pageToken", page.as_str()));
+        }
+
+        let resp = client
+            .get(&url)
+            .bearer_auth(token)
+            .query(&query)
+            .send()
+            .await
+            .map_err(|e| AppError::Other(format!("Could not reach Google Calendar: {e}")))?;
+        let body: Value = check_response(CalendarProvider::Google, resp)
+            .await?
+            .json()
+            .await
+            .map_err(|e| AppError::ParseError(format!("Unexpected calendar response: {e}")))?;
+
+        if let Some(items) = body.get("items").and_then(Value::as_array) {
+            events.extend(items.iter().filter_map(parse_event));
+        }
+
+        page_token = body
+            .get("nextPageToken")
+            .and_then(Value::as_str)
+            .map(String::from);
+        if page_token.is_none() {
+            return Ok(events);
+        }
+    }
+}
+
+/// Skips cancelled, declined and all-day events, which aren't meetings to log.
+fn parse_event(item: &Value) -> Option<CalendarEvent> {
+    if item.get("status").and_then(Value::as_str) == Some("cancelled") {
+        return None;
+    }
+
+    let attendees = item
+        .get("attendees")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or(&[]);
+    let declined = attendees.iter().any(|a| {
+        a.get("self").and_then(Value::as_bool) == Some(true)
+            && a.get("responseStatus").and_then(Value::as_str) == Some("declined")
+    });
+    if declined {
+        return None;
+    }
+
+    // All-day events only have `date`, not `dateTime`
+    let time = |key: &str| {
+        item.get(key)
+            .and_then(|t| t.get("dateTime"))
+            .and_then(Value::as_str)
+            .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+            .map(|t| t.with_timezone(&Utc))
+    };
+
+    Some(CalendarEvent {
+        title: item
+            .get("summary")
+            .and_then(Value::as_str)
+            .unwrap_or("(No title)")
+            .trim()
+            .to_string(),
+        start: time("start")?,
+        end: time("end")?,
+        attendees: attendees.len(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::{Matcher, Server};
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_event_skips_declined_and_all_day() {
+        let meeting = json!({
+            "summary": "Design review",
+            "start": { "dateTime": "2025-07-09T10:00:00+02:00" },
+            "end": { "dateTime": "2025-07-09T10:45:00+02:00" },
+            "attendees": [{ "email": "a@x.com", "self": true, "responseStatus": "accepted" }, { "email": "b@x.com" }]
+        });
+        let event = parse_event(&meeting).unwrap();
+        assert_eq!(event.title, "Design review");
+        assert_eq!(event.start.to_rfc3339(), "2025-07-09T08:00:00+00:00");
+        assert_eq!(event.duration_minutes(), 45);
+        assert_eq!(event.attendees, 2);
+
+        let declined = json!({
+            "summary": "Optional sync",
+            "start": { "dateTime": "2025-07-09T11:00:00Z" },
+            "end": { "dateTime": "2025-07-09T11:30:00Z" },
+            "attendees": [{ "self": true, "responseStatus": "declined" }]
+        });
+        assert_eq!(parse_event(&declined), None);
+
+        let all_day = json!({
+            "summary": "Company holiday",
+            "start": { "date": "2025-07-09" },
+            "end": { "date": "2025-07-10" }
+        });
+        assert_eq!(parse_event(&all_day), None);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_events_follows_pages() {
+        let mut server = Server::new_async().await;
+        let event = |title: &str, hour: u32| {
+            json!({
+                "summary": title,
+                "start": { "dateTime": format!("2025-07-09T{hour:02}:00:00Z") },
+                "end": { "dateTime": format!("2025-07-09T{hour:02}:30:00Z") }
+            })
+        };
+        let _first = server
+            .mock("GET", "/calendars/primary/events")
+            .match_header("authorization", "Bearer cal-token")
+            .match_query(Matcher::UrlEncoded("singleEvents".into(), "true".into()))
+            .with_body(json!({ "items": [event("Standup", 9)], "nextPageToken": "p2" }).to_string())
+            .create();
+        let _second = server
+            .mock("GET", "/calendars/primary/events")
+            .match_query(Matcher::UrlEncoded("pageToken".into(), "p2".into()))
+            .with_body(json!({ "items": [event("1:1", 14)] }).to_string())
+            .create();
+
+        let config = CalendarConfig::for_tests(CalendarProvider::Google, &server.url());
+        let from = "2025-07-09T00:00:00Z".parse().unwrap();
+        let to = "2025-07-10T00:00:00Z".parse().unwrap();
+        let events = fetch_events(&Client::new(), &config, "cal-token", from, to)
+            .await
+            .unwrap();
+        let titles: Vec<&str> = events.iter().map(|e| e.title.as_str()).collect();
+        assert_eq!(titles, vec!["Standup", "1:1"]);
+    }
+}
@@ -0,0 +1,183 @@
+mod google;
+pub mod oauth;
+mod outlook;
+
+use crate::errors::AppError;
+use crate::user_agent::generate_user_agent;
+use chrono::{DateTime, Utc};
+use reqwest::Client;
+use serde::Deserialize;
+use std::path::Path;
+
+/// Calendar services that meetings can be captured from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CalendarProvider {
+    Google,
+    Outlook,
+}
+
+impl CalendarProvider {
+    pub fn name(&self) -> &'static str {
+        match self {
+            CalendarProvider::Google => "Google",
+            CalendarProvider::Outlook => "Outlook",
+        }
+    }
+
+    /// Read-only calendar access, plus a refresh token where it has to be asked for.
+    fn scope(&self) -> &'static str {
+        match self {
+            CalendarProvider::Google => "https://www.googleapis.com/auth/calendar.readonly",
+            CalendarProvider::Outlook => "offline_access Calendars.Read",
+        }
+    }
+}
+
+/// `[<profile>.integrations.calendar]`: the calendar `acc capture --calendar`
+/// reads meetings from, and the OAuth app used to sign in to it.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CalendarConfig {
+    pub provider: CalendarProvider,
+    pub client_id: String,
+    /// Required by Google for installed apps, unused by Outlook
+    #[serde(default)]
+    pub client_secret: Option<String>,
+    /// Microsoft Entra tenant (default: "common")
+    #[serde(default)]
+    pub tenant: Option<String>,
+    /// Tags added to entries created from meetings
+    #[serde(default = "default_tags")]
+    pub tags: Vec<String>,
+    /// Overrides the provider's OAuth endpoints (for proxies and tests)
+    #[serde(default)]
+    pub auth_url: Option<String>,
+    /// Overrides the provider's calendar API (for proxies and tests)
+    #[serde(default)]
+    pub api_url: Option<String>,
+}
+
+fn default_tags() -> Vec<String> {
+    vec!["meeting".to_string()]
+}
+
+/// A meeting worth logging: accepted (or organized), timed and not cancelled.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalendarEvent {
+    pub title: String,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub attendees: usize,
+}
+
+impl CalendarEvent {
+    pub fn duration_minutes(&self) -> i64 {
+        (self.end - self.start).num_minutes()
+    }
+}
+
+impl CalendarConfig {
+    /// Where Google sends the browser to approve access. Outlook signs in
+    /// with a device code instead.
+    fn authorize_url(&self) -> String {
+        format!(
+            "{}/auth",
+            self.auth_base("https://accounts.google.com/o/oauth2/v2")
+        )
+    }
+
+    fn device_code_url(&self) -> String {
+        format!("{}/devicecode", self.microsoft_auth_base())
+    }
+
+    fn token_url(&self) -> String {
+        match self.provider {
+            CalendarProvider::Google => {
+                format!("{}/token", self.auth_base("https://oauth2.googleapis.com"))
+            }
+            CalendarProvider::Outlook => format!("{}/token", self.microsoft_auth_base()),
+        }
+    }
+
+    fn microsoft_auth_base(&self) -> String {
+        let default = format!(
+            "https://login.microsoftonline.com/{}/oauth2/v2.0",
+            self.tenant.as_deref().unwrap_or("common")
+        );
+        self.auth_base(&default)
+    }
+
+    fn auth_base(&self, default: &str) -> String {
+        self.auth_url
+            .as_deref()
+            .unwrap_or(default)
+            .trim_end_matches('/')
+            .to_string()
+    }
+
+    fn api_base(&self, default: &str) -> String {
+        self.api_url
+            .as_deref()
+            .unwrap_or(default)
+            .trim_end_matches('/')
+            .to_string()
+    }
+
+    /// Signs in if needed and returns the meetings between `from` and `to`,
+    /// in start order.
+    pub async fn fetch_events(
+        &self,
+        state_dir: &Path,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<CalendarEvent>, AppError> {
+        let client = Client::builder()
+            .user_agent(generate_user_agent())
+            .build()
+            .map_err(|e| AppError::Other(format!("Could not create HTTP client: {e}")))?;
+
+        let token_path = state_dir.join(format!(
+            "calendar-{}.json",
+            self.provider.name().to_lowercase()
+        ));
+        let token = oauth::access_token(&client, self, &token_path).await?;
+
+        let mut events = match self.provider {
+            CalendarProvider::Google => google::fetch_events(&client, self, &token, from, to).await,
+            CalendarProvider::Outlook => {
+                outlook::fetch_events(&client, self, &token, from, to).await
+            }
+        }?;
+        events.sort_by_key(|e| e.start);
+        Ok(events)
+    }
+
+    #[cfg(test)]
+    pub fn for_tests(provider: CalendarProvider, server_url: &str) -> Self {
+        Self {
+            provider,
+            client_id: "calendar-client".to_string(),
+            client_secret: None,
+            tenant: None,
+            tags: default_tags(),
+            auth_url: Some(server_url.to_string()),
+            api_url: Some(server_url.to_string()),
+        }
+    }
+}
+
+/// Turns a non-success calendar API response into an error.
+async fn check_response(
+    provider: CalendarProvider,
+    resp: reqwest::Response,
+) -> Result<reqwest::Response, AppError> {
+    let status = resp.status();
+    if status.is_success() {
+        return Ok(resp);
+    }
+    let text = resp.text().await.unwrap_or_default();
+    Err(AppError::Other(format!(
+        "{} calendar returned {status}: {text}",
+        provider.name()
+    )))
+}
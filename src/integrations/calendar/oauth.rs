@@ -0,0 +1,447 @@
+use super::{CalendarConfig, CalendarProvider};
+use crate::errors::AppError;
+use crate::storage::{load_token, save_token};
+use axum::{extract::Query, http::StatusCode, response::Html, routing::get, Router};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::{DateTime, Duration, Utc};
+use rand::distr::Alphanumeric;
+use rand::Rng;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tokio::net::TcpListener;
+use tokio::sync::oneshot;
+use url::Url;
+
+/// Tokens are refreshed this long before they expire, so a request never
+/// starts with a token that runs out halfway.
+const EXPIRY_MARGIN_SECS: i64 = 60;
+
+/// How long to wait for the browser to come back from Google's consent page.
+const LOOPBACK_TIMEOUT_SECS: u64 = 300;
+
+/// Calendar credentials, saved next to the API token with the same permissions.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredToken {
+    pub access_token: String,
+    pub refresh_token: Option<String>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl StoredToken {
+    fn is_fresh(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at - Duration::seconds(EXPIRY_MARGIN_SECS) > now
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct DeviceCodeResponse {
+    device_code: String,
+    user_code: String,
+    verification_uri: String,
+    #[serde(default = "default_interval")]
+    interval: u64,
+}
+
+fn default_interval() -> u64 {
+    5
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    refresh_token: Option<String>,
+    expires_in: i64,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenError {
+    error: String,
+    error_description: Option<String>,
+}
+
+/// Returns a valid access token for the configured calendar: the saved one,
+/// a refreshed one, or a new one from signing in: in the browser for Google,
+/// with a device code for Outlook.
+pub async fn access_token(
+    client: &Client,
+    config: &CalendarConfig,
+    token_path: &PathBuf,
+) -> Result<String, AppError> {
+    let saved = load_token(token_path)?
+        .and_then(|content| serde_json::from_str::<StoredToken>(&content).ok());
+
+    if let Some(token) = saved {
+        if token.is_fresh(Utc::now()) {
+            return Ok(token.access_token);
+        }
+        if let Some(refresh_token) = &token.refresh_token {
+            match refresh(client, config, refresh_token).await {
+                Ok(refreshed) => {
+                    store(token_path, &refreshed)?;
+                    return Ok(refreshed.access_token);
+                }
+                Err(e) => eprintln!(
+                    "⚠️  Warning: Could not refresh calendar access ({e}). Signing in again."
+                ),
+            }
+        }
+    }
+
+    let token = match config.provider {
+        CalendarProvider::Google => loopback_flow(client, config, open_browser).await?,
+        CalendarProvider::Outlook => device_flow(client, config).await?,
+    };
+    store(token_path, &token)?;
+    Ok(token.access_token)
+}
+
+fn store(token_path: &PathBuf, token: &StoredToken) -> Result<(), AppError> {
+    save_token(token_path, &serde_json::to_string(token)?)?;
+    Ok(())
+}
+
+fn open_browser(url: &str) {
+    let _ = webbrowser::open(url);
+    println!("\nTo connect your Google calendar, approve access in the browser. If it didn't open, visit:\n{url}");
+}
+
+/// OAuth 2.0 for installed apps (RFC 8252) with PKCE (RFC 7636): the browser
+/// approves access and is redirected back to a one-off server on localhost.
+/// Google's device flow doesn't allow calendar scopes, so it can't be used.
+async fn loopback_flow(
+    client: &Client,
+    config: &CalendarConfig,
+    open: impl FnOnce(&str),
+) -> Result<StoredToken, AppError> {
+    let listener = TcpListener::bind(("127.0.0.1", 0))
+        .await
+        .and_then(|listener| listener.local_addr().map(|addr| (listener, addr)));
+    let (listener, addr) = listener
+        .map_err(|e| AppError::Other(format!("Could not listen for the calendar sign-in: {e}")))?;
+    let redirect_uri = format!("http://{addr}/");
+    let verifier = random_string(64);
+    let state = random_string(32);
+
+    let mut url = Url::parse(&config.authorize_url())
+        .map_err(|e| AppError::Other(format!("Invalid calendar auth_url: {e}")))?;
+    url.query_pairs_mut()
+        .append_pair("client_id", &config.client_id)
+        .append_pair("redirect_uri", &redirect_uri)
+        .append_pair("response_type", "code")
+        .append_pair("scope", config.provider.scope())
+        .append_pair("code_challenge", &pkce_challenge(&verifier))
+        .append_pair("code_challenge_method", "S256")
+        .append_pair("state", &state)
+        // Refresh tokens are only issued for offline access, on a fresh consent
+        .append_pair("access_type", "offline")
+        .append_pair("prompt", "consent");
+
+    let (tx, rx) = oneshot::channel::<HashMap<String, String>>();
+    let tx = Arc::new(Mutex::new(Some(tx)));
+    let expected_state = state.clone();
+    let app = Router::new().route(
+        "/",
+        get(
+            move |Query(params): Query<HashMap<String, String>>| async move {
+                // Anything else reaching the port can't end the sign-in
+                if params.get("state") != Some(&expected_state) {
+                    return (StatusCode::BAD_REQUEST, Html("Unexpected request."));
+                }
+                if let Some(tx) = tx.lock().unwrap().take() {
+                    let _ = tx.send(params);
+                }
+                (
+                    StatusCode::OK,
+                    Html("Calendar connected. You can close this tab and return to the terminal."),
+                )
+            },
+        ),
+    );
+    let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+    let server = tokio::spawn(async move {
+        axum::serve(listener, app)
+            .with_graceful_shutdown(async {
+                let _ = shutdown_rx.await;
+            })
+            .await
+    });
+
+    open(url.as_str());
+    let received =
+        tokio::time::timeout(std::time::Duration::from_secs(LOOPBACK_TIMEOUT_SECS), rx).await;
+    let _ = shutdown_tx.send(());
+    let _ = server.await;
+
+    let params = match received {
+        Ok(Ok(params)) => params,
+        _ => {
+            return Err(AppError::Other(
+                "Timed out waiting for the calendar sign-in in the browser".to_string(),
+            ))
+        }
+    };
+    if let Some(error) = params.get("error") {
+        return Err(AppError::Other(format!("Calendar sign-in failed: {error}")));
+    }
+    let code = params
+        .get("code")
+        .ok_or_else(|| AppError::Other("Calendar sign-in returned no code".to_string()))?;
+
+    let mut form = vec![
+        ("client_id", config.client_id.as_str()),
+        ("code", code.as_str()),
+        ("code_verifier", verifier.as_str()),
+        ("grant_type", "authorization_code"),
+        ("redirect_uri", redirect_uri.as_str()),
+    ];
+    if let Some(secret) = &config.client_secret {
+        form.push(("client_secret", secret.as_str()));
+    }
+
+    match request_token(client, config, &form).await? {
+        Ok(token) => {
+            println!("✅ Connected your {} calendar", config.provider.name());
+            Ok(token)
+        }
+        Err(e) => Err(AppError::Other(format!(
+            "Calendar sign-in failed: {}",
+            e.error_description.unwrap_or(e.error)
+        ))),
+    }
+}
+
+fn random_string(len: usize) -> String {
+    rand::rng()
+        .sample_iter(Alphanumeric)
+        .take(len)
+        .map(char::from)
+        .collect()
+}
+
+/// The S256 code challenge for a PKCE verifier.
+fn pkce_challenge(verifier: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()))
+}
+
+/// OAuth 2.0 device authorization grant (RFC 8628), the same flow `acc login` uses.
+async fn device_flow(client: &Client, config: &CalendarConfig) -> Result<StoredToken, AppError> {
+    let provider = config.provider;
+    let resp = client
+        .post(config.device_code_url())
+        .form(&[
+            ("client_id", config.client_id.as_str()),
+            ("scope", provider.scope()),
+        ])
+        .send()
+        .await
+        .map_err(|e| AppError::Other(format!("Could not reach {}: {e}", provider.name())))?;
+    if !resp.status().is_success() {
+        let text = resp.text().await.unwrap_or_default();
+        return Err(AppError::Other(format!(
+            "{} rejected the sign-in request: {text}",
+            provider.name()
+        )));
+    }
+    let device: DeviceCodeResponse = resp
+        .json()
+        .await
+        .map_err(|e| AppError::ParseError(format!("Unexpected sign-in response: {e}")))?;
+
+    let _ = webbrowser::open(&device.verification_uri);
+    println!(
+        "\nTo connect your {} calendar, visit {} and enter code {}",
+        provider.name(),
+        device.verification_uri,
+        device.user_code
+    );
+
+    let mut interval = device.interval;
+    loop {
+        tokio::time::sleep(std::time::Duration::from_secs(interval)).await;
+
+        let mut form = vec![
+            ("client_id", config.client_id.as_str()),
+            ("device_code", device.device_code.as_str()),
+            ("grant_type", "urn:ietf:params:oauth:grant-type:device_code"),
+        ];
+        if let Some(secret) = &config.client_secret {
+            form.push(("client_secret", secret.as_str()));
+        }
+
+        match request_token(client, config, &form).await? {
+            Ok(token) => {
+                println!("✅ Connected your {} calendar", provider.name());
+                return Ok(token);
+            }
+            Err(e) if e.error == "authorization_pending" => {}
+            Err(e) if e.error == "slow_down" => interval += 5,
+            Err(e) => {
+                return Err(AppError::Other(format!(
+                    "Calendar sign-in failed: {}",
+                    e.error_description.unwrap_or(e.error)
+                )))
+            }
+        }
+    }
+}
+
+async fn refresh(
+    client: &Client,
+    config: &CalendarConfig,
+    refresh_token: &str,
+) -> Result<StoredToken, AppError> {
+    let mut form = vec![
+        ("client_id", config.client_id.as_str()),
+        ("refresh_token", refresh_token),
+        ("grant_type", "refresh_token"),
+    ];
+    if let Some(secret) = &config.client_secret {
+        form.push(("client_secret", secret.as_str()));
+    }
+
+    let mut token = request_token(client, config, &form)
+        .await?
+        .map_err(|e| AppError::Other(e.error_description.unwrap_or(e.error)))?;
+    // Google doesn't send a new refresh token on refresh; keep using the old one
+    if token.refresh_token.is_none() {
+        token.refresh_token = Some(refresh_token.to_string());
+    }
+    Ok(token)
+}
+
+/// Posts to the token endpoint. OAuth errors (like a pending authorization) are
+/// returned as `Ok(Err(..))` so the device flow can keep polling.
+async fn request_token(
+    client: &Client,
+    config: &CalendarConfig,
+    form: &[(&str, &str)],
+) -> Result<Result<StoredToken, TokenError>, AppError> {
+    let resp = client
+        .post(config.token_url())
+        .form(form)
+        .send()
+        .await
+        .map_err(|e| AppError::Other(format!("Could not reach {}: {e}", config.provider.name())))?;
+
+    let success = resp.status().is_success();
+    let text = resp.text().await.unwrap_or_default();
+    if !success {
+        return serde_json::from_str::<TokenError>(&text)
+            .map(Err)
+            .map_err(|_| AppError::Other(format!("Unexpected token response: {text}")));
+    }
+
+    let token: TokenResponse = serde_json::from_str(&text)
+        .map_err(|e| AppError::ParseError(format!("Unexpected token response: {e}")))?;
+    Ok(Ok(StoredToken {
+        access_token: token.access_token,
+        refresh_token: token.refresh_token,
+        expires_at: Utc::now() + Duration::seconds(token.expires_in),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::{Matcher, Server};
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_saved_token_is_refreshed_when_expired() {
+        let mut server = Server::new_async().await;
+        let temp_dir = TempDir::new().unwrap();
+        let token_path = temp_dir.path().join("calendar-google.json");
+        store(
+            &token_path,
+            &StoredToken {
+                access_token: "old".to_string(),
+                refresh_token: Some("refresh-1".to_string()),
+                expires_at: Utc::now() - Duration::minutes(5),
+            },
+        )
+        .unwrap();
+
+        let m = server
+            .mock("POST", "/token")
+            .match_body(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("grant_type".into(), "refresh_token".into()),
+                Matcher::UrlEncoded("refresh_token".into(), "refresh-1".into()),
+            ]))
+            .with_status(200)
+            .with_body(r#"{"access_token":"new","expires_in":3600}"#)
+            .create();
+
+        let config = CalendarConfig::for_tests(CalendarProvider::Google, &server.url());
+        let token = access_token(&Client::new(), &config, &token_path)
+            .await
+            .unwrap();
+        assert_eq!(token, "new");
+
+        // The refresh token is kept, and the fresh token is reused without a request
+        let saved: StoredToken =
+            serde_json::from_str(&load_token(&token_path).unwrap().unwrap()).unwrap();
+        assert_eq!(saved.refresh_token.as_deref(), Some("refresh-1"));
+        let again = access_token(&Client::new(), &config, &token_path)
+            .await
+            .unwrap();
+        assert_eq!(again, "new");
+        m.assert();
+    }
+
+    #[test]
+    fn test_pkce_challenge() {
+        // The example from RFC 7636, appendix B
+        assert_eq!(
+            pkce_challenge("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk"),
+            "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_google_signs_in_through_a_loopback_redirect() {
+        let mut server = Server::new_async().await;
+        let m = server
+            .mock("POST", "/token")
+            .match_body(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("grant_type".into(), "authorization_code".into()),
+                Matcher::UrlEncoded("code".into(), "auth-code".into()),
+                Matcher::Regex("code_verifier=[A-Za-z0-9]{64}".into()),
+            ]))
+            .with_status(200)
+            .with_body(r#"{"access_token":"new","refresh_token":"refresh-1","expires_in":3600}"#)
+            .create();
+
+        let config = CalendarConfig::for_tests(CalendarProvider::Google, &server.url());
+        let token = loopback_flow(&Client::new(), &config, |url| {
+            let url = Url::parse(url).unwrap();
+            assert_eq!(url.path(), "/auth");
+            let params: HashMap<String, String> = url.query_pairs().into_owned().collect();
+            assert_eq!(params["code_challenge_method"], "S256");
+            assert_eq!(params["scope"], CalendarProvider::Google.scope());
+            let redirect_uri = params["redirect_uri"].clone();
+            let state = params["state"].clone();
+
+            // The browser coming back, after a stray request that's ignored
+            tokio::spawn(async move {
+                let stray = reqwest::get(format!("{redirect_uri}?code=other&state=wrong"))
+                    .await
+                    .unwrap();
+                assert_eq!(stray.status(), 400);
+                reqwest::get(format!("{redirect_uri}?code=auth-code&state={state}"))
+                    .await
+                    .unwrap();
+            });
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(token.access_token, "new");
+        assert_eq!(token.refresh_token.as_deref(), Some("refresh-1"));
+        m.assert();
+    }
+}
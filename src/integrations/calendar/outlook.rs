@@ -0,0 +1,121 @@
+use super::{check_response, CalendarConfig, CalendarEvent, CalendarProvider};
+use crate::errors::AppError;
+use chrono::{DateTime, NaiveDateTime, SecondsFormat, Utc};
+use reqwest::Client;
+use serde_json::Value;
+
+const GRAPH_API: &str = "https://graph.microsoft.com/v1.0";
+
+pub async fn fetch_events(
+    client: &Client,
+    config: &CalendarConfig,
+    token: &str,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Result<Vec<CalendarEvent>, AppError> {
+    let start = from.to_rfc3339_opts(SecondsFormat::Secs, true);
+    let end = to.to_rfc3339_opts(SecondsFormat::Secs, true);
+
+    let mut events = Vec::new();
+    let mut request = client
+        .get(format!("{}/me/calendarView", config.api_base(GRAPH_API)))
+        .query(&[
+            ("startDateTime", start.as_str()),
+            ("endDateTime", end.as_str()),
+            ("$top", "100"),
+        ]);
+    loop {
+        let resp = request
+            .bearer_auth(token)
+            // Report times in UTC rather than each event's own time zone
+            .header("Prefer", "outlook.timezone=\"UTC\"")
+            .send()
+            .await
+            .map_err(|e| AppError::Other(format!("Could not reach Outlook: {e}")))?;
+        let body: Value = check_response(CalendarProvider::Outlook, resp)
+            .await?
+            .json()
+            .await
+            .map_err(|e| AppError::ParseError(format!("Unexpected calendar response: {e}")))?;
+
+        if let Some(items) = body.get("value").and_then(Value::as_array) {
+            events.extend(items.iter().filter_map(parse_event));
+        }
+
+        // The next link already carries the query
+        match body.get("@odata.nextLink").and_then(Value::as_str) {
+            Some(next) => request = client.get(next),
+            None => return Ok(events),
+        }
+    }
+}
+
+/// Skips cancelled, declined and all-day events, which aren't meetings to log.
+fn parse_event(item: &Value) -> Option<CalendarEvent> {
+    let flag = |key: &str| item.get(key).and_then(Value::as_bool).unwrap_or(false);
+    if flag("isCancelled") || flag("isAllDay") {
+        return None;
+    }
+    let response = item
+        .get("responseStatus")
+        .and_then(|r| r.get("response"))
+        .and_then(Value::as_str);
+    if response == Some("declined") {
+        return None;
+    }
+
+    // e.g. "2025-07-09T10:00:00.0000000", in UTC thanks to the Prefer header
+    let time = |key: &str| {
+        item.get(key)
+            .and_then(|t| t.get("dateTime"))
+            .and_then(Value::as_str)
+            .and_then(|t| NaiveDateTime::parse_from_str(t, "%Y-%m-%dT%H:%M:%S%.f").ok())
+            .map(|t| t.and_utc())
+    };
+
+    Some(CalendarEvent {
+        title: item
+            .get("subject")
+            .and_then(Value::as_str)
+            .unwrap_or("(No title)")
+            .trim()
+            .to_string(),
+        start: time("start")?,
+        end: time("end")?,
+        attendees: item
+            .get("attendees")
+            .and_then(Value::as_array)
+            .map(Vec::len)
+            .unwrap_or(0),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_event() {
+        let meeting = json!({
+            "subject": "Sprint planning",
+            "start": { "dateTime": "2025-07-09T13:00:00.0000000", "timeZone": "UTC" },
+            "end": { "dateTime": "2025-07-09T14:30:00.0000000", "timeZone": "UTC" },
+            "attendees": [{}, {}, {}],
+            "responseStatus": { "response": "accepted" }
+        });
+        let event = parse_event(&meeting).unwrap();
+        assert_eq!(event.title, "Sprint planning");
+        assert_eq!(event.start.to_rfc3339(), "2025-07-09T13:00:00+00:00");
+        assert_eq!(event.duration_minutes(), 90);
+        assert_eq!(event.attendees, 3);
+
+        let mut declined = meeting.clone();
+        declined["responseStatus"]["response"] = json!("declined");
+        assert_eq!(parse_event(&declined), None);
+
+        let mut cancelled = meeting;
+        cancelled["isCancelled"] = json!(true);
+        assert_eq!(parse_event(&cancelled), None);
+    }
+}
@@ -0,0 +1,197 @@
+use super::{print_dry_run, PushedEntry};
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+/// `[<profile>.integrations.jira]`: Jira Cloud REST API settings.
+#[derive(Debug, Clone, Deserialize)]
+pub struct JiraConfig {
+    /// Site URL, e.g. `https://acme.atlassian.net`
+    pub base_url: String,
+    pub email: String,
+    pub api_token: String,
+    /// Project keys pushed to Jira. Empty means every issue key not claimed by Linear.
+    #[serde(default)]
+    pub project_keys: Vec<String>,
+    #[serde(default)]
+    pub mode: JiraMode,
+    /// Time logged per entry in worklog mode, in Jira notation (e.g. "30m", "1h")
+    #[serde(default = "default_time_spent")]
+    pub time_spent: String,
+    /// Print the requests instead of sending them
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// What an entry becomes on the Jira issue.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JiraMode {
+    #[default]
+    Comment,
+    Worklog,
+}
+
+fn default_time_spent() -> String {
+    "30m".to_string()
+}
+
+impl JiraConfig {
+    pub async fn push(
+        &self,
+        client: &Client,
+        key: &str,
+        entry: &PushedEntry<'_>,
+    ) -> Result<(), String> {
+        let (path, body) = match self.mode {
+            JiraMode::Comment => ("comment", json!({ "body": document(entry.content) })),
+            JiraMode::Worklog => (
+                "worklog",
+                json!({
+                    "timeSpent": self.time_spent,
+                    "started": entry.recorded_at.format("%Y-%m-%dT%H:%M:%S%.3f%z").to_string(),
+                    "comment": document(entry.content),
+                }),
+            ),
+        };
+        let url = format!(
+            "{}/rest/api/3/issue/{key}/{path}",
+            self.base_url.trim_end_matches('/')
+        );
+
+        if self.dry_run {
+            let body = serde_json::to_string_pretty(&body).unwrap_or_default();
+            print_dry_run("Jira", key, &format!("POST {url}\n{body}"));
+            return Ok(());
+        }
+
+        let resp = client
+            .post(&url)
+            .basic_auth(&self.email, Some(&self.api_token))
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+
+        let status = resp.status();
+        if !status.is_success() {
+            let text = resp.text().await.unwrap_or_default();
+            return Err(format!("Jira returned {status}: {text}"));
+        }
+
+        let what = match self.mode {
+            JiraMode::Comment => "comment",
+            JiraMode::Worklog => "worklog",
+        };
+        eprintln!("🔗 Added Jira {what} to {key}");
+        Ok(())
+    }
+
+    #[cfg(test)]
+    pub fn for_tests(base_url: &str) -> Self {
+        Self {
+            base_url: base_url.to_string(),
+            email: "me@example.com".to_string(),
+            api_token: "jira-token".to_string(),
+            project_keys: Vec::new(),
+            mode: JiraMode::Comment,
+            time_spent: default_time_spent(),
+            dry_run: false,
+        }
+    }
+}
+
+/// Atlassian Document Format body with one paragraph per block of text.
+fn document(content: &str) -> Value {
+    let paragraphs: Vec<Value> = content
+        .split("\n\n")
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .map(|p| {
+            json!({
+                "type": "paragraph",
+                "content": [{ "type": "text", "text": p }]
+            })
+        })
+        .collect();
+
+    json!({ "type": "doc", "version": 1, "content": paragraphs })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::{Matcher, Server};
+
+    fn entry<'a>(tags: &'a [String]) -> PushedEntry<'a> {
+        PushedEntry {
+            content: "Fixed login\n\nAdded a regression test",
+            tags,
+            recorded_at: "2025-07-09T10:31:00Z".parse().unwrap(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_push_comment() {
+        let mut server = Server::new_async().await;
+        let m = server
+            .mock("POST", "/rest/api/3/issue/PROJ-12/comment")
+            // me@example.com:jira-token
+            .match_header(
+                "authorization",
+                "Basic bWVAZXhhbXBsZS5jb206amlyYS10b2tlbg==",
+            )
+            .match_body(Matcher::PartialJson(json!({
+                "body": { "content": [
+                    { "content": [{ "text": "Fixed login" }] },
+                    { "content": [{ "text": "Added a regression test" }] }
+                ]}
+            })))
+            .with_status(201)
+            .create();
+
+        let jira = JiraConfig::for_tests(&server.url());
+        jira.push(&Client::new(), "PROJ-12", &entry(&[]))
+            .await
+            .unwrap();
+        m.assert();
+    }
+
+    #[tokio::test]
+    async fn test_push_worklog() {
+        let mut server = Server::new_async().await;
+        let m = server
+            .mock("POST", "/rest/api/3/issue/PROJ-12/worklog")
+            .match_body(Matcher::PartialJson(json!({
+                "timeSpent": "1h",
+                "started": "2025-07-09T10:31:00.000+0000"
+            })))
+            .with_status(201)
+            .create();
+
+        let jira = JiraConfig {
+            mode: JiraMode::Worklog,
+            time_spent: "1h".to_string(),
+            ..JiraConfig::for_tests(&server.url())
+        };
+        jira.push(&Client::new(), "PROJ-12", &entry(&[]))
+            .await
+            .unwrap();
+        m.assert();
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_sends_nothing() {
+        let mut server = Server::new_async().await;
+        let m = server.mock("POST", Matcher::Any).expect(0).create();
+
+        let jira = JiraConfig {
+            dry_run: true,
+            ..JiraConfig::for_tests(&server.url())
+        };
+        jira.push(&Client::new(), "PROJ-12", &entry(&[]))
+            .await
+            .unwrap();
+        m.assert();
+    }
+}
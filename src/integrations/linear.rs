@@ -0,0 +1,146 @@
+use super::{print_dry_run, PushedEntry};
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+const LINEAR_API_URL: &str = "https://api.linear.app/graphql";
+
+/// Linear has no worklogs, so entries are added as comments.
+const COMMENT_MUTATION: &str = "mutation($issueId: String!, $body: String!) { commentCreate(input: { issueId: $issueId, body: $body }) { success } }";
+
+/// `[<profile>.integrations.linear]`: Linear GraphQL API settings.
+#[derive(Debug, Clone, Deserialize)]
+pub struct LinearConfig {
+    /// Personal API key from Linear's security settings
+    pub api_key: String,
+    /// Team keys pushed to Linear. Empty means every issue key not claimed by Jira.
+    #[serde(default)]
+    pub team_keys: Vec<String>,
+    #[serde(default = "default_api_url")]
+    pub api_url: String,
+    /// Print the requests instead of sending them
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+fn default_api_url() -> String {
+    LINEAR_API_URL.to_string()
+}
+
+impl LinearConfig {
+    pub async fn push(
+        &self,
+        client: &Client,
+        key: &str,
+        entry: &PushedEntry<'_>,
+    ) -> Result<(), String> {
+        let body = json!({
+            "query": COMMENT_MUTATION,
+            "variables": { "issueId": key, "body": entry.content },
+        });
+
+        if self.dry_run {
+            let body = serde_json::to_string_pretty(&body).unwrap_or_default();
+            print_dry_run("Linear", key, &format!("POST {}\n{body}", self.api_url));
+            return Ok(());
+        }
+
+        let resp = client
+            .post(&self.api_url)
+            .header("Authorization", &self.api_key)
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+
+        let status = resp.status();
+        let text = resp.text().await.unwrap_or_default();
+        if !status.is_success() {
+            return Err(format!("Linear returned {status}: {text}"));
+        }
+
+        // GraphQL reports failures in the body of a 200 response
+        let response: Value = serde_json::from_str(&text).map_err(|e| e.to_string())?;
+        if let Some(message) = response
+            .get("errors")
+            .and_then(Value::as_array)
+            .and_then(|errors| errors.first())
+            .and_then(|error| error.get("message"))
+            .and_then(Value::as_str)
+        {
+            return Err(format!("Linear returned an error: {message}"));
+        }
+        let success = response
+            .pointer("/data/commentCreate/success")
+            .and_then(Value::as_bool)
+            .unwrap_or(false);
+        if !success {
+            return Err("Linear did not create the comment".to_string());
+        }
+
+        eprintln!("🔗 Added Linear comment to {key}");
+        Ok(())
+    }
+
+    #[cfg(test)]
+    pub fn for_tests(api_url: &str) -> Self {
+        Self {
+            api_key: "lin_api_test".to_string(),
+            team_keys: Vec::new(),
+            api_url: api_url.to_string(),
+            dry_run: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::{Matcher, Server};
+
+    fn entry() -> PushedEntry<'static> {
+        PushedEntry {
+            content: "Shipped the importer",
+            tags: &[],
+            recorded_at: "2025-07-09T10:31:00Z".parse().unwrap(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_push_comment() {
+        let mut server = Server::new_async().await;
+        let m = server
+            .mock("POST", "/graphql")
+            .match_header("authorization", "lin_api_test")
+            .match_body(Matcher::PartialJson(json!({
+                "variables": { "issueId": "ENG-7", "body": "Shipped the importer" }
+            })))
+            .with_status(200)
+            .with_body(r#"{"data":{"commentCreate":{"success":true}}}"#)
+            .create();
+
+        let linear = LinearConfig::for_tests(&format!("{}/graphql", server.url()));
+        linear
+            .push(&Client::new(), "ENG-7", &entry())
+            .await
+            .unwrap();
+        m.assert();
+    }
+
+    #[tokio::test]
+    async fn test_push_reports_graphql_errors() {
+        let mut server = Server::new_async().await;
+        let _m = server
+            .mock("POST", "/graphql")
+            .with_status(200)
+            .with_body(r#"{"errors":[{"message":"Entity not found: Issue"}]}"#)
+            .create();
+
+        let linear = LinearConfig::for_tests(&format!("{}/graphql", server.url()));
+        let err = linear
+            .push(&Client::new(), "ENG-999", &entry())
+            .await
+            .unwrap_err();
+        assert!(err.contains("Entity not found"));
+    }
+}
@@ -0,0 +1,171 @@
+pub mod calendar;
+pub mod jira;
+pub mod linear;
+
+use crate::user_agent::generate_user_agent;
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use reqwest::Client;
+use serde::Deserialize;
+use std::sync::OnceLock;
+
+/// Third-party services configured under `[<profile>.integrations.*]`: issue
+/// trackers that entries are pushed to, and the calendar meetings are captured from.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Integrations {
+    pub jira: Option<jira::JiraConfig>,
+    pub linear: Option<linear::LinearConfig>,
+    pub calendar: Option<calendar::CalendarConfig>,
+}
+
+/// The parts of a newly created entry that are pushed to an issue.
+#[derive(Debug)]
+pub struct PushedEntry<'a> {
+    pub content: &'a str,
+    pub tags: &'a [String],
+    pub recorded_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tracker {
+    Jira,
+    Linear,
+}
+
+impl Integrations {
+    fn has_issue_trackers(&self) -> bool {
+        self.jira.is_some() || self.linear.is_some()
+    }
+
+    /// Pushes the entry to every issue its tags reference. The entry already
+    /// exists by now, so failures are reported as warnings rather than errors.
+    pub async fn push_entry(&self, entry: &PushedEntry<'_>) {
+        if !self.has_issue_trackers() {
+            return;
+        }
+
+        let client = match Client::builder().user_agent(generate_user_agent()).build() {
+            Ok(client) => client,
+            Err(e) => {
+                eprintln!("⚠️  Warning: Could not create HTTP client for integrations: {e}");
+                return;
+            }
+        };
+
+        for key in issue_keys(entry.tags) {
+            let result = match self.route(&key) {
+                Some(Tracker::Jira) => match &self.jira {
+                    Some(jira) => jira.push(&client, &key, entry).await,
+                    None => continue,
+                },
+                Some(Tracker::Linear) => match &self.linear {
+                    Some(linear) => linear.push(&client, &key, entry).await,
+                    None => continue,
+                },
+                None => continue,
+            };
+
+            if let Err(e) = result {
+                eprintln!("⚠️  Warning: Could not push entry to {key}: {e}");
+            }
+        }
+    }
+
+    /// Picks the tracker for an issue key: the one listing its prefix, or else
+    /// the one configured without a prefix list (Jira first when both are).
+    fn route(&self, key: &str) -> Option<Tracker> {
+        let prefix = key.split('-').next().unwrap_or("");
+        let jira_keys = self.jira.as_ref().map(|j| j.project_keys.as_slice());
+        let linear_keys = self.linear.as_ref().map(|l| l.team_keys.as_slice());
+
+        let lists = |keys: Option<&[String]>| {
+            keys.map(|k| k.iter().any(|p| p.eq_ignore_ascii_case(prefix)))
+                .unwrap_or(false)
+        };
+        if lists(jira_keys) {
+            return Some(Tracker::Jira);
+        }
+        if lists(linear_keys) {
+            return Some(Tracker::Linear);
+        }
+
+        match (jira_keys, linear_keys) {
+            (Some([]), _) => Some(Tracker::Jira),
+            (_, Some([])) => Some(Tracker::Linear),
+            _ => None,
+        }
+    }
+}
+
+/// Tags that look like issue keys (`PROJ-123`), uppercased and deduplicated.
+fn issue_keys(tags: &[String]) -> Vec<String> {
+    static ISSUE_KEY: OnceLock<Regex> = OnceLock::new();
+    let pattern = ISSUE_KEY.get_or_init(|| Regex::new(r"^[A-Za-z][A-Za-z0-9]*-[0-9]+$").unwrap());
+
+    let mut keys: Vec<String> = Vec::new();
+    for tag in tags {
+        let tag = tag.trim().trim_start_matches('#');
+        if pattern.is_match(tag) {
+            let key = tag.to_uppercase();
+            if !keys.contains(&key) {
+                keys.push(key);
+            }
+        }
+    }
+    keys
+}
+
+/// Shows what a dry run would have sent.
+fn print_dry_run(tracker: &str, key: &str, request: &str) {
+    println!("🔎 Dry run: would push to {tracker} issue {key}:");
+    for line in request.lines() {
+        println!("   {line}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn integrations(jira_keys: Option<&[&str]>, linear_keys: Option<&[&str]>) -> Integrations {
+        let to_vec = |keys: &[&str]| keys.iter().map(|k| k.to_string()).collect();
+        Integrations {
+            jira: jira_keys.map(|keys| jira::JiraConfig {
+                project_keys: to_vec(keys),
+                ..jira::JiraConfig::for_tests("http://jira.test")
+            }),
+            linear: linear_keys.map(|keys| linear::LinearConfig {
+                team_keys: to_vec(keys),
+                ..linear::LinearConfig::for_tests("http://linear.test")
+            }),
+            calendar: None,
+        }
+    }
+
+    #[test]
+    fn test_issue_keys_from_tags() {
+        let tags = vec![
+            "backend".to_string(),
+            "proj-12".to_string(),
+            "#ENG-7".to_string(),
+            "PROJ-12".to_string(),
+            "v2-beta".to_string(),
+        ];
+        assert_eq!(issue_keys(&tags), vec!["PROJ-12", "ENG-7"]);
+    }
+
+    #[test]
+    fn test_route_by_prefix() {
+        let both = integrations(Some(&["PROJ"]), Some(&["ENG"]));
+        assert_eq!(both.route("PROJ-1"), Some(Tracker::Jira));
+        assert_eq!(both.route("ENG-1"), Some(Tracker::Linear));
+        assert_eq!(both.route("OPS-1"), None);
+
+        // An integration without a prefix list takes whatever isn't claimed
+        let fallback = integrations(Some(&[]), Some(&["ENG"]));
+        assert_eq!(fallback.route("ENG-1"), Some(Tracker::Linear));
+        assert_eq!(fallback.route("OPS-1"), Some(Tracker::Jira));
+
+        assert_eq!(integrations(None, None).route("OPS-1"), None);
+    }
+}
@@ -1,45 +1,204 @@
-mod api;
 mod auth;
+mod capture_state;
 mod cli;
 mod commands;
+mod completion_cache;
 mod config;
-mod errors;
-mod storage;
-mod user_agent;
+mod daemon_state;
+mod entry_cache;
+mod hooks;
+mod instance_lock;
+mod integrations;
+mod outbox;
+mod pins;
+mod plugins;
+mod porcelain;
+mod scheduler;
+mod suggestions;
+mod tag_index;
+mod trash;
 mod utils;
 
 use crate::api::errors::ApiError;
+use accomplish_core::{api, diagnostics, errors, storage, user_agent};
+use api::cassette::Cassette;
+use api::retry::RetryPolicy;
 use auth::AuthService;
-use clap::Parser;
-use cli::{Cli, Commands, ProjectCommands};
-use commands::{capture, init, log, login, logout, logs, project, recap, status};
+use clap::{Parser, ValueEnum};
+use cli::{
+    CacheCommands, Cli, Commands, ConfigCommands, DaemonCommands, DigestCommands,
+    DigestScheduleCommands, ExportCommands, GoalsCommands, HooksCommands, LogsCommands,
+    ProfileCommands, ProjectCommands, RecapAudience, RecapCommands, RepoCommands, SortOrder,
+    TagsCommands, TodoCommands,
+};
+use commands::{
+    audit, cache, capture, capture_calendar, capture_reflog, completions, config_route, daemon,
+    delete, digest, digest_schedule, edit, export, git_hooks, goals, init, init_scan, log,
+    log_amend, log_porcelain, login, logout, logs, logs_browser, logs_diff, logs_history,
+    logs_show, logs_tail, logs_trash, pin, profile, project, prompt, recap, repo, review, standup,
+    status, suggest, sync, tags, today, todo,
+};
 use config::Settings;
 use errors::AppError;
+use hooks::{HookEvent, Hooks};
+use instance_lock::{InstanceLock, LockPolicy};
 use serde_json::Value;
 use std::env;
+use std::io;
 use std::process;
+use utils::branch_routes;
+use utils::commit_message::CommitMessageFormat;
+use utils::recap_defaults::RecapDefaults;
+use utils::visibility::Visibility;
 
 #[tokio::main]
 async fn main() -> Result<(), AppError> {
+    let cli = Cli::parse();
+    diagnostics::set_warning_handler(|message| eprintln!("⚠️  Warning: {message}"));
+    if let Some(profile) = &cli.profile {
+        config::set_profile_override(profile);
+    }
+
+    // Profiles are managed before one is loaded, so a broken one can be fixed
+    if let Commands::Profile { command } = &cli.command {
+        let result = match command {
+            ProfileCommands::List => profile::list(),
+            ProfileCommands::Use { name } => profile::use_profile(name),
+            ProfileCommands::Add {
+                name,
+                api_base,
+                client_id,
+                credentials_dir,
+            } => profile::add(
+                name,
+                &profile::NewProfile {
+                    api_base: api_base.clone(),
+                    client_id: client_id.clone(),
+                    credentials_dir: credentials_dir.clone(),
+                },
+            ),
+            ProfileCommands::Remove { name, force } => profile::remove(name, *force),
+        };
+        if let Err(e) = result {
+            eprintln!("\nerror: {e}");
+            process::exit(1);
+        }
+        return Ok(());
+    }
+
     // 1) Load settings
-    let settings = Settings::new()?;
+    let mut settings = Settings::new().unwrap_or_else(|e| {
+        eprintln!("\nerror: {e}");
+        process::exit(1);
+    });
+
+    // Completion runs on every Tab, so it's answered before credentials are loaded
+    if let Commands::Completions { shell, list } = cli.command {
+        if let Some(values) = list {
+            completions::list(&settings.credentials_dir.join(&settings.profile), values);
+        } else if let Some(shell) = shell {
+            if let Err(e) = completions::print_script(shell) {
+                eprintln!("\nerror: {e}");
+                process::exit(1);
+            }
+        }
+        return Ok(());
+    }
+
+    // Runs on every prompt, so it only reads local state
+    if let Commands::Prompt { format } = &cli.command {
+        prompt::execute(&settings.credentials_dir.join(&settings.profile), format);
+        return Ok(());
+    }
 
     // 2) Init AuthService
-    let mut auth_service = AuthService::new(
+    let mut auth_service = AuthService::with_backend(
         settings.api_base.clone(),
         settings.credentials_dir.clone(),
         &settings.profile,
+        settings.credential_store,
     );
+    auth_service
+        .api_client()
+        .clock()
+        .set_adjust(settings.adjust_clock_skew);
+    auth_service
+        .api_client()
+        .limiter()
+        .set_max(settings.max_concurrent_requests);
+    if let Some(cassette) = Cassette::from_env()? {
+        auth_service.use_cassette(cassette);
+    }
+    let hooks = Hooks::new(settings.hooks_dir.clone(), &settings.profile);
 
     // 3) Dispatch commands
-    match Cli::parse().command {
+    let lock_policy = if cli.wait {
+        LockPolicy::Wait
+    } else {
+        LockPolicy::Fail
+    };
+    utils::prompt::set_assume_defaults(cli.defaults || settings.assume_defaults);
+    log::set_require_project(settings.require_project);
+    log::set_field_schema(settings.field_schema.clone());
+    logs::set_relative_dates(settings.relative_dates);
+    recap::set_filters(std::mem::take(&mut settings.recap_filters));
+    capture::set_branch_routes(settings.branch_routes.clone());
+    if cli.no_color {
+        colored::control::set_override(false);
+    }
+    auth_service
+        .api_client()
+        .set_ignore_schema_errors(cli.ignore_schema_errors);
+    auth_service.api_client().set_retry_policy(if cli.no_retry {
+        RetryPolicy::none()
+    } else {
+        RetryPolicy::with_max_retries(settings.max_retries)
+    });
+
+    // `acc did` is `acc log -m` with everything else left to the defaults
+    let command = match cli.command {
+        Commands::Did {
+            words,
+            tags,
+            project_identifier,
+            no_project,
+            quiet,
+        } => Commands::Log {
+            messages: vec![words.join(" ")],
+            tags,
+            edit: false,
+            template: None,
+            file: None,
+            project_identifier,
+            no_project,
+            at: None,
+            fields: Vec::new(),
+            visibility: None,
+            follows: None,
+            relates_to: Vec::new(),
+            branch_context: false,
+            amend: false,
+            force: false,
+            auto_tags: false,
+            quiet,
+            porcelain: false,
+        },
+        command => command,
+    };
+
+    match command {
         Commands::Version => {
             const VERSION: &str = env!("CARGO_PKG_VERSION");
             const NAME: &str = env!("CARGO_PKG_NAME");
             println!("{NAME} {VERSION}");
         }
-        Commands::Login => {
-            if let Err(e) = login::execute(&mut auth_service, &settings.client_id).await {
+        Commands::Login { resume, qr } => {
+            let result = if resume {
+                login::resume(&mut auth_service, qr).await
+            } else {
+                login::execute(&mut auth_service, &settings.client_id, qr).await
+            };
+            if let Err(e) = result {
                 if let AppError::Api(ApiError::Unauthorized(body)) = &e {
                     let err_code = serde_json::from_str::<Value>(body.as_str())
                         .ok()
@@ -63,6 +222,10 @@ async fn main() -> Result<(), AppError> {
                             "Device code expired".to_string(),
                             "Restart `accomplish login` to get a new code".to_string(),
                         ),
+                        "access_denied" => (
+                            "Login request was denied".to_string(),
+                            "Run `acc login` to start over".to_string(),
+                        ),
                         other => (
                             format!("Authentication error: {other}"),
                             "See API docs for error codes".to_string(),
@@ -83,41 +246,144 @@ async fn main() -> Result<(), AppError> {
             auth_service.clear_tokens();
             logout::execute();
         }
-        Commands::Status => {
-            status::execute(&mut auth_service).await?;
-        }
-        Commands::Capture { limit, edit } => {
-            if let Err(e) = auth_service.ensure_authenticated().await {
-                if matches!(e, AppError::Auth(_)) {
-                    eprintln!();
-                    eprintln!("You are not authenticated. Run `accomplish login` first.");
-                    process::exit(1);
-                } else {
-                    eprintln!();
-                    eprintln!("error: {e}");
-                    process::exit(1);
-                }
+        Commands::Status { watch, interval } => {
+            if watch {
+                status::watch(&mut auth_service, interval).await?;
+            } else {
+                status::execute(&mut auth_service).await?;
             }
+        }
+        Commands::Api {
+            method,
+            endpoint,
+            fields,
+            paginate,
+        } => {
+            require_auth(&mut auth_service).await;
 
-            if let Err(e) = capture::execute(&mut auth_service, limit, edit).await {
+            if let Err(e) =
+                commands::api::execute(&mut auth_service, &method, &endpoint, &fields, paginate)
+                    .await
+            {
                 eprintln!("\nerror: {e}");
                 process::exit(1);
             }
         }
-        Commands::Init => {
-            if let Err(e) = auth_service.ensure_authenticated().await {
-                if matches!(e, AppError::Auth(_)) {
-                    eprintln!();
-                    eprintln!("You are not authenticated. Run `accomplish login` first.");
+        Commands::Capture {
+            limit,
+            edit,
+            date,
+            no_cache,
+            path,
+            paths,
+            message_style,
+            keep_trailers,
+            calendar,
+            reflog,
+            since,
+            fixup,
+            auto,
+            log_entry,
+            quiet,
+        } => {
+            require_connectivity(&auth_service).await;
+            require_auth(&mut auth_service).await;
+            // Unattended runs never prompt, so they don't hold the lock
+            let _lock = if auto {
+                None
+            } else {
+                Some(lock_interactive(&auth_service, "capture", lock_policy).await)
+            };
+
+            if calendar {
+                let project = config::lookup_default_project_for_dir(&env::current_dir().unwrap())
+                    .or(settings.default_project.clone());
+                if let Err(e) = capture_calendar::execute(
+                    &mut auth_service,
+                    settings.integrations.calendar.as_ref(),
+                    date.as_deref(),
+                    project.as_deref(),
+                )
+                .await
+                {
+                    eprintln!("\nerror: {e}");
                     process::exit(1);
-                } else {
-                    eprintln!();
-                    eprintln!("error: {e}");
+                }
+                return Ok(());
+            }
+
+            if reflog {
+                if let Err(e) = capture_reflog::execute(
+                    &mut auth_service,
+                    path.as_deref(),
+                    &since,
+                    edit,
+                    settings.default_project.as_deref(),
+                )
+                .await
+                {
+                    eprintln!("\nerror: {e}");
                     process::exit(1);
                 }
+                return Ok(());
+            }
+
+            let message_format = CommitMessageFormat {
+                style: message_style.unwrap_or(settings.commit_message.style),
+                strip_trailers: settings.commit_message.strip_trailers && !keep_trailers,
+                ..settings.commit_message
+            };
+            if let Err(e) = capture::execute(
+                &mut auth_service,
+                limit,
+                edit,
+                date.as_deref(),
+                no_cache,
+                path.as_deref(),
+                &paths,
+                &message_format,
+                fixup.as_deref(),
+                if auto {
+                    capture::CaptureMode::Auto {
+                        log: log_entry,
+                        quiet,
+                    }
+                } else {
+                    capture::CaptureMode::Interactive
+                },
+                &hooks,
+            )
+            .await
+            {
+                eprintln!("\nerror: {e}");
+                process::exit(1);
             }
+        }
+        Commands::Init {
+            scan,
+            project,
+            local,
+            global,
+            repo_name,
+            yes,
+        } => {
+            require_connectivity(&auth_service).await;
+            require_auth(&mut auth_service).await;
+            let _lock = lock_interactive(&auth_service, "init", lock_policy).await;
 
-            if let Err(e) = init::execute(&mut auth_service).await {
+            let result = match scan {
+                Some(root) => init_scan::execute(&mut auth_service, &root).await,
+                None => {
+                    let options = init::InitOptions {
+                        project,
+                        local: (local || global).then_some(local),
+                        repo_name,
+                        yes,
+                    };
+                    init::execute(&mut auth_service, &options).await
+                }
+            };
+            if let Err(e) = result {
                 eprintln!("\nerror: {e}");
                 process::exit(1);
             }
@@ -126,36 +392,225 @@ async fn main() -> Result<(), AppError> {
             messages,
             tags,
             edit,
+            template,
+            file,
             project_identifier,
+            no_project,
+            at,
+            fields,
+            visibility,
+            follows,
+            relates_to,
+            branch_context,
+            amend,
+            force,
+            auto_tags,
+            quiet,
+            porcelain,
         } => {
-            if let Err(e) = auth_service.ensure_authenticated().await {
-                if matches!(e, AppError::Auth(_)) {
-                    eprintln!();
-                    eprintln!("You are not authenticated. Run `accomplish login` first.");
-                    process::exit(1);
+            if porcelain {
+                let options = log_porcelain::PorcelainOptions {
+                    profile: &settings.profile,
+                    default_project: settings.default_project.as_deref(),
+                    visibility: &settings.visibility,
+                    field_schema: &settings.field_schema,
+                };
+                let code = log_porcelain::execute(
+                    &mut auth_service,
+                    &options,
+                    io::stdin().lock(),
+                    io::stdout().lock(),
+                )
+                .await;
+                process::exit(code);
+            }
+            if amend {
+                require_auth(&mut auth_service).await;
+                let _lock = if messages.is_empty() {
+                    Some(lock_interactive(&auth_service, "log", lock_policy).await)
                 } else {
-                    eprintln!();
-                    eprintln!("error: {e}");
+                    None
+                };
+                if let Err(e) = log_amend::execute(
+                    &mut auth_service,
+                    &messages,
+                    settings.amend_window.as_deref(),
+                    force,
+                )
+                .await
+                {
+                    eprintln!("\nerror: {e}");
                     process::exit(1);
                 }
+                return Ok(());
             }
 
-            let processed_tags: Vec<String> = tags
+            // A prepared entry is read like the editor's content, front-matter included
+            let (messages, file_metadata) = match file {
+                Some(path) => match utils::editor::read_entry_file(&path) {
+                    Ok((_, content)) if content.is_empty() => {
+                        eprintln!("\nerror: '{}' has no entry content", path.display());
+                        process::exit(1);
+                    }
+                    Ok((metadata, content)) => (vec![content], metadata),
+                    Err(e) => {
+                        eprintln!("\nerror: {e}");
+                        process::exit(1);
+                    }
+                },
+                None => (messages, utils::editor::EntryMetadata::default()),
+            };
+
+            // Validate before opening the editor so a typo doesn't cost the written entry
+            let template = if edit {
+                match utils::templates::Templates::new(settings.templates_dir.clone())
+                    .load(template.as_deref())
+                {
+                    Ok(template) => template,
+                    Err(e) => {
+                        eprintln!("\nerror: {e}");
+                        process::exit(1);
+                    }
+                }
+            } else {
+                String::new()
+            };
+            let custom_fields = match settings.field_schema.validate(&fields) {
+                Ok(values) => values,
+                Err(e) => {
+                    eprintln!("\nerror: {e}");
+                    process::exit(1);
+                }
+            };
+
+            let at = match at.as_deref().map(log::parse_at).transpose() {
+                Ok(at) => at.or(file_metadata.date),
+                Err(e) => {
+                    eprintln!("\nerror: {e}");
+                    process::exit(1);
+                }
+            };
+
+            // Offline, the entry is kept in the outbox for `acc sync` instead
+            let offline = require_auth_or_offline(&mut auth_service).await;
+
+            let mut processed_tags: Vec<String> = tags
                 .unwrap_or_default()
                 .iter()
                 .flat_map(|s| s.split(','))
                 .map(|s| s.trim().to_string())
                 .filter(|s| !s.is_empty())
                 .collect();
+            for tag in file_metadata.tags {
+                if !processed_tags.contains(&tag) {
+                    processed_tags.push(tag);
+                }
+            }
+
+            let branch_route = if branch_context {
+                let Some(branch) = branch_routes::current_branch(&env::current_dir().unwrap())
+                else {
+                    eprintln!(
+                        "\nerror: --branch-context needs a branch checked out in a git repository"
+                    );
+                    process::exit(1);
+                };
+                match settings.branch_routes.route(&branch) {
+                    Some((_, route)) => Some(route.clone()),
+                    None => {
+                        eprintln!("⚠️  Warning: No branch route matches '{branch}'");
+                        None
+                    }
+                }
+            } else {
+                None
+            };
+            let route_project = match &branch_route {
+                Some(route) if !no_project => route.project.clone(),
+                _ => None,
+            };
+            for tag in branch_route.iter().flat_map(|route| &route.tags) {
+                if !processed_tags.contains(tag) {
+                    processed_tags.push(tag.clone());
+                }
+            }
+
+            let mut resolved_project_identifier = project_identifier
+                .or(file_metadata.project)
+                .or(route_project)
+                .or_else(|| config::lookup_default_project_for_dir(&env::current_dir().unwrap()))
+                .or(settings.default_project.clone());
+            if no_project && settings.require_project {
+                eprintln!("\nerror: --no-project can't be used while require_project is set");
+                process::exit(1);
+            }
+            // With require_project, log::execute asks without offering "No project"
+            if resolved_project_identifier.is_none()
+                && !no_project
+                && !settings.require_project
+                && !offline
+            {
+                resolved_project_identifier =
+                    prompt_for_project(&mut auth_service, "Log to which project?", "No project")
+                        .await;
+            }
+
+            let relations = match log::resolve_relations(
+                auth_service.api_client(),
+                follows.as_deref(),
+                &relates_to,
+            )
+            .await
+            {
+                Ok(relations) => relations,
+                Err(e) => {
+                    eprintln!("\nerror: {e}");
+                    process::exit(1);
+                }
+            };
+
+            // Only the editor is interactive
+            let _lock = if edit {
+                Some(lock_interactive(&auth_service, "log", lock_policy).await)
+            } else {
+                None
+            };
 
-            let final_messages = if edit {
-                match utils::editor::open_in_editor(Some(utils::editor::DEFAULT_TEMPLATE)) {
-                    Ok(content) => {
+            let (final_messages, final_tags, final_project, recorded_at) = if edit {
+                let date = at.unwrap_or_else(chrono::Local::now);
+                let context = utils::templates::TemplateContext::gather(
+                    &template,
+                    &env::current_dir().unwrap(),
+                    resolved_project_identifier.as_deref(),
+                    date,
+                );
+                let template = utils::templates::expand(&template, &context);
+
+                // Tags, project and date are edited as front-matter alongside the content
+                let metadata = utils::editor::EntryMetadata {
+                    tags: processed_tags,
+                    project: resolved_project_identifier,
+                    date: Some(date),
+                };
+                let template = format!(
+                    "{}{template}",
+                    utils::editor::render_front_matter(&metadata)
+                );
+
+                let edited = utils::editor::open_in_editor(Some(&template))
+                    .and_then(|content| utils::editor::parse_front_matter(&content));
+                match edited {
+                    Ok((metadata, content)) => {
                         if content.is_empty() {
                             eprintln!("No content provided. Aborting.");
                             process::exit(1);
                         }
-                        vec![content]
+                        (
+                            vec![content],
+                            metadata.tags,
+                            metadata.project,
+                            metadata.date.map(|d| d.to_rfc3339()),
+                        )
                     }
                     Err(e) => {
                         eprintln!("\nerror: {e}");
@@ -163,24 +618,136 @@ async fn main() -> Result<(), AppError> {
                     }
                 }
             } else {
-                messages
+                (
+                    messages,
+                    processed_tags,
+                    resolved_project_identifier,
+                    at.map(|d| d.to_rfc3339()),
+                )
             };
+            let mut final_tags = final_tags;
+            if auto_tags {
+                match tags::auto_tags(
+                    &mut auth_service,
+                    offline,
+                    &final_messages.join("\n\n"),
+                    &final_tags,
+                    settings.auto_tags_threshold,
+                )
+                .await
+                {
+                    Ok(suggested) if !suggested.is_empty() => {
+                        eprintln!("🏷️  Added suggested tags: {}", suggested.join(", "));
+                        final_tags.extend(suggested);
+                    }
+                    Ok(_) => {}
+                    // The entry matters more than its tags
+                    Err(e) => eprintln!("⚠️  Warning: Could not suggest tags: {e}"),
+                }
+            }
+            let final_tags =
+                settings
+                    .visibility
+                    .tag_new_entry(final_tags, visibility, final_project.as_deref());
 
-            let resolved_project_identifier = project_identifier
-                .or_else(|| config::lookup_default_project_for_dir(&env::current_dir().unwrap()))
-                .or(settings.default_project.clone());
+            let created = if offline {
+                None
+            } else if quiet {
+                Some(
+                    log::create(
+                        &mut auth_service,
+                        &final_messages,
+                        &final_tags,
+                        final_project.as_deref(),
+                        recorded_at.as_deref(),
+                        Some(&custom_fields),
+                    )
+                    .await
+                    .map(|created| {
+                        println!("{}", created.id);
+                        created.id
+                    }),
+                )
+            } else {
+                Some(
+                    log::execute(
+                        &mut auth_service,
+                        &final_messages,
+                        &final_tags,
+                        final_project.as_deref(),
+                        recorded_at.as_deref(),
+                        Some(&custom_fields),
+                    )
+                    .await,
+                )
+            };
+            match created {
+                Some(Ok(id)) => {
+                    if let Err(e) =
+                        log::link_entry(auth_service.api_client(), &id, &relations).await
+                    {
+                        eprintln!("⚠️  Warning: Could not link the entry: {e}");
+                    }
 
-            if let Err(e) = log::execute(
-                &mut auth_service,
-                &final_messages,
-                &processed_tags,
-                resolved_project_identifier.as_deref(),
-            )
-            .await
-            .map(|_| ())
-            {
-                eprintln!("\nerror: {e}");
-                process::exit(1);
+                    let entry = serde_json::json!({
+                        "id": id,
+                        "content": final_messages.join("\n\n"),
+                        "tags": final_tags,
+                        "project": final_project,
+                        "recorded_at": recorded_at,
+                        "custom_fields": custom_fields,
+                    });
+                    // post-* hooks only warn on failure
+                    let _ = hooks.run(HookEvent::PostLog, serde_json::json!({ "entry": entry }));
+
+                    // Private entries stay out of issue trackers
+                    let visibility = Visibility::from_tags(final_tags.iter().map(String::as_str))
+                        .unwrap_or_else(|| settings.visibility.unmarked());
+                    if visibility != Visibility::Private {
+                        let content = final_messages.join("\n\n");
+                        let recorded_at = recorded_at
+                            .as_deref()
+                            .and_then(|d| chrono::DateTime::parse_from_rfc3339(d).ok())
+                            .map(|d| d.with_timezone(&chrono::Utc))
+                            .unwrap_or_else(|| auth_service.api_client().clock().now());
+                        settings
+                            .integrations
+                            .push_entry(&integrations::PushedEntry {
+                                content: &content,
+                                tags: &final_tags,
+                                recorded_at,
+                            })
+                            .await;
+                    }
+                }
+                Some(Err(e)) if e.is_timeout() => {
+                    eprintln!("\nerror: {e}");
+                    eprintln!(
+                        "Check `acc logs` before logging it again; it wasn't saved for `acc sync`"
+                    );
+                    process::exit(1);
+                }
+                Some(Err(e)) if !e.is_offline() => {
+                    eprintln!("\nerror: {e}");
+                    process::exit(1);
+                }
+                _ => match log::queue(
+                    &auth_service,
+                    &final_messages,
+                    &final_tags,
+                    final_project.as_deref(),
+                    recorded_at.as_deref(),
+                    Some(&custom_fields),
+                ) {
+                    Ok(path) => {
+                        eprintln!("📥 Offline: saved the entry to {}", path.display());
+                        eprintln!("   Send it with `acc sync` once you're back online");
+                    }
+                    Err(e) => {
+                        eprintln!("\nerror: Could not save the entry for later: {e}");
+                        process::exit(1);
+                    }
+                },
             }
         }
         Commands::Project { command } => {
@@ -195,19 +762,13 @@ async fn main() -> Result<(), AppError> {
                         None => println!("(no default project configured)"),
                     }
                 }
-                ProjectCommands::List | ProjectCommands::New { .. } => {
+                ProjectCommands::List
+                | ProjectCommands::Show { .. }
+                | ProjectCommands::New { .. }
+                | ProjectCommands::Update { .. }
+                | ProjectCommands::Archive { .. } => {
                     // These commands need authentication
-                    if let Err(e) = auth_service.ensure_authenticated().await {
-                        if matches!(e, AppError::Auth(_)) {
-                            eprintln!();
-                            eprintln!("You are not authenticated. Run `accomplish login` first.");
-                            process::exit(1);
-                        } else {
-                            eprintln!();
-                            eprintln!("error: {e}");
-                            process::exit(1);
-                        }
-                    }
+                    require_auth(&mut auth_service).await;
 
                     match command {
                         ProjectCommands::List => {
@@ -216,6 +777,39 @@ async fn main() -> Result<(), AppError> {
                                 process::exit(1);
                             }
                         }
+                        ProjectCommands::Update {
+                            project,
+                            name,
+                            description,
+                            identifier,
+                        } => {
+                            if let Err(e) = project::update_project(
+                                &mut auth_service,
+                                &project,
+                                name.as_deref(),
+                                description.as_deref(),
+                                identifier.as_deref(),
+                            )
+                            .await
+                            {
+                                eprintln!("\nerror: {e}");
+                                process::exit(1);
+                            }
+                        }
+                        ProjectCommands::Archive { project, force } => {
+                            if let Err(e) =
+                                project::archive_project(&mut auth_service, &project, force).await
+                            {
+                                eprintln!("\nerror: {e}");
+                                process::exit(1);
+                            }
+                        }
+                        ProjectCommands::Show { identifier } => {
+                            if let Err(e) = project::show(&mut auth_service, &identifier).await {
+                                eprintln!("\nerror: {e}");
+                                process::exit(1);
+                            }
+                        }
                         ProjectCommands::New {
                             name,
                             description,
@@ -239,38 +833,80 @@ async fn main() -> Result<(), AppError> {
             }
         }
         Commands::Logs {
-            project,
-            all,
-            tags,
-            from,
-            to,
-            limit,
-            verbose,
+            command: Some(LogsCommands::Pin { id }),
+            ..
         } => {
-            if let Err(e) = auth_service.ensure_authenticated().await {
-                if matches!(e, AppError::Auth(_)) {
-                    eprintln!();
-                    eprintln!("You are not authenticated. Run `accomplish login` first.");
-                    process::exit(1);
-                } else {
-                    eprintln!();
-                    eprintln!("error: {e}");
-                    process::exit(1);
-                }
+            require_auth(&mut auth_service).await;
+
+            if let Err(e) = pin::pin(&mut auth_service, &id).await {
+                eprintln!("\nerror: {e}");
+                process::exit(1);
             }
+        }
+        Commands::Logs {
+            command: Some(LogsCommands::Show { id }),
+            ..
+        } => {
+            require_auth(&mut auth_service).await;
 
-            let processed_tags: Option<Vec<String>> = tags.map(|t| {
-                t.iter()
-                    .flat_map(|s| s.split(','))
-                    .map(|s| s.trim().to_string())
-                    .filter(|s| !s.is_empty())
-                    .collect()
-            });
+            if let Err(e) = logs_show::execute(&mut auth_service, &id).await {
+                eprintln!("\nerror: {e}");
+                process::exit(1);
+            }
+        }
+        Commands::Logs {
+            command: Some(LogsCommands::Trash),
+            ..
+        } => {
+            if let Err(e) = logs_trash::list(auth_service.state_dir(), settings.trash_retention) {
+                eprintln!("\nerror: {e}");
+                process::exit(1);
+            }
+        }
+        Commands::Logs {
+            command: Some(LogsCommands::Restore { id }),
+            ..
+        } => {
+            require_auth(&mut auth_service).await;
 
-            // Determine effective project filter:
-            // 1. If --all is specified, show all projects (no filter)
-            // 2. If -p/--project is specified, use that project
-            // 3. Otherwise, use current project if configured
+            if let Err(e) =
+                logs_trash::restore(&mut auth_service, &id, settings.trash_retention).await
+            {
+                eprintln!("\nerror: {e}");
+                process::exit(1);
+            }
+        }
+        Commands::Logs {
+            command: Some(LogsCommands::History { id }),
+            ..
+        } => {
+            require_auth(&mut auth_service).await;
+
+            if let Err(e) = logs_history::execute(&mut auth_service, &id).await {
+                eprintln!("\nerror: {e}");
+                process::exit(1);
+            }
+        }
+        Commands::Logs {
+            command: Some(LogsCommands::Unpin { id }),
+            ..
+        } => {
+            if let Err(e) = pin::unpin(&auth_service, &id) {
+                eprintln!("\nerror: {e}");
+                process::exit(1);
+            }
+        }
+        Commands::Logs {
+            command:
+                Some(LogsCommands::Tail {
+                    count,
+                    project,
+                    all,
+                    oneline,
+                }),
+            ..
+        } => {
+            // Authenticates itself, so cached entries can be shown when offline
             let effective_project = if all {
                 None
             } else {
@@ -279,15 +915,17 @@ async fn main() -> Result<(), AppError> {
                         .or(settings.default_project.clone())
                 })
             };
+            let format = if oneline {
+                logs::EntryFormat::Oneline
+            } else {
+                logs::EntryFormat::Preview
+            };
 
-            if let Err(e) = logs::execute(
+            if let Err(e) = logs_tail::execute(
                 &mut auth_service,
                 effective_project.as_deref(),
-                processed_tags.as_deref(),
-                from.as_deref(),
-                to.as_deref(),
-                limit,
-                verbose,
+                count,
+                format,
             )
             .await
             {
@@ -295,25 +933,654 @@ async fn main() -> Result<(), AppError> {
                 process::exit(1);
             }
         }
-        Commands::Recap {
-            from,
+        Commands::Logs {
+            command:
+                Some(LogsCommands::Diff {
+                    from,
+                    to,
+                    project,
+                    all,
+                }),
+            ..
+        } => {
+            require_auth(&mut auth_service).await;
+
+            let effective_project = if all {
+                None
+            } else {
+                project.or_else(|| {
+                    config::lookup_default_project_for_dir(&env::current_dir().unwrap())
+                        .or(settings.default_project.clone())
+                })
+            };
+
+            if let Err(e) =
+                logs_diff::execute(&mut auth_service, effective_project.as_deref(), &from, &to)
+                    .await
+            {
+                eprintln!("\nerror: {e}");
+                process::exit(1);
+            }
+        }
+        Commands::Logs {
+            command: None,
+            project,
+            all,
+            tags,
+            from,
             to,
+            has_commits,
+            no_commits,
+            limit,
+            verbose,
+            oneline,
+            json,
+            id_only,
+            pinned,
+            order,
+            reverse,
+            relative_dates,
+            absolute_dates,
+            interactive,
+        } => {
+            require_auth(&mut auth_service).await;
+
+            let oldest_first = (order == SortOrder::Asc) != reverse;
+            let format = if id_only {
+                logs::EntryFormat::Ids
+            } else if json {
+                logs::EntryFormat::Json
+            } else if oneline {
+                logs::EntryFormat::Oneline
+            } else if verbose {
+                logs::EntryFormat::Verbose
+            } else {
+                logs::EntryFormat::Preview
+            };
+            if relative_dates || absolute_dates {
+                logs::set_relative_dates(relative_dates);
+            }
+
+            if pinned {
+                if let Err(e) = pin::list(&mut auth_service, limit, format, oldest_first).await {
+                    eprintln!("\nerror: {e}");
+                    process::exit(1);
+                }
+                return Ok(());
+            }
+
+            let processed_tags: Option<Vec<String>> = tags.map(|t| {
+                t.iter()
+                    .flat_map(|s| s.split(','))
+                    .map(|s| s.trim().to_string())
+                    .filter(|s| !s.is_empty())
+                    .collect()
+            });
+
+            // Determine effective project filter:
+            // 1. If --all is specified, show all projects (no filter)
+            // 2. If -p/--project is specified, use that project
+            // 3. Otherwise, use current project if configured
+            let mut effective_project = if all {
+                None
+            } else {
+                project.or_else(|| {
+                    config::lookup_default_project_for_dir(&env::current_dir().unwrap())
+                        .or(settings.default_project.clone())
+                })
+            };
+            if effective_project.is_none() && !all {
+                effective_project =
+                    prompt_for_project(&mut auth_service, "Show which project?", "All projects")
+                        .await;
+            }
+
+            let commit_filter = match (has_commits, no_commits) {
+                (true, _) => Some(true),
+                (_, true) => Some(false),
+                _ => None,
+            };
+
+            let pins = pins::Pins::load(auth_service.state_dir());
+            if interactive {
+                if let Err(e) = logs_browser::execute(
+                    &mut auth_service,
+                    effective_project.as_deref(),
+                    processed_tags.as_deref(),
+                    from.as_deref(),
+                    to.as_deref(),
+                    commit_filter,
+                    limit,
+                    &pins,
+                )
+                .await
+                {
+                    eprintln!("\nerror: {e}");
+                    process::exit(1);
+                }
+                return Ok(());
+            }
+            if let Err(e) = logs::execute(
+                &mut auth_service,
+                effective_project.as_deref(),
+                processed_tags.as_deref(),
+                from.as_deref(),
+                to.as_deref(),
+                commit_filter,
+                limit,
+                format,
+                oldest_first,
+                &pins,
+            )
+            .await
+            {
+                eprintln!("\nerror: {e}");
+                process::exit(1);
+            }
+        }
+        Commands::Audit { since, all_authors } => {
+            require_auth(&mut auth_service).await;
+
+            if let Err(e) = audit::execute(&mut auth_service, &since, all_authors).await {
+                eprintln!("\nerror: {e}");
+                process::exit(1);
+            }
+        }
+        Commands::Sync => {
+            require_connectivity(&auth_service).await;
+            require_auth(&mut auth_service).await;
+            let _lock = lock_interactive(&auth_service, "sync", lock_policy).await;
+
+            if let Err(e) = sync::execute(&mut auth_service).await {
+                eprintln!("\nerror: {e}");
+                process::exit(1);
+            }
+        }
+        Commands::Suggest {
             since,
-            tags,
-            exclude_tags,
-            project,
+            all_authors,
+            shell_history,
+            list,
         } => {
-            if let Err(e) = auth_service.ensure_authenticated().await {
-                if matches!(e, AppError::Auth(_)) {
-                    eprintln!();
-                    eprintln!("You are not authenticated. Run `accomplish login` first.");
+            // Listing only reads local activity
+            let _lock = if list {
+                None
+            } else {
+                require_connectivity(&auth_service).await;
+                require_auth(&mut auth_service).await;
+                Some(lock_interactive(&auth_service, "suggest", lock_policy).await)
+            };
+
+            let default_project =
+                config::lookup_default_project_for_dir(&env::current_dir().unwrap())
+                    .or(settings.default_project.clone());
+            let options = suggest::SuggestOptions {
+                since: &since,
+                all_authors,
+                shell_history: shell_history || settings.suggest_shell_history,
+                list_only: list,
+                default_project: default_project.as_deref(),
+            };
+            if let Err(e) = suggest::execute(&mut auth_service, &options).await {
+                eprintln!("\nerror: {e}");
+                process::exit(1);
+            }
+        }
+        Commands::Tags { command } => match command {
+            TagsCommands::Suggest {
+                messages,
+                file,
+                tags: existing,
+                threshold,
+                refresh,
+                list,
+            } => {
+                // Offline, the tag index is used as it is
+                let offline = require_auth_or_offline(&mut auth_service).await;
+                let options = tags::SuggestOptions {
+                    messages: &messages,
+                    file: file.as_deref(),
+                    tags: &existing,
+                    threshold: threshold.unwrap_or(settings.auto_tags_threshold),
+                    refresh,
+                    list_only: list,
+                };
+                if let Err(e) = tags::suggest(&mut auth_service, offline, &options).await {
+                    eprintln!("\nerror: {e}");
                     process::exit(1);
-                } else {
-                    eprintln!();
-                    eprintln!("error: {e}");
+                }
+            }
+        },
+        Commands::Digest {
+            command:
+                Some(DigestCommands::Schedule {
+                    command,
+                    weekly,
+                    daily,
+                    day,
+                    at,
+                    email,
+                    output,
+                }),
+            ..
+        } => {
+            let state_dir = auth_service.state_dir();
+            let result = match command {
+                Some(DigestScheduleCommands::List) => digest_schedule::list(state_dir),
+                Some(DigestScheduleCommands::Remove { id }) => {
+                    digest_schedule::remove(state_dir, &id)
+                }
+                None => {
+                    let options = digest_schedule::ScheduleOptions {
+                        weekly,
+                        daily,
+                        day: day.as_deref(),
+                        at: at.as_deref(),
+                        email: email.as_deref(),
+                        output: output.as_deref(),
+                    };
+                    digest_schedule::add(
+                        state_dir,
+                        &settings.profile,
+                        &options,
+                        settings.digest_file.as_deref(),
+                    )
+                }
+            };
+            if let Err(e) = result {
+                eprintln!("\nerror: {e}");
+                process::exit(1);
+            }
+        }
+        Commands::Digest {
+            command: None,
+            date,
+            week,
+            output,
+            stdout,
+            email,
+        } => {
+            require_auth(&mut auth_service).await;
+
+            let journal = output.or(settings.digest_file.clone());
+            let delivery = match (&email, &journal) {
+                (Some(address), _) => digest::Delivery::Email(address),
+                (None, Some(path)) if !stdout => digest::Delivery::Journal(path),
+                _ => digest::Delivery::Stdout,
+            };
+
+            if let Err(e) = digest::execute(
+                &mut auth_service,
+                date.as_deref(),
+                week,
+                delivery,
+                &settings.redaction,
+            )
+            .await
+            {
+                eprintln!("\nerror: {e}");
+                process::exit(1);
+            }
+        }
+        Commands::Review {
+            week_of,
+            no_recap,
+            output_dir,
+        } => {
+            require_auth(&mut auth_service).await;
+            let _lock = lock_interactive(&auth_service, "review", lock_policy).await;
+
+            let reviews_dir = output_dir.unwrap_or_else(|| settings.reviews_dir.clone());
+
+            if let Err(e) = review::execute(
+                &mut auth_service,
+                week_of.as_deref(),
+                &reviews_dir,
+                no_recap,
+                &settings.redaction,
+            )
+            .await
+            {
+                eprintln!("\nerror: {e}");
+                process::exit(1);
+            }
+        }
+        Commands::Goals { command } => {
+            let result = match command {
+                GoalsCommands::Add {
+                    name,
+                    tags,
+                    start_date,
+                    target_date,
+                    target_entries,
+                } => goals::add(
+                    auth_service.state_dir(),
+                    &name,
+                    &tags,
+                    start_date.as_deref(),
+                    target_date.as_deref(),
+                    target_entries,
+                ),
+                GoalsCommands::Remove { name } => goals::remove(auth_service.state_dir(), &name),
+                GoalsCommands::Status => {
+                    require_auth(&mut auth_service).await;
+                    goals::status(&mut auth_service).await
+                }
+            };
+
+            if let Err(e) = result {
+                eprintln!("\nerror: {e}");
+                process::exit(1);
+            }
+        }
+        Commands::Did { .. } => unreachable!("`did` is handled as `log`"),
+        Commands::Plugin(args) => {
+            let (name, args) = args.split_first().expect("clap passes the command name");
+            let Some(plugin) = plugins::find_plugin(name) else {
+                eprintln!("error: unrecognized subcommand '{name}'");
+                eprintln!();
+                eprintln!("No acc-{name} plugin was found on PATH either. See `acc --help`.");
+                process::exit(2);
+            };
+
+            // Plugins that don't need the API still run when logged out or offline
+            let token = match auth_service.ensure_authenticated().await {
+                Ok(()) => auth_service
+                    .access_token()
+                    .map(|(token, expires_at)| (token.to_string(), expires_at)),
+                Err(_) => None,
+            };
+            let context = plugins::PluginContext {
+                api_base: settings.api_base.clone(),
+                profile: settings.profile.clone(),
+                project: config::lookup_default_project_for_dir(&env::current_dir().unwrap())
+                    .or(settings.default_project.clone()),
+                token,
+            };
+            match plugins::run(&plugin, args, &context, auth_service.state_dir()) {
+                Ok(code) => process::exit(code),
+                Err(e) => {
+                    eprintln!("\nerror: {e}");
                     process::exit(1);
                 }
             }
+        }
+        Commands::Delete { ids, force } => {
+            require_auth(&mut auth_service).await;
+            let _lock = if force {
+                None
+            } else {
+                Some(lock_interactive(&auth_service, "delete", lock_policy).await)
+            };
+
+            if let Err(e) =
+                delete::execute(&mut auth_service, &ids, force, settings.trash_retention).await
+            {
+                eprintln!("\nerror: {e}");
+                process::exit(1);
+            }
+        }
+        Commands::Edit { id, messages, tags } => {
+            require_auth(&mut auth_service).await;
+            // Only the editor is interactive
+            let _lock = if messages.is_empty() && tags.is_none() {
+                Some(lock_interactive(&auth_service, "edit", lock_policy).await)
+            } else {
+                None
+            };
+
+            let tags: Option<Vec<String>> = tags.map(|t| {
+                t.iter()
+                    .map(|s| s.trim().to_string())
+                    .filter(|s| !s.is_empty())
+                    .collect()
+            });
+            if let Err(e) = edit::execute(&mut auth_service, &id, &messages, tags.as_deref()).await
+            {
+                eprintln!("\nerror: {e}");
+                process::exit(1);
+            }
+        }
+        Commands::Today {
+            project,
+            verbose,
+            oneline,
+        } => {
+            require_auth(&mut auth_service).await;
+
+            let format = if oneline {
+                logs::EntryFormat::Oneline
+            } else if verbose {
+                logs::EntryFormat::Verbose
+            } else {
+                logs::EntryFormat::Preview
+            };
+            if let Err(e) = today::execute(&mut auth_service, project.as_deref(), format).await {
+                eprintln!("\nerror: {e}");
+                process::exit(1);
+            }
+        }
+        Commands::Config {
+            command: ConfigCommands::TestRoute { branch },
+        } => {
+            if let Err(e) = config_route::test_route(&settings.branch_routes, branch.as_deref()) {
+                eprintln!("\nerror: {e}");
+                process::exit(1);
+            }
+        }
+        Commands::Standup { project, recap } => {
+            require_auth(&mut auth_service).await;
+
+            if let Err(e) = standup::execute(
+                &mut auth_service,
+                project.as_deref(),
+                recap,
+                &settings.redaction,
+            )
+            .await
+            {
+                eprintln!("\nerror: {e}");
+                process::exit(1);
+            }
+        }
+        Commands::Completions { .. } | Commands::Profile { .. } | Commands::Prompt { .. } => {
+            unreachable!("answered before dispatch")
+        }
+        Commands::Cache { command } => {
+            let result = match command {
+                CacheCommands::Status => cache::status(auth_service.state_dir()),
+                CacheCommands::Clear { kind } => cache::clear(auth_service.state_dir(), kind),
+            };
+            if let Err(e) = result {
+                eprintln!("\nerror: {e}");
+                process::exit(1);
+            }
+        }
+        Commands::Todo { command } => {
+            let result = match command {
+                TodoCommands::Add {
+                    text,
+                    project,
+                    tags,
+                } => {
+                    let project = project
+                        .or_else(|| {
+                            config::lookup_default_project_for_dir(&env::current_dir().unwrap())
+                        })
+                        .or(settings.default_project.clone());
+                    todo::add(auth_service.state_dir(), &text, project.as_deref(), &tags)
+                }
+                TodoCommands::List => todo::list(auth_service.state_dir()),
+                TodoCommands::Done { id } => {
+                    require_auth(&mut auth_service).await;
+                    todo::done(&mut auth_service, id, &settings.visibility).await
+                }
+                TodoCommands::Remove { id } => todo::remove(auth_service.state_dir(), id),
+            };
+
+            if let Err(e) = result {
+                eprintln!("\nerror: {e}");
+                process::exit(1);
+            }
+        }
+        Commands::Export {
+            command:
+                ExportCommands::ReviewPacket {
+                    from,
+                    to,
+                    project,
+                    audience,
+                    no_recaps,
+                    output,
+                },
+        } => {
+            require_auth(&mut auth_service).await;
+
+            let output = output.unwrap_or_else(|| {
+                export::default_packet_path(&settings.reviews_dir, &from, to.as_deref())
+            });
+            let audience = audience.unwrap_or(RecapAudience::Manager);
+
+            if let Err(e) = export::review_packet(
+                &mut auth_service,
+                &from,
+                to.as_deref(),
+                project.as_deref(),
+                Some(audience.as_str()),
+                no_recaps,
+                &output,
+                &settings.redaction,
+                &settings.visibility,
+            )
+            .await
+            {
+                eprintln!("\nerror: {e}");
+                process::exit(1);
+            }
+        }
+        Commands::Hooks {
+            command:
+                HooksCommands::Install {
+                    path,
+                    force,
+                    prepare_commit_msg,
+                    project,
+                },
+        } => {
+            let hook = if prepare_commit_msg {
+                git_hooks::GitHook::PrepareCommitMsg {
+                    project: project.as_deref(),
+                }
+            } else {
+                git_hooks::GitHook::PostCommit {
+                    profile: &settings.profile,
+                }
+            };
+            if let Err(e) = git_hooks::install(path.as_deref(), hook, force) {
+                eprintln!("\nerror: {e}");
+                process::exit(1);
+            }
+        }
+        Commands::Daemon { command } => {
+            let result = match command {
+                DaemonCommands::Start => {
+                    require_auth_or_offline(&mut auth_service).await;
+                    daemon::start(auth_service.state_dir(), &settings.profile)
+                }
+                DaemonCommands::Run { once } => {
+                    require_auth_or_offline(&mut auth_service).await;
+                    daemon::run(
+                        &mut auth_service,
+                        &settings.daemon,
+                        &settings.notifications,
+                        &settings.commit_message,
+                        &hooks,
+                        once,
+                    )
+                    .await
+                }
+                DaemonCommands::Stop => daemon::stop(auth_service.state_dir()),
+                DaemonCommands::Status => daemon::status(auth_service.state_dir()),
+            };
+            if let Err(e) = result {
+                eprintln!("\nerror: {e}");
+                process::exit(1);
+            }
+        }
+        Commands::Repo { command } => {
+            require_auth(&mut auth_service).await;
+
+            let result = match command {
+                RepoCommands::List { project } => {
+                    repo::list(&mut auth_service, project.as_deref()).await
+                }
+                RepoCommands::Link { repo } => repo::link(&mut auth_service, repo.as_deref()).await,
+                RepoCommands::Update {
+                    repo,
+                    name,
+                    remote_url,
+                    default_branch,
+                    project,
+                } => {
+                    repo::update(
+                        &mut auth_service,
+                        &repo,
+                        name.as_deref(),
+                        remote_url.as_deref(),
+                        default_branch.as_deref(),
+                        project.as_deref(),
+                    )
+                    .await
+                }
+                RepoCommands::Rm { repo, force } => {
+                    repo::remove(&mut auth_service, &repo, force).await
+                }
+                RepoCommands::Sync { dry_run } => repo::sync(&mut auth_service, dry_run).await,
+            };
+            if let Err(e) = result {
+                eprintln!("\nerror: {e}");
+                process::exit(1);
+            }
+        }
+        Commands::Recap {
+            command:
+                Some(RecapCommands::Wait {
+                    id,
+                    notify,
+                    format,
+                    output,
+                }),
+            ..
+        } => {
+            require_auth(&mut auth_service).await;
+
+            let destinations = recap::RecapDestinations {
+                file: output,
+                hooks: Some(hooks.clone()),
+            };
+            if let Err(e) = recap::wait(&mut auth_service, &id, notify, format, destinations).await
+            {
+                eprintln!("\nerror: {e}");
+                process::exit(1);
+            }
+        }
+        Commands::Recap {
+            command: None,
+            from,
+            to,
+            since,
+            tags,
+            exclude_tags,
+            project,
+            no_project,
+            audience,
+            team,
+            background,
+            notify,
+            format,
+            output,
+        } => {
+            require_auth(&mut auth_service).await;
 
             let processed_tags: Option<Vec<String>> = tags.map(|t| {
                 t.iter()
@@ -330,22 +1597,130 @@ async fn main() -> Result<(), AppError> {
                     .filter(|s| !s.is_empty())
                     .collect()
             });
-
-            let resolved_project = project
+            let mut resolved_project = project
                 .or_else(|| config::lookup_default_project_for_dir(&env::current_dir().unwrap()))
                 .or(settings.default_project.clone());
+            if resolved_project.is_none() && !no_project {
+                resolved_project =
+                    prompt_for_project(&mut auth_service, "Recap which project?", "All projects")
+                        .await;
+            }
 
-            if let Err(e) = recap::execute(
-                &mut auth_service,
-                from.as_deref(),
-                to.as_deref(),
-                since.as_deref(),
-                processed_tags.as_deref(),
-                processed_exclude_tags.as_deref(),
-                resolved_project.as_deref(),
-            )
-            .await
-            {
+            // The project's own defaults, from .accomplish.toml then the config,
+            // fill in what wasn't given as flags
+            let project_defaults = match &resolved_project {
+                Some(project) => {
+                    let local =
+                        match config::lookup_recap_defaults_for_dir(&env::current_dir().unwrap()) {
+                            Ok(local) => local
+                                .filter(|(dir_project, _)| {
+                                    dir_project.eq_ignore_ascii_case(project)
+                                })
+                                .map(|(_, defaults)| defaults)
+                                .unwrap_or_default(),
+                            Err(e) => {
+                                eprintln!("\nerror: {e}");
+                                process::exit(1);
+                            }
+                        };
+                    local.or(settings.project_recaps.for_project(project))
+                }
+                None => RecapDefaults::default(),
+            };
+            let format = format.or(project_defaults.format).unwrap_or_default();
+            let processed_exclude_tags = processed_exclude_tags.or(project_defaults.exclude_tags);
+
+            let resolved_audience = audience.or(project_defaults.audience).or_else(|| {
+                let configured = settings.recap_audience.as_deref()?;
+                match RecapAudience::from_str(configured, true) {
+                    Ok(a) => Some(a),
+                    Err(_) => {
+                        eprintln!(
+                            "⚠️  Warning: Ignoring unknown recap_audience '{configured}' in config"
+                        );
+                        None
+                    }
+                }
+            });
+
+            // Recaps are generated server-side, so redacted tags and entries the
+            // audience may not see are always excluded
+            let mut processed_exclude_tags = processed_exclude_tags.unwrap_or_default();
+            if let Some(audience) = resolved_audience {
+                let minimum = Visibility::minimum_for_audience(audience.as_str());
+                processed_exclude_tags.extend(settings.visibility.exclude_tags(minimum));
+            }
+            let processed_exclude_tags = settings
+                .redaction
+                .merge_exclude_tags(Some(processed_exclude_tags));
+
+            let hook_data = serde_json::json!({
+                "from": from,
+                "to": to,
+                "since": since,
+                "tags": processed_tags,
+                "exclude_tags": processed_exclude_tags,
+                "project": resolved_project,
+                "audience": resolved_audience.map(|a| a.as_str()),
+                "team": team,
+            });
+            if let Err(e) = hooks.run(HookEvent::PreRecap, hook_data) {
+                eprintln!("\nerror: {e}");
+                process::exit(1);
+            }
+
+            let notify = notify
+                || settings
+                    .notifications
+                    .enabled(utils::notify::NotificationKind::RecapReady);
+            let destinations = recap::RecapDestinations {
+                file: output,
+                hooks: Some(hooks.clone()),
+            };
+            let result = if background {
+                recap::execute_background(
+                    &mut auth_service,
+                    &settings.profile,
+                    from.as_deref(),
+                    to.as_deref(),
+                    since.as_deref(),
+                    processed_tags.as_deref(),
+                    processed_exclude_tags.as_deref(),
+                    resolved_project.as_deref(),
+                    resolved_audience.map(|a| a.as_str()),
+                    team,
+                    notify,
+                    format,
+                    destinations,
+                )
+                .await
+            } else {
+                let result = recap::execute(
+                    &mut auth_service,
+                    from.as_deref(),
+                    to.as_deref(),
+                    since.as_deref(),
+                    processed_tags.as_deref(),
+                    processed_exclude_tags.as_deref(),
+                    resolved_project.as_deref(),
+                    resolved_audience.map(|a| a.as_str()),
+                    team,
+                    format,
+                    destinations,
+                )
+                .await;
+                if notify {
+                    match &result {
+                        Ok(_) => {
+                            utils::notify::desktop("Accomplish recap ready", "Your recap is ready.")
+                        }
+                        Err(e) => utils::notify::desktop("Accomplish recap failed", &e.to_string()),
+                    }
+                }
+                result.map(|_| ())
+            };
+
+            if let Err(e) = result {
                 eprintln!("\nerror: {e}");
                 process::exit(1);
             }
@@ -354,3 +1729,78 @@ async fn main() -> Result<(), AppError> {
 
     Ok(())
 }
+
+/// Takes the per-profile lock for commands that prompt or open an editor, so
+/// they don't interleave on the terminal. Exits if it's held and not waited for.
+async fn lock_interactive(
+    auth_service: &AuthService,
+    command: &str,
+    policy: LockPolicy,
+) -> InstanceLock {
+    match InstanceLock::acquire(auth_service.state_dir(), command, policy).await {
+        Ok(lock) => lock,
+        Err(e) => {
+            eprintln!("\nerror: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Exits with guidance when the API can't be reached, before an interactive
+/// flow gets as far as prompting.
+async fn require_connectivity(auth_service: &AuthService) {
+    if let Err(e) = auth_service.api_client().check_reachable().await {
+        eprintln!();
+        eprintln!(
+            "error: Can't reach Accomplish at {} ({e})",
+            auth_service.api_client().base_url()
+        );
+        eprintln!(
+            "hint: Check your network connection, VPN or proxy and try again. Nothing was sent."
+        );
+        process::exit(1);
+    }
+}
+
+/// Asks for a project when none was given or configured (see
+/// `project::prompt_for_project`), exiting if the projects can't be listed.
+async fn prompt_for_project(
+    auth_service: &mut AuthService,
+    message: &str,
+    none_label: &str,
+) -> Option<String> {
+    match project::prompt_for_project(auth_service, message, none_label).await {
+        Ok(project) => project,
+        Err(e) => {
+            eprintln!("\nerror: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Exits with a friendly message unless the stored token is valid.
+async fn require_auth(auth_service: &mut AuthService) {
+    if let Err(e) = auth_service.ensure_authenticated().await {
+        exit_unauthenticated(e);
+    }
+}
+
+/// Like `require_auth`, but returns `true` instead of exiting when the token
+/// couldn't be checked because the API is unreachable.
+async fn require_auth_or_offline(auth_service: &mut AuthService) -> bool {
+    match auth_service.ensure_authenticated().await {
+        Ok(()) => false,
+        Err(e) if e.is_offline() => true,
+        Err(e) => exit_unauthenticated(e),
+    }
+}
+
+fn exit_unauthenticated(e: AppError) -> ! {
+    eprintln!();
+    if matches!(e, AppError::Auth(_)) {
+        eprintln!("You are not authenticated. Run `accomplish login` first.");
+    } else {
+        eprintln!("error: {e}");
+    }
+    process::exit(1);
+}
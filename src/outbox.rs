@@ -0,0 +1,155 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const OUTBOX_DIR: &str = "outbox";
+
+/// An entry written while the API couldn't be reached, waiting for `acc sync`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QueuedEntry {
+    pub queued_at: DateTime<Utc>,
+    pub content: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Project identifier, resolved when the entry is synced
+    #[serde(default)]
+    pub project: Option<String>,
+    /// RFC 3339, fixed when the entry was written rather than when it's synced
+    pub recorded_at: String,
+    #[serde(default)]
+    pub custom_fields: Option<Map<String, Value>>,
+}
+
+/// A queued entry and the file it's kept in.
+#[derive(Debug)]
+pub struct PendingEntry {
+    pub path: PathBuf,
+    pub entry: QueuedEntry,
+}
+
+/// Entries waiting to be sent, one JSON file each in `<state_dir>/outbox/`,
+/// so a crash or a failed sync never loses more than the entry at hand.
+#[derive(Debug)]
+pub struct Outbox {
+    dir: PathBuf,
+}
+
+impl Outbox {
+    pub fn new(state_dir: &Path) -> Self {
+        Self {
+            dir: state_dir.join(OUTBOX_DIR),
+        }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Writes the entry to its own file, returning the path.
+    pub fn push(&self, entry: &QueuedEntry) -> io::Result<PathBuf> {
+        fs::create_dir_all(&self.dir)?;
+        let stem = entry.queued_at.format("%Y%m%dT%H%M%S%.3fZ").to_string();
+        let mut n = 1;
+        let mut path = self.dir.join(format!("{stem}-{n:03}.json"));
+        while path.exists() {
+            n += 1;
+            path = self.dir.join(format!("{stem}-{n:03}.json"));
+        }
+
+        // Written aside first so a half-written file is never picked up by sync
+        let partial = path.with_extension("json.partial");
+        fs::write(&partial, serde_json::to_string_pretty(entry)?)?;
+        fs::rename(&partial, &path)?;
+        Ok(path)
+    }
+
+    /// Queued entries, oldest first. Files that can't be read are left alone
+    /// and reported in the second list.
+    pub fn pending(&self) -> io::Result<(Vec<PendingEntry>, Vec<PathBuf>)> {
+        let read_dir = match fs::read_dir(&self.dir) {
+            Ok(read_dir) => read_dir,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok((Vec::new(), Vec::new())),
+            Err(e) => return Err(e),
+        };
+
+        let mut paths: Vec<PathBuf> = read_dir
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .collect();
+        paths.sort();
+
+        let mut pending = Vec::new();
+        let mut unreadable = Vec::new();
+        for path in paths {
+            match fs::read_to_string(&path)
+                .ok()
+                .and_then(|content| serde_json::from_str::<QueuedEntry>(&content).ok())
+            {
+                Some(entry) => pending.push(PendingEntry { path, entry }),
+                None => unreadable.push(path),
+            }
+        }
+        Ok((pending, unreadable))
+    }
+
+    /// How many entries are waiting, without reading them.
+    pub fn count(&self) -> usize {
+        fs::read_dir(&self.dir)
+            .map(|read_dir| {
+                read_dir
+                    .filter_map(Result::ok)
+                    .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "json"))
+                    .count()
+            })
+            .unwrap_or(0)
+    }
+
+    pub fn remove(&self, pending: &PendingEntry) -> io::Result<()> {
+        fs::remove_file(&pending.path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn entry(content: &str) -> QueuedEntry {
+        QueuedEntry {
+            queued_at: "2025-07-09T08:15:00Z".parse().unwrap(),
+            content: content.to_string(),
+            tags: vec!["train".to_string()],
+            project: Some("web".to_string()),
+            recorded_at: "2025-07-09T08:15:00+00:00".to_string(),
+            custom_fields: None,
+        }
+    }
+
+    #[test]
+    fn test_push_pending_and_remove() {
+        let temp_dir = TempDir::new().unwrap();
+        let outbox = Outbox::new(temp_dir.path());
+        assert_eq!(outbox.count(), 0);
+        assert!(outbox.pending().unwrap().0.is_empty());
+
+        // Same timestamp twice still gets two files, kept in order
+        outbox.push(&entry("first")).unwrap();
+        outbox.push(&entry("second")).unwrap();
+        fs::write(outbox.dir().join("broken.json"), "not json").unwrap();
+
+        let (pending, unreadable) = outbox.pending().unwrap();
+        let contents: Vec<&str> = pending.iter().map(|p| p.entry.content.as_str()).collect();
+        assert_eq!(contents, ["first", "second"]);
+        assert_eq!(pending[0].entry, entry("first"));
+        assert_eq!(unreadable.len(), 1);
+        assert_eq!(outbox.count(), 3);
+
+        outbox.remove(&pending[0]).unwrap();
+        let (pending, _) = outbox.pending().unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].entry.content, "second");
+    }
+}
@@ -0,0 +1,131 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const PINS_FILE: &str = "pins.json";
+
+/// Entries pinned with `acc logs pin`, kept locally per profile as entry ID
+/// mapped to when it was pinned.
+#[derive(Debug, Default)]
+pub struct Pins {
+    path: PathBuf,
+    entries: BTreeMap<String, DateTime<Utc>>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct PinsFile {
+    #[serde(default)]
+    entries: BTreeMap<String, DateTime<Utc>>,
+}
+
+impl Pins {
+    /// Loads pins from `<state_dir>/pins.json`, starting empty if the file is
+    /// missing or unreadable.
+    pub fn load(state_dir: &Path) -> Self {
+        let path = state_dir.join(PINS_FILE);
+        let entries = fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<PinsFile>(&content).ok())
+            .map(|file| file.entries)
+            .unwrap_or_default();
+
+        Self { path, entries }
+    }
+
+    pub fn contains(&self, entry_id: &str) -> bool {
+        self.entries.contains_key(entry_id)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Pinned entry IDs, most recently pinned first.
+    pub fn ids(&self) -> Vec<String> {
+        let mut pins: Vec<(&String, &DateTime<Utc>)> = self.entries.iter().collect();
+        pins.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+        pins.into_iter().map(|(id, _)| id.clone()).collect()
+    }
+
+    /// Returns false when the entry was already pinned.
+    pub fn pin(&mut self, entry_id: &str) -> bool {
+        if self.contains(entry_id) {
+            return false;
+        }
+        self.entries.insert(entry_id.to_string(), Utc::now());
+        true
+    }
+
+    /// Unpins the entry whose ID is `id_or_prefix` or uniquely starts with it,
+    /// returning its full ID.
+    pub fn unpin(&mut self, id_or_prefix: &str) -> Result<String, String> {
+        let matches: Vec<&String> = self
+            .entries
+            .keys()
+            .filter(|id| id.starts_with(id_or_prefix))
+            .collect();
+
+        let id = match matches.as_slice() {
+            [] => return Err(format!("No pinned entry matches '{id_or_prefix}'")),
+            [id] => (*id).clone(),
+            _ if self.contains(id_or_prefix) => id_or_prefix.to_string(),
+            _ => {
+                return Err(format!(
+                    "'{id_or_prefix}' matches several pinned entries. Use a longer ID"
+                ))
+            }
+        };
+        self.entries.remove(&id);
+        Ok(id)
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let file = PinsFile {
+            entries: self.entries.clone(),
+        };
+        let content = serde_json::to_string_pretty(&file)?;
+        fs::write(&self.path, content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_pin_persists_across_loads() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut pins = Pins::load(temp_dir.path());
+        assert!(pins.is_empty());
+
+        assert!(pins.pin("entry-1"));
+        assert!(!pins.pin("entry-1"));
+        pins.save().unwrap();
+
+        let reloaded = Pins::load(temp_dir.path());
+        assert!(reloaded.contains("entry-1"));
+        assert!(!reloaded.contains("entry-2"));
+    }
+
+    #[test]
+    fn test_unpin_by_prefix() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut pins = Pins::load(temp_dir.path());
+        pins.pin("abc12345-1");
+        pins.pin("abc12345-2");
+        pins.pin("def67890");
+
+        assert_eq!(pins.unpin("def"), Ok("def67890".to_string()));
+        assert!(pins.unpin("abc").is_err());
+        assert!(pins.unpin("zzz").is_err());
+        assert_eq!(pins.unpin("abc12345-2"), Ok("abc12345-2".to_string()));
+        assert_eq!(pins.ids(), vec!["abc12345-1".to_string()]);
+    }
+}
@@ -0,0 +1,194 @@
+use crate::errors::AppError;
+use crate::hooks::is_executable;
+use chrono::{DateTime, Utc};
+use serde_json::json;
+use std::env;
+use std::ffi::OsStr;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Plugins are executables named `acc-<name>`, run for `acc <name>`.
+const PLUGIN_PREFIX: &str = "acc-";
+
+/// Where session files handed to plugins are written, under the state dir.
+const SESSIONS_DIR: &str = "plugin-sessions";
+
+/// What a plugin is told about the CLI it was started from.
+#[derive(Debug, Default)]
+pub struct PluginContext {
+    pub api_base: String,
+    pub profile: String,
+    pub project: Option<String>,
+    /// Access token and when it expires, if logged in
+    pub token: Option<(String, Option<DateTime<Utc>>)>,
+}
+
+/// The `acc-<name>` executable on `PATH`, the way git and cargo find their
+/// external subcommands.
+pub fn find_plugin(name: &str) -> Option<PathBuf> {
+    find_plugin_in(name, &env::var_os("PATH")?)
+}
+
+fn find_plugin_in(name: &str, path_var: &OsStr) -> Option<PathBuf> {
+    // A name with a path separator would run something outside PATH
+    if name.is_empty() || name.contains(['/', '\\']) {
+        return None;
+    }
+    let file_name = format!("{PLUGIN_PREFIX}{name}{}", env::consts::EXE_SUFFIX);
+    env::split_paths(path_var)
+        .map(|dir| dir.join(&file_name))
+        .find(|candidate| is_executable(candidate))
+}
+
+/// Runs a plugin with `args` and returns its exit code.
+///
+/// Plugins get `ACC_API_BASE`, `ACC_PROFILE`, `ACC_PROJECT` (when one applies)
+/// and `ACC_BIN`, the path of this `acc`. When logged in, `ACC_SESSION_FILE`
+/// names a file readable only by the user holding the access token, its
+/// expiry and the API base as JSON. The file is removed as soon as the plugin
+/// exits, so the token is never put in the environment or left on disk.
+pub fn run(
+    plugin: &Path,
+    args: &[String],
+    context: &PluginContext,
+    state_dir: &Path,
+) -> Result<i32, AppError> {
+    let mut command = Command::new(plugin);
+    command
+        .args(args)
+        .env("ACC_API_BASE", &context.api_base)
+        .env("ACC_PROFILE", &context.profile)
+        .env_remove("ACC_PROJECT")
+        .env_remove("ACC_SESSION_FILE");
+    if let Some(project) = &context.project {
+        command.env("ACC_PROJECT", project);
+    }
+    if let Ok(bin) = env::current_exe() {
+        command.env("ACC_BIN", bin);
+    }
+
+    let session = match &context.token {
+        Some((token, expires_at)) => {
+            let session = SessionFile::create(state_dir, &context.api_base, token, *expires_at)?;
+            command.env("ACC_SESSION_FILE", &session.path);
+            Some(session)
+        }
+        None => None,
+    };
+
+    let status = command
+        .status()
+        .map_err(|e| AppError::Other(format!("Could not run plugin {}: {e}", plugin.display())))?;
+    drop(session);
+
+    // Killed by a signal: report it the way shells do
+    Ok(status.code().unwrap_or(128))
+}
+
+/// The session handed to a plugin, deleted when dropped.
+struct SessionFile {
+    path: PathBuf,
+}
+
+impl SessionFile {
+    fn create(
+        state_dir: &Path,
+        api_base: &str,
+        token: &str,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<Self, AppError> {
+        let dir = state_dir.join(SESSIONS_DIR);
+        fs::create_dir_all(&dir)?;
+        let nanos = Utc::now().timestamp_nanos_opt().unwrap_or_default();
+        let path = dir.join(format!("{}-{nanos}.json", std::process::id()));
+
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let mut file = options.open(&path)?;
+        let session = SessionFile { path };
+
+        let content = json!({
+            "api_base": api_base,
+            "access_token": token,
+            "expires_at": expires_at.map(|at| at.to_rfc3339()),
+        });
+        file.write_all(content.to_string().as_bytes())?;
+        Ok(session)
+    }
+}
+
+impl Drop for SessionFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+    use std::os::unix::fs::PermissionsExt;
+    use tempfile::TempDir;
+
+    fn write_plugin(dir: &Path, name: &str, body: &str) -> PathBuf {
+        let path = dir.join(format!("acc-{name}"));
+        fs::write(&path, format!("#!/bin/sh\n{body}\n")).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_find_plugin_on_path() {
+        let first = TempDir::new().unwrap();
+        let second = TempDir::new().unwrap();
+        let plugin = write_plugin(second.path(), "jira", "exit 0");
+        // Not executable, so not a plugin
+        fs::write(first.path().join("acc-jira"), "").unwrap();
+
+        let path_var = env::join_paths([first.path(), second.path()]).unwrap();
+        assert_eq!(find_plugin_in("jira", &path_var), Some(plugin));
+        assert_eq!(find_plugin_in("missing", &path_var), None);
+        assert_eq!(find_plugin_in("../jira", &path_var), None);
+    }
+
+    #[test]
+    fn test_run_passes_context_and_removes_session() {
+        let temp_dir = TempDir::new().unwrap();
+        let out = temp_dir.path().join("out.txt");
+        let plugin = write_plugin(
+            temp_dir.path(),
+            "env",
+            &format!(
+                "echo \"$1 $ACC_PROFILE $ACC_PROJECT $ACC_API_BASE $ACC_SESSION_FILE\" > {out}\ncat \"$ACC_SESSION_FILE\" >> {out}\nexit 3",
+                out = out.display()
+            ),
+        );
+        let context = PluginContext {
+            api_base: "https://accomplish.dev".to_string(),
+            profile: "work".to_string(),
+            project: Some("web".to_string()),
+            token: Some(("secret".to_string(), None)),
+        };
+
+        let code = run(&plugin, &["sync".to_string()], &context, temp_dir.path()).unwrap();
+        assert_eq!(code, 3);
+
+        let written = fs::read_to_string(&out).unwrap();
+        let (line, session) = written.split_once('\n').unwrap();
+        let parts: Vec<&str> = line.split(' ').collect();
+        assert_eq!(
+            parts[..4],
+            ["sync", "work", "web", "https://accomplish.dev"]
+        );
+        let session: Value = serde_json::from_str(session).unwrap();
+        assert_eq!(session["access_token"], "secret");
+        assert!(!Path::new(parts[4]).exists());
+    }
+}
@@ -0,0 +1,216 @@
+use crate::api::errors::ApiError;
+use crate::errors::AppError;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::io::{self, BufRead, Write};
+
+/// Version of the porcelain protocol, announced in the `hello` line. Bumped
+/// only for changes that break existing clients; new methods and new fields
+/// in results don't count.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// Input ended and every request was answered, successfully or not.
+pub const EXIT_OK: i32 = 0;
+/// Output could not be written, or input could not be read.
+pub const EXIT_IO: i32 = 1;
+/// Not logged in. A `fatal` line says so before exiting.
+pub const EXIT_UNAUTHENTICATED: i32 = 3;
+
+/// One line of input: `{"id": 1, "method": "projects", "params": {}}`.
+#[derive(Debug, Deserialize)]
+pub struct Request {
+    pub method: String,
+    #[serde(default)]
+    pub params: Map<String, Value>,
+}
+
+/// Machine-readable reason a request failed, stable across releases.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    /// The line isn't a JSON object with a `method`
+    InvalidRequest,
+    UnknownMethod,
+    /// A parameter is missing or has the wrong type or value
+    InvalidParams,
+    Unauthenticated,
+    /// The API couldn't be reached
+    Offline,
+    /// The API didn't answer in time; the request may have been handled
+    Timeout,
+    UnknownProject,
+    /// `require_project` is set and the entry has none
+    ProjectRequired,
+    /// The API answered with an error
+    Api,
+}
+
+#[derive(Debug)]
+pub struct RequestError {
+    pub code: ErrorCode,
+    pub message: String,
+}
+
+impl RequestError {
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+impl From<AppError> for RequestError {
+    fn from(e: AppError) -> Self {
+        let code = match &e {
+            AppError::Auth(_) | AppError::Api(ApiError::Unauthorized(_)) => {
+                ErrorCode::Unauthenticated
+            }
+            e if e.is_offline() => ErrorCode::Offline,
+            e if e.is_timeout() => ErrorCode::Timeout,
+            _ => ErrorCode::Api,
+        };
+        Self::new(code, e.to_string())
+    }
+}
+
+/// Newline-delimited JSON over a reader and a writer, for editor plugins and
+/// other programs driving `acc`. Every line written is a single JSON object
+/// with a `type`: one `hello` first, then a `response` per request, in order,
+/// or a `fatal` line just before exiting early. Nothing else is written to the
+/// writer, and each line is flushed as soon as it's complete.
+pub struct Porcelain<R, W> {
+    reader: R,
+    writer: W,
+}
+
+impl<R: BufRead, W: Write> Porcelain<R, W> {
+    pub fn new(reader: R, writer: W) -> Self {
+        Self { reader, writer }
+    }
+
+    /// Announces the protocol version along with `fields`.
+    pub fn hello(&mut self, fields: Value) -> io::Result<()> {
+        let mut line = json!({
+            "type": "hello",
+            "protocol": PROTOCOL_VERSION,
+        });
+        if let (Some(line), Value::Object(fields)) = (line.as_object_mut(), fields) {
+            line.extend(fields);
+        }
+        self.write_line(&line)
+    }
+
+    /// The next request and its `id` (null when missing), or None at the end
+    /// of input. Blank lines are skipped.
+    #[allow(clippy::type_complexity)]
+    pub fn next_request(&mut self) -> io::Result<Option<(Value, Result<Request, RequestError>)>> {
+        let mut line = String::new();
+        loop {
+            line.clear();
+            if self.reader.read_line(&mut line)? == 0 {
+                return Ok(None);
+            }
+            if !line.trim().is_empty() {
+                break;
+            }
+        }
+
+        let value: Value = match serde_json::from_str(&line) {
+            Ok(value) => value,
+            Err(e) => {
+                let error = RequestError::new(ErrorCode::InvalidRequest, format!("Not JSON: {e}"));
+                return Ok(Some((Value::Null, Err(error))));
+            }
+        };
+        let id = value.get("id").cloned().unwrap_or(Value::Null);
+        let request = serde_json::from_value(value)
+            .map_err(|e| RequestError::new(ErrorCode::InvalidRequest, e.to_string()));
+        Ok(Some((id, request)))
+    }
+
+    pub fn respond(&mut self, id: Value, result: Result<Value, RequestError>) -> io::Result<()> {
+        let line = match result {
+            Ok(result) => json!({ "type": "response", "id": id, "ok": true, "result": result }),
+            Err(error) => json!({
+                "type": "response",
+                "id": id,
+                "ok": false,
+                "error": { "code": error.code, "message": error.message },
+            }),
+        };
+        self.write_line(&line)
+    }
+
+    /// Reports why the session is ending before input ran out.
+    pub fn fatal(&mut self, error: RequestError) -> io::Result<()> {
+        self.write_line(&json!({
+            "type": "fatal",
+            "error": { "code": error.code, "message": error.message },
+        }))
+    }
+
+    fn write_line(&mut self, line: &Value) -> io::Result<()> {
+        serde_json::to_writer(&mut self.writer, line)?;
+        self.writer.write_all(b"\n")?;
+        self.writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_requests_and_responses_are_one_json_object_per_line() {
+        let input = "{\"id\":1,\"method\":\"tags\"}\n\nnot json\n{\"id\":\"b\"}\n";
+        let mut output = Vec::new();
+        let mut porcelain = Porcelain::new(input.as_bytes(), &mut output);
+        porcelain.hello(json!({ "version": "1.0.0" })).unwrap();
+
+        let (id, request) = porcelain.next_request().unwrap().unwrap();
+        assert_eq!(id, json!(1));
+        assert_eq!(request.unwrap().method, "tags");
+        porcelain.respond(id, Ok(json!(["rust"]))).unwrap();
+
+        let (id, request) = porcelain.next_request().unwrap().unwrap();
+        assert_eq!(id, Value::Null);
+        assert_eq!(request.unwrap_err().code, ErrorCode::InvalidRequest);
+
+        // Valid JSON without a method keeps its id for the error response
+        let (id, request) = porcelain.next_request().unwrap().unwrap();
+        assert_eq!(id, json!("b"));
+        porcelain.respond(id, Err(request.unwrap_err())).unwrap();
+        assert!(porcelain.next_request().unwrap().is_none());
+
+        let lines: Vec<Value> = String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(
+            lines[0],
+            json!({ "type": "hello", "protocol": 1, "version": "1.0.0" })
+        );
+        assert_eq!(
+            lines[1],
+            json!({ "type": "response", "id": 1, "ok": true, "result": ["rust"] })
+        );
+        assert_eq!(lines[2]["ok"], false);
+        assert_eq!(lines[2]["id"], "b");
+        assert_eq!(lines[2]["error"]["code"], "invalid_request");
+    }
+
+    #[test]
+    fn test_timeouts_are_not_reported_as_offline() {
+        let code = |e: ApiError| RequestError::from(AppError::Api(e)).code;
+        assert_eq!(
+            code(ApiError::Unreachable("connection refused".to_string())),
+            ErrorCode::Offline
+        );
+        assert_eq!(
+            code(ApiError::TimedOut("operation timed out".to_string())),
+            ErrorCode::Timeout
+        );
+    }
+}
@@ -0,0 +1,470 @@
+use crate::errors::AppError;
+use chrono::{DateTime, NaiveTime, Timelike, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+const SCHEDULES_FILE: &str = "schedules.json";
+
+/// Ends every crontab line acc manages, followed by the job's name, so a job
+/// can be found again without touching the user's own lines.
+const CRON_MARKER: &str = "# acc:";
+
+/// launchd labels are reverse-DNS names; the job's name follows this prefix.
+const LAUNCHD_PREFIX: &str = "dev.accomplish.";
+
+/// How often a scheduled job runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Frequency {
+    Daily,
+    Weekly(Weekday),
+}
+
+impl fmt::Display for Frequency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const DAYS: [&str; 7] = [
+            "Monday",
+            "Tuesday",
+            "Wednesday",
+            "Thursday",
+            "Friday",
+            "Saturday",
+            "Sunday",
+        ];
+        match self {
+            Frequency::Daily => write!(f, "every day"),
+            Frequency::Weekly(day) => {
+                write!(f, "every {}", DAYS[day.num_days_from_monday() as usize])
+            }
+        }
+    }
+}
+
+/// A command run by the system's scheduler: launchd on macOS, Task Scheduler
+/// on Windows, cron elsewhere. Jobs installed for a profile are kept in
+/// `<state_dir>/schedules.json`, which is what `list` reads.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScheduledJob {
+    /// Identifies the job within its profile, e.g. `digest-weekly`
+    pub id: String,
+    /// Name the system scheduler knows the job by, unique across profiles
+    pub name: String,
+    pub frequency: Frequency,
+    /// Local time the job runs at
+    pub at: NaiveTime,
+    pub program: PathBuf,
+    pub args: Vec<String>,
+    /// File the job's output is appended to (not on Windows, where Task
+    /// Scheduler keeps the last result instead)
+    pub log: PathBuf,
+    pub installed_at: DateTime<Utc>,
+}
+
+/// The jobs installed for the profile whose state directory this is. Empty
+/// when there are none, or the file is unreadable.
+pub fn load(state_dir: &Path) -> Vec<ScheduledJob> {
+    fs::read_to_string(state_dir.join(SCHEDULES_FILE))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save(state_dir: &Path, jobs: &[ScheduledJob]) -> io::Result<()> {
+    fs::create_dir_all(state_dir)?;
+    let content = serde_json::to_string_pretty(jobs)?;
+    fs::write(state_dir.join(SCHEDULES_FILE), content)
+}
+
+/// Installs `job` with the system scheduler, replacing the job of the same
+/// name if there is one, so installing the same job twice leaves a single
+/// copy. Returns the job it replaced.
+pub fn install(state_dir: &Path, job: &ScheduledJob) -> Result<Option<ScheduledJob>, AppError> {
+    if cfg!(target_os = "macos") {
+        install_launchd(job)?;
+    } else if cfg!(windows) {
+        run(Command::new("schtasks").args(schtasks_create_args(job)))?;
+    } else {
+        let crontab = read_crontab()?;
+        write_crontab(&update_crontab(
+            &crontab,
+            &job.name,
+            Some(&crontab_line(job)),
+        ))?;
+    }
+
+    let mut jobs = load(state_dir);
+    let replaced = jobs
+        .iter()
+        .position(|j| j.id == job.id)
+        .map(|i| jobs.remove(i));
+    jobs.push(job.clone());
+    jobs.sort_by(|a, b| a.id.cmp(&b.id));
+    save(state_dir, &jobs)?;
+    Ok(replaced)
+}
+
+/// Removes the job with this ID from the system scheduler and from the
+/// profile's list.
+pub fn uninstall(state_dir: &Path, id: &str) -> Result<ScheduledJob, AppError> {
+    let mut jobs = load(state_dir);
+    let index = jobs.iter().position(|j| j.id == id).ok_or_else(|| {
+        AppError::Other(format!(
+            "No scheduled job '{id}'. See `acc digest schedule list`"
+        ))
+    })?;
+
+    let job = &jobs[index];
+    if cfg!(target_os = "macos") {
+        let plist = launchd_plist_path(&job.name)?;
+        if plist.exists() {
+            let _ = run(Command::new("launchctl").arg("unload").arg(&plist));
+            fs::remove_file(&plist)?;
+        }
+    } else if cfg!(windows) {
+        if is_installed(job) {
+            run(Command::new("schtasks").args(["/Delete", "/F", "/TN", &job.name]))?;
+        }
+    } else {
+        let crontab = read_crontab()?;
+        let updated = update_crontab(&crontab, &job.name, None);
+        if updated != crontab {
+            write_crontab(&updated)?;
+        }
+    }
+
+    let job = jobs.remove(index);
+    save(state_dir, &jobs)?;
+    Ok(job)
+}
+
+/// Whether the system scheduler still has the job, which it won't if it was
+/// removed by hand (e.g. with `crontab -e`).
+pub fn is_installed(job: &ScheduledJob) -> bool {
+    if cfg!(target_os = "macos") {
+        launchd_plist_path(&job.name).is_ok_and(|path| path.exists())
+    } else if cfg!(windows) {
+        Command::new("schtasks")
+            .args(["/Query", "/TN", &job.name])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .is_ok_and(|status| status.success())
+    } else {
+        let marker = format!("{CRON_MARKER}{}", job.name);
+        read_crontab().is_ok_and(|crontab| crontab.lines().any(|l| l.ends_with(&marker)))
+    }
+}
+
+/// Runs a scheduler tool, turning a failure into an error with what it said.
+fn run(command: &mut Command) -> Result<(), AppError> {
+    let program = command.get_program().to_string_lossy().into_owned();
+    let output = command
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| AppError::Other(format!("Failed to run {program}: {e}")))?;
+    if !output.status.success() {
+        return Err(AppError::Other(format!(
+            "{program} failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(())
+}
+
+fn read_crontab() -> Result<String, AppError> {
+    let output = Command::new("crontab")
+        .arg("-l")
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| AppError::Other(format!("Failed to run crontab: {e}")))?;
+    // `crontab -l` fails when the user has no crontab yet
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    } else {
+        Ok(String::new())
+    }
+}
+
+fn write_crontab(content: &str) -> Result<(), AppError> {
+    let mut child = Command::new("crontab")
+        .arg("-")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| AppError::Other(format!("Failed to run crontab: {e}")))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(content.as_bytes())?;
+    }
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        return Err(AppError::Other(format!(
+            "crontab failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(())
+}
+
+/// The crontab line running `job`, ending in its marker.
+fn crontab_line(job: &ScheduledJob) -> String {
+    let day = match job.frequency {
+        Frequency::Daily => "*".to_string(),
+        Frequency::Weekly(day) => day.num_days_from_sunday().to_string(),
+    };
+    let command = std::iter::once(job.program.to_string_lossy().into_owned())
+        .chain(job.args.iter().cloned())
+        .map(|arg| cron_quote(&arg))
+        .collect::<Vec<_>>()
+        .join(" ");
+    format!(
+        "{} {} * * {day} {command} >> {} 2>&1 {CRON_MARKER}{}",
+        job.at.minute(),
+        job.at.hour(),
+        cron_quote(&job.log.to_string_lossy()),
+        job.name
+    )
+}
+
+/// `crontab` with the line of the job called `name` replaced by `line`, or
+/// removed when there's no `line`. Every other line is kept as it was.
+fn update_crontab(crontab: &str, name: &str, line: Option<&str>) -> String {
+    let marker = format!("{CRON_MARKER}{name}");
+    let mut lines: Vec<&str> = crontab
+        .lines()
+        .filter(|l| !l.trim_end().ends_with(&marker))
+        .collect();
+    lines.extend(line);
+
+    let mut updated = lines.join("\n");
+    if !updated.is_empty() {
+        updated.push('\n');
+    }
+    updated
+}
+
+/// Single-quoted for the shell cron runs lines with. `%` means a newline to
+/// cron unless escaped, even inside quotes.
+fn cron_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''").replace('%', r"\%"))
+}
+
+fn launchd_plist_path(name: &str) -> Result<PathBuf, AppError> {
+    let home = dirs_next::home_dir()
+        .ok_or_else(|| AppError::Other("Could not find your home directory".to_string()))?;
+    Ok(home
+        .join("Library/LaunchAgents")
+        .join(format!("{LAUNCHD_PREFIX}{name}.plist")))
+}
+
+fn install_launchd(job: &ScheduledJob) -> Result<(), AppError> {
+    let path = launchd_plist_path(&job.name)?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    // An already loaded job keeps its old schedule until it's unloaded
+    if path.exists() {
+        let _ = run(Command::new("launchctl").arg("unload").arg(&path));
+    }
+    fs::write(&path, launchd_plist(job))?;
+    run(Command::new("launchctl").args(["load", "-w"]).arg(&path))
+}
+
+/// A launchd agent running `job` at its calendar interval.
+fn launchd_plist(job: &ScheduledJob) -> String {
+    let arguments = std::iter::once(job.program.to_string_lossy().into_owned())
+        .chain(job.args.iter().cloned())
+        .map(|arg| format!("        <string>{}</string>\n", xml_escape(&arg)))
+        .collect::<String>();
+    let weekday = match job.frequency {
+        Frequency::Daily => String::new(),
+        Frequency::Weekly(day) => format!(
+            "        <key>Weekday</key>\n        <integer>{}</integer>\n",
+            day.num_days_from_sunday()
+        ),
+    };
+    let log = xml_escape(&job.log.to_string_lossy());
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <key>Label</key>
+    <string>{LAUNCHD_PREFIX}{}</string>
+    <key>ProgramArguments</key>
+    <array>
+{arguments}    </array>
+    <key>StartCalendarInterval</key>
+    <dict>
+{weekday}        <key>Hour</key>
+        <integer>{}</integer>
+        <key>Minute</key>
+        <integer>{}</integer>
+    </dict>
+    <key>StandardOutPath</key>
+    <string>{log}</string>
+    <key>StandardErrorPath</key>
+    <string>{log}</string>
+</dict>
+</plist>
+"#,
+        xml_escape(&job.name),
+        job.at.hour(),
+        job.at.minute()
+    )
+}
+
+fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Arguments to `schtasks` creating `job`. `/F` replaces a task of the same
+/// name instead of failing.
+fn schtasks_create_args(job: &ScheduledJob) -> Vec<String> {
+    let command = std::iter::once(job.program.to_string_lossy().into_owned())
+        .chain(job.args.iter().cloned())
+        .map(|arg| {
+            if arg.contains(' ') {
+                format!("\"{arg}\"")
+            } else {
+                arg
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let mut args: Vec<String> = ["/Create", "/F", "/TN", &job.name]
+        .into_iter()
+        .map(str::to_string)
+        .collect();
+    match job.frequency {
+        Frequency::Daily => args.extend(["/SC".to_string(), "DAILY".to_string()]),
+        Frequency::Weekly(day) => args.extend([
+            "/SC".to_string(),
+            "WEEKLY".to_string(),
+            "/D".to_string(),
+            day.to_string().to_uppercase(),
+        ]),
+    }
+    args.extend([
+        "/ST".to_string(),
+        job.at.format("%H:%M").to_string(),
+        "/TR".to_string(),
+        command,
+    ]);
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn job() -> ScheduledJob {
+        ScheduledJob {
+            id: "digest-weekly".to_string(),
+            name: "acc-default-digest-weekly".to_string(),
+            frequency: Frequency::Weekly(Weekday::Fri),
+            at: NaiveTime::from_hms_opt(17, 5, 0).unwrap(),
+            program: PathBuf::from("/usr/local/bin/acc"),
+            args: vec![
+                "--profile".to_string(),
+                "default".to_string(),
+                "digest".to_string(),
+                "--email".to_string(),
+                "me@example.com".to_string(),
+            ],
+            log: PathBuf::from("/home/me/.accomplish/default/digest.log"),
+            installed_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_crontab_line() {
+        assert_eq!(
+            crontab_line(&job()),
+            "5 17 * * 5 '/usr/local/bin/acc' '--profile' 'default' 'digest' '--email' 'me@example.com' \
+             >> '/home/me/.accomplish/default/digest.log' 2>&1 # acc:acc-default-digest-weekly"
+        );
+        assert_eq!(cron_quote("it's 100%"), r"'it'\''s 100\%'");
+    }
+
+    #[test]
+    fn test_update_crontab_replaces_only_its_own_line() {
+        let crontab =
+            "MAILTO=me\n0 9 * * 1 backup.sh\n0 8 * * * old # acc:acc-default-digest-weekly\n";
+
+        let line = "0 17 * * 5 new # acc:acc-default-digest-weekly";
+        let updated = update_crontab(crontab, "acc-default-digest-weekly", Some(line));
+        assert_eq!(updated, format!("MAILTO=me\n0 9 * * 1 backup.sh\n{line}\n"));
+        // Installing again changes nothing
+        assert_eq!(
+            update_crontab(&updated, "acc-default-digest-weekly", Some(line)),
+            updated
+        );
+
+        let removed = update_crontab(crontab, "acc-default-digest-weekly", None);
+        assert_eq!(removed, "MAILTO=me\n0 9 * * 1 backup.sh\n");
+        assert_eq!(update_crontab("", "acc-default-digest-weekly", None), "");
+    }
+
+    #[test]
+    fn test_launchd_plist() {
+        let plist = launchd_plist(&job());
+        assert!(plist.contains("<string>dev.accomplish.acc-default-digest-weekly</string>"));
+        assert!(plist
+            .contains("<string>/usr/local/bin/acc</string>\n        <string>--profile</string>"));
+        assert!(plist.contains("<key>Weekday</key>\n        <integer>5</integer>"));
+        assert!(plist.contains("<key>Hour</key>\n        <integer>17</integer>"));
+
+        let daily = ScheduledJob {
+            frequency: Frequency::Daily,
+            ..job()
+        };
+        assert!(!launchd_plist(&daily).contains("Weekday"));
+    }
+
+    #[test]
+    fn test_schtasks_create_args() {
+        let job = ScheduledJob {
+            program: PathBuf::from(r"C:\Program Files\acc\acc.exe"),
+            ..job()
+        };
+        assert_eq!(
+            schtasks_create_args(&job),
+            vec![
+                "/Create",
+                "/F",
+                "/TN",
+                "acc-default-digest-weekly",
+                "/SC",
+                "WEEKLY",
+                "/D",
+                "FRI",
+                "/ST",
+                "17:05",
+                "/TR",
+                r#""C:\Program Files\acc\acc.exe" --profile default digest --email me@example.com"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_jobs_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(load(temp_dir.path()).is_empty());
+
+        let jobs = vec![job()];
+        save(temp_dir.path(), &jobs).unwrap();
+        assert_eq!(load(temp_dir.path()), jobs);
+        assert_eq!(Frequency::Weekly(Weekday::Fri).to_string(), "every Friday");
+        assert_eq!(Frequency::Daily.to_string(), "every day");
+    }
+}
@@ -0,0 +1,577 @@
+use crate::commands::capture::GitCommit;
+use crate::errors::AppError;
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use dirs_next::home_dir;
+use git2::{BranchType, Oid, Repository, Sort};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const SUGGESTIONS_FILE: &str = "suggestions.json";
+
+/// How long logged or dismissed activity is remembered. Older activity is
+/// outside any window worth suggesting from.
+const SET_ASIDE_RETENTION_DAYS: i64 = 90;
+
+/// Fewest uses of one tool in the shell history worth suggesting an entry for.
+const MIN_SHELL_COMMANDS: usize = 5;
+
+/// Commands shown under a suggestion's title before the rest are counted.
+const MAX_DETAILS: usize = 5;
+
+/// Commands too common to say anything about the work, plus git and acc whose
+/// activity is covered by commits and entries.
+const IGNORED_COMMANDS: &[&str] = &[
+    "acc", "cat", "cd", "clear", "code", "cp", "echo", "exit", "git", "grep", "head", "history",
+    "less", "ll", "ls", "man", "mkdir", "mv", "nano", "nvim", "open", "pwd", "rg", "rm", "tail",
+    "vi", "vim", "which",
+];
+
+/// A draft entry proposed from local activity.
+#[derive(Debug, Clone)]
+pub struct Suggestion {
+    /// First line of the draft, e.g. `Worked on feature/checkout — 5 commits, 3h span`
+    pub title: String,
+    /// Commit summaries or commands listed under the title
+    pub details: Vec<String>,
+    /// Where the activity came from: a repository name or "shell history"
+    pub source: String,
+    /// Project configured for the repository, if any
+    pub project: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: DateTime<Utc>,
+    /// Commit SHAs or shell history lines behind the suggestion, set aside once
+    /// it's logged or dismissed so they aren't suggested again
+    pub activity: Vec<String>,
+}
+
+impl Suggestion {
+    /// The entry text offered for logging.
+    pub fn draft(&self) -> String {
+        let mut draft = self.title.clone();
+        if !self.details.is_empty() {
+            draft.push_str("\n\n");
+            let details: Vec<String> = self.details.iter().map(|d| format!("- {d}")).collect();
+            draft.push_str(&details.join("\n"));
+        }
+        draft
+    }
+}
+
+/// Suggestions from the local branches of one repository: one per branch with
+/// commits since `since`, each commit counted on the first branch it's found
+/// on. Feature branches are named in the title; commits on the default branch
+/// are attributed to the repository. `skip` filters out commits already
+/// captured or set aside.
+pub fn git_suggestions<F>(
+    dir: &Path,
+    since: DateTime<Utc>,
+    author_email: Option<&str>,
+    skip: F,
+) -> Result<Vec<Suggestion>, AppError>
+where
+    F: Fn(&str) -> bool,
+{
+    let git_error = |action: &str, e: git2::Error| {
+        AppError::ParseError(format!("Failed to {action} in {}: {e}", dir.display()))
+    };
+    let repo = Repository::open(dir).map_err(|e| git_error("open the repository", e))?;
+    let repo_name = dir
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| dir.display().to_string());
+
+    let mut branches: Vec<(String, Oid, i64)> = Vec::new();
+    for branch in repo
+        .branches(Some(BranchType::Local))
+        .map_err(|e| git_error("list branches", e))?
+    {
+        let (branch, _) = branch.map_err(|e| git_error("read a branch", e))?;
+        let (Some(name), Ok(tip)) = (branch.name().ok().flatten(), branch.get().peel_to_commit())
+        else {
+            continue;
+        };
+        branches.push((name.to_string(), tip.id(), tip.time().seconds()));
+    }
+
+    let default = default_branch(&repo, &branches);
+    let default_tip = branches
+        .iter()
+        .find(|(name, _, _)| Some(name) == default.as_ref())
+        .map(|(_, tip, _)| *tip);
+    // Feature branches claim their commits first, most recently active first
+    branches.sort_by_key(|(name, _, time)| (Some(name) == default.as_ref(), -time));
+
+    let mut claimed: HashSet<String> = HashSet::new();
+    let mut suggestions = Vec::new();
+    for (name, tip, _) in &branches {
+        let is_default = Some(name) == default.as_ref();
+        let mut revwalk = repo.revwalk().map_err(|e| git_error("walk commits", e))?;
+        revwalk
+            .set_sorting(Sort::TIME)
+            .map_err(|e| git_error("sort commits", e))?;
+        revwalk
+            .push(*tip)
+            .map_err(|e| git_error("walk commits", e))?;
+        if let Some(default_tip) = default_tip.filter(|_| !is_default) {
+            revwalk
+                .hide(default_tip)
+                .map_err(|e| git_error("walk commits", e))?;
+        }
+
+        let mut commits: Vec<GitCommit> = Vec::new();
+        for oid in revwalk {
+            let oid = oid.map_err(|e| git_error("walk commits", e))?;
+            let commit = repo
+                .find_commit(oid)
+                .map_err(|e| git_error("read a commit", e))?;
+            let commit = GitCommit::from_git2_commit(&commit)?;
+            if commit.committed_at < since {
+                break;
+            }
+            let is_author = match (author_email, commit.author_email.as_deref()) {
+                (Some(wanted), Some(email)) => wanted.eq_ignore_ascii_case(email),
+                (Some(_), None) => false,
+                (None, _) => true,
+            };
+            if is_author && !skip(&commit.sha) && claimed.insert(commit.sha.clone()) {
+                commits.push(commit);
+            }
+        }
+
+        let subject = if is_default || (default.is_none() && branches.len() == 1) {
+            repo_name.as_str()
+        } else {
+            name.as_str()
+        };
+        if let Some(suggestion) = commit_suggestion(subject, &repo_name, &commits) {
+            suggestions.push(suggestion);
+        }
+    }
+
+    Ok(suggestions)
+}
+
+/// The branch commits are merged into: what `origin/HEAD` points at, or else
+/// the first of the usual names that exists.
+fn default_branch(repo: &Repository, branches: &[(String, Oid, i64)]) -> Option<String> {
+    let exists = |name: &str| branches.iter().any(|(branch, _, _)| branch == name);
+
+    let remote_head = repo
+        .find_reference("refs/remotes/origin/HEAD")
+        .ok()
+        .and_then(|reference| reference.symbolic_target().map(str::to_string))
+        .and_then(|target| {
+            target
+                .strip_prefix("refs/remotes/origin/")
+                .map(str::to_string)
+        })
+        .filter(|name| exists(name));
+
+    remote_head.or_else(|| {
+        ["main", "master", "trunk", "develop"]
+            .into_iter()
+            .find(|name| exists(name))
+            .map(str::to_string)
+    })
+}
+
+/// `commits` are newest first, as walked.
+fn commit_suggestion(subject: &str, repo_name: &str, commits: &[GitCommit]) -> Option<Suggestion> {
+    let newest = commits.first()?;
+    let oldest = commits.last()?;
+    let count = match commits.len() {
+        1 => "1 commit".to_string(),
+        n => format!("{n} commits"),
+    };
+
+    Some(Suggestion {
+        title: title(
+            &format!("Worked on {subject}"),
+            &count,
+            oldest.committed_at,
+            newest.committed_at,
+        ),
+        details: commits.iter().rev().map(|c| c.summary.clone()).collect(),
+        source: repo_name.to_string(),
+        project: None,
+        started_at: oldest.committed_at,
+        ended_at: newest.committed_at,
+        activity: commits.iter().map(|c| c.sha.clone()).collect(),
+    })
+}
+
+/// `summary` followed by how much activity there was and over how long.
+fn title(summary: &str, count: &str, start: DateTime<Utc>, end: DateTime<Utc>) -> String {
+    match describe_span(end - start) {
+        Some(span) => format!("{summary} — {count}, {span} span"),
+        None => format!("{summary} — {count}"),
+    }
+}
+
+/// Rounded to hours from an hour up, e.g. `45m` or `3h`. `None` under a minute.
+fn describe_span(span: Duration) -> Option<String> {
+    let minutes = span.num_minutes();
+    match minutes {
+        m if m < 1 => None,
+        m if m < 60 => Some(format!("{m}m")),
+        m => Some(format!("{}h", (m + 30) / 60)),
+    }
+}
+
+/// A timestamped line from a shell history file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShellCommand {
+    pub at: DateTime<Utc>,
+    pub command: String,
+}
+
+impl ShellCommand {
+    /// Identifies the line when it's set aside.
+    fn activity_id(&self) -> String {
+        format!("shell:{}:{}", self.at.timestamp(), self.command)
+    }
+
+    /// The program run, skipping `sudo` and leading `VAR=value` assignments.
+    fn program(&self) -> Option<&str> {
+        self.command
+            .split_whitespace()
+            .find(|word| *word != "sudo" && !word.contains('='))
+            .map(|word| word.rsplit('/').next().unwrap_or(word))
+    }
+}
+
+/// The history file of the user's shell: `$HISTFILE`, or the default for the
+/// shell in `$SHELL` (zsh, bash or fish).
+pub fn shell_history_path() -> Option<PathBuf> {
+    if let Some(path) = env::var_os("HISTFILE").map(PathBuf::from) {
+        if path.is_file() {
+            return Some(path);
+        }
+    }
+
+    let home = home_dir()?;
+    let zsh = home.join(".zsh_history");
+    let bash = home.join(".bash_history");
+    let fish = home.join(".local/share/fish/fish_history");
+    let shell = env::var("SHELL").unwrap_or_default();
+    let candidates = if shell.ends_with("fish") {
+        [fish, zsh, bash]
+    } else if shell.ends_with("bash") {
+        [bash, zsh, fish]
+    } else {
+        [zsh, bash, fish]
+    };
+    candidates.into_iter().find(|path| path.is_file())
+}
+
+/// Commands with a time from zsh extended history (`: 1720000000:0;cargo test`),
+/// bash history with `HISTTIMEFORMAT` set (`#1720000000` before each command)
+/// or fish history. Lines without a time can't be placed and are left out.
+pub fn parse_shell_history(content: &str) -> Vec<ShellCommand> {
+    let at = |seconds: &str| {
+        seconds
+            .trim()
+            .parse::<i64>()
+            .ok()
+            .and_then(|s| Utc.timestamp_opt(s, 0).single())
+    };
+
+    let mut commands = Vec::new();
+    let mut pending: Option<DateTime<Utc>> = None;
+    let mut fish_command: Option<String> = None;
+    for line in content.lines() {
+        if let Some(rest) = line.strip_prefix(": ") {
+            // zsh: `: <start>:<elapsed>;<command>`
+            if let Some((meta, command)) = rest.split_once(';') {
+                if let Some(at) = meta.split(':').next().and_then(at) {
+                    commands.push(ShellCommand {
+                        at,
+                        command: command.trim().to_string(),
+                    });
+                }
+            }
+        } else if let Some(command) = line.strip_prefix("- cmd: ") {
+            fish_command = Some(command.trim().to_string());
+        } else if let Some(when) = line.trim_start().strip_prefix("when: ") {
+            if let (Some(command), Some(at)) = (fish_command.take(), at(when)) {
+                commands.push(ShellCommand { at, command });
+            }
+        } else if let Some(seconds) = line.strip_prefix('#') {
+            pending = at(seconds);
+        } else if let Some(at) = pending.take() {
+            commands.push(ShellCommand {
+                at,
+                command: line.trim().to_string(),
+            });
+        }
+    }
+    commands.retain(|c| !c.command.is_empty());
+    commands
+}
+
+/// One suggestion per tool used at least `MIN_SHELL_COMMANDS` times since
+/// `since`, e.g. `Worked with kubectl — 14 commands, 2h span`.
+pub fn shell_suggestions<F>(
+    commands: &[ShellCommand],
+    since: DateTime<Utc>,
+    skip: F,
+) -> Vec<Suggestion>
+where
+    F: Fn(&str) -> bool,
+{
+    let mut by_program: HashMap<&str, Vec<&ShellCommand>> = HashMap::new();
+    for command in commands.iter().filter(|c| c.at >= since) {
+        let Some(program) = command.program() else {
+            continue;
+        };
+        if IGNORED_COMMANDS.contains(&program) || skip(&command.activity_id()) {
+            continue;
+        }
+        by_program.entry(program).or_default().push(command);
+    }
+
+    let mut suggestions: Vec<Suggestion> = by_program
+        .into_iter()
+        .filter(|(_, commands)| commands.len() >= MIN_SHELL_COMMANDS)
+        .map(|(program, mut commands)| {
+            commands.sort_by_key(|c| c.at);
+            let started_at = commands[0].at;
+            let ended_at = commands[commands.len() - 1].at;
+
+            let mut details: Vec<String> = Vec::new();
+            for command in commands.iter().rev() {
+                if !details.contains(&command.command) {
+                    details.push(command.command.clone());
+                }
+            }
+            let distinct = details.len();
+            details.truncate(MAX_DETAILS);
+            if distinct > MAX_DETAILS {
+                details.push(format!("…and {} more", distinct - MAX_DETAILS));
+            }
+
+            Suggestion {
+                title: title(
+                    &format!("Worked with {program}"),
+                    &format!("{} commands", commands.len()),
+                    started_at,
+                    ended_at,
+                ),
+                details,
+                source: "shell history".to_string(),
+                project: None,
+                started_at,
+                ended_at,
+                activity: commands.iter().map(|c| c.activity_id()).collect(),
+            }
+        })
+        .collect();
+    suggestions.sort_by_key(|s| std::cmp::Reverse(s.ended_at));
+    suggestions
+}
+
+/// Activity behind suggestions that were logged or dismissed, kept per profile
+/// in `<state_dir>/suggestions.json`.
+#[derive(Debug, Default)]
+pub struct SuggestionState {
+    path: PathBuf,
+    set_aside: HashMap<String, DateTime<Utc>>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SuggestionStateFile {
+    #[serde(default)]
+    set_aside: HashMap<String, DateTime<Utc>>,
+}
+
+impl SuggestionState {
+    /// Loads the state, starting empty if the file is missing or unreadable.
+    pub fn load(state_dir: &Path) -> Self {
+        let path = state_dir.join(SUGGESTIONS_FILE);
+        let set_aside = fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<SuggestionStateFile>(&content).ok())
+            .map(|file| file.set_aside)
+            .unwrap_or_default();
+
+        Self { path, set_aside }
+    }
+
+    pub fn is_set_aside(&self, activity: &str) -> bool {
+        self.set_aside.contains_key(activity)
+    }
+
+    /// Keeps the suggestion's activity out of later suggestions.
+    pub fn set_aside(&mut self, suggestion: &Suggestion) {
+        let now = Utc::now();
+        for activity in &suggestion.activity {
+            self.set_aside.insert(activity.clone(), now);
+        }
+    }
+
+    pub fn save(&mut self) -> io::Result<()> {
+        let cutoff = Utc::now() - Duration::days(SET_ASIDE_RETENTION_DAYS);
+        self.set_aside.retain(|_, at| *at >= cutoff);
+
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let file = SuggestionStateFile {
+            set_aside: self.set_aside.clone(),
+        };
+        let content = serde_json::to_string_pretty(&file)?;
+        fs::write(&self.path, content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git2::Signature;
+    use tempfile::TempDir;
+
+    fn commit_at(repo: &Repository, message: &str, timestamp: i64) -> Oid {
+        let signature =
+            Signature::new("Me", "me@example.com", &git2::Time::new(timestamp, 0)).unwrap();
+        let tree_id = repo.index().unwrap().write_tree().unwrap();
+        let tree = repo.find_tree(tree_id).unwrap();
+        let parent = repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            message,
+            &tree,
+            &parents,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_git_suggestions_per_branch() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = Repository::init(temp_dir.path()).unwrap();
+        let now = Utc::now().timestamp();
+        commit_at(&repo, "Old work", now - 10 * 86_400);
+        commit_at(&repo, "Fix build", now - 4 * 3600);
+        let main_tip = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.branch("feature/checkout", &main_tip, false).unwrap();
+        repo.set_head("refs/heads/feature/checkout").unwrap();
+        commit_at(&repo, "Add cart", now - 3 * 3600);
+        commit_at(&repo, "Add payment", now - 3600);
+
+        let since = Utc::now() - Duration::days(1);
+        let suggestions = git_suggestions(temp_dir.path(), since, None, |_| false).unwrap();
+        let titles: Vec<&str> = suggestions.iter().map(|s| s.title.as_str()).collect();
+        let repo_name = temp_dir.path().file_name().unwrap().to_string_lossy();
+        assert_eq!(
+            titles,
+            [
+                "Worked on feature/checkout — 2 commits, 2h span".to_string(),
+                format!("Worked on {repo_name} — 1 commit"),
+            ]
+        );
+        assert_eq!(suggestions[0].details, ["Add cart", "Add payment"]);
+
+        let skipped = suggestions[1].activity[0].clone();
+        let suggestions =
+            git_suggestions(temp_dir.path(), since, Some("other@example.com"), |_| false).unwrap();
+        assert!(suggestions.is_empty());
+        let suggestions =
+            git_suggestions(temp_dir.path(), since, None, |sha| sha == skipped).unwrap();
+        assert_eq!(suggestions.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_shell_history_formats() {
+        let zsh = ": 1720000000:0;cargo test\n: 1720000060:3;kubectl get pods\nno time\n";
+        let bash = "#1720000000\ncargo build\nls\n";
+        let fish = "- cmd: terraform plan\n  when: 1720000000\n  paths:\n    - main.tf\n";
+
+        let commands = parse_shell_history(zsh);
+        assert_eq!(commands.len(), 2);
+        assert_eq!(commands[1].command, "kubectl get pods");
+        assert_eq!(commands[1].at.timestamp(), 1_720_000_060);
+
+        let commands = parse_shell_history(bash);
+        assert_eq!(commands.len(), 1);
+        assert_eq!(commands[0].command, "cargo build");
+
+        let commands = parse_shell_history(fish);
+        assert_eq!(commands.len(), 1);
+        assert_eq!(commands[0].command, "terraform plan");
+    }
+
+    #[test]
+    fn test_shell_suggestions_group_by_program() {
+        let start = Utc.timestamp_opt(1_720_000_000, 0).unwrap();
+        let mut commands = Vec::new();
+        for (i, command) in [
+            "kubectl get pods",
+            "sudo kubectl logs api",
+            "KUBECONFIG=x kubectl get pods",
+            "kubectl describe pod api",
+            "/usr/local/bin/kubectl rollout restart",
+            "ls",
+            "cargo test",
+        ]
+        .iter()
+        .enumerate()
+        {
+            commands.push(ShellCommand {
+                at: start + Duration::minutes(30 * i as i64),
+                command: command.to_string(),
+            });
+        }
+        commands.push(ShellCommand {
+            at: start - Duration::days(1),
+            command: "kubectl version".to_string(),
+        });
+
+        let suggestions = shell_suggestions(&commands, start, |_| false);
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(
+            suggestions[0].title,
+            "Worked with kubectl — 5 commands, 2h span"
+        );
+        assert_eq!(
+            suggestions[0].details[0],
+            "/usr/local/bin/kubectl rollout restart"
+        );
+        assert_eq!(suggestions[0].details.len(), 5);
+
+        let first = commands[0].activity_id();
+        assert!(shell_suggestions(&commands, start, |id| id == first).is_empty());
+    }
+
+    #[test]
+    fn test_set_aside_survives_reload() {
+        let temp_dir = TempDir::new().unwrap();
+        let suggestion = Suggestion {
+            title: "Worked on web — 1 commit".to_string(),
+            details: vec!["Fix build".to_string()],
+            source: "web".to_string(),
+            project: None,
+            started_at: Utc::now(),
+            ended_at: Utc::now(),
+            activity: vec!["abc123".to_string()],
+        };
+        assert_eq!(
+            suggestion.draft(),
+            "Worked on web — 1 commit\n\n- Fix build"
+        );
+
+        let mut state = SuggestionState::load(temp_dir.path());
+        state.set_aside(&suggestion);
+        state.save().unwrap();
+
+        let state = SuggestionState::load(temp_dir.path());
+        assert!(state.is_set_aside("abc123"));
+        assert!(!state.is_set_aside("def456"));
+    }
+}
@@ -0,0 +1,271 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+pub const TAG_INDEX_FILE: &str = "tag_index.json";
+
+/// How sure a suggestion must be for `acc log --auto-tags` to add it, unless
+/// `auto_tags_threshold` says otherwise.
+pub const DEFAULT_AUTO_TAGS_THRESHOLD: f64 = 0.6;
+
+/// Words too common to say anything about which tags an entry needs.
+const STOP_WORDS: &[&str] = &[
+    "a", "about", "after", "again", "all", "also", "an", "and", "any", "are", "as", "at", "be",
+    "been", "before", "but", "by", "can", "did", "do", "for", "from", "had", "has", "have", "in",
+    "into", "is", "it", "its", "more", "not", "of", "on", "or", "our", "out", "so", "some", "than",
+    "that", "the", "then", "there", "this", "to", "up", "was", "we", "were", "what", "when",
+    "which", "while", "will", "with",
+];
+
+/// Entries a word must appear in before it's trusted to predict a tag.
+const MIN_WORD_ENTRIES: u32 = 2;
+
+/// Confidence in a tag the entry names outright, e.g. `backend` for
+/// "Moved the backend to the new queue".
+const NAMED_CONFIDENCE: f64 = 0.9;
+
+/// Which words appear in entries carrying which tags, learned from past
+/// entries so tags can be suggested for a new one without the network.
+#[derive(Debug, Default)]
+pub struct TagIndex {
+    path: PathBuf,
+    file: TagIndexFile,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct TagIndexFile {
+    built_at: Option<DateTime<Utc>>,
+    /// Entries the index was built from
+    #[serde(default)]
+    entries: u32,
+    /// Entries carrying each tag
+    #[serde(default)]
+    tags: HashMap<String, u32>,
+    /// Entries each word appears in
+    #[serde(default)]
+    words: HashMap<String, u32>,
+    /// Entries each word appears in, counted per tag they carry
+    #[serde(default)]
+    word_tags: HashMap<String, HashMap<String, u32>>,
+}
+
+/// A tag that likely fits an entry, with how sure the index is (0 to 1).
+#[derive(Debug, Clone, PartialEq)]
+pub struct TagSuggestion {
+    pub tag: String,
+    pub confidence: f64,
+}
+
+impl TagIndex {
+    /// Loads the index from `<state_dir>/tag_index.json`, starting empty if
+    /// the file is missing or unreadable.
+    pub fn load(state_dir: &Path) -> Self {
+        let path = state_dir.join(TAG_INDEX_FILE);
+        let file = fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+
+        Self { path, file }
+    }
+
+    pub fn built_at(&self) -> Option<DateTime<Utc>> {
+        self.file.built_at
+    }
+
+    /// Whether there's nothing to learn from: no entry had a tag.
+    pub fn is_empty(&self) -> bool {
+        self.file.tags.is_empty()
+    }
+
+    /// Replaces what the index knows with what `entries` show.
+    pub fn rebuild(&mut self, entries: &[Value]) {
+        let mut file = TagIndexFile {
+            built_at: Some(Utc::now()),
+            ..Default::default()
+        };
+        for entry in entries {
+            let content = entry.get("content").and_then(Value::as_str).unwrap_or("");
+            let tags: BTreeSet<String> = entry
+                .get("tags")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(Value::as_str)
+                .filter(|tag| learnable(tag))
+                .map(str::to_lowercase)
+                .collect();
+
+            file.entries += 1;
+            for tag in &tags {
+                *file.tags.entry(tag.clone()).or_default() += 1;
+            }
+            for word in words(content) {
+                *file.words.entry(word.clone()).or_default() += 1;
+                let counts = file.word_tags.entry(word).or_default();
+                for tag in &tags {
+                    *counts.entry(tag.clone()).or_default() += 1;
+                }
+            }
+        }
+        self.file = file;
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let content = serde_json::to_string_pretty(&self.file)?;
+        fs::write(&self.path, content)
+    }
+
+    /// Tags that fit `content`, most likely first, leaving out those in
+    /// `exclude` (the ones the entry already has).
+    ///
+    /// Each word of the entry that usually comes with a tag counts towards
+    /// it, by the share of its entries carrying the tag, weighed down for
+    /// words seen only a few times. Naming a tag outright counts the most.
+    pub fn suggest(&self, content: &str, exclude: &[String]) -> Vec<TagSuggestion> {
+        let words = words(content);
+        let mut suggestions: Vec<TagSuggestion> = self
+            .file
+            .tags
+            .keys()
+            .filter(|tag| !exclude.iter().any(|e| e.eq_ignore_ascii_case(tag)))
+            .filter_map(|tag| {
+                let parts = self::words(tag);
+                let named = !parts.is_empty() && parts.is_subset(&words);
+                let mut missed = if named { 1.0 - NAMED_CONFIDENCE } else { 1.0 };
+
+                for word in &words {
+                    let seen = self.file.words.get(word).copied().unwrap_or(0);
+                    if seen < MIN_WORD_ENTRIES {
+                        continue;
+                    }
+                    let with_tag = self
+                        .file
+                        .word_tags
+                        .get(word)
+                        .and_then(|tags| tags.get(tag))
+                        .copied()
+                        .unwrap_or(0);
+                    let share = f64::from(with_tag) / f64::from(seen);
+                    let weight = f64::from(seen) / (f64::from(seen) + 2.0);
+                    missed *= 1.0 - share * weight;
+                }
+
+                let confidence = 1.0 - missed;
+                (confidence > 0.0).then(|| TagSuggestion {
+                    tag: tag.clone(),
+                    confidence,
+                })
+            })
+            .collect();
+
+        suggestions.sort_by(|a, b| {
+            b.confidence
+                .partial_cmp(&a.confidence)
+                .unwrap_or(Ordering::Equal)
+                .then_with(|| self.file.tags[&b.tag].cmp(&self.file.tags[&a.tag]))
+                .then_with(|| a.tag.cmp(&b.tag))
+        });
+        suggestions
+    }
+}
+
+/// Tags the index learns. Tags acc adds itself, like `visibility:team` or
+/// `co-author:sam`, say nothing about what the entry is about.
+fn learnable(tag: &str) -> bool {
+    !tag.contains(':')
+}
+
+/// The distinct lowercased words of `text`, without stop words and numbers.
+fn words(text: &str) -> BTreeSet<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .map(str::to_lowercase)
+        .filter(|word| word.chars().count() >= 2)
+        .filter(|word| !word.chars().all(|c| c.is_ascii_digit()))
+        .filter(|word| !STOP_WORDS.contains(&word.as_str()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn index(entries: &[Value]) -> TagIndex {
+        let mut index = TagIndex::default();
+        index.rebuild(entries);
+        index
+    }
+
+    fn tags(suggestions: &[TagSuggestion]) -> Vec<&str> {
+        suggestions.iter().map(|s| s.tag.as_str()).collect()
+    }
+
+    #[test]
+    fn test_words() {
+        assert_eq!(
+            words("Fixed the CI build (again) for PR 42: flaky-tests"),
+            BTreeSet::from(["build", "ci", "fixed", "flaky", "pr", "tests"].map(str::to_string))
+        );
+    }
+
+    #[test]
+    fn test_suggest_learns_from_words_used_with_a_tag() {
+        let index = index(&[
+            json!({ "content": "Tuned the slow invoice query", "tags": ["db", "visibility:team"] }),
+            json!({ "content": "Added an index for the invoice query", "tags": ["db"] }),
+            json!({ "content": "Query planner digging", "tags": ["db", "perf"] }),
+            json!({ "content": "Invoice PDF layout", "tags": ["billing"] }),
+            json!({ "content": "Standup" }),
+        ]);
+
+        let suggestions = index.suggest("Rewrote the invoice totals query", &[]);
+        assert_eq!(tags(&suggestions), vec!["db", "billing", "perf"]);
+        assert!(suggestions[0].confidence > 0.6, "{suggestions:?}");
+        assert!(suggestions[1].confidence < 0.5, "{suggestions:?}");
+
+        // Tags the entry already has aren't suggested again
+        let suggestions = index.suggest("Rewrote the invoice totals query", &["DB".to_string()]);
+        assert!(!tags(&suggestions).contains(&"db"));
+
+        // acc's own tags are never learned
+        assert!(index.suggest("team visibility", &[]).is_empty());
+    }
+
+    #[test]
+    fn test_suggest_trusts_a_tag_named_outright() {
+        let index = index(&[json!({ "content": "Review", "tags": ["code-review"] })]);
+        let suggestions = index.suggest("Did a code review for Sam", &[]);
+        assert_eq!(tags(&suggestions), vec!["code-review"]);
+        assert!((suggestions[0].confidence - NAMED_CONFIDENCE).abs() < 1e-9);
+        assert!(index.suggest("Reviewed code", &[]).is_empty());
+    }
+
+    #[test]
+    fn test_index_round_trips() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut index = TagIndex::load(temp_dir.path());
+        assert!(index.is_empty());
+        assert!(index.built_at().is_none());
+
+        index.rebuild(&[
+            json!({ "content": "Deploy", "tags": ["ops"] }),
+            json!({ "content": "Deploy again", "tags": ["ops"] }),
+        ]);
+        index.save().unwrap();
+
+        let loaded = TagIndex::load(temp_dir.path());
+        assert!(!loaded.is_empty());
+        assert!(loaded.built_at().is_some());
+        assert_eq!(tags(&loaded.suggest("deploy", &[])), vec!["ops"]);
+    }
+}
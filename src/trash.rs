@@ -0,0 +1,138 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const TRASH_FILE: &str = "trash.json";
+
+/// Entries removed with `acc delete`, kept locally per profile so they can be
+/// brought back with `acc logs restore` until the retention window passes.
+#[derive(Debug, Default)]
+pub struct Trash {
+    path: PathBuf,
+    entries: Vec<TrashedEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashedEntry {
+    pub deleted_at: DateTime<Utc>,
+    /// The entry as the API returned it just before it was deleted
+    pub entry: Value,
+}
+
+impl TrashedEntry {
+    pub fn id(&self) -> &str {
+        self.entry.get("id").and_then(Value::as_str).unwrap_or("")
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct TrashFile {
+    #[serde(default)]
+    entries: Vec<TrashedEntry>,
+}
+
+impl Trash {
+    /// Loads the trash from `<state_dir>/trash.json`, starting empty if the
+    /// file is missing or unreadable.
+    pub fn load(state_dir: &Path) -> Self {
+        let path = state_dir.join(TRASH_FILE);
+        let entries = fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<TrashFile>(&content).ok())
+            .map(|file| file.entries)
+            .unwrap_or_default();
+
+        Self { path, entries }
+    }
+
+    /// Trashed entries, most recently deleted first.
+    pub fn entries(&self) -> &[TrashedEntry] {
+        &self.entries
+    }
+
+    pub fn put(&mut self, entry: Value) {
+        self.entries.insert(
+            0,
+            TrashedEntry {
+                deleted_at: Utc::now(),
+                entry,
+            },
+        );
+    }
+
+    /// Forgets entries deleted longer than `retention` ago, returning how many.
+    pub fn purge_expired(&mut self, retention: Duration) -> usize {
+        let cutoff = Utc::now() - retention;
+        let before = self.entries.len();
+        self.entries.retain(|trashed| trashed.deleted_at >= cutoff);
+        before - self.entries.len()
+    }
+
+    /// The trashed entry whose ID is `id_or_prefix` or uniquely starts with it.
+    pub fn find(&self, id_or_prefix: &str) -> Result<&TrashedEntry, String> {
+        let matches: Vec<&TrashedEntry> = self
+            .entries
+            .iter()
+            .filter(|trashed| trashed.id().starts_with(id_or_prefix))
+            .collect();
+
+        match matches.as_slice() {
+            [] => Err(format!("No entry in the trash matches '{id_or_prefix}'")),
+            [trashed] => Ok(trashed),
+            _ => matches
+                .iter()
+                .find(|trashed| trashed.id() == id_or_prefix)
+                .copied()
+                .ok_or_else(|| {
+                    format!(
+                        "'{id_or_prefix}' matches several entries in the trash. Use a longer ID"
+                    )
+                }),
+        }
+    }
+
+    pub fn remove(&mut self, entry_id: &str) {
+        self.entries.retain(|trashed| trashed.id() != entry_id);
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let file = TrashFile {
+            entries: self.entries.clone(),
+        };
+        let content = serde_json::to_string_pretty(&file)?;
+        fs::write(&self.path, content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_find_by_prefix_and_purge() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut trash = Trash::load(temp_dir.path());
+        trash.put(json!({ "id": "abc12345-1" }));
+        trash.put(json!({ "id": "abc12345-2" }));
+        trash.put(json!({ "id": "def67890" }));
+        trash.entries[2].deleted_at = Utc::now() - Duration::days(40);
+        trash.save().unwrap();
+
+        let mut trash = Trash::load(temp_dir.path());
+        assert_eq!(trash.find("def").unwrap().id(), "def67890");
+        assert!(trash.find("abc").is_err());
+        assert!(trash.find("zzz").is_err());
+
+        assert_eq!(trash.purge_expired(Duration::days(30)), 1);
+        assert!(trash.find("abc12345-1").is_err());
+        assert_eq!(trash.find("abc").unwrap().id(), "abc12345-2");
+    }
+}
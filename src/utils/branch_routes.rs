@@ -0,0 +1,267 @@
+use git2::{BranchType, Oid, Repository};
+use regex::Regex;
+use serde::Deserialize;
+use std::path::Path;
+
+/// A `branch_routes` rule as written in the config file.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RawBranchRoute {
+    pub pattern: String,
+    pub project: Option<String>,
+    pub tags: Option<Vec<String>>,
+}
+
+/// Sends work on branches matching `pattern` to `project`, with `tags`.
+#[derive(Debug, Clone)]
+pub struct BranchRoute {
+    pub pattern: String,
+    pub project: Option<String>,
+    pub tags: Vec<String>,
+    regex: Regex,
+}
+
+impl BranchRoute {
+    pub fn matches(&self, branch: &str) -> bool {
+        self.regex.is_match(branch)
+    }
+}
+
+/// Rules mapping branch names to projects and tags, from `branch_routes` in
+/// the config. The first rule whose pattern matches wins.
+///
+/// In patterns, `*` matches anything but `/`, `**` matches anything, and `?`
+/// matches one character other than `/`. So `billing/*` matches
+/// `billing/invoices` but not `billing/v2/invoices`, which `billing/**` does.
+#[derive(Debug, Clone, Default)]
+pub struct BranchRoutes {
+    routes: Vec<BranchRoute>,
+}
+
+impl BranchRoutes {
+    pub fn from_raw(raw: Vec<RawBranchRoute>) -> Result<Self, String> {
+        let routes = raw
+            .into_iter()
+            .map(|raw| {
+                let pattern = raw.pattern.trim().to_string();
+                if pattern.is_empty() {
+                    return Err("A branch route has an empty pattern".to_string());
+                }
+                let project = raw
+                    .project
+                    .map(|p| p.trim().to_string())
+                    .filter(|p| !p.is_empty());
+                let tags: Vec<String> = raw
+                    .tags
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|t| t.trim().to_string())
+                    .filter(|t| !t.is_empty())
+                    .collect();
+                if project.is_none() && tags.is_empty() {
+                    return Err(format!(
+                        "The branch route for '{pattern}' needs a project or tags"
+                    ));
+                }
+                let regex = pattern_regex(&pattern)
+                    .map_err(|e| format!("Invalid branch route pattern '{pattern}': {e}"))?;
+                Ok(BranchRoute {
+                    pattern,
+                    project,
+                    tags,
+                    regex,
+                })
+            })
+            .collect::<Result<_, String>>()?;
+        Ok(Self { routes })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.routes.is_empty()
+    }
+
+    /// The rule for `branch` and its position among the rules, counting from 1.
+    pub fn route(&self, branch: &str) -> Option<(usize, &BranchRoute)> {
+        self.routes
+            .iter()
+            .enumerate()
+            .find(|(_, route)| route.matches(branch))
+            .map(|(i, route)| (i + 1, route))
+    }
+
+    /// The rule for the branch checked out in the repository containing `dir`.
+    pub fn route_dir(&self, dir: &Path) -> Option<&BranchRoute> {
+        if self.is_empty() {
+            return None;
+        }
+        let branch = current_branch(dir)?;
+        self.route(&branch).map(|(_, route)| route)
+    }
+}
+
+/// The branch checked out in the repository containing `dir`. None outside a
+/// repository and on a detached HEAD.
+pub fn current_branch(dir: &Path) -> Option<String> {
+    let repo = Repository::discover(dir).ok()?;
+    let head = repo.head().ok()?;
+    if !head.is_branch() {
+        return None;
+    }
+    head.shorthand().map(str::to_string)
+}
+
+/// Whether every commit in `shas` was made on the branch checked out in the
+/// repository containing `dir`: none is on another branch, local or remote,
+/// other than the checked-out branch's upstream and branches started from
+/// its tip. So commits from before a branch was cut belong to the branch it
+/// was cut from.
+pub fn only_on_current_branch(dir: &Path, shas: &[&str]) -> bool {
+    let Ok(repo) = Repository::discover(dir) else {
+        return false;
+    };
+    let Ok(head) = repo.head() else {
+        return false;
+    };
+    let (Some(head_oid), Some(current)) = (head.target(), head.shorthand()) else {
+        return false;
+    };
+    if !head.is_branch() {
+        return false;
+    }
+    let upstream = repo
+        .find_branch(current, BranchType::Local)
+        .and_then(|branch| branch.upstream())
+        .ok()
+        .and_then(|upstream| upstream.get().target());
+    let Ok(branches) = repo.branches(None) else {
+        return false;
+    };
+
+    let others: Vec<Oid> = branches
+        .flatten()
+        // Symbolic refs like `origin/HEAD` have no target of their own
+        .filter_map(|(branch, _)| branch.get().target())
+        .filter(|&tip| Some(tip) != upstream)
+        .filter(|&tip| tip != head_oid && !repo.graph_descendant_of(tip, head_oid).unwrap_or(false))
+        .collect();
+
+    shas.iter().all(|sha| {
+        Oid::from_str(sha).is_ok_and(|oid| {
+            others
+                .iter()
+                .all(|&tip| tip != oid && !repo.graph_descendant_of(tip, oid).unwrap_or(true))
+        })
+    })
+}
+
+fn pattern_regex(pattern: &str) -> Result<Regex, regex::Error> {
+    let mut regex = String::from("^");
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                regex.push_str(".*");
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    regex.push('$');
+    Regex::new(&regex)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git2::Signature;
+    use tempfile::TempDir;
+
+    fn rule(pattern: &str, project: Option<&str>, tags: &[&str]) -> RawBranchRoute {
+        RawBranchRoute {
+            pattern: pattern.to_string(),
+            project: project.map(str::to_string),
+            tags: Some(tags.iter().map(|t| t.to_string()).collect()),
+        }
+    }
+
+    #[test]
+    fn test_first_matching_route_wins() {
+        let routes = BranchRoutes::from_raw(vec![
+            rule("billing/*", Some("BIL"), &["billing"]),
+            rule("**/hotfix-?", None, &["hotfix"]),
+            rule("**", Some("WEB"), &[]),
+        ])
+        .unwrap();
+
+        let (index, route) = routes.route("billing/invoices").unwrap();
+        assert_eq!(index, 1);
+        assert_eq!(route.project.as_deref(), Some("BIL"));
+        assert_eq!(route.tags, vec!["billing"]);
+
+        assert_eq!(routes.route("billing/v2/invoices").unwrap().0, 3);
+        assert_eq!(routes.route("ops/db/hotfix-1").unwrap().0, 2);
+        assert_eq!(routes.route("ops/db/hotfix-12").unwrap().0, 3);
+        assert_eq!(routes.route("main").unwrap().0, 3);
+
+        // Regex syntax in patterns is literal
+        let routes = BranchRoutes::from_raw(vec![rule("release.1", None, &["r"])]).unwrap();
+        assert!(routes.route("release.1").is_some());
+        assert!(routes.route("releasex1").is_none());
+    }
+
+    #[test]
+    fn test_only_on_current_branch() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = Repository::init(temp_dir.path()).unwrap();
+        let signature = Signature::now("Me", "me@example.com").unwrap();
+        let commit = |message: &str| {
+            let tree = repo
+                .find_tree(repo.index().unwrap().write_tree().unwrap())
+                .unwrap();
+            let parent = repo.head().ok().map(|h| h.peel_to_commit().unwrap());
+            let parents: Vec<&git2::Commit> = parent.iter().collect();
+            repo.commit(
+                Some("HEAD"),
+                &signature,
+                &signature,
+                message,
+                &tree,
+                &parents,
+            )
+            .unwrap()
+            .to_string()
+        };
+
+        let base = commit("Base");
+        let main = repo.head().unwrap().shorthand().unwrap().to_string();
+        repo.branch(
+            "billing/invoices",
+            &repo.find_commit(Oid::from_str(&base).unwrap()).unwrap(),
+            false,
+        )
+        .unwrap();
+        repo.set_head("refs/heads/billing/invoices").unwrap();
+        let invoices = commit("Invoices");
+
+        // Only the branch's own commits count as made on it
+        assert!(only_on_current_branch(temp_dir.path(), &[&invoices]));
+        assert!(!only_on_current_branch(
+            temp_dir.path(),
+            &[&invoices, &base]
+        ));
+
+        // On the base branch, a branch started from its tip doesn't take its commits
+        repo.set_head(&format!("refs/heads/{main}")).unwrap();
+        assert!(only_on_current_branch(temp_dir.path(), &[&base]));
+        let fix = commit("Fix");
+        assert!(only_on_current_branch(temp_dir.path(), &[&fix]));
+        assert!(!only_on_current_branch(temp_dir.path(), &[&fix, &base]));
+    }
+
+    #[test]
+    fn test_routes_need_a_pattern_and_a_destination() {
+        assert!(BranchRoutes::from_raw(vec![rule(" ", Some("BIL"), &[])]).is_err());
+        assert!(BranchRoutes::from_raw(vec![rule("billing/*", None, &[" "])]).is_err());
+    }
+}
@@ -0,0 +1,371 @@
+use clap::ValueEnum;
+
+/// How much of a commit message goes into a captured entry.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum MessageStyle {
+    /// Only the first line
+    Summary,
+    /// The whole message, as written
+    #[default]
+    Full,
+    /// The first line, then the body with hard wraps joined into one line per paragraph
+    Collapsed,
+}
+
+impl MessageStyle {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "summary" => Some(MessageStyle::Summary),
+            "full" => Some(MessageStyle::Full),
+            "collapsed" => Some(MessageStyle::Collapsed),
+            _ => None,
+        }
+    }
+}
+
+/// Where `Co-authored-by` trailers are recorded on a captured entry.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CoAuthorMode {
+    /// Not recorded
+    None,
+    /// In the `co_authors` custom field
+    #[default]
+    Field,
+    /// In the field and as `co-author:<name>` tags
+    Tag,
+}
+
+impl CoAuthorMode {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "none" | "off" => Some(CoAuthorMode::None),
+            "field" => Some(CoAuthorMode::Field),
+            "tag" | "tags" => Some(CoAuthorMode::Tag),
+            _ => None,
+        }
+    }
+}
+
+/// Configured policy for turning commit messages into entry content, from
+/// `capture_message_style`, `capture_strip_trailers` and `capture_co_authors`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CommitMessageFormat {
+    pub style: MessageStyle,
+    pub strip_trailers: bool,
+    pub co_authors: CoAuthorMode,
+}
+
+impl Default for CommitMessageFormat {
+    fn default() -> Self {
+        Self {
+            style: MessageStyle::Full,
+            strip_trailers: true,
+            co_authors: CoAuthorMode::default(),
+        }
+    }
+}
+
+impl CommitMessageFormat {
+    pub fn apply(&self, message: &str) -> String {
+        let message = CommitMessage::parse(message);
+        let mut parts = vec![message.summary.to_string()];
+
+        match self.style {
+            MessageStyle::Summary => {}
+            MessageStyle::Full => parts.extend(message.body.iter().map(|p| p.to_string())),
+            MessageStyle::Collapsed => parts.extend(message.body.iter().map(|p| collapse(p))),
+        }
+        if !self.strip_trailers && self.style != MessageStyle::Summary {
+            parts.extend(message.trailer_block.map(str::to_string));
+        }
+
+        parts.join("\n\n")
+    }
+}
+
+/// A commit message split into its parts, following git's conventions: the
+/// first paragraph is the summary, and a final paragraph made only of
+/// `Token: value` lines holds the trailers.
+#[derive(Debug, PartialEq)]
+pub struct CommitMessage<'a> {
+    pub summary: &'a str,
+    pub body: Vec<&'a str>,
+    pub trailers: Vec<(&'a str, &'a str)>,
+    trailer_block: Option<&'a str>,
+}
+
+impl<'a> CommitMessage<'a> {
+    pub fn parse(message: &'a str) -> Self {
+        let mut paragraphs = split_paragraphs(message.trim());
+        let summary = if paragraphs.is_empty() {
+            ""
+        } else {
+            paragraphs.remove(0)
+        };
+
+        let trailers = paragraphs.last().and_then(|last| parse_trailers(last));
+        let trailer_block = trailers.as_ref().and_then(|_| paragraphs.pop());
+
+        Self {
+            summary,
+            body: paragraphs,
+            trailers: trailers.unwrap_or_default(),
+            trailer_block,
+        }
+    }
+}
+
+/// Someone credited with a `Co-authored-by: Name <email>` trailer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CoAuthor {
+    pub name: String,
+    pub email: Option<String>,
+}
+
+impl CoAuthor {
+    fn parse(value: &str) -> Option<Self> {
+        let (name, email) = match value.split_once('<') {
+            Some((name, rest)) => (name.trim(), rest.split('>').next().map(str::trim)),
+            None => (value.trim(), None),
+        };
+        let email = email.filter(|e| !e.is_empty()).map(str::to_string);
+        if name.is_empty() && email.is_none() {
+            return None;
+        }
+        Some(Self {
+            name: name.to_string(),
+            email,
+        })
+    }
+
+    /// `co-author:<handle>`, the handle being the email's local part, or the
+    /// name when there's no email.
+    pub fn tag(&self) -> String {
+        let handle = self
+            .email
+            .as_deref()
+            .and_then(|e| e.split('@').next())
+            .filter(|h| !h.is_empty())
+            .unwrap_or(&self.name);
+        let handle = handle
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join("-")
+            .to_lowercase();
+        format!("co-author:{handle}")
+    }
+
+    fn is(&self, other: &CoAuthor) -> bool {
+        match (&self.email, &other.email) {
+            (Some(a), Some(b)) => a.eq_ignore_ascii_case(b),
+            _ => self.name.eq_ignore_ascii_case(&other.name),
+        }
+    }
+}
+
+impl std::fmt::Display for CoAuthor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.email {
+            Some(email) if self.name.is_empty() => write!(f, "<{email}>"),
+            Some(email) => write!(f, "{} <{email}>", self.name),
+            None => write!(f, "{}", self.name),
+        }
+    }
+}
+
+/// Trailer naming the project a commit belongs to, as added by the hook from
+/// `acc hooks install --prepare-commit-msg`.
+pub const PROJECT_TRAILER: &str = "Accomplish-Project";
+
+/// The project in the `Accomplish-Project` trailer of the first message that
+/// has one.
+pub fn trailer_project<'a>(messages: impl IntoIterator<Item = &'a str>) -> Option<String> {
+    messages.into_iter().find_map(|message| {
+        CommitMessage::parse(message)
+            .trailers
+            .into_iter()
+            .find(|(token, value)| token.eq_ignore_ascii_case(PROJECT_TRAILER) && !value.is_empty())
+            .map(|(_, value)| value.to_string())
+    })
+}
+
+/// Co-authors credited across several commit messages, each listed once and
+/// in order of first appearance. Anyone whose email is in `exclude` (the
+/// commits' own authors) is skipped.
+pub fn co_authors<'a>(
+    messages: impl IntoIterator<Item = &'a str>,
+    exclude: &[&str],
+) -> Vec<CoAuthor> {
+    let mut found: Vec<CoAuthor> = Vec::new();
+    for message in messages {
+        let parsed = CommitMessage::parse(message);
+        for (token, value) in parsed.trailers {
+            if !token.eq_ignore_ascii_case("Co-authored-by") {
+                continue;
+            }
+            let Some(co_author) = CoAuthor::parse(value) else {
+                continue;
+            };
+            let excluded = co_author
+                .email
+                .as_deref()
+                .is_some_and(|e| exclude.iter().any(|x| x.eq_ignore_ascii_case(e)));
+            if !excluded && !found.iter().any(|f| f.is(&co_author)) {
+                found.push(co_author);
+            }
+        }
+    }
+    found
+}
+
+fn split_paragraphs(text: &str) -> Vec<&str> {
+    let mut paragraphs = Vec::new();
+    let mut start: Option<usize> = None;
+    let mut end = 0;
+    let mut offset = 0;
+
+    for line in text.split_inclusive('\n') {
+        let content = line.trim_end();
+        if content.trim().is_empty() {
+            if let Some(s) = start.take() {
+                paragraphs.push(&text[s..end]);
+            }
+        } else {
+            start.get_or_insert(offset);
+            end = offset + content.len();
+        }
+        offset += line.len();
+    }
+    if let Some(s) = start {
+        paragraphs.push(&text[s..end]);
+    }
+
+    paragraphs
+}
+
+/// The `Token: value` pairs of a trailer paragraph, or `None` if any line
+/// isn't a trailer.
+fn parse_trailers(paragraph: &str) -> Option<Vec<(&str, &str)>> {
+    paragraph
+        .lines()
+        .map(|line| {
+            let (token, value) = line.split_once(':')?;
+            let is_token = !token.is_empty()
+                && !token.starts_with('-')
+                && token.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+            (is_token && value.starts_with(' ')).then(|| (token, value.trim()))
+        })
+        .collect()
+}
+
+/// Joins a paragraph's hard-wrapped lines into one. Lists, indented lines and
+/// code fences keep their line breaks.
+fn collapse(paragraph: &str) -> String {
+    let keeps_layout = paragraph.lines().any(|line| {
+        let trimmed = line.trim_start();
+        line.starts_with(' ')
+            || line.starts_with('\t')
+            || trimmed.starts_with("- ")
+            || trimmed.starts_with("* ")
+            || trimmed.starts_with("```")
+    });
+    if keeps_layout {
+        return paragraph.to_string();
+    }
+    paragraph.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MESSAGE: &str = "Fix token refresh race\n\n\
+        Two requests could refresh the token at the same\n\
+        time and one would be logged out.\n\n\
+        - lock around refresh\n\
+        - retry once\n\n\
+        Signed-off-by: Ada <ada@example.com>\n\
+        Co-authored-by: Bob <bob@example.com>\n";
+
+    #[test]
+    fn test_parse() {
+        let message = CommitMessage::parse(MESSAGE);
+        assert_eq!(message.summary, "Fix token refresh race");
+        assert_eq!(message.body.len(), 2);
+        assert_eq!(
+            message.trailers,
+            vec![
+                ("Signed-off-by", "Ada <ada@example.com>"),
+                ("Co-authored-by", "Bob <bob@example.com>")
+            ]
+        );
+
+        // A closing paragraph that isn't all trailers is body
+        let message = CommitMessage::parse("Bump deps\n\nNote: see the changelog\nfor details");
+        assert!(message.trailers.is_empty());
+        assert_eq!(message.body, vec!["Note: see the changelog\nfor details"]);
+        // A one-line message is never a trailer block
+        assert_eq!(CommitMessage::parse("Fixes: #12").summary, "Fixes: #12");
+    }
+
+    #[test]
+    fn test_apply_styles() {
+        let format = |style, strip_trailers| {
+            CommitMessageFormat {
+                style,
+                strip_trailers,
+                ..Default::default()
+            }
+            .apply(MESSAGE)
+        };
+
+        assert_eq!(
+            format(MessageStyle::Summary, false),
+            "Fix token refresh race"
+        );
+        assert_eq!(
+            format(MessageStyle::Full, true),
+            "Fix token refresh race\n\n\
+             Two requests could refresh the token at the same\n\
+             time and one would be logged out.\n\n\
+             - lock around refresh\n\
+             - retry once"
+        );
+        assert_eq!(
+            format(MessageStyle::Collapsed, false),
+            "Fix token refresh race\n\n\
+             Two requests could refresh the token at the same time and one would be logged out.\n\n\
+             - lock around refresh\n\
+             - retry once\n\n\
+             Signed-off-by: Ada <ada@example.com>\n\
+             Co-authored-by: Bob <bob@example.com>"
+        );
+    }
+
+    #[test]
+    fn test_co_authors() {
+        let other = "Pair on checkout\n\n\
+            Co-authored-by: bob <BOB@example.com>\n\
+            Co-authored-by: Ada <ada@example.com>\n\
+            Co-authored-by: Carol Ann";
+        let found = co_authors([MESSAGE, other], &["ada@example.com"]);
+
+        assert_eq!(
+            found.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            vec!["Bob <bob@example.com>", "Carol Ann"]
+        );
+        assert_eq!(found[0].tag(), "co-author:bob");
+        assert_eq!(found[1].tag(), "co-author:carol-ann");
+    }
+
+    #[test]
+    fn test_trailer_project() {
+        let messages = [
+            "Fix typo",
+            "Add login\n\nSigned-off-by: Ann <ann@example.com>\nAccomplish-Project: web",
+            "Older\n\nAccomplish-Project: api",
+        ];
+        assert_eq!(trailer_project(messages), Some("web".to_string()));
+        assert_eq!(trailer_project(["Fix typo"]), None);
+    }
+}
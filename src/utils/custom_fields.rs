@@ -0,0 +1,307 @@
+use regex::Regex;
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+use std::collections::{BTreeMap, HashMap};
+
+/// Value type of a custom entry field.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldType {
+    String,
+    Number,
+    Integer,
+    Boolean,
+    /// One of a fixed set of values
+    Enum(Vec<String>),
+}
+
+/// A custom field as defined under `[<profile>.fields.<name>]` in the config.
+#[derive(Debug, Clone)]
+pub struct FieldDefinition {
+    pub field_type: FieldType,
+    pub required: bool,
+    pub pattern: Option<Regex>,
+}
+
+/// Raw TOML shape of a field definition.
+#[derive(Debug, Deserialize)]
+pub struct RawFieldDefinition {
+    #[serde(rename = "type", default = "default_field_type")]
+    field_type: String,
+    #[serde(default)]
+    required: bool,
+    #[serde(default)]
+    values: Vec<String>,
+    pattern: Option<String>,
+}
+
+fn default_field_type() -> String {
+    "string".to_string()
+}
+
+/// Org-defined extra fields that `acc log --field key=value` validates against.
+#[derive(Debug, Clone, Default)]
+pub struct FieldSchema {
+    fields: BTreeMap<String, FieldDefinition>,
+}
+
+impl FieldSchema {
+    /// Builds the schema from the config table, rejecting unknown types and bad patterns.
+    pub fn from_raw(raw: HashMap<String, RawFieldDefinition>) -> Result<Self, String> {
+        let mut fields = BTreeMap::new();
+
+        for (name, def) in raw {
+            let field_type = match def.field_type.to_lowercase().as_str() {
+                "string" => FieldType::String,
+                "number" => FieldType::Number,
+                "integer" => FieldType::Integer,
+                "boolean" | "bool" => FieldType::Boolean,
+                "enum" => {
+                    if def.values.is_empty() {
+                        return Err(format!("Field '{name}' is an enum but lists no values"));
+                    }
+                    FieldType::Enum(def.values)
+                }
+                other => {
+                    return Err(format!(
+                        "Field '{name}' has unknown type '{other}'. Expected string, number, integer, boolean or enum"
+                    ))
+                }
+            };
+
+            let pattern = def
+                .pattern
+                .map(|p| {
+                    Regex::new(&format!("^(?:{p})$"))
+                        .map_err(|e| format!("Field '{name}' has an invalid pattern: {e}"))
+                })
+                .transpose()?;
+
+            fields.insert(
+                name.to_lowercase(),
+                FieldDefinition {
+                    field_type,
+                    required: def.required,
+                    pattern,
+                },
+            );
+        }
+
+        Ok(Self { fields })
+    }
+
+    /// Names of the defined fields, in order.
+    pub fn field_names(&self) -> Vec<&str> {
+        self.fields.keys().map(String::as_str).collect()
+    }
+
+    /// Validates `key=value` arguments and converts them to the `custom_fields`
+    /// object sent with an entry, typed according to the schema.
+    pub fn validate(&self, args: &[String]) -> Result<Map<String, Value>, String> {
+        let mut values = Map::new();
+
+        for arg in args {
+            let (key, raw) = arg
+                .split_once('=')
+                .ok_or_else(|| format!("Invalid field '{arg}'. Expected key=value"))?;
+            let key = key.trim().to_lowercase();
+            let raw = raw.trim();
+
+            let definition = self.fields.get(&key).ok_or_else(|| {
+                if self.fields.is_empty() {
+                    format!("Unknown field '{key}'. No custom fields are defined in the config")
+                } else {
+                    format!(
+                        "Unknown field '{key}'. Defined fields: {}",
+                        self.field_names().join(", ")
+                    )
+                }
+            })?;
+
+            if values.contains_key(&key) {
+                return Err(format!("Field '{key}' was given more than once"));
+            }
+            values.insert(key.clone(), convert_value(&key, raw, definition)?);
+        }
+
+        let missing = self.missing_required(&values);
+        if !missing.is_empty() {
+            return Err(format!(
+                "Missing required field(s): {}. Pass them with --field key=value",
+                missing.join(", ")
+            ));
+        }
+
+        Ok(values)
+    }
+
+    /// Required fields that `values` lacks, in order.
+    pub fn missing_required(&self, values: &Map<String, Value>) -> Vec<&str> {
+        self.fields
+            .iter()
+            .filter(|(name, def)| def.required && !values.contains_key(*name))
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+}
+
+fn convert_value(key: &str, raw: &str, definition: &FieldDefinition) -> Result<Value, String> {
+    if let Some(pattern) = &definition.pattern {
+        if !pattern.is_match(raw) {
+            return Err(format!(
+                "Field '{key}' value '{raw}' doesn't match the configured pattern"
+            ));
+        }
+    }
+
+    match &definition.field_type {
+        FieldType::String => {
+            if raw.is_empty() {
+                return Err(format!("Field '{key}' cannot be empty"));
+            }
+            Ok(json!(raw))
+        }
+        FieldType::Number => raw
+            .parse::<f64>()
+            .ok()
+            .filter(|n| n.is_finite())
+            .map(|n| json!(n))
+            .ok_or_else(|| format!("Field '{key}' must be a number, got '{raw}'")),
+        FieldType::Integer => raw
+            .parse::<i64>()
+            .map(|n| json!(n))
+            .map_err(|_| format!("Field '{key}' must be an integer, got '{raw}'")),
+        FieldType::Boolean => match raw.to_lowercase().as_str() {
+            "true" | "yes" | "1" => Ok(json!(true)),
+            "false" | "no" | "0" => Ok(json!(false)),
+            _ => Err(format!("Field '{key}' must be true or false, got '{raw}'")),
+        },
+        FieldType::Enum(allowed) => allowed
+            .iter()
+            .find(|v| v.eq_ignore_ascii_case(raw))
+            .map(|v| json!(v))
+            .ok_or_else(|| {
+                format!(
+                    "Field '{key}' must be one of: {}, got '{raw}'",
+                    allowed.join(", ")
+                )
+            }),
+    }
+}
+
+/// Formats an entry's `custom_fields` as `key=value` pairs for display.
+pub fn format_custom_fields(entry: &Value) -> Option<String> {
+    let fields = entry.get("custom_fields")?.as_object()?;
+    if fields.is_empty() {
+        return None;
+    }
+    Some(format_fields(fields))
+}
+
+/// Formats field values as `key=value` pairs, unquoting strings.
+pub fn format_fields(fields: &Map<String, Value>) -> String {
+    fields
+        .iter()
+        .map(|(key, value)| match value {
+            Value::String(s) => format!("{key}={s}"),
+            other => format!("{key}={other}"),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schema() -> FieldSchema {
+        let raw: HashMap<String, RawFieldDefinition> = toml::from_str(
+            r#"
+            [ticket]
+            pattern = "[A-Z]+-[0-9]+"
+            required = true
+
+            [effort]
+            type = "number"
+
+            [billable]
+            type = "boolean"
+
+            [customer]
+            type = "enum"
+            values = ["Acme", "Initech"]
+            "#,
+        )
+        .unwrap();
+        FieldSchema::from_raw(raw).unwrap()
+    }
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_validate_converts_types() {
+        let values = schema()
+            .validate(&args(&[
+                "ticket=PROJ-1",
+                "Effort=1.5",
+                "billable=yes",
+                "customer=acme",
+            ]))
+            .unwrap();
+
+        assert_eq!(values["ticket"], json!("PROJ-1"));
+        assert_eq!(values["effort"], json!(1.5));
+        assert_eq!(values["billable"], json!(true));
+        assert_eq!(values["customer"], json!("Acme"));
+    }
+
+    #[test]
+    fn test_missing_required() {
+        let schema = schema();
+        assert_eq!(schema.missing_required(&Map::new()), vec!["ticket"]);
+        let mut values = Map::new();
+        values.insert("ticket".to_string(), json!("PROJ-1"));
+        assert!(schema.missing_required(&values).is_empty());
+    }
+
+    #[test]
+    fn test_validate_rejects_invalid_values() {
+        let schema = schema();
+        assert!(schema.validate(&args(&["effort=2"])).is_err()); // missing required ticket
+        assert!(schema.validate(&args(&["ticket=proj"])).is_err());
+        assert!(schema
+            .validate(&args(&["ticket=P-1", "effort=lots"]))
+            .is_err());
+        assert!(schema
+            .validate(&args(&["ticket=P-1", "customer=Globex"]))
+            .is_err());
+        assert!(schema.validate(&args(&["ticket=P-1", "owner=me"])).is_err());
+        assert!(schema
+            .validate(&args(&["ticket=P-1", "ticket=P-2"]))
+            .is_err());
+        assert!(schema.validate(&args(&["ticket"])).is_err());
+    }
+
+    #[test]
+    fn test_from_raw_rejects_bad_definitions() {
+        let raw: HashMap<String, RawFieldDefinition> =
+            toml::from_str("[size]\ntype = \"enum\"\n").unwrap();
+        assert!(FieldSchema::from_raw(raw).is_err());
+
+        let raw: HashMap<String, RawFieldDefinition> =
+            toml::from_str("[size]\ntype = \"date\"\n").unwrap();
+        assert!(FieldSchema::from_raw(raw).is_err());
+    }
+
+    #[test]
+    fn test_format_custom_fields() {
+        let entry = json!({ "custom_fields": { "effort": 2, "ticket": "PROJ-1" } });
+        assert_eq!(
+            format_custom_fields(&entry),
+            Some("effort=2, ticket=PROJ-1".to_string())
+        );
+        assert_eq!(format_custom_fields(&json!({ "custom_fields": {} })), None);
+        assert_eq!(format_custom_fields(&json!({})), None);
+    }
+}
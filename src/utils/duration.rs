@@ -83,24 +83,33 @@ pub fn parse_since_duration(since: &str) -> Result<String, ApiError> {
         }
     }
 
+    let total_duration = parse_duration(since)?;
+    let now = Utc::now();
+    let from_time = now - total_duration;
+
+    Ok(from_time.format("%Y-%m-%dT%H:%M:%SZ").to_string())
+}
+
+/// Parses a duration like `24h`, `3h30m`, `2d` or `1w` (weeks, days, hours, minutes).
+pub fn parse_duration(value: &str) -> Result<Duration, ApiError> {
     let regex = Regex::new(r"(\d+)([wdhm])")
         .map_err(|e| ApiError::InvalidInput(format!("Failed to compile duration regex: {e}")))?;
 
     let mut total_duration = Duration::zero();
     let mut found_match = false;
 
-    for cap in regex.captures_iter(since) {
+    for cap in regex.captures_iter(value) {
         found_match = true;
-        let value: i64 = cap[1].parse().map_err(|_| {
+        let amount: i64 = cap[1].parse().map_err(|_| {
             ApiError::InvalidInput(format!("Invalid number in duration: {}", &cap[1]))
         })?;
 
         let unit = &cap[2];
         let duration = match unit {
-            "w" => Duration::weeks(value),
-            "d" => Duration::days(value),
-            "h" => Duration::hours(value),
-            "m" => Duration::minutes(value),
+            "w" => Duration::weeks(amount),
+            "d" => Duration::days(amount),
+            "h" => Duration::hours(amount),
+            "m" => Duration::minutes(amount),
             _ => {
                 return Err(ApiError::InvalidInput(format!(
                     "Unsupported duration unit: {unit}"
@@ -117,10 +126,7 @@ pub fn parse_since_duration(since: &str) -> Result<String, ApiError> {
         ));
     }
 
-    let now = Utc::now();
-    let from_time = now - total_duration;
-
-    Ok(from_time.format("%Y-%m-%dT%H:%M:%SZ").to_string())
+    Ok(total_duration)
 }
 
 #[cfg(test)]
@@ -1,17 +1,170 @@
 use std::env;
 use std::fs::{self, File};
-use std::io::{Read, Write};
-use std::path::PathBuf;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
 use crate::errors::AppError;
+use crate::utils::duration::parse_duration;
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone};
 
-/// Default template for worklog entries when opening the editor
+/// Built-in template for worklog entries, used when the templates directory
+/// has no `default.md`
 pub const DEFAULT_TEMPLATE: &str = r#"# Enter your worklog entry below
 # Lines starting with # will be ignored
 
 "#;
 
+const FRONT_MATTER_DELIMITER: &str = "---";
+const FRONT_MATTER_DATE_FORMAT: &str = "%Y-%m-%d %H:%M";
+
+/// Entry metadata edited alongside the content as front-matter:
+///
+/// ```text
+/// ---
+/// tags: [rust, cli]
+/// project: web
+/// date: 2025-07-09 14:30
+/// ---
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct EntryMetadata {
+    pub tags: Vec<String>,
+    pub project: Option<String>,
+    pub date: Option<DateTime<Local>>,
+}
+
+/// Renders metadata as a front-matter block to put above the entry template.
+pub fn render_front_matter(metadata: &EntryMetadata) -> String {
+    format!(
+        "{FRONT_MATTER_DELIMITER}\ntags: [{}]\nproject: {}\ndate: {}\n{FRONT_MATTER_DELIMITER}\n",
+        metadata.tags.join(", "),
+        metadata.project.as_deref().unwrap_or(""),
+        metadata
+            .date
+            .map(|d| d.format(FRONT_MATTER_DATE_FORMAT).to_string())
+            .unwrap_or_default()
+    )
+}
+
+/// Splits edited content into its front-matter metadata and the entry body.
+/// Content without a leading `---` block is returned unchanged with empty metadata.
+/// Empty values clear the field (e.g. `project:` means no project).
+pub fn parse_front_matter(content: &str) -> Result<(EntryMetadata, String), AppError> {
+    let trimmed = content.trim_start();
+    let Some(rest) = trimmed.strip_prefix(FRONT_MATTER_DELIMITER) else {
+        return Ok((EntryMetadata::default(), content.to_string()));
+    };
+
+    let mut metadata = EntryMetadata::default();
+    let mut lines = rest.lines();
+    // Skip the remainder of the opening delimiter line
+    lines.next();
+
+    let mut closed = false;
+    for line in lines.by_ref() {
+        if line.trim() == FRONT_MATTER_DELIMITER {
+            closed = true;
+            break;
+        }
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let (key, value) = line
+            .split_once(':')
+            .ok_or_else(|| AppError::ParseError(format!("Invalid front-matter line: '{line}'")))?;
+        let value = value.trim();
+
+        match key.trim().to_lowercase().as_str() {
+            "tags" => {
+                metadata.tags = value
+                    .trim_start_matches('[')
+                    .trim_end_matches(']')
+                    .split(',')
+                    .map(|t| t.trim().trim_matches(['"', '\'']).to_string())
+                    .filter(|t| !t.is_empty())
+                    .collect();
+            }
+            "project" => {
+                let project = value.trim_matches(['"', '\'']);
+                metadata.project = (!project.is_empty()).then(|| project.to_string());
+            }
+            "date" => {
+                let date = value.trim_matches(['"', '\'']);
+                metadata.date = if date.is_empty() {
+                    None
+                } else {
+                    Some(parse_entry_date(date, Local::now())?)
+                };
+            }
+            other => {
+                return Err(AppError::ParseError(format!(
+                    "Unknown front-matter field '{other}'. Expected tags, project or date"
+                )));
+            }
+        }
+    }
+
+    if !closed {
+        return Err(AppError::ParseError(
+            "Front-matter is missing its closing '---' line".to_string(),
+        ));
+    }
+
+    let body = lines.collect::<Vec<&str>>().join("\n").trim().to_string();
+    Ok((metadata, body))
+}
+
+/// Parses when an entry happened, as used by front-matter and `acc log --at`.
+/// Accepts RFC 3339, `YYYY-MM-DD HH:MM` (or with a `T`), a bare `YYYY-MM-DD`,
+/// `today` or `yesterday` (taken as midday unless a `HH:MM` follows), `now`,
+/// and durations before `now` such as `2h`, `1d3h` or `90m ago`.
+pub fn parse_entry_date(value: &str, now: DateTime<Local>) -> Result<DateTime<Local>, AppError> {
+    let value = value.trim();
+    if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
+        return Ok(dt.with_timezone(&Local));
+    }
+    if value == "now" {
+        return Ok(now);
+    }
+
+    let ago = value.strip_suffix("ago").unwrap_or(value).trim_end();
+    if ago.starts_with(|c: char| c.is_ascii_digit())
+        && ago
+            .chars()
+            .all(|c| c.is_ascii_digit() || "wdhm".contains(c))
+    {
+        let duration = parse_duration(ago).map_err(|e| AppError::ParseError(e.to_string()))?;
+        return Ok(now - duration);
+    }
+
+    let (day, time) = value.split_once([' ', 'T']).unwrap_or((value, ""));
+    let date = match day {
+        "today" => Some(now.date_naive()),
+        "yesterday" => now.date_naive().pred_opt(),
+        _ => NaiveDate::parse_from_str(day, "%Y-%m-%d").ok(),
+    };
+    let time = if time.is_empty() {
+        NaiveTime::from_hms_opt(12, 0, 0)
+    } else {
+        NaiveTime::parse_from_str(time.trim(), "%H:%M").ok()
+    };
+    let naive = date
+        .zip(time)
+        .map(|(date, time)| NaiveDateTime::new(date, time))
+        .ok_or_else(|| {
+            AppError::ParseError(format!(
+                "Invalid date '{value}'. Expected YYYY-MM-DD HH:MM, YYYY-MM-DD, today, yesterday or a duration like 2h"
+            ))
+        })?;
+
+    Local
+        .from_local_datetime(&naive)
+        .earliest()
+        .ok_or_else(|| AppError::ParseError(format!("Date '{value}' does not exist locally")))
+}
+
 /// Opens the user's preferred editor to edit a temporary file.
 ///
 /// This function will:
@@ -70,14 +223,33 @@ pub fn open_in_editor(initial_content: Option<&str>) -> Result<String, AppError>
         eprintln!("Warning: Failed to remove temporary file: {e}");
     }
 
-    // Filter out comment lines (lines starting with #)
-    let filtered_content = content
+    Ok(strip_comments(&content))
+}
+
+/// Reads an entry prepared ahead of time, for `acc log --file`: `-` reads
+/// stdin. Treated like the editor's content, so comment lines are dropped and
+/// an optional front-matter block sets the metadata.
+pub fn read_entry_file(path: &Path) -> Result<(EntryMetadata, String), AppError> {
+    let content = if path == Path::new("-") {
+        let mut content = String::new();
+        io::stdin().read_to_string(&mut content)?;
+        content
+    } else {
+        fs::read_to_string(path)
+            .map_err(|e| AppError::Other(format!("Failed to read '{}': {e}", path.display())))?
+    };
+
+    let (metadata, body) = parse_front_matter(&strip_comments(&content))?;
+    Ok((metadata, body.trim().to_string()))
+}
+
+/// Drops comment lines (lines starting with #), like the template's instructions.
+fn strip_comments(content: &str) -> String {
+    content
         .lines()
         .filter(|line| !line.trim_start().starts_with('#'))
         .collect::<Vec<&str>>()
-        .join("\n");
-
-    Ok(filtered_content)
+        .join("\n")
 }
 
 /// Determines the best editor to use based on environment variables and common editors
@@ -151,4 +323,106 @@ mod tests {
         // Verify
         assert_eq!(content, "Test content\nLine 2");
     }
+
+    #[test]
+    fn test_read_entry_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("notes.md");
+        fs::write(
+            &path,
+            "---\ntags: [release]\nproject: web\n---\n# Drafted before standup\n\nShipped the checkout fix\n",
+        )
+        .unwrap();
+
+        let (metadata, content) = read_entry_file(&path).unwrap();
+        assert_eq!(metadata.tags, vec!["release"]);
+        assert_eq!(metadata.project.as_deref(), Some("web"));
+        assert_eq!(content, "Shipped the checkout fix");
+
+        // Front-matter is optional
+        fs::write(&path, "\n# todo: link the PR\nPaired on the flaky test\n\n").unwrap();
+        let (metadata, content) = read_entry_file(&path).unwrap();
+        assert_eq!(metadata, EntryMetadata::default());
+        assert_eq!(content, "Paired on the flaky test");
+
+        let missing = dir.path().join("missing.md");
+        assert!(read_entry_file(&missing).is_err());
+    }
+
+    #[test]
+    fn test_front_matter_round_trip() {
+        let date = Local.with_ymd_and_hms(2025, 7, 9, 14, 30, 0).unwrap();
+        let metadata = EntryMetadata {
+            tags: vec!["rust".to_string(), "cli".to_string()],
+            project: Some("web".to_string()),
+            date: Some(date),
+        };
+
+        let rendered = render_front_matter(&metadata);
+        assert_eq!(
+            rendered,
+            "---\ntags: [rust, cli]\nproject: web\ndate: 2025-07-09 14:30\n---\n"
+        );
+
+        let (parsed, body) =
+            parse_front_matter(&format!("{rendered}\nFixed the checkout bug\n")).unwrap();
+        assert_eq!(parsed, metadata);
+        assert_eq!(body, "Fixed the checkout bug");
+    }
+
+    #[test]
+    fn test_parse_front_matter_edits_and_clears_fields() {
+        let content = "---\ntags: \"api\", backend\nproject:\ndate: 2025-07-08\n---\nBody line 1\nBody line 2";
+        let (metadata, body) = parse_front_matter(content).unwrap();
+
+        assert_eq!(metadata.tags, vec!["api", "backend"]);
+        assert_eq!(metadata.project, None);
+        assert_eq!(
+            metadata.date.unwrap().naive_local(),
+            NaiveDate::from_ymd_opt(2025, 7, 8)
+                .unwrap()
+                .and_hms_opt(12, 0, 0)
+                .unwrap()
+        );
+        assert_eq!(body, "Body line 1\nBody line 2");
+    }
+
+    #[test]
+    fn test_parse_front_matter_without_block() {
+        let (metadata, body) = parse_front_matter("Just content").unwrap();
+        assert_eq!(metadata, EntryMetadata::default());
+        assert_eq!(body, "Just content");
+    }
+
+    #[test]
+    fn test_parse_front_matter_errors() {
+        assert!(parse_front_matter("---\ntags: [a]\nBody").is_err());
+        assert!(parse_front_matter("---\nowner: me\n---\nBody").is_err());
+        assert!(parse_front_matter("---\ndate: tomorrow\n---\nBody").is_err());
+    }
+
+    #[test]
+    fn test_parse_entry_date_forms() {
+        let now = Local.with_ymd_and_hms(2025, 7, 9, 16, 45, 0).unwrap();
+        let at = |value: &str| {
+            parse_entry_date(value, now)
+                .unwrap()
+                .naive_local()
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        };
+
+        assert_eq!(at("2025-07-01T14:00"), "2025-07-01 14:00");
+        assert_eq!(at("2025-07-01 09:30"), "2025-07-01 09:30");
+        assert_eq!(at("2025-07-01"), "2025-07-01 12:00");
+        assert_eq!(at("yesterday"), "2025-07-08 12:00");
+        assert_eq!(at("yesterday 17:15"), "2025-07-08 17:15");
+        assert_eq!(at("today 09:00"), "2025-07-09 09:00");
+        assert_eq!(at("now"), "2025-07-09 16:45");
+        assert_eq!(at("2h"), "2025-07-09 14:45");
+        assert_eq!(at("1d30m ago"), "2025-07-08 16:15");
+
+        assert!(parse_entry_date("last tuesday", now).is_err());
+        assert!(parse_entry_date("yesterday 25:00", now).is_err());
+    }
 }
@@ -0,0 +1,160 @@
+use colored::*;
+use std::sync::OnceLock;
+use syntect::easy::HighlightLines;
+use syntect::highlighting::{Theme, ThemeSet};
+use syntect::parsing::SyntaxSet;
+use syntect::util::{as_24_bit_terminal_escaped, LinesWithEndings};
+
+const THEME: &str = "base16-ocean.dark";
+
+/// A run of entry content: prose, or a fenced code block.
+#[derive(Debug, PartialEq)]
+enum Segment<'a> {
+    Text(&'a str),
+    Code {
+        /// First word of the fence's info string, e.g. `rust` in ```` ```rust ````
+        language: Option<&'a str>,
+        open: &'a str,
+        code: String,
+        /// Missing when the block runs to the end of the content
+        close: Option<&'a str>,
+    },
+}
+
+/// Renders entry content for the terminal, highlighting fenced code blocks by
+/// the language named after the opening fence. Content is returned as is when
+/// colors are off (`--no-color`, `NO_COLOR`).
+pub fn render_content(content: &str) -> String {
+    if !colored::control::SHOULD_COLORIZE.should_colorize() {
+        return content.to_string();
+    }
+
+    let mut lines = Vec::new();
+    for segment in split_code_blocks(content) {
+        match segment {
+            Segment::Text(line) => lines.push(line.white().to_string()),
+            Segment::Code {
+                language,
+                open,
+                code,
+                close,
+            } => {
+                lines.push(open.bright_black().to_string());
+                lines.push(highlight(&code, language));
+                if let Some(close) = close {
+                    lines.push(close.bright_black().to_string());
+                }
+            }
+        }
+    }
+    lines.join("\n")
+}
+
+fn split_code_blocks(content: &str) -> Vec<Segment<'_>> {
+    let mut segments = Vec::new();
+    let mut lines = content.lines();
+
+    while let Some(line) = lines.next() {
+        let Some(fence) = fence_marker(line) else {
+            segments.push(Segment::Text(line));
+            continue;
+        };
+
+        let info = line.trim_start()[fence.len()..].trim();
+        let language = info.split_whitespace().next();
+        let mut code = Vec::new();
+        let mut close = None;
+        for line in lines.by_ref() {
+            if line.trim() == fence {
+                close = Some(line);
+                break;
+            }
+            code.push(line);
+        }
+
+        segments.push(Segment::Code {
+            language,
+            open: line,
+            code: code.join("\n"),
+            close,
+        });
+    }
+
+    segments
+}
+
+/// The fence (three or more backticks or tildes) opening a code block.
+fn fence_marker(line: &str) -> Option<&str> {
+    let trimmed = line.trim_start();
+    let fence_char = trimmed.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let len = trimmed.chars().take_while(|c| *c == fence_char).count();
+    (len >= 3).then(|| &trimmed[..len])
+}
+
+fn syntax_set() -> &'static SyntaxSet {
+    static SYNTAXES: OnceLock<SyntaxSet> = OnceLock::new();
+    SYNTAXES.get_or_init(SyntaxSet::load_defaults_newlines)
+}
+
+fn theme() -> &'static Theme {
+    static THEME_SET: OnceLock<ThemeSet> = OnceLock::new();
+    &THEME_SET.get_or_init(ThemeSet::load_defaults).themes[THEME]
+}
+
+/// Highlights a code block, guessing the language from its first line (e.g. a
+/// shebang) when the fence doesn't name a known one.
+fn highlight(code: &str, language: Option<&str>) -> String {
+    let syntaxes = syntax_set();
+    let syntax = language
+        .and_then(|lang| syntaxes.find_syntax_by_token(lang))
+        .or_else(|| syntaxes.find_syntax_by_first_line(code))
+        .unwrap_or_else(|| syntaxes.find_syntax_plain_text());
+
+    let mut highlighter = HighlightLines::new(syntax, theme());
+    let mut out = String::new();
+    for line in LinesWithEndings::from(code) {
+        match highlighter.highlight_line(line, syntaxes) {
+            Ok(ranges) => out.push_str(&as_24_bit_terminal_escaped(&ranges, false)),
+            Err(_) => out.push_str(line),
+        }
+    }
+    out.push_str("\x1b[0m");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_code_blocks() {
+        let content = "Fixed parsing:\n```rust title=\"main.rs\"\nfn main() {}\nlet x = 1;\n```\nDone\n~~~\nunterminated";
+        assert_eq!(
+            split_code_blocks(content),
+            vec![
+                Segment::Text("Fixed parsing:"),
+                Segment::Code {
+                    language: Some("rust"),
+                    open: "```rust title=\"main.rs\"",
+                    code: "fn main() {}\nlet x = 1;".to_string(),
+                    close: Some("```"),
+                },
+                Segment::Text("Done"),
+                Segment::Code {
+                    language: None,
+                    open: "~~~",
+                    code: "unterminated".to_string(),
+                    close: None,
+                },
+            ]
+        );
+        assert_eq!(fence_marker("``not a fence"), None);
+    }
+
+    #[test]
+    fn test_highlight_known_language() {
+        let highlighted = highlight("fn main() {}", Some("rs"));
+        assert!(highlighted.contains("\x1b[38;2;"));
+        assert!(highlighted.contains("main"));
+    }
+}
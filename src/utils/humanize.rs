@@ -0,0 +1,114 @@
+use chrono::{DateTime, Datelike, Duration, Local};
+
+/// A timestamp as people say it, relative to `now`: "today 14:32",
+/// "yesterday 09:10", "last Tue 16:45" within the past week, then the date
+/// alone ("Mar 4", or "Mar 4, 2024" in another year). Times in the future,
+/// e.g. from clock skew, are shown in full.
+pub fn relative_time(at: DateTime<Local>, now: DateTime<Local>) -> String {
+    let days = (now.date_naive() - at.date_naive()).num_days();
+    let time = at.format("%H:%M");
+    match days {
+        ..0 => at.format("%Y-%m-%d %H:%M").to_string(),
+        0 => format!("today {time}"),
+        1 => format!("yesterday {time}"),
+        2..=6 => format!("last {} {time}", at.format("%a")),
+        _ if at.year() == now.year() => at.format("%b %-d").to_string(),
+        _ => at.format("%b %-d, %Y").to_string(),
+    }
+}
+
+/// How long ago `at` was, roughly: "just now", "5 minutes ago", "3 hours
+/// ago", then days as in `relative_time`.
+pub fn time_ago(at: DateTime<Local>, now: DateTime<Local>) -> String {
+    let age = now - at;
+    if age.num_hours() < 12 {
+        ago(age)
+    } else {
+        relative_time(at, now)
+    }
+}
+
+/// An age in words: "just now", "a minute ago", "5 minutes ago", "an hour
+/// ago", "3 hours ago", "4 days ago".
+pub fn ago(age: Duration) -> String {
+    if age.num_minutes() < 1 {
+        return "just now".to_string();
+    }
+    format!("{} ago", span(age))
+}
+
+/// Time left until something happens: "now" once it's due, then "in a
+/// minute", "in 25 minutes", "in 5 hours", "in 30 days".
+pub fn time_until(remaining: Duration) -> String {
+    if remaining <= Duration::zero() {
+        return "now".to_string();
+    }
+    format!("in {}", span(remaining.max(Duration::minutes(1))))
+}
+
+/// A positive duration rounded down to its largest sensible unit.
+fn span(duration: Duration) -> String {
+    match duration.num_minutes() {
+        ..2 => "a minute".to_string(),
+        minutes @ 2..60 => format!("{minutes} minutes"),
+        60..120 => "an hour".to_string(),
+        minutes @ 120..2880 => format!("{} hours", minutes / 60),
+        minutes => format!("{} days", minutes / 1440),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<Local> {
+        Local.with_ymd_and_hms(y, m, d, h, min, 0).unwrap()
+    }
+
+    #[test]
+    fn test_relative_time() {
+        // 2025-07-10 is a Thursday
+        let now = at(2025, 7, 10, 15, 0);
+        assert_eq!(relative_time(at(2025, 7, 10, 14, 32), now), "today 14:32");
+        assert_eq!(relative_time(at(2025, 7, 9, 9, 10), now), "yesterday 09:10");
+        assert_eq!(relative_time(at(2025, 7, 8, 16, 45), now), "last Tue 16:45");
+        assert_eq!(relative_time(at(2025, 7, 4, 12, 0), now), "last Fri 12:00");
+        assert_eq!(relative_time(at(2025, 7, 3, 12, 0), now), "Jul 3");
+        assert_eq!(relative_time(at(2024, 12, 31, 12, 0), now), "Dec 31, 2024");
+        assert_eq!(
+            relative_time(at(2025, 7, 11, 8, 0), now),
+            "2025-07-11 08:00"
+        );
+    }
+
+    #[test]
+    fn test_time_ago() {
+        let now = at(2025, 7, 10, 15, 0);
+        assert_eq!(time_ago(now, now), "just now");
+        assert_eq!(time_ago(at(2025, 7, 10, 14, 59), now), "a minute ago");
+        assert_eq!(time_ago(at(2025, 7, 10, 14, 35), now), "25 minutes ago");
+        assert_eq!(time_ago(at(2025, 7, 10, 13, 30), now), "an hour ago");
+        assert_eq!(time_ago(at(2025, 7, 10, 9, 0), now), "6 hours ago");
+        assert_eq!(time_ago(at(2025, 7, 9, 9, 0), now), "yesterday 09:00");
+    }
+
+    #[test]
+    fn test_ago() {
+        assert_eq!(ago(Duration::seconds(20)), "just now");
+        assert_eq!(ago(Duration::minutes(1)), "a minute ago");
+        assert_eq!(ago(Duration::minutes(90)), "an hour ago");
+        assert_eq!(ago(Duration::hours(30)), "30 hours ago");
+        assert_eq!(ago(Duration::days(3)), "3 days ago");
+    }
+
+    #[test]
+    fn test_time_until() {
+        assert_eq!(time_until(Duration::days(30)), "in 30 days");
+        assert_eq!(time_until(Duration::hours(5)), "in 5 hours");
+        assert_eq!(time_until(Duration::minutes(90)), "in an hour");
+        assert_eq!(time_until(Duration::minutes(25)), "in 25 minutes");
+        assert_eq!(time_until(Duration::seconds(20)), "in a minute");
+        assert_eq!(time_until(Duration::minutes(-3)), "now");
+    }
+}
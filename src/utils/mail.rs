@@ -0,0 +1,96 @@
+use crate::errors::AppError;
+use std::io::{ErrorKind, Write};
+use std::process::{Command, Stdio};
+
+/// Where `sendmail` is looked for, in order. Cron runs jobs with a short
+/// PATH that usually leaves out the sbin directories.
+const SENDMAIL: &[&str] = &["sendmail", "/usr/sbin/sendmail", "/usr/lib/sendmail"];
+
+/// Checks that `address` looks like an email address, before it's used as a
+/// recipient or put in a scheduled job.
+pub fn validate_address(address: &str) -> Result<(), AppError> {
+    let valid = match address.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && !domain.is_empty()
+                && !domain.contains('@')
+                && !address.chars().any(|c| c.is_whitespace() || c.is_control())
+        }
+        None => false,
+    };
+    if valid {
+        Ok(())
+    } else {
+        Err(AppError::Other(format!(
+            "'{address}' is not an email address"
+        )))
+    }
+}
+
+/// Sends a plain-text email through the local `sendmail` (postfix, msmtp and
+/// the like all provide one), so no mail server settings are needed here.
+pub fn send(to: &str, subject: &str, body: &str) -> Result<(), AppError> {
+    validate_address(to)?;
+    let message = render_message(to, subject, body);
+
+    for program in SENDMAIL {
+        let child = Command::new(program)
+            .args(["-i", "--", to])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn();
+        let mut child = match child {
+            Ok(child) => child,
+            Err(e) if e.kind() == ErrorKind::NotFound => continue,
+            Err(e) => return Err(AppError::Other(format!("Failed to run {program}: {e}"))),
+        };
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(message.as_bytes())?;
+        }
+        let output = child.wait_with_output()?;
+        if !output.status.success() {
+            return Err(AppError::Other(format!(
+                "{program} couldn't send the email: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        return Ok(());
+    }
+
+    Err(AppError::Other(
+        "Sending email needs sendmail, which wasn't found. Install one (e.g. msmtp or postfix)"
+            .to_string(),
+    ))
+}
+
+fn render_message(to: &str, subject: &str, body: &str) -> String {
+    format!(
+        "To: {to}\nSubject: {subject}\nMIME-Version: 1.0\nContent-Type: text/plain; charset=utf-8\nContent-Transfer-Encoding: 8bit\n\n{body}"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_address() {
+        assert!(validate_address("me@example.com").is_ok());
+        assert!(validate_address("me").is_err());
+        assert!(validate_address("@example.com").is_err());
+        assert!(validate_address("me@").is_err());
+        assert!(validate_address("me@a@b").is_err());
+        // Nothing that could add a header
+        assert!(validate_address("me@example.com\nBcc: x@y.z").is_err());
+    }
+
+    #[test]
+    fn test_render_message() {
+        assert_eq!(
+            render_message("me@example.com", "Digest", "## 2025-07-09\n"),
+            "To: me@example.com\nSubject: Digest\nMIME-Version: 1.0\n\
+             Content-Type: text/plain; charset=utf-8\nContent-Transfer-Encoding: 8bit\n\n## 2025-07-09\n"
+        );
+    }
+}
@@ -1,3 +1,21 @@
+pub mod branch_routes;
+pub mod commit_message;
+pub mod custom_fields;
 pub mod duration;
 pub mod editor;
+pub mod fuzzy;
+pub mod highlight;
+pub mod humanize;
+pub mod mail;
+pub mod notify;
+pub mod period;
+pub mod progress;
+pub mod prompt;
+pub mod qr;
+pub mod recap_defaults;
+pub mod recap_filters;
+pub mod redaction;
+pub mod relations;
 pub mod spinner;
+pub mod templates;
+pub mod visibility;
@@ -0,0 +1,192 @@
+use chrono::NaiveTime;
+use serde::Deserialize;
+use std::io::{self, IsTerminal, Write};
+use std::process::{Command, Stdio};
+
+/// When the nothing-logged reminder is shown unless configured.
+const DEFAULT_REMIND_AT: &str = "17:00";
+
+/// The notifications that can be turned on and off in the config.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationKind {
+    /// `acc daemon` found nothing logged on a weekday by `remind_at`
+    NothingLogged,
+    /// `acc daemon` captured new commits
+    Captured,
+    /// `acc daemon` couldn't capture a repository
+    CaptureFailed,
+    /// A recap is ready, without `--notify` having to be passed
+    RecapReady,
+}
+
+/// `[<profile>.notifications]` as written in the config file.
+#[derive(Debug, Default, Deserialize)]
+pub struct RawNotificationSettings {
+    nothing_logged: Option<bool>,
+    remind_at: Option<String>,
+    captured: Option<bool>,
+    capture_failed: Option<bool>,
+    recap_ready: Option<bool>,
+}
+
+/// Which desktop notifications are shown.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NotificationSettings {
+    pub nothing_logged: bool,
+    /// Local time after which the nothing-logged reminder is shown
+    pub remind_at: NaiveTime,
+    pub captured: bool,
+    pub capture_failed: bool,
+    pub recap_ready: bool,
+}
+
+impl Default for NotificationSettings {
+    fn default() -> Self {
+        Self::from_raw(RawNotificationSettings::default()).expect("default settings are valid")
+    }
+}
+
+impl NotificationSettings {
+    pub fn from_raw(raw: RawNotificationSettings) -> Result<Self, String> {
+        let remind_at = raw.remind_at.as_deref().unwrap_or(DEFAULT_REMIND_AT);
+        let remind_at = NaiveTime::parse_from_str(remind_at, "%H:%M").map_err(|_| {
+            format!("Invalid notifications remind_at '{remind_at}'. Expected a time like 17:00")
+        })?;
+
+        Ok(Self {
+            nothing_logged: raw.nothing_logged.unwrap_or(true),
+            remind_at,
+            captured: raw.captured.unwrap_or(false),
+            capture_failed: raw.capture_failed.unwrap_or(true),
+            recap_ready: raw.recap_ready.unwrap_or(false),
+        })
+    }
+
+    pub fn enabled(&self, kind: NotificationKind) -> bool {
+        match kind {
+            NotificationKind::NothingLogged => self.nothing_logged,
+            NotificationKind::Captured => self.captured,
+            NotificationKind::CaptureFailed => self.capture_failed,
+            NotificationKind::RecapReady => self.recap_ready,
+        }
+    }
+
+    /// Shows the notification if its kind is turned on.
+    pub fn show(&self, kind: NotificationKind, title: &str, body: &str) {
+        if self.enabled(kind) {
+            desktop(title, body);
+        }
+    }
+}
+
+/// Shows a desktop notification (via `osascript` on macOS, a PowerShell toast
+/// on Windows, `notify-send` elsewhere), falling back to ringing the terminal
+/// bell when there's a terminal to ring.
+pub fn desktop(title: &str, body: &str) {
+    let shown = if cfg!(target_os = "macos") {
+        let script = format!(
+            "display notification {} with title {}",
+            applescript_string(body),
+            applescript_string(title)
+        );
+        run_quietly(Command::new("osascript").args(["-e", &script]))
+    } else if cfg!(windows) {
+        let script = toast_script(title, body);
+        run_quietly(Command::new("powershell").args([
+            "-NoProfile",
+            "-NonInteractive",
+            "-Command",
+            &script,
+        ]))
+    } else if cfg!(unix) {
+        run_quietly(Command::new("notify-send").args([title, body]))
+    } else {
+        false
+    };
+
+    if !shown && io::stdout().is_terminal() {
+        print!("\x07");
+        let _ = io::stdout().flush();
+    }
+}
+
+fn run_quietly(command: &mut Command) -> bool {
+    command
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map(|status| status.success())
+        .unwrap_or(false)
+}
+
+fn applescript_string(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Shows a toast through the Windows Runtime. Toasts need an app ID Windows
+/// already knows, so PowerShell's own is borrowed.
+fn toast_script(title: &str, body: &str) -> String {
+    const APP_ID: &str =
+        r"{1AC14E77-02E7-4E5D-B744-2EB1AE5198B7}\WindowsPowerShell\v1.0\powershell.exe";
+    format!(
+        "[Windows.UI.Notifications.ToastNotificationManager, Windows.UI.Notifications, ContentType = WindowsRuntime] > $null; \
+         $xml = [Windows.UI.Notifications.ToastNotificationManager]::GetTemplateContent([Windows.UI.Notifications.ToastTemplateType]::ToastText02); \
+         $text = $xml.GetElementsByTagName('text'); \
+         $text.Item(0).AppendChild($xml.CreateTextNode({})) > $null; \
+         $text.Item(1).AppendChild($xml.CreateTextNode({})) > $null; \
+         [Windows.UI.Notifications.ToastNotificationManager]::CreateToastNotifier({}).Show([Windows.UI.Notifications.ToastNotification]::new($xml))",
+        powershell_string(title),
+        powershell_string(body),
+        powershell_string(APP_ID)
+    )
+}
+
+/// Single-quoted, so nothing in it is expanded.
+fn powershell_string(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_applescript_string_escapes_quotes() {
+        assert_eq!(
+            applescript_string(r#"Run "acc recap wait""#),
+            r#""Run \"acc recap wait\"""#
+        );
+        assert_eq!(powershell_string("It's ready"), "'It''s ready'");
+    }
+
+    #[test]
+    fn test_notification_settings_defaults_and_toggles() {
+        let settings = NotificationSettings::default();
+        assert!(settings.enabled(NotificationKind::NothingLogged));
+        assert!(settings.enabled(NotificationKind::CaptureFailed));
+        assert!(!settings.enabled(NotificationKind::Captured));
+        assert!(!settings.enabled(NotificationKind::RecapReady));
+        assert_eq!(
+            settings.remind_at,
+            NaiveTime::from_hms_opt(17, 0, 0).unwrap()
+        );
+
+        let raw = RawNotificationSettings {
+            nothing_logged: Some(false),
+            remind_at: Some("16:30".to_string()),
+            ..Default::default()
+        };
+        let settings = NotificationSettings::from_raw(raw).unwrap();
+        assert!(!settings.enabled(NotificationKind::NothingLogged));
+        assert_eq!(
+            settings.remind_at,
+            NaiveTime::from_hms_opt(16, 30, 0).unwrap()
+        );
+
+        let raw = RawNotificationSettings {
+            remind_at: Some("5pm".to_string()),
+            ..Default::default()
+        };
+        assert!(NotificationSettings::from_raw(raw).is_err());
+    }
+}
@@ -0,0 +1,189 @@
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+
+/// An inclusive range of calendar days named by the user, e.g. `2025-06` or `last-week`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Period {
+    pub label: String,
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+}
+
+impl Period {
+    /// Number of days covered by the period.
+    pub fn days(&self) -> i64 {
+        (self.end - self.start).num_days() + 1
+    }
+}
+
+/// Parses a period relative to `today`.
+///
+/// Supports:
+/// - "2025-07-09" -> that single day
+/// - "2025-07" -> that calendar month
+/// - "2025-W28" -> that ISO week (Monday to Sunday)
+/// - "2025-06-01..2025-06-15" -> an explicit inclusive range
+/// - "today", "yesterday", "this-week", "last-week", "this-month", "last-month"
+pub fn parse_period(spec: &str, today: NaiveDate) -> Result<Period, String> {
+    let spec = spec.trim();
+    let period = |start: NaiveDate, end: NaiveDate| Period {
+        label: spec.to_string(),
+        start,
+        end,
+    };
+
+    match spec {
+        "today" => return Ok(period(today, today)),
+        "yesterday" => {
+            let day = today - Duration::days(1);
+            return Ok(period(day, day));
+        }
+        "this-week" => {
+            let (start, end) = week_of(today);
+            return Ok(period(start, end));
+        }
+        "last-week" => {
+            let (start, end) = week_of(today - Duration::days(7));
+            return Ok(period(start, end));
+        }
+        "this-month" => {
+            let (start, end) = month_of(today.year(), today.month())?;
+            return Ok(period(start, end));
+        }
+        "last-month" => {
+            let first = today.with_day(1).unwrap() - Duration::days(1);
+            let (start, end) = month_of(first.year(), first.month())?;
+            return Ok(period(start, end));
+        }
+        _ => {}
+    }
+
+    if let Some((from, to)) = spec.split_once("..") {
+        let start = parse_day(from)?;
+        let end = parse_day(to)?;
+        if end < start {
+            return Err(format!("Invalid period '{spec}': end is before start"));
+        }
+        return Ok(period(start, end));
+    }
+
+    if let Ok(day) = NaiveDate::parse_from_str(spec, "%Y-%m-%d") {
+        return Ok(period(day, day));
+    }
+
+    if let Some((year, week)) = spec.split_once("-W") {
+        let year: i32 = year
+            .parse()
+            .map_err(|_| format!("Invalid week period '{spec}'"))?;
+        let week: u32 = week
+            .parse()
+            .map_err(|_| format!("Invalid week period '{spec}'"))?;
+        let start = NaiveDate::from_isoywd_opt(year, week, Weekday::Mon)
+            .ok_or_else(|| format!("Invalid week period '{spec}'"))?;
+        return Ok(period(start, start + Duration::days(6)));
+    }
+
+    if let Some((year, month)) = spec.split_once('-') {
+        if let (Ok(year), Ok(month)) = (year.parse::<i32>(), month.parse::<u32>()) {
+            let (start, end) = month_of(year, month)?;
+            return Ok(period(start, end));
+        }
+    }
+
+    Err(format!(
+        "Invalid period '{spec}'. Use YYYY-MM-DD, YYYY-MM, YYYY-Www, FROM..TO, or a name like last-week"
+    ))
+}
+
+fn parse_day(value: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d")
+        .map_err(|_| format!("Invalid date '{value}'. Expected YYYY-MM-DD"))
+}
+
+fn week_of(day: NaiveDate) -> (NaiveDate, NaiveDate) {
+    let monday = day - Duration::days(day.weekday().num_days_from_monday() as i64);
+    (monday, monday + Duration::days(6))
+}
+
+fn month_of(year: i32, month: u32) -> Result<(NaiveDate, NaiveDate), String> {
+    let start = NaiveDate::from_ymd_opt(year, month, 1)
+        .ok_or_else(|| format!("Invalid month {year}-{month:02}"))?;
+    let next = if month == 12 {
+        NaiveDate::from_ymd_opt(year + 1, 1, 1)
+    } else {
+        NaiveDate::from_ymd_opt(year, month + 1, 1)
+    }
+    .ok_or_else(|| format!("Invalid month {year}-{month:02}"))?;
+    Ok((start, next - Duration::days(1)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    fn bounds(spec: &str) -> (NaiveDate, NaiveDate) {
+        // 2025-07-09 is a Wednesday
+        let period = parse_period(spec, date("2025-07-09")).unwrap();
+        (period.start, period.end)
+    }
+
+    #[test]
+    fn test_parse_calendar_periods() {
+        assert_eq!(
+            bounds("2025-07-01"),
+            (date("2025-07-01"), date("2025-07-01"))
+        );
+        assert_eq!(bounds("2025-02"), (date("2025-02-01"), date("2025-02-28")));
+        assert_eq!(bounds("2024-12"), (date("2024-12-01"), date("2024-12-31")));
+        assert_eq!(bounds("2025-W28"), (date("2025-07-07"), date("2025-07-13")));
+        assert_eq!(
+            bounds("2025-06-01..2025-06-15"),
+            (date("2025-06-01"), date("2025-06-15"))
+        );
+    }
+
+    #[test]
+    fn test_parse_named_periods() {
+        assert_eq!(bounds("today"), (date("2025-07-09"), date("2025-07-09")));
+        assert_eq!(
+            bounds("yesterday"),
+            (date("2025-07-08"), date("2025-07-08"))
+        );
+        assert_eq!(
+            bounds("this-week"),
+            (date("2025-07-07"), date("2025-07-13"))
+        );
+        assert_eq!(
+            bounds("last-week"),
+            (date("2025-06-30"), date("2025-07-06"))
+        );
+        assert_eq!(
+            bounds("this-month"),
+            (date("2025-07-01"), date("2025-07-31"))
+        );
+        assert_eq!(
+            bounds("last-month"),
+            (date("2025-06-01"), date("2025-06-30"))
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_periods() {
+        let today = date("2025-07-09");
+        assert!(parse_period("2025-13", today).is_err());
+        assert!(parse_period("2025-W60", today).is_err());
+        assert!(parse_period("2025-06-15..2025-06-01", today).is_err());
+        assert!(parse_period("someday", today).is_err());
+    }
+
+    #[test]
+    fn test_period_days() {
+        assert_eq!(
+            parse_period("2025-02", date("2025-07-09")).unwrap().days(),
+            28
+        );
+    }
+}
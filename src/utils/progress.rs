@@ -0,0 +1,108 @@
+use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
+use std::time::Duration;
+
+/// Stages a recap goes through, with the progress (in percent) each starts at.
+/// Used to label progress when the server doesn't name the stage.
+const STAGES: &[(u32, &str)] = &[
+    (0, "Collecting entries"),
+    (35, "Summarizing"),
+    (80, "Formatting"),
+];
+
+/// Progress of a recap being generated. Starts as a spinner and turns into a
+/// bar with the current stage once the server reports how far along it is.
+/// Nothing is drawn when the output isn't a terminal.
+pub struct RecapProgress {
+    bar: ProgressBar,
+    determinate: bool,
+}
+
+impl RecapProgress {
+    /// Draws on stdout, or on stderr to leave stdout to the command's output.
+    pub fn new(stderr: bool) -> Self {
+        let target = if stderr {
+            ProgressDrawTarget::stderr()
+        } else {
+            ProgressDrawTarget::stdout()
+        };
+        let bar = ProgressBar::with_draw_target(Some(100), target);
+        bar.set_style(
+            ProgressStyle::with_template("{spinner:.red} {msg:.red}... ({elapsed})")
+                .expect("valid progress template"),
+        );
+        bar.set_message("Generating your recap");
+        bar.enable_steady_tick(Duration::from_millis(100));
+        Self {
+            bar,
+            determinate: false,
+        }
+    }
+
+    /// Shows how far along the recap is. Without a `progress` the spinner
+    /// keeps going; progress never goes backwards.
+    pub fn update(&mut self, progress: Option<u32>, stage: Option<&str>) {
+        let Some(progress) = progress else {
+            return;
+        };
+        if !self.determinate {
+            self.determinate = true;
+            self.bar.set_style(
+                ProgressStyle::with_template(
+                    "{spinner:.red} {msg:<18.red} [{bar:30.red/bright_black}] {pos:>3}% ({elapsed})",
+                )
+                .expect("valid progress template")
+                .progress_chars("█▉▊▋▌▍▎▏ "),
+            );
+        }
+        let position = u64::from(progress.min(100)).max(self.bar.position());
+        self.bar.set_position(position);
+        self.bar.set_message(stage_label(position as u32, stage));
+    }
+
+    /// Removes the progress from the screen, before the result is printed.
+    pub fn finish(&self) {
+        self.bar.finish_and_clear();
+    }
+}
+
+impl Drop for RecapProgress {
+    fn drop(&mut self) {
+        self.finish();
+    }
+}
+
+/// The stage the server named, or the one `progress` falls in.
+fn stage_label(progress: u32, stage: Option<&str>) -> String {
+    if let Some(stage) = stage.map(str::trim).filter(|s| !s.is_empty()) {
+        let stage = stage.replace(['_', '-'], " ");
+        let mut chars = stage.chars();
+        return match chars.next() {
+            Some(first) => first.to_uppercase().chain(chars).collect(),
+            None => stage,
+        };
+    }
+    STAGES
+        .iter()
+        .rev()
+        .find(|(start, _)| progress >= *start)
+        .map_or(STAGES[0].1, |(_, label)| label)
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stage_label() {
+        assert_eq!(stage_label(0, None), "Collecting entries");
+        assert_eq!(stage_label(50, None), "Summarizing");
+        assert_eq!(stage_label(100, None), "Formatting");
+        assert_eq!(stage_label(10, Some("summarizing")), "Summarizing");
+        assert_eq!(
+            stage_label(10, Some("collecting_entries")),
+            "Collecting entries"
+        );
+        assert_eq!(stage_label(90, Some(" ")), "Formatting");
+    }
+}
@@ -0,0 +1,132 @@
+use inquire::error::InquireResult;
+use inquire::list_option::ListOption;
+use inquire::{Confirm, MultiSelect, Text};
+use std::fmt::Display;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static ASSUME_DEFAULTS: AtomicBool = AtomicBool::new(false);
+
+/// Makes prompts that have a default answer take it without asking, from
+/// `--defaults` or `assume_defaults = true` in the config.
+pub fn set_assume_defaults(enabled: bool) {
+    ASSUME_DEFAULTS.store(enabled, Ordering::Relaxed);
+}
+
+pub fn assume_defaults() -> bool {
+    ASSUME_DEFAULTS.load(Ordering::Relaxed)
+}
+
+/// Prompts that can be answered with their default. Prompts without one are
+/// still shown, since there's nothing sensible to assume.
+pub trait PromptOrDefault {
+    type Output;
+
+    fn prompt_or_default(self) -> InquireResult<Self::Output>;
+}
+
+impl PromptOrDefault for Confirm<'_> {
+    type Output = bool;
+
+    fn prompt_or_default(self) -> InquireResult<bool> {
+        match self.default.filter(|_| assume_defaults()) {
+            Some(answer) => {
+                announce(self.message, if answer { "Yes" } else { "No" });
+                Ok(answer)
+            }
+            None => self.prompt(),
+        }
+    }
+}
+
+impl PromptOrDefault for Text<'_> {
+    type Output = String;
+
+    fn prompt_or_default(self) -> InquireResult<String> {
+        match self.default.filter(|_| assume_defaults()) {
+            Some(answer) => {
+                announce(self.message, answer);
+                Ok(answer.to_string())
+            }
+            None => self.prompt(),
+        }
+    }
+}
+
+impl<T: Display> PromptOrDefault for MultiSelect<'_, T> {
+    type Output = Vec<T>;
+
+    fn prompt_or_default(self) -> InquireResult<Vec<T>> {
+        Ok(raw_prompt_or_default(self)?
+            .into_iter()
+            .map(|choice| choice.value)
+            .collect())
+    }
+}
+
+/// Like `prompt_or_default`, but keeps each picked option's index, for options
+/// that can read the same.
+pub fn raw_prompt_or_default<T: Display>(
+    prompt: MultiSelect<'_, T>,
+) -> InquireResult<Vec<ListOption<T>>> {
+    let Some(default) = prompt.default.clone().filter(|_| assume_defaults()) else {
+        return prompt.raw_prompt();
+    };
+
+    let answer: Vec<ListOption<T>> = prompt
+        .options
+        .into_iter()
+        .enumerate()
+        .filter(|(index, _)| default.contains(index))
+        .map(|(index, option)| ListOption::new(index, option))
+        .collect();
+    let shown: Vec<String> = answer
+        .iter()
+        .map(|choice| choice.value.to_string())
+        .collect();
+    announce(prompt.message, &shown.join(", "));
+    Ok(answer)
+}
+
+/// Echoes the assumed answer the way an answered prompt would look.
+fn announce(message: &str, answer: &str) {
+    println!("{} {answer} (default)", message.trim());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defaults_are_taken_without_prompting() {
+        set_assume_defaults(true);
+
+        assert!(Confirm::new("Continue?")
+            .with_default(true)
+            .prompt_or_default()
+            .unwrap());
+        assert_eq!(
+            Text::new("Name:")
+                .with_default("api")
+                .prompt_or_default()
+                .unwrap(),
+            "api"
+        );
+        assert_eq!(
+            MultiSelect::new("Pick:", vec!["a", "b", "c"])
+                .with_default(&[0, 2])
+                .prompt_or_default()
+                .unwrap(),
+            vec!["a", "c"]
+        );
+        let picked: Vec<usize> = raw_prompt_or_default(
+            MultiSelect::new("Pick:", vec!["a", "a", "b"]).with_default(&[1]),
+        )
+        .unwrap()
+        .iter()
+        .map(|choice| choice.index)
+        .collect();
+        assert_eq!(picked, vec![1]);
+
+        set_assume_defaults(false);
+    }
+}
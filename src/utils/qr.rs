@@ -0,0 +1,43 @@
+use qrcode::render::unicode::Dense1x2;
+use qrcode::QrCode;
+use std::env;
+
+/// Renders `data` as a QR code made of unicode half blocks, two modules per
+/// character cell. Colors are inverted so the code scans on the usual dark
+/// terminal background.
+pub fn render(data: &str) -> Option<String> {
+    let code = QrCode::new(data.as_bytes()).ok()?;
+    Some(
+        code.render::<Dense1x2>()
+            .dark_color(Dense1x2::Light)
+            .light_color(Dense1x2::Dark)
+            .quiet_zone(true)
+            .build(),
+    )
+}
+
+/// Whether there's likely no local browser to open: an SSH session, or a
+/// Linux machine without a display server.
+pub fn is_headless() -> bool {
+    let set = |name: &str| env::var_os(name).is_some_and(|v| !v.is_empty());
+    if set("SSH_CONNECTION") || set("SSH_TTY") {
+        return true;
+    }
+    cfg!(target_os = "linux") && !set("DISPLAY") && !set("WAYLAND_DISPLAY")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_is_square_block_art() {
+        let qr = render("https://accomplish.dev/device?code=ABCD-EFGH").unwrap();
+        let lines: Vec<&str> = qr.lines().collect();
+        let width = lines[0].chars().count();
+        assert!(lines.iter().all(|l| l.chars().count() == width));
+        // Two modules per line, so half as many lines as columns (rounded up)
+        assert_eq!(lines.len(), width.div_ceil(2));
+        assert!(qr.contains('█'));
+    }
+}
@@ -0,0 +1,155 @@
+use crate::cli::RecapAudience;
+use crate::commands::recap::RecapFormat;
+use clap::ValueEnum;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Recap options for one project as written in the config, under
+/// `[<profile>.project_recaps.<identifier>]`, or under `[recap]` in the
+/// project's `.accomplish.toml`.
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct RawRecapDefaults {
+    format: Option<String>,
+    audience: Option<String>,
+    exclude_tags: Option<Vec<String>>,
+}
+
+/// Recap options used for a project unless they're given as flags.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RecapDefaults {
+    pub format: Option<RecapFormat>,
+    pub audience: Option<RecapAudience>,
+    pub exclude_tags: Option<Vec<String>>,
+}
+
+impl RecapDefaults {
+    pub fn from_raw(raw: RawRecapDefaults) -> Result<Self, String> {
+        let format = raw
+            .format
+            .map(|f| {
+                RecapFormat::from_str(&f, true).map_err(|_| {
+                    format!("Invalid recap format '{f}'. Expected 'text', 'md', 'plain' or 'json'")
+                })
+            })
+            .transpose()?;
+        let audience = raw
+            .audience
+            .map(|a| {
+                RecapAudience::from_str(&a, true).map_err(|_| {
+                    format!(
+                        "Invalid recap audience '{a}'. Expected 'self', 'manager', 'team' or 'client'"
+                    )
+                })
+            })
+            .transpose()?;
+        Ok(Self {
+            format,
+            audience,
+            exclude_tags: raw.exclude_tags,
+        })
+    }
+
+    /// These defaults, with the ones they leave out taken from `other`.
+    pub fn or(self, other: Self) -> Self {
+        Self {
+            format: self.format.or(other.format),
+            audience: self.audience.or(other.audience),
+            exclude_tags: self.exclude_tags.or(other.exclude_tags),
+        }
+    }
+}
+
+/// The recap defaults of every project that has some, from the config.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ProjectRecapDefaults {
+    /// Keyed by lowercased project identifier
+    projects: HashMap<String, RecapDefaults>,
+}
+
+impl ProjectRecapDefaults {
+    pub fn from_raw(raw: HashMap<String, RawRecapDefaults>) -> Result<Self, String> {
+        let projects = raw
+            .into_iter()
+            .map(|(project, raw)| {
+                let defaults = RecapDefaults::from_raw(raw)
+                    .map_err(|e| format!("{e} in project_recaps.{project}"))?;
+                Ok((project.to_lowercase(), defaults))
+            })
+            .collect::<Result<_, String>>()?;
+        Ok(Self { projects })
+    }
+
+    /// The defaults for the project a recap is for, identified as with `-p`.
+    pub fn for_project(&self, identifier: &str) -> RecapDefaults {
+        self.projects
+            .get(&identifier.to_lowercase())
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn raw(
+        format: Option<&str>,
+        audience: Option<&str>,
+        exclude: Option<&[&str]>,
+    ) -> RawRecapDefaults {
+        RawRecapDefaults {
+            format: format.map(str::to_string),
+            audience: audience.map(str::to_string),
+            exclude_tags: exclude.map(|tags| tags.iter().map(|t| t.to_string()).collect()),
+        }
+    }
+
+    #[test]
+    fn test_for_project_ignores_case() {
+        let defaults = ProjectRecapDefaults::from_raw(HashMap::from([(
+            "WEB".to_string(),
+            raw(Some("md"), Some("Manager"), Some(&["wip"])),
+        )]))
+        .unwrap();
+
+        assert_eq!(
+            defaults.for_project("web"),
+            RecapDefaults {
+                format: Some(RecapFormat::Md),
+                audience: Some(RecapAudience::Manager),
+                exclude_tags: Some(vec!["wip".to_string()]),
+            }
+        );
+        assert_eq!(defaults.for_project("api"), RecapDefaults::default());
+    }
+
+    #[test]
+    fn test_invalid_values_name_the_project() {
+        let err = ProjectRecapDefaults::from_raw(HashMap::from([(
+            "web".to_string(),
+            raw(None, Some("board"), None),
+        )]))
+        .unwrap_err();
+        assert_eq!(
+            err,
+            "Invalid recap audience 'board'. Expected 'self', 'manager', 'team' or 'client' in project_recaps.web"
+        );
+        assert!(RecapDefaults::from_raw(raw(Some("pdf"), None, None)).is_err());
+    }
+
+    #[test]
+    fn test_or_fills_in_what_is_missing() {
+        let local = RecapDefaults::from_raw(raw(None, Some("client"), Some(&[]))).unwrap();
+        let global =
+            RecapDefaults::from_raw(raw(Some("plain"), Some("team"), Some(&["wip"]))).unwrap();
+        assert_eq!(
+            local.or(global),
+            RecapDefaults {
+                format: Some(RecapFormat::Plain),
+                audience: Some(RecapAudience::Client),
+                // An empty list still replaces the global one
+                exclude_tags: Some(Vec::new()),
+            }
+        );
+    }
+}
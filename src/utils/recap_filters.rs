@@ -0,0 +1,413 @@
+use regex::Regex;
+use serde::Deserialize;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// One step of the recap post-processing pipeline.
+pub trait RecapFilter: Send + Sync {
+    /// Name used in `recap_filters` and in warnings.
+    fn name(&self) -> &str;
+
+    fn apply(&self, text: &str) -> Result<String, String>;
+}
+
+/// A `recap_replacements` rule: every match of `pattern` becomes `replacement`,
+/// which may refer to capture groups as `$1` or `${name}`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RawReplacement {
+    pub pattern: String,
+    pub replacement: String,
+}
+
+/// Transforms applied in order to generated recaps before they're shown,
+/// exported or saved, configured with `recap_filters`.
+#[derive(Default)]
+pub struct RecapPipeline {
+    filters: Vec<Box<dyn RecapFilter>>,
+}
+
+impl RecapPipeline {
+    /// Builds the pipeline from filter names, checking that each one is known
+    /// and has the settings it needs.
+    pub fn from_config(
+        names: &[String],
+        translate_command: Option<&str>,
+        replacements: &[RawReplacement],
+    ) -> Result<Self, String> {
+        let mut pipeline = Self::default();
+        for name in names {
+            let filter: Box<dyn RecapFilter> = match name.as_str() {
+                "strip_emoji" => Box::new(StripEmoji),
+                "sentence_case" => Box::new(SentenceCase),
+                "replace" => Box::new(Replace::new(replacements)?),
+                "translate" => Box::new(Translate {
+                    command: translate_command
+                        .filter(|c| !c.trim().is_empty())
+                        .ok_or("The translate recap filter needs recap_translate_command")?
+                        .to_string(),
+                }),
+                other => {
+                    return Err(format!(
+                        "Unknown recap filter '{other}'. Expected strip_emoji, sentence_case, replace or translate"
+                    ))
+                }
+            };
+            pipeline.push(filter);
+        }
+        Ok(pipeline)
+    }
+
+    pub fn push(&mut self, filter: Box<dyn RecapFilter>) {
+        self.filters.push(filter);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.filters.is_empty()
+    }
+
+    /// Runs every filter in turn. A failing filter is skipped with a warning,
+    /// so the recap itself is never lost.
+    pub fn apply(&self, text: &str) -> String {
+        self.filters
+            .iter()
+            .fold(text.to_string(), |text, filter| match filter.apply(&text) {
+                Ok(filtered) => filtered,
+                Err(e) => {
+                    eprintln!("⚠️  Warning: Recap filter {} failed: {e}", filter.name());
+                    text
+                }
+            })
+    }
+}
+
+/// Removes emoji along with the space that separated them from the text.
+/// Everything else, including indentation and line breaks, is kept as is.
+struct StripEmoji;
+
+fn is_emoji(c: char) -> bool {
+    matches!(c as u32,
+        0x1F000..=0x1FAFF // pictographs, emoticons, transport, flags, ...
+        | 0x2600..=0x27BF // misc symbols and dingbats
+        | 0x2B00..=0x2BFF // stars, arrows and squares like ⭐
+        | 0x2300..=0x23FF // watches, hourglasses and media controls
+        | 0xFE0F          // emoji presentation selector
+        | 0x200D          // zero-width joiner in combined emoji
+        | 0xE0020..=0xE007F)
+}
+
+impl RecapFilter for StripEmoji {
+    fn name(&self) -> &str {
+        "strip_emoji"
+    }
+
+    fn apply(&self, text: &str) -> Result<String, String> {
+        let mut out = String::with_capacity(text.len());
+        let mut chars = text.chars().peekable();
+        while let Some(c) = chars.next() {
+            if !is_emoji(c) {
+                out.push(c);
+                continue;
+            }
+            while chars.next_if(|c| is_emoji(*c)).is_some() {}
+
+            let starts_word = out.is_empty() || out.ends_with(char::is_whitespace);
+            let ends_line = matches!(chars.peek(), None | Some('\n' | '\r'));
+            if ends_line {
+                if out.ends_with(' ') {
+                    out.pop();
+                }
+            } else if starts_word {
+                chars.next_if_eq(&' ');
+            }
+        }
+        Ok(out)
+    }
+}
+
+/// Capitalizes the start of each sentence and turns Title Case lines, such
+/// as headings, into sentence case. Other words are left alone, so names and
+/// acronyms survive, as are whitespace and code.
+struct SentenceCase;
+
+fn is_title_word(word: &str) -> bool {
+    let mut chars = word.chars();
+    chars.next().is_some_and(char::is_uppercase)
+        && chars.all(|c| !c.is_alphabetic() || c.is_lowercase())
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+fn lowercase_first(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_lowercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Markdown prefixes (`#`, `-`, `*`, `>`, `1.`) that come before a line's first word.
+fn is_marker(word: &str) -> bool {
+    word.chars().all(|c| "#-*>+".contains(c))
+        || (word.ends_with('.') && word[..word.len() - 1].chars().all(|c| c.is_ascii_digit()))
+}
+
+impl SentenceCase {
+    /// Cases one line in place. `sentence_start` carries over from the line
+    /// before, so a sentence wrapped onto the next line isn't capitalized
+    /// again, and is left as it stands after the line.
+    fn line(line: &str, sentence_start: &mut bool) -> String {
+        let words: Vec<(usize, &str)> = line
+            .split_whitespace()
+            .map(|word| (word.as_ptr() as usize - line.as_ptr() as usize, word))
+            .collect();
+        let content = words.iter().map(|(_, w)| w).skip_while(|w| is_marker(w));
+        let long_words: Vec<&&str> = content.clone().filter(|w| w.chars().count() >= 4).collect();
+        let title_line = long_words.len() >= 2 && long_words.iter().all(|w| is_title_word(w));
+        // Headings and list items start something new
+        if words.first().is_some_and(|(_, w)| is_marker(w)) {
+            *sentence_start = true;
+        }
+
+        let mut out = String::with_capacity(line.len());
+        let mut copied = 0;
+        for (i, &(offset, word)) in words.iter().enumerate() {
+            if is_marker(word) && words[..i].iter().all(|(_, w)| is_marker(w)) {
+                continue;
+            }
+            let cased = if word.starts_with('`') {
+                word.to_string()
+            } else if *sentence_start {
+                capitalize(word)
+            } else if title_line && is_title_word(word) {
+                lowercase_first(word)
+            } else {
+                word.to_string()
+            };
+            *sentence_start = word.ends_with(['.', '!', '?', ':']);
+            out.push_str(&line[copied..offset]);
+            out.push_str(&cased);
+            copied = offset + word.len();
+        }
+        out.push_str(&line[copied..]);
+        out
+    }
+}
+
+impl RecapFilter for SentenceCase {
+    fn name(&self) -> &str {
+        "sentence_case"
+    }
+
+    fn apply(&self, text: &str) -> Result<String, String> {
+        let mut out = String::with_capacity(text.len());
+        let mut sentence_start = true;
+        let mut in_code = false;
+        for line in text.split_inclusive('\n') {
+            let trimmed = line.trim_start();
+            if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+                in_code = !in_code;
+                sentence_start = true;
+                out.push_str(line);
+            } else if in_code {
+                out.push_str(line);
+            } else {
+                if trimmed.trim_end().is_empty() {
+                    sentence_start = true;
+                }
+                out.push_str(&Self::line(line, &mut sentence_start));
+            }
+        }
+        Ok(out)
+    }
+}
+
+/// Applies the `recap_replacements` rules in order.
+struct Replace {
+    rules: Vec<(Regex, String)>,
+}
+
+impl Replace {
+    fn new(raw: &[RawReplacement]) -> Result<Self, String> {
+        let rules = raw
+            .iter()
+            .map(|r| {
+                Regex::new(&r.pattern)
+                    .map(|re| (re, r.replacement.clone()))
+                    .map_err(|e| format!("Invalid recap replacement pattern '{}': {e}", r.pattern))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { rules })
+    }
+}
+
+impl RecapFilter for Replace {
+    fn name(&self) -> &str {
+        "replace"
+    }
+
+    fn apply(&self, text: &str) -> Result<String, String> {
+        Ok(self
+            .rules
+            .iter()
+            .fold(text.to_string(), |text, (pattern, replacement)| {
+                pattern
+                    .replace_all(&text, replacement.as_str())
+                    .into_owned()
+            }))
+    }
+}
+
+/// Pipes the recap through `recap_translate_command` (run by the shell, recap
+/// on stdin, translation on stdout).
+struct Translate {
+    command: String,
+}
+
+impl RecapFilter for Translate {
+    fn name(&self) -> &str {
+        "translate"
+    }
+
+    fn apply(&self, text: &str) -> Result<String, String> {
+        let mut shell = if cfg!(windows) {
+            let mut cmd = Command::new("cmd");
+            cmd.arg("/C");
+            cmd
+        } else {
+            let mut cmd = Command::new("sh");
+            cmd.arg("-c");
+            cmd
+        };
+        let mut child = shell
+            .arg(&self.command)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("could not run '{}': {e}", self.command))?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin
+                .write_all(text.as_bytes())
+                .map_err(|e| format!("could not send the recap: {e}"))?;
+        }
+        let output = child
+            .wait_with_output()
+            .map_err(|e| format!("'{}' failed: {e}", self.command))?;
+        if !output.status.success() {
+            return Err(format!("'{}' exited with {}", self.command, output.status));
+        }
+        let translated = String::from_utf8_lossy(&output.stdout)
+            .trim_end()
+            .to_string();
+        if translated.is_empty() {
+            return Err(format!("'{}' printed nothing", self.command));
+        }
+        Ok(translated)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pipeline(names: &[&str], replacements: &[(&str, &str)]) -> RecapPipeline {
+        let names: Vec<String> = names.iter().map(|n| n.to_string()).collect();
+        let replacements: Vec<RawReplacement> = replacements
+            .iter()
+            .map(|(pattern, replacement)| RawReplacement {
+                pattern: pattern.to_string(),
+                replacement: replacement.to_string(),
+            })
+            .collect();
+        RecapPipeline::from_config(&names, Some("tr a-z A-Z"), &replacements).unwrap()
+    }
+
+    #[test]
+    fn test_strip_emoji_keeps_markers_and_indentation() {
+        let recap = "## 🚀 Highlights\n- ✅ Shipped the API\n  - 🐛 Fixed 2 bugs 👍🏽\nDone✨ today";
+        assert_eq!(
+            pipeline(&["strip_emoji"], &[]).apply(recap),
+            "## Highlights\n- Shipped the API\n  - Fixed 2 bugs\nDone today"
+        );
+    }
+
+    #[test]
+    fn test_sentence_case() {
+        let recap = "## Improved The Checkout Flow\n- worked with Alice on the GitHub API. then paired on CI";
+        assert_eq!(
+            pipeline(&["sentence_case"], &[]).apply(recap),
+            "## Improved the checkout flow\n- Worked with Alice on the GitHub API. Then paired on CI"
+        );
+    }
+
+    #[test]
+    fn test_pipeline_keeps_markdown_layout() {
+        let recap = "\
+## 🚀 Highlights
+
+Shipped the billing API with Alice, which took  most of
+the week.  \n\
+wrapped up JIRA-12 too.
+
+```sh
+cargo test  # still passes
+```
+
+- fixed `acc log` 🐛
+";
+        let filtered = pipeline(
+            &["strip_emoji", "sentence_case", "replace"],
+            &[(r"JIRA-(\d+)", "ticket $1")],
+        )
+        .apply(recap);
+        assert_eq!(
+            filtered,
+            "\
+## Highlights
+
+Shipped the billing API with Alice, which took  most of
+the week.  \n\
+Wrapped up ticket 12 too.
+
+```sh
+cargo test  # still passes
+```
+
+- Fixed `acc log`
+"
+        );
+    }
+
+    #[test]
+    fn test_filters_run_in_order() {
+        let replace_first = pipeline(&["replace", "translate"], &[(r"JIRA-(\d+)", "ticket $1")]);
+        assert_eq!(replace_first.apply("Closed JIRA-12"), "CLOSED TICKET 12");
+
+        let translate_first = pipeline(&["translate", "replace"], &[(r"JIRA-(\d+)", "ticket $1")]);
+        assert_eq!(translate_first.apply("Closed JIRA-12"), "CLOSED ticket 12");
+    }
+
+    #[test]
+    fn test_failing_filter_keeps_text() {
+        let names = vec!["translate".to_string()];
+        let failing = RecapPipeline::from_config(&names, Some("exit 3"), &[]).unwrap();
+        assert_eq!(failing.apply("Recap"), "Recap");
+    }
+
+    #[test]
+    fn test_config_errors() {
+        let names = |n: &str| vec![n.to_string()];
+        assert!(RecapPipeline::from_config(&names("shout"), None, &[]).is_err());
+        assert!(RecapPipeline::from_config(&names("translate"), None, &[]).is_err());
+        let bad = RawReplacement {
+            pattern: "(".into(),
+            replacement: String::new(),
+        };
+        assert!(RecapPipeline::from_config(&names("replace"), None, &[bad]).is_err());
+    }
+}
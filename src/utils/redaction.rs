@@ -0,0 +1,170 @@
+use serde_json::{json, Value};
+
+/// Tags redacted when the config doesn't specify any.
+pub const DEFAULT_REDACTED_TAGS: &[&str] = &["confidential"];
+
+/// Placeholder content used when redacted entries are summarized.
+const REDACTED_CONTENT: &str = "Confidential work (details redacted)";
+
+/// How redacted entries are treated when content leaves the terminal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RedactionMode {
+    /// Drop the entry entirely
+    Exclude,
+    /// Keep the entry but replace its content and tags with a generic placeholder
+    Summarize,
+}
+
+impl RedactionMode {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "exclude" => Some(RedactionMode::Exclude),
+            "summarize" | "summarise" => Some(RedactionMode::Summarize),
+            _ => None,
+        }
+    }
+}
+
+/// Client-side policy for keeping sensitive entries out of recaps, digests and
+/// any other output that is written somewhere other than the user's terminal.
+#[derive(Debug, Clone)]
+pub struct RedactionPolicy {
+    tags: Vec<String>,
+    mode: RedactionMode,
+}
+
+impl RedactionPolicy {
+    pub fn new(tags: Vec<String>, mode: RedactionMode) -> Self {
+        let tags = tags
+            .into_iter()
+            .map(|t| t.trim().to_lowercase())
+            .filter(|t| !t.is_empty())
+            .collect();
+        Self { tags, mode }
+    }
+
+    /// Returns true when the entry carries any of the redacted tags.
+    pub fn is_redacted(&self, entry: &Value) -> bool {
+        entry
+            .get("tags")
+            .and_then(Value::as_array)
+            .map(|tags| {
+                tags.iter()
+                    .filter_map(Value::as_str)
+                    .any(|t| self.tags.contains(&t.to_lowercase()))
+            })
+            .unwrap_or(false)
+    }
+
+    /// Applies the policy to a list of entries.
+    pub fn apply(&self, entries: Vec<Value>) -> Vec<Value> {
+        entries
+            .into_iter()
+            .filter_map(|entry| {
+                if !self.is_redacted(&entry) {
+                    return Some(entry);
+                }
+                match self.mode {
+                    RedactionMode::Exclude => None,
+                    RedactionMode::Summarize => Some(summarize(entry)),
+                }
+            })
+            .collect()
+    }
+
+    /// Merges the redacted tags into a user-supplied exclusion list, for
+    /// server-side operations (like recaps) that never hand entries back to us.
+    pub fn merge_exclude_tags(&self, exclude_tags: Option<Vec<String>>) -> Option<Vec<String>> {
+        let mut merged = exclude_tags.unwrap_or_default();
+        for tag in &self.tags {
+            if !merged.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+                merged.push(tag.clone());
+            }
+        }
+
+        if merged.is_empty() {
+            None
+        } else {
+            Some(merged)
+        }
+    }
+}
+
+fn summarize(mut entry: Value) -> Value {
+    if let Some(obj) = entry.as_object_mut() {
+        obj.insert("content".to_string(), json!(REDACTED_CONTENT));
+        obj.insert("tags".to_string(), json!([]));
+    }
+    entry
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(mode: RedactionMode) -> RedactionPolicy {
+        RedactionPolicy::new(vec!["Confidential".into(), " ".into()], mode)
+    }
+
+    #[test]
+    fn test_is_redacted_case_insensitive() {
+        let policy = policy(RedactionMode::Exclude);
+        assert_eq!(policy.tags, ["confidential"]);
+        assert!(policy.is_redacted(&json!({ "tags": ["CONFIDENTIAL"] })));
+        assert!(!policy.is_redacted(&json!({ "tags": ["public"] })));
+        assert!(!policy.is_redacted(&json!({ "content": "no tags" })));
+    }
+
+    #[test]
+    fn test_apply_exclude() {
+        let entries = vec![
+            json!({ "id": "1", "tags": ["confidential"] }),
+            json!({ "id": "2", "tags": ["rust"] }),
+        ];
+        let result = policy(RedactionMode::Exclude).apply(entries);
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0]["id"], "2");
+    }
+
+    #[test]
+    fn test_apply_summarize() {
+        let entries = vec![json!({
+            "id": "1",
+            "content": "Acquisition talks with Initech",
+            "tags": ["confidential", "legal"]
+        })];
+        let result = policy(RedactionMode::Summarize).apply(entries);
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0]["content"], REDACTED_CONTENT);
+        assert_eq!(result[0]["tags"], json!([]));
+    }
+
+    #[test]
+    fn test_merge_exclude_tags() {
+        let policy = policy(RedactionMode::Exclude);
+        assert_eq!(
+            policy.merge_exclude_tags(Some(vec!["meeting".into()])),
+            Some(vec!["meeting".to_string(), "confidential".to_string()])
+        );
+        assert_eq!(
+            policy.merge_exclude_tags(Some(vec!["CONFIDENTIAL".into()])),
+            Some(vec!["CONFIDENTIAL".to_string()])
+        );
+
+        let empty = RedactionPolicy::new(vec![], RedactionMode::Exclude);
+        assert_eq!(empty.merge_exclude_tags(None), None);
+    }
+
+    #[test]
+    fn test_mode_parse() {
+        assert_eq!(
+            RedactionMode::parse("exclude"),
+            Some(RedactionMode::Exclude)
+        );
+        assert_eq!(
+            RedactionMode::parse("Summarize"),
+            Some(RedactionMode::Summarize)
+        );
+        assert_eq!(RedactionMode::parse("hide"), None);
+    }
+}
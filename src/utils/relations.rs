@@ -0,0 +1,90 @@
+use crate::api::models::WorklogEntry;
+
+/// How one entry relates to another. Relations are stored on the entry that
+/// points at the other one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RelationKind {
+    /// Continues the work of an earlier entry, forming a thread
+    FollowsUp,
+    /// Loosely connected to another entry
+    RelatesTo,
+}
+
+impl RelationKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RelationKind::FollowsUp => "follows_up",
+            RelationKind::RelatesTo => "relates_to",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "follows_up" => Some(RelationKind::FollowsUp),
+            "relates_to" => Some(RelationKind::RelatesTo),
+            _ => None,
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            RelationKind::FollowsUp => "Follows",
+            RelationKind::RelatesTo => "Relates to",
+        }
+    }
+}
+
+/// IDs of the entries an entry points at with the given kind of relation.
+pub fn related_ids(entry: &WorklogEntry, kind: RelationKind) -> Vec<String> {
+    entry
+        .relations
+        .iter()
+        .filter(|r| RelationKind::parse(&r.relation) == Some(kind))
+        .map(|r| r.related_entry_id.clone())
+        .collect()
+}
+
+/// One line per kind of relation, e.g. `Follows: 1a2b3c4d`, for listings.
+pub fn describe(entry: &WorklogEntry) -> Vec<String> {
+    [RelationKind::FollowsUp, RelationKind::RelatesTo]
+        .into_iter()
+        .filter_map(|kind| {
+            let ids = related_ids(entry, kind);
+            (!ids.is_empty()).then(|| {
+                let short: Vec<&str> = ids.iter().map(|id| id.get(..8).unwrap_or(id)).collect();
+                format!("{}: {}", kind.label(), short.join(", "))
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_related_ids_and_describe() {
+        let entry: WorklogEntry = serde_json::from_value(json!({
+            "id": "e1",
+            "relations": [
+                { "relation": "follows_up", "related_entry_id": "1a2b3c4d-0000" },
+                { "relation": "relates_to", "related_entry_id": "5e6f7a8b-0000" },
+                { "relation": "relates_to", "related_entry_id": "9c0d" },
+                { "relation": "blocks", "related_entry_id": "ffff" }
+            ]
+        }))
+        .unwrap();
+
+        assert_eq!(
+            related_ids(&entry, RelationKind::FollowsUp),
+            vec!["1a2b3c4d-0000"]
+        );
+        assert_eq!(
+            describe(&entry),
+            vec!["Follows: 1a2b3c4d", "Relates to: 5e6f7a8b, 9c0d"]
+        );
+        let bare: WorklogEntry = serde_json::from_value(json!({ "id": "e2" })).unwrap();
+        assert!(describe(&bare).is_empty());
+    }
+}
@@ -47,6 +47,7 @@ const WAITING_PHRASES: &[&str] = &[
 pub struct Spinner {
     start_time: Instant,
     current_phrase: String,
+    stderr: bool,
 }
 
 impl Spinner {
@@ -60,9 +61,16 @@ impl Spinner {
         Self {
             start_time: Instant::now(),
             current_phrase: phrase,
+            stderr: false,
         }
     }
 
+    /// Draws on stderr instead, leaving stdout to the command's output.
+    pub fn on_stderr(mut self) -> Self {
+        self.stderr = true;
+        self
+    }
+
     pub async fn spin_with_callback<F, Fut, T>(&mut self, callback: F) -> T
     where
         F: Fn() -> Fut + Send + Sync,
@@ -104,13 +112,21 @@ impl Spinner {
             seconds
         );
 
-        print!("{display}");
-        io::stdout().flush().unwrap();
+        self.write(&display);
     }
 
     fn clear_line(&self) {
-        print!("\r{}\r", " ".repeat(80));
-        io::stdout().flush().unwrap();
+        self.write(&format!("\r{}\r", " ".repeat(80)));
+    }
+
+    fn write(&self, text: &str) {
+        if self.stderr {
+            eprint!("{text}");
+            io::stderr().flush().unwrap();
+        } else {
+            print!("{text}");
+            io::stdout().flush().unwrap();
+        }
     }
 }
 
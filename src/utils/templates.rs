@@ -0,0 +1,267 @@
+use crate::commands::capture::get_recent_commits;
+use crate::errors::AppError;
+use crate::utils::branch_routes;
+use crate::utils::editor::DEFAULT_TEMPLATE;
+use chrono::{DateTime, Local};
+use git2::Repository;
+use regex::{Captures, Regex};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Template used when none is named, if the user wrote one.
+const DEFAULT_NAME: &str = "default";
+
+/// How many commits `{{last_commits}}` lists.
+const LAST_COMMITS: u32 = 5;
+
+/// Entry templates for `acc log --edit`: Markdown files in the templates
+/// directory (`~/.accomplish/templates` unless `templates_dir` is set), named
+/// by their file name without `.md`. A `default.md` replaces the built-in
+/// template, which is otherwise used.
+pub struct Templates {
+    dir: PathBuf,
+}
+
+impl Templates {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// The names of the templates in the directory, sorted.
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(&self.dir)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "md"))
+            .filter_map(|path| Some(path.file_stem()?.to_string_lossy().into_owned()))
+            .collect();
+        names.sort();
+        names
+    }
+
+    /// The text to start the editor with: the template called `name`, or
+    /// without one `default.md`, falling back to the built-in template.
+    pub fn load(&self, name: Option<&str>) -> Result<String, AppError> {
+        let path = match name {
+            Some(name) => {
+                let name = name.strip_suffix(".md").unwrap_or(name);
+                if name.is_empty() || name.contains(['/', '\\']) || name.starts_with('.') {
+                    return Err(AppError::Other(format!("Invalid template name '{name}'")));
+                }
+                let path = self.path(name);
+                if !path.is_file() {
+                    return Err(AppError::Other(self.not_found(name)));
+                }
+                path
+            }
+            None => {
+                let path = self.path(DEFAULT_NAME);
+                if !path.is_file() {
+                    return Ok(DEFAULT_TEMPLATE.to_string());
+                }
+                path
+            }
+        };
+
+        let mut content = fs::read_to_string(&path).map_err(|e| {
+            AppError::Other(format!("Failed to read template '{}': {e}", path.display()))
+        })?;
+        // The entry is written below the template
+        if !content.ends_with('\n') {
+            content.push('\n');
+        }
+        Ok(content)
+    }
+
+    fn path(&self, name: &str) -> PathBuf {
+        self.dir.join(format!("{name}.md"))
+    }
+
+    fn not_found(&self, name: &str) -> String {
+        let names = self.names();
+        if names.is_empty() {
+            format!(
+                "No template '{name}'. Add one as {}",
+                self.path(name).display()
+            )
+        } else {
+            format!(
+                "No template '{name}' in {}. Available: {}",
+                self.dir.display(),
+                names.join(", ")
+            )
+        }
+    }
+}
+
+/// What a template's placeholders are replaced with.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TemplateContext {
+    /// `{{date}}`, as `YYYY-MM-DD`
+    pub date: Option<DateTime<Local>>,
+    /// `{{branch}}`, the branch checked out
+    pub branch: Option<String>,
+    /// `{{project}}`, the entry's project identifier
+    pub project: Option<String>,
+    /// `{{last_commits}}`, one `- <short sha> <summary>` line per commit
+    pub last_commits: Vec<String>,
+}
+
+impl TemplateContext {
+    /// The context of an entry written in `dir`. History is only read when
+    /// `template` asks for `{{last_commits}}`.
+    pub fn gather(
+        template: &str,
+        dir: &Path,
+        project: Option<&str>,
+        date: DateTime<Local>,
+    ) -> Self {
+        let wants_commits = placeholder()
+            .captures_iter(template)
+            .any(|c| &c[1] == "last_commits");
+        let last_commits = if wants_commits {
+            Repository::discover(dir)
+                .ok()
+                .and_then(|repo| repo.workdir().map(Path::to_path_buf))
+                .and_then(|root| get_recent_commits(&root, LAST_COMMITS, &[]).ok())
+                .unwrap_or_default()
+                .iter()
+                .map(|commit| format!("- {} {}", commit.short_sha, commit.summary))
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        Self {
+            date: Some(date),
+            branch: branch_routes::current_branch(dir),
+            project: project.map(str::to_string),
+            last_commits,
+        }
+    }
+}
+
+/// `{{name}}`, with optional spaces inside the braces.
+fn placeholder() -> &'static Regex {
+    static PLACEHOLDER: OnceLock<Regex> = OnceLock::new();
+    PLACEHOLDER.get_or_init(|| Regex::new(r"\{\{\s*([a-z_]+)\s*\}\}").unwrap())
+}
+
+/// Replaces the placeholders in `template` with what `context` knows, or with
+/// nothing when it doesn't (outside a git repository, without a project).
+/// Unknown placeholders are left as they are.
+pub fn expand(template: &str, context: &TemplateContext) -> String {
+    placeholder()
+        .replace_all(template, |caps: &Captures| match &caps[1] {
+            "date" => context
+                .date
+                .map(|d| d.format("%Y-%m-%d").to_string())
+                .unwrap_or_default(),
+            "branch" => context.branch.clone().unwrap_or_default(),
+            "project" => context.project.clone().unwrap_or_default(),
+            "last_commits" => context.last_commits.join("\n"),
+            _ => caps[0].to_string(),
+        })
+        .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_load_falls_back_to_the_built_in_template() {
+        let temp_dir = TempDir::new().unwrap();
+        let templates = Templates::new(temp_dir.path().join("templates"));
+        assert!(templates.names().is_empty());
+        assert_eq!(templates.load(None).unwrap(), DEFAULT_TEMPLATE);
+
+        let err = templates.load(Some("standup")).unwrap_err().to_string();
+        assert!(err.contains("No template 'standup'. Add one as"), "{err}");
+    }
+
+    #[test]
+    fn test_expand() {
+        let context = TemplateContext {
+            date: Some(Local.with_ymd_and_hms(2025, 7, 9, 14, 30, 0).unwrap()),
+            branch: Some("fix/checkout".to_string()),
+            project: Some("web".to_string()),
+            last_commits: vec![
+                "- abc1234 Fix rounding".to_string(),
+                "- def5678 Add test".to_string(),
+            ],
+        };
+        assert_eq!(
+            expand(
+                "## {{date}} on {{ branch }} ({{project}})\n{{last_commits}}\n{{ticket}}",
+                &context
+            ),
+            "## 2025-07-09 on fix/checkout (web)\n- abc1234 Fix rounding\n- def5678 Add test\n{{ticket}}"
+        );
+        assert_eq!(
+            expand("{{branch}}|{{last_commits}}", &TemplateContext::default()),
+            "|"
+        );
+    }
+
+    #[test]
+    fn test_gather_reads_the_repository() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = Repository::init(temp_dir.path()).unwrap();
+        let signature = git2::Signature::now("Me", "me@example.com").unwrap();
+        let tree = repo
+            .find_tree(repo.index().unwrap().write_tree().unwrap())
+            .unwrap();
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            "Initial commit",
+            &tree,
+            &[],
+        )
+        .unwrap();
+        let subdir = temp_dir.path().join("src");
+        fs::create_dir(&subdir).unwrap();
+
+        let now = Local::now();
+        let context = TemplateContext::gather("{{last_commits}}", &subdir, Some("web"), now);
+        assert_eq!(context.last_commits.len(), 1);
+        assert!(context.last_commits[0].ends_with(" Initial commit"));
+        assert!(context.branch.is_some());
+        assert_eq!(context.project.as_deref(), Some("web"));
+
+        // History is only read when asked for
+        let context = TemplateContext::gather("{{branch}}", &subdir, None, now);
+        assert!(context.last_commits.is_empty());
+    }
+
+    #[test]
+    fn test_load_named_and_default_templates() {
+        let temp_dir = TempDir::new().unwrap();
+        let templates = Templates::new(temp_dir.path().to_path_buf());
+        fs::write(temp_dir.path().join("standup.md"), "# Yesterday\n\n# Today").unwrap();
+        fs::write(temp_dir.path().join("default.md"), "# Write it down\n").unwrap();
+        fs::write(temp_dir.path().join("notes.txt"), "not a template").unwrap();
+
+        assert_eq!(templates.names(), vec!["default", "standup"]);
+        assert_eq!(templates.load(None).unwrap(), "# Write it down\n");
+        assert_eq!(
+            templates.load(Some("standup")).unwrap(),
+            "# Yesterday\n\n# Today\n"
+        );
+        assert_eq!(
+            templates.load(Some("standup.md")).unwrap(),
+            "# Yesterday\n\n# Today\n"
+        );
+
+        let err = templates.load(Some("retro")).unwrap_err().to_string();
+        assert!(err.ends_with("Available: default, standup"), "{err}");
+        assert!(templates.load(Some("../config")).is_err());
+    }
+}
@@ -0,0 +1,218 @@
+use clap::ValueEnum;
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Prefix of the reserved tags that record an entry's visibility, e.g.
+/// `visibility:private`. The server has no notion of visibility, so it's kept
+/// in tags and enforced client-side.
+pub const TAG_PREFIX: &str = "visibility:";
+
+/// Who an entry may be shown to, from narrowest to widest.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum Visibility {
+    /// Only you
+    Private,
+    /// You, your manager and your team
+    Team,
+    /// Anyone, including clients
+    Public,
+}
+
+impl Visibility {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Visibility::Private => "private",
+            Visibility::Team => "team",
+            Visibility::Public => "public",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "private" => Some(Visibility::Private),
+            "team" => Some(Visibility::Team),
+            "public" => Some(Visibility::Public),
+            _ => None,
+        }
+    }
+
+    pub fn tag(&self) -> String {
+        format!("{TAG_PREFIX}{}", self.as_str())
+    }
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            Visibility::Private => "🔒",
+            Visibility::Team => "👥",
+            Visibility::Public => "🌐",
+        }
+    }
+
+    /// The narrowest visibility a recap audience may see: `self` sees
+    /// everything, managers and teams see team entries, clients only public ones.
+    pub fn minimum_for_audience(audience: &str) -> Self {
+        match audience {
+            "self" => Visibility::Private,
+            "client" => Visibility::Public,
+            _ => Visibility::Team,
+        }
+    }
+
+    /// The visibility recorded in a list of tags, if any.
+    pub fn from_tags<'a>(tags: impl IntoIterator<Item = &'a str>) -> Option<Self> {
+        tags.into_iter()
+            .filter_map(|t| t.strip_prefix(TAG_PREFIX))
+            .find_map(Self::parse)
+    }
+
+    /// The visibility recorded in an entry's tags, if any.
+    pub fn of_entry(entry: &Value) -> Option<Self> {
+        let tags = entry.get("tags").and_then(Value::as_array)?;
+        Self::from_tags(tags.iter().filter_map(Value::as_str))
+    }
+}
+
+/// Whether a tag is one of the reserved visibility tags, which listings hide in
+/// favour of an icon.
+pub fn is_visibility_tag(tag: &str) -> bool {
+    tag.starts_with(TAG_PREFIX)
+}
+
+/// Configured visibility defaults: `default_visibility` and per-project
+/// overrides from `[<profile>.project_visibility]`.
+#[derive(Debug, Clone, Default)]
+pub struct VisibilityPolicy {
+    default: Option<Visibility>,
+    per_project: HashMap<String, Visibility>,
+}
+
+impl VisibilityPolicy {
+    pub fn new(default: Option<Visibility>, per_project: HashMap<String, Visibility>) -> Self {
+        let per_project = per_project
+            .into_iter()
+            .map(|(project, v)| (project.to_lowercase(), v))
+            .collect();
+        Self {
+            default,
+            per_project,
+        }
+    }
+
+    /// Visibility assumed for entries without a visibility tag.
+    pub fn unmarked(&self) -> Visibility {
+        self.default.unwrap_or(Visibility::Team)
+    }
+
+    /// Adds the visibility tag to a new entry's tags: the requested one, else
+    /// the project's or configured default. Entries already carrying a
+    /// visibility tag, and entries with nothing configured, are left as is.
+    pub fn tag_new_entry(
+        &self,
+        mut tags: Vec<String>,
+        requested: Option<Visibility>,
+        project_identifier: Option<&str>,
+    ) -> Vec<String> {
+        if Visibility::from_tags(tags.iter().map(String::as_str)).is_some() {
+            return tags;
+        }
+        let project_default = project_identifier
+            .and_then(|p| self.per_project.get(&p.to_lowercase()))
+            .copied();
+        if let Some(visibility) = requested.or(project_default).or(self.default) {
+            tags.push(visibility.tag());
+        }
+        tags
+    }
+
+    /// Whether an entry may be shown to an audience needing at least `minimum`.
+    pub fn allows(&self, entry: &Value, minimum: Visibility) -> bool {
+        Visibility::of_entry(entry).unwrap_or_else(|| self.unmarked()) >= minimum
+    }
+
+    /// Drops entries narrower than `minimum`.
+    pub fn filter(&self, entries: Vec<Value>, minimum: Visibility) -> Vec<Value> {
+        entries
+            .into_iter()
+            .filter(|entry| self.allows(entry, minimum))
+            .collect()
+    }
+
+    /// Tags to exclude for server-side operations (like recaps) that must not
+    /// include entries narrower than `minimum`. Unmarked entries can't be
+    /// excluded this way, which is why configuring a default tags every entry.
+    pub fn exclude_tags(&self, minimum: Visibility) -> Vec<String> {
+        [Visibility::Private, Visibility::Team]
+            .into_iter()
+            .filter(|v| *v < minimum)
+            .map(|v| v.tag())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn policy() -> VisibilityPolicy {
+        VisibilityPolicy::new(
+            Some(Visibility::Team),
+            HashMap::from([("WEB".to_string(), Visibility::Public)]),
+        )
+    }
+
+    #[test]
+    fn test_tag_new_entry() {
+        let policy = policy();
+        assert_eq!(
+            policy.tag_new_entry(vec!["rust".into()], Some(Visibility::Private), Some("web")),
+            vec!["rust", "visibility:private"]
+        );
+        assert_eq!(
+            policy.tag_new_entry(vec![], None, Some("web")),
+            vec!["visibility:public"]
+        );
+        assert_eq!(
+            policy.tag_new_entry(vec![], None, Some("api")),
+            vec!["visibility:team"]
+        );
+        // An explicit tag from the editor wins
+        assert_eq!(
+            policy.tag_new_entry(vec!["visibility:private".into()], None, Some("web")),
+            vec!["visibility:private"]
+        );
+        // Nothing configured, nothing requested: no tag
+        assert!(VisibilityPolicy::default()
+            .tag_new_entry(vec![], None, None)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_filter_by_audience() {
+        let entries = vec![
+            json!({ "id": "1", "tags": ["visibility:private"] }),
+            json!({ "id": "2", "tags": ["rust"] }),
+            json!({ "id": "3", "tags": ["visibility:public"] }),
+        ];
+        let ids = |minimum: Visibility| -> Vec<String> {
+            policy()
+                .filter(entries.clone(), minimum)
+                .iter()
+                .map(|e| e["id"].as_str().unwrap().to_string())
+                .collect()
+        };
+
+        assert_eq!(
+            ids(Visibility::minimum_for_audience("self")),
+            ["1", "2", "3"]
+        );
+        assert_eq!(ids(Visibility::minimum_for_audience("manager")), ["2", "3"]);
+        assert_eq!(ids(Visibility::minimum_for_audience("client")), ["3"]);
+
+        assert_eq!(
+            policy().exclude_tags(Visibility::Public),
+            vec!["visibility:private", "visibility:team"]
+        );
+        assert!(policy().exclude_tags(Visibility::Private).is_empty());
+    }
+}